@@ -7,6 +7,8 @@ fn main() {
     rivet_core::run(args)
   {
     eprintln!("error: {err:#}");
-    std::process::exit(1);
+    std::process::exit(
+      rivet_core::exit::exit_code(&err)
+    );
   }
 }
@@ -0,0 +1,523 @@
+//! End-to-end runs of the `task` binary
+//! for behaviour that only shows up
+//! through argv, stdin and the exit
+//! code.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{
+  Command,
+  Output,
+  Stdio
+};
+
+use serde_json::Value;
+use tempfile::TempDir;
+
+/// A throwaway data directory and
+/// taskrc for one test.
+struct Cli {
+  _temp:  TempDir,
+  taskrc: PathBuf,
+  data:   PathBuf
+}
+
+impl Cli {
+  fn new() -> Self {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let taskrc =
+      temp.path().join("taskrc");
+    std::fs::write(
+      &taskrc,
+      [
+        "confirmation=off\n",
+        "verbose=nothing\n",
+        "color=off\n"
+      ]
+      .concat()
+    )
+    .expect("write taskrc");
+    let data = temp.path().join("data");
+    Self {
+      _temp: temp,
+      taskrc,
+      data
+    }
+  }
+
+  fn run_with_stdin(
+    &self,
+    args: &[&str],
+    stdin: Option<&str>
+  ) -> Output {
+    let mut child = Command::new(env!(
+      "CARGO_BIN_EXE_task"
+    ))
+    .arg("--data")
+    .arg(&self.data)
+    .args(args)
+    .env("TASKRC", &self.taskrc)
+    .stdin(
+      if stdin.is_some() {
+        Stdio::piped()
+      } else {
+        Stdio::null()
+      }
+    )
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .expect("spawn task");
+    if let Some(input) = stdin {
+      child
+        .stdin
+        .take()
+        .expect("stdin pipe")
+        .write_all(input.as_bytes())
+        .expect("write stdin");
+    }
+    child
+      .wait_with_output()
+      .expect("wait for task")
+  }
+
+  fn run(
+    &self,
+    args: &[&str]
+  ) -> Output {
+    self.run_with_stdin(args, None)
+  }
+
+  /// Runs a command that must succeed.
+  fn ok(
+    &self,
+    args: &[&str]
+  ) -> String {
+    let output = self.run(args);
+    assert!(
+      output.status.success(),
+      "task {args:?} failed: {}",
+      String::from_utf8_lossy(
+        &output.stderr
+      )
+    );
+    String::from_utf8_lossy(
+      &output.stdout
+    )
+    .into_owned()
+  }
+
+  fn export(&self) -> Vec<Value> {
+    serde_json::from_str(
+      &self.ok(&["export"])
+    )
+    .expect("export json")
+  }
+}
+
+fn has_tag(
+  task: &Value,
+  tag: &str
+) -> bool {
+  task["tags"].as_array().is_some_and(
+    |tags| {
+      tags.iter().any(|t| t == tag)
+    }
+  )
+}
+
+#[test]
+fn uncomplete_reopens_a_completed_task()
+{
+  let cli = Cli::new();
+  cli.ok(&["add", "water", "plants"]);
+  cli.ok(&["1", "done"]);
+  let uuid = cli.export()[0]["uuid"]
+    .as_str()
+    .expect("uuid")
+    .to_string();
+
+  let stdout =
+    cli.ok(&[&uuid, "uncomplete"]);
+  assert!(
+    stdout.contains("Reopened 1 task")
+  );
+  let tasks = cli.export();
+  assert_eq!(tasks.len(), 1);
+  assert_eq!(
+    tasks[0]["status"],
+    "pending"
+  );
+  assert!(tasks[0]["end"].is_null());
+  assert_eq!(tasks[0]["id"], 1);
+}
+
+#[test]
+fn uncomplete_drops_the_instance_a_recurring_task_spawned()
+ {
+  let cli = Cli::new();
+  cli.ok(&[
+    "add",
+    "water",
+    "due:today",
+    "recur:daily",
+    "count:3"
+  ]);
+  cli.ok(&["1", "done"]);
+  let tasks = cli.export();
+  assert_eq!(tasks.len(), 2);
+  let done = tasks
+    .iter()
+    .find(|task| {
+      task["status"] == "completed"
+    })
+    .expect("completed instance");
+  assert!(has_tag(
+    done,
+    "recur_left:3"
+  ));
+  let uuid = done["uuid"]
+    .as_str()
+    .expect("uuid")
+    .to_string();
+
+  cli.ok(&[&uuid, "uncomplete"]);
+  let tasks = cli.export();
+  assert_eq!(
+    tasks.len(),
+    1,
+    "spawned instance should be gone: \
+     {tasks:?}"
+  );
+  assert_eq!(tasks[0]["uuid"], uuid);
+  assert_eq!(
+    tasks[0]["status"],
+    "pending"
+  );
+  assert!(has_tag(
+    &tasks[0],
+    "recur_left:3"
+  ));
+
+  // Completing it again spawns exactly
+  // one successor.
+  cli.ok(&["1", "done"]);
+  let open: Vec<Value> = cli
+    .export()
+    .into_iter()
+    .filter(|task| {
+      task["status"] == "pending"
+    })
+    .collect();
+  assert_eq!(open.len(), 1);
+  assert!(has_tag(
+    &open[0],
+    "recur_left:2"
+  ));
+}
+
+#[test]
+fn uncomplete_fails_when_nothing_is_completed()
+ {
+  let cli = Cli::new();
+  cli.ok(&["add", "still", "open"]);
+
+  let output =
+    cli.run(&["1", "uncomplete"]);
+  assert!(!output.status.success());
+  assert!(
+    String::from_utf8_lossy(
+      &output.stderr
+    )
+    .contains("No matches")
+  );
+  assert_eq!(
+    cli.export()[0]["status"],
+    "pending"
+  );
+}
+
+#[test]
+fn shell_runs_a_script_until_exit_and_keeps_going_after_errors()
+ {
+  let cli = Cli::new();
+  let output = cli.run_with_stdin(
+    &["shell"],
+    Some(
+      "add \"buy milk and eggs\" \
+       +home\n# a comment\n\nadd \
+       \"unbalanced\n99 done\ntask \
+       add second\nexit\nadd never\n"
+    )
+  );
+  assert!(output.status.success());
+  let stderr = String::from_utf8_lossy(
+    &output.stderr
+  );
+  assert!(
+    stderr.contains("unterminated")
+  );
+  assert!(
+    stderr.contains("No matches")
+  );
+
+  let tasks = cli.export();
+  let descriptions: Vec<&str> = tasks
+    .iter()
+    .filter_map(|task| {
+      task["description"].as_str()
+    })
+    .collect();
+  assert_eq!(descriptions, [
+    "buy milk and eggs",
+    "second"
+  ]);
+  assert!(has_tag(&tasks[0], "home"));
+}
+
+#[test]
+fn shell_stops_at_end_of_input() {
+  let cli = Cli::new();
+  let output = cli.run_with_stdin(
+    &["shell"],
+    Some(
+      "add 'last line' without newline"
+    )
+  );
+  assert!(output.status.success());
+  let tasks = cli.export();
+  assert_eq!(tasks.len(), 1);
+  assert_eq!(
+    tasks[0]["description"],
+    "last line without newline"
+  );
+}
+
+#[test]
+fn original_abbreviations_resolve_to_the_same_commands()
+ {
+  let cli = Cli::new();
+  cli.ok(&["add", "water", "plants"]);
+
+  cli.ok(&["1", "sta"]);
+  assert!(
+    cli.export()[0]["start"]
+      .is_string()
+  );
+
+  let info = cli.ok(&["1", "in"]);
+  assert!(
+    info.contains("water plants"),
+    "{info}"
+  );
+
+  let exported: Vec<Value> =
+    serde_json::from_str(
+      &cli.ok(&["ex"])
+    )
+    .expect("export json");
+  assert_eq!(exported.len(), 1);
+
+  assert!(
+    cli
+      .ok(&["h"])
+      .contains("Implemented commands")
+  );
+
+  cli.ok(&["u"]);
+  assert!(
+    cli.export()[0]["start"].is_null()
+  );
+}
+
+#[test]
+fn exact_report_names_beat_command_prefixes()
+ {
+  let cli = Cli::new();
+  cli.ok(&["add", "water", "plants"]);
+
+  let report = cli.ok(&[
+    "rc.report.b.columns=id,\
+     description",
+    "b"
+  ]);
+  assert!(
+    report.contains("water plants"),
+    "{report}"
+  );
+
+  // Without the report, one letter is
+  // not enough to reach `backup`.
+  cli.run(&["b"]);
+  assert!(
+    !cli.data.join("backups").exists()
+  );
+}
+
+#[test]
+fn inbox_captures_and_takes_a_configured_verb()
+ {
+  let cli = Cli::new();
+  cli.ok(&["inbox", "call", "mum"]);
+  cli.ok(&[
+    "rc.inbox.command=in",
+    "in",
+    "book",
+    "dentist"
+  ]);
+
+  let tasks = cli.export();
+  assert_eq!(tasks.len(), 2);
+  assert!(tasks.iter().all(|task| {
+    has_tag(task, "inbox")
+  }));
+  assert_eq!(
+    tasks[1]["description"],
+    "book dentist"
+  );
+}
+
+#[test]
+fn inbox_captures_are_recorded_in_history()
+ {
+  let cli = Cli::new();
+  cli.ok(&["inbox", "call", "mum"]);
+  cli.ok(&[
+    "rc.inbox.command=in",
+    "in",
+    "book",
+    "dentist"
+  ]);
+
+  for id in ["1", "2"] {
+    let history =
+      cli.ok(&[id, "history"]);
+    assert!(
+      history.contains("created"),
+      "{history}"
+    );
+    assert!(
+      !history.contains(
+        "no recorded changes"
+      ),
+      "{history}"
+    );
+  }
+}
+
+#[cfg(unix)]
+#[test]
+fn dry_run_skips_hook_scripts() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let cli = Cli::new();
+  let marker =
+    cli.data.join("hook-ran");
+  let hooks = cli.data.join("hooks");
+  std::fs::create_dir_all(&hooks)
+    .expect("hooks dir");
+  let script = hooks.join("on-add.sh");
+  std::fs::write(
+    &script,
+    format!(
+      "#!/bin/sh\nread task\ntouch \
+       '{}'\necho \"$task\"\n",
+      marker.display()
+    )
+  )
+  .expect("write hook");
+  std::fs::set_permissions(
+    &script,
+    std::fs::Permissions::from_mode(
+      0o755
+    )
+  )
+  .expect("chmod hook");
+
+  let stdout = cli.ok(&[
+    "--dry-run",
+    "add",
+    "call",
+    "the",
+    "bank"
+  ]);
+  assert!(
+    stdout.contains(
+      "hook scripts are not run"
+    ),
+    "{stdout}"
+  );
+  assert!(!marker.exists());
+
+  cli.ok(&["add", "real", "task"]);
+  assert!(marker.exists());
+}
+
+#[test]
+fn stdin_selectors_that_match_nothing_are_reported_without_aborting()
+ {
+  let cli = Cli::new();
+  cli.ok(&["add", "first"]);
+  cli.ok(&["add", "second"]);
+
+  let output = cli.run_with_stdin(
+    &["-", "modify", "+piped"],
+    Some("2 98\nabcd1\n")
+  );
+  assert_eq!(
+    output.status.code(),
+    Some(3)
+  );
+  let stderr = String::from_utf8_lossy(
+    &output.stderr
+  );
+  assert!(stderr.contains("'98'"));
+  assert!(stderr.contains("'abcd1'"));
+  let tasks = cli.export();
+  assert!(!has_tag(&tasks[0], "piped"));
+  assert!(has_tag(&tasks[1], "piped"));
+
+  let output = cli.run_with_stdin(
+    &["-", "modify", "+piped"],
+    Some("1,2\n")
+  );
+  assert!(output.status.success());
+  assert!(cli.export().iter().all(
+    |task| has_tag(task, "piped")
+  ));
+}
+
+#[test]
+fn width_applies_before_or_after_the_report_command()
+ {
+  let cli = Cli::new();
+  cli.ok(&[
+    "add",
+    "a description long enough to be \
+     cut down to size"
+  ]);
+
+  let before =
+    cli.ok(&["--width", "30", "list"]);
+  assert!(
+    before.contains('…'),
+    "{before}"
+  );
+  assert!(!before.contains("to size"));
+  assert_eq!(
+    cli.ok(&["list", "--width", "30"]),
+    before
+  );
+  assert_eq!(
+    cli.ok(&["list", "--width=30"]),
+    before
+  );
+
+  let output = cli
+    .run(&["list", "--width", "wide"]);
+  assert_eq!(
+    output.status.code(),
+    Some(2)
+  );
+}
@@ -0,0 +1,100 @@
+//! Compares cold (parse from disk) and
+//! cached reads of a 10k-task store.
+//!
+//! Run with `cargo bench -p rivet_core
+//! --bench datastore_cache`.
+
+use std::hint::black_box;
+use std::time::{
+  Duration,
+  Instant
+};
+
+use chrono::Utc;
+use rivet_core::datastore::DataStore;
+use rivet_core::task::Task;
+
+const TASKS: u64 = 10_000;
+const ROUNDS: u32 = 20;
+
+fn main() -> anyhow::Result<()> {
+  let temp = tempfile::tempdir()?;
+  let store =
+    DataStore::open(temp.path())?;
+  let now = Utc::now();
+
+  let tasks: Vec<Task> = (1..=TASKS)
+    .map(|id| {
+      let mut task = Task::new_pending(
+        format!("benchmark task {id}"),
+        now,
+        id
+      );
+      task.project = Some(format!(
+        "project-{}",
+        id % 25
+      ));
+      task.tags = vec![
+        format!("tag-{}", id % 40),
+        "bench".to_string(),
+      ];
+      task
+    })
+    .collect();
+  store.save_pending(&tasks)?;
+
+  let cold = time_rounds(|| {
+    store.invalidate_cache();
+    store
+      .load_pending()
+      .map(|rows| rows.len())
+  })?;
+  let cached = time_rounds(|| {
+    store
+      .load_pending()
+      .map(|rows| rows.len())
+  })?;
+  let indexed = time_rounds(|| {
+    store.pending_index().map(|index| {
+      index.with_tag("tag-7").count()
+    })
+  })?;
+
+  println!(
+    "{TASKS} tasks, {ROUNDS} rounds \
+     each"
+  );
+  println!(
+    "cold load_pending:   \
+     {cold:?}/read"
+  );
+  println!(
+    "cached load_pending: \
+     {cached:?}/read"
+  );
+  println!(
+    "indexed tag lookup:  \
+     {indexed:?}/read"
+  );
+  println!(
+    "cached speedup: {:.1}x",
+    cold.as_secs_f64()
+      / cached
+        .as_secs_f64()
+        .max(f64::EPSILON)
+  );
+  Ok(())
+}
+
+fn time_rounds(
+  mut read: impl FnMut() -> anyhow::Result<
+    usize
+  >
+) -> anyhow::Result<Duration> {
+  black_box(read()?);
+  let started = Instant::now();
+  for _ in 0..ROUNDS {
+    black_box(read()?);
+  }
+  Ok(started.elapsed() / ROUNDS)
+}
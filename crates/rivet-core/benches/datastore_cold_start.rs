@@ -0,0 +1,127 @@
+//! Times a cold start on a 50k-task
+//! store: opening the data directory
+//! and reading pending.data the way a
+//! fresh `task <id> info` process does,
+//! with and without the persisted
+//! `pending.index` sidecar.
+//!
+//! Run with `cargo bench -p rivet_core
+//! --bench datastore_cold_start`.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{
+  Duration,
+  Instant
+};
+
+use chrono::Utc;
+use rivet_core::datastore::DataStore;
+use rivet_core::index_file;
+use rivet_core::task::Task;
+
+const TASKS: u64 = 50_000;
+const ROUNDS: u32 = 10;
+
+fn main() -> anyhow::Result<()> {
+  let temp = tempfile::tempdir()?;
+  let now = Utc::now();
+
+  let tasks: Vec<Task> = (1..=TASKS)
+    .map(|id| {
+      let mut task = Task::new_pending(
+        format!("benchmark task {id}"),
+        now,
+        id
+      );
+      task.project = Some(format!(
+        "project-{}",
+        id % 25
+      ));
+      task.tags = vec![
+        format!("tag-{}", id % 40),
+        "bench".to_string(),
+      ];
+      task.due = Some(now);
+      task.extra.insert(
+        "estimate".to_string(),
+        serde_json::json!(id % 8)
+      );
+      task
+    })
+    .collect();
+  let mut store =
+    DataStore::open(temp.path())?;
+  store.set_persist_index(true);
+  store.save_pending(&tasks)?;
+  let sidecar = index_file::index_path(
+    &store.pending_path
+  );
+  drop(store);
+
+  let parsed = time_rounds(|| {
+    cold_start(temp.path(), false)
+  })?;
+  let persisted = time_rounds(|| {
+    cold_start(temp.path(), true)
+  })?;
+
+  println!(
+    "{TASKS} tasks, {ROUNDS} rounds \
+     each"
+  );
+  println!(
+    "pending.data: {} KiB, \
+     pending.index: {} KiB",
+    std::fs::metadata(
+      temp.path().join("pending.data")
+    )?
+    .len()
+      / 1024,
+    std::fs::metadata(&sidecar)?.len()
+      / 1024
+  );
+  println!(
+    "cold start, parse jsonl: \
+     {parsed:?}/start"
+  );
+  println!(
+    "cold start, sidecar:     \
+     {persisted:?}/start"
+  );
+  println!(
+    "sidecar speedup: {:.1}x",
+    parsed.as_secs_f64()
+      / persisted
+        .as_secs_f64()
+        .max(f64::EPSILON)
+  );
+  Ok(())
+}
+
+/// A new store per call, so nothing is
+/// served from the in-memory cache.
+fn cold_start(
+  data_dir: &Path,
+  persist_index: bool
+) -> anyhow::Result<usize> {
+  let mut store =
+    DataStore::open(data_dir)?;
+  store
+    .set_persist_index(persist_index);
+  let index = store.pending_index()?;
+  Ok(index.tasks().len())
+}
+
+fn time_rounds(
+  mut start: impl FnMut() -> anyhow::Result<
+    usize
+  >
+) -> anyhow::Result<Duration> {
+  black_box(start()?);
+  let started = Instant::now();
+  for _ in 0..ROUNDS {
+    black_box(start()?);
+  }
+  Ok(started.elapsed() / ROUNDS)
+}
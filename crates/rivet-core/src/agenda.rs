@@ -0,0 +1,185 @@
+//! `task agenda` and the
+//! `agenda.on_launch` banner: how many
+//! open tasks are overdue or due later
+//! today, bucketed by
+//! `datetime::due_bucket` so the count
+//! agrees with the GUI's due groups.
+
+use chrono::{
+  DateTime,
+  Utc,
+  Weekday
+};
+
+use crate::config::Config;
+use crate::datetime::{
+  DueBucket,
+  due_bucket
+};
+use crate::task::{
+  Status,
+  Task
+};
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub struct AgendaCounts {
+  pub overdue: usize,
+  /// Due later today; overdue tasks
+  /// are not counted again.
+  pub today:   usize
+}
+
+impl AgendaCounts {
+  /// Counts pending tasks only; waiting
+  /// tasks are hidden until their wait
+  /// date and are not on the agenda.
+  pub fn collect<'a>(
+    tasks: impl IntoIterator<
+      Item = &'a Task
+    >,
+    now: DateTime<Utc>,
+    week_start: Weekday
+  ) -> Self {
+    let mut counts = Self::default();
+    for task in tasks {
+      if task.status != Status::Pending
+      {
+        continue;
+      }
+      match due_bucket(
+        task.due, now, week_start
+      ) {
+        | DueBucket::Overdue => {
+          counts.overdue += 1;
+        }
+        | DueBucket::Today => {
+          counts.today += 1;
+        }
+        | _ => {}
+      }
+    }
+    counts
+  }
+
+  /// The one-line banner, e.g.
+  /// `Agenda: 3 due today, 1 overdue.`
+  pub fn banner(&self) -> String {
+    if self.overdue == 0
+      && self.today == 0
+    {
+      return "Agenda: nothing due \
+              today."
+        .to_string();
+    }
+    let mut parts = Vec::new();
+    if self.today > 0 {
+      parts.push(format!(
+        "{} due today",
+        self.today
+      ));
+    }
+    if self.overdue > 0 {
+      parts.push(format!(
+        "{} overdue",
+        self.overdue
+      ));
+    }
+    format!(
+      "Agenda: {}.",
+      parts.join(", ")
+    )
+  }
+}
+
+/// Whether `agenda.on_launch` asks for
+/// the banner before each command. Off
+/// by default.
+pub fn on_launch(cfg: &Config) -> bool {
+  cfg
+    .get_bool("agenda.on_launch")
+    .unwrap_or(false)
+}
+
+/// Commands that skip the launch
+/// banner: `agenda` prints it anyway,
+/// and the machine-facing ones
+/// (`rpc`, `_`-helpers used by
+/// completion scripts) must not get
+/// extra output.
+pub fn command_skips_banner(
+  command: &str
+) -> bool {
+  command == "agenda"
+    || command == "rpc"
+    || command.starts_with('_')
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{
+    Duration,
+    TimeZone
+  };
+
+  use super::*;
+
+  #[test]
+  fn counts_overdue_and_today_from_pending_tasks()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 4, 12, 0, 0
+      )
+      .unwrap();
+    let due = |hours: i64| {
+      let mut task = Task::new_pending(
+        "t".into(),
+        now,
+        1
+      );
+      task.due = Some(
+        now + Duration::hours(hours)
+      );
+      task
+    };
+    let mut waiting = due(-2);
+    waiting.status = Status::Waiting;
+    let tasks = vec![
+      due(-2),
+      due(-30),
+      due(1),
+      due(72),
+      waiting,
+      Task::new_pending(
+        "no due".into(),
+        now,
+        2
+      ),
+    ];
+
+    let counts = AgendaCounts::collect(
+      &tasks,
+      now,
+      Weekday::Mon
+    );
+    assert_eq!(counts, AgendaCounts {
+      overdue: 2,
+      today:   1
+    });
+    assert_eq!(
+      counts.banner(),
+      "Agenda: 1 due today, 2 overdue."
+    );
+    assert_eq!(
+      AgendaCounts::default().banner(),
+      "Agenda: nothing due today."
+    );
+  }
+}
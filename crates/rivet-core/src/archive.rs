@@ -0,0 +1,239 @@
+//! `auto_archive.days`: completed
+//! tasks that ended at least that many
+//! days ago are moved to the archived
+//! status, the same as `task archive`.
+//! Unlike purge nothing is deleted;
+//! archived tasks stay in
+//! completed.data and only show up when
+//! filtered by `status:archived`.
+
+use anyhow::bail;
+use chrono::{
+  DateTime,
+  Duration,
+  Utc
+};
+use tracing::info;
+
+use crate::config::Config;
+use crate::datastore::DataStore;
+use crate::history::diff_snapshots;
+use crate::task::{
+  Status,
+  Task
+};
+
+/// History command recorded for
+/// archives made by the policy rather
+/// than by `task archive`.
+pub const AUTO_ARCHIVE_COMMAND: &str =
+  "auto_archive";
+
+/// The configured threshold; `None`
+/// (off) when unset, zero or not a
+/// positive whole number of days. A
+/// count no date span can hold is an
+/// error.
+pub fn auto_archive_days(
+  cfg: &Config
+) -> anyhow::Result<Option<i64>> {
+  cfg
+    .get("auto_archive.days")
+    .and_then(|raw| {
+      raw.trim().parse::<i64>().ok()
+    })
+    .filter(|days| *days > 0)
+    .map(check_days)
+    .transpose()
+}
+
+/// Fails when `days` is too large for
+/// a date span.
+pub fn check_days(
+  days: i64
+) -> anyhow::Result<i64> {
+  if Duration::try_days(days).is_none()
+  {
+    bail!(
+      "auto_archive.days {days} is \
+       out of range"
+    );
+  }
+  Ok(days)
+}
+
+/// Archives every completed task whose
+/// `end` is `days` or more before
+/// `now`; returns how many changed.
+pub fn archive_expired(
+  completed: &mut [Task],
+  days: i64,
+  now: DateTime<Utc>
+) -> usize {
+  // A threshold reaching past the
+  // earliest representable date
+  // archives nothing.
+  let Some(cutoff) =
+    Duration::try_days(days).and_then(
+      |span| {
+        now.checked_sub_signed(span)
+      }
+    )
+  else {
+    return 0;
+  };
+  let mut changed = 0;
+  for task in completed {
+    if task.status != Status::Completed
+    {
+      continue;
+    }
+    if task
+      .end
+      .is_some_and(|end| end <= cutoff)
+    {
+      task.status = Status::Archived;
+      task.modified = now;
+      changed += 1;
+    }
+  }
+  changed
+}
+
+/// Applies `archive_expired` to the
+/// store as one bulk write: a single
+/// undo snapshot (so one `task undo`
+/// restores them all) and history
+/// entries under
+/// `AUTO_ARCHIVE_COMMAND`. Nothing is
+/// written when no task qualifies.
+pub fn run(
+  store: &DataStore,
+  days: i64,
+  now: DateTime<Utc>
+) -> anyhow::Result<usize> {
+  let pending = store.load_pending()?;
+  let before =
+    store.load_completed()?;
+  let mut completed = before.clone();
+  let changed = archive_expired(
+    &mut completed,
+    days,
+    now
+  );
+  if changed == 0 {
+    return Ok(0);
+  }
+  store.push_undo_snapshot(
+    &pending, &before
+  )?;
+  store.save_completed(&completed)?;
+  store.append_history(
+    &diff_snapshots(
+      AUTO_ARCHIVE_COMMAND,
+      &before,
+      &completed,
+      now
+    )
+  )?;
+  info!(
+    changed,
+    days,
+    "auto-archived completed tasks"
+  );
+  Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn archives_only_completed_tasks_past_the_threshold()
+   {
+    let now = Utc::now();
+    let finished = |days_ago: i64| {
+      let mut task = Task::new_pending(
+        format!("done {days_ago}"),
+        now,
+        1
+      );
+      task.id = None;
+      task.status = Status::Completed;
+      task.end = Some(
+        now - Duration::days(days_ago)
+      );
+      task
+    };
+    let mut deleted = finished(90);
+    deleted.status = Status::Deleted;
+    let mut completed = vec![
+      finished(31),
+      finished(30),
+      finished(2),
+      deleted,
+    ];
+
+    assert_eq!(
+      archive_expired(
+        &mut completed,
+        30,
+        now
+      ),
+      2
+    );
+    let statuses: Vec<Status> =
+      completed
+        .iter()
+        .map(|task| task.status.clone())
+        .collect();
+    assert_eq!(statuses, vec![
+      Status::Archived,
+      Status::Archived,
+      Status::Completed,
+      Status::Deleted
+    ]);
+
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let store =
+      DataStore::open(temp.path())
+        .expect("open store");
+    store
+      .save_completed(&[finished(40)])
+      .expect("save");
+    assert_eq!(
+      run(&store, 30, now)
+        .expect("run"),
+      1
+    );
+    assert_eq!(
+      run(&store, 30, now)
+        .expect("rerun"),
+      0
+    );
+    assert_eq!(
+      archive_expired(
+        &mut completed,
+        i64::MAX,
+        now
+      ),
+      0
+    );
+    assert!(
+      check_days(99_999_999_999_999)
+        .is_err()
+    );
+    assert!(
+      store
+        .pop_undo_snapshot()
+        .expect("undo")
+        .is_some_and(
+          |(_, completed)| {
+            completed[0].status
+              == Status::Completed
+          }
+        )
+    );
+  }
+}
@@ -0,0 +1,323 @@
+//! Tagging rules from config, a
+//! script-free stand-in for simple
+//! on-add/on-modify hooks:
+//!
+//! ```text
+//! autotag.finance.description=/invoice/i
+//! autotag.finance.tags=finance
+//! autotag.billable.project=client\..*
+//! autotag.billable.tags=billable
+//! ```
+//!
+//! A rule fires when every condition it
+//! sets matches. `description` is
+//! searched anywhere in the text,
+//! `project` must match the whole
+//! project name; both take a regex,
+//! optionally written `/regex/i` for a
+//! case-insensitive match. Rules run in
+//! name order and never add a tag
+//! twice.
+
+use std::collections::BTreeMap;
+
+use anyhow::{
+  anyhow,
+  bail
+};
+use regex::{
+  Regex,
+  RegexBuilder
+};
+
+use crate::config::Config;
+use crate::task::{
+  Task,
+  split_tag_list
+};
+
+#[derive(Debug, Clone)]
+struct AutoTagRule {
+  description: Option<Regex>,
+  project:     Option<Regex>,
+  tags:        Vec<String>
+}
+
+impl AutoTagRule {
+  fn matches(
+    &self,
+    task: &Task
+  ) -> bool {
+    self
+      .description
+      .as_ref()
+      .is_none_or(|re| {
+        re.is_match(&task.description)
+      })
+      && self
+        .project
+        .as_ref()
+        .is_none_or(|re| {
+          task
+            .project
+            .as_deref()
+            .is_some_and(|p| {
+              re.is_match(p)
+            })
+        })
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AutoTagRules {
+  rules: Vec<(String, AutoTagRule)>
+}
+
+impl AutoTagRules {
+  /// Reads every `autotag.<name>.*`
+  /// key; a rule with an unknown field,
+  /// a bad pattern, no condition or no
+  /// tags is an error.
+  pub fn from_config(
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    let mut fields: BTreeMap<
+      String,
+      BTreeMap<String, String>
+    > = BTreeMap::new();
+    for (key, value) in cfg.iter() {
+      if let Some((name, field)) = key
+        .strip_prefix("autotag.")
+        .and_then(|rest| {
+          rest.rsplit_once('.')
+        })
+      {
+        fields
+          .entry(name.to_string())
+          .or_default()
+          .insert(
+            field.to_string(),
+            value.clone()
+          );
+      }
+    }
+
+    let mut rules = Vec::new();
+    for (name, fields) in fields {
+      let mut rule = AutoTagRule {
+        description: None,
+        project:     None,
+        tags:        Vec::new()
+      };
+      for (field, value) in fields {
+        let pattern = |anchored| {
+          parse_pattern(&value, anchored)
+            .map_err(|err| {
+              anyhow!(
+                "autotag.{name}.{field}: \
+                 {err}"
+              )
+            })
+        };
+        match field.as_str() {
+          | "description" => {
+            rule.description =
+              Some(pattern(false)?);
+          }
+          | "project" => {
+            rule.project =
+              Some(pattern(true)?);
+          }
+          | "tags" => {
+            rule.tags =
+              split_tag_list(&value);
+          }
+          | other => {
+            bail!(
+              "autotag.{name}.{other}: \
+               unknown field (expected \
+               description, project or \
+               tags)"
+            )
+          }
+        }
+      }
+      if rule.tags.is_empty() {
+        bail!(
+          "autotag.{name}: no tags to \
+           add"
+        );
+      }
+      if rule.description.is_none()
+        && rule.project.is_none()
+      {
+        bail!(
+          "autotag.{name}: needs a \
+           description or project \
+           pattern"
+        );
+      }
+      rules.push((name, rule));
+    }
+    Ok(Self {
+      rules
+    })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.rules.is_empty()
+  }
+
+  /// Adds the tags of every matching
+  /// rule the task does not have yet
+  /// and returns them in the order
+  /// added.
+  pub fn apply(
+    &self,
+    task: &mut Task
+  ) -> Vec<String> {
+    let mut added = Vec::new();
+    for (_, rule) in &self.rules {
+      if !rule.matches(task) {
+        continue;
+      }
+      for tag in &rule.tags {
+        if !task.tags.contains(tag) {
+          task.tags.push(tag.clone());
+          added.push(tag.clone());
+        }
+      }
+    }
+    added
+  }
+}
+
+/// `/regex/` or `/regex/i`, otherwise
+/// the whole value is the regex.
+fn parse_pattern(
+  value: &str,
+  anchored: bool
+) -> anyhow::Result<Regex> {
+  let value = value.trim();
+  let (source, case_insensitive) =
+    match value
+      .strip_prefix('/')
+      .and_then(|rest| {
+        rest.rsplit_once('/')
+      }) {
+      | Some((source, "")) => {
+        (source, false)
+      }
+      | Some((source, "i")) => {
+        (source, true)
+      }
+      | Some((_, flags)) => {
+        bail!(
+          "unsupported flags \
+           '{flags}' (only i)"
+        )
+      }
+      | None => (value, false)
+    };
+  let source = if anchored {
+    format!("^(?:{source})$")
+  } else {
+    source.to_string()
+  };
+  Ok(
+    RegexBuilder::new(&source)
+      .case_insensitive(
+        case_insensitive
+      )
+      .build()?
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+
+  use super::AutoTagRules;
+  use crate::config::Config;
+  use crate::task::Task;
+
+  fn rules(
+    lines: &[&str]
+  ) -> anyhow::Result<AutoTagRules> {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let taskrc =
+      temp.path().join("taskrc");
+    std::fs::write(
+      &taskrc,
+      lines.join("\n")
+    )
+    .expect("write taskrc");
+    AutoTagRules::from_config(
+      &Config::load(Some(&taskrc))
+        .expect("load config")
+    )
+  }
+
+  #[test]
+  fn rules_apply_in_name_order_without_duplicates()
+   {
+    let rules = rules(&[
+      r"autotag.b_bill.project=client\..*",
+      "autotag.b_bill.tags=billable,finance",
+      "autotag.a_inv.description=/invoice/i",
+      "autotag.a_inv.tags=finance"
+    ])
+    .expect("rules");
+    let mut task = Task::new_pending(
+      "Send INVOICE".to_string(),
+      Utc::now(),
+      1
+    );
+    task.project =
+      Some("client.acme".to_string());
+    assert_eq!(
+      rules.apply(&mut task),
+      vec!["finance", "billable"]
+    );
+    assert_eq!(task.tags, vec![
+      "finance", "billable"
+    ]);
+    assert!(
+      rules.apply(&mut task).is_empty()
+    );
+
+    task.project =
+      Some("clientele".to_string());
+    task.tags.clear();
+    assert_eq!(
+      rules.apply(&mut task),
+      vec!["finance"]
+    );
+  }
+
+  #[test]
+  fn invalid_rules_are_rejected() {
+    for lines in [
+      &["autotag.x.tags=a"][..],
+      &[
+        "autotag.x.description=(",
+        "autotag.x.tags=a"
+      ],
+      &[
+        "autotag.x.description=/a/g",
+        "autotag.x.tags=a"
+      ],
+      &[
+        "autotag.x.descripton=a",
+        "autotag.x.tags=a"
+      ],
+      &["autotag.x.description=a"]
+    ] {
+      assert!(
+        rules(lines).is_err(),
+        "{lines:?} should be rejected"
+      );
+    }
+  }
+}
@@ -0,0 +1,1214 @@
+//! Whole-directory snapshots for
+//! `backup` / `restore`.
+//!
+//! A snapshot is one gzip stream: a
+//! JSON header line naming the store
+//! format and every file with its
+//! size, followed by the raw bytes of
+//! those files in header order. The
+//! header alone is enough to tell
+//! whether this build can restore it.
+
+use std::fs;
+use std::io::{
+  BufRead,
+  BufReader,
+  Read,
+  Write
+};
+use std::path::{
+  Component,
+  Path,
+  PathBuf
+};
+
+use anyhow::{
+  Context,
+  bail
+};
+use chrono::{
+  DateTime,
+  Utc
+};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{
+  Deserialize,
+  Serialize
+};
+use tempfile::NamedTempFile;
+use tracing::info;
+
+use crate::datastore::STORE_FORMAT_VERSION;
+use crate::migrate;
+
+/// `format` value in every snapshot
+/// header.
+pub const SNAPSHOT_FORMAT: &str =
+  "rivet-snapshot";
+
+/// Bump when the container layout
+/// (not the store format) changes.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Directory under the data directory
+/// that snapshots default to. It is
+/// never captured or replaced itself.
+pub const BACKUPS_DIR: &str = "backups";
+
+/// File name suffix of snapshots.
+pub const SNAPSHOT_SUFFIX: &str =
+  ".snapshot.gz";
+
+/// Prefix of the snapshot `restore`
+/// takes of the current state first.
+pub const PRE_RESTORE_PREFIX: &str =
+  "pre-restore";
+
+/// Prefix of automatic daily
+/// snapshots.
+pub const DAILY_PREFIX: &str = "daily";
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+  Serialize,
+  Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotRoot {
+  /// Relative to the data directory.
+  #[default]
+  Data,
+  /// The taskrc the snapshot was taken
+  /// with; `path` is its file name.
+  Taskrc
+}
+
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+)]
+pub struct SnapshotEntry {
+  #[serde(default)]
+  pub root:       SnapshotRoot,
+  /// `/`-separated, relative to
+  /// `root`.
+  pub path:       String,
+  pub size:       u64,
+  #[serde(default)]
+  pub executable: bool
+}
+
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+)]
+pub struct SnapshotHeader {
+  pub format:               String,
+  pub snapshot_version:     u32,
+  pub store_format_version: String,
+  #[serde(default)]
+  pub written_by:           String,
+  pub created: DateTime<Utc>,
+  pub files: Vec<SnapshotEntry>
+}
+
+impl SnapshotHeader {
+  pub fn total_bytes(&self) -> u64 {
+    self
+      .files
+      .iter()
+      .map(|entry| entry.size)
+      .sum()
+  }
+
+  /// Refuses headers this build cannot
+  /// restore: other containers, newer
+  /// container layouts and newer store
+  /// formats. Older store formats are
+  /// fine; the next open migrates them.
+  pub fn check_restorable(
+    &self
+  ) -> anyhow::Result<()> {
+    if self.format != SNAPSHOT_FORMAT {
+      bail!(
+        "not a rivet snapshot (format \
+         {:?})",
+        self.format
+      );
+    }
+    if self.snapshot_version
+      > SNAPSHOT_VERSION
+    {
+      bail!(
+        "snapshot container version \
+         {} is newer than this build \
+         understands \
+         ({SNAPSHOT_VERSION})",
+        self.snapshot_version
+      );
+    }
+    let snapshot_key =
+      migrate::parse_version(
+        &self.store_format_version
+      )?;
+    let current_key =
+      migrate::parse_version(
+        STORE_FORMAT_VERSION
+      )?;
+    if snapshot_key > current_key {
+      bail!(
+        "snapshot uses store format \
+         {}, but this build only \
+         understands up to \
+         {STORE_FORMAT_VERSION}; \
+         upgrade rivet to restore it",
+        self.store_format_version
+      );
+    }
+    for entry in &self.files {
+      relative_path(&entry.path)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotSummary {
+  pub path:   PathBuf,
+  pub header: SnapshotHeader
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreReport {
+  pub header:         SnapshotHeader,
+  /// Snapshot of the state that was
+  /// replaced.
+  pub safety:         PathBuf,
+  pub restored:       usize,
+  /// Files that were not in the
+  /// snapshot and were deleted.
+  pub removed:        usize,
+  /// Set when the snapshot carried a
+  /// taskrc but none is loaded to
+  /// restore it over.
+  pub skipped_taskrc: bool,
+  pub migrated_to:    Option<String>
+}
+
+pub fn default_backup_dir(
+  data_dir: &Path
+) -> PathBuf {
+  data_dir.join(BACKUPS_DIR)
+}
+
+/// Writes a snapshot of `data_dir`
+/// (plus `taskrc` when given) into
+/// `dest_dir` as
+/// `<prefix>-<stamp>.snapshot.gz`.
+/// `*.index` sidecars are skipped since
+/// they are rebuilt on demand.
+pub fn write_snapshot(
+  data_dir: &Path,
+  taskrc: Option<&Path>,
+  dest_dir: &Path,
+  prefix: &str,
+  now: DateTime<Utc>
+) -> anyhow::Result<SnapshotSummary> {
+  fs::create_dir_all(dest_dir)
+    .with_context(|| {
+      format!(
+        "failed to create {}",
+        dest_dir.display()
+      )
+    })?;
+  let skip_dir =
+    dest_dir.canonicalize().ok();
+  let mut sources = Vec::new();
+  collect_data_files(
+    data_dir,
+    data_dir,
+    skip_dir.as_deref(),
+    &mut sources
+  )?;
+  sources.sort_by(|a, b| a.0.cmp(&b.0));
+
+  let mut files = Vec::new();
+  let mut paths = Vec::new();
+  for (relative, source) in sources {
+    let meta = fs::metadata(&source)
+      .with_context(|| {
+        format!(
+          "failed to stat {}",
+          source.display()
+        )
+      })?;
+    files.push(SnapshotEntry {
+      root:       SnapshotRoot::Data,
+      path:       relative,
+      size:       meta.len(),
+      executable: is_executable(&meta)
+    });
+    paths.push(source);
+  }
+  if let Some(taskrc) =
+    taskrc.filter(|path| path.is_file())
+  {
+    let meta = fs::metadata(taskrc)?;
+    files.push(SnapshotEntry {
+      root:       SnapshotRoot::Taskrc,
+      path:       taskrc
+        .file_name()
+        .map(|name| {
+          name
+            .to_string_lossy()
+            .into_owned()
+        })
+        .unwrap_or_else(|| {
+          "taskrc".to_string()
+        }),
+      size:       meta.len(),
+      executable: false
+    });
+    paths.push(taskrc.to_path_buf());
+  }
+
+  let mut header = SnapshotHeader {
+    format: SNAPSHOT_FORMAT.to_string(),
+    snapshot_version: SNAPSHOT_VERSION,
+    store_format_version:
+      current_store_format(data_dir)?,
+    written_by: env!(
+      "CARGO_PKG_VERSION"
+    )
+    .to_string(),
+    created: now,
+    files
+  };
+
+  // Read everything up front so the
+  // sizes in the header match the
+  // bytes that follow even if a file
+  // changes while we write.
+  let mut contents = Vec::new();
+  for (entry, path) in
+    header.files.iter_mut().zip(&paths)
+  {
+    let bytes = fs::read(path)
+      .with_context(|| {
+        format!(
+          "failed to read {}",
+          path.display()
+        )
+      })?;
+    entry.size = bytes.len() as u64;
+    contents.push(bytes);
+  }
+
+  let temp =
+    NamedTempFile::new_in(dest_dir)
+      .with_context(|| {
+        format!(
+          "failed to create a \
+           temporary file in {}",
+          dest_dir.display()
+        )
+      })?;
+  let mut encoder = GzEncoder::new(
+    temp,
+    Compression::default()
+  );
+  serde_json::to_writer(
+    &mut encoder,
+    &header
+  )?;
+  encoder.write_all(b"\n")?;
+  for bytes in &contents {
+    encoder.write_all(bytes)?;
+  }
+  let temp = encoder.finish()?;
+
+  let path = unused_snapshot_path(
+    dest_dir, prefix, now
+  );
+  temp.persist(&path).with_context(
+    || {
+      format!(
+        "failed to write {}",
+        path.display()
+      )
+    }
+  )?;
+  info!(
+    snapshot = %path.display(),
+    files = header.files.len(),
+    "wrote snapshot"
+  );
+  Ok(SnapshotSummary {
+    path,
+    header
+  })
+}
+
+/// Reads just the header.
+pub fn read_snapshot_header(
+  path: &Path
+) -> anyhow::Result<SnapshotHeader> {
+  let mut reader = open_snapshot(path)?;
+  read_header(&mut reader, path)
+}
+
+/// Replaces `data_dir` (and `taskrc`,
+/// when the snapshot has one) with the
+/// snapshot's contents. The whole
+/// snapshot is read and checked before
+/// anything is touched, and the current
+/// state is snapshotted into the
+/// backups directory first.
+pub fn restore_snapshot(
+  snapshot: &Path,
+  data_dir: &Path,
+  taskrc: Option<&Path>,
+  now: DateTime<Utc>
+) -> anyhow::Result<RestoreReport> {
+  let mut reader =
+    open_snapshot(snapshot)?;
+  let header =
+    read_header(&mut reader, snapshot)?;
+  header.check_restorable()?;
+  let mut contents = Vec::with_capacity(
+    header.files.len()
+  );
+  for entry in &header.files {
+    // The header is untrusted, so grow
+    // the buffer with the stream rather
+    // than allocating the declared size
+    // up front.
+    let mut bytes = Vec::new();
+    let read = reader
+      .by_ref()
+      .take(entry.size)
+      .read_to_end(&mut bytes)
+      .with_context(|| {
+        format!(
+          "{} is unreadable at {}",
+          snapshot.display(),
+          entry.path
+        )
+      })?;
+    if read as u64 != entry.size {
+      bail!(
+        "{} is truncated at {}",
+        snapshot.display(),
+        entry.path
+      );
+    }
+    contents.push(bytes);
+  }
+
+  let safety = write_snapshot(
+    data_dir,
+    taskrc,
+    &default_backup_dir(data_dir),
+    PRE_RESTORE_PREFIX,
+    now
+  )
+  .context(
+    "failed to back up the current \
+     state; nothing was restored"
+  )?;
+
+  // Restoring from a backup directory
+  // inside the data directory must not
+  // delete its other snapshots.
+  let skip_dir =
+    snapshot.parent().and_then(|dir| {
+      dir.canonicalize().ok()
+    });
+  let mut current = Vec::new();
+  collect_data_files(
+    data_dir,
+    data_dir,
+    skip_dir.as_deref(),
+    &mut current
+  )?;
+  let mut removed = 0;
+  for (relative, path) in current {
+    let kept = header.files.iter().any(
+      |entry| {
+        entry.root == SnapshotRoot::Data
+          && entry.path == relative
+      }
+    );
+    if !kept {
+      fs::remove_file(&path)
+        .with_context(|| {
+          format!(
+            "failed to remove {}",
+            path.display()
+          )
+        })?;
+      removed += 1;
+    }
+  }
+
+  let mut restored = 0;
+  let mut skipped_taskrc = false;
+  for (entry, bytes) in
+    header.files.iter().zip(contents)
+  {
+    let target = match entry.root {
+      | SnapshotRoot::Data => {
+        data_dir.join(relative_path(
+          &entry.path
+        )?)
+      }
+      | SnapshotRoot::Taskrc => {
+        match taskrc {
+          | Some(path) => {
+            path.to_path_buf()
+          }
+          | None => {
+            skipped_taskrc = true;
+            continue;
+          }
+        }
+      }
+    };
+    if let Some(parent) =
+      target.parent()
+    {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, bytes)
+      .with_context(|| {
+        format!(
+          "failed to restore {}",
+          target.display()
+        )
+      })?;
+    if entry.executable {
+      set_executable(&target)?;
+    }
+    restored += 1;
+  }
+
+  let report =
+    migrate::prepare_data_dir(
+      data_dir
+    )?;
+  info!(
+    snapshot = %snapshot.display(),
+    safety = %safety.path.display(),
+    restored,
+    removed,
+    "restored snapshot"
+  );
+  Ok(RestoreReport {
+    header,
+    safety: safety.path,
+    restored,
+    removed,
+    skipped_taskrc,
+    migrated_to: (!report
+      .applied
+      .is_empty())
+    .then_some(report.to)
+  })
+}
+
+/// Snapshots in `dir` whose names
+/// start with `prefix`, oldest first.
+pub fn list_snapshots(
+  dir: &Path,
+  prefix: &str
+) -> anyhow::Result<Vec<PathBuf>> {
+  if !dir.is_dir() {
+    return Ok(Vec::new());
+  }
+  let mut found = Vec::new();
+  for entry in fs::read_dir(dir)? {
+    let path = entry?.path();
+    let Some(name) = path
+      .file_name()
+      .and_then(|name| name.to_str())
+    else {
+      continue;
+    };
+    if name.starts_with(&format!(
+      "{prefix}-"
+    )) && name
+      .ends_with(SNAPSHOT_SUFFIX)
+    {
+      found.push(path);
+    }
+  }
+  // Names embed a sortable UTC stamp.
+  found.sort();
+  Ok(found)
+}
+
+/// Deletes all but the newest `keep`
+/// snapshots with `prefix` and returns
+/// the ones it removed.
+pub fn prune_snapshots(
+  dir: &Path,
+  prefix: &str,
+  keep: usize
+) -> anyhow::Result<Vec<PathBuf>> {
+  let found =
+    list_snapshots(dir, prefix)?;
+  let excess =
+    found.len().saturating_sub(keep);
+  let removed: Vec<PathBuf> = found
+    .into_iter()
+    .take(excess)
+    .collect();
+  for path in &removed {
+    fs::remove_file(path)
+      .with_context(|| {
+        format!(
+          "failed to remove {}",
+          path.display()
+        )
+      })?;
+  }
+  Ok(removed)
+}
+
+/// Takes a `daily-*` snapshot into the
+/// backups directory unless the newest
+/// one is less than a day old, then
+/// keeps only the newest `keep` of
+/// them. Returns the new snapshot, if
+/// any.
+pub fn ensure_daily_snapshot(
+  data_dir: &Path,
+  keep: usize,
+  now: DateTime<Utc>
+) -> anyhow::Result<
+  Option<SnapshotSummary>
+> {
+  let dir =
+    default_backup_dir(data_dir);
+  let latest =
+    list_snapshots(&dir, DAILY_PREFIX)?
+      .last()
+      .and_then(|path| {
+        read_snapshot_header(path).ok()
+      });
+  if latest.is_some_and(|header| {
+    now - header.created
+      < chrono::Duration::days(1)
+  }) {
+    return Ok(None);
+  }
+
+  let summary = write_snapshot(
+    data_dir,
+    None,
+    &dir,
+    DAILY_PREFIX,
+    now
+  )?;
+  prune_snapshots(
+    &dir,
+    DAILY_PREFIX,
+    keep.max(1)
+  )?;
+  Ok(Some(summary))
+}
+
+fn current_store_format(
+  data_dir: &Path
+) -> anyhow::Result<String> {
+  Ok(
+    migrate::read_manifest(data_dir)?
+      .map(|manifest| {
+        manifest.format_version
+      })
+      .unwrap_or_else(|| {
+        STORE_FORMAT_VERSION.to_string()
+      })
+  )
+}
+
+fn collect_data_files(
+  data_dir: &Path,
+  dir: &Path,
+  skip_dir: Option<&Path>,
+  out: &mut Vec<(String, PathBuf)>
+) -> anyhow::Result<()> {
+  if !dir.is_dir() {
+    return Ok(());
+  }
+  for entry in fs::read_dir(dir)
+    .with_context(|| {
+      format!(
+        "failed to read {}",
+        dir.display()
+      )
+    })?
+  {
+    let path = entry?.path();
+    let relative = path
+      .strip_prefix(data_dir)
+      .unwrap_or(&path)
+      .components()
+      .map(|part| {
+        part
+          .as_os_str()
+          .to_string_lossy()
+          .into_owned()
+      })
+      .collect::<Vec<_>>()
+      .join("/");
+    if path.is_dir() {
+      let skipped = relative
+        == BACKUPS_DIR
+        || skip_dir.is_some_and(
+          |skip| {
+            path
+              .canonicalize()
+              .is_ok_and(|dir| {
+                dir == skip
+              })
+          }
+        );
+      if !skipped {
+        collect_data_files(
+          data_dir, &path, skip_dir,
+          out
+        )?;
+      }
+    } else if path
+      .extension()
+      .is_none_or(|ext| ext != "index")
+      && !relative
+        .ends_with(SNAPSHOT_SUFFIX)
+    {
+      out.push((relative, path));
+    }
+  }
+  Ok(())
+}
+
+/// Rejects absolute paths and `..` so
+/// a snapshot can only write inside
+/// the data directory.
+fn relative_path(
+  raw: &str
+) -> anyhow::Result<PathBuf> {
+  let path = PathBuf::from(raw);
+  let safe = !raw.is_empty()
+    && path.components().all(|part| {
+      matches!(
+        part,
+        Component::Normal(_)
+      )
+    });
+  if !safe {
+    bail!(
+      "snapshot entry {raw:?} points \
+       outside the data directory"
+    );
+  }
+  Ok(path)
+}
+
+fn open_snapshot(
+  path: &Path
+) -> anyhow::Result<
+  BufReader<GzDecoder<fs::File>>
+> {
+  let file = fs::File::open(path)
+    .with_context(|| {
+      format!(
+        "failed to open snapshot {}",
+        path.display()
+      )
+    })?;
+  Ok(BufReader::new(GzDecoder::new(
+    file
+  )))
+}
+
+fn read_header(
+  reader: &mut impl BufRead,
+  path: &Path
+) -> anyhow::Result<SnapshotHeader> {
+  let mut line = String::new();
+  reader
+    .read_line(&mut line)
+    .with_context(|| {
+      format!(
+        "{} is not a readable snapshot",
+        path.display()
+      )
+    })?;
+  serde_json::from_str(line.trim_end())
+    .with_context(|| {
+      format!(
+        "{} has no snapshot header",
+        path.display()
+      )
+    })
+}
+
+fn unused_snapshot_path(
+  dir: &Path,
+  prefix: &str,
+  now: DateTime<Utc>
+) -> PathBuf {
+  let stamp =
+    now.format("%Y%m%dT%H%M%SZ");
+  let mut path = dir.join(format!(
+    "{prefix}-{stamp}{SNAPSHOT_SUFFIX}"
+  ));
+  let mut counter = 1;
+  while path.exists() {
+    path = dir.join(format!(
+      "{prefix}-{stamp}-{counter}\
+       {SNAPSHOT_SUFFIX}"
+    ));
+    counter += 1;
+  }
+  path
+}
+
+#[cfg(unix)]
+fn is_executable(
+  meta: &fs::Metadata
+) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+  meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(
+  _meta: &fs::Metadata
+) -> bool {
+  false
+}
+
+#[cfg(unix)]
+fn set_executable(
+  path: &Path
+) -> anyhow::Result<()> {
+  use std::os::unix::fs::PermissionsExt;
+  let mut permissions =
+    fs::metadata(path)?.permissions();
+  permissions.set_mode(
+    permissions.mode() | 0o755
+  );
+  fs::set_permissions(
+    path,
+    permissions
+  )?;
+  Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(
+  _path: &Path
+) -> anyhow::Result<()> {
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fill(data_dir: &Path) {
+    fs::create_dir_all(
+      data_dir.join("hooks")
+    )
+    .expect("hooks dir");
+    fs::write(
+      data_dir.join("pending.data"),
+      "{\"a\":1}\n"
+    )
+    .expect("pending");
+    fs::write(
+      data_dir.join("contacts.data"),
+      "contact\n"
+    )
+    .expect("contacts");
+    fs::write(
+      data_dir.join("pending.index"),
+      [0u8, 1, 2]
+    )
+    .expect("index");
+    fs::write(
+      data_dir
+        .join("hooks")
+        .join("on-add.sh"),
+      "#!/bin/sh\n"
+    )
+    .expect("hook");
+  }
+
+  #[test]
+  fn snapshots_round_trip_and_restore_rolls_back()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let data_dir =
+      temp.path().join("data");
+    let taskrc =
+      temp.path().join("taskrc");
+    fill(&data_dir);
+    fs::write(&taskrc, "color=off\n")
+      .expect("taskrc");
+    let now = Utc::now();
+
+    let summary = write_snapshot(
+      &data_dir,
+      Some(&taskrc),
+      &default_backup_dir(&data_dir),
+      "rivet",
+      now
+    )
+    .expect("snapshot");
+    let paths: Vec<&str> = summary
+      .header
+      .files
+      .iter()
+      .map(|entry| entry.path.as_str())
+      .collect();
+    assert_eq!(paths, vec![
+      "contacts.data",
+      "hooks/on-add.sh",
+      "pending.data",
+      "taskrc"
+    ]);
+    assert_eq!(
+      read_snapshot_header(
+        &summary.path
+      )
+      .expect("header"),
+      summary.header
+    );
+
+    fs::write(
+      data_dir.join("pending.data"),
+      "broken"
+    )
+    .expect("corrupt");
+    fs::write(
+      data_dir.join("stray.data"),
+      "new"
+    )
+    .expect("stray");
+    fs::write(&taskrc, "color=on\n")
+      .expect("taskrc");
+
+    let report = restore_snapshot(
+      &summary.path,
+      &data_dir,
+      Some(&taskrc),
+      now
+    )
+    .expect("restore");
+    assert_eq!(report.restored, 4);
+    assert_eq!(
+      fs::read_to_string(
+        data_dir.join("pending.data")
+      )
+      .expect("pending"),
+      "{\"a\":1}\n"
+    );
+    assert!(
+      !data_dir
+        .join("stray.data")
+        .exists()
+    );
+    assert_eq!(
+      fs::read_to_string(&taskrc)
+        .expect("taskrc"),
+      "color=off\n"
+    );
+    assert!(summary.path.exists());
+
+    let safety = read_snapshot_header(
+      &report.safety
+    )
+    .expect("safety header");
+    assert!(safety.files.iter().any(
+      |entry| {
+        entry.path == "stray.data"
+      }
+    ));
+  }
+
+  #[test]
+  fn restore_keeps_snapshots_in_a_nested_backup_dir()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let data_dir =
+      temp.path().join("data");
+    let custom =
+      data_dir.join("snapshots");
+    fill(&data_dir);
+    let now = Utc::now();
+
+    let older = write_snapshot(
+      &data_dir, None, &custom,
+      "older", now
+    )
+    .expect("older snapshot");
+    let summary = write_snapshot(
+      &data_dir, None, &custom,
+      "rivet", now
+    )
+    .expect("snapshot");
+    assert!(
+      !summary.header.files.iter().any(
+        |entry| {
+          entry
+            .path
+            .ends_with(SNAPSHOT_SUFFIX)
+        }
+      )
+    );
+
+    let report = restore_snapshot(
+      &summary.path,
+      &data_dir,
+      None,
+      now
+    )
+    .expect("restore");
+    assert_eq!(report.removed, 0);
+    assert!(older.path.exists());
+    assert!(summary.path.exists());
+    assert!(report.safety.exists());
+  }
+
+  #[test]
+  fn newer_store_formats_and_escaping_paths_are_refused()
+   {
+    let mut header = SnapshotHeader {
+      format:
+        SNAPSHOT_FORMAT.to_string(),
+      snapshot_version:
+        SNAPSHOT_VERSION,
+      store_format_version: "99.0.0"
+        .to_string(),
+      written_by:           String::new(
+      ),
+      created:              Utc::now(),
+      files:                Vec::new()
+    };
+    assert!(
+      header
+        .check_restorable()
+        .is_err()
+    );
+
+    header.store_format_version =
+      STORE_FORMAT_VERSION.to_string();
+    header.files.push(SnapshotEntry {
+      root:       SnapshotRoot::Data,
+      path:       "../outside"
+        .to_string(),
+      size:       0,
+      executable: false
+    });
+    assert!(
+      header
+        .check_restorable()
+        .is_err()
+    );
+
+    header.files[0].path =
+      "hooks/on-add.sh".to_string();
+    assert!(
+      header.check_restorable().is_ok()
+    );
+  }
+
+  #[test]
+  fn oversized_entries_fail_without_allocating_them()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let data_dir =
+      temp.path().join("data");
+    fill(&data_dir);
+    let header = SnapshotHeader {
+      format:
+        SNAPSHOT_FORMAT.to_string(),
+      snapshot_version:
+        SNAPSHOT_VERSION,
+      store_format_version:
+        STORE_FORMAT_VERSION.to_string(),
+      written_by:           String::new(
+      ),
+      created:              Utc::now(),
+      files:                vec![
+        SnapshotEntry {
+          root:
+            SnapshotRoot::Data,
+          path:       "pending.data"
+            .to_string(),
+          size:       u64::MAX,
+          executable: false
+        },
+      ]
+    };
+    let snapshot = temp
+      .path()
+      .join("hostile.snapshot.gz");
+    let mut encoder = GzEncoder::new(
+      fs::File::create(&snapshot)
+        .expect("create snapshot"),
+      Compression::default()
+    );
+    serde_json::to_writer(
+      &mut encoder,
+      &header
+    )
+    .expect("header");
+    encoder
+      .write_all(b"\n{}\n")
+      .expect("body");
+    encoder.finish().expect("finish");
+
+    let err = restore_snapshot(
+      &snapshot,
+      &data_dir,
+      None,
+      Utc::now()
+    )
+    .expect_err("oversized entry");
+    assert!(
+      err
+        .to_string()
+        .contains("truncated")
+    );
+    assert_eq!(
+      fs::read_to_string(
+        data_dir.join("pending.data")
+      )
+      .expect("pending"),
+      "{\"a\":1}\n"
+    );
+  }
+
+  #[test]
+  fn daily_snapshots_run_once_a_day_and_respect_retention()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let data_dir = temp.path();
+    fill(data_dir);
+    let start = Utc::now();
+
+    for (offset_hours, expect_new) in [
+      (0, true),
+      (5, false),
+      (25, true),
+      (50, true)
+    ] {
+      let taken =
+        ensure_daily_snapshot(
+          data_dir,
+          2,
+          start
+            + chrono::Duration::hours(
+              offset_hours
+            )
+        )
+        .expect("daily snapshot");
+      assert_eq!(
+        taken.is_some(),
+        expect_new,
+        "after {offset_hours}h"
+      );
+    }
+    assert_eq!(
+      list_snapshots(
+        &default_backup_dir(data_dir),
+        DAILY_PREFIX
+      )
+      .expect("list")
+      .len(),
+      2
+    );
+  }
+
+  #[test]
+  fn pruning_keeps_the_newest_snapshots()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    for stamp in [
+      "20260101T000000Z",
+      "20260102T000000Z",
+      "20260103T000000Z"
+    ] {
+      fs::write(
+        temp.path().join(format!(
+          "daily-{stamp}{SNAPSHOT_SUFFIX}"
+        )),
+        ""
+      )
+      .expect("snapshot");
+    }
+    fs::write(
+      temp.path().join(format!(
+        "rivet-20250101T000000Z\
+         {SNAPSHOT_SUFFIX}"
+      )),
+      ""
+    )
+    .expect("manual");
+
+    let removed = prune_snapshots(
+      temp.path(),
+      "daily",
+      2
+    )
+    .expect("prune");
+    assert_eq!(removed.len(), 1);
+    assert!(
+      removed[0]
+        .to_string_lossy()
+        .contains("20260101")
+    );
+    assert_eq!(
+      list_snapshots(
+        temp.path(),
+        "daily"
+      )
+      .expect("list")
+      .len(),
+      2
+    );
+    assert_eq!(
+      list_snapshots(
+        temp.path(),
+        "rivet"
+      )
+      .expect("list")
+      .len(),
+      1
+    );
+  }
+}
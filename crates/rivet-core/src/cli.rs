@@ -1,6 +1,9 @@
 use std::collections::BTreeSet;
 use std::ffi::OsString;
-use std::io::IsTerminal;
+use std::io::{
+  IsTerminal,
+  Read
+};
 use std::path::PathBuf;
 
 use anyhow::anyhow;
@@ -78,6 +81,37 @@ pub struct GlobalCli {
   #[arg(long = "data")]
   pub data: Option<PathBuf>,
 
+  /// Run the command and print what it
+  /// would change without writing.
+  #[arg(long = "dry-run")]
+  pub dry_run: bool,
+
+  /// Fit tables to N columns instead
+  /// of the terminal width; 0 turns
+  /// fitting off.
+  #[arg(
+    long = "width",
+    value_name = "N"
+  )]
+  pub width: Option<usize>,
+
+  /// Same as `rc.gc=on|off`: whether
+  /// reports renumber pending ids.
+  #[arg(
+    long = "gc",
+    value_name = "on|off"
+  )]
+  pub gc: Option<String>,
+
+  /// Same as `rc.id.display=id|uuid`:
+  /// what the ID column of reports
+  /// shows.
+  #[arg(
+    long = "ids",
+    value_name = "id|uuid"
+  )]
+  pub ids: Option<String>,
+
   #[arg(
     trailing_var_arg = true,
     allow_hyphen_values = true
@@ -190,11 +224,141 @@ pub fn preprocess_args(
   })
 }
 
+/// Row cap requested with `limit:N` or
+/// `limit:page`.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum RowLimit {
+  /// `limit:0`: no cap, even over a
+  /// report's own `limit`.
+  Unlimited,
+  Rows(usize),
+  /// As many rows as fit on one screen.
+  Page
+}
+
+impl RowLimit {
+  pub fn parse(
+    value: &str
+  ) -> anyhow::Result<Self> {
+    if value
+      .eq_ignore_ascii_case("page")
+    {
+      return Ok(Self::Page);
+    }
+    match value.parse::<usize>() {
+      | Ok(0) => Ok(Self::Unlimited),
+      | Ok(rows) => {
+        Ok(Self::Rows(rows))
+      }
+      | Err(_) => {
+        Err(anyhow!(
+          "invalid limit: {value} \
+           (expected a number or \
+           'page')"
+        ))
+      }
+    }
+  }
+
+  /// Maximum rows to print, or `None`
+  /// for no cap. Page size comes from
+  /// `$LINES` minus the table header
+  /// and footer.
+  pub fn max_rows(
+    self
+  ) -> Option<usize> {
+    match self {
+      | Self::Unlimited => None,
+      | Self::Rows(rows) => Some(rows),
+      | Self::Page => {
+        let lines =
+          std::env::var("LINES")
+            .ok()
+            .and_then(|raw| {
+              raw
+                .trim()
+                .parse::<usize>()
+                .ok()
+            })
+            .unwrap_or(24);
+        Some(
+          lines
+            .saturating_sub(3)
+            .max(1)
+        )
+      }
+    }
+  }
+}
+
+/// Splits one command line into words
+/// the way a shell would for simple
+/// input: whitespace separates words,
+/// and single or double quotes group
+/// them.
+pub fn split_command_line(
+  line: &str
+) -> anyhow::Result<Vec<String>> {
+  let mut words = Vec::new();
+  let mut current = String::new();
+  let mut in_word = false;
+  let mut quote: Option<char> = None;
+
+  for ch in line.chars() {
+    match quote {
+      | Some(open) if ch == open => {
+        quote = None;
+      }
+      | Some(_) => current.push(ch),
+      | None
+        if ch == '"' || ch == '\'' =>
+      {
+        quote = Some(ch);
+        in_word = true;
+      }
+      | None if ch.is_whitespace() => {
+        if in_word {
+          words.push(std::mem::take(
+            &mut current
+          ));
+          in_word = false;
+        }
+      }
+      | None => {
+        current.push(ch);
+        in_word = true;
+      }
+    }
+  }
+
+  if let Some(open) = quote {
+    return Err(anyhow!(
+      "unterminated {open} quote"
+    ));
+  }
+  if in_word {
+    words.push(current);
+  }
+  Ok(words)
+}
+
 #[derive(Debug, Clone)]
 pub struct Invocation {
-  pub filter_terms: Vec<String>,
-  pub command:      String,
-  pub command_args: Vec<String>
+  pub filter_terms:    Vec<String>,
+  pub command:         String,
+  pub command_args:    Vec<String>,
+  pub limit:           Option<RowLimit>,
+  /// `--width N` given after the
+  /// command, where clap no longer
+  /// sees it.
+  pub width:           Option<usize>,
+  pub dry_run:         bool,
+  /// Selectors a `-` term read from
+  /// stdin, each of which has to match
+  /// a task.
+  pub stdin_selectors: Vec<String>
 }
 
 impl Invocation {
@@ -222,9 +386,13 @@ impl Invocation {
         });
       debug!(command = %cmd, "no explicit command, using default");
       return Ok(Self {
-        filter_terms: vec![],
-        command:      cmd,
-        command_args: vec![]
+        filter_terms:    vec![],
+        command:         cmd,
+        command_args:    vec![],
+        limit:           None,
+        width:           None,
+        dry_run:         false,
+        stdin_selectors: Vec::new()
       });
     }
 
@@ -235,19 +403,23 @@ impl Invocation {
     {
       debug!(token = %tokens[0], "single numeric token interpreted as task info query");
       return Ok(Self {
-        filter_terms: vec![
+        filter_terms:    vec![
           tokens[0].clone(),
         ],
-        command:      "info"
+        command:         "info"
           .to_string(),
-        command_args: vec![]
+        command_args:    vec![],
+        limit:           None,
+        width:           None,
+        dry_run:         false,
+        stdin_selectors: Vec::new()
       });
     }
 
     let (
-      filter_terms,
+      mut filter_terms,
       command,
-      command_args
+      mut command_args
     ) = split_filter_command(
       cfg, &tokens
     );
@@ -255,6 +427,40 @@ impl Invocation {
     let report_commands =
       report_command_names(cfg);
 
+    let stdin_selectors =
+      expand_stdin_selectors(
+        &mut filter_terms,
+        read_stdin_selectors
+      )?;
+
+    let mut limit = take_limit_terms(
+      &mut filter_terms
+    )?;
+    let mut width = take_width_terms(
+      &mut filter_terms
+    )?;
+    if matches!(
+      command.as_str(),
+      "list" | "next" | "stale"
+    ) || report_commands
+      .contains(&command)
+    {
+      if let Some(arg_limit) =
+        take_limit_terms(
+          &mut command_args
+        )?
+      {
+        limit = Some(arg_limit);
+      }
+      if let Some(arg_width) =
+        take_width_terms(
+          &mut command_args
+        )?
+      {
+        width = Some(arg_width);
+      }
+    }
+
     if command == "next"
             && !tokens.is_empty()
             && !tokens.iter().any(|tok| {
@@ -278,11 +484,223 @@ impl Invocation {
     Ok(Self {
       filter_terms,
       command,
-      command_args
+      command_args,
+      limit,
+      width,
+      dry_run: false,
+      stdin_selectors
     })
   }
 }
 
+/// Largest `N-M` range accepted from
+/// stdin, so a typo can't expand into
+/// millions of ids.
+const MAX_STDIN_RANGE: u64 = 10_000;
+
+fn read_stdin_selectors()
+-> anyhow::Result<String> {
+  let mut stdin = std::io::stdin();
+  if stdin.is_terminal() {
+    return Err(anyhow!(
+      "'-' reads task selectors from \
+       stdin, but stdin is a terminal"
+    ));
+  }
+  let mut input = String::new();
+  stdin
+    .read_to_string(&mut input)
+    .map_err(|err| {
+      anyhow!(
+        "failed reading selectors \
+         from stdin: {err}"
+      )
+    })?;
+  Ok(input)
+}
+
+/// Splits piped selectors on
+/// whitespace and commas into filter
+/// terms (ids, `N-M` ranges expanded
+/// to ids, uuids and uuid prefixes),
+/// returning the unusable entries
+/// separately.
+fn parse_stdin_selectors(
+  input: &str
+) -> (Vec<String>, Vec<String>) {
+  let mut selectors = Vec::new();
+  let mut invalid = Vec::new();
+
+  for entry in input
+    .split(|ch: char| {
+      ch.is_whitespace() || ch == ','
+    })
+    .filter(|entry| !entry.is_empty())
+  {
+    if entry.parse::<u64>().is_ok()
+      || uuid::Uuid::parse_str(entry)
+        .is_ok()
+      || crate::filter::is_uuid_prefix(
+        entry
+      )
+    {
+      selectors.push(entry.to_string());
+      continue;
+    }
+    let range = entry
+      .split_once('-')
+      .and_then(|(start, end)| {
+        Some((
+          start.parse::<u64>().ok()?,
+          end.parse::<u64>().ok()?
+        ))
+      })
+      .filter(|(start, end)| {
+        start <= end
+          && end - start
+            < MAX_STDIN_RANGE
+      });
+    match range {
+      | Some((start, end)) => {
+        selectors.extend(
+          (start..=end)
+            .map(|id| id.to_string())
+        );
+      }
+      | None => {
+        invalid.push(entry.to_string())
+      }
+    }
+  }
+
+  (selectors, invalid)
+}
+
+/// Replaces a bare `-` filter term
+/// with the selectors piped on stdin,
+/// OR-ed together so the command
+/// applies to each of them, and returns
+/// those selectors. Invalid entries are
+/// reported and skipped.
+fn expand_stdin_selectors(
+  terms: &mut Vec<String>,
+  read: impl FnOnce() -> anyhow::Result<
+    String
+  >
+) -> anyhow::Result<Vec<String>> {
+  if !terms
+    .iter()
+    .any(|term| term == "-")
+  {
+    return Ok(Vec::new());
+  }
+
+  let (selectors, invalid) =
+    parse_stdin_selectors(&read()?);
+  for entry in &invalid {
+    eprintln!(
+      "Warning: ignoring invalid task \
+       selector '{entry}' from stdin."
+    );
+  }
+  if selectors.is_empty() {
+    return Err(anyhow!(
+      "no valid task selectors on \
+       stdin"
+    ));
+  }
+  debug!(
+    count = selectors.len(),
+    invalid = invalid.len(),
+    "expanded stdin selectors"
+  );
+
+  let mut group = vec!["(".to_string()];
+  for (idx, selector) in
+    selectors.iter().enumerate()
+  {
+    if idx > 0 {
+      group.push("or".to_string());
+    }
+    group.push(selector.clone());
+  }
+  group.push(")".to_string());
+
+  *terms = std::mem::take(terms)
+    .into_iter()
+    .flat_map(|term| {
+      if term == "-" {
+        group.clone()
+      } else {
+        vec![term]
+      }
+    })
+    .collect();
+  Ok(selectors)
+}
+
+/// Removes `limit:` terms, returning
+/// the last one.
+fn take_limit_terms(
+  terms: &mut Vec<String>
+) -> anyhow::Result<Option<RowLimit>> {
+  let mut limit = None;
+  let mut kept =
+    Vec::with_capacity(terms.len());
+  for term in terms.drain(..) {
+    if let Some(value) =
+      term.strip_prefix("limit:")
+    {
+      limit =
+        Some(RowLimit::parse(value)?);
+    } else {
+      kept.push(term);
+    }
+  }
+  *terms = kept;
+  Ok(limit)
+}
+
+/// Removes `--width N` and
+/// `--width=N`, returning the last
+/// value.
+fn take_width_terms(
+  terms: &mut Vec<String>
+) -> anyhow::Result<Option<usize>> {
+  let mut width = None;
+  let mut kept =
+    Vec::with_capacity(terms.len());
+  let mut iter = terms.drain(..);
+  while let Some(term) = iter.next() {
+    let raw = if term == "--width" {
+      iter.next().ok_or_else(|| {
+        anyhow!("--width needs a value")
+      })?
+    } else if let Some(value) =
+      term.strip_prefix("--width=")
+    {
+      value.to_string()
+    } else {
+      kept.push(term);
+      continue;
+    };
+    width = Some(
+      raw.parse::<usize>().map_err(
+        |_| {
+          anyhow!(
+            "invalid --width: {raw} \
+             (expected a number of \
+             columns)"
+          )
+        }
+      )?
+    );
+  }
+  drop(iter);
+  *terms = kept;
+  Ok(width)
+}
+
 fn split_filter_command(
   cfg: &Config,
   tokens: &[String]
@@ -291,9 +709,48 @@ fn split_filter_command(
   let known = crate::commands::known_command_names();
   let report_commands =
     report_command_names(cfg);
+  let capture_verb =
+    inbox_command_name(cfg);
 
   for i in 0..tokens.len() {
     let token = tokens[i].as_str();
+    if !known.contains(&token)
+      && capture_verb.as_deref()
+        == Some(token)
+    {
+      debug!(
+          token = %token,
+          split_index = i,
+          "resolved capture verb"
+      );
+      return (
+        tokens[..i].to_vec(),
+        "inbox".to_string(),
+        tokens[i + 1..].to_vec()
+      );
+    }
+
+    // An exact report name beats a
+    // built-in prefix, so
+    // `report.b.columns` keeps `task b`
+    // a report.
+    if !known.contains(&token)
+      && report_commands
+        .iter()
+        .any(|name| name == token)
+    {
+      debug!(
+          token = %token,
+          split_index = i,
+          "resolved report name"
+      );
+      return (
+        tokens[..i].to_vec(),
+        token.to_string(),
+        tokens[i + 1..].to_vec()
+      );
+    }
+
     if let Some(full) = crate::commands::expand_command_abbrev(token, &known) {
             debug!(
                 token = %token,
@@ -335,6 +792,21 @@ fn split_filter_command(
   )
 }
 
+/// Extra verb for `inbox` set by
+/// `inbox.command` (e.g. `in`). It only
+/// matches exactly and never shadows a
+/// built-in command.
+fn inbox_command_name(
+  cfg: &Config
+) -> Option<String> {
+  cfg
+    .get("inbox.command")
+    .map(|value| {
+      value.trim().to_string()
+    })
+    .filter(|value| !value.is_empty())
+}
+
 fn report_command_names(
   cfg: &Config
 ) -> Vec<String> {
@@ -381,3 +853,164 @@ fn expand_report_abbrev(
     Some(first)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    RowLimit,
+    expand_stdin_selectors,
+    parse_stdin_selectors,
+    split_command_line,
+    take_limit_terms,
+    take_width_terms
+  };
+
+  #[test]
+  fn limit_terms_are_extracted_from_filters()
+   {
+    let mut terms = vec![
+      "+home".to_string(),
+      "limit:5".to_string(),
+      "project:rivet".to_string(),
+    ];
+    let limit =
+      take_limit_terms(&mut terms)
+        .expect("valid limit");
+    assert_eq!(
+      limit,
+      Some(RowLimit::Rows(5))
+    );
+    assert_eq!(terms, vec![
+      "+home".to_string(),
+      "project:rivet".to_string()
+    ]);
+
+    assert_eq!(
+      RowLimit::parse("0")
+        .expect("zero")
+        .max_rows(),
+      None
+    );
+    assert_eq!(
+      RowLimit::parse("page")
+        .expect("page"),
+      RowLimit::Page
+    );
+    assert!(
+      RowLimit::parse("lots").is_err()
+    );
+  }
+
+  #[test]
+  fn width_terms_are_extracted_in_either_form()
+   {
+    let mut terms = vec![
+      "+home".to_string(),
+      "--width".to_string(),
+      "40".to_string(),
+      "--width=60".to_string(),
+    ];
+    assert_eq!(
+      take_width_terms(&mut terms)
+        .expect("valid width"),
+      Some(60)
+    );
+    assert_eq!(terms, vec!["+home"]);
+
+    assert!(
+      take_width_terms(&mut vec![
+        "--width".to_string()
+      ])
+      .is_err()
+    );
+    assert!(
+      take_width_terms(&mut vec![
+        "--width=wide".to_string()
+      ])
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn command_lines_split_on_whitespace_and_quotes()
+   {
+    let words = split_command_line(
+      "  Call \"the bank\" \
+       project:home +'phone call' ''"
+    )
+    .expect("line splits");
+    assert_eq!(words, vec![
+      "Call",
+      "the bank",
+      "project:home",
+      "+phone call",
+      ""
+    ]);
+
+    assert!(
+      split_command_line(
+        "unterminated \"quote"
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn stdin_selectors_expand_into_an_or_group()
+   {
+    let (selectors, invalid) =
+      parse_stdin_selectors(
+        "1-3,7\n\
+         b2005bdd-0000-4000-8000-\
+         000000000001 abc1 nope 9-2\n"
+      );
+    assert_eq!(selectors, vec![
+      "1",
+      "2",
+      "3",
+      "7",
+      "b2005bdd-0000-4000-8000-\
+       000000000001",
+      "abc1"
+    ]);
+    assert_eq!(invalid, vec![
+      "nope", "9-2"
+    ]);
+
+    let mut terms = vec![
+      "+urgent".to_string(),
+      "-".to_string(),
+    ];
+    assert_eq!(
+      expand_stdin_selectors(
+        &mut terms,
+        || Ok("4 5 bad".to_string())
+      )
+      .expect("valid selectors"),
+      vec!["4", "5"]
+    );
+    assert_eq!(terms, vec![
+      "+urgent", "(", "4", "or", "5",
+      ")"
+    ]);
+
+    let mut untouched =
+      vec!["-home".to_string()];
+    expand_stdin_selectors(
+      &mut untouched,
+      || panic!("stdin not read")
+    )
+    .expect("no stdin selector");
+    assert_eq!(untouched, vec![
+      "-home"
+    ]);
+
+    assert!(
+      expand_stdin_selectors(
+        &mut vec!["-".to_string()],
+        || Ok("bad".to_string())
+      )
+      .is_err()
+    );
+  }
+}
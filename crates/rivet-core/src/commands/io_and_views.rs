@@ -4,19 +4,544 @@ fn cmd_undo(
 ) -> anyhow::Result<()> {
   info!("command undo");
 
-  let Some((pending, completed)) =
-    store.pop_undo_snapshot()?
-  else {
+  if !store.undo()? {
     println!(
       "No undo transactions available."
     );
     return Ok(());
-  };
+  }
+
+  println!("Undo completed.");
+  Ok(())
+}
+
+/// Reapplies what the last `undo`
+/// rolled back. Any other write since
+/// then clears the redo stack.
+#[instrument(skip(store))]
+fn cmd_redo(
+  store: &mut DataStore
+) -> anyhow::Result<()> {
+  info!("command redo");
+
+  if !store.redo()? {
+    println!(
+      "No redo transactions available."
+    );
+    return Ok(());
+  }
+
+  println!("Redo completed.");
+  Ok(())
+}
+
+/// Drops soft-deleted tasks for good,
+/// together with their audit history.
+/// Nothing here is undoable.
+#[instrument(skip(store))]
+fn cmd_purge(
+  store: &mut DataStore
+) -> anyhow::Result<()> {
+  info!("command purge");
+
+  let purged: BTreeSet<uuid::Uuid> =
+    store
+      .load_pending()?
+      .iter()
+      .filter(|task| {
+        task.status == Status::Deleted
+      })
+      .map(|task| task.uuid)
+      .collect();
+  if !purged.is_empty() {
+    store.purge_deleted()?;
+  }
+  let pruned =
+    store.prune_history(|entry| {
+      !purged.contains(&entry.uuid)
+    })?;
+
+  println!(
+    "Purged {} deleted task(s) and \
+     {pruned} history entr{}.",
+    purged.len(),
+    if pruned == 1 {
+      "y"
+    } else {
+      "ies"
+    }
+  );
+  Ok(())
+}
+
+/// `task gc` renumbers ids like the
+/// `gc` setting does before reports,
+/// whatever that setting says, then
+/// applies `auto_archive.days` when it
+/// is configured.
+#[instrument(skip(store, cfg))]
+fn cmd_gc(
+  store: &mut DataStore,
+  cfg: &Config,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command gc");
+
+  if !args.is_empty() {
+    return Err(usage_error(anyhow!(
+      "usage: task gc"
+    )));
+  }
+  let renumbered = store.compact_ids()?;
+  println!(
+    "{}",
+    if renumbered {
+      "Renumbered task ids."
+    } else {
+      "Task ids already compact."
+    }
+  );
+  match archive::auto_archive_days(cfg)
+    .map_err(usage_error)?
+  {
+    | Some(days) if store.is_dry_run() => {
+      let mut completed =
+        store.load_completed()?;
+      println!(
+        "Would archive {} completed \
+         task(s) older than {days} \
+         day(s).",
+        archive::archive_expired(
+          &mut completed,
+          days,
+          now
+        )
+      );
+    }
+    | Some(days) => {
+      println!(
+        "Archived {} completed task(s) \
+         older than {days} day(s).",
+        archive::run(store, days, now)?
+      );
+    }
+    | None => {}
+  }
+  Ok(())
+}
+
+/// `task demo clear` removes every
+/// task seeded by `seed_demo=on`,
+/// wherever it ended up. Undoable.
+#[instrument(skip(store))]
+fn cmd_demo(
+  store: &mut DataStore,
+  args: &[String]
+) -> anyhow::Result<()> {
+  info!("command demo");
 
+  if args != ["clear"] {
+    return Err(usage_error(anyhow!(
+      "usage: task demo clear"
+    )));
+  }
+  let mut pending = store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let before =
+    (pending.clone(), completed.clone());
+  let removed = demo::remove_demo(
+    &mut pending,
+    &mut completed
+  );
+  if removed.is_empty() {
+    println!("No demo tasks to clear.");
+    return Ok(());
+  }
+  store
+    .push_undo_snapshot(&before.0, &before.1)?;
   store.save_pending(&pending)?;
   store.save_completed(&completed)?;
+  println!(
+    "Cleared {} demo task(s).",
+    removed.len()
+  );
+  Ok(())
+}
 
-  println!("Undo completed.");
+fn cmd_backup(
+  store: &DataStore,
+  cfg: &Config,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command backup");
+
+  let dest_dir = match args {
+    | [] => {
+      backup::default_backup_dir(
+        &store.data_dir
+      )
+    }
+    | [dir] => PathBuf::from(dir),
+    | _ => {
+      return Err(usage_error(anyhow!(
+        "usage: task backup [dir]"
+      )));
+    }
+  };
+  if store.is_dry_run() {
+    println!(
+      "Would write a snapshot of {} to \
+       {}.",
+      store.data_dir.display(),
+      dest_dir.display()
+    );
+    return Ok(());
+  }
+
+  let summary = backup::write_snapshot(
+    &store.data_dir,
+    cfg
+      .loaded_files
+      .first()
+      .map(PathBuf::as_path),
+    &dest_dir,
+    "rivet",
+    now
+  )?;
+  println!(
+    "Wrote {} ({} file(s), {} bytes, \
+     store format {}).",
+    summary.path.display(),
+    summary.header.files.len(),
+    summary.header.total_bytes(),
+    summary.header.store_format_version
+  );
+  Ok(())
+}
+
+const CONFIG_USAGE: &str =
+  "usage: task config export <file> | \
+   task config import <file> \
+   [--on-conflict=ask|overwrite|keep]";
+
+/// `config export <file>` writes the
+/// taskrc, `rivet.toml` and tag schema
+/// into one bundle; `config import
+/// <file>` puts them back, copying
+/// each file it replaces to a `.bak`
+/// first. Files that exist with other
+/// contents are asked about on a
+/// terminal, or settled by
+/// `--on-conflict`.
+fn cmd_config(
+  store: &DataStore,
+  cfg: &Config,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command config");
+
+  let targets =
+    config_bundle::cli_targets(cfg);
+  match args {
+    | [action, path]
+      if action == "export" =>
+    {
+      let bundle = ConfigBundle::collect(
+        &targets, now
+      )?;
+      if bundle.files.is_empty() {
+        return Err(anyhow!(
+          "config export: no config \
+           files found"
+        ));
+      }
+      let names = bundle
+        .files
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+      if store.is_dry_run() {
+        println!(
+          "Would export {names} to \
+           {path}."
+        );
+        return Ok(());
+      }
+      std::fs::write(
+        path,
+        bundle.to_json()?
+      )
+      .with_context(|| {
+        format!("failed to write {path}")
+      })?;
+      println!(
+        "Exported {names} to {path}."
+      );
+      Ok(())
+    }
+    | [action, path, rest @ ..]
+      if action == "import" =>
+    {
+      let mut policy =
+        ConflictPolicy::default();
+      for (_, value) in parse_flag_args(
+        "config import",
+        rest,
+        &["--on-conflict"]
+      )? {
+        policy =
+          ConflictPolicy::parse(&value)
+            .map_err(usage_error)?;
+      }
+      let raw = std::fs::read_to_string(
+        path
+      )
+      .with_context(|| {
+        format!("failed to read {path}")
+      })?;
+      let bundle =
+        ConfigBundle::parse(&raw)
+          .with_context(|| {
+            format!(
+              "config import: {path}"
+            )
+          })?;
+      let changes =
+        bundle.plan(&targets)?;
+      for key in bundle.files.keys() {
+        if !changes.iter().any(|change| {
+          change.file.key() == key
+        }) {
+          eprintln!(
+            "Warning: no location for \
+             the bundled {key}; \
+             skipped."
+          );
+        }
+      }
+
+      let conflicts: Vec<String> =
+        changes
+          .iter()
+          .filter(|change| {
+            change.kind
+              == ChangeKind::Conflict
+          })
+          .map(|change| {
+            change
+              .path
+              .display()
+              .to_string()
+          })
+          .collect();
+      if store.is_dry_run() {
+        for change in &changes {
+          println!(
+            "Would {} {}.",
+            match change.kind {
+              | ChangeKind::New => "write",
+              | ChangeKind::Unchanged => {
+                "leave unchanged"
+              }
+              | ChangeKind::Conflict => {
+                match policy {
+                  | ConflictPolicy::Keep => {
+                    "keep"
+                  }
+                  | _ => "replace"
+                }
+              }
+            },
+            change.path.display()
+          );
+        }
+        return Ok(());
+      }
+      if policy == ConflictPolicy::Ask
+        && !conflicts.is_empty()
+        && !io::stdin().is_terminal()
+      {
+        return Err(CommandError::usage(
+          format!(
+            "config import: these files \
+             differ from the bundle: {}; \
+             pass --on-conflict=overwrite \
+             or --on-conflict=keep",
+            conflicts.join(", ")
+          )
+        ));
+      }
+
+      let report = config_bundle::apply(
+        &changes,
+        now,
+        |change| {
+          match policy {
+            | ConflictPolicy::Overwrite => {
+              Ok(true)
+            }
+            | ConflictPolicy::Keep => {
+              Ok(false)
+            }
+            | ConflictPolicy::Ask => {
+              confirm_config_replace(
+                change
+              )
+            }
+          }
+        }
+      )?;
+      for (original, copy) in
+        &report.backups
+      {
+        println!(
+          "Backed up {} to {}.",
+          original.display(),
+          copy.display()
+        );
+      }
+      for path in &report.written {
+        println!(
+          "Wrote {}.",
+          path.display()
+        );
+      }
+      for path in &report.kept {
+        println!(
+          "Kept {} (differs from the \
+           bundle).",
+          path.display()
+        );
+      }
+      for path in &report.unchanged {
+        println!(
+          "Unchanged {}.",
+          path.display()
+        );
+      }
+      if bundle.gui.is_some() {
+        println!(
+          "The bundle's kanban boards \
+           and external calendars are \
+           applied from the GUI \
+           (Settings > Import \
+           Settings)."
+        );
+      }
+      Ok(())
+    }
+    | _ => {
+      Err(CommandError::usage(
+        CONFIG_USAGE
+      ))
+    }
+  }
+}
+
+fn confirm_config_replace(
+  change: &BundleChange
+) -> anyhow::Result<bool> {
+  print!(
+    "Replace {} with the bundled {}? \
+     [y/N] ",
+    change.path.display(),
+    change.file.key()
+  );
+  io::stdout().flush()?;
+  let mut answer = String::new();
+  io::stdin().read_line(&mut answer)?;
+  Ok(matches!(
+    answer
+      .trim()
+      .to_ascii_lowercase()
+      .as_str(),
+    "y" | "yes"
+  ))
+}
+
+fn cmd_restore(
+  store: &mut DataStore,
+  cfg: &Config,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command restore");
+
+  let [snapshot] = args else {
+    return Err(usage_error(anyhow!(
+      "usage: task restore <snapshot>"
+    )));
+  };
+  let snapshot = PathBuf::from(snapshot);
+  if store.is_dry_run() {
+    let header =
+      backup::read_snapshot_header(
+        &snapshot
+      )?;
+    header.check_restorable()?;
+    println!(
+      "Would restore {} file(s) from \
+       {} (taken {}, store format {}).",
+      header.files.len(),
+      snapshot.display(),
+      header.created
+        .to_rfc3339_opts(
+          chrono::SecondsFormat::Secs,
+          true
+        ),
+      header.store_format_version
+    );
+    return Ok(());
+  }
+
+  let report = backup::restore_snapshot(
+    &snapshot,
+    &store.data_dir,
+    cfg
+      .loaded_files
+      .first()
+      .map(PathBuf::as_path),
+    now
+  )?;
+  store.invalidate_cache();
+  println!(
+    "Restored {} file(s) from {} \
+     (taken {}); removed {} file(s) \
+     the snapshot did not have.",
+    report.restored,
+    snapshot.display(),
+    report.header.created
+        .to_rfc3339_opts(
+          chrono::SecondsFormat::Secs,
+          true
+        ),
+    report.removed
+  );
+  println!(
+    "The previous state was saved to \
+     {}.",
+    report.safety.display()
+  );
+  if report.skipped_taskrc {
+    println!(
+      "The snapshot includes a taskrc, \
+       but none is loaded; it was not \
+       restored."
+    );
+  }
+  if let Some(version) =
+    report.migrated_to
+  {
+    println!(
+      "Migrated the restored data to \
+       store format {version}."
+    );
+  }
   Ok(())
 }
 
@@ -27,11 +552,15 @@ fn cmd_undo(
 ))]
 fn cmd_export(
   store: &mut DataStore,
+  cfg: &Config,
   filter_terms: &[String],
+  args: &[String],
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
   info!("command export");
 
+  let (format, columns) =
+    parse_export_args(args, cfg)?;
   let pending = store.load_pending()?;
   let completed =
     store.load_completed()?;
@@ -47,14 +576,247 @@ fn cmd_export(
           task, now
         )
     })
+    .map(Task::into_export)
     .collect();
 
-  let out =
-    serde_json::to_string(&rows)?;
-  println!("{out}");
+  let mut out = io::BufWriter::new(
+    io::stdout().lock()
+  );
+  match format {
+    | ExportFormat::Json => {
+      if let Some(columns) = &columns {
+        let projected = rows
+          .iter()
+          .map(|task| {
+            columns.project(task)
+          })
+          .collect::<anyhow::Result<
+            Vec<_>
+          >>()?;
+        serde_json::to_writer(
+          &mut out,
+          &projected
+        )?;
+      } else {
+        serde_json::to_writer(
+          &mut out, &rows
+        )?;
+      }
+      writeln!(out)?;
+    }
+    | ExportFormat::Delimited(
+      delimited
+    ) => {
+      let columns = columns
+        .unwrap_or_else(
+          ExportColumns::attributes
+        );
+      delimited.write_record(
+        &mut out,
+        columns.columns()
+      )?;
+      for task in &rows {
+        let projected =
+          columns.project(task)?;
+        let cells: Vec<String> = columns
+          .columns()
+          .iter()
+          .map(|column| {
+            projected
+              .get(column)
+              .map(delimited_cell)
+              .unwrap_or_default()
+          })
+          .collect();
+        delimited.write_record(
+          &mut out, &cells
+        )?;
+      }
+    }
+    | ExportFormat::Command => {
+      for task in &rows {
+        writeln!(
+          out,
+          "{}",
+          task.to_add_command()
+        )?;
+      }
+    }
+  }
+  out.flush()?;
   Ok(())
 }
 
+#[derive(Debug, Clone, Copy)]
+enum ExportFormat {
+  Json,
+  Command,
+  /// `csv` or `tsv`, with any
+  /// `--delimiter`/`--quote` applied.
+  Delimited(Delimited)
+}
+
+/// `--flag=value` or `--flag value`
+/// pairs for `export` and `import`,
+/// rejecting flags outside `allowed`.
+fn parse_flag_args<'a>(
+  command: &str,
+  args: &'a [String],
+  allowed: &[&str]
+) -> anyhow::Result<Vec<(&'a str, String)>>
+{
+  let mut flags = Vec::new();
+  let mut iter = args.iter();
+  while let Some(arg) = iter.next() {
+    let (flag, inline) =
+      match arg.split_once('=') {
+        | Some((flag, value)) => {
+          (flag, Some(value.to_string()))
+        }
+        | None => (arg.as_str(), None)
+      };
+    if !allowed.contains(&flag) {
+      return Err(CommandError::usage(
+        format!(
+          "{command}: unexpected \
+           argument: {arg}"
+        )
+      ));
+    }
+    let value = match inline {
+      | Some(value) => value,
+      | None => {
+        iter.next().cloned().ok_or_else(
+          || {
+            CommandError::usage(format!(
+              "{command}: {flag} \
+               requires a value"
+            ))
+          }
+        )?
+      }
+    };
+    flags.push((flag, value));
+  }
+  Ok(flags)
+}
+
+/// `--format`, `--columns`,
+/// `--delimiter` and `--quote`.
+/// Columns apply to JSON and the
+/// delimited formats; delimiter and
+/// quoting only to `csv`/`tsv`.
+fn parse_export_args(
+  args: &[String],
+  cfg: &Config
+) -> anyhow::Result<(
+  ExportFormat,
+  Option<ExportColumns>
+)> {
+  let mut format = "json".to_string();
+  let mut columns = None;
+  let mut delimiter = None;
+  let mut quote = None;
+  for (flag, value) in parse_flag_args(
+    "export",
+    args,
+    &[
+      "--format",
+      "--columns",
+      "--delimiter",
+      "--quote"
+    ]
+  )? {
+    let usage = |err: anyhow::Error| {
+      CommandError::usage(format!(
+        "export: {err}"
+      ))
+    };
+    match flag {
+      | "--columns" => {
+        columns = Some(
+          ExportColumns::parse(
+            &value, cfg
+          )
+          .map_err(usage)?
+        );
+      }
+      | "--delimiter" => {
+        delimiter = Some(
+          Delimited::parse_delimiter(
+            &value
+          )
+          .map_err(usage)?
+        );
+      }
+      | "--quote" => {
+        quote = Some(
+          QuotePolicy::parse(&value)
+            .map_err(usage)?
+        );
+      }
+      | _ => {
+        format =
+          value.to_ascii_lowercase();
+      }
+    }
+  }
+
+  let format = match format.as_str() {
+    | "json" => ExportFormat::Json,
+    | "command" => ExportFormat::Command,
+    | "csv" | "tsv" => {
+      let default = if format == "csv" {
+        ','
+      } else {
+        '\t'
+      };
+      ExportFormat::Delimited(
+        Delimited {
+          delimiter: delimiter
+            .unwrap_or(default),
+          quote:     quote
+            .unwrap_or_default()
+        }
+      )
+    }
+    | other => {
+      return Err(CommandError::usage(
+        format!(
+          "export: unknown format \
+           '{other}' (expected json, \
+           command, csv or tsv)"
+        )
+      ));
+    }
+  };
+  if (delimiter.is_some()
+    || quote.is_some())
+    && !matches!(
+      format,
+      ExportFormat::Delimited(_)
+    )
+  {
+    return Err(CommandError::usage(
+      "export: --delimiter and --quote \
+       apply only to --format=csv or \
+       tsv"
+    ));
+  }
+  if columns.is_some()
+    && matches!(
+      format,
+      ExportFormat::Command
+    )
+  {
+    return Err(CommandError::usage(
+      "export: --columns does not \
+       apply to --format=command"
+    ));
+  }
+  Ok((format, columns))
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct ImportTask {
   #[serde(default)]
@@ -109,18 +871,30 @@ struct ImportTask {
   #[serde(default)]
   depends:     Vec<uuid::Uuid>,
   #[serde(default)]
+  parent:      Option<uuid::Uuid>,
+  #[serde(default)]
+  contact:     Option<uuid::Uuid>,
+  #[serde(default)]
   annotations: Vec<Annotation>,
   #[serde(flatten)]
   extra:       BTreeMap<String, Value>
 }
 
-#[instrument(skip(store, hooks))]
+#[instrument(skip(
+  store, cfg, args, hooks
+))]
 fn cmd_import(
   store: &mut DataStore,
+  cfg: &Config,
+  args: &[String],
   hooks: &HookRunner
 ) -> anyhow::Result<()> {
   info!("command import");
   let now = Utc::now();
+  let limit =
+    DescriptionLimit::from_config(cfg)
+      .map_err(usage_error)?;
+  let format = parse_import_args(args)?;
 
   let mut stdin = String::new();
   io::stdin()
@@ -142,8 +916,28 @@ fn cmd_import(
   let completed_before =
     completed.clone();
 
-  let imported =
-    parse_import_items(trimmed)?;
+  let imported = match format {
+    | ImportFormat::Json => {
+      parse_import_items(trimmed)?
+    }
+    | ImportFormat::Delimited(
+      delimiter
+    ) => {
+      parse_delimited_import(
+        trimmed, delimiter, cfg
+      )?
+    }
+    | ImportFormat::Auto
+      if trimmed.starts_with(['[', '{']) =>
+    {
+      parse_import_items(trimmed)?
+    }
+    | ImportFormat::Auto => {
+      parse_delimited_import(
+        trimmed, None, cfg
+      )?
+    }
+  };
   let mut adds = 0_u64;
   let mut mods = 0_u64;
 
@@ -156,6 +950,9 @@ fn cmd_import(
       });
     let mut task =
       normalize_import_item(row, now);
+    enforce_description_limit(
+      &limit, &mut task, now
+    )?;
     normalize_import_identity_and_status(&mut task, existing.as_ref(), store.next_id(&pending));
 
     if let Some(old) = existing.as_ref()
@@ -194,42 +991,256 @@ fn cmd_import(
   Ok(())
 }
 
-fn parse_import_items(
-  trimmed: &str
-) -> anyhow::Result<Vec<ImportTask>> {
-  if trimmed.starts_with('[') {
-    return serde_json::from_str(
-      trimmed
-    )
-    .context(
-      "failed parsing JSON array"
-    );
-  }
+#[derive(Debug, Clone, Copy)]
+enum ImportFormat {
+  /// JSON when the input starts with
+  /// `[` or `{`, otherwise delimited
+  /// with a detected delimiter.
+  Auto,
+  Json,
+  /// `None` detects the delimiter from
+  /// the header line.
+  Delimited(Option<char>)
+}
 
-  if trimmed.starts_with('{') {
-    if let Ok(item) =
-      serde_json::from_str::<ImportTask>(
-        trimmed
-      )
+/// `--format=json|csv|tsv` and
+/// `--delimiter`; a delimiter alone
+/// implies delimited input.
+fn parse_import_args(
+  args: &[String]
+) -> anyhow::Result<ImportFormat> {
+  let mut format = None;
+  let mut delimiter = None;
+  for (flag, value) in parse_flag_args(
+    "import",
+    args,
+    &["--format", "--delimiter"]
+  )? {
+    if flag == "--delimiter" {
+      delimiter = Some(
+        Delimited::parse_delimiter(
+          &value
+        )
+        .map_err(|err| {
+          CommandError::usage(format!(
+            "import: {err}"
+          ))
+        })?
+      );
+    } else {
+      format =
+        Some(value.to_ascii_lowercase());
+    }
+  }
+  match format.as_deref() {
+    | None if delimiter.is_some() => {
+      Ok(ImportFormat::Delimited(
+        delimiter
+      ))
+    }
+    | None => Ok(ImportFormat::Auto),
+    | Some("json")
+      if delimiter.is_none() =>
     {
-      return Ok(vec![item]);
+      Ok(ImportFormat::Json)
+    }
+    | Some("json") => {
+      Err(CommandError::usage(
+        "import: --delimiter does not \
+         apply to --format=json"
+      ))
+    }
+    | Some("csv") => {
+      Ok(ImportFormat::Delimited(Some(
+        delimiter.unwrap_or(',')
+      )))
+    }
+    | Some("tsv") => {
+      Ok(ImportFormat::Delimited(Some(
+        delimiter.unwrap_or('\t')
+      )))
     }
+    | Some(other) => {
+      Err(CommandError::usage(format!(
+        "import: unknown format \
+         '{other}' (expected json, csv \
+         or tsv)"
+      )))
+    }
+  }
+}
+
+/// CSV/TSV rows keyed by a header line
+/// of attribute or UDA names, as
+/// `export --format=csv` writes them.
+/// Empty cells are left unset, `tags`
+/// and `depends` split on whitespace,
+/// `annotations` hold their JSON and
+/// UDAs typed `numeric` become
+/// numbers; everything else is taken
+/// as the string an export would
+/// carry.
+fn parse_delimited_import(
+  trimmed: &str,
+  delimiter: Option<char>,
+  cfg: &Config
+) -> anyhow::Result<Vec<ImportTask>> {
+  let delimited = Delimited::new(
+    delimiter.unwrap_or_else(|| {
+      Delimited::detect(trimmed)
+    })
+  );
+  let mut records = delimited
+    .parse_records(trimmed)
+    .context("import")?
+    .into_iter();
+  let header: Vec<String> = records
+    .next()
+    .unwrap_or_default()
+    .into_iter()
+    .map(|name| name.trim().to_string())
+    .collect();
+  if header.iter().any(String::is_empty)
+  {
+    return Err(anyhow!(
+      "import: empty column name in \
+       header"
+    ));
   }
 
   let mut out = Vec::new();
-  for (idx, line) in
-    trimmed.lines().enumerate()
+  for (idx, record) in records.enumerate()
   {
-    let token = line.trim();
-    if token.is_empty() {
-      continue;
+    let row = idx + 2;
+    if record.len() != header.len() {
+      return Err(anyhow!(
+        "import: row {row} has {} \
+         field(s), header has {}",
+        record.len(),
+        header.len()
+      ));
     }
-    let item: ImportTask =
-      serde_json::from_str(token)
-        .with_context(|| {
-          format!(
-            "failed parsing import \
-             line {}",
+    let mut object = Map::new();
+    for (column, cell) in
+      header.iter().zip(record)
+    {
+      if cell.is_empty() {
+        continue;
+      }
+      let kind = TASK_ATTRIBUTES
+        .iter()
+        .find(|(name, _)| name == column)
+        .map(|(_, kind)| *kind);
+      let value = match (
+        column.as_str(),
+        kind
+      ) {
+        | ("annotations", _) => {
+          serde_json::from_str(&cell)
+            .with_context(|| {
+              format!(
+                "import: row {row}: \
+                 annotations must be \
+                 JSON"
+              )
+            })?
+        }
+        | (_, Some("list")) => {
+          Value::from(
+            cell
+              .split_whitespace()
+              .collect::<Vec<_>>()
+          )
+        }
+        | (_, Some(_)) => {
+          Value::from(cell)
+        }
+        | (name, None)
+          if cfg
+            .get(&format!(
+              "uda.{name}.type"
+            ))
+            .is_some_and(|kind| {
+              kind
+                .trim()
+                .eq_ignore_ascii_case(
+                  "numeric"
+                )
+            }) =>
+        {
+          serde_json::from_str::<Value>(
+            &cell
+          )
+          .ok()
+          .filter(Value::is_number)
+          .ok_or_else(|| {
+            anyhow!(
+              "import: row {row}: \
+               {name} must be numeric, \
+               got '{cell}'"
+            )
+          })?
+        }
+        | (_, None) => Value::from(cell)
+      };
+      object
+        .insert(column.clone(), value);
+    }
+    let item = serde_json::from_value(
+      Value::Object(object)
+    )
+    .with_context(|| {
+      format!(
+        "failed parsing import row \
+         {row}"
+      )
+    })?;
+    out.push(item);
+  }
+
+  if out.is_empty() {
+    return Err(anyhow!(
+      "import: empty input"
+    ));
+  }
+  Ok(out)
+}
+
+fn parse_import_items(
+  trimmed: &str
+) -> anyhow::Result<Vec<ImportTask>> {
+  if trimmed.starts_with('[') {
+    return serde_json::from_str(
+      trimmed
+    )
+    .context(
+      "failed parsing JSON array"
+    );
+  }
+
+  if trimmed.starts_with('{')
+    && let Ok(item) =
+      serde_json::from_str::<ImportTask>(
+        trimmed
+      )
+  {
+    return Ok(vec![item]);
+  }
+
+  let mut out = Vec::new();
+  for (idx, line) in
+    trimmed.lines().enumerate()
+  {
+    let token = line.trim();
+    if token.is_empty() {
+      continue;
+    }
+    let item: ImportTask =
+      serde_json::from_str(token)
+        .with_context(|| {
+          format!(
+            "failed parsing import \
+             line {}",
             idx + 1
           )
         })?;
@@ -255,6 +1266,11 @@ fn normalize_import_item(
   let entry = item.entry.unwrap_or(now);
   let modified =
     item.modified.unwrap_or(now);
+  // Exports carry the display id, which
+  // is reassigned below; left in extra
+  // it would be written twice.
+  let mut extra = item.extra;
+  extra.remove("id");
   let mut task = Task {
     uuid: item.uuid.unwrap_or_else(
       uuid::Uuid::new_v4
@@ -275,9 +1291,12 @@ fn normalize_import_item(
     scheduled: item.scheduled,
     wait: item.wait,
     depends: item.depends,
+    parent: item.parent,
+    contact: item.contact,
     annotations: item.annotations,
-    extra: item.extra
+    extra
   };
+  task.restore_archived_from_export();
   normalize_import_status(&mut task);
   task
 }
@@ -299,7 +1318,8 @@ fn normalize_import_status(
         task.end = Some(task.modified);
       }
     }
-    | Status::Waiting => {}
+    | Status::Waiting
+    | Status::Archived => {}
   }
 }
 
@@ -321,7 +1341,8 @@ fn normalize_import_identity_and_status(
         .or(Some(next_id));
     }
     | Status::Completed
-    | Status::Deleted => {
+    | Status::Deleted
+    | Status::Archived => {
       task.id = None;
     }
     | Status::Waiting => {}
@@ -363,7 +1384,8 @@ fn upsert_imported_task(
   });
 
   match task.status {
-    | Status::Completed => {
+    | Status::Completed
+    | Status::Archived => {
       completed.push(task)
     }
     | Status::Pending
@@ -400,10 +1422,191 @@ fn cmd_projects(
   Ok(())
 }
 
+/// Month grid of open tasks' due dates
+/// in the project timezone. The week
+/// starts on `weekstart` (Monday by
+/// default).
+#[instrument(skip(
+  store,
+  cfg,
+  renderer,
+  filter_terms,
+  args,
+  now
+))]
+fn cmd_calendar(
+  store: &mut DataStore,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let today = to_project_date(now);
+  let months =
+    calendar_months(args, today)?;
+  let locale = Locale::configured(cfg);
+  let week_start =
+    configured_week_start(cfg)?;
+  info!(
+    months = months.len(),
+    "command calendar"
+  );
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let mut due_counts = BTreeMap::new();
+  for task in store.load_pending()? {
+    if !matches!(
+      task.status,
+      Status::Pending | Status::Waiting
+    ) || !filter.matches(&task, now)
+    {
+      continue;
+    }
+    if let Some(due) = task.due {
+      *due_counts
+        .entry(to_project_date(due))
+        .or_insert(0) += 1;
+    }
+  }
+
+  renderer.print_calendar(
+    &months,
+    &due_counts,
+    week_start,
+    &locale,
+    today
+  )
+}
+
+/// `weekstart`, Monday by default;
+/// `weekstart=locale` takes the first
+/// weekday of the configured locale.
+fn configured_week_start(
+  cfg: &Config
+) -> anyhow::Result<Weekday> {
+  match cfg.get("weekstart") {
+    | Some(raw)
+      if raw.trim().eq_ignore_ascii_case(
+        "locale"
+      ) =>
+    {
+      Ok(
+        Locale::configured(cfg)
+          .first_weekday()
+      )
+    }
+    | Some(raw) => {
+      parse_weekday_name(
+        &raw.trim().to_ascii_lowercase()
+      )
+      .ok_or_else(|| {
+        anyhow!(
+          "invalid weekstart: {raw}"
+        )
+      })
+    }
+    | None => Ok(Weekday::Mon)
+  }
+}
+
+/// One-line count of the open tasks
+/// that are overdue or due today,
+/// narrowed by the filter.
+#[instrument(skip(
+  store,
+  cfg,
+  filter_terms,
+  now
+))]
+fn cmd_agenda(
+  store: &mut DataStore,
+  cfg: &Config,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command agenda");
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let pending = store.load_pending()?;
+  let counts = AgendaCounts::collect(
+    pending.iter().filter(|task| {
+      filter.matches(task, now)
+    }),
+    now,
+    configured_week_start(cfg)?
+  );
+  println!("{}", counts.banner());
+  Ok(())
+}
+
+/// The `agenda.on_launch` banner,
+/// printed to stderr before the
+/// command so piped output stays
+/// clean. A failure here only warns;
+/// the command still runs.
+pub fn print_launch_agenda(
+  store: &mut DataStore,
+  cfg: &Config,
+  command: &str
+) {
+  if !agenda::on_launch(cfg)
+    || agenda::command_skips_banner(
+      command
+    )
+  {
+    return;
+  }
+  let now = Utc::now();
+  let counts = configured_week_start(
+    cfg
+  )
+  .and_then(|week_start| {
+    Ok(AgendaCounts::collect(
+      &store.load_pending()?,
+      now,
+      week_start
+    ))
+  });
+  match counts {
+    | Ok(counts) => {
+      eprintln!("{}", counts.banner());
+    }
+    | Err(err) => {
+      eprintln!(
+        "Warning: could not build the \
+         agenda: {err:#}"
+      );
+    }
+  }
+}
+
 #[instrument(skip(store))]
 fn cmd_tags(
-  store: &mut DataStore
+  store: &mut DataStore,
+  args: &[String],
+  now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
+  match args.split_first() {
+    | None => {}
+    | Some((sub, remaps))
+      if sub == "remap"
+        && !remaps.is_empty() =>
+    {
+      return cmd_tags_remap(
+        store, remaps, now
+      );
+    }
+    | Some(_) => {
+      return Err(usage_error(anyhow!(
+        "usage: task tags [remap \
+         key:old=new...]"
+      )));
+    }
+  }
+
   let pending = store.load_pending()?;
   let mut set = BTreeSet::new();
   for task in pending {
@@ -418,6 +1621,281 @@ fn cmd_tags(
   Ok(())
 }
 
+/// `task [filter] stats tags|projects
+/// [sort:count|recent|name] [--json]`:
+/// usage per tag or project across open
+/// and closed tasks, read through the
+/// store index when it is persisted.
+#[instrument(skip(
+  store,
+  renderer,
+  filter_terms,
+  args,
+  now
+))]
+fn cmd_stats(
+  store: &mut DataStore,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command stats");
+
+  let usage = || {
+    usage_error(anyhow!(
+      "usage: task stats \
+       tags|projects \
+       [sort:count|recent|name] \
+       [--json]"
+    ))
+  };
+  let Some((kind, rest)) =
+    args.split_first()
+  else {
+    return Err(usage());
+  };
+  let mut sort = StatsSort::default();
+  let mut json = false;
+  for arg in rest {
+    if arg == "--json" {
+      json = true;
+    } else if let Some(raw) =
+      arg.strip_prefix("sort:")
+    {
+      sort = StatsSort::parse(raw)
+        .map_err(usage_error)?;
+    } else {
+      return Err(usage());
+    }
+  }
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let mut tasks = store.load_pending()?;
+  tasks.extend(store.load_completed()?);
+  let tasks = tasks
+    .iter()
+    .filter(|task| filter.matches(task, now));
+  let (label, mut stats) =
+    match kind.as_str() {
+      | "tags" | "tag" => {
+        ("Tag", stats::tag_stats(tasks))
+      }
+      | "projects" | "project" => {
+        (
+          "Project",
+          stats::project_stats(tasks)
+        )
+      }
+      | _ => return Err(usage())
+    };
+  sort.apply(&mut stats);
+
+  if json {
+    println!(
+      "{}",
+      serde_json::to_string_pretty(
+        &stats
+      )?
+    );
+    return Ok(());
+  }
+  if stats.is_empty() {
+    return Err(CommandError::no_match());
+  }
+  let dates =
+    renderer.report_date_format().clone();
+  let headers = [
+    label,
+    "Tasks",
+    "Open",
+    "Last used",
+    if label == "Tag" {
+      "With"
+    } else {
+      "Tags"
+    }
+  ]
+  .map(str::to_string);
+  renderer.print_report_table(
+    &headers,
+    &[],
+    &stats,
+    |stat| {
+      vec![
+        stat.name.clone(),
+        stat.count.to_string(),
+        stat.open.to_string(),
+        dates.format(stat.last_used, now),
+        stat
+          .co_occurring
+          .iter()
+          .map(|other| {
+            format!(
+              "{} ({})",
+              other.name, other.count
+            )
+          })
+          .collect::<Vec<_>>()
+          .join(", ")
+      ]
+    }
+  )
+}
+
+/// Rewrites `key:old` to `key:new` on
+/// every task, open or closed, as one
+/// undo step; for keeping boards in
+/// step after a tag schema rename.
+fn cmd_tags_remap(
+  store: &mut DataStore,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command tags remap");
+
+  let remaps = args
+    .iter()
+    .map(|raw| TagRemap::parse(raw))
+    .collect::<anyhow::Result<Vec<_>>>()
+    .map_err(usage_error)?;
+  let mut pending = store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let before =
+    (pending.clone(), completed.clone());
+  let mut total = 0;
+  for remap in &remaps {
+    let changed = remap
+      .apply(&mut pending, now)
+      + remap.apply(&mut completed, now);
+    println!(
+      "Remapped {} -> {} on {changed} \
+       task(s).",
+      remap.from_tag(),
+      remap.to_tag()
+    );
+    total += changed;
+  }
+  if total > 0 {
+    store.push_undo_snapshot(
+      &before.0, &before.1
+    )?;
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+  }
+  Ok(())
+}
+
+/// `task uda rename <old> <new>`: moves
+/// a UDA across pending and completed
+/// tasks in one undoable step. Filters
+/// and report columns cannot name UDAs,
+/// so the taskrc lines that still say
+/// `old` are listed rather than edited.
+#[instrument(skip(store, cfg, args))]
+fn cmd_uda(
+  store: &mut DataStore,
+  cfg: &Config,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let [sub, from, to] = args else {
+    return Err(usage_error(anyhow!(
+      "usage: task uda rename <old> \
+       <new>"
+    )));
+  };
+  if sub != "rename" {
+    return Err(usage_error(anyhow!(
+      "usage: task uda rename <old> \
+       <new>"
+    )));
+  }
+  info!("command uda rename");
+
+  let rename = UdaRename::new(from, to)
+    .map_err(usage_error)?;
+  let mut pending = store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let before =
+    (pending.clone(), completed.clone());
+  // Nothing is saved until both lists
+  // rename cleanly, so a clash leaves
+  // the data as it was.
+  let changed = rename
+    .apply(&mut pending, now)
+    .and_then(|count| {
+      Ok(count
+        + rename
+          .apply(&mut completed, now)?)
+    })
+    .map_err(usage_error)?;
+  if changed > 0 {
+    store.push_undo_snapshot(
+      &before.0, &before.1
+    )?;
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+  }
+  println!(
+    "Renamed uda {} -> {} on {changed} \
+     task(s).",
+    rename.from, rename.to
+  );
+
+  let prefix =
+    format!("uda.{}.", rename.from);
+  let mentions = |value: &str| {
+    value
+      .split(|ch: char| {
+        ch.is_whitespace() || ch == ','
+      })
+      .any(|token| {
+        token
+          .trim_start_matches(['-', '+'])
+          .split([':', '.'])
+          .next()
+          == Some(rename.from.as_str())
+      })
+  };
+  let mut entries =
+    cfg.iter().collect::<Vec<_>>();
+  entries.sort();
+  for (key, value) in entries {
+    let stale = key.starts_with(&prefix)
+      || ((key.starts_with("report.")
+        || key.starts_with("context."))
+        && mentions(value));
+    if !stale {
+      continue;
+    }
+    match cfg.source(key) {
+      | Some(
+        source @ ConfigSource::File {
+          ..
+        }
+      ) => {
+        println!(
+          "Config still names '{}': \
+           {key} ({source})",
+          rename.from
+        );
+      }
+      | _ => {
+        println!(
+          "Config still names '{}': \
+           {key}",
+          rename.from
+        );
+      }
+    }
+  }
+  Ok(())
+}
+
 #[instrument(skip(store, cfg, args))]
 fn cmd_context(
   store: &mut DataStore,
@@ -437,6 +1915,7 @@ fn cmd_context(
     for (key, value) in cfg.iter() {
       if let Some(name) =
         key.strip_prefix("context.")
+        && !name.ends_with(".write")
       {
         println!("{name} {value}");
       }
@@ -455,8 +1934,10 @@ fn cmd_context(
   let name = args[0].as_str();
   let key = format!("context.{name}");
   if cfg.get(&key).is_none() {
-    return Err(anyhow!(
-      "unknown context: {name}"
+    return Err(CommandError::usage(
+      format!(
+        "unknown context: {name}"
+      )
     ));
   }
 
@@ -474,6 +1955,93 @@ fn cmd_commands() -> anyhow::Result<()>
   Ok(())
 }
 
+/// Machine-readable description of
+/// what this build supports. Fields are
+/// only ever added, so integrators can
+/// rely on existing keys.
+pub fn capabilities(
+  cfg: &Config
+) -> Value {
+  let mut reports = BTreeSet::new();
+  let mut udas: BTreeMap<
+    String,
+    serde_json::Map<String, Value>
+  > = BTreeMap::new();
+  for (key, value) in cfg.iter() {
+    if let Some(name) = key
+      .strip_prefix("report.")
+      .and_then(|rest| {
+        rest
+          .strip_suffix(".columns")
+          .or_else(|| {
+            rest.strip_suffix(".template")
+          })
+      })
+    {
+      reports.insert(name.to_string());
+    }
+
+    if let Some((name, field)) =
+      key.strip_prefix("uda.").and_then(
+        |rest| rest.rsplit_once('.')
+      )
+    {
+      udas
+        .entry(name.to_string())
+        .or_default()
+        .insert(
+          field.to_string(),
+          Value::String(value.clone())
+        );
+    }
+  }
+
+  let attributes: Vec<Value> =
+    TASK_ATTRIBUTES
+      .iter()
+      .map(|(name, kind)| {
+        serde_json::json!({
+          "name": name,
+          "type": kind
+        })
+      })
+      .collect();
+  let udas: Vec<Value> = udas
+    .into_iter()
+    .map(|(name, mut fields)| {
+      fields.insert(
+        "name".to_string(),
+        Value::String(name)
+      );
+      Value::Object(fields)
+    })
+    .collect();
+
+  serde_json::json!({
+    "capabilities_version": 1,
+    "rivet_version": env!("CARGO_PKG_VERSION"),
+    "dto_version": rivet_gui_shared::DTO_VERSION,
+    "store_format_version": STORE_FORMAT_VERSION,
+    "commands": known_command_names(),
+    "reports": reports,
+    "attributes": attributes,
+    "virtual_tags": VirtualTag::NAMES,
+    "udas": udas
+  })
+}
+
+fn cmd_capabilities(
+  cfg: &Config
+) -> anyhow::Result<()> {
+  println!(
+    "{}",
+    serde_json::to_string_pretty(
+      &capabilities(cfg)
+    )?
+  );
+  Ok(())
+}
+
 fn cmd_show(
   cfg: &Config
 ) -> anyhow::Result<()> {
@@ -483,6 +2051,325 @@ fn cmd_show(
   Ok(())
 }
 
+#[derive(Debug, serde::Serialize)]
+struct ShownSetting<'a> {
+  key:    &'a str,
+  value:  &'a str,
+  source: Option<&'a crate::config::ConfigSource>
+}
+
+/// `task show [pattern] [--json]`: the
+/// effective config after the taskrc
+/// and `rc.` overrides, with where each
+/// value came from. `pattern` matches
+/// anywhere in the key, ignoring case.
+fn cmd_show_config(
+  store: &DataStore,
+  cfg: &Config,
+  args: &[String]
+) -> anyhow::Result<()> {
+  info!("command show");
+
+  let mut json = false;
+  let mut pattern: Option<String> = None;
+  for arg in args {
+    if arg == "--json" {
+      json = true;
+    } else if pattern.is_none() {
+      pattern =
+        Some(arg.to_ascii_lowercase());
+    } else {
+      return Err(usage_error(anyhow!(
+        "usage: task show [pattern] \
+         [--json]"
+      )));
+    }
+  }
+
+  let mut settings: Vec<ShownSetting> =
+    cfg
+      .iter()
+      .filter(|(key, _)| {
+        pattern.as_deref().is_none_or(
+          |pattern| {
+            key
+              .to_ascii_lowercase()
+              .contains(pattern)
+          }
+        )
+      })
+      .map(|(key, value)| {
+        ShownSetting {
+          key,
+          value,
+          source: cfg.source(key)
+        }
+      })
+      .collect();
+  settings
+    .sort_by(|a, b| a.key.cmp(b.key));
+  let context =
+    store.get_active_context()?;
+
+  if json {
+    println!(
+      "{}",
+      serde_json::to_string_pretty(
+        &serde_json::json!({
+          "files": cfg.loaded_files,
+          "context": context,
+          "settings": settings
+        })
+      )?
+    );
+    return Ok(());
+  }
+
+  if cfg.loaded_files.is_empty() {
+    println!("No taskrc loaded.");
+  } else {
+    for path in &cfg.loaded_files {
+      println!(
+        "Loaded {}",
+        path.display()
+      );
+    }
+  }
+  if settings.is_empty() {
+    println!(
+      "No matching configuration \
+       settings."
+    );
+  } else {
+    let key_width = settings
+      .iter()
+      .map(|setting| setting.key.len())
+      .max()
+      .unwrap_or(0)
+      .max("Config".len());
+    let value_width = settings
+      .iter()
+      .map(|setting| {
+        setting.value.chars().count()
+      })
+      .max()
+      .unwrap_or(0)
+      .max("Value".len());
+    println!();
+    println!(
+      "{:key_width$}  \
+       {:value_width$}  Source",
+      "Config", "Value"
+    );
+    for setting in &settings {
+      println!(
+        "{:key_width$}  \
+         {:value_width$}  {}",
+        setting.key,
+        setting.value,
+        setting
+          .source
+          .map(ToString::to_string)
+          .unwrap_or_default()
+      );
+    }
+  }
+  if let Some(context) = context {
+    println!();
+    println!(
+      "Active context: {context} \
+       (applied to filters, not to \
+       these settings)"
+    );
+  }
+  Ok(())
+}
+
+/// `task doctor [--fix] [--json]`:
+/// store health report. Read-only
+/// unless `--fix` is given; fails when
+/// errors remain so scripts notice.
+fn cmd_doctor(
+  store: &mut DataStore,
+  cfg: &Config,
+  args: &[String]
+) -> anyhow::Result<()> {
+  info!("command doctor");
+
+  let mut fix = false;
+  let mut json = false;
+  for arg in args {
+    match arg.as_str() {
+      | "--fix" => fix = true,
+      | "--json" => json = true,
+      | _ => {
+        return Err(usage_error(anyhow!(
+          "usage: task doctor [--fix] \
+           [--json]"
+        )));
+      }
+    }
+  }
+
+  let date_udas: Vec<String> = cfg
+    .iter()
+    .filter_map(|(key, value)| {
+      let name = key
+        .strip_prefix("uda.")?
+        .strip_suffix(".type")?;
+      value
+        .trim()
+        .eq_ignore_ascii_case("date")
+        .then(|| name.to_string())
+    })
+    .collect();
+  let mut pending = store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let mut quarantined =
+    store.load_quarantined()?;
+  let checked = (
+    pending.len(),
+    completed.len(),
+    quarantined.len()
+  );
+
+  let mut repaired = None;
+  if fix {
+    let pending_before = pending.clone();
+    let completed_before =
+      completed.clone();
+    let summary = doctor::repair(
+      &mut pending,
+      &mut completed,
+      &mut quarantined,
+      &date_udas
+    );
+    // Rewriting also drops quarantined
+    // lines still sitting in the data
+    // files; corrupt.data keeps them.
+    if summary.fixed + summary.restored
+      > 0
+      || checked.2 > 0
+    {
+      store.push_undo_snapshot(
+        &pending_before,
+        &completed_before
+      )?;
+      store.save_pending(&pending)?;
+      store.save_completed(&completed)?;
+      store
+        .save_quarantined(&quarantined)?;
+    }
+    repaired = Some(summary);
+  }
+
+  let mut findings = doctor::diagnose(
+    &pending, &completed, &date_udas
+  );
+  findings.extend(
+    doctor::quarantine_findings(
+      &quarantined
+    )
+  );
+  findings
+    .extend(doctor::timezone_findings());
+  findings.extend(
+    doctor::business_calendar_findings()
+  );
+  let errors = findings
+    .iter()
+    .filter(|finding| {
+      finding.severity
+        == doctor::Severity::Error
+    })
+    .count();
+
+  if json {
+    println!(
+      "{}",
+      serde_json::to_string_pretty(
+        &serde_json::json!({
+          "checked": {
+            "pending": checked.0,
+            "completed": checked.1,
+            "quarantined": checked.2
+          },
+          "fixed": repaired
+            .as_ref()
+            .map(|summary| summary.fixed),
+          "restored": repaired
+            .as_ref()
+            .map(|summary| summary.restored),
+          "findings": findings
+        })
+      )?
+    );
+  } else {
+    println!(
+      "Checked {} pending and {} \
+       completed task(s), {} \
+       quarantined line(s).",
+      checked.0, checked.1, checked.2
+    );
+    if let Some(summary) = &repaired {
+      println!(
+        "Fixed {} problem(s); restored \
+         {} quarantined line(s).",
+        summary.fixed, summary.restored
+      );
+    }
+    for finding in &findings {
+      println!(
+        "{:7}  {:20}  {}",
+        match finding.severity {
+          | doctor::Severity::Error => {
+            "error"
+          }
+          | doctor::Severity::Warning => {
+            "warning"
+          }
+        },
+        finding.check,
+        finding.message
+      );
+      println!(
+        "{:7}  {:20}  {}: {}",
+        "",
+        "",
+        if finding.fixable {
+          "--fix will"
+        } else {
+          "suggestion"
+        },
+        finding.suggestion
+      );
+    }
+    let fixable = findings
+      .iter()
+      .filter(|finding| finding.fixable)
+      .count();
+    if findings.is_empty() {
+      println!("No problems found.");
+    } else {
+      println!(
+        "{errors} error(s), {} \
+         warning(s); {fixable} can be \
+         fixed with `task doctor --fix`.",
+        findings.len() - errors
+      );
+    }
+  }
+
+  if errors > 0 {
+    return Err(anyhow!(
+      "{errors} store error(s) need \
+       attention"
+    ));
+  }
+  Ok(())
+}
+
 fn cmd_unique(
   store: &mut DataStore,
   args: &[String]
@@ -498,7 +2385,7 @@ fn cmd_unique(
     | "project" | "projects" => {
       cmd_projects(store)
     }
-    | "tag" | "tags" => cmd_tags(store),
+    | "tag" | "tags" => cmd_tags(store, &[], Utc::now()),
     | "status" => {
       println!("pending");
       println!("completed");
@@ -510,14 +2397,421 @@ fn cmd_unique(
   }
 }
 
+/// Prints the ids of matching pending
+/// tasks as ranges, e.g. `1-3 5`, for
+/// use as a filter in the next call.
+/// It never triggers id gc, so the ids
+/// stay valid until a report runs.
+#[instrument(skip(
+  store,
+  filter_terms,
+  now
+))]
+fn cmd_ids(
+  store: &mut DataStore,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let mut ids: Vec<u64> = store
+    .load_pending()?
+    .iter()
+    .filter(|task| {
+      matches!(
+        task.status,
+        Status::Pending
+          | Status::Waiting
+      ) && filter.matches(task, now)
+    })
+    .filter_map(|task| task.id)
+    .collect();
+  ids.sort_unstable();
+  ids.dedup();
+  if ids.is_empty() {
+    return Err(CommandError::no_match());
+  }
+
+  let mut ranges: Vec<(u64, u64)> =
+    Vec::new();
+  for id in ids {
+    match ranges.last_mut() {
+      | Some((_, end))
+        if *end + 1 == id =>
+      {
+        *end = id;
+      }
+      | _ => ranges.push((id, id))
+    }
+  }
+  let ranges: Vec<String> = ranges
+    .into_iter()
+    .map(|(start, end)| {
+      if start == end {
+        start.to_string()
+      } else {
+        format!("{start}-{end}")
+      }
+    })
+    .collect();
+  println!("{}", ranges.join(" "));
+  Ok(())
+}
+
+/// Reads commands from stdin until
+/// `exit`, `quit` or end of input and
+/// runs each against the store that is
+/// already open, so large data files
+/// are parsed once per session. `!!`
+/// repeats the previous command.
+#[instrument(skip(
+  store, cfg, renderer
+))]
+fn cmd_shell(
+  store: &mut DataStore,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  dry_run: bool
+) -> anyhow::Result<()> {
+  info!("command shell");
+
+  let interactive =
+    io::stdin().is_terminal();
+  let prompt = cfg
+    .get("shell.prompt")
+    .unwrap_or_else(|| {
+      "task> ".to_string()
+    });
+  let mut previous: Option<String> =
+    None;
+  let mut line = String::new();
+  loop {
+    if interactive {
+      print!("{prompt}");
+      io::stdout().flush()?;
+    }
+    line.clear();
+    if io::stdin()
+      .read_line(&mut line)?
+      == 0
+    {
+      if interactive {
+        println!();
+      }
+      break;
+    }
+
+    let mut entry =
+      line.trim().to_string();
+    if entry.is_empty()
+      || entry.starts_with('#')
+    {
+      continue;
+    }
+    if entry == "!!" {
+      let Some(last) = previous.clone()
+      else {
+        eprintln!(
+          "error: no previous command"
+        );
+        continue;
+      };
+      println!("{last}");
+      entry = last;
+    }
+    if matches!(
+      entry.as_str(),
+      "exit" | "quit"
+    ) {
+      break;
+    }
+    previous = Some(entry.clone());
+
+    if let Err(err) = run_shell_line(
+      store, cfg, renderer, &entry,
+      dry_run
+    ) {
+      eprintln!("error: {err:#}");
+    }
+  }
+  Ok(())
+}
+
+fn run_shell_line(
+  store: &mut DataStore,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  line: &str,
+  dry_run: bool
+) -> anyhow::Result<()> {
+  let mut words =
+    split_command_line(line)?;
+  // Accept lines pasted from a normal
+  // prompt, e.g. `task 3 done`.
+  if words
+    .first()
+    .is_some_and(|word| word == "task")
+  {
+    words.remove(0);
+  }
+  let mut inv = Invocation::parse(
+    cfg,
+    words
+      .into_iter()
+      .map(std::ffi::OsString::from)
+      .collect()
+  )?;
+  if inv.command == "shell" {
+    return Err(CommandError::usage(
+      "already in the shell"
+    ));
+  }
+  inv.dry_run = dry_run;
+  renderer.set_limit(inv.limit);
+  // A `--width` on one line does not
+  // carry over to the next.
+  let width = renderer.width();
+  renderer.set_width(inv.width);
+  let result =
+    dispatch(store, cfg, renderer, inv);
+  renderer
+    .set_width(Some(width.unwrap_or(0)));
+  result
+}
+
+/// Steps through matching pending tasks
+/// one at a time, least recently
+/// reviewed first (never-reviewed tasks
+/// lead). Each answer is read from
+/// stdin: keep and modify stamp the
+/// task's `reviewed` UDA, done and
+/// delete run those commands, skip
+/// leaves it for next time and quit (or
+/// end of input) stops.
+#[instrument(skip(
+  store,
+  cfg,
+  renderer,
+  filter_terms,
+  now
+))]
+fn cmd_review(
+  store: &mut DataStore,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  dry_run: bool,
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command review");
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let mut queue: Vec<Task> = store
+    .load_pending()?
+    .into_iter()
+    .filter(|task| {
+      task.status == Status::Pending
+        && filter.matches(task, now)
+    })
+    .collect();
+  if queue.is_empty() {
+    return Err(CommandError::no_match());
+  }
+  queue.sort_by_key(|task| {
+    (task.reviewed_at(), task.id)
+  });
+
+  let interactive =
+    io::stdin().is_terminal();
+  let total = queue.len();
+  let mut reviewed = 0_usize;
+  let mut line = String::new();
+  'tasks: for (index, queued) in
+    queue.iter().enumerate()
+  {
+    let Some(task) = store
+      .load_pending()?
+      .into_iter()
+      .find(|task| {
+        task.uuid == queued.uuid
+      })
+    else {
+      continue;
+    };
+    println!(
+      "Review {} of {total}",
+      index + 1
+    );
+    renderer.print_task_info(&task, now)?;
+    let target = task.uuid.to_string();
+    loop {
+      print!(
+        "[k]eep, [m]odify <changes>, \
+         [d]one, [x] delete, [s]kip, \
+         [q]uit? "
+      );
+      io::stdout().flush()?;
+      line.clear();
+      if io::stdin()
+        .read_line(&mut line)?
+        == 0
+      {
+        if interactive {
+          println!();
+        }
+        break 'tasks;
+      }
+      let answer = line.trim();
+      let (action, rest) = answer
+        .split_once(char::is_whitespace)
+        .map_or((answer, ""), |(a, r)| {
+          (a, r.trim())
+        });
+      let outcome = match action {
+        | "k" | "keep" => {
+          stamp_reviewed(
+            store, task.uuid, now
+          )
+        }
+        | "m" | "modify"
+          if rest.is_empty() =>
+        {
+          eprintln!(
+            "error: modify needs \
+             changes, e.g. 'm \
+             due:friday'"
+          );
+          continue;
+        }
+        | "m" | "modify" => {
+          run_shell_line(
+            store,
+            cfg,
+            renderer,
+            &format!(
+              "{target} modify {rest}"
+            ),
+            dry_run
+          )
+          .and_then(|()| {
+            stamp_reviewed(
+              store, task.uuid, now
+            )
+          })
+        }
+        | "d" | "done" => {
+          run_shell_line(
+            store,
+            cfg,
+            renderer,
+            &format!("{target} done"),
+            dry_run
+          )
+        }
+        | "x" | "delete" => {
+          run_shell_line(
+            store,
+            cfg,
+            renderer,
+            &format!("{target} delete"),
+            dry_run
+          )
+        }
+        | "s" | "skip" => break,
+        | "q" | "quit" => break 'tasks,
+        | _ => {
+          eprintln!(
+            "error: unknown answer \
+             '{answer}'"
+          );
+          continue;
+        }
+      };
+      match outcome {
+        | Ok(()) => {
+          reviewed += 1;
+          break;
+        }
+        | Err(err) => {
+          eprintln!("error: {err:#}");
+        }
+      }
+    }
+  }
+  println!(
+    "Reviewed {reviewed} of {total} \
+     task(s)."
+  );
+  Ok(())
+}
+
+/// Sets `reviewed` to `now` on one
+/// pending task as its own undo step,
+/// recorded in history as `review`.
+fn stamp_reviewed(
+  store: &mut DataStore,
+  uuid: uuid::Uuid,
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let mut pending =
+    store.load_pending()?;
+  let before = pending.clone();
+  let Some(task) = pending
+    .iter_mut()
+    .find(|task| task.uuid == uuid)
+  else {
+    return Ok(());
+  };
+  task.mark_reviewed(now);
+  let completed =
+    store.load_completed()?;
+  store.push_undo_snapshot(
+    &before, &completed
+  )?;
+  store.update_pending(&pending)?;
+  store.append_history(
+    &diff_snapshots(
+      "review", &before, &pending, now
+    )
+  )?;
+  Ok(())
+}
+
+/// `rpc`: JSON-RPC on stdin/stdout
+/// for tools; see `crate::rpc`.
+fn cmd_rpc(
+  store: &mut DataStore,
+  cfg: &Config,
+  hooks: &HookRunner
+) -> anyhow::Result<()> {
+  info!("command rpc");
+  crate::rpc::serve(
+    store,
+    cfg,
+    hooks,
+    io::stdin().lock(),
+    io::stdout().lock()
+  )
+}
+
 fn cmd_help() -> anyhow::Result<()> {
   println!(
-    "Implemented commands: add, \
+    "Implemented commands: add, inbox, \
      append, prepend, list/next, \
-     info, modify, start, stop, \
-     annotate, denotate, duplicate, \
-     log, done, delete, undo, export, \
-     import, projects, tags, context"
+     stale, info, explain, ids, \
+     subtasks, \
+     history, modify, autotag, start, \
+     stop, snooze, postpone, annotate, \
+     denotate, \
+     duplicate, dedupe, log, done, \
+     skip, uncomplete, archive, \
+     unarchive, \
+     delete, undo, redo, purge, gc, \
+     backup, restore, export, import, \
+     projects, tags, uda, stats, \
+     calendar, agenda, context, \
+     config, show, doctor, demo, review, shell, rpc"
   );
   Ok(())
 }
@@ -569,15 +2863,24 @@ fn command_uses_filter(
       | "prepend"
       | "list"
       | "next"
+      | "stale"
       | "info"
+      | "ids"
+      | "subtasks"
+      | "history"
+      | "calendar"
+      | "agenda"
       | "modify"
+      | "autotag"
       | "start"
       | "stop"
       | "annotate"
       | "denotate"
       | "duplicate"
+      | "dedupe"
       | "done"
+      | "skip"
+      | "archive"
       | "delete"
   ) || is_report_command(cfg, command)
 }
-
@@ -7,13 +7,23 @@ enum Mod {
   Due(chrono::DateTime<Utc>),
   Scheduled(chrono::DateTime<Utc>),
   Wait(chrono::DateTime<Utc>),
-  Depends(uuid::Uuid)
+  /// Moves an existing `due`,
+  /// `scheduled` or `wait` date by an
+  /// offset (`due:due+3d`).
+  Shift(&'static str, String),
+  Depends(uuid::Uuid),
+  Parent(Option<uuid::Uuid>),
+  Contact(Option<uuid::Uuid>),
+  /// Replaces the `key:value` tag for
+  /// one recurrence key.
+  RecurTag(&'static str, String)
 }
 
-#[instrument(skip(args, now))]
+#[instrument(skip(args, now, times))]
 fn parse_desc_and_mods(
   args: &[String],
-  now: chrono::DateTime<Utc>
+  now: chrono::DateTime<Utc>,
+  times: &DefaultTimes
 ) -> anyhow::Result<(String, Vec<Mod>)>
 {
   let mut desc_parts = Vec::new();
@@ -28,7 +38,8 @@ fn parse_desc_and_mods(
 
     if !literal
       && let Some(one_mod) =
-        parse_one_mod(arg, now)?
+        parse_one_mod(arg, now, times)
+          .map_err(usage_error)?
     {
       mods.push(one_mod);
       continue;
@@ -38,7 +49,7 @@ fn parse_desc_and_mods(
   }
 
   if desc_parts.is_empty() {
-    return Err(anyhow!(
+    return Err(CommandError::usage(
       "add/log: description is \
        required"
     ));
@@ -47,15 +58,17 @@ fn parse_desc_and_mods(
   Ok((desc_parts.join(" "), mods))
 }
 
-#[instrument(skip(args, now))]
+#[instrument(skip(args, now, times))]
 fn parse_mods(
   args: &[String],
-  now: chrono::DateTime<Utc>
+  now: chrono::DateTime<Utc>,
+  times: &DefaultTimes
 ) -> anyhow::Result<Vec<Mod>> {
   let mut mods = Vec::new();
   for arg in args {
     if let Some(one_mod) =
-      parse_one_mod(arg, now)?
+      parse_one_mod(arg, now, times)
+        .map_err(usage_error)?
     {
       mods.push(one_mod);
     } else {
@@ -67,7 +80,8 @@ fn parse_mods(
 
 fn parse_one_mod(
   tok: &str,
-  now: chrono::DateTime<Utc>
+  now: chrono::DateTime<Utc>,
+  times: &DefaultTimes
 ) -> anyhow::Result<Option<Mod>> {
   if let Some(tag) =
     tok.strip_prefix('+')
@@ -99,6 +113,20 @@ fn parse_one_mod(
 
   let key = key.to_ascii_lowercase();
 
+  if let Some(field) = SHIFT_FIELDS
+    .iter()
+    .copied()
+    .find(|field| *field == key)
+    && let Some(offset) =
+      shift_offset(field, value)
+  {
+    shift_date(now, offset)?;
+    return Ok(Some(Mod::Shift(
+      field,
+      offset.to_string()
+    )));
+  }
+
   match key.as_str() {
     | "project" => {
       Ok(Some(Mod::Project(
@@ -112,17 +140,29 @@ fn parse_one_mod(
     }
     | "due" => {
       Ok(Some(Mod::Due(
-        parse_date_expr(value, now)?
+        parse_date_expr_at(
+          value,
+          now,
+          times.due
+        )?
       )))
     }
     | "scheduled" => {
       Ok(Some(Mod::Scheduled(
-        parse_date_expr(value, now)?
+        parse_date_expr_at(
+          value,
+          now,
+          times.scheduled
+        )?
       )))
     }
     | "wait" => {
       Ok(Some(Mod::Wait(
-        parse_date_expr(value, now)?
+        parse_date_expr_at(
+          value,
+          now,
+          times.wait
+        )?
       )))
     }
     | "depends" => {
@@ -130,10 +170,318 @@ fn parse_one_mod(
         uuid::Uuid::parse_str(value)?;
       Ok(Some(Mod::Depends(uuid)))
     }
+    | "parent" => {
+      if value.is_empty() {
+        return Ok(Some(Mod::Parent(
+          None
+        )));
+      }
+      let uuid =
+        uuid::Uuid::parse_str(value)?;
+      Ok(Some(Mod::Parent(Some(uuid))))
+    }
+    | "contact" => {
+      if value.is_empty() {
+        return Ok(Some(Mod::Contact(
+          None
+        )));
+      }
+      let uuid =
+        uuid::Uuid::parse_str(value)?;
+      Ok(Some(Mod::Contact(Some(uuid))))
+    }
+    | "recur" => {
+      let pattern =
+        RecurPattern::parse(value)
+          .ok_or_else(|| {
+            anyhow!(
+              "unknown recurrence \
+               '{value}': use daily, \
+               weekly, months, \
+               monthly or yearly"
+            )
+          })?;
+      Ok(Some(Mod::RecurTag(
+        RECUR_TAG_KEY,
+        format!("{pattern:?}")
+          .to_ascii_lowercase()
+      )))
+    }
+    | "until" => {
+      let date = to_project_date(
+        parse_date_expr(value, now)?
+      );
+      Ok(Some(Mod::RecurTag(
+        RECUR_UNTIL_TAG_KEY,
+        date
+          .format("%Y-%m-%d")
+          .to_string()
+      )))
+    }
+    | "count" => {
+      let count = value
+        .parse::<u32>()
+        .ok()
+        .filter(|count| *count > 0)
+        .ok_or_else(|| {
+          anyhow!(
+            "count must be a positive \
+             number, got '{value}'"
+          )
+        })?;
+      Ok(Some(Mod::RecurTag(
+        RECUR_COUNT_TAG_KEY,
+        count.to_string()
+      )))
+    }
     | _ => Ok(None)
   }
 }
 
+/// Date fields `postpone` and
+/// `<field>:<field>+N` can shift.
+const SHIFT_FIELDS: &[&str] =
+  &["due", "scheduled", "wait"];
+
+/// The offset of a value naming its own
+/// field, e.g. `+3d` in `due:due+3d`.
+fn shift_offset<'a>(
+  field: &str,
+  value: &'a str
+) -> Option<&'a str> {
+  let offset = value.get(field.len()..)?;
+  (value[..field.len()]
+    .eq_ignore_ascii_case(field)
+    && offset.starts_with(['+', '-']))
+  .then_some(offset)
+}
+
+/// Keys `parse_one_mod` accepts; typos
+/// of these are corrected on quick-add.
+const MODIFIER_KEYS: &[&str] = &[
+  "project",
+  "priority",
+  "due",
+  "scheduled",
+  "wait",
+  "depends",
+  "parent",
+  "contact",
+  "recur",
+  "until",
+  "count"
+];
+
+/// Below this share of matching
+/// characters a typo is left alone.
+const QUICK_ADD_MIN_SIMILARITY: f64 =
+  0.75;
+
+/// A quick-add token rewritten to the
+/// closest key, project or tag in use.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct QuickAddCorrection {
+  pub original:  String,
+  pub corrected: String
+}
+
+/// Rewrites likely typos in `add`
+/// modifiers: unknown `key:value` keys
+/// close to a modifier key, and
+/// `project:`/`+tag` values close to
+/// one already used by `tasks`. Words
+/// after `--` are never touched.
+pub fn correct_quick_add_tokens(
+  args: &[String],
+  tasks: &[Task]
+) -> (
+  Vec<String>,
+  Vec<QuickAddCorrection>
+) {
+  let projects: BTreeSet<&str> = tasks
+    .iter()
+    .filter_map(|task| {
+      task.project.as_deref()
+    })
+    .collect();
+  let tags: BTreeSet<&str> = tasks
+    .iter()
+    .flat_map(|task| {
+      task
+        .tags
+        .iter()
+        .map(String::as_str)
+    })
+    .collect();
+
+  let mut out = Vec::new();
+  let mut corrections = Vec::new();
+  let mut literal = false;
+  for arg in args {
+    let fixed = if literal
+      || arg == "--"
+    {
+      literal = true;
+      None
+    } else if let Some(tag) =
+      arg.strip_prefix('+')
+    {
+      closest_match(
+        tag,
+        tags.iter().copied()
+      )
+      .map(|known| format!("+{known}"))
+    } else {
+      correct_modifier_token(
+        arg, &projects
+      )
+    };
+
+    match fixed {
+      | Some(fixed) if fixed != *arg => {
+        corrections.push(
+          QuickAddCorrection {
+            original:  arg.clone(),
+            corrected: fixed.clone()
+          }
+        );
+        out.push(fixed);
+      }
+      | _ => out.push(arg.clone())
+    }
+  }
+  (out, corrections)
+}
+
+fn correct_modifier_token(
+  tok: &str,
+  projects: &BTreeSet<&str>
+) -> Option<String> {
+  let sep = tok.find([':', '='])?;
+  let (key, rest) = tok.split_at(sep);
+  let (sep, value) = rest.split_at(1);
+  let lower = key.to_ascii_lowercase();
+  let key = if lower == "pri"
+    || MODIFIER_KEYS
+      .contains(&lower.as_str())
+  {
+    key.to_string()
+  } else {
+    closest_match(
+      &lower,
+      MODIFIER_KEYS.iter().copied()
+    )?
+    .to_string()
+  };
+
+  let value = if key
+    .eq_ignore_ascii_case("project")
+    && !value.is_empty()
+  {
+    closest_match(
+      value,
+      projects.iter().copied()
+    )
+    .unwrap_or(value)
+  } else {
+    value
+  };
+  Some(format!("{key}{sep}{value}"))
+}
+
+/// The candidate nearest to `word`, if
+/// it is close enough to be a typo.
+/// `None` when `word` is a candidate.
+fn closest_match<'a>(
+  word: &str,
+  candidates: impl IntoIterator<
+    Item = &'a str
+  >
+) -> Option<&'a str> {
+  let mut best: Option<(usize, &str)> =
+    None;
+  for candidate in candidates {
+    if candidate == word {
+      return None;
+    }
+    let distance =
+      edit_distance(word, candidate);
+    let longest = word
+      .chars()
+      .count()
+      .max(candidate.chars().count());
+    let similarity = 1.0
+      - distance as f64
+        / longest as f64;
+    if similarity
+      >= QUICK_ADD_MIN_SIMILARITY
+      && best.is_none_or(|(d, _)| {
+        distance < d
+      })
+    {
+      best =
+        Some((distance, candidate));
+    }
+  }
+  best.map(|(_, candidate)| candidate)
+}
+
+/// Case-insensitive edit distance where
+/// swapping two neighbours counts as a
+/// single edit (`projetc` ->
+/// `project`).
+fn edit_distance(
+  left: &str,
+  right: &str
+) -> usize {
+  let a: Vec<char> = left
+    .to_lowercase()
+    .chars()
+    .collect();
+  let b: Vec<char> = right
+    .to_lowercase()
+    .chars()
+    .collect();
+  let mut rows =
+    vec![
+      vec![0usize; b.len() + 1];
+      a.len() + 1
+    ];
+  for (i, row) in
+    rows.iter_mut().enumerate()
+  {
+    row[0] = i;
+  }
+  for (j, cell) in
+    rows[0].iter_mut().enumerate()
+  {
+    *cell = j;
+  }
+  for i in 1..=a.len() {
+    for j in 1..=b.len() {
+      let cost = usize::from(
+        a[i - 1] != b[j - 1]
+      );
+      let mut best = (rows[i - 1][j]
+        + 1)
+        .min(rows[i][j - 1] + 1)
+        .min(rows[i - 1][j - 1] + cost);
+      if i > 1
+        && j > 1
+        && a[i - 1] == b[j - 2]
+        && a[i - 2] == b[j - 1]
+      {
+        best = best
+          .min(rows[i - 2][j - 2] + 1);
+      }
+      rows[i][j] = best;
+    }
+  }
+  rows[a.len()][b.len()]
+}
+
 fn apply_mods(
   task: &mut Task,
   mods: &[Mod],
@@ -168,13 +516,12 @@ fn apply_mods(
         task.scheduled = Some(*dt);
       }
       | Mod::Wait(dt) => {
-        task.wait = Some(*dt);
-        if *dt <= now
-          && task.status
-            == Status::Waiting
-        {
-          task.status = Status::Pending;
-        }
+        set_wait(task, *dt, now);
+      }
+      | Mod::Shift(field, offset) => {
+        shift_task_date(
+          task, field, offset, now
+        )?;
       }
       | Mod::Depends(dep) => {
         if task.depends.iter().all(
@@ -183,8 +530,141 @@ fn apply_mods(
           task.depends.push(*dep);
         }
       }
+      | Mod::Parent(parent) => {
+        task.parent = *parent;
+      }
+      | Mod::Contact(contact) => {
+        task.contact = *contact;
+      }
+      | Mod::RecurTag(key, value) => {
+        let prefix = format!("{key}:");
+        task.tags.retain(|existing| {
+          !existing.starts_with(&prefix)
+        });
+        task.tags.push(format!(
+          "{prefix}{value}"
+        ));
+        // A new count restarts the
+        // series' remaining instances.
+        if *key == RECUR_COUNT_TAG_KEY {
+          let left = format!(
+            "{RECUR_LEFT_TAG_KEY}:"
+          );
+          task.tags.retain(
+            |existing| {
+              !existing
+                .starts_with(&left)
+            }
+          );
+          task.tags.push(format!(
+            "{left}{value}"
+          ));
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn set_wait(
+  task: &mut Task,
+  wait: chrono::DateTime<Utc>,
+  now: chrono::DateTime<Utc>
+) {
+  task.wait = Some(wait);
+  if wait <= now
+    && task.status == Status::Waiting
+  {
+    task.status = Status::Pending;
+  }
+}
+
+/// Moves `field` (one of
+/// `SHIFT_FIELDS`) by `offset`; a task
+/// without that date is an error rather
+/// than a date counted from now.
+fn shift_task_date(
+  task: &mut Task,
+  field: &str,
+  offset: &str,
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let current = match field {
+    | "due" => task.due,
+    | "scheduled" => task.scheduled,
+    | _ => task.wait
+  };
+  let Some(current) = current else {
+    return Err(CommandError::usage(
+      format!(
+        "cannot shift {field}: task \
+         {} has no {field} date",
+        task.id.map_or_else(
+          || task.uuid.to_string(),
+          |id| id.to_string()
+        )
+      )
+    ));
+  };
+  let shifted =
+    shift_date(current, offset)
+      .map_err(usage_error)?;
+  match field {
+    | "due" => task.due = Some(shifted),
+    | "scheduled" => {
+      task.scheduled = Some(shifted);
     }
+    | _ => set_wait(task, shifted, now)
   }
+  Ok(())
+}
 
+fn mods_set_parent(
+  mods: &[Mod]
+) -> bool {
+  mods.iter().any(|one_mod| {
+    matches!(
+      one_mod,
+      Mod::Parent(Some(_))
+    )
+  })
+}
+
+/// Rejects `parent` links on the
+/// `changed` tasks that point at an
+/// unknown task or back into the task's
+/// own subtree.
+fn check_parent_links(
+  tasks: &[Task],
+  changed: &[uuid::Uuid]
+) -> anyhow::Result<()> {
+  for uuid in changed {
+    let Some(parent) = tasks
+      .iter()
+      .find(|task| task.uuid == *uuid)
+      .and_then(|task| task.parent)
+    else {
+      continue;
+    };
+    if !tasks
+      .iter()
+      .any(|task| task.uuid == parent)
+    {
+      return Err(anyhow!(
+        "parent {parent} does not \
+         match any task"
+      ));
+    }
+    if parent_would_cycle(
+      tasks, *uuid, parent
+    ) {
+      return Err(anyhow!(
+        "parent {parent} would make \
+         task {uuid} its own ancestor"
+      ));
+    }
+  }
   Ok(())
 }
+
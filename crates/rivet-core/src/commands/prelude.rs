@@ -5,16 +5,25 @@ use std::collections::{
 };
 use std::io::{
   self,
-  Read
+  IsTerminal,
+  Read,
+  Write
 };
+use std::path::PathBuf;
 
 use anyhow::{
   Context,
   anyhow
 };
-use chrono::Utc;
+use chrono::{
+  Utc,
+  Weekday
+};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{
+  Map,
+  Value
+};
 use tracing::{
   debug,
   info,
@@ -22,48 +31,163 @@ use tracing::{
   warn
 };
 
-use crate::cli::Invocation;
-use crate::config::Config;
-use crate::datastore::DataStore;
+use crate::agenda::{
+  self,
+  AgendaCounts
+};
+use crate::archive;
+use crate::autotag::AutoTagRules;
+use crate::backup;
+use crate::dedupe;
+use crate::demo;
+use crate::doctor;
+use crate::cli::{
+  Invocation,
+  RowLimit,
+  split_command_line
+};
+use crate::config::{
+  Config,
+  ConfigSource
+};
+use crate::config_bundle::{
+  self,
+  BundleChange,
+  ChangeKind,
+  ConfigBundle,
+  ConflictPolicy
+};
+use crate::datastore::{
+  DataStore,
+  STORE_FORMAT_VERSION
+};
 use crate::datetime::{
-  format_project_date,
-  parse_date_expr
+  DateFormat,
+  DefaultTimes,
+  calendar_months,
+  parse_date_expr,
+  parse_date_expr_at,
+  parse_snooze_expr,
+  parse_weekday_name,
+  project_timezone,
+  shift_date,
+  to_project_date
 };
-use crate::filter::Filter;
+use crate::exit::{
+  CommandError,
+  usage_error
+};
+use crate::filter::{
+  self,
+  Filter,
+  VirtualTag
+};
+use crate::history::diff_snapshots;
+use crate::locale::Locale;
 use crate::hooks::HookRunner;
-use crate::render::Renderer;
+use crate::recur::{
+  self,
+  RECUR_COUNT_TAG_KEY,
+  RECUR_LEFT_TAG_KEY,
+  RECUR_SKIPPED_KEY,
+  RECUR_TAG_KEY,
+  RECUR_UNTIL_TAG_KEY,
+  RecurMode,
+  RecurPattern,
+  RecurSpec,
+  next_instance,
+  recur_horizon_days
+};
+use crate::render::{
+  Align,
+  Delimited,
+  ExportColumns,
+  IdDisplay,
+  LineTemplate,
+  QuotePolicy,
+  Renderer,
+  delimited_cell
+};
+use crate::stats::{
+  self,
+  StatsSort
+};
 use crate::task::{
   Annotation,
+  CompletionPolicy,
+  DescriptionLimit,
+  StalePolicy,
   Status,
-  Task
+  TASK_ATTRIBUTES,
+  TagRemap,
+  Task,
+  TaskDefaults,
+  UdaRename,
+  UrgencyCoefficients,
+  newly_unblocked,
+  open_descendants,
+  parent_would_cycle,
+  subtask_progress,
+  subtasks_of
 };
 
 pub fn known_command_names()
 -> Vec<&'static str> {
   vec![
     "add",
+    "inbox",
     "append",
     "prepend",
     "list",
     "next",
+    "stale",
     "info",
+    "explain",
+    "ids",
+    "subtasks",
+    "history",
     "modify",
+    "autotag",
     "start",
     "stop",
+    "snooze",
+    "postpone",
     "annotate",
     "denotate",
     "duplicate",
+    "dedupe",
     "log",
     "done",
+    "skip",
+    "uncomplete",
+    "archive",
+    "unarchive",
     "delete",
     "undo",
+    "redo",
+    "purge",
+    "gc",
+    "backup",
+    "restore",
     "export",
     "import",
     "projects",
     "tags",
+    "uda",
+    "stats",
+    "calendar",
+    "agenda",
     "context",
     "contexts",
+    "config",
+    "show",
+    "doctor",
+    "demo",
+    "review",
+    "shell",
+    "rpc",
     "_commands",
+    "_capabilities",
     "_show",
     "_unique",
     "help",
@@ -71,6 +195,41 @@ pub fn known_command_names()
   ]
 }
 
+/// Commands that shipped first. A prefix
+/// they resolve on their own keeps
+/// naming them after newer commands
+/// share it, so `sta` stays `start`
+/// rather than clashing with `stale`.
+const ORIGINAL_COMMANDS: &[&str] = &[
+  "add",
+  "append",
+  "prepend",
+  "list",
+  "next",
+  "info",
+  "modify",
+  "start",
+  "stop",
+  "annotate",
+  "denotate",
+  "duplicate",
+  "log",
+  "done",
+  "delete",
+  "undo",
+  "export",
+  "import",
+  "projects",
+  "tags",
+  "context",
+  "contexts",
+  "_commands",
+  "_show",
+  "_unique",
+  "help",
+  "version"
+];
+
 pub fn expand_command_abbrev<'a>(
   token: &'a str,
   known: &[&'a str]
@@ -79,8 +238,34 @@ pub fn expand_command_abbrev<'a>(
     return Some(token);
   }
 
+  let original = known
+    .iter()
+    .copied()
+    .filter(|name| {
+      ORIGINAL_COMMANDS.contains(name)
+    })
+    .collect::<Vec<_>>();
+  unique_prefix_match(token, &original)
+    .or_else(|| {
+      // Newer commands, several of which
+      // write to disk (`backup`,
+      // `restore`, `purge`), need at
+      // least two letters.
+      if token.chars().count() < 2 {
+        return None;
+      }
+      unique_prefix_match(token, known)
+    })
+}
+
+/// The one name starting with `token`,
+/// or `None` when none or several do.
+fn unique_prefix_match<'a>(
+  token: &str,
+  names: &[&'a str]
+) -> Option<&'a str> {
   let mut matches =
-    known.iter().copied().filter(
+    names.iter().copied().filter(
       |name| name.starts_with(token)
     );
   let first = matches.next()?;
@@ -101,12 +286,121 @@ pub fn dispatch(
   inv: Invocation
 ) -> anyhow::Result<()> {
   let now = Utc::now();
+  if inv.dry_run {
+    store.set_dry_run(true);
+    println!(
+      "DRY RUN: simulating '{}'; \
+       nothing will be written.",
+      inv.command
+    );
+  }
+  // Seeding happens before the history
+  // baseline so the example tasks do
+  // not show up as the command's own
+  // writes.
+  if !inv.dry_run
+    && cfg
+      .get_bool("seed_demo")
+      .unwrap_or(false)
+  {
+    let seeded =
+      demo::seed_if_empty(store, now)?;
+    if seeded > 0 {
+      info!(seeded, "seeded demo tasks");
+      eprintln!(
+        "Seeded {seeded} demo task(s); \
+         remove them with 'task demo \
+         clear'."
+      );
+    }
+  }
+  // Auto-archive records its own
+  // history and undo snapshot. `gc`
+  // runs it itself and reports it, and
+  // `undo`/`restore` must act on what
+  // the user last did, not on a fresh
+  // archive.
+  if !inv.dry_run
+    && !matches!(
+      inv.command.as_str(),
+      "gc" | "undo" | "redo" | "restore"
+    )
+    && let Some(days) =
+      archive::auto_archive_days(cfg)
+        .map_err(usage_error)?
+  {
+    let archived =
+      archive::run(store, days, now)?;
+    if archived > 0 {
+      eprintln!(
+        "Auto-archived {archived} \
+         completed task(s) older than \
+         {days} day(s)."
+      );
+    }
+  }
+  // Lazy recurrence: the instance after
+  // a completed one appears once it is
+  // within the horizon. Like
+  // auto-archive it is its own undo
+  // step, so `undo` and `restore` skip
+  // it.
+  if !inv.dry_run
+    && !matches!(
+      inv.command.as_str(),
+      "undo" | "redo" | "restore"
+    )
+    && RecurMode::from_config(cfg)
+      .map_err(usage_error)?
+      == RecurMode::Lazy
+  {
+    let created = recur::run_sweep(
+      store,
+      recur_horizon_days(cfg)
+        .map_err(usage_error)?,
+      now
+    )?;
+    if created > 0 {
+      eprintln!(
+        "Created {created} recurring \
+         task(s)."
+      );
+    }
+  }
+  let baseline = if inv.dry_run
+    || command_records_history(
+      &inv.command
+    ) {
+    Some(all_tasks(store)?)
+  } else {
+    None
+  };
   let hooks = HookRunner::new(
     cfg,
     &store.data_dir
-  );
+  )
+  .with_auto_tags(
+    AutoTagRules::from_config(cfg)
+      .map_err(usage_error)?
+  )
+  .with_dry_run(inv.dry_run);
+  if inv.dry_run && hooks.has_scripts()?
+  {
+    println!(
+      "DRY RUN: hook scripts are not \
+       run; autotag rules still apply."
+    );
+  }
   hooks.run_on_launch()?;
   let command = inv.command.as_str();
+  if !inv.dry_run
+    && command_runs_gc(cfg, command)
+    && cfg
+      .get_bool("gc")
+      .unwrap_or(true)
+  {
+    store.compact_ids()?;
+  }
   let effective_filters =
     resolve_effective_filter_terms(
       store,
@@ -114,6 +408,35 @@ pub fn dispatch(
       command,
       &inv.filter_terms
     )?;
+  // A uuid prefix has to name one task
+  // across every status, so check it
+  // once here rather than per command.
+  if effective_filters
+    .iter()
+    .any(|term| {
+      filter::is_uuid_prefix(
+        term.trim_matches(['(', ')'])
+      )
+    })
+  {
+    Filter::parse(&effective_filters, now)?
+      .ensure_unambiguous(&all_tasks(
+        store
+      )?)?;
+  }
+  // Each selector piped in with `-`
+  // should name a task. The command
+  // still runs on the ones that do.
+  let unmatched_stdin =
+    if inv.stdin_selectors.is_empty() {
+      0
+    } else {
+      report_unmatched_stdin_selectors(
+        &inv.stdin_selectors,
+        &all_tasks(store)?,
+        now
+      )?
+    };
 
   debug!(
       command,
@@ -122,7 +445,7 @@ pub fn dispatch(
       "dispatching command"
   );
 
-  match command {
+  let result = match command {
     | "add" => {
       cmd_add(
         store,
@@ -133,10 +456,21 @@ pub fn dispatch(
         now
       )
     }
+    | "inbox" => {
+      cmd_inbox(
+        store,
+        &hooks,
+        cfg,
+        renderer,
+        &inv.command_args,
+        now
+      )
+    }
     | "append" => {
       cmd_append(
         store,
         &hooks,
+        cfg,
         &effective_filters,
         &inv.command_args,
         now
@@ -146,6 +480,7 @@ pub fn dispatch(
       cmd_prepend(
         store,
         &hooks,
+        cfg,
         &effective_filters,
         &inv.command_args,
         now
@@ -161,6 +496,15 @@ pub fn dispatch(
         now
       )
     }
+    | "stale" => {
+      cmd_stale(
+        store,
+        cfg,
+        renderer,
+        &effective_filters,
+        now
+      )
+    }
     | "info" => {
       cmd_info(
         store,
@@ -169,18 +513,51 @@ pub fn dispatch(
         now
       )
     }
+    | "ids" => {
+      cmd_ids(
+        store,
+        &effective_filters,
+        now
+      )
+    }
+    | "subtasks" => {
+      cmd_subtasks(
+        store,
+        renderer,
+        &effective_filters,
+        now
+      )
+    }
+    | "history" => {
+      cmd_history(
+        store,
+        renderer,
+        &effective_filters,
+        now
+      )
+    }
     | "modify" => {
       cmd_modify(
         store,
         &hooks,
+        cfg,
         &effective_filters,
         &inv.command_args,
         now
       )
     }
+    | "autotag" => {
+      cmd_autotag(
+        store,
+        &hooks,
+        &effective_filters,
+        now
+      )
+    }
     | "start" => {
       cmd_start(
         store,
+        cfg,
         &hooks,
         &effective_filters,
         now
@@ -194,6 +571,33 @@ pub fn dispatch(
         now
       )
     }
+    | "snooze" => {
+      cmd_snooze(
+        store,
+        &hooks,
+        &effective_filters,
+        &inv.command_args,
+        now
+      )
+    }
+    | "explain" => {
+      cmd_explain(
+        store,
+        cfg,
+        &effective_filters,
+        &inv.command_args,
+        now
+      )
+    }
+    | "postpone" => {
+      cmd_postpone(
+        store,
+        &hooks,
+        &effective_filters,
+        &inv.command_args,
+        now
+      )
+    }
     | "annotate" => {
       cmd_annotate(
         store,
@@ -220,16 +624,54 @@ pub fn dispatch(
         now
       )
     }
+    | "dedupe" => {
+      cmd_dedupe(
+        store,
+        &hooks,
+        cfg,
+        renderer,
+        &effective_filters,
+        &inv.command_args,
+        now
+      )
+    }
     | "log" => {
       cmd_log(
         store,
         &hooks,
+        cfg,
         &inv.command_args,
         now
       )
     }
-    | "done" => {
+    | "done" | "skip" => {
       cmd_done(
+        store,
+        cfg,
+        &hooks,
+        &effective_filters,
+        command == "skip",
+        now
+      )
+    }
+    | "uncomplete" => {
+      cmd_uncomplete(
+        store,
+        &hooks,
+        &effective_filters,
+        now
+      )
+    }
+    | "archive" => {
+      cmd_archive(
+        store,
+        &hooks,
+        &effective_filters,
+        now
+      )
+    }
+    | "unarchive" => {
+      cmd_unarchive(
         store,
         &hooks,
         &effective_filters,
@@ -245,18 +687,87 @@ pub fn dispatch(
       )
     }
     | "undo" => cmd_undo(store),
+    | "redo" => cmd_redo(store),
+    | "purge" => cmd_purge(store),
+    | "gc" => {
+      cmd_gc(store, cfg, &inv.command_args, now)
+    }
+    | "backup" => {
+      cmd_backup(
+        store,
+        cfg,
+        &inv.command_args,
+        now
+      )
+    }
+    | "restore" => {
+      cmd_restore(
+        store,
+        cfg,
+        &inv.command_args,
+        now
+      )
+    }
     | "export" => {
       cmd_export(
         store,
+        cfg,
         &effective_filters,
+        &inv.command_args,
         now
       )
     }
     | "import" => {
-      cmd_import(store, &hooks)
+      cmd_import(
+        store,
+        cfg,
+        &inv.command_args,
+        &hooks
+      )
     }
     | "projects" => cmd_projects(store),
-    | "tags" => cmd_tags(store),
+    | "tags" => {
+      cmd_tags(
+        store,
+        &inv.command_args,
+        now
+      )
+    }
+    | "uda" => {
+      cmd_uda(
+        store,
+        cfg,
+        &inv.command_args,
+        now
+      )
+    }
+    | "stats" => {
+      cmd_stats(
+        store,
+        renderer,
+        &effective_filters,
+        &inv.command_args,
+        now
+      )
+    }
+    | "calendar" => {
+      cmd_calendar(
+        store,
+        cfg,
+        renderer,
+        &effective_filters,
+        &inv.command_args,
+        now
+      )
+    }
+    | "agenda" => {
+      cmd_agenda(
+        store,
+        cfg,
+        &effective_filters,
+        now
+      )
+    }
     | "context" | "contexts" => {
       cmd_context(
         store,
@@ -264,7 +775,54 @@ pub fn dispatch(
         &inv.command_args
       )
     }
+    | "config" => {
+      cmd_config(
+        store,
+        cfg,
+        &inv.command_args,
+        now
+      )
+    }
+    | "doctor" => {
+      cmd_doctor(
+        store,
+        cfg,
+        &inv.command_args
+      )
+    }
+    | "demo" => {
+      cmd_demo(store, &inv.command_args)
+    }
+    | "show" => {
+      cmd_show_config(
+        store,
+        cfg,
+        &inv.command_args
+      )
+    }
+    | "review" => {
+      cmd_review(
+        store,
+        cfg,
+        renderer,
+        &effective_filters,
+        inv.dry_run,
+        now
+      )
+    }
+    | "shell" => {
+      cmd_shell(
+        store,
+        cfg,
+        renderer,
+        inv.dry_run
+      )
+    }
+    | "rpc" => cmd_rpc(store, cfg, &hooks),
     | "_commands" => cmd_commands(),
+    | "_capabilities" => {
+      cmd_capabilities(cfg)
+    }
     | "_show" => cmd_show(cfg),
     | "_unique" => {
       cmd_unique(
@@ -291,11 +849,261 @@ pub fn dispatch(
           now
         )
       } else {
-        Err(anyhow!(
-          "unknown command: {other}"
+        Err(CommandError::usage(
+          format!(
+            "unknown command: {other}"
+          )
         ))
       }
     }
+  };
+
+  let Some(before) = baseline else {
+    return result.and_then(|()| {
+      stdin_match_outcome(
+        unmatched_stdin
+      )
+    });
+  };
+  if inv.dry_run {
+    result?;
+    print_dry_run_diff(
+      &before,
+      &all_tasks(store)?
+    );
+    return stdin_match_outcome(
+      unmatched_stdin
+    );
+  }
+  // Record whatever was written, even
+  // when the command failed part way.
+  let entries = diff_snapshots(
+    command,
+    &before,
+    &all_tasks(store)?,
+    now
+  );
+  store.append_history(&entries)?;
+  result.and_then(|()| {
+    stdin_match_outcome(unmatched_stdin)
+  })
+}
+
+/// Commands whose writes land in the
+/// audit log. `purge` is left out so
+/// the history it prunes is not
+/// re-added as removals.
+fn command_records_history(
+  command: &str
+) -> bool {
+  matches!(
+    command,
+    "add"
+      | "inbox"
+      | "append"
+      | "prepend"
+      | "modify"
+      | "autotag"
+      | "start"
+      | "stop"
+      | "snooze"
+      | "postpone"
+      | "annotate"
+      | "denotate"
+      | "duplicate"
+      | "dedupe"
+      | "log"
+      | "done"
+      | "skip"
+      | "uncomplete"
+      | "archive"
+      | "unarchive"
+      | "delete"
+      | "undo"
+      | "redo"
+      | "import"
+      | "doctor"
+      | "demo"
+      | "tags"
+      | "uda"
+  )
+}
+
+/// Reports renumber pending ids first
+/// (when `gc` is on) so the ids they
+/// print are compact; writes and `ids`
+/// never do, so ids a script already
+/// holds keep pointing at the same
+/// tasks.
+fn command_runs_gc(
+  cfg: &Config,
+  command: &str
+) -> bool {
+  matches!(
+    command,
+    "list" | "next" | "stale"
+  ) || is_report_command(cfg, command)
+}
+
+fn all_tasks(
+  store: &DataStore
+) -> anyhow::Result<Vec<Task>> {
+  let mut tasks =
+    store.load_pending()?;
+  tasks.extend(store.load_completed()?);
+  Ok(tasks)
+}
+
+/// Names every stdin selector that
+/// matches no task on stderr and
+/// returns how many there were. The
+/// command still runs on the rest.
+fn report_unmatched_stdin_selectors(
+  selectors: &[String],
+  tasks: &[Task],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<usize> {
+  let mut unmatched = 0;
+  for selector in selectors {
+    let filter = Filter::parse(
+      std::slice::from_ref(selector),
+      now
+    )?;
+    if !tasks
+      .iter()
+      .any(|task| filter.matches(task, now))
+    {
+      eprintln!(
+        "No task matches '{selector}' \
+         from stdin."
+      );
+      unmatched += 1;
+    }
+  }
+  Ok(unmatched)
+}
+
+/// Ends a batch that skipped stdin
+/// selectors in the no-match code, so a
+/// pipeline does not mistake it for a
+/// full success.
+fn stdin_match_outcome(
+  unmatched: usize
+) -> anyhow::Result<()> {
+  if unmatched > 0 {
+    return Err(
+      CommandError::no_match().context(
+        format!(
+          "{unmatched} stdin selector(s) \
+           matched no task"
+        )
+      )
+    );
   }
+  Ok(())
 }
 
+/// Prints the per-task field changes a
+/// dry run staged. `modified` is left
+/// out since every write bumps it.
+fn print_dry_run_diff(
+  before: &[Task],
+  after: &[Task]
+) {
+  let fields = |task: &Task| -> BTreeMap<
+    String,
+    Value
+  > {
+    match serde_json::to_value(task) {
+      | Ok(Value::Object(map)) => {
+        map
+          .into_iter()
+          .filter(|(key, _)| {
+            key != "modified"
+          })
+          .collect()
+      }
+      | _ => BTreeMap::new()
+    }
+  };
+  let show = |value: Option<&Value>| {
+    match value {
+      | None | Some(Value::Null) => {
+        "(none)".to_string()
+      }
+      | Some(Value::String(text)) => {
+        text.clone()
+      }
+      | Some(other) => other.to_string()
+    }
+  };
+  let label = |task: &Task| {
+    let uuid = task.uuid.to_string();
+    format!(
+      "{} '{}'",
+      &uuid[..8],
+      task.description
+    )
+  };
+
+  let before_by_uuid: BTreeMap<_, _> =
+    before
+      .iter()
+      .map(|task| (task.uuid, task))
+      .collect();
+  let after_by_uuid: BTreeMap<_, _> =
+    after
+      .iter()
+      .map(|task| (task.uuid, task))
+      .collect();
+
+  let mut changed = 0usize;
+  for (uuid, task) in &after_by_uuid {
+    let Some(old) =
+      before_by_uuid.get(uuid)
+    else {
+      changed += 1;
+      println!("  + {}", label(task));
+      continue;
+    };
+    let old_fields = fields(old);
+    let new_fields = fields(task);
+    let keys: BTreeSet<&String> =
+      old_fields
+        .keys()
+        .chain(new_fields.keys())
+        .collect();
+    let diffs: Vec<String> = keys
+      .into_iter()
+      .filter(|key| {
+        old_fields.get(*key)
+          != new_fields.get(*key)
+      })
+      .map(|key| {
+        format!(
+          "      {key}: {} -> {}",
+          show(old_fields.get(key)),
+          show(new_fields.get(key))
+        )
+      })
+      .collect();
+    if !diffs.is_empty() {
+      changed += 1;
+      println!("  ~ {}", label(task));
+      for line in diffs {
+        println!("{line}");
+      }
+    }
+  }
+  for (uuid, task) in &before_by_uuid {
+    if !after_by_uuid.contains_key(uuid)
+    {
+      changed += 1;
+      println!("  - {}", label(task));
+    }
+  }
+  println!(
+    "DRY RUN: {changed} task(s) would \
+     change; nothing was written."
+  );
+}
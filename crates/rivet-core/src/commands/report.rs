@@ -18,6 +18,24 @@ enum ReportColumn {
 }
 
 impl ReportColumn {
+  const NAMES: &[&str] = &[
+    "id",
+    "uuid",
+    "status",
+    "project",
+    "tags",
+    "priority",
+    "due",
+    "scheduled",
+    "wait",
+    "entry",
+    "modified",
+    "end",
+    "start",
+    "description",
+    "urgency"
+  ];
+
   fn parse(
     token: &str
   ) -> Option<Self> {
@@ -81,6 +99,250 @@ impl ReportColumn {
       | Self::Urgency => "Urgency"
     }
   }
+
+  fn is_date(self) -> bool {
+    matches!(
+      self,
+      Self::Due
+        | Self::Scheduled
+        | Self::Wait
+        | Self::Entry
+        | Self::Modified
+        | Self::End
+        | Self::Start
+    )
+  }
+
+  /// The `column.format` directives
+  /// this column takes, its default
+  /// first.
+  fn formats(
+    self
+  ) -> &'static [&'static str] {
+    match self {
+      | Self::Id => &["number"],
+      | Self::Uuid | Self::Status => {
+        &["long", "short"]
+      }
+      | Self::Project => {
+        &["full", "parent", "short"]
+      }
+      | Self::Tags => {
+        &["list", "count", "indicator"]
+      }
+      | Self::Priority => &["long"],
+      | Self::Description => {
+        &["full", "oneline", "count"]
+      }
+      | Self::Urgency => {
+        &["real", "integer", "Nf"]
+      }
+      | _ => {
+        &[
+          "formatted",
+          "relative",
+          "iso",
+          "epoch"
+        ]
+      }
+    }
+  }
+
+  fn parse_format(
+    self,
+    name: &str
+  ) -> Option<CellFormat> {
+    let name =
+      name.to_ascii_lowercase();
+    if self.formats().first()
+      == Some(&name.as_str())
+    {
+      return Some(CellFormat::Default);
+    }
+    match (self, name.as_str()) {
+      | (Self::Urgency, "integer") => {
+        Some(CellFormat::Decimals(0))
+      }
+      | (Self::Urgency, fixed) => {
+        fixed
+          .strip_suffix('f')
+          .and_then(|digits| {
+            digits.parse().ok()
+          })
+          .filter(|digits| {
+            *digits <= 9
+          })
+          .map(CellFormat::Decimals)
+      }
+      | (Self::Project, "parent") => {
+        Some(CellFormat::Parent)
+      }
+      | (
+        Self::Project
+        | Self::Uuid
+        | Self::Status,
+        "short"
+      ) => Some(CellFormat::Short),
+      | (
+        Self::Tags | Self::Description,
+        "count"
+      ) => Some(CellFormat::Count),
+      | (Self::Tags, "indicator") => {
+        Some(CellFormat::Indicator)
+      }
+      | (
+        Self::Description,
+        "oneline"
+      ) => Some(CellFormat::Oneline),
+      | (column, "relative")
+        if column.is_date() =>
+      {
+        Some(CellFormat::Relative)
+      }
+      | (column, "iso")
+        if column.is_date() =>
+      {
+        Some(CellFormat::Iso)
+      }
+      | (column, "epoch")
+        if column.is_date() =>
+      {
+        Some(CellFormat::Epoch)
+      }
+      | _ => None
+    }
+  }
+}
+
+/// How a report column renders its
+/// value, from `column.format` in
+/// `report.<name>.columns`.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+)]
+enum CellFormat {
+  #[default]
+  Default,
+  /// Dates relative to now, in the
+  /// `humanize` style.
+  Relative,
+  /// Dates as `YYYYMMDDTHHMMSSZ`.
+  Iso,
+  /// Dates as Unix seconds.
+  Epoch,
+  /// Urgency with this many decimals.
+  Decimals(usize),
+  /// The top-level project.
+  Parent,
+  /// The last project segment, the
+  /// uuid prefix or the status letter.
+  Short,
+  /// Annotations on the description's
+  /// line.
+  Oneline,
+  /// `[N]` tags, or the description
+  /// with `[N]` annotations.
+  Count,
+  /// `+` when the task has tags.
+  Indicator
+}
+
+/// One `column[.format][:align]` entry
+/// of `report.<name>.columns`.
+#[derive(Debug, Clone, Copy)]
+struct ReportCell {
+  column: ReportColumn,
+  format: CellFormat,
+  align:  Align
+}
+
+impl ReportCell {
+  fn plain(
+    column: ReportColumn
+  ) -> Self {
+    Self {
+      column,
+      format: CellFormat::Default,
+      align: Align::Left
+    }
+  }
+
+  /// `Ok(None)` for an unknown column,
+  /// which reports skip; an unknown
+  /// format or alignment is an error.
+  /// Numbers align right unless told
+  /// otherwise.
+  fn parse(
+    token: &str
+  ) -> anyhow::Result<Option<Self>> {
+    let (spec, align) =
+      match token.rsplit_once(':') {
+        | Some((spec, align)) => {
+          (
+            spec,
+            Some(Align::parse(align)?)
+          )
+        }
+        | None => (token, None)
+      };
+    let (name, format) =
+      match spec.split_once('.') {
+        | Some((name, format)) => {
+          (name, Some(format))
+        }
+        | None => (spec, None)
+      };
+    let Some(column) =
+      ReportColumn::parse(name)
+    else {
+      return Ok(None);
+    };
+    let format = match format {
+      | Some(format) => {
+        column
+          .parse_format(format)
+          .ok_or_else(|| {
+            anyhow!(
+              "unknown format \
+               '{name}.{format}' \
+               (valid: {})",
+              column.formats().join(
+                ", "
+              )
+            )
+          })?
+      }
+      | None => CellFormat::Default
+    };
+    let numeric = matches!(
+      column,
+      ReportColumn::Id
+        | ReportColumn::Urgency
+    ) || matches!(
+      (column, format),
+      (_, CellFormat::Epoch)
+        | (
+          ReportColumn::Tags,
+          CellFormat::Count
+        )
+    );
+    Ok(Some(Self {
+      column,
+      format,
+      align: align.unwrap_or(
+        if numeric {
+          Align::Right
+        } else {
+          Align::Left
+        }
+      )
+    }))
+  }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -92,41 +354,80 @@ struct SortSpec {
 #[derive(Debug, Clone)]
 struct ReportSpec {
   name:         String,
-  columns:      Vec<ReportColumn>,
+  columns:      Vec<ReportCell>,
   labels:       Vec<String>,
   sort:         Vec<SortSpec>,
   filter_terms: Vec<String>,
-  limit:        Option<usize>
+  limit:        Option<usize>,
+  urgency:      UrgencyCoefficients,
+  /// `report.<name>.template`: one
+  /// formatted line per task instead
+  /// of the column table.
+  template:
+    Option<LineTemplate<ReportColumn>>
 }
 
 fn is_report_command(
   cfg: &Config,
   command: &str
 ) -> bool {
-  cfg
-    .get(&format!(
-      "report.{command}.columns"
-    ))
-    .is_some()
+  ["columns", "template"].iter().any(
+    |key| {
+      cfg
+        .get(&format!(
+          "report.{command}.{key}"
+        ))
+        .is_some()
+    }
+  )
 }
 
 fn load_report_spec(
   cfg: &Config,
   report_name: &str
-) -> Option<ReportSpec> {
-  let columns_raw =
-    cfg.get(&format!(
-      "report.{report_name}.columns"
-    ))?;
-  let columns: Vec<ReportColumn> =
-    parse_config_list(&columns_raw)
-      .into_iter()
-      .filter_map(|token| {
-        ReportColumn::parse(&token)
+) -> anyhow::Result<Option<ReportSpec>> {
+  let template = cfg
+    .get(&format!(
+      "report.{report_name}.template"
+    ))
+    .filter(|raw| !raw.trim().is_empty())
+    .map(|raw| {
+      LineTemplate::parse(
+        &raw,
+        ReportColumn::parse
+      )
+      .map_err(|err| {
+        anyhow!(
+          "invalid \
+           report.{report_name}.\
+           template: {err} \
+           (placeholders: {})",
+          ReportColumn::NAMES.join(", ")
+        )
       })
-      .collect();
-  if columns.is_empty() {
-    return None;
+    })
+    .transpose()?;
+  let columns: Vec<ReportCell> = cfg
+    .get(&format!(
+      "report.{report_name}.columns"
+    ))
+    .map(|raw| parse_config_list(&raw))
+    .unwrap_or_default()
+    .iter()
+    .map(|token| ReportCell::parse(token))
+    .filter_map(Result::transpose)
+    .collect::<anyhow::Result<_>>()
+    .map_err(|err| {
+      anyhow!(
+        "invalid \
+         report.{report_name}.\
+         columns: {err}"
+      )
+    })?;
+  if columns.is_empty()
+    && template.is_none()
+  {
+    return Ok(None);
   }
 
   let labels_key = format!(
@@ -139,6 +440,7 @@ fn load_report_spec(
   while labels.len() < columns.len() {
     labels.push(
       columns[labels.len()]
+        .column
         .default_label()
         .to_string()
     );
@@ -170,14 +472,19 @@ fn load_report_spec(
     })
     .filter(|value| *value > 0);
 
-  Some(ReportSpec {
+  Ok(Some(ReportSpec {
     name: report_name.to_string(),
     columns,
     labels,
     sort,
     filter_terms,
-    limit
-  })
+    limit,
+    urgency:
+      UrgencyCoefficients::from_config(
+        cfg
+      ),
+    template
+  }))
 }
 
 fn parse_config_list(
@@ -228,6 +535,7 @@ fn compare_tasks_for_report(
   a: &Task,
   b: &Task,
   sort_specs: &[SortSpec],
+  urgency: &UrgencyCoefficients,
   now: chrono::DateTime<Utc>
 ) -> Ordering {
   for sort_spec in sort_specs {
@@ -236,6 +544,7 @@ fn compare_tasks_for_report(
         a,
         b,
         sort_spec.column,
+        urgency,
         now
       );
     if ordering != Ordering::Equal {
@@ -257,6 +566,7 @@ fn compare_tasks_on_column(
   a: &Task,
   b: &Task,
   column: ReportColumn,
+  urgency: &UrgencyCoefficients,
   now: chrono::DateTime<Utc>
 ) -> Ordering {
   match column {
@@ -335,10 +645,10 @@ fn compare_tasks_on_column(
         )
     }
     | ReportColumn::Urgency => {
-      task_urgency(a, now)
-        .partial_cmp(&task_urgency(
-          b, now
-        ))
+      a.urgency(now, urgency)
+        .partial_cmp(
+          &b.urgency(now, urgency)
+        )
         .unwrap_or(Ordering::Equal)
     }
   }
@@ -358,40 +668,110 @@ fn cmp_optional<T: Ord>(
   }
 }
 
+/// `relative` renders
+/// `CellFormat::Relative` dates;
+/// `dates` the rest of the default
+/// ones.
 fn format_report_cell(
   task: &Task,
-  column: ReportColumn,
+  cell: ReportCell,
+  ids: IdDisplay,
+  dates: &DateFormat,
+  relative: &DateFormat,
+  urgency: &UrgencyCoefficients,
   now: chrono::DateTime<Utc>
 ) -> String {
-  match column {
-    | ReportColumn::Id => {
-      task
-        .id
-        .map(|id| id.to_string())
-        .unwrap_or_else(|| {
-          "-".to_string()
-        })
-    }
+  let format_date = |date: Option<
+    chrono::DateTime<Utc>
+  >| {
+    date
+      .map(|date| {
+        match cell.format {
+          | CellFormat::Relative => {
+            relative.format(date, now)
+          }
+          | CellFormat::Iso => {
+            date
+              .format("%Y%m%dT%H%M%SZ")
+              .to_string()
+          }
+          | CellFormat::Epoch => {
+            date.timestamp().to_string()
+          }
+          | _ => dates.format(date, now)
+        }
+      })
+      .unwrap_or_default()
+  };
+
+  match cell.column {
+    | ReportColumn::Id => ids.format(task),
     | ReportColumn::Uuid => {
-      task.uuid.to_string()
+      if cell.format == CellFormat::Short
+      {
+        task.short_uuid()
+      } else {
+        task.uuid.to_string()
+      }
     }
     | ReportColumn::Status => {
-      display_status(task, now)
-        .to_string()
+      let status =
+        display_status(task, now);
+      if cell.format == CellFormat::Short
+      {
+        status[..1].to_ascii_uppercase()
+      } else {
+        status.to_string()
+      }
     }
     | ReportColumn::Project => {
-      task
+      let project = task
         .project
-        .clone()
-        .unwrap_or_default()
+        .as_deref()
+        .unwrap_or_default();
+      match cell.format {
+        | CellFormat::Parent => {
+          project
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+        }
+        | CellFormat::Short => {
+          project
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+        }
+        | _ => project.to_string()
+      }
     }
     | ReportColumn::Tags => {
-      task
-        .tags
-        .iter()
-        .map(|tag| format!("+{tag}"))
-        .collect::<Vec<_>>()
-        .join(" ")
+      match cell.format {
+        | CellFormat::Count
+          if !task.tags.is_empty() =>
+        {
+          format!("[{}]", task.tags.len())
+        }
+        | CellFormat::Indicator
+          if !task.tags.is_empty() =>
+        {
+          "+".to_string()
+        }
+        | CellFormat::Count
+        | CellFormat::Indicator => {
+          String::new()
+        }
+        | _ => {
+          task
+            .tags
+            .iter()
+            .map(|tag| format!("+{tag}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+        }
+      }
     }
     | ReportColumn::Priority => {
       task
@@ -400,46 +780,74 @@ fn format_report_cell(
         .unwrap_or_default()
     }
     | ReportColumn::Due => {
-      format_report_date(task.due)
+      format_date(task.due)
     }
     | ReportColumn::Scheduled => {
-      format_report_date(task.scheduled)
+      format_date(task.scheduled)
     }
     | ReportColumn::Wait => {
-      format_report_date(task.wait)
+      format_date(task.wait)
     }
     | ReportColumn::Entry => {
-      format_project_date(task.entry)
+      format_date(Some(task.entry))
     }
     | ReportColumn::Modified => {
-      format_project_date(task.modified)
+      format_date(Some(task.modified))
     }
     | ReportColumn::End => {
-      format_report_date(task.end)
+      format_date(task.end)
     }
     | ReportColumn::Start => {
-      format_report_date(task.start)
+      format_date(task.start)
     }
     | ReportColumn::Description => {
-      task.description.clone()
+      match cell.format {
+        | CellFormat::Oneline => {
+          std::iter::once(
+            task.description.as_str()
+          )
+          .chain(
+            task.annotations.iter().map(
+              |note| {
+                note
+                  .description
+                  .as_str()
+              }
+            )
+          )
+          .collect::<Vec<_>>()
+          .join(" ")
+        }
+        | CellFormat::Count
+          if !task
+            .annotations
+            .is_empty() =>
+        {
+          format!(
+            "{} [{}]",
+            task.description,
+            task.annotations.len()
+          )
+        }
+        | _ => task.description.clone()
+      }
     }
     | ReportColumn::Urgency => {
+      let decimals = match cell.format
+      {
+        | CellFormat::Decimals(
+          decimals
+        ) => decimals,
+        | _ => 3
+      };
       format!(
-        "{:.3}",
-        task_urgency(task, now)
+        "{:.decimals$}",
+        task.urgency(now, urgency)
       )
     }
   }
 }
 
-fn format_report_date(
-  date: Option<chrono::DateTime<Utc>>
-) -> String {
-  date
-    .map(format_project_date)
-    .unwrap_or_default()
-}
-
 fn display_status(
   task: &Task,
   now: chrono::DateTime<Utc>
@@ -455,73 +863,9 @@ fn display_status(
         "completed"
       }
       | Status::Deleted => "deleted",
-      | Status::Waiting => "waiting"
+      | Status::Waiting => "waiting",
+      | Status::Archived => "archived"
     }
   }
 }
 
-fn task_urgency(
-  task: &Task,
-  now: chrono::DateTime<Utc>
-) -> f64 {
-  if matches!(
-    task.status,
-    Status::Completed | Status::Deleted
-  ) {
-    return 0.0;
-  }
-
-  let mut urgency = 0.0;
-
-  urgency +=
-    task.tags.len() as f64 * 0.8;
-
-  if let Some(priority) =
-    task.priority.as_deref()
-  {
-    urgency += match priority
-      .to_ascii_uppercase()
-      .as_str()
-    {
-      | "H" => 6.0,
-      | "M" => 3.9,
-      | "L" => 1.8,
-      | _ => 0.0
-    };
-  }
-
-  if task.start.is_some()
-    && !task.is_waiting(now)
-  {
-    urgency += 4.0;
-  }
-  if task.is_waiting(now) {
-    urgency -= 3.0;
-  }
-  if !task.depends.is_empty() {
-    urgency -= 5.0;
-  }
-
-  if let Some(due) = task.due {
-    let delta = due - now;
-    let days = delta.num_minutes()
-      as f64
-      / (60.0 * 24.0);
-    urgency += if days <= -1.0 {
-      9.7
-    } else if days <= 0.0 {
-      9.3
-    } else if days <= 1.0 {
-      8.8
-    } else if days <= 2.0 {
-      8.4
-    } else if days <= 7.0 {
-      6.0
-    } else {
-      3.0
-    };
-  }
-
-  urgency
-}
-
@@ -1,17 +1,30 @@
 #[instrument(skip(
-  store, hooks, _cfg, _renderer, args,
+  store, hooks, cfg, _renderer, args,
   now
 ))]
 fn cmd_add(
   store: &mut DataStore,
   hooks: &HookRunner,
-  _cfg: &Config,
+  cfg: &Config,
   _renderer: &mut Renderer,
   args: &[String],
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
   info!("command add");
 
+  if args
+    .first()
+    .is_some_and(|arg| arg == "--batch")
+  {
+    return cmd_add_batch(
+      store,
+      hooks,
+      cfg,
+      &args[1..],
+      now
+    );
+  }
+
   let mut pending =
     store.load_pending()?;
   let completed =
@@ -19,18 +32,50 @@ fn cmd_add(
   let pending_before = pending.clone();
 
   let next_id = store.next_id(&pending);
+  let args = autocorrect_quick_add(
+    cfg, args, &pending, &completed
+  );
+  let times =
+    DefaultTimes::from_config(cfg)
+      .map_err(usage_error)?;
   let (description, mods) =
-    parse_desc_and_mods(args, now)?;
+    parse_desc_and_mods(
+      &args, now, &times
+    )?;
   let mut task = Task::new_pending(
     description,
     now,
     next_id
   );
   apply_mods(&mut task, &mods, now)?;
+  enforce_description_limit(
+    &DescriptionLimit::from_config(cfg)
+      .map_err(usage_error)?,
+    &mut task,
+    now
+  )?;
+  TaskDefaults::from_config(
+    cfg,
+    store
+      .get_active_context()?
+      .as_deref()
+  )
+  .apply(&mut task);
   task = hooks.apply_on_add(&task)?;
   if task.id.is_none() {
     task.id = Some(next_id);
   }
+  if task.parent.is_some() {
+    let all: Vec<Task> = pending
+      .iter()
+      .chain(&completed)
+      .chain(std::iter::once(&task))
+      .cloned()
+      .collect();
+    check_parent_links(&all, &[
+      task.uuid
+    ])?;
+  }
 
   pending = store
     .add_task(pending, task.clone())?;
@@ -50,9 +95,292 @@ fn cmd_add(
   Ok(())
 }
 
+/// `inbox <text>`: quick capture
+/// through `add`, tagged with
+/// `inbox.tag` (default `inbox`) and
+/// preceded by any `inbox.modifiers`,
+/// so explicit modifiers on the line
+/// still win.
+#[instrument(skip(
+  store, hooks, cfg, renderer, args,
+  now
+))]
+fn cmd_inbox(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command inbox");
+
+  if args.is_empty() {
+    return Err(CommandError::usage(
+      "inbox requires text to capture"
+    ));
+  }
+
+  let tag = cfg
+    .get("inbox.tag")
+    .map(|value| {
+      value.trim().to_string()
+    })
+    .filter(|value| !value.is_empty())
+    .unwrap_or_else(|| {
+      "inbox".to_string()
+    });
+
+  let mut add_args = Vec::new();
+  if let Some(raw) =
+    cfg.get("inbox.modifiers")
+  {
+    let times =
+      DefaultTimes::from_config(cfg)?;
+    for word in split_command_line(&raw)
+      .context(
+        "invalid inbox.modifiers"
+      )?
+    {
+      if parse_one_mod(
+        &word, now, &times
+      )?
+      .is_none()
+      {
+        return Err(anyhow!(
+          "inbox.modifiers: not a \
+           modifier: {word}"
+        ));
+      }
+      add_args.push(word);
+    }
+  }
+  add_args.push(format!("+{tag}"));
+  add_args.extend(args.iter().cloned());
+
+  cmd_add(
+    store, hooks, cfg, renderer,
+    &add_args, now
+  )
+}
+
+/// Applies `description.max_length`,
+/// saying so when it truncates.
+fn enforce_description_limit(
+  limit: &DescriptionLimit,
+  task: &mut Task,
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  if limit
+    .apply(task, now)
+    .map_err(usage_error)?
+  {
+    eprintln!(
+      "Warning: description cut to \
+       {} characters \
+       (description.max_length); the \
+       full text is kept as an \
+       annotation.",
+      limit.max_length.unwrap_or(0)
+    );
+  }
+  Ok(())
+}
+
+/// `add --batch`: one task per stdin
+/// line, parsed like `add` arguments.
+/// Applies `correct_quick_add_tokens`
+/// unless `quickadd.autocorrect` is
+/// off, warning about each rewrite.
+fn autocorrect_quick_add(
+  cfg: &Config,
+  args: &[String],
+  pending: &[Task],
+  completed: &[Task]
+) -> Vec<String> {
+  if !cfg
+    .get_bool("quickadd.autocorrect")
+    .unwrap_or(true)
+  {
+    return args.to_vec();
+  }
+  let known: Vec<Task> = pending
+    .iter()
+    .chain(completed)
+    .cloned()
+    .collect();
+  let (args, corrections) =
+    correct_quick_add_tokens(
+      args, &known
+    );
+  for fix in corrections {
+    eprintln!(
+      "Warning: read '{}' as '{}' \
+       (set quickadd.autocorrect=off \
+       to keep it).",
+      fix.original, fix.corrected
+    );
+  }
+  args
+}
+
+/// Modifiers after `--batch` apply to
+/// every line before the line's own.
+#[instrument(skip(
+  store,
+  hooks,
+  cfg,
+  shared_args,
+  now
+))]
+fn cmd_add_batch(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  cfg: &Config,
+  shared_args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command add --batch");
+
+  let times =
+    DefaultTimes::from_config(cfg)
+      .map_err(usage_error)?;
+  let shared = parse_mods(
+    shared_args,
+    now,
+    &times
+  )?;
+  let limit =
+    DescriptionLimit::from_config(cfg)
+      .map_err(usage_error)?;
+  let defaults =
+    TaskDefaults::from_config(
+      cfg,
+      store
+        .get_active_context()?
+        .as_deref()
+    );
+  let mut input = String::new();
+  io::stdin()
+    .read_to_string(&mut input)
+    .context("failed reading stdin")?;
+
+  let mut pending =
+    store.load_pending()?;
+  let completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+
+  let mut created = 0_u64;
+  let mut failures = Vec::new();
+  for (idx, line) in
+    input.lines().enumerate()
+  {
+    let trimmed = line.trim();
+    if trimmed.is_empty()
+      || trimmed.starts_with('#')
+    {
+      continue;
+    }
+
+    let next_id =
+      store.next_id(&pending);
+    let task =
+      split_command_line(trimmed)
+        .and_then(|words| {
+          let words =
+            autocorrect_quick_add(
+              cfg, &words, &pending,
+              &completed
+            );
+          let (description, mods) =
+            parse_desc_and_mods(
+              &words, now, &times
+            )?;
+          let mut task =
+            Task::new_pending(
+              description,
+              now,
+              next_id
+            );
+          apply_mods(
+            &mut task, &shared, now
+          )?;
+          apply_mods(
+            &mut task, &mods, now
+          )?;
+          enforce_description_limit(
+            &limit, &mut task, now
+          )?;
+          defaults.apply(&mut task);
+          let mut task = hooks
+            .apply_on_add(&task)?;
+          if task.id.is_none() {
+            task.id = Some(next_id);
+          }
+          if task.parent.is_some() {
+            let all: Vec<Task> =
+              pending
+                .iter()
+                .chain(&completed)
+                .chain(std::iter::once(
+                  &task
+                ))
+                .cloned()
+                .collect();
+            check_parent_links(
+              &all,
+              &[task.uuid]
+            )?;
+          }
+          Ok(task)
+        });
+
+    match task {
+      | Ok(task) => {
+        pending.push(task);
+        created += 1;
+      }
+      | Err(err) => {
+        failures.push(format!(
+          "line {}: {err:#}",
+          idx + 1
+        ));
+      }
+    }
+  }
+
+  if created > 0 {
+    pending.sort_by_key(|t| {
+      t.id.unwrap_or(u64::MAX)
+    });
+    store.save_pending(&pending)?;
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+  }
+
+  for failure in &failures {
+    println!("{failure}");
+  }
+  println!(
+    "Created {created} task(s)."
+  );
+
+  if !failures.is_empty() {
+    return Err(anyhow!(
+      "add --batch: {} line(s) failed",
+      failures.len()
+    ));
+  }
+  Ok(())
+}
+
 #[instrument(skip(
   store,
   hooks,
+  cfg,
   filter_terms,
   args,
   now
@@ -60,6 +388,7 @@ fn cmd_add(
 fn cmd_append(
   store: &mut DataStore,
   hooks: &HookRunner,
+  cfg: &Config,
   filter_terms: &[String],
   args: &[String],
   now: chrono::DateTime<Utc>
@@ -67,11 +396,14 @@ fn cmd_append(
   info!("command append");
 
   if args.is_empty() {
-    return Err(anyhow!(
+    return Err(CommandError::usage(
       "append requires text argument"
     ));
   }
   let suffix = args.join(" ");
+  let limit =
+    DescriptionLimit::from_config(cfg)
+      .map_err(usage_error)?;
 
   let filter =
     Filter::parse(filter_terms, now)?;
@@ -102,6 +434,9 @@ fn cmd_append(
       )
       .trim()
       .to_string();
+      enforce_description_limit(
+        &limit, task, now
+      )?;
       task.modified = now;
       *task = hooks
         .apply_on_modify(&old, task)?;
@@ -119,6 +454,9 @@ fn cmd_append(
         )
         .trim()
         .to_string();
+        enforce_description_limit(
+          &limit, task, now
+        )?;
         task.modified = now;
         *task = hooks.apply_on_modify(
           &old, task
@@ -143,12 +481,13 @@ fn cmd_append(
   println!(
     "Modified {changed} task(s)."
   );
-  Ok(())
+  ensure_matched(changed)
 }
 
 #[instrument(skip(
   store,
   hooks,
+  cfg,
   filter_terms,
   args,
   now
@@ -156,6 +495,7 @@ fn cmd_append(
 fn cmd_prepend(
   store: &mut DataStore,
   hooks: &HookRunner,
+  cfg: &Config,
   filter_terms: &[String],
   args: &[String],
   now: chrono::DateTime<Utc>
@@ -163,11 +503,14 @@ fn cmd_prepend(
   info!("command prepend");
 
   if args.is_empty() {
-    return Err(anyhow!(
+    return Err(CommandError::usage(
       "prepend requires text argument"
     ));
   }
   let prefix = args.join(" ");
+  let limit =
+    DescriptionLimit::from_config(cfg)
+      .map_err(usage_error)?;
 
   let filter =
     Filter::parse(filter_terms, now)?;
@@ -198,6 +541,9 @@ fn cmd_prepend(
       )
       .trim()
       .to_string();
+      enforce_description_limit(
+        &limit, task, now
+      )?;
       task.modified = now;
       *task = hooks
         .apply_on_modify(&old, task)?;
@@ -215,6 +561,9 @@ fn cmd_prepend(
         )
         .trim()
         .to_string();
+        enforce_description_limit(
+          &limit, task, now
+        )?;
         task.modified = now;
         *task = hooks.apply_on_modify(
           &old, task
@@ -239,7 +588,7 @@ fn cmd_prepend(
   println!(
     "Modified {changed} task(s)."
   );
-  Ok(())
+  ensure_matched(changed)
 }
 
 #[instrument(skip(
@@ -269,7 +618,7 @@ fn cmd_list(
   if let Some(spec) = load_report_spec(
     cfg,
     effective_report_name
-  ) {
+  )? {
     return run_report(
       store,
       renderer,
@@ -279,27 +628,100 @@ fn cmd_list(
     );
   }
 
-  let mut pending =
-    store.load_pending()?;
-  pending.retain(|task| {
-    task.status == Status::Pending
-      || task.status == Status::Waiting
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  // The index narrows the scan to the
+  // filter's tag, project or status
+  // bucket before the full match.
+  let keys = filter.index_keys();
+  let mut rows: Vec<Task> = store
+    .pending_index()?
+    .candidates(&keys)
+    .into_iter()
+    .filter(|task| {
+      (task.status == Status::Pending
+        || task.status
+          == Status::Waiting)
+        && filter.matches(task, now)
+    })
+    .cloned()
+    .collect();
+  // Archived tasks live with the
+  // completed ones; list shows them
+  // only when the filter names them.
+  if filter.has_explicit_status_filter()
+  {
+    rows.extend(
+      store
+        .completed_index()?
+        .candidates(&keys)
+        .into_iter()
+        .filter(|task| {
+          task.status
+            == Status::Archived
+            && filter.matches(task, now)
+        })
+        .cloned()
+    );
+  }
+
+  rows.sort_by_key(|task| {
+    (task.due, task.id)
   });
+  renderer
+    .print_task_table(&rows, now)?;
+  if rows.is_empty() {
+    return Err(CommandError::no_match());
+  }
+  Ok(())
+}
+
+#[instrument(skip(
+  store,
+  cfg,
+  renderer,
+  filter_terms,
+  now
+))]
+fn cmd_stale(
+  store: &mut DataStore,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let policy =
+    StalePolicy::from_config(cfg)
+      .map_err(usage_error)?;
+  info!(
+    days = policy.days,
+    "command stale"
+  );
 
   let filter =
     Filter::parse(filter_terms, now)?;
-  let mut rows: Vec<Task> = pending
+  let mut rows: Vec<Task> = store
+    .pending_index()?
+    .candidates(&filter.index_keys())
     .into_iter()
     .filter(|task| {
-      filter.matches(task, now)
+      task.is_stale(now, &policy)
+        && filter
+          .matches_without_waiting_guard(
+            task, now
+          )
     })
+    .cloned()
     .collect();
 
   rows.sort_by_key(|task| {
-    (task.due, task.id)
+    (task.modified, task.id)
   });
   renderer
     .print_task_table(&rows, now)?;
+  if rows.is_empty() {
+    return Err(CommandError::no_match());
+  }
   Ok(())
 }
 
@@ -319,12 +741,12 @@ fn cmd_report(
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
   let spec =
-    load_report_spec(cfg, report_name)
+    load_report_spec(cfg, report_name)?
       .ok_or_else(|| {
-        anyhow!(
+        CommandError::usage(format!(
           "unknown report: \
            {report_name}"
-        )
+        ))
       })?;
   run_report(
     store,
@@ -376,93 +798,603 @@ fn run_report(
 
   rows.sort_by(|a, b| {
     compare_tasks_for_report(
-      a, b, &spec.sort, now
+      a,
+      b,
+      &spec.sort,
+      &spec.urgency,
+      now
     )
   });
-  if let Some(limit) = spec.limit {
-    rows.truncate(limit);
+  if renderer.limit().is_none()
+    && let Some(limit) = spec.limit
+  {
+    renderer.set_limit(Some(
+      RowLimit::Rows(limit)
+    ));
   }
 
-  let table_rows: Vec<Vec<String>> =
-    rows
-      .iter()
-      .map(|task| {
-        spec
-          .columns
-          .iter()
-          .map(|col| {
-            format_report_cell(
-              task, *col, now
-            )
-          })
-          .collect()
-      })
-      .collect();
-
+  let dates =
+    renderer.report_date_format().clone();
+  let relative =
+    renderer.relative_date_format();
+  let ids = renderer.id_display();
+  if let Some(template) = &spec.template
+  {
+    renderer.print_report_lines(
+      &rows,
+      |task| {
+        template.render(|col| {
+          // A missing id is blank
+          // here, not the table's `-`.
+          if matches!(
+            col,
+            ReportColumn::Id
+          ) && ids == IdDisplay::Id
+            && task.id.is_none()
+          {
+            return String::new();
+          }
+          format_report_cell(
+            task,
+            ReportCell::plain(*col),
+            ids,
+            &dates,
+            &relative,
+            &spec.urgency,
+            now
+          )
+        })
+      }
+    )?;
+    if rows.is_empty() {
+      return Err(
+        CommandError::no_match()
+      );
+    }
+    return Ok(());
+  }
+  let aligns: Vec<Align> = spec
+    .columns
+    .iter()
+    .map(|cell| cell.align)
+    .collect();
   renderer.print_report_table(
     &spec.labels,
-    &table_rows
+    &aligns,
+    &rows,
+    |task| {
+      spec
+        .columns
+        .iter()
+        .map(|cell| {
+          format_report_cell(
+            task,
+            *cell,
+            ids,
+            &dates,
+            &relative,
+            &spec.urgency,
+            now
+          )
+        })
+        .collect()
+    }
   )?;
+  if rows.is_empty() {
+    return Err(CommandError::no_match());
+  }
   Ok(())
 }
 
+#[derive(Debug, serde::Serialize)]
+struct ExplainReason {
+  /// `status`, `hidden`, `context`,
+  /// `report`, `filter` or `limit`.
+  kind: &'static str,
+  text: String
+}
+
+#[derive(Debug, serde::Serialize)]
+struct TaskExplanation<'a> {
+  uuid:        String,
+  id:          Option<u64>,
+  description: &'a str,
+  view:        &'a str,
+  context:     Option<&'a str>,
+  shown:       bool,
+  reasons:     Vec<ExplainReason>
+}
+
+/// `task <filter> explain [report]
+/// [filter...] [--json]`: whether each
+/// selected task shows in a report
+/// (`next` by default) and, if not,
+/// which conditions drop it: the
+/// statuses the view lists, the
+/// waiting/archived guard, the active
+/// context, the report's own filter,
+/// the extra terms and the report
+/// limit.
 #[instrument(skip(
   store,
-  renderer,
+  cfg,
   filter_terms,
+  args,
   now
 ))]
-fn cmd_info(
+fn cmd_explain(
   store: &mut DataStore,
-  renderer: &mut Renderer,
+  cfg: &Config,
   filter_terms: &[String],
+  args: &[String],
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
-  info!("command info");
+  info!("command explain");
 
-  let pending = store.load_pending()?;
-  let completed =
-    store.load_completed()?;
-  let filter =
-    Filter::parse(filter_terms, now)?;
+  if filter_terms.is_empty() {
+    return Err(usage_error(anyhow!(
+      "usage: task <filter> explain \
+       [report] [filter...] [--json]"
+    )));
+  }
+  let json =
+    args.iter().any(|arg| arg == "--json");
+  let mut extra_terms: Vec<String> = args
+    .iter()
+    .filter(|arg| *arg != "--json")
+    .cloned()
+    .collect();
+  let view = match extra_terms.first() {
+    | Some(first)
+      if matches!(
+        first.as_str(),
+        "list" | "next"
+      ) || is_report_command(
+        cfg, first
+      ) =>
+    {
+      extra_terms.remove(0)
+    }
+    | _ => "next".to_string()
+  };
 
-  let mut rows: Vec<Task> = pending
-    .into_iter()
-    .chain(completed)
+  let selector =
+    Filter::parse(filter_terms, now)?;
+  let mut all = store.load_pending()?;
+  all.extend(store.load_completed()?);
+  let selected: Vec<&Task> = all
+    .iter()
     .filter(|task| {
-      filter.matches(task, now)
+      selector
+        .matches_without_waiting_guard(
+          task, now
+        )
     })
     .collect();
-
-  rows.sort_by_key(|task| {
-    task.id.unwrap_or(u64::MAX)
-  });
-
-  if rows.is_empty() {
-    return Err(anyhow!(
-      "no matching tasks"
-    ));
+  if selected.is_empty() {
+    return Err(CommandError::no_match());
   }
 
-  for task in rows {
-    renderer.print_task_info(&task)?;
-    println!();
+  // The same pieces `list` and reports
+  // join: report filter, then context,
+  // then the terms typed.
+  let context =
+    store.get_active_context()?;
+  let context_terms: Vec<String> =
+    context
+      .as_ref()
+      .and_then(|name| {
+        cfg.get(&format!("context.{name}"))
+      })
+      .map(|expr| {
+        expr
+          .split_whitespace()
+          .map(ToString::to_string)
+          .collect()
+      })
+      .unwrap_or_default();
+  let spec = load_report_spec(
+    cfg,
+    if view == "list" {
+      "next"
+    } else {
+      &view
+    }
+  )?;
+  let report_terms = spec
+    .as_ref()
+    .map(|spec| spec.filter_terms.clone())
+    .unwrap_or_default();
+  let combined_terms: Vec<String> =
+    report_terms
+      .iter()
+      .chain(&context_terms)
+      .chain(&extra_terms)
+      .cloned()
+      .collect();
+  let context_filter =
+    Filter::parse(&context_terms, now)?;
+  let report_filter =
+    Filter::parse(&report_terms, now)?;
+  let extra_filter =
+    Filter::parse(&extra_terms, now)?;
+  let combined =
+    Filter::parse(&combined_terms, now)?;
+
+  // Only needed to place a task against
+  // `report.<name>.limit`.
+  let limit =
+    spec.as_ref().and_then(|spec| {
+      spec.limit.map(|limit| (spec, limit))
+    });
+  let mut rows: Vec<&Task> = Vec::new();
+  if let Some((spec, _)) = limit {
+    rows = all
+      .iter()
+      .filter(|task| {
+        combined.matches(task, now)
+      })
+      .collect();
+    rows.sort_by(|a, b| {
+      compare_tasks_for_report(
+        a,
+        b,
+        &spec.sort,
+        &spec.urgency,
+        now
+      )
+    });
   }
 
-  Ok(())
-}
-
-#[instrument(skip(
-  store,
-  hooks,
-  filter_terms,
-  args,
-  now
-))]
-fn cmd_modify(
-  store: &mut DataStore,
-  hooks: &HookRunner,
-  filter_terms: &[String],
+  let explanations: Vec<TaskExplanation> =
+    selected
+      .into_iter()
+      .map(|task| {
+        let mut reasons = Vec::new();
+        let reason =
+          |kind: &'static str, text| {
+            ExplainReason {
+              kind,
+              text
+            }
+          };
+        let listed = matches!(
+          task.status,
+          Status::Pending
+            | Status::Waiting
+        ) || (task.status
+          == Status::Archived
+          && combined
+            .has_explicit_status_filter());
+        if spec.is_none() && !listed {
+          reasons.push(reason(
+            "status",
+            format!(
+              "hidden: status={} \
+               ({view} lists pending \
+               and waiting tasks)",
+              display_status(task, now)
+            )
+          ));
+        } else if let Some(hidden) =
+          combined.hidden_reason(task, now)
+        {
+          reasons.push(reason(
+            "hidden",
+            format!("hidden: {hidden}")
+          ));
+        }
+        for term in context_filter
+          .unmet_terms(task, now)
+        {
+          reasons.push(reason(
+            "context",
+            format!(
+              "excluded by context \
+               '{}': {term}",
+              context
+                .as_deref()
+                .unwrap_or_default()
+            )
+          ));
+        }
+        for term in report_filter
+          .unmet_terms(task, now)
+        {
+          reasons.push(reason(
+            "report",
+            format!(
+              "excluded by report \
+               '{view}' filter: {term}"
+            )
+          ));
+        }
+        for term in extra_filter
+          .unmet_terms(task, now)
+        {
+          reasons.push(reason(
+            "filter",
+            format!(
+              "filtered out by {term}"
+            )
+          ));
+        }
+        // `or` spanning the pieces can
+        // fail as a whole only.
+        if reasons.is_empty()
+          && !combined.matches(task, now)
+        {
+          reasons.push(reason(
+            "filter",
+            "filtered out by the \
+             combined filter"
+              .to_string()
+          ));
+        }
+        if reasons.is_empty()
+          && let Some((_, limit)) = limit
+          && let Some(row) = rows
+            .iter()
+            .position(|row| {
+              row.uuid == task.uuid
+            })
+          && row >= limit
+        {
+          reasons.push(reason(
+            "limit",
+            format!(
+              "past report '{view}' \
+               limit: row {} of {}, \
+               limit {limit}",
+              row + 1,
+              rows.len()
+            )
+          ));
+        }
+        TaskExplanation {
+          uuid: task.uuid.to_string(),
+          id: task.id,
+          description: &task.description,
+          view: &view,
+          context: context.as_deref(),
+          shown: reasons.is_empty(),
+          reasons
+        }
+      })
+      .collect();
+
+  if json {
+    println!(
+      "{}",
+      serde_json::to_string_pretty(
+        &explanations
+      )?
+    );
+    return Ok(());
+  }
+  for explanation in &explanations {
+    let label = explanation
+      .id
+      .map_or_else(
+        || {
+          explanation.uuid[..8]
+            .to_string()
+        },
+        |id| id.to_string()
+      );
+    if explanation.shown {
+      println!(
+        "Task {label} '{}' is shown in \
+         {view}.",
+        explanation.description
+      );
+      continue;
+    }
+    println!(
+      "Task {label} '{}' is hidden from \
+       {view}:",
+      explanation.description
+    );
+    for reason in &explanation.reasons {
+      println!("  {}", reason.text);
+    }
+  }
+  Ok(())
+}
+
+#[instrument(skip(
+  store,
+  renderer,
+  filter_terms,
+  now
+))]
+fn cmd_info(
+  store: &mut DataStore,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command info");
+
+  let pending = store.load_pending()?;
+  let completed =
+    store.load_completed()?;
+  let filter =
+    Filter::parse(filter_terms, now)?;
+
+  let all: Vec<Task> = pending
+    .into_iter()
+    .chain(completed)
+    .collect();
+  let mut rows: Vec<&Task> = all
+    .iter()
+    .filter(|task| {
+      filter.matches(task, now)
+    })
+    .collect();
+
+  rows.sort_by_key(|task| {
+    task.id.unwrap_or(u64::MAX)
+  });
+
+  if rows.is_empty() {
+    return Err(CommandError::no_match());
+  }
+
+  for task in rows {
+    renderer
+      .print_task_info(task, now)?;
+    let (done, total) =
+      subtask_progress(&all, task.uuid);
+    if total > 0 {
+      println!(
+        "subtasks  {done}/{total} done"
+      );
+    }
+    println!();
+  }
+
+  Ok(())
+}
+
+#[instrument(skip(
+  store,
+  renderer,
+  filter_terms,
+  now
+))]
+fn cmd_subtasks(
+  store: &mut DataStore,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command subtasks");
+
+  let all: Vec<Task> = store
+    .load_pending()?
+    .into_iter()
+    .chain(store.load_completed()?)
+    .collect();
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  if !filter.has_identity_selector() {
+    return Err(CommandError::usage(
+      "subtasks: select a parent task \
+       by id or uuid"
+    ));
+  }
+
+  let mut parents: Vec<&Task> = all
+    .iter()
+    .filter(|task| {
+      filter.matches(task, now)
+    })
+    .collect();
+  parents.sort_by_key(|task| {
+    task.id.unwrap_or(u64::MAX)
+  });
+  if parents.is_empty() {
+    return Err(CommandError::no_match());
+  }
+
+  for parent in parents {
+    let mut rows: Vec<Task> =
+      subtasks_of(&all, parent.uuid)
+        .into_iter()
+        .cloned()
+        .collect();
+    rows.sort_by_key(|task| {
+      (task.end.is_some(), task.id)
+    });
+    let done = rows
+      .iter()
+      .filter(|task| {
+        task.status == Status::Completed
+      })
+      .count();
+    println!(
+      "Subtasks of {} '{}' ({done}/{} \
+       done)",
+      parent.id.map_or_else(
+        || parent.uuid.to_string(),
+        |id| id.to_string()
+      ),
+      parent.description,
+      rows.len()
+    );
+    renderer
+      .print_task_table(&rows, now)?;
+  }
+
+  Ok(())
+}
+
+#[instrument(skip(
+  store,
+  renderer,
+  filter_terms,
+  now
+))]
+fn cmd_history(
+  store: &mut DataStore,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command history");
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  if !filter.has_identity_selector() {
+    return Err(CommandError::usage(
+      "history: select a task by id \
+       or uuid"
+    ));
+  }
+  let mut rows: Vec<Task> =
+    all_tasks(store)?
+      .into_iter()
+      .filter(|task| {
+        filter.matches(task, now)
+      })
+      .collect();
+  rows.sort_by_key(|task| {
+    task.id.unwrap_or(u64::MAX)
+  });
+  if rows.is_empty() {
+    return Err(CommandError::no_match());
+  }
+
+  let history = store.load_history()?;
+  for task in rows {
+    let entries: Vec<_> = history
+      .iter()
+      .filter(|entry| {
+        entry.uuid == task.uuid
+      })
+      .collect();
+    renderer.print_task_history(
+      &task, &entries, now
+    )?;
+    println!();
+  }
+  Ok(())
+}
+
+#[instrument(skip(
+  store,
+  hooks,
+  cfg,
+  filter_terms,
+  args,
+  now
+))]
+fn cmd_modify(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  cfg: &Config,
+  filter_terms: &[String],
   args: &[String],
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
@@ -481,9 +1413,14 @@ fn cmd_modify(
   let include_non_pending = filter
     .has_explicit_status_filter()
     || filter.has_identity_selector();
-  let mods = parse_mods(args, now)?;
+  let times =
+    DefaultTimes::from_config(cfg)
+      .map_err(usage_error)?;
+  let mods =
+    parse_mods(args, now, &times)?;
 
   let mut changed = 0_u64;
+  let mut touched = Vec::new();
   for task in &mut pending {
     if !include_non_pending
       && task.status != Status::Pending
@@ -497,6 +1434,7 @@ fn cmd_modify(
       task.modified = now;
       *task = hooks
         .apply_on_modify(&old, task)?;
+      touched.push(task.uuid);
       changed += 1;
     }
   }
@@ -510,11 +1448,21 @@ fn cmd_modify(
         *task = hooks.apply_on_modify(
           &old, task
         )?;
+        touched.push(task.uuid);
         changed += 1;
       }
     }
   }
 
+  if mods_set_parent(&mods) {
+    let all: Vec<Task> = pending
+      .iter()
+      .chain(&completed)
+      .cloned()
+      .collect();
+    check_parent_links(&all, &touched)?;
+  }
+
   if changed > 0 {
     store.push_undo_snapshot(
       &pending_before,
@@ -530,7 +1478,86 @@ fn cmd_modify(
   println!(
     "Modified {changed} task(s)."
   );
-  Ok(())
+  ensure_matched(changed)
+}
+
+/// Runs the `autotag.*` rules over
+/// open tasks matching the filter, for
+/// tasks added before a rule existed.
+/// `--dry-run` previews the tags.
+#[instrument(skip(
+  store,
+  hooks,
+  filter_terms,
+  now
+))]
+fn cmd_autotag(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command autotag");
+
+  let rules = hooks.auto_tags();
+  if rules.is_empty() {
+    return Err(CommandError::usage(
+      "no autotag.<name>.* rules are \
+       configured"
+    ));
+  }
+
+  let mut pending =
+    store.load_pending()?;
+  let pending_before = pending.clone();
+  let filter =
+    Filter::parse(filter_terms, now)?;
+
+  let mut matched = 0_u64;
+  let mut tagged = 0_u64;
+  for task in &mut pending {
+    if (task.status != Status::Pending
+      && task.status != Status::Waiting)
+      || !filter.matches(task, now)
+    {
+      continue;
+    }
+    matched += 1;
+    let old = task.clone();
+    let added = rules.apply(task);
+    if added.is_empty() {
+      continue;
+    }
+    task.modified = now;
+    *task =
+      hooks.apply_on_modify(&old, task)?;
+    tagged += 1;
+    println!(
+      "  {} {}",
+      task.id.map_or_else(
+        || task.uuid.to_string(),
+        |id| id.to_string()
+      ),
+      added
+        .iter()
+        .map(|tag| format!("+{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+    );
+  }
+
+  if tagged > 0 {
+    let completed =
+      store.load_completed()?;
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+    store.save_pending(&pending)?;
+  }
+
+  println!("Tagged {tagged} task(s).");
+  ensure_matched(matched)
 }
 
 #[instrument(skip(
@@ -541,6 +1568,7 @@ fn cmd_modify(
 ))]
 fn cmd_start(
   store: &mut DataStore,
+  cfg: &Config,
   hooks: &HookRunner,
   filter_terms: &[String],
   now: chrono::DateTime<Utc>
@@ -554,6 +1582,7 @@ fn cmd_start(
     Filter::parse(filter_terms, now)?;
 
   let mut started = 0_u64;
+  let mut touched = BTreeSet::new();
   for task in &mut pending {
     if task.status != Status::Pending
       || task.is_waiting(now)
@@ -568,6 +1597,7 @@ fn cmd_start(
       task.modified = now;
       *task = hooks
         .apply_on_modify(&old, task)?;
+      touched.insert(task.uuid);
       started += 1;
     }
   }
@@ -585,7 +1615,15 @@ fn cmd_start(
   println!(
     "Started {started} task(s)."
   );
-  Ok(())
+  if started > 0 {
+    print_nag(
+      cfg,
+      &pending_before,
+      &touched,
+      now
+    );
+  }
+  ensure_matched(started)
 }
 
 #[instrument(skip(
@@ -617,7 +1655,7 @@ fn cmd_stop(
       && task.start.is_some()
     {
       let old = task.clone();
-      task.start = None;
+      task.stop_clock(now);
       task.modified = now;
       *task = hooks
         .apply_on_modify(&old, task)?;
@@ -638,9 +1676,14 @@ fn cmd_stop(
   println!(
     "Stopped {stopped} task(s)."
   );
-  Ok(())
+  ensure_matched(stopped)
 }
 
+/// Defers matching open tasks by
+/// moving `wait` to the snooze target.
+/// A due date that would fall before
+/// the new wait moves with it, so the
+/// task does not come back overdue.
 #[instrument(skip(
   store,
   hooks,
@@ -648,64 +1691,253 @@ fn cmd_stop(
   args,
   now
 ))]
-fn cmd_annotate(
+fn cmd_snooze(
   store: &mut DataStore,
   hooks: &HookRunner,
   filter_terms: &[String],
   args: &[String],
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
-  info!("command annotate");
+  info!("command snooze");
 
-  if args.is_empty() {
-    return Err(anyhow!(
-      "annotate requires annotation \
-       text"
+  if filter_terms.is_empty() {
+    return Err(CommandError::usage(
+      "snooze needs a filter, e.g. \
+       `task 3 snooze 1d`"
     ));
   }
-  let note = args.join(" ");
+  let expr = args.join(" ");
+  if expr.trim().is_empty() {
+    return Err(CommandError::usage(
+      "snooze needs a target: 1d, \
+       +1w, tomorrow, next monday, \
+       YYYY-MM-DD, ..."
+    ));
+  }
+  let until =
+    parse_snooze_expr(&expr, now)
+      .map_err(usage_error)?;
 
   let mut pending =
     store.load_pending()?;
-  let mut completed =
-    store.load_completed()?;
   let pending_before = pending.clone();
-  let completed_before =
-    completed.clone();
-
   let filter =
     Filter::parse(filter_terms, now)?;
-  let mut touched = 0_u64;
 
+  let mut snoozed = 0_u64;
   for task in &mut pending {
-    if filter.matches(task, now) {
-      let old = task.clone();
-      task.annotations.push(
-        Annotation {
-          entry:       now,
-          description: note.clone()
-        }
-      );
-      task.modified = now;
-      *task = hooks
-        .apply_on_modify(&old, task)?;
-      touched += 1;
+    if !matches!(
+      task.status,
+      Status::Pending | Status::Waiting
+    ) || !filter.matches(task, now)
+    {
+      continue;
     }
+    let old = task.clone();
+    task.wait = Some(until);
+    if task
+      .due
+      .is_some_and(|due| due < until)
+    {
+      task.due = Some(until);
+    }
+    task.modified = now;
+    *task = hooks
+      .apply_on_modify(&old, task)?;
+    snoozed += 1;
   }
 
-  for task in &mut completed {
-    if filter.matches(task, now) {
-      let old = task.clone();
-      task.annotations.push(
-        Annotation {
-          entry:       now,
-          description: note.clone()
-        }
-      );
-      task.modified = now;
-      *task = hooks
-        .apply_on_modify(&old, task)?;
-      touched += 1;
+  if snoozed > 0 {
+    let completed =
+      store.load_completed()?;
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+    store.update_pending(&pending)?;
+  }
+
+  println!(
+    "Snoozed {snoozed} task(s) until \
+     {}.",
+    until
+      .with_timezone(project_timezone())
+      .format("%Y-%m-%d %H:%M")
+  );
+  ensure_matched(snoozed)
+}
+
+/// Moves a date of matching open tasks
+/// by an offset instead of replacing
+/// it: `postpone 1w` pushes `due` a
+/// week out, `postpone -2d scheduled`
+/// pulls `scheduled` two days in. Every
+/// matched task must already have the
+/// date.
+#[instrument(skip(
+  store,
+  hooks,
+  filter_terms,
+  args,
+  now
+))]
+fn cmd_postpone(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  filter_terms: &[String],
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command postpone");
+
+  if filter_terms.is_empty() {
+    return Err(CommandError::usage(
+      "postpone needs a filter, e.g. \
+       `task 3 postpone 1w`"
+    ));
+  }
+  let mut field = "due";
+  let mut offset = None;
+  for arg in args {
+    if let Some(name) = SHIFT_FIELDS
+      .iter()
+      .copied()
+      .find(|name| {
+        arg.eq_ignore_ascii_case(name)
+      })
+    {
+      field = name;
+    } else if offset
+      .replace(arg.as_str())
+      .is_some()
+    {
+      return Err(CommandError::usage(
+        format!(
+          "postpone takes one offset, \
+           got '{}'",
+          args.join(" ")
+        )
+      ));
+    }
+  }
+  let Some(offset) = offset else {
+    return Err(CommandError::usage(
+      "postpone needs an offset: 1w, \
+       +3d, -2d, 4h, +1b, ... \
+       optionally followed by due, \
+       scheduled or wait"
+    ));
+  };
+  shift_date(now, offset)
+    .map_err(usage_error)?;
+
+  let mut pending =
+    store.load_pending()?;
+  let pending_before = pending.clone();
+  let filter =
+    Filter::parse(filter_terms, now)?;
+
+  let mut postponed = 0_u64;
+  for task in &mut pending {
+    if !matches!(
+      task.status,
+      Status::Pending | Status::Waiting
+    ) || !filter.matches(task, now)
+    {
+      continue;
+    }
+    let old = task.clone();
+    shift_task_date(
+      task, field, offset, now
+    )?;
+    task.modified = now;
+    *task = hooks
+      .apply_on_modify(&old, task)?;
+    postponed += 1;
+  }
+
+  if postponed > 0 {
+    let completed =
+      store.load_completed()?;
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+    store.update_pending(&pending)?;
+  }
+
+  println!(
+    "Moved {field} of {postponed} \
+     task(s) by {offset}."
+  );
+  ensure_matched(postponed)
+}
+
+#[instrument(skip(
+  store,
+  hooks,
+  filter_terms,
+  args,
+  now
+))]
+fn cmd_annotate(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  filter_terms: &[String],
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command annotate");
+
+  if args.is_empty() {
+    return Err(CommandError::usage(
+      "annotate requires annotation \
+       text"
+    ));
+  }
+  let note = args.join(" ");
+
+  let mut pending =
+    store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+  let completed_before =
+    completed.clone();
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let mut touched = 0_u64;
+
+  for task in &mut pending {
+    if filter.matches(task, now) {
+      let old = task.clone();
+      task.annotations.push(
+        Annotation {
+          entry:       now,
+          description: note.clone()
+        }
+      );
+      task.modified = now;
+      *task = hooks
+        .apply_on_modify(&old, task)?;
+      touched += 1;
+    }
+  }
+
+  for task in &mut completed {
+    if filter.matches(task, now) {
+      let old = task.clone();
+      task.annotations.push(
+        Annotation {
+          entry:       now,
+          description: note.clone()
+        }
+      );
+      task.modified = now;
+      *task = hooks
+        .apply_on_modify(&old, task)?;
+      touched += 1;
     }
   }
 
@@ -721,7 +1953,7 @@ fn cmd_annotate(
   println!(
     "Annotated {touched} task(s)."
   );
-  Ok(())
+  ensure_matched(touched)
 }
 
 #[instrument(skip(
@@ -741,7 +1973,7 @@ fn cmd_denotate(
   info!("command denotate");
 
   if args.is_empty() {
-    return Err(anyhow!(
+    return Err(CommandError::usage(
       "denotate requires an index or \
        text selector"
     ));
@@ -814,7 +2046,7 @@ fn cmd_denotate(
     "Removed {removed} annotation(s) \
      from {tasks_touched} task(s)."
   );
-  Ok(())
+  ensure_matched(tasks_touched)
 }
 
 fn denotate_tasks(
@@ -933,15 +2165,213 @@ fn cmd_duplicate(
   println!(
     "Duplicated {duplicated} task(s)."
   );
-  Ok(())
+  ensure_matched(duplicated)
+}
+
+const DEDUPE_USAGE: &str =
+  "usage: task [filter] dedupe \
+   [merge|delete [group...]] \
+   [--threshold=N]";
+
+/// `dedupe` lists groups of likely
+/// duplicate open tasks among those
+/// the filter matches, scored against
+/// `dedupe.threshold` or
+/// `--threshold`. `dedupe merge` folds
+/// each chosen group (all by default)
+/// into its oldest task and deletes the
+/// others; `dedupe delete` deletes them
+/// without merging. Dependencies on a
+/// removed task move to the kept one.
+#[instrument(skip(
+  store,
+  hooks,
+  cfg,
+  renderer,
+  filter_terms,
+  args,
+  now
+))]
+fn cmd_dedupe(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  cfg: &Config,
+  renderer: &mut Renderer,
+  filter_terms: &[String],
+  args: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let split = args
+    .iter()
+    .position(|arg| arg.starts_with("--"))
+    .unwrap_or(args.len());
+  let mut threshold =
+    dedupe::threshold_from_config(cfg);
+  for (_, value) in parse_flag_args(
+    "dedupe",
+    &args[split..],
+    &["--threshold"]
+  )? {
+    threshold =
+      dedupe::parse_threshold(&value)
+        .ok_or_else(|| {
+          CommandError::usage(format!(
+            "dedupe: --threshold must \
+             be 1-100, got {value}"
+          ))
+        })?;
+  }
+  let (action, picks) =
+    match args[..split].split_first() {
+      | None => (None, &[][..]),
+      | Some((action, picks))
+        if action == "merge"
+          || action == "delete" =>
+      {
+        (Some(action.as_str()), picks)
+      }
+      | Some(_) => {
+        return Err(CommandError::usage(
+          DEDUPE_USAGE
+        ));
+      }
+    };
+  info!(
+    threshold,
+    action = action.unwrap_or("preview"),
+    "command dedupe"
+  );
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+  let mut pending =
+    store.load_pending()?;
+  let candidates: Vec<Task> = pending
+    .iter()
+    .filter(|task| {
+      filter.matches(task, now)
+    })
+    .cloned()
+    .collect();
+  let groups = dedupe::find_groups(
+    &candidates,
+    threshold
+  );
+  if groups.is_empty() {
+    println!(
+      "No duplicate tasks found at \
+       threshold {threshold}."
+    );
+    return Err(CommandError::no_match());
+  }
+
+  let mut chosen = Vec::new();
+  for pick in picks {
+    let index = pick
+      .parse::<usize>()
+      .ok()
+      .filter(|index| {
+        (1..=groups.len())
+          .contains(index)
+      })
+      .ok_or_else(|| {
+        CommandError::usage(format!(
+          "dedupe: no group {pick}; \
+           groups run 1-{}",
+          groups.len()
+        ))
+      })?;
+    chosen.push(&groups[index - 1]);
+  }
+  if picks.is_empty() {
+    chosen.extend(groups.iter());
+  }
+
+  let by_uuid = |uuid: &uuid::Uuid| {
+    candidates
+      .iter()
+      .find(|task| task.uuid == *uuid)
+      .cloned()
+  };
+  let Some(action) = action else {
+    for (index, group) in
+      groups.iter().enumerate()
+    {
+      println!(
+        "Group {} (score {}, {}):",
+        index + 1,
+        group.score,
+        group.reason
+      );
+      let rows: Vec<Task> = group
+        .tasks
+        .iter()
+        .filter_map(by_uuid)
+        .collect();
+      renderer
+        .print_task_table(&rows, now)?;
+    }
+    println!(
+      "'task dedupe merge [group...]' \
+       keeps the first (oldest) task \
+       of each group; 'task dedupe \
+       delete [group...]' drops the \
+       rest without merging."
+    );
+    return Ok(());
+  };
+
+  let pending_before = pending.clone();
+  let picked: Vec<Vec<uuid::Uuid>> =
+    chosen
+      .iter()
+      .map(|group| group.tasks.clone())
+      .collect();
+  let removed = dedupe::apply_groups(
+    &mut pending,
+    &picked,
+    action == "merge",
+    now
+  );
+  for (old, task) in pending_before
+    .iter()
+    .zip(pending.iter_mut())
+  {
+    if task.modified != old.modified {
+      *task =
+        hooks.apply_on_modify(old, task)?;
+    }
+  }
+
+  if removed > 0 {
+    let completed =
+      store.load_completed()?;
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+    store.save_pending(&pending)?;
+  }
+  let verb = if action == "merge" {
+    "Merged"
+  } else {
+    "Deleted"
+  };
+  println!(
+    "{verb} {removed} duplicate \
+     task(s) in {} group(s).",
+    chosen.len()
+  );
+  ensure_matched(removed)
 }
 
 #[instrument(skip(
-  store, hooks, args, now
+  store, hooks, cfg, args, now
 ))]
 fn cmd_log(
   store: &mut DataStore,
   hooks: &HookRunner,
+  cfg: &Config,
   args: &[String],
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
@@ -955,8 +2385,13 @@ fn cmd_log(
     completed.clone();
 
   let next_id = store.next_id(&pending);
+  let times =
+    DefaultTimes::from_config(cfg)
+      .map_err(usage_error)?;
   let (description, mods) =
-    parse_desc_and_mods(args, now)?;
+    parse_desc_and_mods(
+      args, now, &times
+    )?;
 
   let mut task = Task::new_pending(
     description,
@@ -964,6 +2399,12 @@ fn cmd_log(
     next_id
   );
   apply_mods(&mut task, &mods, now)?;
+  enforce_description_limit(
+    &DescriptionLimit::from_config(cfg)
+      .map_err(usage_error)?,
+    &mut task,
+    now
+  )?;
   task.status = Status::Completed;
   task.end = Some(now);
   task.start = None;
@@ -972,6 +2413,17 @@ fn cmd_log(
   if task.id.is_none() {
     task.id = Some(next_id);
   }
+  if task.parent.is_some() {
+    let all: Vec<Task> = pending
+      .iter()
+      .chain(&completed)
+      .chain(std::iter::once(&task))
+      .cloned()
+      .collect();
+    check_parent_links(&all, &[
+      task.uuid
+    ])?;
+  }
 
   let mut completed_new = completed;
   completed_new.push(task.clone());
@@ -1000,12 +2452,20 @@ fn cmd_log(
 ))]
 fn cmd_done(
   store: &mut DataStore,
+  cfg: &Config,
   hooks: &HookRunner,
   filter_terms: &[String],
+  skip: bool,
   now: chrono::DateTime<Utc>
 ) -> anyhow::Result<()> {
-  info!("command done");
-
+  info!(skip, "command done");
+
+  let policy =
+    CompletionPolicy::from_config(cfg)
+      .map_err(usage_error)?;
+  let recur_mode =
+    RecurMode::from_config(cfg)
+      .map_err(usage_error)?;
   let mut pending =
     store.load_pending()?;
   let mut completed =
@@ -1017,25 +2477,79 @@ fn cmd_done(
   let filter =
     Filter::parse(filter_terms, now)?;
 
+  let is_open = |task: &Task| {
+    task.status == Status::Pending
+      || task.status == Status::Waiting
+  };
+  let mut selected: BTreeSet<
+    uuid::Uuid
+  > = pending
+    .iter()
+    .filter(|task| {
+      is_open(task)
+        && filter.matches(task, now)
+        && (!skip
+          || RecurSpec::from_tags(
+            &task.tags
+          )
+          .is_some())
+    })
+    .map(|task| task.uuid)
+    .collect();
+  let tree: Vec<Task> = pending
+    .iter()
+    .chain(&completed)
+    .cloned()
+    .collect();
+  let open_children =
+    open_descendants(&tree, &selected);
+  if !open_children.is_empty() {
+    let count = open_children.len();
+    if policy.cascade_subtasks(
+      count,
+      complete_children_confirmed
+    )? {
+      selected.extend(open_children);
+    } else {
+      println!(
+        "Warning: leaving {count} open \
+         subtask(s) pending."
+      );
+    }
+  }
+
   let mut moved = 0_u64;
   let mut keep =
     Vec::with_capacity(pending.len());
+  let mut finished = Vec::new();
 
   for mut task in pending.drain(..) {
-    if (task.status == Status::Pending
-      || task.status == Status::Waiting)
-      && filter.matches(&task, now)
+    if is_open(&task)
+      && selected.contains(&task.uuid)
     {
       let old = task.clone();
       task.status = Status::Completed;
       task.end = Some(now);
       task.start = None;
       task.modified = now;
+      if skip
+        && RecurSpec::from_tags(&task.tags)
+          .is_some()
+      {
+        task.extra.insert(
+          RECUR_SKIPPED_KEY.to_string(),
+          true.into()
+        );
+      }
       task = hooks
         .apply_on_modify(&old, &task)?;
 
       match task.status {
         | Status::Completed => {
+          finished.push(task.clone());
+          completed.push(task)
+        }
+        | Status::Archived => {
           completed.push(task)
         }
         | Status::Deleted
@@ -1050,6 +2564,21 @@ fn cmd_done(
     }
   }
 
+  // Lazy series wait for the sweep
+  // when the store is next opened.
+  let mut spawned = Vec::new();
+  for task in finished.iter().filter(|_| {
+    recur_mode == RecurMode::Eager
+  }) {
+    let next_id = store.next_id(&keep);
+    if let Some(next) = next_instance(
+      task, &keep, next_id, now
+    ) {
+      spawned.push(next_id);
+      keep.push(next);
+    }
+  }
+
   if moved > 0 {
     store.push_undo_snapshot(
       &pending_before,
@@ -1059,10 +2588,347 @@ fn cmd_done(
     store.save_completed(&completed)?;
   }
 
+  if skip {
+    println!("Skipped {moved} task(s).");
+  } else {
+    println!(
+      "Completed {moved} task(s)."
+    );
+  }
+  for id in spawned {
+    println!(
+      "Created recurring task {id}."
+    );
+  }
+  if policy.notify_unblocked {
+    for task in
+      newly_unblocked(&keep, &finished)
+    {
+      println!(
+        "Unblocked task {} '{}'.",
+        task
+          .id
+          .map_or_else(
+            || task.uuid.to_string(),
+            |id| id.to_string()
+          ),
+        task.description
+      );
+    }
+  }
+  if moved > 0 {
+    print_nag(
+      cfg,
+      &pending_before,
+      &selected,
+      now
+    );
+  }
+  ensure_matched(moved)
+}
+
+/// Moves completed tasks back to
+/// pending. Recurrence and subtask
+/// fields are left as they were; the
+/// id is kept unless a newer task has
+/// taken it since.
+#[instrument(skip(
+  store,
+  hooks,
+  filter_terms,
+  now
+))]
+fn cmd_uncomplete(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command uncomplete");
+
+  if filter_terms.is_empty() {
+    return Err(CommandError::usage(
+      "uncomplete needs a filter, \
+       e.g. `task 3 uncomplete`"
+    ));
+  }
+
+  let mut pending =
+    store.load_pending()?;
+  let completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+
+  let mut reopened = 0_u64;
+  let mut keep =
+    Vec::with_capacity(completed.len());
+  for mut task in completed.clone() {
+    if task.status != Status::Completed
+      || !filter.matches(&task, now)
+    {
+      keep.push(task);
+      continue;
+    }
+    let old = task.clone();
+    let successor =
+      recur::spawned_successor(
+        &task, &pending
+      );
+    task.status = Status::Pending;
+    task.end = None;
+    task.modified = now;
+    let id_taken =
+      task.id.is_none_or(|id| {
+        pending.iter().enumerate().any(
+          |(idx, t)| {
+            t.id == Some(id)
+              && successor != Some(idx)
+          }
+        )
+      });
+    if id_taken {
+      task.id =
+        Some(store.next_id(&pending));
+    }
+    task = hooks
+      .apply_on_modify(&old, &task)?;
+    if task.status == Status::Completed
+    {
+      keep.push(task);
+      continue;
+    }
+    // The instance its completion
+    // spawned would otherwise be a
+    // second open one in the series.
+    if let Some(idx) = successor {
+      pending.remove(idx);
+    }
+    pending.push(task);
+    reopened += 1;
+  }
+
+  if reopened > 0 {
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+    pending.sort_by_key(|t| {
+      t.id.unwrap_or(u64::MAX)
+    });
+    store.save_pending(&pending)?;
+    store.save_completed(&keep)?;
+  }
+
   println!(
-    "Completed {moved} task(s)."
+    "Reopened {reopened} task(s)."
   );
-  Ok(())
+  ensure_matched(reopened)
+}
+
+/// Shelves matching open and completed
+/// tasks as `archived` in
+/// completed.data. They lose their id
+/// and drop out of reports until a
+/// filter asks for `status:archived`
+/// or `+ARCHIVED`.
+#[instrument(skip(
+  store,
+  hooks,
+  filter_terms,
+  now
+))]
+fn cmd_archive(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command archive");
+
+  if filter_terms.is_empty() {
+    return Err(CommandError::usage(
+      "archive needs a filter, e.g. \
+       `task 3 archive`"
+    ));
+  }
+
+  let pending = store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+  let completed_before =
+    completed.clone();
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+
+  let mut archived = 0_u64;
+  let mut keep =
+    Vec::with_capacity(pending.len());
+  for mut task in pending {
+    if !matches!(
+      task.status,
+      Status::Pending | Status::Waiting
+    ) || !filter.matches(&task, now)
+    {
+      keep.push(task);
+      continue;
+    }
+    let old = task.clone();
+    task.status = Status::Archived;
+    task.stop_clock(now);
+    task.id = None;
+    task.modified = now;
+    task = hooks
+      .apply_on_modify(&old, &task)?;
+    if task.status == Status::Archived {
+      completed.push(task);
+      archived += 1;
+    } else {
+      keep.push(task);
+    }
+  }
+  for task in &mut completed {
+    if task.status != Status::Completed
+      || !filter.matches(task, now)
+    {
+      continue;
+    }
+    let old = task.clone();
+    task.status = Status::Archived;
+    task.modified = now;
+    *task = hooks
+      .apply_on_modify(&old, task)?;
+    if task.status == Status::Archived {
+      archived += 1;
+    }
+  }
+
+  if archived > 0 {
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed_before
+    )?;
+    store.save_pending(&keep)?;
+    store.save_completed(&completed)?;
+  }
+
+  println!(
+    "Archived {archived} task(s)."
+  );
+  ensure_matched(archived)
+}
+
+/// Restores archived tasks: finished
+/// ones go back to completed, the rest
+/// to pending with a fresh id.
+#[instrument(skip(
+  store,
+  hooks,
+  filter_terms,
+  now
+))]
+fn cmd_unarchive(
+  store: &mut DataStore,
+  hooks: &HookRunner,
+  filter_terms: &[String],
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  info!("command unarchive");
+
+  if filter_terms.is_empty() {
+    return Err(CommandError::usage(
+      "unarchive needs a filter, e.g. \
+       `task project:home unarchive`"
+    ));
+  }
+
+  let mut pending =
+    store.load_pending()?;
+  let completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+
+  let filter =
+    Filter::parse(filter_terms, now)?;
+
+  // Only archived tasks are candidates,
+  // so the filter need not name them.
+  let mut restored = 0_u64;
+  let mut keep =
+    Vec::with_capacity(completed.len());
+  for mut task in completed.clone() {
+    if task.status != Status::Archived
+      || !filter
+        .matches_without_waiting_guard(
+          &task, now
+        )
+    {
+      keep.push(task);
+      continue;
+    }
+    let old = task.clone();
+    task.status =
+      task.unarchived_status();
+    task.modified = now;
+    if task.status == Status::Pending {
+      task.id =
+        Some(store.next_id(&pending));
+    }
+    task = hooks
+      .apply_on_modify(&old, &task)?;
+    restored += 1;
+    if matches!(
+      task.status,
+      Status::Pending | Status::Waiting
+    ) {
+      pending.push(task);
+    } else {
+      keep.push(task);
+    }
+  }
+
+  if restored > 0 {
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed
+    )?;
+    store.save_pending(&pending)?;
+    store.save_completed(&keep)?;
+  }
+
+  println!(
+    "Restored {restored} task(s)."
+  );
+  ensure_matched(restored)
+}
+
+/// The `ask` subtask policy: prompts
+/// only when stdin is a terminal and
+/// otherwise leaves subtasks open.
+fn complete_children_confirmed(
+  count: usize
+) -> anyhow::Result<bool> {
+  if !io::stdin().is_terminal() {
+    return Ok(false);
+  }
+  print!(
+    "Also complete {count} open \
+     subtask(s)? [y/N] "
+  );
+  io::stdout().flush()?;
+  let mut answer = String::new();
+  io::stdin().read_line(&mut answer)?;
+  Ok(matches!(
+    answer
+      .trim()
+      .to_ascii_lowercase()
+      .as_str(),
+    "y" | "yes"
+  ))
 }
 
 #[instrument(skip(
@@ -1116,6 +2982,71 @@ fn cmd_delete(
     "Deleted {deleted} task(s) \
      (soft-delete)."
   );
+  ensure_matched(deleted)
+}
+
+/// Prints the `nag` setting when an
+/// active pending task not acted on
+/// is more urgent than one that was,
+/// scored before the change. `verbose`
+/// can silence it.
+fn print_nag(
+  cfg: &Config,
+  before: &[Task],
+  acted: &BTreeSet<uuid::Uuid>,
+  now: chrono::DateTime<Utc>
+) {
+  let Some(nag) =
+    cfg.get("nag").filter(|nag| {
+      !nag.trim().is_empty()
+    })
+  else {
+    return;
+  };
+  if !cfg.verbose("nag") {
+    return;
+  }
+  let coefficients =
+    UrgencyCoefficients::from_config(
+      cfg
+    );
+  let urgency = |task: &Task| {
+    task.urgency(now, &coefficients)
+  };
+  let least_acted = before
+    .iter()
+    .filter(|task| {
+      acted.contains(&task.uuid)
+    })
+    .map(urgency)
+    .reduce(f64::min);
+  let most_other = before
+    .iter()
+    .filter(|task| {
+      !acted.contains(&task.uuid)
+        && task.status
+          == Status::Pending
+        && !task.is_waiting(now)
+    })
+    .map(urgency)
+    .reduce(f64::max);
+  if let (Some(acted), Some(other)) =
+    (least_acted, most_other)
+    && other > acted
+  {
+    println!("{nag}");
+  }
+}
+
+/// Filtered commands that changed
+/// nothing end with the no-match exit
+/// code, after their usual summary.
+fn ensure_matched(
+  count: u64
+) -> anyhow::Result<()> {
+  if count == 0 {
+    return Err(CommandError::no_match());
+  }
   Ok(())
 }
 
@@ -9,6 +9,7 @@ use anyhow::{
   Context,
   anyhow
 };
+use serde::Serialize;
 use tracing::{
   debug,
   info,
@@ -16,9 +17,59 @@ use tracing::{
   warn
 };
 
+/// Where the effective value of a key
+/// came from; the last writer wins.
+#[derive(
+  Debug, Clone, PartialEq, Eq, Serialize,
+)]
+#[serde(
+  tag = "kind",
+  rename_all = "lowercase"
+)]
+pub enum ConfigSource {
+  /// Built into rivet.
+  Default,
+  /// A taskrc line (or an `include`d
+  /// file).
+  File { path: PathBuf, line: usize },
+  /// `rc.<key>=<value>` on the command
+  /// line (or `--gc`).
+  Override
+}
+
+impl std::fmt::Display
+  for ConfigSource
+{
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>
+  ) -> std::fmt::Result {
+    match self {
+      | Self::Default => {
+        f.write_str("default")
+      }
+      | Self::File {
+        path,
+        line
+      } => {
+        write!(
+          f,
+          "{}:{line}",
+          path.display()
+        )
+      }
+      | Self::Override => {
+        f.write_str("override")
+      }
+    }
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
   map: HashMap<String, String>,
+  sources:
+    HashMap<String, ConfigSource>,
   pub loaded_files: Vec<PathBuf>
 }
 
@@ -31,21 +82,21 @@ impl Config {
   ) -> anyhow::Result<Self> {
     let mut cfg = Config {
       map:          HashMap::new(),
+      sources:      HashMap::new(),
       loaded_files: vec![]
     };
 
-    cfg.map.insert(
-      "data.location".to_string(),
-      "~/.task".to_string()
-    );
-    cfg.map.insert(
-      "default.command".to_string(),
-      "next".to_string()
-    );
-    cfg.map.insert(
-      "color".to_string(),
-      "on".to_string()
-    );
+    for (key, value) in [
+      ("data.location", "~/.task"),
+      ("default.command", "next"),
+      ("color", "on")
+    ] {
+      cfg.set(
+        key.to_string(),
+        value.to_string(),
+        ConfigSource::Default
+      );
+    }
 
     let taskrc = resolve_taskrc_path(
       taskrc_override
@@ -80,10 +131,35 @@ impl Config {
         .unwrap_or(&k)
         .to_string();
       debug!(key = %key, value = %v, "applying override");
-      self.map.insert(key, v);
+      self.set(
+        key,
+        v,
+        ConfigSource::Override
+      );
     }
   }
 
+  fn set(
+    &mut self,
+    key: String,
+    value: String,
+    source: ConfigSource
+  ) {
+    self
+      .sources
+      .insert(key.clone(), source);
+    self.map.insert(key, value);
+  }
+
+  /// Where `key`'s effective value was
+  /// set, for `task show`.
+  pub fn source(
+    &self,
+    key: &str
+  ) -> Option<&ConfigSource> {
+    self.sources.get(key)
+  }
+
   pub fn get(
     &self,
     key: &str
@@ -101,6 +177,38 @@ impl Config {
       .map(|v| parse_bool(v))
   }
 
+  /// Whether the `verbose` setting lets
+  /// `token` through: unset or `on`
+  /// allows everything, `off`/`nothing`
+  /// nothing, and otherwise only the
+  /// comma-separated tokens listed, as
+  /// in Taskwarrior.
+  pub fn verbose(
+    &self,
+    token: &str
+  ) -> bool {
+    let Some(value) =
+      self.map.get("verbose")
+    else {
+      return true;
+    };
+    match value
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "1" | "y" | "yes" | "on"
+      | "true" => true,
+      | "0" | "n" | "no" | "off"
+      | "false" | "nothing" => false,
+      | list => {
+        list.split(',').any(|entry| {
+          entry.trim() == token
+        })
+      }
+    }
+  }
+
   pub fn iter(
     &self
   ) -> impl Iterator<Item = (&String, &String)>
@@ -193,7 +301,14 @@ impl Config {
       let key = k.trim().to_string();
       let value = v.trim().to_string();
       trace!(key = %key, value = %value, "loaded config key");
-      self.map.insert(key, value);
+      self.set(
+        key,
+        value,
+        ConfigSource::File {
+          path: path.clone(),
+          line: line_num + 1
+        }
+      );
     }
 
     Ok(())
@@ -0,0 +1,705 @@
+//! Portable preference bundles for
+//! `config export` / `config import`.
+//!
+//! A bundle is one JSON document
+//! holding the text of the config
+//! files (taskrc, `rivet.toml` with the
+//! calendar settings, the GUI tag
+//! schema) plus an opaque `gui`
+//! section the desktop app fills with
+//! its locally stored kanban boards
+//! and external calendar sources. Task
+//! data is never included; that is
+//! what `backup` is for.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{
+  Path,
+  PathBuf
+};
+
+use anyhow::{
+  Context,
+  anyhow,
+  bail
+};
+use chrono::{
+  DateTime,
+  Utc
+};
+use serde::{
+  Deserialize,
+  Serialize
+};
+use tempfile::NamedTempFile;
+use tracing::info;
+
+use crate::config::Config;
+
+/// `format` value of every bundle.
+pub const BUNDLE_FORMAT: &str =
+  "rivet-config-bundle";
+
+/// Bump when the bundle layout
+/// changes.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Where the GUI reads its tag schema,
+/// relative to the working directory
+/// or one of its parents.
+pub const TAG_SCHEMA_PATH: &str =
+  "crates/rivet-gui/ui/assets/tags.\
+   toml";
+
+/// Suffix of the copies `apply` takes
+/// of files it replaces.
+pub const BACKUP_SUFFIX: &str = ".bak";
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+)]
+pub enum BundleFile {
+  Taskrc,
+  RivetToml,
+  TagSchema
+}
+
+impl BundleFile {
+  pub const ALL: [Self; 3] = [
+    Self::Taskrc,
+    Self::RivetToml,
+    Self::TagSchema
+  ];
+
+  /// The key under `files` in the
+  /// bundle.
+  pub fn key(self) -> &'static str {
+    match self {
+      | Self::Taskrc => "taskrc",
+      | Self::RivetToml => "rivet.toml",
+      | Self::TagSchema => "tags.toml"
+    }
+  }
+
+  fn from_key(
+    key: &str
+  ) -> Option<Self> {
+    Self::ALL
+      .into_iter()
+      .find(|file| file.key() == key)
+  }
+
+  /// The TOML files must still parse;
+  /// a taskrc is free-form `key=value`
+  /// lines and is taken as is.
+  fn validate(
+    self,
+    contents: &str
+  ) -> anyhow::Result<()> {
+    match self {
+      | Self::Taskrc => Ok(()),
+      | Self::RivetToml
+      | Self::TagSchema => {
+        toml::from_str::<toml::Value>(
+          contents
+        )
+        .map(|_| ())
+        .with_context(|| {
+          format!(
+            "bundled {} is not valid \
+             TOML",
+            self.key()
+          )
+        })
+      }
+    }
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Serialize,
+  Deserialize,
+)]
+pub struct ConfigBundle {
+  pub format:  String,
+  pub version: u32,
+  pub created: DateTime<Utc>,
+  /// File contents keyed by
+  /// `BundleFile::key`.
+  #[serde(default)]
+  pub files:   BTreeMap<String, String>,
+  /// GUI-only preferences, passed
+  /// through untouched by the CLI.
+  #[serde(
+    default,
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub gui: Option<serde_json::Value>
+}
+
+impl ConfigBundle {
+  /// Reads every target that exists;
+  /// missing files are left out.
+  pub fn collect(
+    targets: &[(BundleFile, PathBuf)],
+    now: DateTime<Utc>
+  ) -> anyhow::Result<Self> {
+    let mut files = BTreeMap::new();
+    for (file, path) in targets {
+      if !path.is_file() {
+        continue;
+      }
+      let contents =
+        fs::read_to_string(path)
+          .with_context(|| {
+            format!(
+              "failed to read {}",
+              path.display()
+            )
+          })?;
+      files.insert(
+        file.key().to_string(),
+        contents
+      );
+    }
+    Ok(Self {
+      format: BUNDLE_FORMAT.to_string(),
+      version: BUNDLE_VERSION,
+      created: now,
+      files,
+      gui: None
+    })
+  }
+
+  /// Parses and checks a bundle before
+  /// anything is written: the format
+  /// and version must match, file keys
+  /// must be known and the TOML files
+  /// must parse.
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    let bundle: Self =
+      serde_json::from_str(raw)
+        .context(
+          "not a rivet config bundle"
+        )?;
+    if bundle.format != BUNDLE_FORMAT {
+      bail!(
+        "not a rivet config bundle \
+         (format '{}')",
+        bundle.format
+      );
+    }
+    if bundle.version > BUNDLE_VERSION {
+      bail!(
+        "config bundle version {} is \
+         newer than this build \
+         supports ({BUNDLE_VERSION})",
+        bundle.version
+      );
+    }
+    for (key, contents) in &bundle.files
+    {
+      BundleFile::from_key(key)
+        .ok_or_else(|| {
+          anyhow!(
+            "unknown file '{key}' in \
+             config bundle"
+          )
+        })?
+        .validate(contents)?;
+    }
+    Ok(bundle)
+  }
+
+  pub fn to_json(
+    &self
+  ) -> anyhow::Result<String> {
+    let mut json =
+      serde_json::to_string_pretty(
+        self
+      )?;
+    json.push('\n');
+    Ok(json)
+  }
+
+  /// What importing would do to each
+  /// bundled file that has a target.
+  pub fn plan(
+    &self,
+    targets: &[(BundleFile, PathBuf)]
+  ) -> anyhow::Result<Vec<BundleChange>>
+  {
+    let mut changes = Vec::new();
+    for (file, path) in targets {
+      let Some(contents) =
+        self.files.get(file.key())
+      else {
+        continue;
+      };
+      let kind = if !path.exists() {
+        ChangeKind::New
+      } else if fs::read_to_string(path)
+        .with_context(|| {
+          format!(
+            "failed to read {}",
+            path.display()
+          )
+        })?
+        == *contents
+      {
+        ChangeKind::Unchanged
+      } else {
+        ChangeKind::Conflict
+      };
+      changes.push(BundleChange {
+        file: *file,
+        path: path.clone(),
+        kind,
+        contents: contents.clone()
+      });
+    }
+    Ok(changes)
+  }
+}
+
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum ChangeKind {
+  New,
+  Unchanged,
+  /// The file exists with different
+  /// contents.
+  Conflict
+}
+
+#[derive(Debug, Clone)]
+pub struct BundleChange {
+  pub file: BundleFile,
+  pub path: PathBuf,
+  pub kind: ChangeKind,
+  contents: String
+}
+
+/// How `config import` settles files
+/// that exist with other contents.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+)]
+pub enum ConflictPolicy {
+  /// Prompt per file on a terminal;
+  /// refuse otherwise.
+  #[default]
+  Ask,
+  Overwrite,
+  Keep
+}
+
+impl ConflictPolicy {
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "ask" => Ok(Self::Ask),
+      | "overwrite" => {
+        Ok(Self::Overwrite)
+      }
+      | "keep" => Ok(Self::Keep),
+      | other => {
+        Err(anyhow!(
+          "unknown conflict policy \
+           '{other}' (expected ask, \
+           overwrite or keep)"
+        ))
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+  pub written:   Vec<PathBuf>,
+  pub kept:      Vec<PathBuf>,
+  pub unchanged: Vec<PathBuf>,
+  /// `(original, copy)` for every file
+  /// replaced.
+  pub backups: Vec<(PathBuf, PathBuf)>
+}
+
+/// Writes new files and the conflicts
+/// `overwrite` accepts. Every decision
+/// is made before the first write, and
+/// each replaced file is first copied
+/// to `<name>.<timestamp>.bak` beside
+/// it.
+pub fn apply(
+  changes: &[BundleChange],
+  now: DateTime<Utc>,
+  mut overwrite: impl FnMut(
+    &BundleChange
+  ) -> anyhow::Result<bool>
+) -> anyhow::Result<ImportReport> {
+  let mut report =
+    ImportReport::default();
+  let mut writes = Vec::new();
+  for change in changes {
+    match change.kind {
+      | ChangeKind::Unchanged => {
+        report
+          .unchanged
+          .push(change.path.clone());
+      }
+      | ChangeKind::New => {
+        writes.push(change);
+      }
+      | ChangeKind::Conflict => {
+        if overwrite(change)? {
+          writes.push(change);
+        } else {
+          report
+            .kept
+            .push(change.path.clone());
+        }
+      }
+    }
+  }
+
+  let stamp = now
+    .format("%Y%m%dT%H%M%SZ")
+    .to_string();
+  for change in &writes {
+    if change.kind
+      != ChangeKind::Conflict
+    {
+      continue;
+    }
+    let backup =
+      backup_path(&change.path, &stamp);
+    fs::copy(&change.path, &backup)
+      .with_context(|| {
+        format!(
+          "failed to back up {} to {}",
+          change.path.display(),
+          backup.display()
+        )
+      })?;
+    report.backups.push((
+      change.path.clone(),
+      backup
+    ));
+  }
+  for change in writes {
+    write_atomic(
+      &change.path,
+      &change.contents
+    )?;
+    info!(file = change.file.key(), path = %change.path.display(), "imported config file");
+    report
+      .written
+      .push(change.path.clone());
+  }
+  Ok(report)
+}
+
+fn backup_path(
+  path: &Path,
+  stamp: &str
+) -> PathBuf {
+  let name = path
+    .file_name()
+    .map(|name| {
+      name
+        .to_string_lossy()
+        .into_owned()
+    })
+    .unwrap_or_default();
+  path.with_file_name(format!(
+    "{name}.{stamp}{BACKUP_SUFFIX}"
+  ))
+}
+
+fn write_atomic(
+  path: &Path,
+  contents: &str
+) -> anyhow::Result<()> {
+  let dir = path
+    .parent()
+    .filter(|dir| {
+      !dir.as_os_str().is_empty()
+    })
+    .unwrap_or_else(|| Path::new("."));
+  fs::create_dir_all(dir)
+    .with_context(|| {
+      format!(
+        "failed to create {}",
+        dir.display()
+      )
+    })?;
+  let mut tmp =
+    NamedTempFile::new_in(dir)
+      .with_context(|| {
+        format!(
+          "failed to create a temp \
+           file in {}",
+          dir.display()
+        )
+      })?;
+  tmp.write_all(contents.as_bytes())?;
+  tmp.persist(path).with_context(
+    || {
+      format!(
+        "failed to write {}",
+        path.display()
+      )
+    }
+  )?;
+  Ok(())
+}
+
+/// The files the CLI bundles: the
+/// loaded taskrc (or where one would be
+/// read from), `rivet.toml` as the
+/// timezone settings resolve it, and
+/// the tag schema when one is found
+/// from the working directory up.
+pub fn cli_targets(
+  cfg: &Config
+) -> Vec<(BundleFile, PathBuf)> {
+  let mut targets = Vec::new();
+  if let Some(taskrc) = cfg
+    .loaded_files
+    .first()
+    .cloned()
+    .or_else(default_taskrc_path)
+  {
+    targets.push((
+      BundleFile::Taskrc,
+      taskrc
+    ));
+  }
+  if let Some(rivet_toml) =
+    crate::datetime::timezone_config_path()
+  {
+    targets.push((
+      BundleFile::RivetToml,
+      rivet_toml
+    ));
+  }
+  if let Some(tags) =
+    find_upwards(TAG_SCHEMA_PATH)
+  {
+    targets.push((
+      BundleFile::TagSchema,
+      tags
+    ));
+  }
+  targets
+}
+
+fn default_taskrc_path()
+-> Option<PathBuf> {
+  match std::env::var("TASKRC") {
+    | Ok(path) if path == "/dev/null" => {
+      None
+    }
+    | Ok(path) => {
+      Some(PathBuf::from(path))
+    }
+    | Err(_) => {
+      dirs::home_dir().map(|home| {
+        home.join(".taskrc")
+      })
+    }
+  }
+}
+
+fn find_upwards(
+  rel_path: &str
+) -> Option<PathBuf> {
+  let cwd =
+    std::env::current_dir().ok()?;
+  cwd
+    .ancestors()
+    .map(|dir| dir.join(rel_path))
+    .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::TimeZone;
+
+  use super::*;
+
+  #[test]
+  fn bundles_round_trip_and_import_backs_up_conflicts()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 4, 12, 0, 0
+      )
+      .unwrap();
+    let source = tempfile::tempdir()
+      .expect("tempdir");
+    let rivet_toml =
+      source.path().join("rivet.toml");
+    fs::write(
+      &rivet_toml,
+      "[calendar]\nweek_start = \
+       \"sunday\"\n"
+    )
+    .unwrap();
+    let targets = vec![
+      (
+        BundleFile::Taskrc,
+        source.path().join("taskrc")
+      ),
+      (
+        BundleFile::RivetToml,
+        rivet_toml.clone()
+      ),
+    ];
+    let mut bundle =
+      ConfigBundle::collect(
+        &targets, now
+      )
+      .expect("collect");
+    assert_eq!(
+      bundle
+        .files
+        .keys()
+        .collect::<Vec<_>>(),
+      ["rivet.toml"]
+    );
+    bundle.files.insert(
+      "taskrc".into(),
+      "color=off\n".into()
+    );
+    let parsed = ConfigBundle::parse(
+      &bundle.to_json().unwrap()
+    )
+    .expect("parse");
+    assert_eq!(parsed, bundle);
+
+    let dest = tempfile::tempdir()
+      .expect("tempdir");
+    let dest_toml =
+      dest.path().join("rivet.toml");
+    fs::write(&dest_toml, "[ui]\n")
+      .unwrap();
+    let dest_targets = vec![
+      (
+        BundleFile::Taskrc,
+        dest.path().join("taskrc")
+      ),
+      (
+        BundleFile::RivetToml,
+        dest_toml.clone()
+      ),
+    ];
+    let changes = parsed
+      .plan(&dest_targets)
+      .expect("plan");
+    assert_eq!(
+      changes
+        .iter()
+        .map(|change| change.kind)
+        .collect::<Vec<_>>(),
+      [
+        ChangeKind::New,
+        ChangeKind::Conflict
+      ]
+    );
+
+    let kept =
+      apply(&changes, now, |_| {
+        Ok(false)
+      })
+      .expect("keep");
+    assert_eq!(
+      kept.kept,
+      std::slice::from_ref(&dest_toml)
+    );
+    assert_eq!(
+      fs::read_to_string(&dest_toml)
+        .unwrap(),
+      "[ui]\n"
+    );
+
+    let changes = parsed
+      .plan(&dest_targets)
+      .expect("plan");
+    let report =
+      apply(&changes, now, |_| {
+        Ok(true)
+      })
+      .expect("overwrite");
+    assert_eq!(report.unchanged, [
+      dest.path().join("taskrc")
+    ]);
+    assert_eq!(
+      report.written,
+      std::slice::from_ref(&dest_toml)
+    );
+    assert_eq!(
+      fs::read_to_string(
+        dest.path().join(
+          "rivet.toml.\
+           20260304T120000Z.bak"
+        )
+      )
+      .unwrap(),
+      "[ui]\n"
+    );
+    assert_eq!(
+      fs::read_to_string(&dest_toml)
+        .unwrap(),
+      fs::read_to_string(&rivet_toml)
+        .unwrap()
+    );
+
+    let mut broken = bundle.clone();
+    broken.files.insert(
+      "tags.toml".into(),
+      "[keys".into()
+    );
+    assert!(
+      ConfigBundle::parse(
+        &broken.to_json().unwrap()
+      )
+      .is_err()
+    );
+    broken.files.clear();
+    broken.files.insert(
+      "notes".into(),
+      "".into()
+    );
+    assert!(
+      ConfigBundle::parse(
+        &broken.to_json().unwrap()
+      )
+      .is_err()
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use std::fs;
+use std::collections::HashMap;
 use std::io::{
   BufRead,
   BufReader,
@@ -8,11 +8,27 @@ use std::path::{
   Path,
   PathBuf
 };
+use std::sync::{
+  Arc,
+  PoisonError,
+  RwLock
+};
+use std::time::SystemTime;
+use std::{
+  fmt,
+  fs
+};
 
 use anyhow::{
   Context,
-  anyhow
+  anyhow,
+  bail
 };
+use chrono::{
+  DateTime,
+  Utc
+};
+use serde::de::DeserializeOwned;
 use serde::{
   Deserialize,
   Serialize
@@ -20,38 +36,520 @@ use serde::{
 use tempfile::NamedTempFile;
 use tracing::{
   debug,
-  info
+  info,
+  warn
 };
 use uuid::Uuid;
 
+use crate::history::HistoryEntry;
 use crate::task::{
   Status,
   Task
 };
+use crate::{
+  index_file,
+  migrate
+};
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::{
+  SQLITE_FILE,
+  SqliteBackend
+};
+
+/// Semantic version of the on-disk
+/// `*.data` line format.
+pub const STORE_FORMAT_VERSION: &str =
+  "1.1.0";
 
+/// Sidecar in the data directory that
+/// collects lines no data file could
+/// parse.
+pub const CORRUPT_FILE: &str =
+  "corrupt.data";
+
+/// One unparseable line moved out of a
+/// data file. Loads skip the line and
+/// record it here once; the next save
+/// of that file drops it for good.
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+)]
+pub struct QuarantinedLine {
+  /// Data file name, e.g.
+  /// `pending.data`.
+  pub file:        String,
+  pub line:        usize,
+  pub raw:         String,
+  pub error:       String,
+  pub quarantined: DateTime<Utc>
+}
+
+/// The `*_path` fields name the file
+/// backend's layout; other backends
+/// keep their data elsewhere in
+/// `data_dir`.
 #[derive(Debug)]
 pub struct DataStore {
   pub data_dir:       PathBuf,
   pub pending_path:   PathBuf,
   pub completed_path: PathBuf,
   pub undo_path:      PathBuf,
-  pub context_path:   PathBuf
+  pub redo_path:      PathBuf,
+  pub context_path:   PathBuf,
+  pub history_path:   PathBuf,
+  pub corrupt_path:   PathBuf,
+  backend: Box<dyn StorageBackend>,
+  dry_run:            bool,
+  persist_index:      bool,
+  undo_depth:         Option<usize>
+}
+
+/// Where a `DataStore` keeps its data
+/// (`data.backend`).
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub enum BackendKind {
+  /// JSONL `*.data` files.
+  #[default]
+  File,
+  /// One `rivet.sqlite` database;
+  /// needs the `sqlite` feature.
+  Sqlite
+}
+
+impl BackendKind {
+  /// An empty value is the file
+  /// backend.
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "" | "file" => Ok(Self::File),
+      | "sqlite" => Ok(Self::Sqlite),
+      | other => {
+        bail!(
+          "unknown data.backend \
+           '{other}' (expected file \
+           or sqlite)"
+        )
+      }
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      | Self::File => "file",
+      | Self::Sqlite => "sqlite"
+    }
+  }
+}
+
+/// Parses `undo.depth`: a whole number
+/// of steps, with empty or `0` keeping
+/// every step.
+pub fn parse_undo_depth(
+  raw: &str
+) -> anyhow::Result<Option<usize>> {
+  let raw = raw.trim();
+  if raw.is_empty() {
+    return Ok(None);
+  }
+  match raw.parse::<usize>() {
+    | Ok(0) => Ok(None),
+    | Ok(depth) => Ok(Some(depth)),
+    | Err(_) => {
+      bail!(
+        "invalid undo.depth '{raw}' \
+         (expected a whole number of \
+         steps, 0 for unlimited)"
+      )
+    }
+  }
+}
+
+/// One of the two task lists.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum TaskSet {
+  Pending,
+  Completed
+}
+
+impl TaskSet {
+  /// Data file name; every backend
+  /// also uses it as the
+  /// `QuarantinedLine::file` label.
+  pub fn file_name(
+    self
+  ) -> &'static str {
+    match self {
+      | Self::Pending => "pending.data",
+      | Self::Completed => {
+        "completed.data"
+      }
+    }
+  }
+}
+
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum UndoStack {
+  Undo,
+  Redo
+}
+
+impl UndoStack {
+  pub fn file_name(
+    self
+  ) -> &'static str {
+    match self {
+      | Self::Undo => "undo.data",
+      | Self::Redo => "redo.data"
+    }
+  }
+}
+
+/// Persistence behind a `DataStore`.
+/// The store keeps the dry-run, undo
+/// depth and id rules; a backend only
+/// reads and replaces whole lists.
+/// Loads with `record_corrupt` set
+/// quarantine entries they cannot
+/// parse, and a `dry_run` save stages
+/// the tasks in the backend's cache
+/// without writing them.
+pub trait StorageBackend:
+  fmt::Debug + Send + Sync
+{
+  fn kind(&self) -> BackendKind;
+
+  fn task_index(
+    &self,
+    set: TaskSet,
+    mode: IndexMode,
+    record_corrupt: bool
+  ) -> anyhow::Result<Arc<TaskIndex>>;
+
+  fn save_tasks(
+    &self,
+    set: TaskSet,
+    tasks: &[Task],
+    mode: IndexMode,
+    dry_run: bool
+  ) -> anyhow::Result<()>;
+
+  fn invalidate_cache(&self);
+
+  /// Oldest snapshot first.
+  fn load_undo(
+    &self,
+    stack: UndoStack,
+    record_corrupt: bool
+  ) -> anyhow::Result<Vec<UndoEntry>>;
+
+  fn save_undo(
+    &self,
+    stack: UndoStack,
+    entries: &[UndoEntry]
+  ) -> anyhow::Result<()>;
+
+  /// Raw active context; empty when
+  /// none is set.
+  fn load_context(
+    &self
+  ) -> anyhow::Result<String>;
+
+  fn save_context(
+    &self,
+    name: &str
+  ) -> anyhow::Result<()>;
+
+  fn append_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()>;
+
+  fn load_history(
+    &self,
+    record_corrupt: bool
+  ) -> anyhow::Result<Vec<HistoryEntry>>;
+
+  fn save_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()>;
+
+  fn load_quarantined(
+    &self
+  ) -> anyhow::Result<
+    Vec<QuarantinedLine>
+  >;
+
+  /// An empty list clears the
+  /// quarantine.
+  fn save_quarantined(
+    &self,
+    entries: &[QuarantinedLine]
+  ) -> anyhow::Result<()>;
+}
+
+/// A filter term a [`TaskIndex`] can
+/// look up directly.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub enum IndexKey {
+  Tag(String),
+  Project(String),
+  Status(Status)
+}
+
+/// Parsed contents of one data file
+/// with lookup tables for the common
+/// filter keys. Positions refer to
+/// `tasks`.
+#[derive(Debug, Default)]
+pub struct TaskIndex {
+  tasks:      Vec<Task>,
+  by_uuid:    HashMap<Uuid, usize>,
+  by_status:
+    HashMap<Status, Vec<usize>>,
+  by_tag: HashMap<String, Vec<usize>>,
+  by_project:
+    HashMap<String, Vec<usize>>
+}
+
+impl TaskIndex {
+  pub fn build(
+    tasks: Vec<Task>
+  ) -> Self {
+    let mut index = Self::default();
+    for (pos, task) in
+      tasks.iter().enumerate()
+    {
+      index
+        .by_uuid
+        .insert(task.uuid, pos);
+      index
+        .by_status
+        .entry(task.status.clone())
+        .or_default()
+        .push(pos);
+      for tag in &task.tags {
+        index
+          .by_tag
+          .entry(tag.clone())
+          .or_default()
+          .push(pos);
+      }
+      if let Some(project) =
+        task.project.as_ref()
+      {
+        index
+          .by_project
+          .entry(project.clone())
+          .or_default()
+          .push(pos);
+      }
+    }
+    index.tasks = tasks;
+    index
+  }
+
+  pub fn tasks(&self) -> &[Task] {
+    &self.tasks
+  }
+
+  pub fn get(
+    &self,
+    uuid: &Uuid
+  ) -> Option<&Task> {
+    self
+      .by_uuid
+      .get(uuid)
+      .map(|pos| &self.tasks[*pos])
+  }
+
+  pub fn with_status(
+    &self,
+    status: &Status
+  ) -> impl Iterator<Item = &Task> {
+    self.positions(
+      self.by_status.get(status)
+    )
+  }
+
+  pub fn with_tag(
+    &self,
+    tag: &str
+  ) -> impl Iterator<Item = &Task> {
+    self.positions(self.by_tag.get(tag))
+  }
+
+  pub fn in_project(
+    &self,
+    project: &str
+  ) -> impl Iterator<Item = &Task> {
+    self.positions(
+      self.by_project.get(project)
+    )
+  }
+
+  /// Tasks that can satisfy every key
+  /// in `keys`, in file order: the
+  /// smallest matching bucket, or every
+  /// task when `keys` is empty. Callers
+  /// still apply the full filter.
+  pub fn candidates(
+    &self,
+    keys: &[IndexKey]
+  ) -> Vec<&Task> {
+    let smallest = keys
+      .iter()
+      .map(|key| self.bucket(key))
+      .min_by_key(|bucket| {
+        bucket.len()
+      });
+    match smallest {
+      | Some(bucket) => {
+        bucket
+          .iter()
+          .map(|pos| &self.tasks[*pos])
+          .collect()
+      }
+      | None => {
+        self.tasks.iter().collect()
+      }
+    }
+  }
+
+  fn bucket(
+    &self,
+    key: &IndexKey
+  ) -> &[usize] {
+    let positions = match key {
+      | IndexKey::Tag(tag) => {
+        self.by_tag.get(tag)
+      }
+      | IndexKey::Project(project) => {
+        self.by_project.get(project)
+      }
+      | IndexKey::Status(status) => {
+        self.by_status.get(status)
+      }
+    };
+    positions.map_or(&[], Vec::as_slice)
+  }
+
+  fn positions<'a>(
+    &'a self,
+    positions: Option<&'a Vec<usize>>
+  ) -> impl Iterator<Item = &'a Task>
+  {
+    positions
+      .into_iter()
+      .flatten()
+      .map(|pos| &self.tasks[*pos])
+  }
+}
+
+/// Size and mtime of a data file when
+/// it was last read or written; a
+/// mismatch means another process
+/// changed it.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+struct FileStamp {
+  modified: Option<SystemTime>,
+  len:      u64
+}
+
+#[derive(Debug)]
+struct CachedFile {
+  stamp: FileStamp,
+  index: Arc<TaskIndex>
+}
+
+type FileCache =
+  RwLock<Option<CachedFile>>;
+
+/// How a read or save treats the
+/// on-disk sidecar index. Backends
+/// without sidecars ignore it.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum IndexMode {
+  Off,
+  ReadOnly,
+  ReadWrite
 }
 
+/// Both task lists as they were before
+/// one undoable write.
 #[derive(
   Debug, Clone, Serialize, Deserialize,
 )]
-struct UndoEntry {
-  pending:   Vec<Task>,
-  completed: Vec<Task>
+pub struct UndoEntry {
+  pub pending:   Vec<Task>,
+  pub completed: Vec<Task>
+}
+
+/// JSONL files in the data directory,
+/// cached in memory until their size
+/// or mtime changes.
+#[derive(Debug)]
+pub struct FileBackend {
+  pending_path:    PathBuf,
+  completed_path:  PathBuf,
+  undo_path:       PathBuf,
+  redo_path:       PathBuf,
+  context_path:    PathBuf,
+  history_path:    PathBuf,
+  corrupt_path:    PathBuf,
+  pending_cache:   FileCache,
+  completed_cache: FileCache
 }
 
 impl DataStore {
+  /// Opens the file backend.
+  pub fn open(
+    data_dir: &Path
+  ) -> anyhow::Result<Self> {
+    Self::open_with(
+      data_dir,
+      BackendKind::File
+    )
+  }
+
   #[tracing::instrument(skip(
     data_dir
   ))]
-  pub fn open(
-    data_dir: &Path
+  pub fn open_with(
+    data_dir: &Path,
+    kind: BackendKind
   ) -> anyhow::Result<Self> {
     let data_dir =
       data_dir.to_path_buf();
@@ -63,66 +561,222 @@ impl DataStore {
         )
       })?;
 
-    let pending_path =
-      data_dir.join("pending.data");
-    let completed_path =
-      data_dir.join("completed.data");
-    let undo_path =
-      data_dir.join("undo.data");
-    let context_path =
-      data_dir.join("context.data");
-
-    if !pending_path.exists() {
-      fs::write(&pending_path, "")?;
-    }
-    if !completed_path.exists() {
-      fs::write(&completed_path, "")?;
-    }
-    if !undo_path.exists() {
-      fs::write(&undo_path, "")?;
-    }
-    if !context_path.exists() {
-      fs::write(&context_path, "")?;
+    let report =
+      migrate::prepare_data_dir(
+        &data_dir
+      )?;
+    if !report.applied.is_empty() {
+      info!(
+        from = %report.from,
+        to = %report.to,
+        backup = ?report.backup,
+        "migrated data directory"
+      );
     }
 
+    let backend: Box<
+      dyn StorageBackend
+    > = match kind {
+      | BackendKind::File => {
+        Box::new(FileBackend::open(
+          &data_dir
+        )?)
+      }
+      #[cfg(feature = "sqlite")]
+      | BackendKind::Sqlite => {
+        Box::new(SqliteBackend::open(
+          &data_dir
+        )?)
+      }
+      #[cfg(not(feature = "sqlite"))]
+      | BackendKind::Sqlite => {
+        bail!(
+          "data.backend=sqlite needs \
+           rivet built with the \
+           `sqlite` feature"
+        )
+      }
+    };
     info!(
-        data_dir = %data_dir.display(),
-        pending = %pending_path.display(),
-        completed = %completed_path.display(),
-        undo = %undo_path.display(),
-        context = %context_path.display(),
-        "opened datastore"
+      data_dir = %data_dir.display(),
+      backend = kind.name(),
+      "opened datastore"
     );
+    Ok(Self::with_backend(
+      &data_dir, backend
+    ))
+  }
 
-    Ok(Self {
-      data_dir,
-      pending_path,
-      completed_path,
-      undo_path,
-      context_path
-    })
+  /// Wraps an already opened backend;
+  /// `open_with` also migrates the data
+  /// directory first.
+  pub fn with_backend(
+    data_dir: &Path,
+    backend: Box<dyn StorageBackend>
+  ) -> Self {
+    Self {
+      data_dir: data_dir.to_path_buf(),
+      pending_path: data_dir
+        .join("pending.data"),
+      completed_path: data_dir
+        .join("completed.data"),
+      undo_path: data_dir
+        .join("undo.data"),
+      redo_path: data_dir
+        .join("redo.data"),
+      context_path: data_dir
+        .join("context.data"),
+      history_path: data_dir
+        .join("history.data"),
+      corrupt_path: data_dir
+        .join(CORRUPT_FILE),
+      backend,
+      dry_run: false,
+      persist_index: false,
+      undo_depth: None
+    }
+  }
+
+  pub fn backend_kind(
+    &self
+  ) -> BackendKind {
+    self.backend.kind()
   }
 
+  /// Indexed view of pending.data,
+  /// served from memory until the file
+  /// changes on disk.
   #[tracing::instrument(skip(self))]
-  pub fn load_pending(
+  pub fn pending_index(
     &self
-  ) -> anyhow::Result<Vec<Task>> {
-    load_jsonl(&self.pending_path)
+  ) -> anyhow::Result<Arc<TaskIndex>>
+  {
+    self
+      .backend
+      .task_index(
+        TaskSet::Pending,
+        self.index_mode(),
+        !self.dry_run
+      )
       .context(
         "failed to load pending.data"
       )
   }
 
+  /// Indexed view of completed.data,
+  /// served from memory until the file
+  /// changes on disk.
   #[tracing::instrument(skip(self))]
-  pub fn load_completed(
+  pub fn completed_index(
     &self
-  ) -> anyhow::Result<Vec<Task>> {
-    load_jsonl(&self.completed_path)
+  ) -> anyhow::Result<Arc<TaskIndex>>
+  {
+    self
+      .backend
+      .task_index(
+        TaskSet::Completed,
+        self.index_mode(),
+        !self.dry_run
+      )
       .context(
         "failed to load completed.data"
       )
   }
 
+  /// In dry-run mode saves only replace
+  /// the in-memory index (later reads
+  /// in this process see them) and
+  /// undo and context writes are
+  /// skipped, so nothing reaches
+  /// disk.
+  pub fn set_dry_run(
+    &mut self,
+    dry_run: bool
+  ) {
+    self.dry_run = dry_run;
+  }
+
+  pub fn is_dry_run(&self) -> bool {
+    self.dry_run
+  }
+
+  /// Keeps a `*.index` sidecar next to
+  /// each data file (`index.persist`)
+  /// so the first read in a new process
+  /// loads that instead of parsing
+  /// every line.
+  pub fn set_persist_index(
+    &mut self,
+    persist: bool
+  ) {
+    self.persist_index = persist;
+  }
+
+  /// Caps the undo and redo stacks at
+  /// `depth` steps each (`undo.depth`),
+  /// dropping the oldest first. `None`
+  /// keeps every step.
+  pub fn set_undo_depth(
+    &mut self,
+    depth: Option<usize>
+  ) {
+    self.undo_depth = depth;
+  }
+
+  /// Sidecars are read whenever
+  /// persistence is on but never
+  /// written in a dry run.
+  fn index_mode(&self) -> IndexMode {
+    match (
+      self.persist_index,
+      self.dry_run
+    ) {
+      | (false, _) => IndexMode::Off,
+      | (true, true) => {
+        IndexMode::ReadOnly
+      }
+      | (true, false) => {
+        IndexMode::ReadWrite
+      }
+    }
+  }
+
+  #[tracing::instrument(skip(self))]
+  pub fn load_pending(
+    &self
+  ) -> anyhow::Result<Vec<Task>> {
+    Ok(
+      self
+        .pending_index()?
+        .tasks()
+        .to_vec()
+    )
+  }
+
+  #[tracing::instrument(skip(self))]
+  pub fn load_completed(
+    &self
+  ) -> anyhow::Result<Vec<Task>> {
+    Ok(
+      self
+        .completed_index()?
+        .tasks()
+        .to_vec()
+    )
+  }
+
+  /// Drops the in-memory indexes so the
+  /// next read goes back to disk. Reads
+  /// already notice size/mtime changes;
+  /// this also catches a same-size
+  /// rewrite within one mtime tick.
+  pub fn invalidate_cache(&self) {
+    self.backend.invalidate_cache();
+    debug!(
+      "datastore cache invalidated"
+    );
+  }
+
   #[tracing::instrument(skip(
     self, tasks
   ))]
@@ -130,13 +784,11 @@ impl DataStore {
     &self,
     tasks: &[Task]
   ) -> anyhow::Result<()> {
-    save_jsonl_atomic(
-      &self.pending_path,
+    self.clear_redo()?;
+    self.write_set(
+      TaskSet::Pending,
       tasks
     )
-    .context(
-      "failed to save pending.data"
-    )
   }
 
   #[tracing::instrument(skip(
@@ -146,13 +798,57 @@ impl DataStore {
     &self,
     tasks: &[Task]
   ) -> anyhow::Result<()> {
-    save_jsonl_atomic(
-      &self.completed_path,
+    self.clear_redo()?;
+    self.write_set(
+      TaskSet::Completed,
       tasks
     )
-    .context(
-      "failed to save completed.data"
-    )
+  }
+
+  fn write_set(
+    &self,
+    set: TaskSet,
+    tasks: &[Task]
+  ) -> anyhow::Result<()> {
+    self
+      .backend
+      .save_tasks(
+        set,
+        tasks,
+        self.index_mode(),
+        self.dry_run
+      )
+      .with_context(|| {
+        format!(
+          "failed to save {}",
+          set.file_name()
+        )
+      })
+  }
+
+  /// Redo snapshots are whole-store
+  /// states recorded against the data
+  /// as it was right after an undo, so
+  /// any other write makes them stale.
+  /// Only `step` writes without coming
+  /// through here.
+  fn clear_redo(
+    &self
+  ) -> anyhow::Result<()> {
+    if self.dry_run
+      || self
+        .backend
+        .load_undo(
+          UndoStack::Redo,
+          false
+        )?
+        .is_empty()
+    {
+      return Ok(());
+    }
+    self
+      .backend
+      .save_undo(UndoStack::Redo, &[])
   }
 
   pub fn next_id(
@@ -224,6 +920,11 @@ impl DataStore {
     self.save_pending(tasks)
   }
 
+  /// Records the state before an
+  /// undoable write. A new write makes
+  /// anything undone before it
+  /// unreachable, so the redo stack is
+  /// cleared.
   #[tracing::instrument(skip(
     self, pending, completed
   ))]
@@ -232,19 +933,21 @@ impl DataStore {
     pending: &[Task],
     completed: &[Task]
   ) -> anyhow::Result<()> {
-    let mut entries =
-      load_undo_entries(
-        &self.undo_path
-      )?;
-    entries.push(UndoEntry {
-      pending:   pending.to_vec(),
-      completed: completed.to_vec()
-    });
-    save_undo_entries(
-      &self.undo_path,
-      &entries
+    if self.dry_run {
+      debug!(
+        "dry run: skipping undo \
+         snapshot"
+      );
+      return Ok(());
+    }
+    self.push_snapshot(
+      UndoStack::Undo,
+      pending,
+      completed
     )?;
-    Ok(())
+    self
+      .backend
+      .save_undo(UndoStack::Redo, &[])
   }
 
   #[tracing::instrument(skip(self))]
@@ -266,67 +969,262 @@ impl DataStore {
   ) -> anyhow::Result<
     Option<(Vec<Task>, Vec<Task>)>
   > {
-    let mut entries =
-      load_undo_entries(
-        &self.undo_path
-      )?;
-    let Some(entry) = entries.pop()
-    else {
-      return Ok(None);
-    };
-    save_undo_entries(
-      &self.undo_path,
-      &entries
-    )?;
-    Ok(Some((
-      entry.pending,
-      entry.completed
-    )))
+    self.pop_snapshot(UndoStack::Undo)
   }
 
+  /// Steps back one undoable write and
+  /// keeps the state it replaces on the
+  /// redo stack. `false` when there is
+  /// nothing to undo.
   #[tracing::instrument(skip(self))]
-  pub fn get_active_context(
+  pub fn undo(
     &self
-  ) -> anyhow::Result<Option<String>>
-  {
-    let raw = fs::read_to_string(
-      &self.context_path
+  ) -> anyhow::Result<bool> {
+    self.step(
+      UndoStack::Undo,
+      UndoStack::Redo
     )
-    .with_context(|| {
-      format!(
-        "failed reading {}",
-        self.context_path.display()
-      )
-    })?;
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-      Ok(None)
-    } else {
-      Ok(Some(trimmed.to_string()))
-    }
   }
 
+  /// Reapplies the last undone write
+  /// and makes it undoable again,
+  /// leaving the rest of the redo stack
+  /// alone. `false` when there is
+  /// nothing to redo.
   #[tracing::instrument(skip(self))]
-  pub fn set_active_context(
-    &self,
-    name: Option<&str>
-  ) -> anyhow::Result<()> {
-    let payload =
-      name.unwrap_or_default();
-    fs::write(
-      &self.context_path,
-      payload
+  pub fn redo(
+    &self
+  ) -> anyhow::Result<bool> {
+    self.step(
+      UndoStack::Redo,
+      UndoStack::Undo
     )
-    .with_context(|| {
-      format!(
-        "failed writing {}",
-        self.context_path.display()
-      )
-    })?;
-    Ok(())
   }
 
-  #[tracing::instrument(skip(self))]
+  /// Number of steps `undo` and `redo`
+  /// can take.
+  pub fn undo_redo_depths(
+    &self
+  ) -> anyhow::Result<(usize, usize)>
+  {
+    Ok((
+      self
+        .backend
+        .load_undo(
+          UndoStack::Undo,
+          false
+        )?
+        .len(),
+      self
+        .backend
+        .load_undo(
+          UndoStack::Redo,
+          false
+        )?
+        .len()
+    ))
+  }
+
+  fn step(
+    &self,
+    from: UndoStack,
+    to: UndoStack
+  ) -> anyhow::Result<bool> {
+    let Some((pending, completed)) =
+      self.pop_snapshot(from)?
+    else {
+      return Ok(false);
+    };
+    if !self.dry_run {
+      self.push_snapshot(
+        to,
+        &self.load_pending()?,
+        &self.load_completed()?
+      )?;
+    }
+    self.write_set(
+      TaskSet::Pending,
+      &pending
+    )?;
+    self.write_set(
+      TaskSet::Completed,
+      &completed
+    )?;
+    Ok(true)
+  }
+
+  fn push_snapshot(
+    &self,
+    stack: UndoStack,
+    pending: &[Task],
+    completed: &[Task]
+  ) -> anyhow::Result<()> {
+    let mut entries = self
+      .backend
+      .load_undo(stack, true)?;
+    entries.push(UndoEntry {
+      pending:   pending.to_vec(),
+      completed: completed.to_vec()
+    });
+    if let Some(depth) = self.undo_depth
+      && entries.len() > depth
+    {
+      entries
+        .drain(..entries.len() - depth);
+    }
+    self
+      .backend
+      .save_undo(stack, &entries)
+  }
+
+  fn pop_snapshot(
+    &self,
+    stack: UndoStack
+  ) -> anyhow::Result<
+    Option<(Vec<Task>, Vec<Task>)>
+  > {
+    let mut entries =
+      self.backend.load_undo(
+        stack,
+        !self.dry_run
+      )?;
+    let Some(entry) = entries.pop()
+    else {
+      return Ok(None);
+    };
+    if !self.dry_run {
+      self
+        .backend
+        .save_undo(stack, &entries)?;
+    }
+    Ok(Some((
+      entry.pending,
+      entry.completed
+    )))
+  }
+
+  #[tracing::instrument(skip(self))]
+  pub fn get_active_context(
+    &self
+  ) -> anyhow::Result<Option<String>>
+  {
+    let raw =
+      self.backend.load_context()?;
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+      Ok(None)
+    } else {
+      Ok(Some(trimmed.to_string()))
+    }
+  }
+
+  #[tracing::instrument(skip(self))]
+  pub fn set_active_context(
+    &self,
+    name: Option<&str>
+  ) -> anyhow::Result<()> {
+    let payload =
+      name.unwrap_or_default();
+    if self.dry_run {
+      debug!(
+        payload,
+        "dry run: skipping context \
+         write"
+      );
+      return Ok(());
+    }
+    self.backend.save_context(payload)
+  }
+
+  /// Appends to the audit log. Existing
+  /// lines are never rewritten here;
+  /// only `prune_history` drops them.
+  #[tracing::instrument(skip(
+    self, entries
+  ))]
+  pub fn append_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()> {
+    if entries.is_empty() {
+      return Ok(());
+    }
+    if self.dry_run {
+      debug!(
+        count = entries.len(),
+        "dry run: skipping history \
+         append"
+      );
+      return Ok(());
+    }
+    self.backend.append_history(entries)
+  }
+
+  #[tracing::instrument(skip(self))]
+  pub fn load_history(
+    &self
+  ) -> anyhow::Result<Vec<HistoryEntry>>
+  {
+    self
+      .backend
+      .load_history(!self.dry_run)
+  }
+
+  /// Everything quarantined so far
+  /// (`corrupt.data` for the file
+  /// backend), oldest first.
+  #[tracing::instrument(skip(self))]
+  pub fn load_quarantined(
+    &self
+  ) -> anyhow::Result<
+    Vec<QuarantinedLine>
+  > {
+    self.backend.load_quarantined()
+  }
+
+  /// Replaces the quarantine; an empty
+  /// list clears it.
+  pub fn save_quarantined(
+    &self,
+    entries: &[QuarantinedLine]
+  ) -> anyhow::Result<()> {
+    if self.dry_run {
+      return Ok(());
+    }
+    self
+      .backend
+      .save_quarantined(entries)
+  }
+
+  /// Rewrites the audit log keeping
+  /// only entries for which `keep`
+  /// holds and returns how many were
+  /// dropped.
+  #[tracing::instrument(skip(
+    self, keep
+  ))]
+  pub fn prune_history(
+    &self,
+    keep: impl Fn(&HistoryEntry) -> bool
+  ) -> anyhow::Result<usize> {
+    let entries =
+      self.load_history()?;
+    let before = entries.len();
+    let kept: Vec<HistoryEntry> =
+      entries
+        .into_iter()
+        .filter(|entry| keep(entry))
+        .collect();
+    let dropped = before - kept.len();
+    if dropped == 0 || self.dry_run {
+      return Ok(dropped);
+    }
+    self.backend.save_history(&kept)?;
+    info!(dropped, "pruned history");
+    Ok(dropped)
+  }
+
+  #[tracing::instrument(skip(self))]
   pub fn purge_deleted(
     &self
   ) -> anyhow::Result<()> {
@@ -346,17 +1244,470 @@ impl DataStore {
     );
     self.save_pending(&kept)
   }
+
+  /// Taskwarrior-style id garbage
+  /// collection: pending and waiting
+  /// tasks are renumbered 1..n in their
+  /// current id order, and deleted or
+  /// completed tasks lose their id.
+  /// Returns whether any id changed;
+  /// nothing is written otherwise.
+  #[tracing::instrument(skip(self))]
+  pub fn compact_ids(
+    &self
+  ) -> anyhow::Result<bool> {
+    let mut pending =
+      self.load_pending()?;
+    pending.sort_by_key(|t| {
+      t.id.unwrap_or(u64::MAX)
+    });
+    let mut next = 0_u64;
+    let mut pending_changed = false;
+    for task in &mut pending {
+      let id = if matches!(
+        task.status,
+        Status::Pending
+          | Status::Waiting
+      ) {
+        next += 1;
+        Some(next)
+      } else {
+        None
+      };
+      if task.id != id {
+        task.id = id;
+        pending_changed = true;
+      }
+    }
+
+    let mut completed =
+      self.load_completed()?;
+    let mut completed_changed = false;
+    for task in &mut completed {
+      if task.id.take().is_some() {
+        completed_changed = true;
+      }
+    }
+
+    if pending_changed {
+      pending.sort_by_key(|t| {
+        t.id.unwrap_or(u64::MAX)
+      });
+      self.save_pending(&pending)?;
+    }
+    if completed_changed {
+      self
+        .save_completed(&completed)?;
+    }
+    debug!(
+      pending_changed,
+      completed_changed,
+      "compacted task ids"
+    );
+    Ok(
+      pending_changed
+        || completed_changed
+    )
+  }
+}
+
+impl FileBackend {
+  /// Creates any missing data file.
+  pub fn open(
+    data_dir: &Path
+  ) -> anyhow::Result<Self> {
+    let backend = Self {
+      pending_path:    data_dir.join(
+        TaskSet::Pending.file_name()
+      ),
+      completed_path:  data_dir.join(
+        TaskSet::Completed.file_name()
+      ),
+      undo_path:       data_dir.join(
+        UndoStack::Undo.file_name()
+      ),
+      redo_path:       data_dir.join(
+        UndoStack::Redo.file_name()
+      ),
+      context_path:    data_dir
+        .join("context.data"),
+      history_path:    data_dir
+        .join("history.data"),
+      corrupt_path:    data_dir
+        .join(CORRUPT_FILE),
+      pending_cache:   RwLock::new(
+        None
+      ),
+      completed_cache: RwLock::new(
+        None
+      )
+    };
+    for path in [
+      &backend.pending_path,
+      &backend.completed_path,
+      &backend.undo_path,
+      &backend.redo_path,
+      &backend.context_path,
+      &backend.history_path
+    ] {
+      if !path.exists() {
+        fs::write(path, "")?;
+      }
+    }
+    Ok(backend)
+  }
+
+  fn task_file(
+    &self,
+    set: TaskSet
+  ) -> (&FileCache, &Path) {
+    match set {
+      | TaskSet::Pending => {
+        (
+          &self.pending_cache,
+          &self.pending_path
+        )
+      }
+      | TaskSet::Completed => {
+        (
+          &self.completed_cache,
+          &self.completed_path
+        )
+      }
+    }
+  }
+
+  fn undo_file(
+    &self,
+    stack: UndoStack
+  ) -> &Path {
+    match stack {
+      | UndoStack::Undo => {
+        &self.undo_path
+      }
+      | UndoStack::Redo => {
+        &self.redo_path
+      }
+    }
+  }
+}
+
+impl StorageBackend for FileBackend {
+  fn kind(&self) -> BackendKind {
+    BackendKind::File
+  }
+
+  fn task_index(
+    &self,
+    set: TaskSet,
+    mode: IndexMode,
+    record_corrupt: bool
+  ) -> anyhow::Result<Arc<TaskIndex>>
+  {
+    let (cache, path) =
+      self.task_file(set);
+    cached_index(
+      cache,
+      path,
+      mode,
+      record_corrupt
+    )
+  }
+
+  fn save_tasks(
+    &self,
+    set: TaskSet,
+    tasks: &[Task],
+    mode: IndexMode,
+    dry_run: bool
+  ) -> anyhow::Result<()> {
+    let (cache, path) =
+      self.task_file(set);
+    save_cached(
+      cache, path, tasks, dry_run, mode
+    )
+  }
+
+  fn invalidate_cache(&self) {
+    for cache in [
+      &self.pending_cache,
+      &self.completed_cache
+    ] {
+      *cache.write().unwrap_or_else(
+        PoisonError::into_inner
+      ) = None;
+    }
+  }
+
+  fn load_undo(
+    &self,
+    stack: UndoStack,
+    record_corrupt: bool
+  ) -> anyhow::Result<Vec<UndoEntry>>
+  {
+    load_jsonl_lines(
+      self.undo_file(stack),
+      record_corrupt
+    )
+  }
+
+  fn save_undo(
+    &self,
+    stack: UndoStack,
+    entries: &[UndoEntry]
+  ) -> anyhow::Result<()> {
+    save_jsonl_lines(
+      self.undo_file(stack),
+      entries
+    )
+  }
+
+  fn load_context(
+    &self
+  ) -> anyhow::Result<String> {
+    fs::read_to_string(
+      &self.context_path
+    )
+    .with_context(|| {
+      format!(
+        "failed reading {}",
+        self.context_path.display()
+      )
+    })
+  }
+
+  fn save_context(
+    &self,
+    name: &str
+  ) -> anyhow::Result<()> {
+    fs::write(&self.context_path, name)
+      .with_context(|| {
+        format!(
+          "failed writing {}",
+          self.context_path.display()
+        )
+      })
+  }
+
+  fn append_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()> {
+    let mut file =
+      fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&self.history_path)
+        .with_context(|| {
+          format!(
+            "failed opening {}",
+            self.history_path.display()
+          )
+        })?;
+    let mut buffer = String::new();
+    for entry in entries {
+      buffer.push_str(
+        &serde_json::to_string(entry)?
+      );
+      buffer.push('\n');
+    }
+    file
+      .write_all(buffer.as_bytes())?;
+    Ok(())
+  }
+
+  fn load_history(
+    &self,
+    record_corrupt: bool
+  ) -> anyhow::Result<Vec<HistoryEntry>>
+  {
+    if !self.history_path.exists() {
+      return Ok(Vec::new());
+    }
+    load_jsonl_lines(
+      &self.history_path,
+      record_corrupt
+    )
+  }
+
+  fn save_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()> {
+    save_jsonl_lines(
+      &self.history_path,
+      entries
+    )
+  }
+
+  fn load_quarantined(
+    &self
+  ) -> anyhow::Result<
+    Vec<QuarantinedLine>
+  > {
+    load_quarantine(&self.corrupt_path)
+  }
+
+  fn save_quarantined(
+    &self,
+    entries: &[QuarantinedLine]
+  ) -> anyhow::Result<()> {
+    if entries.is_empty() {
+      if self.corrupt_path.exists() {
+        fs::remove_file(
+          &self.corrupt_path
+        )?;
+      }
+      return Ok(());
+    }
+    save_jsonl_lines(
+      &self.corrupt_path,
+      entries
+    )
+  }
+}
+
+fn file_stamp(
+  path: &Path
+) -> anyhow::Result<FileStamp> {
+  let meta = fs::metadata(path)
+    .with_context(|| {
+      format!(
+        "failed to stat {}",
+        path.display()
+      )
+    })?;
+  Ok(FileStamp {
+    modified: meta.modified().ok(),
+    len:      meta.len()
+  })
+}
+
+fn cached_index(
+  cache: &FileCache,
+  path: &Path,
+  mode: IndexMode,
+  record_corrupt: bool
+) -> anyhow::Result<Arc<TaskIndex>> {
+  let stamp = file_stamp(path)?;
+  if let Some(cached) = cache
+    .read()
+    .unwrap_or_else(
+      PoisonError::into_inner
+    )
+    .as_ref()
+    && cached.stamp == stamp
+  {
+    return Ok(Arc::clone(
+      &cached.index
+    ));
+  }
+
+  let persisted = match mode {
+    | IndexMode::Off => None,
+    | _ => index_file::load(path)
+  };
+  let tasks = match persisted {
+    | Some(tasks) => tasks,
+    | None => {
+      let tasks = load_jsonl(
+        path,
+        record_corrupt
+      )?;
+      if mode == IndexMode::ReadWrite {
+        write_index(path, &tasks);
+      }
+      tasks
+    }
+  };
+  let index =
+    Arc::new(TaskIndex::build(tasks));
+  *cache.write().unwrap_or_else(
+    PoisonError::into_inner
+  ) = Some(CachedFile {
+    stamp,
+    index: Arc::clone(&index)
+  });
+  Ok(index)
+}
+
+fn save_cached(
+  cache: &FileCache,
+  path: &Path,
+  tasks: &[Task],
+  dry_run: bool,
+  mode: IndexMode
+) -> anyhow::Result<()> {
+  let mut guard =
+    cache.write().unwrap_or_else(
+      PoisonError::into_inner
+    );
+  *guard = None;
+  if dry_run {
+    debug!(file = %path.display(), count = tasks.len(), "dry run: staging instead of writing");
+  } else {
+    save_jsonl_atomic(path, tasks)?;
+    if mode == IndexMode::ReadWrite {
+      write_index(path, tasks);
+    }
+  }
+  *guard = Some(CachedFile {
+    stamp: file_stamp(path)?,
+    index: Arc::new(TaskIndex::build(
+      tasks.to_vec()
+    ))
+  });
+  Ok(())
+}
+
+/// The sidecar is only a cache: failing
+/// to write it leaves the next start
+/// parsing the data file, nothing more.
+fn write_index(
+  path: &Path,
+  tasks: &[Task]
+) {
+  if let Err(err) =
+    index_file::store(path, tasks)
+  {
+    warn!(file = %path.display(), err = %format!("{err:#}"), "failed to write task index");
+  }
 }
 
 #[tracing::instrument(skip(path))]
 fn load_jsonl(
-  path: &Path
+  path: &Path,
+  record_corrupt: bool
 ) -> anyhow::Result<Vec<Task>> {
   debug!(file = %path.display(), "loading jsonl");
+  let out = parse_jsonl_lines(
+    path,
+    record_corrupt
+  )?;
+  debug!(
+    count = out.len(),
+    "loaded tasks from jsonl"
+  );
+  Ok(out)
+}
+
+/// Parses every non-empty line of
+/// `path`. Lines that fail to parse are
+/// skipped with a warning and, when
+/// `record_corrupt` is set, appended to
+/// `corrupt.data` next to the file.
+fn parse_jsonl_lines<T>(
+  path: &Path,
+  record_corrupt: bool
+) -> anyhow::Result<Vec<T>>
+where
+  T: DeserializeOwned
+{
   let file = fs::File::open(path)?;
   let reader = BufReader::new(file);
 
   let mut out = Vec::new();
+  let mut corrupt = Vec::new();
   for (idx, line) in
     reader.lines().enumerate()
   {
@@ -365,24 +1716,135 @@ fn load_jsonl(
     if trimmed.is_empty() {
       continue;
     }
+    match serde_json::from_str(trimmed)
+    {
+      | Ok(entry) => out.push(entry),
+      | Err(err) => {
+        corrupt.push((
+          idx + 1,
+          trimmed.to_string(),
+          err.to_string()
+        ));
+      }
+    }
+  }
 
-    let task: Task =
-      serde_json::from_str(trimmed)
-        .with_context(|| {
-          format!(
-            "failed parsing {} line {}",
-            path.display(),
-            idx + 1
-          )
-        })?;
-    out.push(task);
+  if !corrupt.is_empty() {
+    quarantine(
+      path,
+      corrupt,
+      record_corrupt
+    )?;
   }
+  Ok(out)
+}
 
-  debug!(
-    count = out.len(),
-    "loaded tasks from jsonl"
+fn quarantine(
+  path: &Path,
+  corrupt: Vec<(usize, String, String)>,
+  record: bool
+) -> anyhow::Result<()> {
+  let file = path
+    .file_name()
+    .map(|name| {
+      name
+        .to_string_lossy()
+        .into_owned()
+    })
+    .unwrap_or_default();
+  let sidecar = path
+    .parent()
+    .unwrap_or_else(|| Path::new("."))
+    .join(CORRUPT_FILE);
+  warn!(
+    file = %path.display(),
+    skipped = corrupt.len(),
+    quarantine = %sidecar.display(),
+    "skipped unparseable line(s); run \
+     `task doctor` to review them"
   );
-  Ok(out)
+  if !record {
+    return Ok(());
+  }
+
+  let mut entries =
+    load_quarantine(&sidecar)?;
+  if merge_quarantined(
+    &mut entries,
+    &file,
+    corrupt,
+    Utc::now()
+  ) {
+    save_jsonl_lines(
+      &sidecar, &entries
+    )
+    .with_context(|| {
+      format!(
+        "failed to write {}",
+        sidecar.display()
+      )
+    })?;
+  }
+  Ok(())
+}
+
+/// Adds the `(line, raw, error)`
+/// triples not yet recorded for
+/// `file`; `true` when any was new.
+fn merge_quarantined(
+  entries: &mut Vec<QuarantinedLine>,
+  file: &str,
+  corrupt: Vec<(usize, String, String)>,
+  now: DateTime<Utc>
+) -> bool {
+  let before = entries.len();
+  for (line, raw, error) in corrupt {
+    let known =
+      entries.iter().any(|entry| {
+        entry.file == file
+          && entry.raw == raw
+      });
+    if !known {
+      entries.push(QuarantinedLine {
+        file: file.to_string(),
+        line,
+        raw,
+        error,
+        quarantined: now
+      });
+    }
+  }
+  entries.len() > before
+}
+
+/// The sidecar is read leniently too;
+/// a damaged line in it is dropped
+/// rather than quarantined again.
+fn load_quarantine(
+  path: &Path
+) -> anyhow::Result<Vec<QuarantinedLine>>
+{
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+  let raw = fs::read_to_string(path)
+    .with_context(|| {
+      format!(
+        "failed to read {}",
+        path.display()
+      )
+    })?;
+  Ok(
+    raw
+      .lines()
+      .filter_map(|line| {
+        serde_json::from_str(
+          line.trim()
+        )
+        .ok()
+      })
+      .collect()
+  )
 }
 
 #[tracing::instrument(skip(
@@ -420,45 +1882,31 @@ fn save_jsonl_atomic(
 }
 
 #[tracing::instrument(skip(path))]
-fn load_undo_entries(
-  path: &Path
-) -> anyhow::Result<Vec<UndoEntry>> {
-  debug!(file = %path.display(), "loading undo entries");
-  let file = fs::File::open(path)?;
-  let reader = BufReader::new(file);
-
-  let mut out = Vec::new();
-  for (idx, line) in
-    reader.lines().enumerate()
-  {
-    let line = line?;
-    let trimmed = line.trim();
-    if trimmed.is_empty() {
-      continue;
-    }
-    let entry: UndoEntry =
-      serde_json::from_str(trimmed)
-        .with_context(|| {
-          format!(
-            "failed parsing {} line {}",
-            path.display(),
-            idx + 1
-          )
-        })?;
-    out.push(entry);
-  }
-
-  Ok(out)
+fn load_jsonl_lines<T>(
+  path: &Path,
+  record_corrupt: bool
+) -> anyhow::Result<Vec<T>>
+where
+  T: DeserializeOwned
+{
+  debug!(file = %path.display(), "loading journal entries");
+  parse_jsonl_lines(
+    path,
+    record_corrupt
+  )
 }
 
 #[tracing::instrument(skip(
   path, entries
 ))]
-fn save_undo_entries(
+fn save_jsonl_lines<T>(
   path: &Path,
-  entries: &[UndoEntry]
-) -> anyhow::Result<()> {
-  debug!(file = %path.display(), count = entries.len(), "saving undo entries");
+  entries: &[T]
+) -> anyhow::Result<()>
+where
+  T: Serialize
+{
+  debug!(file = %path.display(), count = entries.len(), "saving journal entries");
   let dir = path
     .parent()
     .unwrap_or_else(|| Path::new("."));
@@ -481,3 +1929,536 @@ fn save_undo_entries(
   )?;
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::history::HistoryAction;
+
+  fn descriptions(
+    tasks: &[Task]
+  ) -> Vec<&str> {
+    tasks
+      .iter()
+      .map(|task| {
+        task.description.as_str()
+      })
+      .collect()
+  }
+
+  fn history_entry(
+    task: &Task,
+    action: HistoryAction
+  ) -> HistoryEntry {
+    HistoryEntry {
+      at: task.entry,
+      uuid: task.uuid,
+      command: "add".to_string(),
+      action,
+      changes: Vec::new()
+    }
+  }
+
+  /// Every backend must behave the same
+  /// through `DataStore`.
+  fn run_suite(kind: BackendKind) {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let mut store =
+      DataStore::open_with(
+        temp.path(),
+        kind
+      )
+      .expect("open store");
+    assert_eq!(
+      store.backend_kind(),
+      kind
+    );
+    store.set_undo_depth(Some(2));
+    let now = Utc::now();
+
+    let first = Task::new_pending(
+      "first".to_string(),
+      now,
+      1
+    );
+    let second = Task::new_pending(
+      "second".to_string(),
+      now,
+      2
+    );
+    let pending = store
+      .add_task(
+        Vec::new(),
+        second.clone()
+      )
+      .expect("add second");
+    store
+      .add_task(pending, first.clone())
+      .expect("add first");
+    assert_eq!(
+      descriptions(
+        &store
+          .load_pending()
+          .expect("load")
+      ),
+      ["first", "second"]
+    );
+    let index = store
+      .pending_index()
+      .expect("index");
+    assert!(
+      index.get(&second.uuid).is_some()
+    );
+    assert_eq!(
+      index
+        .with_status(&Status::Pending)
+        .count(),
+      2
+    );
+
+    store
+      .push_current_undo_snapshot()
+      .expect("snapshot");
+    store
+      .move_to_completed(second.uuid)
+      .expect("complete");
+    assert_eq!(
+      store
+        .load_pending()
+        .expect("load")
+        .len(),
+      1
+    );
+    assert_eq!(
+      descriptions(
+        &store
+          .load_completed()
+          .expect("load")
+      ),
+      ["second"]
+    );
+    assert!(
+      store.undo().expect("undo")
+    );
+    assert_eq!(
+      store
+        .load_pending()
+        .expect("load")
+        .len(),
+      2
+    );
+    assert_eq!(
+      store
+        .undo_redo_depths()
+        .expect("depths"),
+      (0, 1)
+    );
+    assert!(
+      store.redo().expect("redo")
+    );
+    assert!(
+      store
+        .load_completed()
+        .expect("load")
+        .len()
+        == 1
+    );
+
+    // A write that takes no snapshot
+    // still strands the redo stack.
+    assert!(
+      store.undo().expect("undo")
+    );
+    let after_undo = store
+      .load_pending()
+      .expect("load");
+    let after_undo =
+      descriptions(&after_undo);
+    store
+      .save_completed(&[])
+      .expect("plain write");
+    assert!(
+      !store.redo().expect("redo")
+    );
+    assert_eq!(
+      descriptions(
+        &store
+          .load_pending()
+          .expect("load")
+      ),
+      after_undo
+    );
+    assert!(
+      store
+        .load_completed()
+        .expect("load")
+        .is_empty()
+    );
+    store
+      .save_completed(
+        std::slice::from_ref(&second)
+      )
+      .expect("restore");
+    for _ in 0..3 {
+      store
+        .push_current_undo_snapshot()
+        .expect("snapshot");
+    }
+    assert_eq!(
+      store
+        .undo_redo_depths()
+        .expect("depths"),
+      (2, 0)
+    );
+
+    // A second handle stands in for
+    // another process.
+    let other = DataStore::open_with(
+      temp.path(),
+      kind
+    )
+    .expect("reopen");
+    other
+      .save_pending(&[])
+      .expect("save");
+    assert!(
+      store
+        .load_pending()
+        .expect("load")
+        .is_empty()
+    );
+
+    store.set_dry_run(true);
+    store
+      .save_pending(
+        std::slice::from_ref(&first)
+      )
+      .expect("stage");
+    store
+      .set_active_context(Some(
+        "staged"
+      ))
+      .expect("context");
+    assert_eq!(
+      descriptions(
+        &store
+          .load_pending()
+          .expect("load")
+      ),
+      ["first"]
+    );
+    assert!(
+      other
+        .load_pending()
+        .expect("load")
+        .is_empty()
+    );
+    store.set_dry_run(false);
+    store.invalidate_cache();
+    assert!(
+      store
+        .load_pending()
+        .expect("load")
+        .is_empty()
+    );
+    assert_eq!(
+      store
+        .get_active_context()
+        .expect("context"),
+      None
+    );
+
+    store
+      .set_active_context(Some("work"))
+      .expect("context");
+    assert_eq!(
+      other
+        .get_active_context()
+        .expect("context")
+        .as_deref(),
+      Some("work")
+    );
+    store
+      .set_active_context(None)
+      .expect("context");
+    assert_eq!(
+      store
+        .get_active_context()
+        .expect("context"),
+      None
+    );
+
+    store
+      .append_history(&[
+        history_entry(
+          &first,
+          HistoryAction::Created
+        ),
+        history_entry(
+          &first,
+          HistoryAction::Completed
+        )
+      ])
+      .expect("history");
+    assert_eq!(
+      other
+        .load_history()
+        .expect("history")
+        .len(),
+      2
+    );
+    assert_eq!(
+      store
+        .prune_history(|entry| {
+          entry.action
+            == HistoryAction::Created
+        })
+        .expect("prune"),
+      1
+    );
+    assert_eq!(
+      store
+        .load_history()
+        .expect("history")
+        .len(),
+      1
+    );
+
+    let line = QuarantinedLine {
+      file:        "pending.data"
+        .to_string(),
+      line:        3,
+      raw:         "{broken"
+        .to_string(),
+      error:       "EOF".to_string(),
+      quarantined: now
+    };
+    store
+      .save_quarantined(
+        std::slice::from_ref(&line)
+      )
+      .expect("quarantine");
+    assert_eq!(
+      store
+        .load_quarantined()
+        .expect("load"),
+      [line]
+    );
+    store
+      .save_quarantined(&[])
+      .expect("clear");
+    assert!(
+      store
+        .load_quarantined()
+        .expect("load")
+        .is_empty()
+    );
+
+    let mut gapped = first.clone();
+    gapped.id = Some(5);
+    let mut later = second.clone();
+    later.id = Some(9);
+    store
+      .save_pending(&[gapped, later])
+      .expect("save");
+    assert!(
+      store
+        .compact_ids()
+        .expect("compact")
+    );
+    assert_eq!(
+      store
+        .load_pending()
+        .expect("load")
+        .iter()
+        .map(|task| task.id)
+        .collect::<Vec<_>>(),
+      [Some(1), Some(2)]
+    );
+    assert!(
+      store
+        .load_completed()
+        .expect("load")[0]
+        .id
+        .is_none()
+    );
+  }
+
+  #[test]
+  fn undo_depth_rejects_malformed_values()
+   {
+    assert_eq!(
+      parse_undo_depth(" 3 ")
+        .expect("depth"),
+      Some(3)
+    );
+    for unlimited in ["", "0"] {
+      assert_eq!(
+        parse_undo_depth(unlimited)
+          .expect("depth"),
+        None
+      );
+    }
+    for bad in ["abc", "-1", "2.5"] {
+      assert!(
+        parse_undo_depth(bad).is_err()
+      );
+    }
+  }
+
+  #[test]
+  fn file_backend_passes_the_datastore_suite()
+   {
+    run_suite(BackendKind::File);
+  }
+
+  #[cfg(feature = "sqlite")]
+  #[test]
+  fn sqlite_backend_passes_the_datastore_suite()
+   {
+    run_suite(BackendKind::Sqlite);
+  }
+
+  #[cfg(feature = "sqlite")]
+  #[test]
+  fn sqlite_backend_imports_the_file_store_once()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let now = Utc::now();
+    let task = Task::new_pending(
+      "carried over".to_string(),
+      now,
+      1
+    );
+    {
+      let store =
+        DataStore::open(temp.path())
+          .expect("open file store");
+      store
+        .push_current_undo_snapshot()
+        .expect("snapshot");
+      store
+        .save_pending(
+          std::slice::from_ref(&task)
+        )
+        .expect("save");
+      store
+        .set_active_context(Some(
+          "work"
+        ))
+        .expect("context");
+      store
+        .append_history(&[
+          history_entry(
+            &task,
+            HistoryAction::Created
+          )
+        ])
+        .expect("history");
+      let mut file =
+        fs::OpenOptions::new()
+          .append(true)
+          .open(&store.pending_path)
+          .expect("open pending.data");
+      writeln!(file, "not json")
+        .expect("corrupt line");
+    }
+
+    let store = DataStore::open_with(
+      temp.path(),
+      BackendKind::Sqlite
+    )
+    .expect("open sqlite store");
+    assert!(
+      temp
+        .path()
+        .join(SQLITE_FILE)
+        .exists()
+    );
+    assert_eq!(
+      descriptions(
+        &store
+          .load_pending()
+          .expect("load")
+      ),
+      ["carried over"]
+    );
+    assert_eq!(
+      store
+        .get_active_context()
+        .expect("context")
+        .as_deref(),
+      Some("work")
+    );
+    assert_eq!(
+      store
+        .load_history()
+        .expect("history")
+        .len(),
+      1
+    );
+    assert_eq!(
+      store
+        .undo_redo_depths()
+        .expect("depths"),
+      (1, 0)
+    );
+    let quarantined = store
+      .load_quarantined()
+      .expect("load");
+    assert_eq!(quarantined.len(), 1);
+    assert_eq!(
+      quarantined[0].file,
+      "pending.data"
+    );
+
+    store
+      .save_pending(&[])
+      .expect("save");
+    drop(store);
+    let reopened =
+      DataStore::open_with(
+        temp.path(),
+        BackendKind::Sqlite
+      )
+      .expect("reopen");
+    assert!(
+      reopened
+        .load_pending()
+        .expect("load")
+        .is_empty()
+    );
+    assert_eq!(
+      DataStore::open(temp.path())
+        .expect("file store")
+        .load_pending()
+        .expect("load")
+        .len(),
+      1
+    );
+  }
+
+  #[cfg(not(feature = "sqlite"))]
+  #[test]
+  fn sqlite_backend_needs_the_feature()
+  {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let err = DataStore::open_with(
+      temp.path(),
+      BackendKind::Sqlite
+    )
+    .expect_err("feature is off");
+    assert!(
+      err
+        .to_string()
+        .contains("`sqlite` feature")
+    );
+    assert!(
+      BackendKind::parse("mysql")
+        .is_err()
+    );
+  }
+}
@@ -0,0 +1,763 @@
+//! SQLite storage backend
+//! (`data.backend=sqlite`, `sqlite`
+//! feature). Everything lives in one
+//! `rivet.sqlite` in the data
+//! directory, in WAL mode so readers in
+//! other processes do not block a
+//! writer. Rows keep the same JSON as
+//! the `*.data` lines, with uuid,
+//! status and project split out into
+//! indexed columns.
+
+use std::fs;
+use std::path::Path;
+use std::sync::{
+  Arc,
+  Mutex,
+  MutexGuard,
+  PoisonError,
+  RwLock
+};
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use rusqlite::{
+  Connection,
+  OptionalExtension,
+  Params,
+  params
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::{
+  debug,
+  info,
+  warn
+};
+
+use super::{
+  BackendKind,
+  CORRUPT_FILE,
+  HistoryEntry,
+  IndexMode,
+  QuarantinedLine,
+  StorageBackend,
+  Task,
+  TaskIndex,
+  TaskSet,
+  UndoEntry,
+  UndoStack,
+  load_quarantine,
+  merge_quarantined,
+  parse_jsonl_lines
+};
+
+/// Database file name in the data
+/// directory.
+pub const SQLITE_FILE: &str =
+  "rivet.sqlite";
+
+const SCHEMA: &str =
+  "
+CREATE TABLE IF NOT EXISTS tasks (
+  task_set TEXT NOT NULL,
+  pos INTEGER NOT NULL,
+  uuid TEXT NOT NULL,
+  status TEXT NOT NULL,
+  project TEXT,
+  body TEXT NOT NULL,
+  PRIMARY KEY (task_set, pos)
+);
+CREATE INDEX IF NOT EXISTS tasks_uuid \
+   ON tasks (uuid);
+CREATE INDEX IF NOT EXISTS \
+   tasks_status ON tasks (task_set, \
+   status);
+CREATE INDEX IF NOT EXISTS \
+   tasks_project ON tasks (project);
+CREATE TABLE IF NOT EXISTS undo (
+  stack TEXT NOT NULL,
+  pos INTEGER NOT NULL,
+  body TEXT NOT NULL,
+  PRIMARY KEY (stack, pos)
+);
+CREATE TABLE IF NOT EXISTS history (
+  seq INTEGER PRIMARY KEY \
+   AUTOINCREMENT,
+  body TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS quarantine (
+  seq INTEGER PRIMARY KEY \
+   AUTOINCREMENT,
+  body TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS meta (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL
+);
+";
+
+/// `meta` key set once the file store
+/// has been copied in.
+const IMPORTED_KEY: &str =
+  "imported_file_store";
+const CONTEXT_KEY: &str = "context";
+
+/// Parsed task list and the
+/// `PRAGMA data_version` it was read
+/// at; the version moves when another
+/// connection commits.
+type SqliteCache =
+  RwLock<Option<(i64, Arc<TaskIndex>)>>;
+
+#[derive(Debug)]
+pub struct SqliteBackend {
+  conn:            Mutex<Connection>,
+  pending_cache:   SqliteCache,
+  completed_cache: SqliteCache
+}
+
+impl SqliteBackend {
+  /// Opens or creates `rivet.sqlite`.
+  /// The first open copies the file
+  /// store's data files into it; they
+  /// are left in place but no longer
+  /// read or written.
+  pub fn open(
+    data_dir: &Path
+  ) -> anyhow::Result<Self> {
+    let path =
+      data_dir.join(SQLITE_FILE);
+    let conn = Connection::open(&path)
+      .with_context(|| {
+        format!(
+          "failed to open {}",
+          path.display()
+        )
+      })?;
+    conn.busy_timeout(
+      Duration::from_secs(5)
+    )?;
+    let _mode: String = conn
+      .pragma_update_and_check(
+        None,
+        "journal_mode",
+        "WAL",
+        |row| row.get(0)
+      )?;
+    conn.execute_batch(SCHEMA)?;
+    let backend = Self {
+      conn:            Mutex::new(conn),
+      pending_cache:   RwLock::new(
+        None
+      ),
+      completed_cache: RwLock::new(
+        None
+      )
+    };
+    backend
+      .import_file_store(data_dir)?;
+    Ok(backend)
+  }
+
+  fn lock(
+    &self
+  ) -> MutexGuard<'_, Connection> {
+    self.conn.lock().unwrap_or_else(
+      PoisonError::into_inner
+    )
+  }
+
+  fn cache(
+    &self,
+    set: TaskSet
+  ) -> &SqliteCache {
+    match set {
+      | TaskSet::Pending => {
+        &self.pending_cache
+      }
+      | TaskSet::Completed => {
+        &self.completed_cache
+      }
+    }
+  }
+
+  /// Copies every data file of the file
+  /// backend in one transaction, unless
+  /// an earlier open already did.
+  fn import_file_store(
+    &self,
+    data_dir: &Path
+  ) -> anyhow::Result<()> {
+    let mut conn = self.lock();
+    let imported: Option<String> = conn
+      .query_row(
+        "SELECT value FROM meta WHERE \
+         key = ?1",
+        [IMPORTED_KEY],
+        |row| row.get(0)
+      )
+      .optional()?;
+    if imported.is_some() {
+      return Ok(());
+    }
+
+    let pending: Vec<Task> = read_file(
+      data_dir,
+      TaskSet::Pending.file_name()
+    )?;
+    let completed: Vec<Task> =
+      read_file(
+        data_dir,
+        TaskSet::Completed.file_name()
+      )?;
+    let undo: Vec<UndoEntry> =
+      read_file(
+        data_dir,
+        UndoStack::Undo.file_name()
+      )?;
+    let redo: Vec<UndoEntry> =
+      read_file(
+        data_dir,
+        UndoStack::Redo.file_name()
+      )?;
+    let history: Vec<HistoryEntry> =
+      read_file(
+        data_dir,
+        "history.data"
+      )?;
+    let context_path =
+      data_dir.join("context.data");
+    let context = if context_path
+      .exists()
+    {
+      fs::read_to_string(&context_path)?
+    } else {
+      String::new()
+    };
+    // Read last: parsing the files
+    // above may have quarantined lines.
+    let quarantined = load_quarantine(
+      &data_dir.join(CORRUPT_FILE)
+    )?;
+
+    let tx = conn.transaction()?;
+    write_tasks(
+      &tx,
+      TaskSet::Pending,
+      &pending
+    )?;
+    write_tasks(
+      &tx,
+      TaskSet::Completed,
+      &completed
+    )?;
+    write_undo(
+      &tx,
+      UndoStack::Undo,
+      &undo
+    )?;
+    write_undo(
+      &tx,
+      UndoStack::Redo,
+      &redo
+    )?;
+    write_bodies(
+      &tx, "history", &history
+    )?;
+    write_bodies(
+      &tx,
+      "quarantine",
+      &quarantined
+    )?;
+    write_meta(
+      &tx,
+      CONTEXT_KEY,
+      &context
+    )?;
+    write_meta(
+      &tx,
+      IMPORTED_KEY,
+      &Utc::now().to_rfc3339()
+    )?;
+    tx.commit()?;
+    info!(
+      pending = pending.len(),
+      completed = completed.len(),
+      history = history.len(),
+      "imported file store into sqlite"
+    );
+    Ok(())
+  }
+
+  /// Parses `body` rows in order.
+  /// Unparseable rows are skipped with
+  /// a warning and, when `record` is
+  /// set, added to the quarantine under
+  /// `file`.
+  fn read_rows<T, P>(
+    &self,
+    conn: &Connection,
+    sql: &str,
+    params: P,
+    file: &str,
+    record: bool
+  ) -> anyhow::Result<Vec<T>>
+  where
+    T: DeserializeOwned,
+    P: Params
+  {
+    let mut stmt = conn.prepare(sql)?;
+    let bodies = stmt
+      .query_map(params, |row| {
+        row.get::<_, String>(0)
+      })?
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let mut out = Vec::new();
+    let mut corrupt = Vec::new();
+    for (idx, body) in
+      bodies.into_iter().enumerate()
+    {
+      match serde_json::from_str(&body)
+      {
+        | Ok(entry) => out.push(entry),
+        | Err(err) => {
+          corrupt.push((
+            idx + 1,
+            body,
+            err.to_string()
+          ))
+        }
+      }
+    }
+    if corrupt.is_empty() {
+      return Ok(out);
+    }
+    warn!(
+      file,
+      skipped = corrupt.len(),
+      "skipped unparseable row(s); \
+       run `task doctor` to review \
+       them"
+    );
+    if record {
+      let mut entries =
+        load_quarantined(conn)?;
+      if merge_quarantined(
+        &mut entries,
+        file,
+        corrupt,
+        Utc::now()
+      ) {
+        replace_bodies(
+          conn,
+          "quarantine",
+          &entries
+        )?;
+      }
+    }
+    Ok(out)
+  }
+}
+
+impl StorageBackend for SqliteBackend {
+  fn kind(&self) -> BackendKind {
+    BackendKind::Sqlite
+  }
+
+  fn task_index(
+    &self,
+    set: TaskSet,
+    _mode: IndexMode,
+    record_corrupt: bool
+  ) -> anyhow::Result<Arc<TaskIndex>>
+  {
+    let conn = self.lock();
+    let version = data_version(&conn)?;
+    let cache = self.cache(set);
+    if let Some((cached, index)) = cache
+      .read()
+      .unwrap_or_else(
+        PoisonError::into_inner
+      )
+      .as_ref()
+      && *cached == version
+    {
+      return Ok(Arc::clone(index));
+    }
+
+    let tasks = self.read_rows(
+      &conn,
+      "SELECT body FROM tasks WHERE \
+       task_set = ?1 ORDER BY pos",
+      [set_key(set)],
+      set.file_name(),
+      record_corrupt
+    )?;
+    let index =
+      Arc::new(TaskIndex::build(tasks));
+    *cache.write().unwrap_or_else(
+      PoisonError::into_inner
+    ) = Some((
+      version,
+      Arc::clone(&index)
+    ));
+    Ok(index)
+  }
+
+  fn save_tasks(
+    &self,
+    set: TaskSet,
+    tasks: &[Task],
+    _mode: IndexMode,
+    dry_run: bool
+  ) -> anyhow::Result<()> {
+    let mut conn = self.lock();
+    let cache = self.cache(set);
+    let mut guard =
+      cache.write().unwrap_or_else(
+        PoisonError::into_inner
+      );
+    *guard = None;
+    if dry_run {
+      debug!(
+        set = set.file_name(),
+        count = tasks.len(),
+        "dry run: staging instead of \
+         writing"
+      );
+    } else {
+      let tx = conn.transaction()?;
+      write_tasks(&tx, set, tasks)?;
+      tx.commit()?;
+    }
+    *guard = Some((
+      data_version(&conn)?,
+      Arc::new(TaskIndex::build(
+        tasks.to_vec()
+      ))
+    ));
+    Ok(())
+  }
+
+  fn invalidate_cache(&self) {
+    for cache in [
+      &self.pending_cache,
+      &self.completed_cache
+    ] {
+      *cache.write().unwrap_or_else(
+        PoisonError::into_inner
+      ) = None;
+    }
+  }
+
+  fn load_undo(
+    &self,
+    stack: UndoStack,
+    record_corrupt: bool
+  ) -> anyhow::Result<Vec<UndoEntry>>
+  {
+    let conn = self.lock();
+    self.read_rows(
+      &conn,
+      "SELECT body FROM undo WHERE \
+       stack = ?1 ORDER BY pos",
+      [stack_key(stack)],
+      stack.file_name(),
+      record_corrupt
+    )
+  }
+
+  fn save_undo(
+    &self,
+    stack: UndoStack,
+    entries: &[UndoEntry]
+  ) -> anyhow::Result<()> {
+    let mut conn = self.lock();
+    let tx = conn.transaction()?;
+    write_undo(&tx, stack, entries)?;
+    tx.commit()?;
+    Ok(())
+  }
+
+  fn load_context(
+    &self
+  ) -> anyhow::Result<String> {
+    let conn = self.lock();
+    Ok(
+      conn
+        .query_row(
+          "SELECT value FROM meta \
+           WHERE key = ?1",
+          [CONTEXT_KEY],
+          |row| row.get(0)
+        )
+        .optional()?
+        .unwrap_or_default()
+    )
+  }
+
+  fn save_context(
+    &self,
+    name: &str
+  ) -> anyhow::Result<()> {
+    let conn = self.lock();
+    write_meta(&conn, CONTEXT_KEY, name)
+  }
+
+  fn append_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()> {
+    let mut conn = self.lock();
+    let tx = conn.transaction()?;
+    write_bodies(
+      &tx, "history", entries
+    )?;
+    tx.commit()?;
+    Ok(())
+  }
+
+  fn load_history(
+    &self,
+    record_corrupt: bool
+  ) -> anyhow::Result<Vec<HistoryEntry>>
+  {
+    let conn = self.lock();
+    self.read_rows(
+      &conn,
+      "SELECT body FROM history ORDER \
+       BY seq",
+      [],
+      "history.data",
+      record_corrupt
+    )
+  }
+
+  fn save_history(
+    &self,
+    entries: &[HistoryEntry]
+  ) -> anyhow::Result<()> {
+    let conn = self.lock();
+    replace_bodies(
+      &conn, "history", entries
+    )
+  }
+
+  fn load_quarantined(
+    &self
+  ) -> anyhow::Result<
+    Vec<QuarantinedLine>
+  > {
+    load_quarantined(&self.lock())
+  }
+
+  fn save_quarantined(
+    &self,
+    entries: &[QuarantinedLine]
+  ) -> anyhow::Result<()> {
+    let conn = self.lock();
+    replace_bodies(
+      &conn,
+      "quarantine",
+      entries
+    )
+  }
+}
+
+fn set_key(
+  set: TaskSet
+) -> &'static str {
+  match set {
+    | TaskSet::Pending => "pending",
+    | TaskSet::Completed => "completed"
+  }
+}
+
+fn stack_key(
+  stack: UndoStack
+) -> &'static str {
+  match stack {
+    | UndoStack::Undo => "undo",
+    | UndoStack::Redo => "redo"
+  }
+}
+
+fn data_version(
+  conn: &Connection
+) -> anyhow::Result<i64> {
+  Ok(conn.query_row(
+    "PRAGMA data_version",
+    [],
+    |row| row.get(0)
+  )?)
+}
+
+/// A data file of the file backend, or
+/// nothing when it was never created.
+fn read_file<T>(
+  data_dir: &Path,
+  name: &str
+) -> anyhow::Result<Vec<T>>
+where
+  T: DeserializeOwned
+{
+  let path = data_dir.join(name);
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+  parse_jsonl_lines(&path, true)
+    .with_context(|| {
+      format!(
+        "failed to import {}",
+        path.display()
+      )
+    })
+}
+
+fn write_tasks(
+  conn: &Connection,
+  set: TaskSet,
+  tasks: &[Task]
+) -> anyhow::Result<()> {
+  let key = set_key(set);
+  conn.execute(
+    "DELETE FROM tasks WHERE task_set \
+     = ?1",
+    [key]
+  )?;
+  let mut stmt = conn.prepare(
+    "INSERT INTO tasks (task_set, \
+     pos, uuid, status, project, \
+     body) VALUES (?1, ?2, ?3, ?4, \
+     ?5, ?6)"
+  )?;
+  for (pos, task) in
+    tasks.iter().enumerate()
+  {
+    let status = serde_json::to_value(
+      &task.status
+    )?;
+    stmt.execute(params![
+      key,
+      pos as i64,
+      task.uuid.to_string(),
+      status
+        .as_str()
+        .unwrap_or_default(),
+      task.project,
+      serde_json::to_string(task)?
+    ])?;
+  }
+  Ok(())
+}
+
+fn write_undo(
+  conn: &Connection,
+  stack: UndoStack,
+  entries: &[UndoEntry]
+) -> anyhow::Result<()> {
+  let key = stack_key(stack);
+  conn.execute(
+    "DELETE FROM undo WHERE stack = ?1",
+    [key]
+  )?;
+  let mut stmt = conn.prepare(
+    "INSERT INTO undo (stack, pos, \
+     body) VALUES (?1, ?2, ?3)"
+  )?;
+  for (pos, entry) in
+    entries.iter().enumerate()
+  {
+    stmt.execute(params![
+      key,
+      pos as i64,
+      serde_json::to_string(entry)?
+    ])?;
+  }
+  Ok(())
+}
+
+/// Appends to `history` or
+/// `quarantine`.
+fn write_bodies<T>(
+  conn: &Connection,
+  table: &str,
+  entries: &[T]
+) -> anyhow::Result<()>
+where
+  T: Serialize
+{
+  let mut stmt =
+    conn.prepare(&format!(
+      "INSERT INTO {table} (body) \
+       VALUES (?1)"
+    ))?;
+  for entry in entries {
+    stmt.execute([
+      serde_json::to_string(entry)?
+    ])?;
+  }
+  Ok(())
+}
+
+fn replace_bodies<T>(
+  conn: &Connection,
+  table: &str,
+  entries: &[T]
+) -> anyhow::Result<()>
+where
+  T: Serialize
+{
+  let tx =
+    conn.unchecked_transaction()?;
+  tx.execute(
+    &format!("DELETE FROM {table}"),
+    []
+  )?;
+  write_bodies(&tx, table, entries)?;
+  tx.commit()?;
+  Ok(())
+}
+
+fn write_meta(
+  conn: &Connection,
+  key: &str,
+  value: &str
+) -> anyhow::Result<()> {
+  conn.execute(
+    "INSERT INTO meta (key, value) \
+     VALUES (?1, ?2) ON CONFLICT \
+     (key) DO UPDATE SET value = \
+     excluded.value",
+    [key, value]
+  )?;
+  Ok(())
+}
+
+/// Read leniently, like
+/// `corrupt.data`.
+fn load_quarantined(
+  conn: &Connection
+) -> anyhow::Result<Vec<QuarantinedLine>>
+{
+  let mut stmt = conn.prepare(
+    "SELECT body FROM quarantine \
+     ORDER BY seq"
+  )?;
+  let bodies = stmt
+    .query_map([], |row| {
+      row.get::<_, String>(0)
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(
+    bodies
+      .iter()
+      .filter_map(|body| {
+        serde_json::from_str(body).ok()
+      })
+      .collect()
+  )
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::OnceLock;
@@ -13,6 +14,7 @@ use chrono::{
   LocalResult,
   NaiveDate,
   NaiveDateTime,
+  NaiveTime,
   TimeZone,
   Utc,
   Weekday
@@ -21,6 +23,8 @@ use chrono_tz::Tz;
 use regex::Regex;
 use serde::Deserialize;
 
+use crate::config::Config;
+
 const TIMEZONE_CONFIG_FILE: &str =
   "rivet.toml";
 const TIMEZONE_ENV_VAR: &str =
@@ -69,16 +73,364 @@ pub fn format_project_date(
     .to_string()
 }
 
+/// Display format for dates, configured
+/// with Taskwarrior-style codes
+/// (`dateformat=Y-M-D H:N`) or the
+/// keyword `relative`, which renders
+/// through `humanize_with`. Storage is
+/// unaffected.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub enum DateFormat {
+  /// Pre-translated chrono pattern,
+  /// rendered in the project timezone.
+  Codes(String),
+  Relative(HumanizeStyle)
+}
+
+impl Default for DateFormat {
+  fn default() -> Self {
+    Self::Codes("%Y-%m-%d".to_string())
+  }
+}
+
+const DATE_FORMAT_CODES: &[(
+  char,
+  &str
+)] = &[
+  ('Y', "%Y"),
+  ('y', "%y"),
+  ('M', "%m"),
+  ('m', "%-m"),
+  ('D', "%d"),
+  ('d', "%-d"),
+  ('H', "%H"),
+  ('h', "%-H"),
+  ('N', "%M"),
+  ('n', "%-M"),
+  ('S', "%S"),
+  ('s', "%-S"),
+  ('A', "%A"),
+  ('a', "%a"),
+  ('B', "%B"),
+  ('b', "%b"),
+  ('V', "%V"),
+  ('J', "%j"),
+  ('j', "%-j")
+];
+
+impl DateFormat {
+  /// Parses a format spec. Letters must
+  /// be known codes; other characters
+  /// are literal and `\` escapes a
+  /// literal letter.
+  pub fn parse(
+    spec: &str
+  ) -> anyhow::Result<Self> {
+    if spec
+      .trim()
+      .eq_ignore_ascii_case("relative")
+    {
+      return Ok(Self::Relative(
+        HumanizeStyle::default()
+      ));
+    }
+
+    let mut pattern = String::new();
+    let mut chars = spec.chars();
+    while let Some(ch) = chars.next() {
+      if ch == '\\' {
+        let literal = chars
+          .next()
+          .ok_or_else(|| {
+            anyhow!(
+              "dangling '\\' at end \
+               of date format '{spec}'"
+            )
+          })?;
+        push_literal(
+          &mut pattern,
+          literal
+        );
+      } else if let Some((_, chrono)) =
+        DATE_FORMAT_CODES
+          .iter()
+          .find(|(code, _)| *code == ch)
+      {
+        pattern.push_str(chrono);
+      } else if ch.is_ascii_alphabetic()
+      {
+        let valid: String =
+          DATE_FORMAT_CODES
+            .iter()
+            .map(|(code, _)| *code)
+            .collect();
+        return Err(anyhow!(
+          "unknown date format code \
+           '{ch}' in '{spec}' (valid \
+           codes: {valid}; use \
+           'relative' for relative \
+           dates or '\\{ch}' for a \
+           literal)"
+        ));
+      } else {
+        push_literal(&mut pattern, ch);
+      }
+    }
+    Ok(Self::Codes(pattern))
+  }
+
+  pub fn format(
+    &self,
+    dt: DateTime<Utc>,
+    now: DateTime<Utc>
+  ) -> String {
+    match self {
+      | Self::Codes(pattern) => {
+        dt.with_timezone(
+          project_timezone()
+        )
+        .format(pattern)
+        .to_string()
+      }
+      | Self::Relative(style) => {
+        humanize_with(dt, now, *style)
+      }
+    }
+  }
+}
+
+fn push_literal(
+  pattern: &mut String,
+  ch: char
+) {
+  if ch == '%' {
+    pattern.push_str("%%");
+  } else {
+    pattern.push(ch);
+  }
+}
+
+/// Long ("in 3 days", "2 hours ago")
+/// or short ("in 3d", "2h ago") forms
+/// for `humanize_with`, set with
+/// `humanize=long|short`.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub enum HumanizeStyle {
+  #[default]
+  Long,
+  Short
+}
+
+impl HumanizeStyle {
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw.trim() {
+      | "long" => Ok(Self::Long),
+      | "short" => Ok(Self::Short),
+      | other => {
+        Err(anyhow!(
+          "unknown style '{other}' \
+           (expected long or short)"
+        ))
+      }
+    }
+  }
+}
+
+/// Renders `dt` relative to `now` in
+/// the long form, e.g. "in 3 days",
+/// "2 hours ago" or "yesterday".
+pub fn humanize(
+  dt: DateTime<Utc>,
+  now: DateTime<Utc>
+) -> String {
+  humanize_with(
+    dt,
+    now,
+    HumanizeStyle::Long
+  )
+}
+
+/// `humanize` in either style. Under a
+/// minute either way is "just now"
+/// ("now" when short); a single whole
+/// day is "yesterday"/"tomorrow" in the
+/// long form. Units step up from
+/// minutes to hours, days (under two
+/// weeks), weeks (under 60 days),
+/// 30-day months and 365-day years,
+/// always rounding down.
+pub fn humanize_with(
+  dt: DateTime<Utc>,
+  now: DateTime<Utc>,
+  style: HumanizeStyle
+) -> String {
+  let delta = dt - now;
+  let future = delta.num_seconds() > 0;
+  let seconds =
+    delta.num_seconds().abs();
+  if seconds < 60 {
+    return match style {
+      | HumanizeStyle::Long => {
+        "just now"
+      }
+      | HumanizeStyle::Short => "now"
+    }
+    .to_string();
+  }
+
+  let (amount, unit, short) =
+    if seconds < 3_600 {
+      (seconds / 60, "minute", "m")
+    } else if seconds < 86_400 {
+      (seconds / 3_600, "hour", "h")
+    } else if seconds < 14 * 86_400 {
+      (seconds / 86_400, "day", "d")
+    } else if seconds < 60 * 86_400 {
+      (
+        seconds / (7 * 86_400),
+        "week",
+        "w"
+      )
+    } else if seconds < 365 * 86_400 {
+      (
+        seconds / (30 * 86_400),
+        "month",
+        "mo"
+      )
+    } else {
+      (
+        seconds / (365 * 86_400),
+        "year",
+        "y"
+      )
+    };
+  let label = match style {
+    | HumanizeStyle::Long => {
+      if unit == "day" && amount == 1 {
+        return if future {
+          "tomorrow"
+        } else {
+          "yesterday"
+        }
+        .to_string();
+      }
+      let plural = if amount == 1 {
+        ""
+      } else {
+        "s"
+      };
+      format!("{amount} {unit}{plural}")
+    }
+    | HumanizeStyle::Short => {
+      format!("{amount}{short}")
+    }
+  };
+
+  if future {
+    format!("in {label}")
+  } else {
+    format!("{label} ago")
+  }
+}
+
+/// Relative due groups used by planning
+/// views, ordered from most to least
+/// urgent.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+)]
+pub enum DueBucket {
+  Overdue,
+  Today,
+  Tomorrow,
+  ThisWeek,
+  Later,
+  NoDue
+}
+
+impl DueBucket {
+  pub fn label(self) -> &'static str {
+    match self {
+      | Self::Overdue => "Overdue",
+      | Self::Today => "Today",
+      | Self::Tomorrow => "Tomorrow",
+      | Self::ThisWeek => "This Week",
+      | Self::Later => "Later",
+      | Self::NoDue => "No Due"
+    }
+  }
+}
+
+/// Buckets a due timestamp relative to
+/// `now` using project-local calendar
+/// days. "This Week" ends on the last
+/// day of the week that begins on
+/// `week_start`.
+pub fn due_bucket(
+  due: Option<DateTime<Utc>>,
+  now: DateTime<Utc>,
+  week_start: Weekday
+) -> DueBucket {
+  let Some(due) = due else {
+    return DueBucket::NoDue;
+  };
+  if due < now {
+    return DueBucket::Overdue;
+  }
+
+  let today = to_project_date(now);
+  let due_date = to_project_date(due);
+  let days_into_week = (7
+    + today
+      .weekday()
+      .num_days_from_monday()
+    - week_start
+      .num_days_from_monday())
+    % 7;
+  let week_end = today
+    + Duration::days(
+      6 - i64::from(days_into_week)
+    );
+
+  if due_date <= today {
+    DueBucket::Today
+  } else if due_date
+    == today + Duration::days(1)
+  {
+    DueBucket::Tomorrow
+  } else if due_date <= week_end {
+    DueBucket::ThisWeek
+  } else {
+    DueBucket::Later
+  }
+}
+
 fn resolve_project_timezone() -> Tz {
   if let Ok(raw) =
     std::env::var(TIMEZONE_ENV_VAR)
-  {
-    if let Some(tz) = parse_timezone(
+    && let Some(tz) = parse_timezone(
       &raw,
       TIMEZONE_ENV_VAR
-    ) {
-      return tz;
-    }
+    )
+  {
+    return tz;
   }
 
   if let Some(path) =
@@ -102,7 +454,93 @@ fn resolve_project_timezone() -> Tz {
   })
 }
 
-fn timezone_config_path()
+/// Problems with the timezone settings
+/// `project_timezone` reads, for
+/// `task doctor`: values that do not
+/// name an IANA zone and files that do
+/// not parse are skipped silently at
+/// runtime, which is easy to miss.
+pub fn timezone_config_issues()
+-> Vec<String> {
+  let mut issues = Vec::new();
+  let mut configured = false;
+  if let Ok(raw) =
+    std::env::var(TIMEZONE_ENV_VAR)
+  {
+    if raw.trim().parse::<Tz>().is_ok()
+    {
+      configured = true;
+    } else {
+      issues.push(format!(
+        "{TIMEZONE_ENV_VAR}={raw:?} \
+         is not an IANA timezone and \
+         is ignored"
+      ));
+    }
+  }
+
+  if let Some(path) =
+    timezone_config_path()
+    && path.exists()
+  {
+    match fs::read_to_string(&path)
+      .map_err(|err| err.to_string())
+      .and_then(|raw| {
+        toml::from_str::<TimezoneConfig>(
+          &raw
+        )
+        .map_err(|err| err.to_string())
+      }) {
+      | Ok(parsed) => {
+        let timezone =
+          parsed.timezone.or_else(|| {
+            parsed.time.and_then(
+              |section| section.timezone
+            )
+          });
+        match timezone {
+          | Some(raw)
+            if raw
+              .trim()
+              .parse::<Tz>()
+              .is_err() =>
+          {
+            issues.push(format!(
+              "{} sets timezone {raw:?}, \
+               which is not an IANA \
+               timezone and is ignored",
+              path.display()
+            ));
+          }
+          | Some(_) => configured = true,
+          | None => {}
+        }
+      }
+      | Err(err) => {
+        issues.push(format!(
+          "{} could not be read for its \
+           timezone: {err}",
+          path.display()
+        ));
+      }
+    }
+  }
+
+  if !configured {
+    issues.push(format!(
+      "no valid timezone is \
+       configured; dates use the \
+       built-in default \
+       {DEFAULT_PROJECT_TIMEZONE}. \
+       Set {TIMEZONE_ENV_VAR} or \
+       [time].timezone in \
+       {TIMEZONE_CONFIG_FILE}"
+    ));
+  }
+  issues
+}
+
+pub(crate) fn timezone_config_path()
 -> Option<PathBuf> {
   if let Ok(raw) = std::env::var(
     TIMEZONE_CONFIG_ENV_VAR
@@ -263,10 +701,486 @@ fn to_utc_from_project_local(
   }
 }
 
-#[tracing::instrument(skip(now), fields(input = input))]
+/// Time of day that date-only inputs
+/// get per date field (`due:2026-02-20`
+/// lands on `due.default_time`), in the
+/// project timezone. Midnight unless
+/// configured.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub struct DefaultTimes {
+  pub due:       NaiveTime,
+  pub wait:      NaiveTime,
+  pub scheduled: NaiveTime
+}
+
+impl DefaultTimes {
+  /// Reads `due.default_time`,
+  /// `wait.default_time` and
+  /// `scheduled.default_time`.
+  pub fn from_config(
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    Self::from_values(
+      cfg.get("due.default_time"),
+      cfg.get("wait.default_time"),
+      cfg.get("scheduled.default_time")
+    )
+  }
+
+  /// Builds the defaults from raw
+  /// `HH:MM` (or `9:00am`) values; a
+  /// missing or empty one stays at
+  /// midnight.
+  pub fn from_values(
+    due: Option<String>,
+    wait: Option<String>,
+    scheduled: Option<String>
+  ) -> anyhow::Result<Self> {
+    let parse = |field: &str,
+                 raw: Option<
+      String
+    >|
+     -> anyhow::Result<
+      NaiveTime
+    > {
+      let Some(raw) =
+        raw.filter(|raw| {
+          !raw.trim().is_empty()
+        })
+      else {
+        return Ok(NaiveTime::MIN);
+      };
+      parse_clock_time(&raw)
+        .and_then(|(hour, minute)| {
+          NaiveTime::from_hms_opt(
+            hour, minute, 0
+          )
+        })
+        .ok_or_else(|| {
+          anyhow!(
+            "invalid {field}.\
+             default_time: {raw} \
+             (expected HH:MM)"
+          )
+        })
+    };
+    Ok(Self {
+      due:       parse("due", due)?,
+      wait:      parse("wait", wait)?,
+      scheduled: parse(
+        "scheduled",
+        scheduled
+      )?
+    })
+  }
+}
+
+/// Working hours and non-working days
+/// from `[time.business]` in
+/// rivet.toml. `sod`/`eod`/`eow` and
+/// `+Nd` offsets use plain calendar
+/// days unless `enabled` is set; `nbd`
+/// and `+Nb` always skip `weekend` and
+/// `holidays`.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct WorkCalendar {
+  pub enabled:   bool,
+  pub day_start: NaiveTime,
+  pub day_end:   NaiveTime,
+  pub weekend:   Vec<Weekday>,
+  pub holidays:  BTreeSet<NaiveDate>
+}
+
+impl Default for WorkCalendar {
+  fn default() -> Self {
+    Self {
+      enabled:   false,
+      day_start:
+        NaiveTime::from_hms_opt(9, 0, 0)
+          .unwrap_or(NaiveTime::MIN),
+      day_end:
+        NaiveTime::from_hms_opt(
+          17, 0, 0
+        )
+        .unwrap_or(NaiveTime::MIN),
+      weekend:   vec![
+        Weekday::Sat,
+        Weekday::Sun,
+      ],
+      holidays:  BTreeSet::new()
+    }
+  }
+}
+
+#[derive(
+  Debug, Default, Deserialize,
+)]
+struct BusinessConfig {
+  time: Option<BusinessTimeSection>
+}
+
+#[derive(
+  Debug, Default, Deserialize,
+)]
+struct BusinessTimeSection {
+  business: Option<BusinessSection>
+}
+
+#[derive(
+  Debug, Default, Deserialize,
+)]
+struct BusinessSection {
+  enabled:   Option<bool>,
+  day_start: Option<String>,
+  day_end:   Option<String>,
+  weekend:   Option<Vec<String>>,
+  holidays:  Option<Vec<String>>
+}
+
+impl WorkCalendar {
+  /// Builds a calendar from a
+  /// `[time.business]` table; unset
+  /// fields keep their defaults.
+  fn from_section(
+    section: BusinessSection
+  ) -> anyhow::Result<Self> {
+    let defaults = Self::default();
+    let clock = |field: &str,
+                 raw: Option<
+      String
+    >,
+                 default: NaiveTime|
+     -> anyhow::Result<
+      NaiveTime
+    > {
+      let Some(raw) = raw else {
+        return Ok(default);
+      };
+      parse_clock_time(&raw)
+        .and_then(|(hour, minute)| {
+          NaiveTime::from_hms_opt(
+            hour, minute, 0
+          )
+        })
+        .ok_or_else(|| {
+          anyhow!(
+            "invalid {field}: {raw} \
+             (expected HH:MM)"
+          )
+        })
+    };
+    let day_start = clock(
+      "day_start",
+      section.day_start,
+      defaults.day_start
+    )?;
+    let day_end = clock(
+      "day_end",
+      section.day_end,
+      defaults.day_end
+    )?;
+    if day_end <= day_start {
+      return Err(anyhow!(
+        "day_end must be after \
+         day_start"
+      ));
+    }
+    let weekend =
+      match section.weekend {
+        | Some(names) => names
+          .iter()
+          .map(|name| {
+            parse_weekday_name(
+              &name
+                .to_ascii_lowercase()
+            )
+            .ok_or_else(
+              || {
+                anyhow!(
+                  "invalid weekend \
+                   day: {name}"
+                )
+              }
+            )
+          })
+          .collect::<anyhow::Result<_>>(
+          )?,
+        | None => defaults.weekend
+      };
+    if weekend.len() >= 7 {
+      return Err(anyhow!(
+        "weekend leaves no working \
+         days"
+      ));
+    }
+    let holidays = section
+      .holidays
+      .unwrap_or_default()
+      .iter()
+      .map(|raw| {
+        NaiveDate::parse_from_str(
+          raw.trim(),
+          "%Y-%m-%d"
+        )
+        .with_context(|| {
+          format!(
+            "invalid holiday: {raw} \
+             (expected YYYY-MM-DD)"
+          )
+        })
+      })
+      .collect::<anyhow::Result<_>>()?;
+    Ok(Self {
+      enabled: section
+        .enabled
+        .unwrap_or(false),
+      day_start,
+      day_end,
+      weekend,
+      holidays
+    })
+  }
+
+  pub fn is_workday(
+    &self,
+    date: NaiveDate
+  ) -> bool {
+    !self
+      .weekend
+      .contains(&date.weekday())
+      && !self.holidays.contains(&date)
+  }
+
+  /// Moves `days` working days from
+  /// `date`, forward or backward; zero
+  /// returns `date` even when it is not
+  /// a working day. Whole weeks are
+  /// skipped in one step, so a huge
+  /// `days` fails instead of walking
+  /// every date.
+  pub fn add_workdays(
+    &self,
+    date: NaiveDate,
+    days: i64
+  ) -> anyhow::Result<NaiveDate> {
+    let out_of_range = || {
+      anyhow!(
+        "{days} working days from \
+         {date} is out of range"
+      )
+    };
+    let sign = days.signum();
+    let per_week = [
+      Weekday::Mon,
+      Weekday::Tue,
+      Weekday::Wed,
+      Weekday::Thu,
+      Weekday::Fri,
+      Weekday::Sat,
+      Weekday::Sun
+    ]
+    .iter()
+    .filter(|day| {
+      !self.weekend.contains(day)
+    })
+    .count() as u64;
+    let mut left = days.unsigned_abs();
+    let mut current = date;
+    if per_week > 0 && left > 1 {
+      // Stop a week short so the walk
+      // below still lands on a working
+      // day.
+      let weeks = (left - 1) / per_week;
+      current = i64::try_from(weeks)
+        .ok()
+        .and_then(|weeks| {
+          weeks.checked_mul(7 * sign)
+        })
+        .and_then(Duration::try_days)
+        .and_then(|span| {
+          date.checked_add_signed(span)
+        })
+        .ok_or_else(out_of_range)?;
+      let (low, high) = if sign < 0 {
+        (current, date)
+      } else {
+        (date, current)
+      };
+      let holidays = self
+        .holidays
+        .range(low..=high)
+        .filter(|day| {
+          **day != date
+            && !self
+              .weekend
+              .contains(&day.weekday())
+        })
+        .count()
+        as u64;
+      left = left - weeks * per_week
+        + holidays;
+    }
+    let step = Duration::days(sign);
+    for _ in 0..left {
+      current = current
+        .checked_add_signed(step)
+        .ok_or_else(out_of_range)?;
+      while !self.is_workday(current) {
+        current = current
+          .checked_add_signed(step)
+          .ok_or_else(out_of_range)?;
+      }
+    }
+    Ok(current)
+  }
+
+  /// `date` itself when it is a working
+  /// day, otherwise the next one.
+  fn workday_on_or_after(
+    &self,
+    date: NaiveDate
+  ) -> anyhow::Result<NaiveDate> {
+    if self.is_workday(date) {
+      Ok(date)
+    } else {
+      self.add_workdays(date, 1)
+    }
+  }
+}
+
+/// The process-wide `[time.business]`
+/// calendar, read once like the project
+/// timezone. A malformed table is
+/// logged and ignored.
+pub fn work_calendar()
+-> &'static WorkCalendar {
+  static CALENDAR: OnceLock<
+    WorkCalendar
+  > = OnceLock::new();
+  CALENDAR.get_or_init(|| {
+    load_work_calendar()
+      .unwrap_or_else(|err| {
+        tracing::warn!(
+          error = %err,
+          "ignoring [time.business]"
+        );
+        None
+      })
+      .unwrap_or_default()
+  })
+}
+
+/// Why `[time.business]` is being
+/// ignored, if it is.
+pub fn work_calendar_issue()
+-> Option<String> {
+  load_work_calendar()
+    .err()
+    .map(|err| format!("{err:#}"))
+}
+
+fn load_work_calendar()
+-> anyhow::Result<Option<WorkCalendar>>
+{
+  let Some(path) =
+    timezone_config_path()
+      .filter(|path| path.exists())
+  else {
+    return Ok(None);
+  };
+  let raw = fs::read_to_string(&path)
+    .with_context(|| {
+    format!(
+      "failed to read {}",
+      path.display()
+    )
+  })?;
+  let section = toml::from_str::<
+    BusinessConfig
+  >(&raw)
+  .with_context(|| {
+    format!(
+      "failed to parse {}",
+      path.display()
+    )
+  })?
+  .time
+  .and_then(|time| time.business);
+  section
+    .map(WorkCalendar::from_section)
+    .transpose()
+    .with_context(|| {
+      format!(
+        "invalid [time.business] in {}",
+        path.display()
+      )
+    })
+}
+
 pub fn parse_date_expr(
   input: &str,
   now: DateTime<Utc>
+) -> anyhow::Result<DateTime<Utc>> {
+  parse_date_expr_at(
+    input,
+    now,
+    NaiveTime::MIN
+  )
+}
+
+/// Like `parse_date_expr`, but
+/// date-only inputs (`2026-02-20`,
+/// `today`, `friday`, `march`,
+/// `march 2027`, `2027`)
+/// land on `time_of_day` in the project
+/// timezone instead of midnight. Inputs
+/// that carry a time keep it.
+#[tracing::instrument(skip(now), fields(input = input))]
+pub fn parse_date_expr_at(
+  input: &str,
+  now: DateTime<Utc>,
+  time_of_day: NaiveTime
+) -> anyhow::Result<DateTime<Utc>> {
+  parse_date_expr_with(
+    input,
+    now,
+    time_of_day,
+    work_calendar()
+  )
+}
+
+/// `parse_date_expr_at` against an
+/// explicit `WorkCalendar`:
+/// - `sod`/`eod`: start and end of
+///   today, or with business hours
+///   enabled the working hours of the
+///   first working day whose end has
+///   not passed.
+/// - `eow`: the end of the ISO week, or
+///   the end of its last working day
+///   (next week's once that has
+///   passed).
+/// - `nbd`: the next working day at
+///   `time_of_day` (`day_start` when
+///   enabled).
+/// - `+Nb`/`-Nb`: N working days away
+///   at the same local time; `+Nd`
+///   counts working days too when
+///   enabled.
+pub fn parse_date_expr_with(
+  input: &str,
+  now: DateTime<Utc>,
+  time_of_day: NaiveTime,
+  calendar: &WorkCalendar
 ) -> anyhow::Result<DateTime<Utc>> {
   let token = input.trim();
   let lower =
@@ -280,32 +1194,39 @@ pub fn parse_date_expr(
           project_timezone()
         );
       let date = local_now.date_naive();
-      let midnight = date
-        .and_hms_opt(0, 0, 0)
-        .ok_or_else(|| {
-          anyhow!(
-            "failed to construct \
-             midnight for today"
-          )
-        })?;
       return to_utc_from_project_local(
-        midnight, "today"
+        date.and_time(time_of_day),
+        "today"
       );
     }
     | "tomorrow" => {
-      let today =
-        parse_date_expr("today", now)?;
+      let today = parse_date_expr_at(
+        "today",
+        now,
+        time_of_day
+      )?;
       return Ok(
         today + Duration::days(1)
       );
     }
     | "yesterday" => {
-      let today =
-        parse_date_expr("today", now)?;
+      let today = parse_date_expr_at(
+        "today",
+        now,
+        time_of_day
+      )?;
       return Ok(
         today - Duration::days(1)
       );
     }
+    | "sod" | "eod" | "eow" | "nbd" => {
+      return business_keyword(
+        lower.as_str(),
+        now,
+        time_of_day,
+        calendar
+      );
+    }
     | _ => {}
   }
 
@@ -326,16 +1247,8 @@ pub fn parse_date_expr(
         "invalid year value: {year}"
       )
     })?;
-    let midnight = date
-      .and_hms_opt(0, 0, 0)
-      .ok_or_else(|| {
-        anyhow!(
-          "failed to construct \
-           midnight for year"
-        )
-      })?;
     return to_utc_from_project_local(
-      midnight,
+      date.and_time(time_of_day),
       "year-4digit"
     );
   }
@@ -353,16 +1266,8 @@ pub fn parse_date_expr(
       local_today,
       target_weekday
     );
-    let midnight = target_date
-      .and_hms_opt(0, 0, 0)
-      .ok_or_else(|| {
-        anyhow!(
-          "failed to construct \
-           weekday midnight"
-        )
-      })?;
     return to_utc_from_project_local(
-      midnight,
+      target_date.and_time(time_of_day),
       "weekday-name"
     );
   }
@@ -412,6 +1317,37 @@ pub fn parse_date_expr(
     );
   }
 
+  if let Some((month, year)) = lower
+    .split_once(char::is_whitespace)
+    && let Some(target_month) =
+      parse_month_name(month)
+    && let year = year.trim()
+    && year.len() == 4
+    && year
+      .chars()
+      .all(|c| c.is_ascii_digit())
+  {
+    let year: i32 =
+      year.parse().context(
+        "invalid 4-digit year"
+      )?;
+    let date = NaiveDate::from_ymd_opt(
+      year,
+      target_month,
+      1
+    )
+    .ok_or_else(|| {
+      anyhow!(
+        "invalid month/year value: \
+         {token}"
+      )
+    })?;
+    return to_utc_from_project_local(
+      date.and_time(time_of_day),
+      "month-year"
+    );
+  }
+
   if let Some(target_month) =
     parse_month_name(&lower)
   {
@@ -458,13 +1394,7 @@ pub fn parse_date_expr(
            candidate"
         )
       })?
-      .and_hms_opt(0, 0, 0)
-      .ok_or_else(|| {
-        anyhow!(
-          "failed to construct month \
-           midnight"
-        )
-      })?;
+      .and_time(time_of_day);
 
     return to_utc_from_project_local(
       candidate_next,
@@ -472,7 +1402,7 @@ pub fn parse_date_expr(
     );
   }
 
-  let rel_re = Regex::new(r"^(?P<sign>[+-])(?P<num>\d+)(?P<unit>[dhm])$")
+  let rel_re = Regex::new(r"^(?P<sign>[+-])(?P<num>\d+)(?P<unit>[dhmb])$")
         .map_err(|e| anyhow!("internal regex compile failure: {e}"))?;
 
   if let Some(caps) =
@@ -503,10 +1433,35 @@ pub fn parse_date_expr(
         anyhow!("missing relative unit")
       })?;
 
+    if unit == "b"
+      || (unit == "d"
+        && calendar.enabled)
+    {
+      let local = now.with_timezone(
+        project_timezone()
+      );
+      let days = if sign == "-" {
+        -num
+      } else {
+        num
+      };
+      return to_utc_from_project_local(
+        calendar
+          .add_workdays(
+            local.date_naive(),
+            days
+          )?
+          .and_time(local.time()),
+        "business-days"
+      );
+    }
+
     let duration = match unit {
-      | "d" => Duration::days(num),
-      | "h" => Duration::hours(num),
-      | "m" => Duration::minutes(num),
+      | "d" => Duration::try_days(num),
+      | "h" => Duration::try_hours(num),
+      | "m" => {
+        Duration::try_minutes(num)
+      }
       | _ => {
         return Err(anyhow!(
           "unknown relative unit: \
@@ -514,15 +1469,24 @@ pub fn parse_date_expr(
         ))
       }
     };
-
-    return Ok(
-      if sign == "-" {
-        now - duration
-      } else {
-        now + duration
-      }
-    );
-  }
+    let shifted =
+      duration.and_then(|duration| {
+        if sign == "-" {
+          now.checked_sub_signed(
+            duration
+          )
+        } else {
+          now.checked_add_signed(
+            duration
+          )
+        }
+      });
+    return shifted.ok_or_else(|| {
+      anyhow!(
+        "date {token} is out of range"
+      )
+    });
+  }
 
   if let Ok(ndt) =
     NaiveDateTime::parse_from_str(
@@ -544,16 +1508,9 @@ pub fn parse_date_expr(
       token, "%Y-%m-%d"
     )
   {
-    let midnight = date
-      .and_hms_opt(0, 0, 0)
-      .ok_or_else(|| {
-        anyhow!(
-          "failed to construct \
-           midnight for date"
-        )
-      })?;
     return to_utc_from_project_local(
-      midnight, "date"
+      date.and_time(time_of_day),
+      "date"
     );
   }
 
@@ -587,7 +1544,237 @@ pub fn parse_date_expr(
   })
 }
 
-fn parse_weekday_name(
+/// Resolves a snooze target. Offsets
+/// (`1d`, `+2w`, `3h`) count from
+/// `now`; day and week offsets keep the
+/// project-local wall clock across DST
+/// changes. `next monday` and anything
+/// `parse_date_expr` accepts are taken
+/// as absolute. The result must lie in
+/// the future.
+pub fn parse_snooze_expr(
+  input: &str,
+  now: DateTime<Utc>
+) -> anyhow::Result<DateTime<Utc>> {
+  let token = input.trim();
+  let lower =
+    token.to_ascii_lowercase();
+  let offset = lower
+    .strip_prefix('+')
+    .unwrap_or(&lower);
+  let target = match offset
+    .char_indices()
+    .last()
+    .filter(|(idx, _)| {
+      *idx > 0
+        && offset[..*idx]
+          .chars()
+          .all(|c| c.is_ascii_digit())
+    }) {
+    | Some((idx, unit)) => {
+      let amount: i64 =
+        offset[..idx].parse().context(
+          "invalid snooze amount"
+        )?;
+      let out_of_range = || {
+        anyhow!(
+          "snooze {input} is out of \
+           range"
+        )
+      };
+      match unit {
+        | 'd' | 'w' => {
+          let days = if unit == 'w' {
+            amount.checked_mul(7)
+          } else {
+            Some(amount)
+          };
+          let local = days
+            .and_then(
+              Duration::try_days
+            )
+            .and_then(|span| {
+              now
+                .with_timezone(
+                  project_timezone()
+                )
+                .naive_local()
+                .checked_add_signed(
+                  span
+                )
+            })
+            .ok_or_else(out_of_range)?;
+          to_utc_from_project_local(
+            local, "snooze"
+          )?
+        }
+        | 'h' => {
+          Duration::try_hours(amount)
+            .and_then(|span| {
+              now.checked_add_signed(
+                span
+              )
+            })
+            .ok_or_else(out_of_range)?
+        }
+        | 'm' => {
+          Duration::try_minutes(amount)
+            .and_then(|span| {
+              now.checked_add_signed(
+                span
+              )
+            })
+            .ok_or_else(out_of_range)?
+        }
+        | _ => {
+          parse_date_expr(token, now)?
+        }
+      }
+    }
+    | None => {
+      parse_date_expr(
+        lower
+          .strip_prefix("next ")
+          .unwrap_or(token),
+        now
+      )?
+    }
+  };
+  if target <= now {
+    return Err(anyhow!(
+      "snooze target {input} is not \
+       in the future"
+    ));
+  }
+  Ok(target)
+}
+
+/// Shifts an existing date by a signed
+/// offset: `+3d`, `-2w`, `4h`, `-30m`
+/// or `+1b` (a bare amount counts
+/// forward). Day and week offsets keep
+/// the project-local wall clock across
+/// DST changes; `b` counts working
+/// days.
+pub fn shift_date(
+  base: DateTime<Utc>,
+  offset: &str
+) -> anyhow::Result<DateTime<Utc>> {
+  shift_date_with(
+    base,
+    offset,
+    work_calendar()
+  )
+}
+
+/// `shift_date` against an explicit
+/// `WorkCalendar`.
+pub fn shift_date_with(
+  base: DateTime<Utc>,
+  offset: &str,
+  calendar: &WorkCalendar
+) -> anyhow::Result<DateTime<Utc>> {
+  let token =
+    offset.trim().to_ascii_lowercase();
+  let (negative, rest) =
+    match token.strip_prefix('-') {
+      | Some(rest) => (true, rest),
+      | None => {
+        (
+          false,
+          token
+            .strip_prefix('+')
+            .unwrap_or(&token)
+        )
+      }
+    };
+  let invalid = || {
+    anyhow!(
+      "invalid date shift '{offset}': \
+       use +Nw, +Nd, +Nb, +Nh or +Nm \
+       (negative to move earlier)"
+    )
+  };
+  let out_of_range = || {
+    anyhow!(
+      "date shift '{offset}' is out \
+       of range"
+    )
+  };
+  let Some((idx, unit)) =
+    rest.char_indices().last()
+  else {
+    return Err(invalid());
+  };
+  if idx == 0
+    || !rest[..idx]
+      .chars()
+      .all(|c| c.is_ascii_digit())
+  {
+    return Err(invalid());
+  }
+  let amount: i64 = rest[..idx]
+    .parse()
+    .map_err(|_| invalid())?;
+  let amount = if negative {
+    -amount
+  } else {
+    amount
+  };
+
+  let local = base
+    .with_timezone(project_timezone())
+    .naive_local();
+  match unit {
+    | 'w' | 'd' => {
+      let days = if unit == 'w' {
+        amount.checked_mul(7)
+      } else {
+        Some(amount)
+      };
+      to_utc_from_project_local(
+        days
+          .and_then(Duration::try_days)
+          .and_then(|span| {
+            local
+              .checked_add_signed(span)
+          })
+          .ok_or_else(out_of_range)?,
+        "shift"
+      )
+    }
+    | 'b' => {
+      to_utc_from_project_local(
+        calendar
+          .add_workdays(
+            local.date(),
+            amount
+          )?
+          .and_time(local.time()),
+        "shift-business-days"
+      )
+    }
+    | 'h' => {
+      Duration::try_hours(amount)
+        .and_then(|span| {
+          base.checked_add_signed(span)
+        })
+        .ok_or_else(out_of_range)
+    }
+    | 'm' => {
+      Duration::try_minutes(amount)
+        .and_then(|span| {
+          base.checked_add_signed(span)
+        })
+        .ok_or_else(out_of_range)
+    }
+    | _ => Err(invalid())
+  }
+}
+
+/// Parses a weekday name or its short
+/// form (`monday`, `mon`).
+pub fn parse_weekday_name(
   token: &str
 ) -> Option<Weekday> {
   match token.trim() {
@@ -615,6 +1802,105 @@ fn parse_weekday_name(
   }
 }
 
+fn business_keyword(
+  keyword: &str,
+  now: DateTime<Utc>,
+  time_of_day: NaiveTime,
+  calendar: &WorkCalendar
+) -> anyhow::Result<DateTime<Utc>> {
+  let local_now = now
+    .with_timezone(project_timezone())
+    .naive_local();
+  let today = local_now.date();
+  let end_of_day =
+    NaiveTime::from_hms_opt(23, 59, 59)
+      .unwrap_or(NaiveTime::MIN);
+  let local = match keyword {
+    | "nbd" => {
+      let time = if calendar.enabled {
+        calendar.day_start
+      } else {
+        time_of_day
+      };
+      calendar
+        .add_workdays(today, 1)?
+        .and_time(time)
+    }
+    | "eow" if calendar.enabled => {
+      let week_start = today
+        - Duration::days(i64::from(
+          today
+            .weekday()
+            .num_days_from_monday()
+        ));
+      let last_workday =
+        |monday: NaiveDate| {
+          (0..7)
+            .rev()
+            .map(|offset| {
+              monday
+                + Duration::days(offset)
+            })
+            .find(|day| {
+              calendar.is_workday(*day)
+            })
+        };
+      // A week made of holidays has no
+      // end of its own; look further.
+      (0..=52)
+        .filter_map(|week| {
+          last_workday(
+            week_start
+              + Duration::weeks(week)
+          )
+        })
+        .map(|day| {
+          day.and_time(calendar.day_end)
+        })
+        .find(|end| *end > local_now)
+        .ok_or_else(|| {
+          anyhow!(
+            "no working day within a \
+             year for eow"
+          )
+        })?
+    }
+    | "eow" => {
+      (today
+        + Duration::days(i64::from(
+          6 - today
+            .weekday()
+            .num_days_from_monday()
+        )))
+      .and_time(end_of_day)
+    }
+    | _ if calendar.enabled => {
+      let mut day = calendar
+        .workday_on_or_after(today)?;
+      if day.and_time(calendar.day_end)
+        <= local_now
+      {
+        day = calendar
+          .add_workdays(day, 1)?;
+      }
+      day.and_time(
+        if keyword == "sod" {
+          calendar.day_start
+        } else {
+          calendar.day_end
+        }
+      )
+    }
+    | "sod" => {
+      today.and_time(NaiveTime::MIN)
+    }
+    | _ => today.and_time(end_of_day)
+  };
+  to_utc_from_project_local(
+    local, keyword
+  )
+}
+
 fn next_weekday_date(
   from: NaiveDate,
   target: Weekday
@@ -698,7 +1984,9 @@ fn parse_clock_time(
   Some((hour, minute))
 }
 
-fn parse_month_name(
+/// Parses a month name or its short
+/// form (`march`, `mar`) to `1..=12`.
+pub fn parse_month_name(
   token: &str
 ) -> Option<u32> {
   match token.trim() {
@@ -720,18 +2008,551 @@ fn parse_month_name(
   }
 }
 
+/// Resolves `calendar` arguments to the
+/// first day of each month to show.
+/// Accepts a month (`3` or `march`), a
+/// 4-digit year and `months:N`; a bare
+/// year shows all twelve of its months.
+pub fn calendar_months(
+  args: &[String],
+  today: NaiveDate
+) -> anyhow::Result<Vec<NaiveDate>> {
+  let mut month = None;
+  let mut year = None;
+  let mut count = None;
+  for arg in args {
+    let token =
+      arg.trim().to_ascii_lowercase();
+    if let Some(raw) =
+      token.strip_prefix("months:")
+    {
+      let value = raw
+        .parse::<u32>()
+        .ok()
+        .filter(|value| {
+          (1..=CALENDAR_MAX_MONTHS)
+            .contains(value)
+        })
+        .ok_or_else(|| {
+          anyhow!(
+            "months:{raw} must be \
+             between 1 and \
+             {CALENDAR_MAX_MONTHS}"
+          )
+        })?;
+      count = Some(value);
+    } else if let Some(value) =
+      parse_month_name(&token)
+    {
+      month = Some(value);
+    } else if let Ok(value) =
+      token.parse::<u32>()
+    {
+      if token.len() == 4 {
+        year = Some(value as i32);
+      } else if (1..=12)
+        .contains(&value)
+      {
+        month = Some(value);
+      } else {
+        return Err(anyhow!(
+          "invalid calendar month: \
+           {arg}"
+        ));
+      }
+    } else {
+      return Err(anyhow!(
+        "unrecognized calendar \
+         argument: {arg} (expected a \
+         month, a 4-digit year or \
+         months:N)"
+      ));
+    }
+  }
+
+  let (month, count) =
+    match (month, year) {
+      | (Some(month), _) => {
+        (month, count.unwrap_or(1))
+      }
+      | (None, Some(_)) => {
+        (1, count.unwrap_or(12))
+      }
+      | (None, None) => {
+        (
+          today.month(),
+          count.unwrap_or(1)
+        )
+      }
+    };
+  let year =
+    year.unwrap_or(today.year());
+  let first = NaiveDate::from_ymd_opt(
+    year, month, 1
+  )
+  .ok_or_else(|| {
+    anyhow!(
+      "invalid calendar month: \
+       {year}-{month:02}"
+    )
+  })?;
+  Ok(
+    (0..count)
+      .filter_map(|offset| {
+        first.checked_add_months(
+          chrono::Months::new(offset)
+        )
+      })
+      .collect()
+  )
+}
+
+const CALENDAR_MAX_MONTHS: u32 = 24;
+
+/// Lays out the month starting at
+/// `first` as rows of seven days that
+/// begin on `week_start`; days outside
+/// the month are `None`.
+pub fn month_weeks(
+  first: NaiveDate,
+  week_start: Weekday
+) -> Vec<[Option<NaiveDate>; 7]> {
+  let lead = (7
+    + first
+      .weekday()
+      .num_days_from_monday()
+    - week_start
+      .num_days_from_monday())
+    % 7;
+  let mut weeks = Vec::new();
+  let mut row = [None; 7];
+  let mut column = lead as usize;
+  let mut day = first;
+  while day.month() == first.month() {
+    row[column] = Some(day);
+    column += 1;
+    if column == 7 {
+      weeks.push(row);
+      row = [None; 7];
+      column = 0;
+    }
+    day += Duration::days(1);
+  }
+  if column > 0 {
+    weeks.push(row);
+  }
+  weeks
+}
+
 #[cfg(test)]
 mod tests {
   use chrono::{
+    DateTime,
+    Datelike,
+    Duration,
+    NaiveDate,
+    NaiveTime,
     TimeZone,
-    Utc
+    Utc,
+    Weekday
   };
 
   use super::{
+    DateFormat,
+    DefaultTimes,
+    DueBucket,
+    HumanizeStyle,
+    WorkCalendar,
+    calendar_months,
+    due_bucket,
+    humanize,
+    humanize_with,
+    month_weeks,
     parse_date_expr,
+    parse_date_expr_at,
+    parse_date_expr_with,
+    parse_snooze_expr,
+    project_timezone,
+    shift_date_with,
     to_project_date
   };
 
+  #[test]
+  fn buckets_due_dates_by_project_day_and_week_start()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 17, 12, 0, 0
+      )
+      .single()
+      .expect("valid now");
+    let at = |day: u32, hour: u32| {
+      Utc
+        .with_ymd_and_hms(
+          2026, 2, day, hour, 0, 0
+        )
+        .single()
+        .expect("valid due")
+    };
+
+    assert_eq!(
+      due_bucket(
+        None,
+        now,
+        Weekday::Mon
+      ),
+      DueBucket::NoDue
+    );
+    assert_eq!(
+      due_bucket(
+        Some(at(17, 11)),
+        now,
+        Weekday::Mon
+      ),
+      DueBucket::Overdue
+    );
+    assert_eq!(
+      due_bucket(
+        Some(at(17, 20)),
+        now,
+        Weekday::Mon
+      ),
+      DueBucket::Today
+    );
+    assert_eq!(
+      due_bucket(
+        Some(at(18, 18)),
+        now,
+        Weekday::Mon
+      ),
+      DueBucket::Tomorrow
+    );
+    assert_eq!(
+      due_bucket(
+        Some(at(21, 18)),
+        now,
+        Weekday::Sun
+      ),
+      DueBucket::ThisWeek
+    );
+    assert_eq!(
+      due_bucket(
+        Some(at(22, 18)),
+        now,
+        Weekday::Mon
+      ),
+      DueBucket::ThisWeek
+    );
+    assert_eq!(
+      due_bucket(
+        Some(at(22, 18)),
+        now,
+        Weekday::Sun
+      ),
+      DueBucket::Later
+    );
+  }
+
+  #[test]
+  fn date_format_translates_codes_and_rejects_unknown()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 17, 18, 5, 0
+      )
+      .single()
+      .expect("valid now");
+
+    let format = DateFormat::parse(
+      "D/M/Y H:N \\a\\t a"
+    )
+    .expect("valid format");
+    assert_eq!(
+      format.format(now, now),
+      "17/02/2026 12:05 at Tue"
+    );
+
+    let err = DateFormat::parse("Y-Q")
+      .expect_err("unknown code");
+    assert!(
+      err.to_string().contains("'Q'")
+    );
+
+    let relative =
+      DateFormat::parse("relative")
+        .expect("relative");
+    assert_eq!(
+      relative.format(
+        now + Duration::days(3),
+        now
+      ),
+      "in 3 days"
+    );
+    assert_eq!(
+      relative.format(
+        now - Duration::hours(2),
+        now
+      ),
+      "2 hours ago"
+    );
+  }
+
+  #[test]
+  fn humanizes_past_and_future_at_unit_boundaries()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 17, 12, 0, 0
+      )
+      .single()
+      .expect("valid now");
+    let cases = [
+      (
+        Duration::seconds(0),
+        "just now",
+        "now"
+      ),
+      (
+        Duration::seconds(59),
+        "just now",
+        "now"
+      ),
+      (
+        Duration::seconds(-59),
+        "just now",
+        "now"
+      ),
+      (
+        Duration::seconds(60),
+        "in 1 minute",
+        "in 1m"
+      ),
+      (
+        Duration::minutes(-59),
+        "59 minutes ago",
+        "59m ago"
+      ),
+      (
+        Duration::hours(-2),
+        "2 hours ago",
+        "2h ago"
+      ),
+      (
+        Duration::hours(23),
+        "in 23 hours",
+        "in 23h"
+      ),
+      (
+        Duration::hours(-24),
+        "yesterday",
+        "1d ago"
+      ),
+      (
+        Duration::hours(47),
+        "tomorrow",
+        "in 1d"
+      ),
+      (
+        Duration::days(3),
+        "in 3 days",
+        "in 3d"
+      ),
+      (
+        Duration::days(-13),
+        "13 days ago",
+        "13d ago"
+      ),
+      (
+        Duration::days(14),
+        "in 2 weeks",
+        "in 2w"
+      ),
+      (
+        Duration::days(-60),
+        "2 months ago",
+        "2mo ago"
+      ),
+      (
+        Duration::days(365),
+        "in 1 year",
+        "in 1y"
+      )
+    ];
+    for (offset, long, short) in cases {
+      assert_eq!(
+        humanize(now + offset, now),
+        long,
+        "{offset}"
+      );
+      assert_eq!(
+        humanize_with(
+          now + offset,
+          now,
+          HumanizeStyle::Short
+        ),
+        short,
+        "{offset}"
+      );
+    }
+    assert!(
+      HumanizeStyle::parse("tiny")
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn business_keywords_skip_weekends_and_holidays_only_when_enabled()
+   {
+    let tz = project_timezone();
+    let local =
+      |day: u32, hour: u32| {
+        tz.with_ymd_and_hms(
+          2026, 2, day, hour, 0, 0
+        )
+        .single()
+        .expect("valid local time")
+        .with_timezone(&Utc)
+      };
+    // Friday after hours; Monday the
+    // 23rd is a holiday.
+    let now = local(20, 18);
+    let mut calendar =
+      WorkCalendar::default();
+    calendar.holidays.insert(
+      NaiveDate::from_ymd_opt(
+        2026, 2, 23
+      )
+      .expect("valid date")
+    );
+    let parse =
+      |input: &str,
+       calendar: &WorkCalendar| {
+        parse_date_expr_with(
+          input,
+          now,
+          NaiveTime::MIN,
+          calendar
+        )
+        .expect(input)
+      };
+
+    assert_eq!(
+      parse("eod", &calendar),
+      local(20, 23)
+        + Duration::seconds(3_599)
+    );
+    assert_eq!(
+      parse("eow", &calendar),
+      local(22, 23)
+        + Duration::seconds(3_599)
+    );
+    assert_eq!(
+      parse("+1d", &calendar),
+      local(21, 18)
+    );
+    assert_eq!(
+      parse("nbd", &calendar),
+      local(24, 0)
+    );
+    assert_eq!(
+      parse("+1b", &calendar),
+      local(24, 18)
+    );
+    assert_eq!(
+      parse("-1b", &calendar),
+      local(19, 18)
+    );
+
+    calendar.enabled = true;
+    assert_eq!(
+      parse("eod", &calendar),
+      local(24, 17)
+    );
+    assert_eq!(
+      parse("sod", &calendar),
+      local(24, 9)
+    );
+    assert_eq!(
+      parse("nbd", &calendar),
+      local(24, 9)
+    );
+    assert_eq!(
+      parse("+1d", &calendar),
+      local(24, 18)
+    );
+    assert_eq!(
+      parse("eow", &calendar),
+      local(27, 17)
+    );
+  }
+
+  #[test]
+  fn add_workdays_skips_whole_weeks_and_rejects_overflow()
+   {
+    let date =
+      |month: u32, day: u32| {
+        NaiveDate::from_ymd_opt(
+          2026, month, day
+        )
+        .expect("valid date")
+      };
+    let mut calendar =
+      WorkCalendar::default();
+    calendar.holidays.extend([
+      date(2, 23),
+      date(2, 28),
+      date(3, 10),
+      date(1, 30)
+    ]);
+    let walk =
+      |mut day: NaiveDate,
+       days: i64| {
+        let step =
+          Duration::days(days.signum());
+        for _ in 0..days.unsigned_abs()
+        {
+          day += step;
+          while !calendar
+            .is_workday(day)
+          {
+            day += step;
+          }
+        }
+        day
+      };
+    for start in
+      [date(2, 20), date(2, 22)]
+    {
+      for days in -40..=40 {
+        assert_eq!(
+          calendar
+            .add_workdays(start, days)
+            .expect("in range"),
+          walk(start, days),
+          "{start} {days}"
+        );
+      }
+    }
+
+    assert!(
+      calendar
+        .add_workdays(
+          date(2, 20),
+          i64::MAX
+        )
+        .is_err()
+    );
+    assert!(
+      parse_date_expr_with(
+        "+999999999b",
+        Utc::now(),
+        NaiveTime::MIN,
+        &calendar
+      )
+      .is_err()
+    );
+  }
+
   #[test]
   fn parses_four_digit_year() {
     let now = Utc
@@ -751,6 +2572,103 @@ mod tests {
     );
   }
 
+  #[test]
+  fn rejects_out_of_range_offsets() {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 17, 12, 0, 0
+      )
+      .single()
+      .expect("valid now");
+    for input in [
+      "+99999999999d",
+      "-99999999999h",
+      "+9999999999999999m"
+    ] {
+      assert!(
+        parse_date_expr(input, now)
+          .is_err(),
+        "{input} should be rejected"
+      );
+    }
+  }
+
+  #[test]
+  fn date_only_inputs_take_the_default_time()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 17, 12, 0, 0
+      )
+      .single()
+      .expect("valid now");
+    let times =
+      DefaultTimes::from_values(
+        Some("23:59".to_string()),
+        Some("9:00am".to_string()),
+        None
+      )
+      .expect("valid times");
+    assert_eq!(
+      times.scheduled,
+      NaiveTime::MIN
+    );
+
+    let local = |dt: DateTime<Utc>| {
+      dt.with_timezone(
+        project_timezone()
+      )
+      .format("%Y-%m-%d %H:%M")
+      .to_string()
+    };
+    for (input, expected) in [
+      (
+        "2026-02-20",
+        "2026-02-20 23:59"
+      ),
+      ("tomorrow", "2026-02-18 23:59"),
+      ("friday", "2026-02-20 23:59"),
+      (
+        "2026-02-20 10:30",
+        "2026-02-20 10:30"
+      )
+    ] {
+      let parsed = parse_date_expr_at(
+        input, now, times.due
+      )
+      .expect("parse");
+      assert_eq!(
+        local(parsed),
+        expected,
+        "{input}"
+      );
+    }
+    assert_eq!(
+      local(
+        parse_date_expr_at(
+          "2026-02-20",
+          now,
+          times.wait
+        )
+        .expect("parse")
+      ),
+      "2026-02-20 09:00"
+    );
+
+    let err =
+      DefaultTimes::from_values(
+        Some("late".to_string()),
+        None,
+        None
+      )
+      .expect_err("invalid time");
+    assert!(
+      err
+        .to_string()
+        .contains("due.default_time")
+    );
+  }
+
   #[test]
   fn parses_weekday_name() {
     let now = Utc
@@ -787,6 +2705,19 @@ mod tests {
         .to_string(),
       "2026-03-01"
     );
+    let parsed =
+      parse_date_expr("Jan 2025", now)
+        .expect("parse month and year");
+    assert_eq!(
+      to_project_date(parsed)
+        .format("%Y-%m-%d")
+        .to_string(),
+      "2025-01-01"
+    );
+    assert!(
+      parse_date_expr("march 27", now)
+        .is_err()
+    );
   }
 
   #[test]
@@ -810,6 +2741,224 @@ mod tests {
       "15:23"
     );
   }
+
+  #[test]
+  fn parses_snooze_offsets_and_dates() {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 17, 12, 0, 0
+      )
+      .single()
+      .expect("valid now");
+    let day = |expr: &str| {
+      to_project_date(
+        parse_snooze_expr(expr, now)
+          .expect("parse snooze")
+      )
+      .format("%Y-%m-%d")
+      .to_string()
+    };
+    assert_eq!(day("1d"), "2026-02-18");
+    assert_eq!(
+      day("+1w"),
+      "2026-02-24"
+    );
+    assert_eq!(
+      day("next monday"),
+      "2026-02-23"
+    );
+    assert_eq!(
+      parse_snooze_expr("3h", now)
+        .expect("hours"),
+      now + Duration::hours(3)
+    );
+    assert!(
+      parse_snooze_expr(
+        "yesterday",
+        now
+      )
+      .is_err()
+    );
+    for huge in [
+      "99999999999999d",
+      "9999999999999999w",
+      "99999999999999h",
+      "999999999999999m"
+    ] {
+      assert!(
+        parse_snooze_expr(huge, now)
+          .is_err(),
+        "{huge}"
+      );
+    }
+  }
+
+  #[test]
+  fn shifts_dates_forward_and_back() {
+    let tz = project_timezone();
+    // Friday 2026-02-20 17:30 local.
+    let base = tz
+      .with_ymd_and_hms(
+        2026, 2, 20, 17, 30, 0
+      )
+      .single()
+      .expect("valid local time")
+      .with_timezone(&Utc);
+    let calendar =
+      WorkCalendar::default();
+    let shift = |offset: &str| {
+      shift_date_with(
+        base, offset, &calendar
+      )
+      .expect(offset)
+    };
+    let local = |dt: DateTime<Utc>| {
+      dt.with_timezone(tz)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+    };
+
+    assert_eq!(
+      local(shift("+3d")),
+      "2026-02-23 17:30"
+    );
+    assert_eq!(
+      local(shift("1w")),
+      "2026-02-27 17:30"
+    );
+    assert_eq!(
+      local(shift("-2d")),
+      "2026-02-18 17:30"
+    );
+    assert_eq!(
+      local(shift("+1b")),
+      "2026-02-23 17:30"
+    );
+    assert_eq!(
+      shift("-90m"),
+      base - Duration::minutes(90)
+    );
+    for bad in [
+      "",
+      "+",
+      "3",
+      "+3x",
+      "d",
+      "99999999999999d",
+      "-9999999999999999w",
+      "99999999999999h",
+      "999999999999999m"
+    ] {
+      assert!(
+        shift_date_with(
+          base, bad, &calendar
+        )
+        .is_err(),
+        "{bad}"
+      );
+    }
+  }
+
+  #[test]
+  fn calendar_months_resolve_month_year_and_range()
+   {
+    let today =
+      NaiveDate::from_ymd_opt(
+        2026, 2, 17
+      )
+      .expect("valid today");
+    let args = |raw: &[&str]| {
+      raw
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<_>>()
+    };
+    let first = |year, month| {
+      NaiveDate::from_ymd_opt(
+        year, month, 1
+      )
+      .expect("valid month")
+    };
+
+    assert_eq!(
+      calendar_months(&[], today)
+        .expect("default"),
+      vec![first(2026, 2)]
+    );
+    assert_eq!(
+      calendar_months(
+        &args(&["march", "2027"]),
+        today
+      )
+      .expect("month year"),
+      vec![first(2027, 3)]
+    );
+    assert_eq!(
+      calendar_months(
+        &args(&["12", "months:3"]),
+        today
+      )
+      .expect("range"),
+      vec![
+        first(2026, 12),
+        first(2027, 1),
+        first(2027, 2),
+      ]
+    );
+    assert_eq!(
+      calendar_months(
+        &args(&["2025"]),
+        today
+      )
+      .expect("year")
+      .len(),
+      12
+    );
+    assert!(
+      calendar_months(
+        &args(&["13"]),
+        today
+      )
+      .is_err()
+    );
+    assert!(
+      calendar_months(
+        &args(&["months:0"]),
+        today
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn month_weeks_align_to_week_start() {
+    let march =
+      NaiveDate::from_ymd_opt(
+        2026, 3, 1
+      )
+      .expect("valid month");
+
+    let monday =
+      month_weeks(march, Weekday::Mon);
+    assert_eq!(monday.len(), 6);
+    assert_eq!(
+      monday[0][6],
+      Some(march)
+    );
+    assert_eq!(monday[0][5], None);
+
+    let sunday =
+      month_weeks(march, Weekday::Sun);
+    assert_eq!(sunday.len(), 5);
+    assert_eq!(
+      sunday[0][0],
+      Some(march)
+    );
+    assert_eq!(
+      sunday[4][2].map(|day| day.day()),
+      Some(31)
+    );
+  }
 }
 
 pub mod taskwarrior_date_serde {
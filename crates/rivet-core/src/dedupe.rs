@@ -0,0 +1,642 @@
+//! Likely-duplicate open tasks for
+//! `task dedupe` and the GUI dedupe
+//! panel. Pairs in the same project
+//! (and under the same parent) are
+//! scored 0-100 the way contact dedupe
+//! scores names: description
+//! similarity carries 80 points and
+//! tag overlap 20. Pairs at or above
+//! `dedupe.threshold` are joined into
+//! groups; each group merges into its
+//! oldest task.
+
+use std::collections::{
+  BTreeMap,
+  BTreeSet
+};
+
+use chrono::{
+  DateTime,
+  Utc
+};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::task::{
+  Status,
+  Task
+};
+
+pub const DEFAULT_THRESHOLD: u32 = 80;
+
+/// `dedupe.threshold` (1-100), 80 by
+/// default.
+pub fn threshold_from_config(
+  cfg: &Config
+) -> u32 {
+  cfg
+    .get("dedupe.threshold")
+    .and_then(|raw| {
+      parse_threshold(&raw)
+    })
+    .unwrap_or(DEFAULT_THRESHOLD)
+}
+
+pub fn parse_threshold(
+  raw: &str
+) -> Option<u32> {
+  raw.trim().parse::<u32>().ok().filter(
+    |value| (1..=100).contains(value)
+  )
+}
+
+/// Lowercase words with punctuation
+/// dropped, so `Buy milk!` and `buy
+/// milk` compare equal.
+pub fn normalize_description(
+  value: &str
+) -> String {
+  value
+    .to_lowercase()
+    .chars()
+    .map(|ch| {
+      if ch.is_alphanumeric() {
+        ch
+      } else {
+        ' '
+      }
+    })
+    .collect::<String>()
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn levenshtein_distance(
+  left: &[char],
+  right: &[char]
+) -> usize {
+  let mut previous: Vec<usize> =
+    (0..=right.len()).collect();
+  let mut current =
+    vec![0; right.len() + 1];
+  for (i, left_ch) in
+    left.iter().enumerate()
+  {
+    current[0] = i + 1;
+    for (j, right_ch) in
+      right.iter().enumerate()
+    {
+      let cost = usize::from(
+        left_ch != right_ch
+      );
+      current[j + 1] =
+        (previous[j + 1] + 1)
+          .min(current[j] + 1)
+          .min(previous[j] + cost);
+    }
+    std::mem::swap(
+      &mut previous,
+      &mut current
+    );
+  }
+  previous[right.len()]
+}
+
+fn similarity(
+  left: &str,
+  right: &str
+) -> f64 {
+  if left == right {
+    return 1.0;
+  }
+  let left: Vec<char> =
+    left.chars().collect();
+  let right: Vec<char> =
+    right.chars().collect();
+  let max_len =
+    left.len().max(right.len());
+  if max_len == 0 {
+    return 1.0;
+  }
+  1.0
+    - levenshtein_distance(
+      &left, &right
+    ) as f64
+      / max_len as f64
+}
+
+/// Jaccard overlap of the tag sets;
+/// two untagged tasks count as full
+/// overlap.
+fn tag_overlap(
+  left: &Task,
+  right: &Task
+) -> f64 {
+  let left: BTreeSet<&str> = left
+    .tags
+    .iter()
+    .map(String::as_str)
+    .collect();
+  let right: BTreeSet<&str> = right
+    .tags
+    .iter()
+    .map(String::as_str)
+    .collect();
+  let union =
+    left.union(&right).count();
+  if union == 0 {
+    return 1.0;
+  }
+  left.intersection(&right).count()
+    as f64
+    / union as f64
+}
+
+fn project_key(task: &Task) -> String {
+  task
+    .project
+    .as_deref()
+    .unwrap_or_default()
+    .trim()
+    .to_lowercase()
+}
+
+/// Score and reason for a pair, or
+/// `None` when they cannot be the same
+/// task (other project or parent, or
+/// one description is empty).
+pub fn score_pair(
+  left: &Task,
+  right: &Task
+) -> Option<(u32, &'static str)> {
+  if project_key(left)
+    != project_key(right)
+    || left.parent != right.parent
+  {
+    return None;
+  }
+  let left_text = normalize_description(
+    &left.description
+  );
+  let right_text =
+    normalize_description(
+      &right.description
+    );
+  if left_text.is_empty()
+    || right_text.is_empty()
+  {
+    return None;
+  }
+  let text =
+    similarity(&left_text, &right_text);
+  let tags = tag_overlap(left, right);
+  let score = (text * 80.0
+    + tags * 20.0)
+    .round() as u32;
+  let reason =
+    if left_text != right_text {
+      "similar description"
+    } else if tags >= 1.0 {
+      "same description and tags"
+    } else {
+      "same description"
+    };
+  Some((score, reason))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+  /// Best pair score in the group.
+  pub score:  u32,
+  pub reason: &'static str,
+  /// Oldest `entry` first; that task
+  /// is the one kept.
+  pub tasks:  Vec<Uuid>
+}
+
+fn find_root(
+  parents: &mut [usize],
+  index: usize
+) -> usize {
+  let mut root = index;
+  while parents[root] != root {
+    root = parents[root];
+  }
+  let mut node = index;
+  while parents[node] != root {
+    let next = parents[node];
+    parents[node] = root;
+    node = next;
+  }
+  root
+}
+
+/// Groups of open tasks whose pairs
+/// score at least `threshold`, best
+/// score first.
+pub fn find_groups(
+  tasks: &[Task],
+  threshold: u32
+) -> Vec<DuplicateGroup> {
+  let open: Vec<&Task> = tasks
+    .iter()
+    .filter(|task| {
+      matches!(
+        task.status,
+        Status::Pending
+          | Status::Waiting
+      )
+    })
+    .collect();
+  let mut parents: Vec<usize> =
+    (0..open.len()).collect();
+  let mut best = BTreeMap::<
+    usize,
+    (u32, &str)
+  >::new();
+  let mut pairs = Vec::new();
+  for left in 0..open.len() {
+    for right in (left + 1)..open.len()
+    {
+      if let Some((score, reason)) =
+        score_pair(
+          open[left],
+          open[right]
+        )
+        && score >= threshold
+      {
+        pairs.push((
+          left, right, score, reason
+        ));
+        let (a, b) = (
+          find_root(&mut parents, left),
+          find_root(
+            &mut parents,
+            right
+          )
+        );
+        if a != b {
+          parents[b] = a;
+        }
+      }
+    }
+  }
+  for (left, _, score, reason) in pairs
+  {
+    let root =
+      find_root(&mut parents, left);
+    let entry = best
+      .entry(root)
+      .or_insert((0, ""));
+    if score > entry.0 {
+      *entry = (score, reason);
+    }
+  }
+
+  let mut members =
+    BTreeMap::<usize, Vec<&Task>>::new(
+    );
+  for (index, task) in
+    open.iter().enumerate()
+  {
+    let root =
+      find_root(&mut parents, index);
+    if best.contains_key(&root) {
+      members
+        .entry(root)
+        .or_default()
+        .push(task);
+    }
+  }
+
+  let mut groups: Vec<DuplicateGroup> =
+    members
+      .into_iter()
+      .map(|(root, mut tasks)| {
+        tasks.sort_by_key(|task| {
+          (task.entry, task.uuid)
+        });
+        let (score, reason) =
+          best[&root];
+        DuplicateGroup {
+          score,
+          reason,
+          tasks: tasks
+            .iter()
+            .map(|task| task.uuid)
+            .collect()
+        }
+      })
+      .collect();
+  groups.sort_by(|a, b| {
+    b.score.cmp(&a.score).then_with(
+      || {
+        b.tasks
+          .len()
+          .cmp(&a.tasks.len())
+      }
+    )
+  });
+  groups
+}
+
+/// Folds `duplicates` into `kept`:
+/// tags, annotations and dependencies
+/// are combined, unset fields and UDAs
+/// are filled from the duplicates, and
+/// the oldest `entry` wins.
+pub fn merge_into(
+  kept: &mut Task,
+  duplicates: &[Task]
+) {
+  let merged: BTreeSet<Uuid> =
+    duplicates
+      .iter()
+      .map(|task| task.uuid)
+      .collect();
+  for duplicate in duplicates {
+    kept.entry =
+      kept.entry.min(duplicate.entry);
+    for tag in &duplicate.tags {
+      if !kept.tags.contains(tag) {
+        kept.tags.push(tag.clone());
+      }
+    }
+    for annotation in
+      &duplicate.annotations
+    {
+      if !kept.annotations.iter().any(
+        |existing| {
+          existing.description
+            == annotation.description
+        }
+      ) {
+        kept
+          .annotations
+          .push(annotation.clone());
+      }
+    }
+    for dependency in &duplicate.depends
+    {
+      if *dependency != kept.uuid
+        && !merged.contains(dependency)
+        && !kept
+          .depends
+          .contains(dependency)
+      {
+        kept.depends.push(*dependency);
+      }
+    }
+    kept.project =
+      kept.project.take().or_else(
+        || duplicate.project.clone()
+      );
+    kept.priority =
+      kept.priority.take().or_else(
+        || duplicate.priority.clone()
+      );
+    kept.due =
+      kept.due.or(duplicate.due);
+    kept.scheduled = kept
+      .scheduled
+      .or(duplicate.scheduled);
+    kept.wait =
+      kept.wait.or(duplicate.wait);
+    for (key, value) in &duplicate.extra
+    {
+      kept
+        .extra
+        .entry(key.clone())
+        .or_insert_with(|| {
+          value.clone()
+        });
+    }
+  }
+  kept.depends.retain(|dependency| {
+    !merged.contains(dependency)
+  });
+  kept.annotations.sort_by_key(
+    |annotation| annotation.entry
+  );
+}
+
+/// Resolves each group (kept uuid
+/// first) in `pending`: the rest are
+/// merged into the kept task when
+/// `merge` is set, then deleted, and
+/// dependencies on them are pointed at
+/// the kept task. Returns how many
+/// tasks were deleted.
+pub fn apply_groups(
+  pending: &mut [Task],
+  groups: &[Vec<Uuid>],
+  merge: bool,
+  now: DateTime<Utc>
+) -> u64 {
+  let mut removed = 0_u64;
+  for group in groups {
+    let Some((kept_uuid, others)) =
+      group.split_first()
+    else {
+      continue;
+    };
+    let duplicates: Vec<Task> = pending
+      .iter()
+      .filter(|task| {
+        others.contains(&task.uuid)
+      })
+      .cloned()
+      .collect();
+    if duplicates.is_empty() {
+      continue;
+    }
+    for task in pending.iter_mut() {
+      if task.uuid == *kept_uuid {
+        if merge {
+          merge_into(task, &duplicates);
+          task.modified = now;
+        }
+      } else if others
+        .contains(&task.uuid)
+      {
+        task.status = Status::Deleted;
+        task.start = None;
+        task.end = Some(now);
+        task.modified = now;
+        removed += 1;
+      } else if task
+        .depends
+        .iter()
+        .any(|dep| others.contains(dep))
+      {
+        task.depends.retain(|dep| {
+          !others.contains(dep)
+        });
+        if !task
+          .depends
+          .contains(kept_uuid)
+        {
+          task.depends.push(*kept_uuid);
+        }
+        task.modified = now;
+      }
+    }
+  }
+  removed
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{
+    Duration,
+    TimeZone,
+    Utc
+  };
+
+  use super::{
+    apply_groups,
+    find_groups,
+    merge_into,
+    score_pair
+  };
+  use crate::task::{
+    Annotation,
+    Status,
+    Task
+  };
+
+  fn task(
+    description: &str,
+    tags: &[&str],
+    age_days: i64
+  ) -> Task {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 12, 0, 0
+      )
+      .unwrap();
+    let mut task = Task::new_pending(
+      description.to_string(),
+      now - Duration::days(age_days),
+      1
+    );
+    task.project =
+      Some("home".to_string());
+    task.tags = tags
+      .iter()
+      .map(|tag| tag.to_string())
+      .collect();
+    task
+  }
+
+  #[test]
+  fn groups_similar_tasks_and_merges_into_the_oldest()
+   {
+    let oldest =
+      task("Buy milk", &["errand"], 9);
+    let mut newer = task(
+      "buy milk!",
+      &["errand", "store"],
+      2
+    );
+    newer.annotations.push(
+      Annotation {
+        entry:       newer.entry,
+        description: "2 litres"
+          .to_string()
+      }
+    );
+    newer.due = Some(newer.entry);
+    let typo =
+      task("Buy mlik", &["errand"], 5);
+    let mut other_project =
+      task("Buy milk", &["errand"], 1);
+    other_project.project =
+      Some("work".to_string());
+    let unrelated =
+      task("Call the plumber", &[], 3);
+
+    assert_eq!(
+      score_pair(&oldest, &newer),
+      Some((90, "same description"))
+    );
+    assert_eq!(
+      score_pair(
+        &oldest,
+        &other_project
+      ),
+      None
+    );
+
+    let tasks = vec![
+      newer.clone(),
+      unrelated,
+      typo.clone(),
+      other_project,
+      oldest.clone(),
+    ];
+    let groups =
+      find_groups(&tasks, 80);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].tasks, vec![
+      oldest.uuid,
+      typo.uuid,
+      newer.uuid
+    ]);
+    assert!(
+      find_groups(&tasks, 95)
+        .is_empty()
+    );
+
+    let mut kept = oldest.clone();
+    merge_into(&mut kept, &[
+      typo,
+      newer.clone()
+    ]);
+    assert_eq!(
+      kept.description,
+      "Buy milk"
+    );
+    assert_eq!(kept.tags, vec![
+      "errand", "store"
+    ]);
+    assert_eq!(
+      kept.annotations.len(),
+      1
+    );
+    assert_eq!(kept.due, newer.due);
+    assert_eq!(
+      kept.entry,
+      oldest.entry
+    );
+
+    let mut follow_up =
+      task("Pay for milk", &[], 0);
+    follow_up.depends.push(newer.uuid);
+    let mut pending = vec![
+      oldest.clone(),
+      newer.clone(),
+      follow_up,
+    ];
+    let now = newer.entry;
+    let removed = apply_groups(
+      &mut pending,
+      &[vec![oldest.uuid, newer.uuid]],
+      true,
+      now
+    );
+    assert_eq!(removed, 1);
+    assert_eq!(
+      pending[1].status,
+      Status::Deleted
+    );
+    assert_eq!(pending[0].tags, vec![
+      "errand", "store"
+    ]);
+    assert_eq!(
+      pending[2].depends,
+      vec![oldest.uuid]
+    );
+  }
+}
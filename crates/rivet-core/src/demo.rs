@@ -0,0 +1,251 @@
+//! Example data behind `seed_demo`.
+//!
+//! A brand-new store can be seeded
+//! with a handful of tasks showing
+//! tags, recurrence and kanban lanes.
+//! Every seeded task carries the
+//! `rivet_demo:true` tag so the whole
+//! set can be removed again without
+//! touching anything the user wrote.
+
+use chrono::{
+  DateTime,
+  Duration,
+  Utc
+};
+
+use crate::datastore::DataStore;
+use crate::recur::tag_value;
+use crate::task::{
+  Status,
+  Task
+};
+
+/// `key:value` tag marking seeded
+/// tasks.
+pub const DEMO_TAG_KEY: &str =
+  "rivet_demo";
+/// Board the seeded tasks sit on; the
+/// GUI creates a board with this id
+/// when it seeds.
+pub const DEMO_BOARD_ID: &str =
+  "rivet-demo";
+
+pub fn is_demo(task: &Task) -> bool {
+  tag_value(&task.tags, DEMO_TAG_KEY)
+    .is_some()
+}
+
+/// True only for a store that has
+/// never been written to: no tasks,
+/// no history and nothing quarantined.
+/// Clearing demo data leaves history
+/// behind, so a cleared store is not
+/// seeded again.
+pub fn store_is_empty(
+  store: &DataStore
+) -> anyhow::Result<bool> {
+  Ok(
+    store.load_pending()?.is_empty()
+      && store
+        .load_completed()?
+        .is_empty()
+      && store
+        .load_history()?
+        .is_empty()
+      && store
+        .load_quarantined()?
+        .is_empty()
+  )
+}
+
+/// The seeded set: four open tasks
+/// (ids from 1) and one completed task
+/// for the `finished` lane. `due_in`
+/// is days from `now`.
+pub fn demo_tasks(
+  now: DateTime<Utc>
+) -> Vec<Task> {
+  let specs: [(
+    &str,
+    &[&str],
+    Option<i64>,
+    bool
+  ); 5] = [
+    (
+      "Welcome to rivet: these are \
+       demo tasks you can clear at \
+       any time",
+      &["welcome", "kanban:todo"],
+      None,
+      false
+    ),
+    (
+      "Group tasks with tags, then \
+       filter on +home",
+      &[
+        "home",
+        "errand",
+        "kanban:todo"
+      ],
+      Some(1),
+      false
+    ),
+    (
+      "Water the plants",
+      &[
+        "home",
+        "kanban:todo",
+        "recur:weekly"
+      ],
+      Some(2),
+      false
+    ),
+    (
+      "Drag this card to another lane",
+      &["kanban:working"],
+      Some(3),
+      false
+    ),
+    (
+      "Install rivet",
+      &["kanban:finished"],
+      None,
+      true
+    )
+  ];
+
+  let mut tasks = Vec::new();
+  for (
+    index,
+    (description, tags, due_in, done)
+  ) in specs.into_iter().enumerate()
+  {
+    let mut task = Task::new_pending(
+      description.to_string(),
+      now,
+      index as u64 + 1
+    );
+    task.project =
+      Some("demo".to_string());
+    task.tags = tags
+      .iter()
+      .map(|tag| tag.to_string())
+      .chain([
+        format!(
+          "board:{DEMO_BOARD_ID}"
+        ),
+        format!("{DEMO_TAG_KEY}:true")
+      ])
+      .collect();
+    task.due = due_in.map(|days| {
+      now + Duration::days(days)
+    });
+    if done {
+      task.id = None;
+      task.status = Status::Completed;
+      task.end = Some(now);
+    }
+    tasks.push(task);
+  }
+  tasks
+}
+
+/// Writes `demo_tasks` when
+/// `store_is_empty`; returns how many
+/// tasks were added.
+pub fn seed_if_empty(
+  store: &DataStore,
+  now: DateTime<Utc>
+) -> anyhow::Result<usize> {
+  if !store_is_empty(store)? {
+    return Ok(0);
+  }
+  let (completed, pending): (
+    Vec<Task>,
+    Vec<Task>
+  ) = demo_tasks(now)
+    .into_iter()
+    .partition(|task| {
+      task.status == Status::Completed
+    });
+  store.save_pending(&pending)?;
+  store.save_completed(&completed)?;
+  Ok(pending.len() + completed.len())
+}
+
+/// Drops every demo task from both
+/// lists and returns what was removed.
+pub fn remove_demo(
+  pending: &mut Vec<Task>,
+  completed: &mut Vec<Task>
+) -> Vec<Task> {
+  let mut removed = Vec::new();
+  for tasks in [pending, completed] {
+    let (demo, kept) =
+      std::mem::take(tasks)
+        .into_iter()
+        .partition(is_demo);
+    *tasks = kept;
+    removed.extend::<Vec<Task>>(demo);
+  }
+  removed
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn seeds_only_an_empty_store_and_clears_cleanly()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let store =
+      DataStore::open(temp.path())
+        .expect("open store");
+    let now = Utc::now();
+
+    let seeded =
+      seed_if_empty(&store, now)
+        .expect("seed");
+    assert_eq!(seeded, 5);
+    assert_eq!(
+      seed_if_empty(&store, now)
+        .expect("reseed"),
+      0
+    );
+
+    let mut pending = store
+      .load_pending()
+      .expect("pending");
+    let mut completed = store
+      .load_completed()
+      .expect("completed");
+    assert_eq!(pending.len(), 4);
+    assert_eq!(completed.len(), 1);
+    assert!(
+      pending.iter().all(is_demo)
+    );
+
+    let own = Task::new_pending(
+      "mine".to_string(),
+      now,
+      5
+    );
+    pending.push(own.clone());
+    let removed = remove_demo(
+      &mut pending,
+      &mut completed
+    );
+    assert_eq!(removed.len(), 5);
+    assert_eq!(
+      pending
+        .iter()
+        .map(|task| task.uuid)
+        .collect::<Vec<_>>(),
+      vec![own.uuid]
+    );
+    assert!(completed.is_empty());
+  }
+}
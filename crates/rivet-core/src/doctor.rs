@@ -0,0 +1,1029 @@
+//! Store health checks behind
+//! `task doctor`.
+//!
+//! Each check mirrors an invariant the
+//! engine relies on: open tasks live in
+//! `pending.data` with unique ids,
+//! closed ones in `completed.data` with
+//! an end date, uuids are unique across
+//! both, `depends`/`parent` point at
+//! tasks that exist, and a recurring
+//! series has at most one open
+//! instance. `diagnose` only reads;
+//! `repair` applies the fixes that need
+//! no judgement call.
+
+use std::collections::{
+  BTreeMap,
+  BTreeSet,
+  HashMap
+};
+
+use chrono::{
+  DateTime,
+  Utc
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::datastore::QuarantinedLine;
+use crate::datetime::parse_date_expr;
+use crate::recur::{
+  RECUR_COUNT_TAG_KEY,
+  RECUR_LEFT_TAG_KEY,
+  RECUR_SERIES_TAG_KEY,
+  RECUR_TAG_KEY,
+  tag_value
+};
+use crate::task::{
+  Status,
+  Task
+};
+
+/// Format the `*.data` files store
+/// dates in.
+const STORE_DATE_FORMAT: &str =
+  "%Y%m%dT%H%M%SZ";
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+  Error,
+  Warning
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+  pub check:      &'static str,
+  pub severity:   Severity,
+  #[serde(
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub uuid:       Option<Uuid>,
+  pub message:    String,
+  /// What `--fix` does, or what to do
+  /// by hand when `fixable` is false.
+  pub suggestion: String,
+  pub fixable:    bool
+}
+
+impl Finding {
+  fn new(
+    check: &'static str,
+    severity: Severity,
+    uuid: Option<Uuid>,
+    message: String,
+    suggestion: impl Into<String>,
+    fixable: bool
+  ) -> Self {
+    Self {
+      check,
+      severity,
+      uuid,
+      message,
+      suggestion: suggestion.into(),
+      fixable
+    }
+  }
+}
+
+/// Deleted tasks stay in pending.data
+/// until `purge`.
+fn belongs_in_pending(
+  status: &Status
+) -> bool {
+  matches!(
+    status,
+    Status::Pending
+      | Status::Waiting
+      | Status::Deleted
+  )
+}
+
+fn is_open(task: &Task) -> bool {
+  matches!(
+    task.status,
+    Status::Pending | Status::Waiting
+  )
+}
+
+fn short(uuid: Uuid) -> String {
+  uuid.to_string()[..8].to_string()
+}
+
+/// Runs every task check. `date_udas`
+/// names the UDAs configured with
+/// `uda.<name>.type=date`.
+pub fn diagnose(
+  pending: &[Task],
+  completed: &[Task],
+  date_udas: &[String]
+) -> Vec<Finding> {
+  let mut findings = Vec::new();
+  let all: Vec<&Task> = pending
+    .iter()
+    .chain(completed)
+    .collect();
+  let by_uuid: HashMap<Uuid, &Task> =
+    all
+      .iter()
+      .map(|task| (task.uuid, *task))
+      .collect();
+
+  let mut seen: HashMap<Uuid, usize> =
+    HashMap::new();
+  for task in &all {
+    *seen
+      .entry(task.uuid)
+      .or_default() += 1;
+  }
+  for (uuid, count) in
+    seen.iter().filter(|(_, n)| **n > 1)
+  {
+    findings.push(Finding::new(
+      "duplicate-uuid",
+      Severity::Error,
+      Some(*uuid),
+      format!(
+        "{uuid} is stored {count} \
+         times"
+      ),
+      "keep the most recently \
+       modified copy",
+      true
+    ));
+  }
+
+  for task in pending {
+    if !belongs_in_pending(&task.status)
+    {
+      findings.push(Finding::new(
+        "misfiled",
+        Severity::Error,
+        Some(task.uuid),
+        format!(
+          "{} is {:?} but stored in \
+           pending.data",
+          short(task.uuid),
+          task.status
+        ),
+        "move it to completed.data",
+        true
+      ));
+    }
+  }
+  for task in completed {
+    if is_open(task) {
+      findings.push(Finding::new(
+        "misfiled",
+        Severity::Error,
+        Some(task.uuid),
+        format!(
+          "{} is {:?} but stored in \
+           completed.data",
+          short(task.uuid),
+          task.status
+        ),
+        "move it to pending.data",
+        true
+      ));
+    }
+  }
+
+  let mut ids: BTreeMap<u64, usize> =
+    BTreeMap::new();
+  for task in
+    all.iter().filter(|t| is_open(t))
+  {
+    match task.id {
+      | Some(id) => {
+        *ids.entry(id).or_default() +=
+          1;
+      }
+      | None => {
+        findings.push(Finding::new(
+          "missing-id",
+          Severity::Warning,
+          Some(task.uuid),
+          format!(
+            "open task {} has no id",
+            short(task.uuid)
+          ),
+          "assign the next free id",
+          true
+        ));
+      }
+    }
+  }
+  for (id, count) in
+    ids.iter().filter(|(_, n)| **n > 1)
+  {
+    findings.push(Finding::new(
+      "duplicate-id",
+      Severity::Error,
+      None,
+      format!(
+        "id {id} is used by {count} \
+         open tasks"
+      ),
+      "give the later copies the next \
+       free ids",
+      true
+    ));
+  }
+
+  for task in &all {
+    if !is_open(task)
+      && task.end.is_none()
+    {
+      findings.push(Finding::new(
+        "missing-end",
+        Severity::Warning,
+        Some(task.uuid),
+        format!(
+          "{:?} task {} has no end \
+           date",
+          task.status,
+          short(task.uuid)
+        ),
+        "use its modified date",
+        true
+      ));
+    }
+
+    for dep in &task.depends {
+      if *dep == task.uuid {
+        findings.push(Finding::new(
+          "dangling-depends",
+          Severity::Warning,
+          Some(task.uuid),
+          format!(
+            "{} depends on itself",
+            short(task.uuid)
+          ),
+          "drop the dependency",
+          true
+        ));
+      } else if !by_uuid
+        .contains_key(dep)
+      {
+        findings.push(Finding::new(
+          "dangling-depends",
+          Severity::Warning,
+          Some(task.uuid),
+          format!(
+            "{} depends on missing \
+             task {dep}",
+            short(task.uuid)
+          ),
+          "drop the dependency",
+          true
+        ));
+      }
+    }
+
+    if let Some(parent) = task.parent {
+      let problem = match by_uuid
+        .get(&parent)
+      {
+        | _ if parent == task.uuid => {
+          Some("is its own parent")
+        }
+        | None => {
+          Some("has a missing parent")
+        }
+        | Some(found)
+          if found.status
+            == Status::Deleted =>
+        {
+          Some("has a deleted parent")
+        }
+        | Some(_) => None
+      };
+      if let Some(problem) = problem {
+        findings.push(Finding::new(
+          "orphaned-subtask",
+          Severity::Warning,
+          Some(task.uuid),
+          format!(
+            "{} {problem} ({})",
+            short(task.uuid),
+            short(parent)
+          ),
+          "clear the parent link",
+          true
+        ));
+      }
+    }
+
+    for name in date_udas {
+      if let Some(value) =
+        task.extra.get(name)
+        && !is_store_date(value)
+      {
+        let parsed = value
+          .as_str()
+          .and_then(|raw| {
+            parse_date_expr(
+              raw,
+              task.modified
+            )
+            .ok()
+          });
+        findings.push(Finding::new(
+          "bad-date",
+          Severity::Warning,
+          Some(task.uuid),
+          format!(
+            "{} has {name}={value}, \
+             which is not a stored \
+             date",
+            short(task.uuid)
+          ),
+          match parsed {
+            | Some(date) => {
+              format!(
+                "rewrite it as {}",
+                date.format(
+                  STORE_DATE_FORMAT
+                )
+              )
+            }
+            | None => {
+              format!(
+                "set {name} again \
+                 with `task {} modify \
+                 {name}:<date>`",
+                short(task.uuid)
+              )
+            }
+          },
+          parsed.is_some()
+        ));
+      }
+    }
+  }
+
+  findings.extend(recurrence_findings(
+    &all, &by_uuid
+  ));
+  findings.sort_by(|a, b| {
+    a.severity
+      .cmp(&b.severity)
+      .then(a.check.cmp(b.check))
+      .then(a.uuid.cmp(&b.uuid))
+  });
+  findings
+}
+
+fn recurrence_findings(
+  all: &[&Task],
+  by_uuid: &HashMap<Uuid, &Task>
+) -> Vec<Finding> {
+  let mut findings = Vec::new();
+  let mut open_by_series: BTreeMap<
+    Uuid,
+    Vec<Uuid>
+  > = BTreeMap::new();
+  for task in all {
+    let Some(raw) = tag_value(
+      &task.tags,
+      RECUR_SERIES_TAG_KEY
+    ) else {
+      if is_open(task)
+        && tag_value(
+          &task.tags,
+          RECUR_TAG_KEY
+        )
+        .is_some()
+      {
+        open_by_series
+          .entry(task.uuid)
+          .or_default()
+          .push(task.uuid);
+      }
+      continue;
+    };
+    let Ok(series) =
+      Uuid::parse_str(raw)
+    else {
+      findings.push(Finding::new(
+        "orphaned-recurrence",
+        Severity::Warning,
+        Some(task.uuid),
+        format!(
+          "{} has an unreadable \
+           series tag \
+           {RECUR_SERIES_TAG_KEY}:\
+           {raw}",
+          short(task.uuid)
+        ),
+        "drop the series tags; the \
+         task starts a new series",
+        true
+      ));
+      continue;
+    };
+    if tag_value(
+      &task.tags,
+      RECUR_TAG_KEY
+    )
+    .is_none()
+    {
+      findings.push(Finding::new(
+        "orphaned-recurrence",
+        Severity::Warning,
+        Some(task.uuid),
+        format!(
+          "{} belongs to series {} \
+           but no longer recurs",
+          short(task.uuid),
+          short(series)
+        ),
+        "drop the leftover series tags",
+        true
+      ));
+      continue;
+    }
+    if is_open(task) {
+      open_by_series
+        .entry(series)
+        .or_default()
+        .push(task.uuid);
+    }
+  }
+  for (series, open) in open_by_series {
+    // The origin task counts as an
+    // instance of its own series.
+    let mut open: BTreeSet<Uuid> =
+      open.into_iter().collect();
+    if by_uuid.get(&series).is_some_and(
+      |origin| is_open(origin)
+    ) {
+      open.insert(series);
+    }
+    if open.len() > 1 {
+      findings.push(Finding::new(
+        "duplicate-recurrence",
+        Severity::Warning,
+        Some(series),
+        format!(
+          "series {} has {} open \
+           instances ({})",
+          short(series),
+          open.len(),
+          open
+            .iter()
+            .map(|uuid| short(*uuid))
+            .collect::<Vec<_>>()
+            .join(", ")
+        ),
+        "complete or delete all but \
+         one; no new instance is \
+         spawned while another is open",
+        false
+      ));
+    }
+  }
+  findings
+}
+
+fn is_store_date(
+  value: &serde_json::Value
+) -> bool {
+  value.as_str().is_some_and(|raw| {
+    chrono::NaiveDateTime::parse_from_str(
+      raw,
+      STORE_DATE_FORMAT
+    )
+    .is_ok()
+  })
+}
+
+/// Findings for lines sitting in
+/// `corrupt.data`.
+pub fn quarantine_findings(
+  quarantined: &[QuarantinedLine]
+) -> Vec<Finding> {
+  quarantined
+    .iter()
+    .map(|entry| {
+      let task_file = matches!(
+        entry.file.as_str(),
+        "pending.data"
+          | "completed.data"
+      );
+      let parses = task_file
+        && serde_json::from_str::<Task>(
+          &entry.raw
+        )
+        .is_ok();
+      Finding::new(
+        "quarantined",
+        Severity::Error,
+        None,
+        format!(
+          "{} line {} could not be \
+           read: {}",
+          entry.file,
+          entry.line,
+          entry.error
+        ),
+        if parses {
+          "it parses now; restore it \
+           to its data file"
+        } else if task_file {
+          "edit the line in \
+           corrupt.data and rerun with \
+           --fix to restore it, or \
+           delete corrupt.data to \
+           discard it"
+        } else {
+          "journal lines are not \
+           restored; delete \
+           corrupt.data to discard it"
+        },
+        parses
+      )
+    })
+    .collect()
+}
+
+/// Timezone settings that are ignored
+/// at runtime, or missing.
+pub fn timezone_findings()
+-> Vec<Finding> {
+  crate::datetime::timezone_config_issues()
+    .into_iter()
+    .map(|issue| {
+      Finding::new(
+        "timezone",
+        Severity::Warning,
+        None,
+        issue,
+        "set it to an IANA zone such as \
+         Europe/Berlin",
+        false
+      )
+    })
+    .collect()
+}
+
+/// A `[time.business]` table that date
+/// math ignores because it is invalid.
+pub fn business_calendar_findings()
+-> Vec<Finding> {
+  crate::datetime::work_calendar_issue()
+    .into_iter()
+    .map(|issue| {
+      Finding::new(
+        "business_calendar",
+        Severity::Warning,
+        None,
+        issue,
+        "fix the table; until then \
+         the defaults apply: business \
+         hours off, Saturday/Sunday \
+         weekend, no holidays",
+        false
+      )
+    })
+    .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct RepairSummary {
+  pub fixed:    usize,
+  /// Quarantined lines moved back into
+  /// their data files.
+  pub restored: usize
+}
+
+/// Applies every fixable finding of
+/// `diagnose` (and restores quarantined
+/// task lines that parse again),
+/// leaving the rest alone. Quarantined
+/// lines that were restored are removed
+/// from `quarantined`.
+pub fn repair(
+  pending: &mut Vec<Task>,
+  completed: &mut Vec<Task>,
+  quarantined: &mut Vec<
+    QuarantinedLine
+  >,
+  date_udas: &[String]
+) -> RepairSummary {
+  let mut summary =
+    RepairSummary::default();
+
+  quarantined.retain(|entry| {
+    let target = match entry
+      .file
+      .as_str()
+    {
+      | "pending.data" => &mut *pending,
+      | "completed.data" => {
+        &mut *completed
+      }
+      | _ => return true
+    };
+    match serde_json::from_str::<Task>(
+      &entry.raw
+    ) {
+      | Ok(task) => {
+        target.push(task);
+        summary.restored += 1;
+        false
+      }
+      | Err(_) => true
+    }
+  });
+
+  // Duplicate uuids: keep the newest
+  // copy, preferring pending.data on a
+  // tie.
+  let mut newest: HashMap<
+    Uuid,
+    (DateTime<Utc>, bool)
+  > = HashMap::new();
+  for (task, in_pending) in pending
+    .iter()
+    .map(|task| (task, true))
+    .chain(
+      completed
+        .iter()
+        .map(|task| (task, false))
+    )
+  {
+    let key =
+      (task.modified, in_pending);
+    newest
+      .entry(task.uuid)
+      .and_modify(|best| {
+        if key > *best {
+          *best = key;
+        }
+      })
+      .or_insert(key);
+  }
+  for (tasks, in_pending) in [
+    (&mut *pending, true),
+    (&mut *completed, false)
+  ] {
+    let mut kept = BTreeSet::new();
+    let before = tasks.len();
+    tasks.retain(|task| {
+      newest.get(&task.uuid)
+        == Some(&(
+          task.modified,
+          in_pending
+        ))
+        && kept.insert(task.uuid)
+    });
+    summary.fixed +=
+      before - tasks.len();
+  }
+
+  // Misfiled tasks.
+  let (stay, to_completed): (
+    Vec<_>,
+    Vec<_>
+  ) = std::mem::take(pending)
+    .into_iter()
+    .partition(|task| {
+      belongs_in_pending(&task.status)
+    });
+  let (to_pending, stay_completed): (
+    Vec<_>,
+    Vec<_>
+  ) = std::mem::take(completed)
+    .into_iter()
+    .partition(is_open);
+  summary.fixed += to_completed.len()
+    + to_pending.len();
+  *pending = stay;
+  pending.extend(to_pending);
+  *completed = stay_completed;
+  completed.extend(to_completed);
+
+  let known: BTreeSet<Uuid> = pending
+    .iter()
+    .chain(completed.iter())
+    .map(|task| task.uuid)
+    .collect();
+  let deleted: BTreeSet<Uuid> = pending
+    .iter()
+    .filter(|task| {
+      task.status == Status::Deleted
+    })
+    .map(|task| task.uuid)
+    .collect();
+
+  for task in pending
+    .iter_mut()
+    .chain(completed.iter_mut())
+  {
+    if !is_open(task) {
+      if task.end.is_none() {
+        task.end = Some(task.modified);
+        summary.fixed += 1;
+      }
+      task.id = None;
+    }
+
+    let uuid = task.uuid;
+    let before = task.depends.len();
+    task.depends.retain(|dep| {
+      *dep != uuid
+        && known.contains(dep)
+    });
+    summary.fixed +=
+      before - task.depends.len();
+
+    if let Some(parent) = task.parent
+      && (parent == uuid
+        || !known.contains(&parent)
+        || deleted.contains(&parent))
+    {
+      task.parent = None;
+      summary.fixed += 1;
+    }
+
+    if let Some(raw) = tag_value(
+      &task.tags,
+      RECUR_SERIES_TAG_KEY
+    ) && (Uuid::parse_str(raw)
+      .is_err()
+      || tag_value(
+        &task.tags,
+        RECUR_TAG_KEY
+      )
+      .is_none())
+    {
+      task.tags.retain(|tag| {
+        [
+          RECUR_SERIES_TAG_KEY,
+          RECUR_LEFT_TAG_KEY,
+          RECUR_COUNT_TAG_KEY
+        ]
+        .iter()
+        .all(|key| {
+          tag_value(
+            std::slice::from_ref(tag),
+            key
+          )
+          .is_none()
+        })
+      });
+      summary.fixed += 1;
+    }
+
+    for name in date_udas {
+      let Some(value) =
+        task.extra.get(name)
+      else {
+        continue;
+      };
+      if is_store_date(value) {
+        continue;
+      }
+      if let Some(date) =
+        value.as_str().and_then(|raw| {
+          parse_date_expr(
+            raw,
+            task.modified
+          )
+          .ok()
+        })
+      {
+        task.extra.insert(
+          name.clone(),
+          serde_json::Value::String(
+            date
+              .format(STORE_DATE_FORMAT)
+              .to_string()
+          )
+        );
+        summary.fixed += 1;
+      }
+    }
+  }
+
+  // Open tasks without an id, or
+  // sharing one, get the next free ids
+  // in entry order.
+  let mut used = BTreeSet::new();
+  let mut needs_id = Vec::new();
+  let mut open: Vec<&mut Task> =
+    pending
+      .iter_mut()
+      .filter(|task| is_open(task))
+      .collect();
+  open.sort_by_key(|task| task.entry);
+  for task in &mut open {
+    match task.id {
+      | Some(id) if used.insert(id) => {
+      }
+      | _ => needs_id.push(task.uuid)
+    }
+  }
+  let mut next =
+    used.last().copied().unwrap_or(0);
+  for task in open {
+    if needs_id.contains(&task.uuid) {
+      next += 1;
+      task.id = Some(next);
+      summary.fixed += 1;
+    }
+  }
+
+  summary
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn task(
+    description: &str,
+    id: u64
+  ) -> Task {
+    Task::new_pending(
+      description.to_string(),
+      Utc::now(),
+      id
+    )
+  }
+
+  fn checks(
+    findings: &[Finding]
+  ) -> Vec<&'static str> {
+    findings
+      .iter()
+      .map(|finding| finding.check)
+      .collect()
+  }
+
+  #[test]
+  fn a_healthy_store_has_no_findings() {
+    let parent = task("parent", 1);
+    let mut child = task("child", 2);
+    child.parent = Some(parent.uuid);
+    child.depends = vec![parent.uuid];
+    let mut done = task("done", 3);
+    done.status = Status::Completed;
+    done.id = None;
+    done.end = Some(Utc::now());
+
+    assert!(
+      diagnose(
+        &[parent, child],
+        &[done],
+        &[]
+      )
+      .is_empty()
+    );
+  }
+
+  #[test]
+  fn broken_invariants_are_reported_and_repaired()
+   {
+    let mut a = task("a", 1);
+    let mut b = task("b", 1);
+    let missing = Uuid::new_v4();
+    a.depends = vec![missing];
+    b.parent = Some(missing);
+    b.tags = vec![format!(
+      "{RECUR_SERIES_TAG_KEY}:nope"
+    )];
+    a.extra.insert(
+      "reviewed".to_string(),
+      serde_json::Value::String(
+        "2026-01-05".to_string()
+      )
+    );
+    let mut misfiled = task("done", 4);
+    misfiled.status = Status::Completed;
+    let mut stale_copy = a.clone();
+    stale_copy.modified -=
+      chrono::Duration::hours(1);
+    stale_copy.status =
+      Status::Completed;
+
+    let udas =
+      vec!["reviewed".to_string()];
+    let mut pending =
+      vec![a.clone(), b, misfiled];
+    let mut completed =
+      vec![stale_copy];
+    let found = checks(&diagnose(
+      &pending, &completed, &udas
+    ));
+    for expected in [
+      "duplicate-uuid",
+      "misfiled",
+      "duplicate-id",
+      "missing-end",
+      "dangling-depends",
+      "orphaned-subtask",
+      "orphaned-recurrence",
+      "bad-date"
+    ] {
+      assert!(
+        found.contains(&expected),
+        "{expected} missing from \
+         {found:?}"
+      );
+    }
+
+    let mut quarantined = vec![
+      QuarantinedLine {
+        file:        "pending.data"
+          .to_string(),
+        line:        3,
+        raw:
+          serde_json::to_string(&task(
+            "restored", 9
+          ))
+          .expect("serialize"),
+        error:       "was broken"
+          .to_string(),
+        quarantined: Utc::now()
+      },
+      QuarantinedLine {
+        file:        "pending.data"
+          .to_string(),
+        line:        4,
+        raw:         "{oops"
+          .to_string(),
+        error:       "still broken"
+          .to_string(),
+        quarantined: Utc::now()
+      },
+    ];
+    let summary = repair(
+      &mut pending,
+      &mut completed,
+      &mut quarantined,
+      &udas
+    );
+    assert!(summary.fixed > 0);
+    assert_eq!(summary.restored, 1);
+    assert_eq!(quarantined.len(), 1);
+    assert!(
+      diagnose(
+        &pending, &completed, &udas
+      )
+      .is_empty(),
+      "{:?}",
+      diagnose(
+        &pending, &completed, &udas
+      )
+    );
+    let kept = pending
+      .iter()
+      .find(|task| task.uuid == a.uuid)
+      .expect("newest copy kept");
+    assert!(kept.depends.is_empty());
+    assert!(is_store_date(
+      &kept.extra["reviewed"]
+    ));
+  }
+
+  #[test]
+  fn two_open_instances_of_a_series_need_a_decision()
+   {
+    let mut origin = task("water", 1);
+    origin.tags = vec![format!(
+      "{RECUR_TAG_KEY}:daily"
+    )];
+    let mut next = task("water", 2);
+    next.tags = vec![
+      format!("{RECUR_TAG_KEY}:daily"),
+      format!(
+        "{RECUR_SERIES_TAG_KEY}:{}",
+        origin.uuid
+      ),
+    ];
+
+    let findings = diagnose(
+      &[origin, next],
+      &[],
+      &[]
+    );
+    assert_eq!(
+      checks(&findings),
+      vec!["duplicate-recurrence"]
+    );
+    assert!(!findings[0].fixable);
+  }
+}
@@ -0,0 +1,404 @@
+//! Mapping between core `Task`s and
+//! the shared `TaskDto` / `TaskPatch`
+//! types, so the GUI and `task rpc`
+//! present tasks the same way.
+
+use std::collections::HashMap;
+
+use chrono::{
+  DateTime,
+  NaiveTime,
+  Utc
+};
+use rivet_gui_shared::{
+  TaskDto,
+  TaskPatch,
+  TaskPriority,
+  TaskStatus
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::datetime::{
+  DefaultTimes,
+  parse_date_expr_at
+};
+use crate::exit::{
+  CommandError,
+  usage_error
+};
+use crate::task::{
+  Status,
+  Task
+};
+
+/// Extra key a task's long-form
+/// `description` is kept under; the
+/// DTO `title` is the task description.
+pub const DETAIL_KEY: &str =
+  "rivet_description";
+
+/// Format of every timestamp in a DTO.
+pub const DTO_DATE_FORMAT: &str =
+  "%Y%m%dT%H%M%SZ";
+
+pub fn format_dto_date(
+  date: DateTime<Utc>
+) -> String {
+  date
+    .format(DTO_DATE_FORMAT)
+    .to_string()
+}
+
+/// Applies the fields `patch` sets.
+/// A task waiting on a date that is no
+/// longer in the future becomes
+/// pending again.
+pub fn apply_patch(
+  task: &mut Task,
+  patch: TaskPatch,
+  now: DateTime<Utc>,
+  times: &DefaultTimes
+) -> anyhow::Result<()> {
+  if let Some(title) = patch.title {
+    let title = title.trim();
+    if title.is_empty() {
+      return Err(CommandError::usage(
+        "task title is required"
+      ));
+    }
+    task.description =
+      title.to_string();
+  }
+  if let Some(description) =
+    patch.description
+  {
+    set_detail(task, &description);
+  }
+  if let Some(project) = patch.project {
+    task.project = project;
+  }
+  if let Some(tags) = patch.tags {
+    task.tags = tags;
+  }
+  if let Some(priority) = patch.priority
+  {
+    task.priority =
+      priority.map(priority_to_core);
+  }
+  if let Some(due) = patch.due {
+    task.due = parse_date(
+      due.as_deref(),
+      now,
+      times.due
+    )?;
+  }
+  if let Some(wait) = patch.wait {
+    task.wait = parse_date(
+      wait.as_deref(),
+      now,
+      times.wait
+    )?;
+  }
+  if let Some(scheduled) =
+    patch.scheduled
+  {
+    task.scheduled = parse_date(
+      scheduled.as_deref(),
+      now,
+      times.scheduled
+    )?;
+  }
+  if let Some(contact) = patch.contact {
+    task.contact = contact;
+  }
+  if task.status == Status::Waiting
+    && !task
+      .wait
+      .is_some_and(|wait| wait > now)
+  {
+    task.status = Status::Pending;
+  }
+  Ok(())
+}
+
+/// Parses an optional date expression,
+/// reporting a bad one as a usage
+/// error.
+pub fn parse_date(
+  value: Option<&str>,
+  now: DateTime<Utc>,
+  time_of_day: NaiveTime
+) -> anyhow::Result<Option<DateTime<Utc>>>
+{
+  value
+    .map(|value| {
+      parse_date_expr_at(
+        value,
+        now,
+        time_of_day
+      )
+      .map_err(usage_error)
+    })
+    .transpose()
+}
+
+/// The long-form description, if the
+/// task has a non-blank one.
+pub fn detail(
+  task: &Task
+) -> Option<String> {
+  task
+    .extra
+    .get(DETAIL_KEY)
+    .and_then(Value::as_str)
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(ToString::to_string)
+}
+
+/// Stores `description` trimmed, or
+/// drops the key when it is blank.
+pub fn set_detail(
+  task: &mut Task,
+  description: &str
+) {
+  let trimmed = description.trim();
+  if trimmed.is_empty() {
+    task.extra.remove(DETAIL_KEY);
+  } else {
+    task.extra.insert(
+      DETAIL_KEY.to_string(),
+      Value::String(
+        trimmed.to_string()
+      )
+    );
+  }
+}
+
+/// Non-deleted subtasks per parent.
+pub fn child_index<'a>(
+  tasks: impl IntoIterator<Item = &'a Task>
+) -> HashMap<Uuid, Vec<Uuid>> {
+  let mut index: HashMap<
+    Uuid,
+    Vec<Uuid>
+  > = HashMap::new();
+  for task in tasks {
+    if task.status == Status::Deleted {
+      continue;
+    }
+    if let Some(parent) = task.parent {
+      index
+        .entry(parent)
+        .or_default()
+        .push(task.uuid);
+    }
+  }
+  index
+}
+
+/// The status a view shows: pending
+/// tasks with a future `wait` count as
+/// waiting.
+pub fn view_status(
+  task: &Task,
+  now: DateTime<Utc>
+) -> TaskStatus {
+  match task.status {
+    | Status::Pending
+      if task.is_waiting(now) =>
+    {
+      TaskStatus::Waiting
+    }
+    | Status::Pending => {
+      TaskStatus::Pending
+    }
+    | Status::Completed => {
+      TaskStatus::Completed
+    }
+    | Status::Deleted => {
+      TaskStatus::Deleted
+    }
+    | Status::Waiting => {
+      TaskStatus::Waiting
+    }
+    | Status::Archived => {
+      TaskStatus::Archived
+    }
+  }
+}
+
+pub fn priority_to_core(
+  priority: TaskPriority
+) -> String {
+  match priority {
+    | TaskPriority::Low => "L",
+    | TaskPriority::Medium => "M",
+    | TaskPriority::High => "H"
+  }
+  .to_string()
+}
+
+/// Also accepts the spelled-out forms
+/// older GUI builds wrote.
+pub fn priority_from_core(
+  priority: Option<&str>
+) -> Option<TaskPriority> {
+  match priority? {
+    | "L" | "low" => {
+      Some(TaskPriority::Low)
+    }
+    | "M" | "med" | "medium" => {
+      Some(TaskPriority::Medium)
+    }
+    | "H" | "high" => {
+      Some(TaskPriority::High)
+    }
+    | _ => None
+  }
+}
+
+pub fn task_to_dto(
+  task: Task,
+  children: Vec<Uuid>,
+  now: DateTime<Utc>
+) -> TaskDto {
+  let focus_seconds =
+    task.focus_seconds();
+  TaskDto {
+    uuid: task.uuid,
+    id: task.id,
+    status: view_status(&task, now),
+    description: detail(&task)
+      .unwrap_or_default(),
+    title: task.description,
+    priority: priority_from_core(
+      task.priority.as_deref()
+    ),
+    project: task.project,
+    tags: task.tags,
+    due: task.due.map(format_dto_date),
+    wait: task
+      .wait
+      .map(format_dto_date),
+    scheduled: task
+      .scheduled
+      .map(format_dto_date),
+    created: Some(format_dto_date(
+      task.entry
+    )),
+    modified: Some(format_dto_date(
+      task.modified
+    )),
+    completed: task
+      .end
+      .map(format_dto_date),
+    focus_seconds,
+    started: task
+      .start
+      .map(format_dto_date),
+    parent: task.parent,
+    children,
+    contact: task.contact
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{
+    Duration,
+    Utc
+  };
+  use rivet_gui_shared::{
+    TaskPatch,
+    TaskPriority,
+    TaskStatus
+  };
+
+  use super::{
+    apply_patch,
+    detail,
+    task_to_dto
+  };
+  use crate::datetime::DefaultTimes;
+  use crate::task::{
+    Status,
+    Task
+  };
+
+  #[test]
+  fn patches_round_trip_through_the_dto()
+   {
+    let now = Utc::now();
+    let mut task = Task::new_pending(
+      "Call mom".to_string(),
+      now,
+      1
+    );
+    task.status = Status::Waiting;
+    task.wait =
+      Some(now + Duration::days(1));
+    apply_patch(
+      &mut task,
+      TaskPatch {
+        description: Some(
+          "  about dinner ".to_string()
+        ),
+        priority: Some(Some(
+          TaskPriority::High
+        )),
+        wait: Some(None),
+        ..TaskPatch::default()
+      },
+      now,
+      &DefaultTimes::default()
+    )
+    .expect("patch");
+    assert_eq!(
+      detail(&task).as_deref(),
+      Some("about dinner")
+    );
+    assert_eq!(
+      task.status,
+      Status::Pending
+    );
+
+    let dto = task_to_dto(
+      task,
+      Vec::new(),
+      now
+    );
+    assert_eq!(dto.title, "Call mom");
+    assert_eq!(
+      dto.description,
+      "about dinner"
+    );
+    assert_eq!(
+      dto.priority,
+      Some(TaskPriority::High)
+    );
+    assert_eq!(
+      dto.status,
+      TaskStatus::Pending
+    );
+
+    let mut blank = Task::new_pending(
+      "x".to_string(),
+      now,
+      2
+    );
+    assert!(
+      apply_patch(
+        &mut blank,
+        TaskPatch {
+          title: Some(" ".to_string()),
+          ..TaskPatch::default()
+        },
+        now,
+        &DefaultTimes::default()
+      )
+      .is_err()
+    );
+  }
+}
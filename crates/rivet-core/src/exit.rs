@@ -0,0 +1,210 @@
+use std::fmt;
+
+use crate::config::Config;
+
+/// The command ran to completion.
+pub const EXIT_SUCCESS: i32 = 0;
+/// Any failure without a more specific
+/// code: I/O, bad data, hook refusals.
+pub const EXIT_FAILURE: i32 = 1;
+/// The command line could not be
+/// understood; same as clap's own
+/// argument errors.
+pub const EXIT_USAGE: i32 = 2;
+/// The filter selected no tasks to act
+/// on or report. `exit.nomatch`
+/// overrides it; Taskwarrior uses 1.
+pub const EXIT_NO_MATCH: i32 = 3;
+
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum ErrorKind {
+  Usage,
+  NoMatch
+}
+
+impl ErrorKind {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      | Self::Usage => "usage",
+      | Self::NoMatch => "no_match"
+    }
+  }
+}
+
+/// Error carrying the exit code it
+/// should end the process with. Other
+/// errors exit with `EXIT_FAILURE`.
+#[derive(Debug)]
+pub struct CommandError {
+  kind:    ErrorKind,
+  message: String,
+  code:    i32
+}
+
+impl CommandError {
+  pub fn usage(
+    message: impl Into<String>
+  ) -> anyhow::Error {
+    anyhow::Error::new(Self {
+      kind:    ErrorKind::Usage,
+      message: message.into(),
+      code:    EXIT_USAGE
+    })
+  }
+
+  pub fn no_match() -> anyhow::Error {
+    anyhow::Error::new(Self {
+      kind:    ErrorKind::NoMatch,
+      message: "No matches."
+        .to_string(),
+      code:    EXIT_NO_MATCH
+    })
+  }
+
+  pub fn kind(&self) -> ErrorKind {
+    self.kind
+  }
+}
+
+impl fmt::Display for CommandError {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter<'_>
+  ) -> fmt::Result {
+    f.write_str(&self.message)
+  }
+}
+
+impl std::error::Error
+  for CommandError
+{
+}
+
+/// Re-labels a parse failure as a usage
+/// error, keeping its message.
+pub fn usage_error(
+  err: anyhow::Error
+) -> anyhow::Error {
+  if err.is::<CommandError>() {
+    return err;
+  }
+  CommandError::usage(format!(
+    "{err:#}"
+  ))
+}
+
+/// Applies `exit.nomatch` to a no-match
+/// error; values outside 0-255 are
+/// ignored.
+pub fn apply_config(
+  mut err: anyhow::Error,
+  cfg: &Config
+) -> anyhow::Error {
+  let configured = cfg
+    .get("exit.nomatch")
+    .and_then(|value| {
+      value.trim().parse::<u8>().ok()
+    });
+  if let Some(code) = configured
+    && let Some(command_err) =
+      err.downcast_mut::<CommandError>()
+    && command_err.kind
+      == ErrorKind::NoMatch
+  {
+    command_err.code = i32::from(code);
+  }
+  err
+}
+
+/// Exit status for an error returned by
+/// `rivet_core::run`.
+pub fn exit_code(
+  err: &anyhow::Error
+) -> i32 {
+  err
+    .chain()
+    .find_map(|cause| {
+      cause
+        .downcast_ref::<CommandError>()
+    })
+    .map_or(
+      EXIT_FAILURE,
+      |command_err| command_err.code
+    )
+}
+
+#[cfg(test)]
+mod tests {
+  use anyhow::{
+    Context,
+    anyhow
+  };
+
+  use super::{
+    CommandError,
+    EXIT_FAILURE,
+    EXIT_NO_MATCH,
+    EXIT_USAGE,
+    apply_config,
+    exit_code,
+    usage_error
+  };
+  use crate::config::Config;
+
+  #[test]
+  fn exit_codes_follow_the_error_kind()
+  {
+    assert_eq!(
+      exit_code(&anyhow!("disk full")),
+      EXIT_FAILURE
+    );
+    assert_eq!(
+      exit_code(&usage_error(anyhow!(
+        "bad date"
+      ))),
+      EXIT_USAGE
+    );
+    let wrapped = Err::<(), _>(
+      CommandError::no_match()
+    )
+    .context("while listing")
+    .expect_err("no match");
+    assert_eq!(
+      exit_code(&wrapped),
+      EXIT_NO_MATCH
+    );
+  }
+
+  #[test]
+  fn exit_nomatch_overrides_only_no_match_errors()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let taskrc =
+      temp.path().join("taskrc");
+    std::fs::write(
+      &taskrc,
+      "exit.nomatch=1\n"
+    )
+    .expect("write taskrc");
+    let cfg =
+      Config::load(Some(&taskrc))
+        .expect("load config");
+    assert_eq!(
+      exit_code(&apply_config(
+        CommandError::no_match(),
+        &cfg
+      )),
+      1
+    );
+    assert_eq!(
+      exit_code(&apply_config(
+        CommandError::usage("unknown"),
+        &cfg
+      )),
+      EXIT_USAGE
+    );
+  }
+}
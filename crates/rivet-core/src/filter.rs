@@ -5,10 +5,13 @@ use chrono::{
 };
 use tracing::trace;
 
+use crate::datastore::IndexKey;
 use crate::datetime::{
+  format_project_date,
   parse_date_expr,
   to_project_date
 };
+use crate::exit::usage_error;
 use crate::task::{
   Status,
   Task
@@ -18,11 +21,16 @@ use crate::task::{
 pub enum Pred {
   Id(u64),
   Uuid(uuid::Uuid),
+  /// Lowercase hex without hyphens.
+  UuidPrefix(String),
   TagInclude(String),
   TagExclude(String),
   VirtualTagInclude(VirtualTag),
   VirtualTagExclude(VirtualTag),
   ProjectEq(String),
+  /// `contact:<uuid>`; a bare
+  /// `contact:` matches unlinked tasks.
+  ContactEq(Option<uuid::Uuid>),
   StatusEq(Status),
   Waiting,
   DueBefore(DateTime<Utc>),
@@ -36,6 +44,7 @@ pub enum VirtualTag {
   Waiting,
   Completed,
   Deleted,
+  Archived,
   Active,
   Ready,
   Blocked,
@@ -43,9 +52,16 @@ pub enum VirtualTag {
   Due,
   Overdue,
   Today,
-  Tomorrow
+  Tomorrow,
+  Tagged
 }
 
+/// Days ahead of today (project
+/// timezone) that `+DUE` considers
+/// due, matching Taskwarrior's
+/// default `rc.due=7`.
+const DUE_WINDOW_DAYS: u64 = 7;
+
 #[derive(Debug, Clone)]
 enum Expr {
   True,
@@ -82,8 +98,12 @@ impl Filter {
     let tokens = lex_terms(terms);
     let mut parser =
       Parser::new(tokens, now);
-    let expr = parser.parse_expr()?;
-    parser.ensure_end()?;
+    let expr = parser
+      .parse_expr()
+      .map_err(usage_error)?;
+    parser
+      .ensure_end()
+      .map_err(usage_error)?;
 
     Ok(Self {
       expr
@@ -104,14 +124,72 @@ impl Filter {
       return false;
     }
 
-    if task.is_waiting(now)
-            && !expr_has_explicit_status_filter(&self.expr)
-            && !expr_has_identity_selector(&self.expr)
-        {
-            return false;
-        }
+    !self.guard_hides(task, now)
+  }
+
+  /// Waiting and archived tasks stay
+  /// out unless the filter names a
+  /// status or picks tasks by id.
+  fn guard_hides(
+    &self,
+    task: &Task,
+    now: DateTime<Utc>
+  ) -> bool {
+    (task.is_waiting(now)
+      || task.status == Status::Archived)
+      && !expr_has_explicit_status_filter(
+        &self.expr
+      )
+      && !expr_has_identity_selector(
+        &self.expr
+      )
+  }
 
-    true
+  /// Why `matches` hides `task` even
+  /// though its terms hold:
+  /// `status=waiting until 2026-03-01`
+  /// or `status=archived`.
+  pub fn hidden_reason(
+    &self,
+    task: &Task,
+    now: DateTime<Utc>
+  ) -> Option<String> {
+    if !self.guard_hides(task, now) {
+      return None;
+    }
+    if task.status == Status::Archived {
+      return Some(
+        "status=archived".to_string()
+      );
+    }
+    Some(match task.wait {
+      | Some(wait) if wait > now => {
+        format!(
+          "status=waiting until {}",
+          format_project_date(wait)
+        )
+      }
+      | _ => {
+        "status=waiting".to_string()
+      }
+    })
+  }
+
+  /// The terms `task` fails, each with
+  /// what the task has instead (`+work
+  /// (not tagged work)`); an `or` group
+  /// counts as one term. Empty when the
+  /// terms hold.
+  pub fn unmet_terms(
+    &self,
+    task: &Task,
+    now: DateTime<Utc>
+  ) -> Vec<String> {
+    let mut unmet = Vec::new();
+    collect_unmet(
+      &self.expr, task, now, &mut unmet
+    );
+    unmet
   }
 
   #[tracing::instrument(skip(
@@ -125,6 +203,23 @@ impl Filter {
     eval_expr(&self.expr, task, now)
   }
 
+  /// `+tag`, `project:` and `status:`
+  /// terms every match has to satisfy,
+  /// for narrowing a [`TaskIndex`]
+  /// lookup. Terms inside an `or` group
+  /// are left out.
+  ///
+  /// [`TaskIndex`]: crate::datastore::TaskIndex
+  pub fn index_keys(
+    &self
+  ) -> Vec<IndexKey> {
+    let mut keys = Vec::new();
+    collect_index_keys(
+      &self.expr, &mut keys
+    );
+    keys
+  }
+
   pub fn has_explicit_status_filter(
     &self
   ) -> bool {
@@ -140,6 +235,117 @@ impl Filter {
       &self.expr
     )
   }
+
+  /// Fails when a uuid prefix in the
+  /// filter matches more than one of
+  /// `tasks`, listing the candidates.
+  pub fn ensure_unambiguous(
+    &self,
+    tasks: &[Task]
+  ) -> anyhow::Result<()> {
+    let mut prefixes = Vec::new();
+    collect_uuid_prefixes(
+      &self.expr,
+      &mut prefixes
+    );
+    for prefix in prefixes {
+      let candidates: Vec<&Task> =
+        tasks
+          .iter()
+          .filter(|task| {
+            task
+              .uuid
+              .simple()
+              .to_string()
+              .starts_with(prefix)
+          })
+          .collect();
+      if candidates.len() > 1 {
+        let listed = candidates
+          .iter()
+          .map(|task| {
+            let hex = task
+              .uuid
+              .simple()
+              .to_string();
+            let shown = &hex[..(prefix
+              .len()
+              + 4)
+              .min(hex.len())];
+            format!(
+              "{shown} '{}'",
+              task.description
+            )
+          })
+          .collect::<Vec<_>>()
+          .join(", ");
+        return Err(usage_error(
+          anyhow::anyhow!(
+            "ambiguous uuid prefix \
+             '{prefix}' matches {} \
+             tasks: {listed}",
+            candidates.len()
+          )
+        ));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// A filter term selecting by uuid
+/// prefix: 4 to 31 hex digits (hyphens
+/// allowed) with at least one digit and
+/// one letter, so plain numbers stay
+/// ids and hex-only words such as
+/// `decade` stay text.
+pub fn is_uuid_prefix(
+  term: &str
+) -> bool {
+  uuid_prefix(term).is_some()
+}
+
+fn uuid_prefix(
+  term: &str
+) -> Option<String> {
+  let hex: String = term
+    .chars()
+    .filter(|c| *c != '-')
+    .collect::<String>()
+    .to_ascii_lowercase();
+  let ok = (4..32).contains(&hex.len())
+    && !term.starts_with('-')
+    && hex
+      .chars()
+      .all(|c| c.is_ascii_hexdigit())
+    && hex
+      .chars()
+      .any(|c| c.is_ascii_digit())
+    && hex
+      .chars()
+      .any(|c| c.is_ascii_alphabetic());
+  ok.then_some(hex)
+}
+
+fn collect_uuid_prefixes<'a>(
+  expr: &'a Expr,
+  out: &mut Vec<&'a str>
+) {
+  match expr {
+    | Expr::True => {}
+    | Expr::Pred(Pred::UuidPrefix(
+      prefix
+    )) => out.push(prefix),
+    | Expr::Pred(_) => {}
+    | Expr::And(nodes)
+    | Expr::Or(nodes) => {
+      for node in nodes {
+        collect_uuid_prefixes(
+          node, out
+        );
+      }
+    }
+  }
 }
 
 struct Parser {
@@ -371,7 +577,7 @@ fn parse_atom(
     term.strip_prefix('+')
   {
     if let Some(virtual_tag) =
-      parse_virtual_tag(tag)
+      VirtualTag::parse(tag)
     {
       return Ok(
         Pred::VirtualTagInclude(
@@ -387,7 +593,7 @@ fn parse_atom(
     term.strip_prefix('-')
   {
     if let Some(virtual_tag) =
-      parse_virtual_tag(tag)
+      VirtualTag::parse(tag)
     {
       return Ok(
         Pred::VirtualTagExclude(
@@ -407,6 +613,11 @@ fn parse_atom(
   {
     return Ok(Pred::Uuid(uuid));
   }
+  if let Some(prefix) =
+    uuid_prefix(term)
+  {
+    return Ok(Pred::UuidPrefix(prefix));
+  }
 
   if let Some(project) =
     term.strip_prefix("project:")
@@ -416,6 +627,25 @@ fn parse_atom(
     ));
   }
 
+  if let Some(contact) =
+    term.strip_prefix("contact:")
+  {
+    if contact.is_empty() {
+      return Ok(Pred::ContactEq(None));
+    }
+    let uuid =
+      uuid::Uuid::parse_str(contact)
+        .map_err(|_| {
+          anyhow::anyhow!(
+            "contact:{contact} is not \
+             a contact uuid"
+          )
+        })?;
+    return Ok(Pred::ContactEq(Some(
+      uuid
+    )));
+  }
+
   if let Some(status_text) =
     term.strip_prefix("status:")
   {
@@ -439,6 +669,11 @@ fn parse_atom(
             Status::Deleted
           )
         }
+        | "archived" => {
+          Pred::StatusEq(
+            Status::Archived
+          )
+        }
         | "waiting" => Pred::Waiting,
         | _ => {
           Pred::TextContains(
@@ -505,6 +740,13 @@ fn eval_pred(
     | Pred::Uuid(uuid) => {
       task.uuid == *uuid
     }
+    | Pred::UuidPrefix(prefix) => {
+      task
+        .uuid
+        .simple()
+        .to_string()
+        .starts_with(prefix.as_str())
+    }
     | Pred::TagInclude(tag) => {
       task.tags.iter().any(|t| t == tag)
     }
@@ -513,26 +755,19 @@ fn eval_pred(
     }
     | Pred::VirtualTagInclude(
       virtual_tag
-    ) => {
-      eval_virtual_tag(
-        *virtual_tag,
-        task,
-        now
-      )
-    }
+    ) => virtual_tag.matches(task, now),
     | Pred::VirtualTagExclude(
       virtual_tag
     ) => {
-      !eval_virtual_tag(
-        *virtual_tag,
-        task,
-        now
-      )
+      !virtual_tag.matches(task, now)
     }
     | Pred::ProjectEq(project) => {
       task.project.as_deref()
         == Some(project.as_str())
     }
+    | Pred::ContactEq(contact) => {
+      task.contact == *contact
+    }
     | Pred::StatusEq(status) => {
       match status {
         | Status::Pending => {
@@ -571,122 +806,403 @@ fn eval_pred(
   ok
 }
 
-fn eval_virtual_tag(
-  virtual_tag: VirtualTag,
+fn collect_unmet(
+  expr: &Expr,
   task: &Task,
-  now: DateTime<Utc>
-) -> bool {
-  let now_local_date =
-    to_project_date(now);
-
-  match virtual_tag {
-    | VirtualTag::Pending => {
-      task.status == Status::Pending
-        && !task.is_waiting(now)
-    }
-    | VirtualTag::Waiting => {
-      task.is_waiting(now)
+  now: DateTime<Utc>,
+  unmet: &mut Vec<String>
+) {
+  match expr {
+    | Expr::True => {}
+    | Expr::Pred(pred) => {
+      if !eval_pred(pred, task, now) {
+        unmet.push(format!(
+          "{} ({})",
+          pred.term(),
+          pred.miss(task, now)
+        ));
+      }
     }
-    | VirtualTag::Completed => {
-      task.status == Status::Completed
+    | Expr::And(nodes) => {
+      for node in nodes {
+        collect_unmet(
+          node, task, now, unmet
+        );
+      }
     }
-    | VirtualTag::Deleted => {
-      task.status == Status::Deleted
+    | Expr::Or(_) => {
+      if !eval_expr(expr, task, now) {
+        unmet.push(expr_term(expr));
+      }
     }
-    | VirtualTag::Active => {
-      task.status == Status::Pending
-        && !task.is_waiting(now)
-        && task.start.is_some()
+  }
+}
+
+fn expr_term(expr: &Expr) -> String {
+  let join = |nodes: &[Expr], op| {
+    let parts = nodes
+      .iter()
+      .map(expr_term)
+      .collect::<Vec<_>>();
+    format!("({})", parts.join(op))
+  };
+  match expr {
+    | Expr::True => String::new(),
+    | Expr::Pred(pred) => pred.term(),
+    | Expr::And(nodes) => {
+      join(nodes, " and ")
     }
-    | VirtualTag::Ready => {
-      task.status == Status::Pending
-        && !task.is_waiting(now)
-        && task.depends.is_empty()
+    | Expr::Or(nodes) => {
+      join(nodes, " or ")
     }
-    | VirtualTag::Blocked => {
-      !task.depends.is_empty()
+  }
+}
+
+impl Pred {
+  /// The filter term, with dates
+  /// resolved (`due.before:eom` reads
+  /// `due.before:2026-03-31`).
+  fn term(&self) -> String {
+    match self {
+      | Self::Id(id) => id.to_string(),
+      | Self::Uuid(uuid) => {
+        uuid.to_string()
+      }
+      | Self::UuidPrefix(prefix) => {
+        prefix.clone()
+      }
+      | Self::TagInclude(tag) => {
+        format!("+{tag}")
+      }
+      | Self::TagExclude(tag) => {
+        format!("-{tag}")
+      }
+      | Self::VirtualTagInclude(
+        tag
+      ) => {
+        format!("+{}", tag.name())
+      }
+      | Self::VirtualTagExclude(
+        tag
+      ) => {
+        format!("-{}", tag.name())
+      }
+      | Self::ProjectEq(project) => {
+        format!("project:{project}")
+      }
+      | Self::ContactEq(contact) => {
+        format!(
+          "contact:{}",
+          contact
+            .map(|uuid| {
+              uuid.to_string()
+            })
+            .unwrap_or_default()
+        )
+      }
+      | Self::StatusEq(status) => {
+        format!(
+          "status:{}",
+          status_name(status)
+        )
+      }
+      | Self::Waiting => {
+        "status:waiting".to_string()
+      }
+      | Self::DueBefore(at) => {
+        format!(
+          "due.before:{}",
+          format_project_date(*at)
+        )
+      }
+      | Self::DueAfter(at) => {
+        format!(
+          "due.after:{}",
+          format_project_date(*at)
+        )
+      }
+      | Self::TextContains(text) => {
+        format!("'{text}'")
+      }
     }
-    | VirtualTag::Unblocked => {
-      task.depends.is_empty()
+  }
+
+  /// What `task` has instead, for a
+  /// predicate it fails.
+  fn miss(
+    &self,
+    task: &Task,
+    now: DateTime<Utc>
+  ) -> String {
+    match self {
+      | Self::Id(_) => {
+        match task.id {
+          | Some(id) => {
+            format!("id is {id}")
+          }
+          | None => {
+            "task has no id".to_string()
+          }
+        }
+      }
+      | Self::Uuid(_)
+      | Self::UuidPrefix(_) => {
+        format!("uuid is {}", task.uuid)
+      }
+      | Self::TagInclude(tag) => {
+        format!("not tagged {tag}")
+      }
+      | Self::TagExclude(tag) => {
+        format!("tagged {tag}")
+      }
+      | Self::VirtualTagInclude(
+        tag
+      ) => {
+        format!("not {}", tag.name())
+      }
+      | Self::VirtualTagExclude(
+        tag
+      ) => {
+        format!("is {}", tag.name())
+      }
+      | Self::ProjectEq(_) => {
+        format!(
+          "project is {}",
+          task
+            .project
+            .as_deref()
+            .unwrap_or("none")
+        )
+      }
+      | Self::ContactEq(_) => {
+        match task.contact {
+          | Some(contact) => {
+            format!(
+              "contact is {contact}"
+            )
+          }
+          | None => {
+            "no contact".to_string()
+          }
+        }
+      }
+      | Self::StatusEq(_)
+      | Self::Waiting => {
+        let status =
+          if task.is_waiting(now) {
+            "waiting"
+          } else {
+            status_name(&task.status)
+          };
+        format!("status is {status}")
+      }
+      | Self::DueBefore(_)
+      | Self::DueAfter(_) => {
+        match task.due {
+          | Some(due) => {
+            format!(
+              "due {}",
+              format_project_date(due)
+            )
+          }
+          | None => {
+            "no due date".to_string()
+          }
+        }
+      }
+      | Self::TextContains(_) => {
+        "not in the description"
+          .to_string()
+      }
     }
-    | VirtualTag::Due => {
-      task
-        .due
-        .map(|due| {
-          to_project_date(due)
-            <= now_local_date
-        })
-        .unwrap_or(false)
+  }
+}
+
+fn status_name(
+  status: &Status
+) -> &'static str {
+  match status {
+    | Status::Pending => "pending",
+    | Status::Completed => "completed",
+    | Status::Deleted => "deleted",
+    | Status::Waiting => "waiting",
+    | Status::Archived => "archived"
+  }
+}
+
+impl VirtualTag {
+  pub const NAMES: &[&str] = &[
+    "PENDING",
+    "WAITING",
+    "COMPLETED",
+    "DELETED",
+    "ARCHIVED",
+    "ACTIVE",
+    "READY",
+    "BLOCKED",
+    "UNBLOCKED",
+    "DUE",
+    "OVERDUE",
+    "TODAY",
+    "TOMORROW",
+    "TAGGED"
+  ];
+
+  pub fn name(self) -> &'static str {
+    match self {
+      | Self::Pending => "PENDING",
+      | Self::Waiting => "WAITING",
+      | Self::Completed => "COMPLETED",
+      | Self::Deleted => "DELETED",
+      | Self::Archived => "ARCHIVED",
+      | Self::Active => "ACTIVE",
+      | Self::Ready => "READY",
+      | Self::Blocked => "BLOCKED",
+      | Self::Unblocked => "UNBLOCKED",
+      | Self::Due => "DUE",
+      | Self::Overdue => "OVERDUE",
+      | Self::Today => "TODAY",
+      | Self::Tomorrow => "TOMORROW",
+      | Self::Tagged => "TAGGED"
     }
-    | VirtualTag::Overdue => {
-      task
-        .due
-        .map(|due| due < now)
-        .unwrap_or(false)
+  }
+
+  /// Resolves an upper-case virtual tag
+  /// name such as `OVERDUE`.
+  pub fn parse(
+    tag: &str
+  ) -> Option<Self> {
+    match tag {
+      | "PENDING" => {
+        Some(Self::Pending)
+      }
+      | "WAITING" => {
+        Some(Self::Waiting)
+      }
+      | "COMPLETED" => {
+        Some(Self::Completed)
+      }
+      | "DELETED" => {
+        Some(Self::Deleted)
+      }
+      | "ARCHIVED" => {
+        Some(Self::Archived)
+      }
+      | "ACTIVE" => Some(Self::Active),
+      | "READY" => Some(Self::Ready),
+      | "BLOCKED" => {
+        Some(Self::Blocked)
+      }
+      | "UNBLOCKED" => {
+        Some(Self::Unblocked)
+      }
+      | "DUE" => Some(Self::Due),
+      | "OVERDUE" => {
+        Some(Self::Overdue)
+      }
+      | "TODAY" => Some(Self::Today),
+      | "TOMORROW" => {
+        Some(Self::Tomorrow)
+      }
+      | "TAGGED" => Some(Self::Tagged),
+      | _ => None
     }
-    | VirtualTag::Today => {
-      task
-        .due
-        .map(|due| {
+  }
+
+  /// Computes the virtual tag from task
+  /// state. Date-based tags compare
+  /// calendar days in the project
+  /// timezone.
+  pub fn matches(
+    self,
+    task: &Task,
+    now: DateTime<Utc>
+  ) -> bool {
+    let now_local_date =
+      to_project_date(now);
+    let open = !matches!(
+      task.status,
+      Status::Completed
+        | Status::Deleted
+        | Status::Archived
+    );
+
+    match self {
+      | Self::Pending => {
+        task.status == Status::Pending
+          && !task.is_waiting(now)
+      }
+      | Self::Waiting => {
+        task.is_waiting(now)
+      }
+      | Self::Completed => {
+        task.status == Status::Completed
+      }
+      | Self::Deleted => {
+        task.status == Status::Deleted
+      }
+      | Self::Archived => {
+        task.status == Status::Archived
+      }
+      | Self::Active => {
+        task.status == Status::Pending
+          && !task.is_waiting(now)
+          && task.start.is_some()
+      }
+      | Self::Ready => {
+        task.status == Status::Pending
+          && !task.is_waiting(now)
+          && task.depends.is_empty()
+      }
+      | Self::Blocked => {
+        !task.depends.is_empty()
+      }
+      | Self::Unblocked => {
+        task.depends.is_empty()
+      }
+      | Self::Due => {
+        let window_end = now_local_date
+          .checked_add_days(Days::new(
+            DUE_WINDOW_DAYS
+          ))
+          .unwrap_or(now_local_date);
+        open
+          && task.due.is_some_and(
+            |due| {
+              let date =
+                to_project_date(due);
+              date >= now_local_date
+                && date <= window_end
+            }
+          )
+      }
+      | Self::Overdue => {
+        open
+          && task.due.is_some_and(
+            |due| due < now
+          )
+      }
+      | Self::Today => {
+        task.due.is_some_and(|due| {
           to_project_date(due)
             == now_local_date
         })
-        .unwrap_or(false)
-    }
-    | VirtualTag::Tomorrow => {
-      let tomorrow = now_local_date
-        .checked_add_days(Days::new(1))
-        .unwrap_or(now_local_date);
-      task
-        .due
-        .map(|due| {
+      }
+      | Self::Tomorrow => {
+        let tomorrow = now_local_date
+          .checked_add_days(Days::new(
+            1
+          ))
+          .unwrap_or(now_local_date);
+        task.due.is_some_and(|due| {
           to_project_date(due)
             == tomorrow
         })
-        .unwrap_or(false)
-    }
-  }
-}
-
-fn parse_virtual_tag(
-  tag: &str
-) -> Option<VirtualTag> {
-  match tag {
-    | "PENDING" => {
-      Some(VirtualTag::Pending)
-    }
-    | "WAITING" => {
-      Some(VirtualTag::Waiting)
-    }
-    | "COMPLETED" => {
-      Some(VirtualTag::Completed)
-    }
-    | "DELETED" => {
-      Some(VirtualTag::Deleted)
-    }
-    | "ACTIVE" => {
-      Some(VirtualTag::Active)
-    }
-    | "READY" => {
-      Some(VirtualTag::Ready)
-    }
-    | "BLOCKED" => {
-      Some(VirtualTag::Blocked)
-    }
-    | "UNBLOCKED" => {
-      Some(VirtualTag::Unblocked)
-    }
-    | "DUE" => Some(VirtualTag::Due),
-    | "OVERDUE" => {
-      Some(VirtualTag::Overdue)
-    }
-    | "TODAY" => {
-      Some(VirtualTag::Today)
-    }
-    | "TOMORROW" => {
-      Some(VirtualTag::Tomorrow)
+      }
+      | Self::Tagged => {
+        !task.tags.is_empty()
+      }
     }
-    | _ => None
   }
 }
 
@@ -713,6 +1229,43 @@ fn expr_has_explicit_status_filter(
   }
 }
 
+fn collect_index_keys(
+  expr: &Expr,
+  keys: &mut Vec<IndexKey>
+) {
+  match expr {
+    | Expr::Pred(Pred::TagInclude(
+      tag
+    )) => {
+      keys.push(IndexKey::Tag(
+        tag.clone()
+      ));
+    }
+    | Expr::Pred(Pred::ProjectEq(
+      project
+    )) => {
+      keys.push(IndexKey::Project(
+        project.clone()
+      ));
+    }
+    | Expr::Pred(Pred::StatusEq(
+      status
+    )) => {
+      keys.push(IndexKey::Status(
+        status.clone()
+      ));
+    }
+    | Expr::And(nodes) => {
+      for node in nodes {
+        collect_index_keys(node, keys);
+      }
+    }
+    | Expr::True
+    | Expr::Pred(_)
+    | Expr::Or(_) => {}
+  }
+}
+
 fn expr_has_identity_selector(
   expr: &Expr
 ) -> bool {
@@ -721,7 +1274,9 @@ fn expr_has_identity_selector(
     | Expr::Pred(pred) => {
       matches!(
         pred,
-        Pred::Id(_) | Pred::Uuid(_)
+        Pred::Id(_)
+          | Pred::Uuid(_)
+          | Pred::UuidPrefix(_)
       )
     }
     | Expr::And(nodes)
@@ -741,8 +1296,15 @@ mod tests {
     Utc
   };
 
-  use super::Filter;
-  use crate::task::Task;
+  use super::{
+    Filter,
+    VirtualTag,
+    is_uuid_prefix
+  };
+  use crate::task::{
+    Status,
+    Task
+  };
 
   #[test]
   fn boolean_precedence_and_parentheses()
@@ -862,6 +1424,150 @@ mod tests {
     );
   }
 
+  #[test]
+  fn virtual_tags_status_blocked_and_tagged()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 5, 0, 0
+      )
+      .unwrap();
+    let mut completed =
+      Task::new_pending(
+        "completed".to_string(),
+        now,
+        1
+      );
+    completed.status =
+      Status::Completed;
+
+    let mut deleted = Task::new_pending(
+      "deleted".to_string(),
+      now,
+      2
+    );
+    deleted.status = Status::Deleted;
+
+    let mut blocked = Task::new_pending(
+      "blocked".to_string(),
+      now,
+      3
+    );
+    blocked.depends =
+      vec![completed.uuid];
+    blocked.tags =
+      vec!["home".to_string()];
+
+    let parse = |term: &str| {
+      Filter::parse(
+        &[term.to_string()],
+        now
+      )
+      .unwrap()
+    };
+
+    assert!(
+      parse("+COMPLETED")
+        .matches(&completed, now)
+    );
+    assert!(
+      !parse("+COMPLETED")
+        .matches(&deleted, now)
+    );
+    assert!(
+      parse("+DELETED")
+        .matches(&deleted, now)
+    );
+    assert!(
+      !parse("+PENDING")
+        .matches(&deleted, now)
+    );
+    assert!(
+      parse("+BLOCKED")
+        .matches(&blocked, now)
+    );
+    assert!(
+      !parse("+BLOCKED")
+        .matches(&completed, now)
+    );
+    assert!(
+      parse("+TAGGED")
+        .matches(&blocked, now)
+    );
+    assert!(
+      !parse("+TAGGED")
+        .matches(&completed, now)
+    );
+    assert!(
+      parse("-TAGGED")
+        .matches(&completed, now)
+    );
+    assert!(
+      !parse("-TAGGED")
+        .matches(&blocked, now)
+    );
+  }
+
+  #[test]
+  fn virtual_tags_due_and_overdue() {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 5, 0, 0
+      )
+      .unwrap();
+    let with_due =
+      |id: u64, due: Duration| {
+        let mut task =
+          Task::new_pending(
+            format!("task {id}"),
+            now,
+            id
+          );
+        task.due = Some(now + due);
+        task
+      };
+
+    let past =
+      with_due(1, Duration::days(-3));
+    let soon =
+      with_due(2, Duration::days(3));
+    let later =
+      with_due(3, Duration::days(10));
+    let mut done_past =
+      with_due(4, Duration::days(-3));
+    done_past.status =
+      Status::Completed;
+
+    let overdue = Filter::parse(
+      &["+OVERDUE".to_string()],
+      now
+    )
+    .unwrap();
+    let due = Filter::parse(
+      &["+DUE".to_string()],
+      now
+    )
+    .unwrap();
+
+    assert!(
+      overdue.matches(&past, now)
+    );
+    assert!(
+      !overdue.matches(&soon, now)
+    );
+    assert!(
+      !overdue.matches(&done_past, now)
+    );
+
+    assert!(due.matches(&soon, now));
+    assert!(!due.matches(&later, now));
+    assert!(!due.matches(&past, now));
+    assert!(
+      !VirtualTag::Due
+        .matches(&done_past, now)
+    );
+  }
+
   #[test]
   fn id_selector_matches_waiting_task()
   {
@@ -918,4 +1624,223 @@ mod tests {
         )
     );
   }
+
+  #[test]
+  fn contact_term_matches_linked_tasks()
+  {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 12, 0, 0
+      )
+      .unwrap();
+    let contact = uuid::Uuid::new_v4();
+    let mut linked = Task::new_pending(
+      "call back".to_string(),
+      now,
+      1
+    );
+    linked.contact = Some(contact);
+    let unlinked = Task::new_pending(
+      "file taxes".to_string(),
+      now,
+      2
+    );
+    let parse = |term: String| {
+      Filter::parse(&[term], now)
+    };
+
+    let by_contact = parse(format!(
+      "contact:{contact}"
+    ))
+    .expect("filter");
+    assert!(
+      by_contact.matches(&linked, now)
+    );
+    assert!(
+      !by_contact
+        .matches(&unlinked, now)
+    );
+    assert_eq!(
+      by_contact
+        .unmet_terms(&unlinked, now),
+      [format!(
+        "contact:{contact} (no \
+         contact)"
+      )]
+    );
+    let without =
+      parse("contact:".to_string())
+        .expect("filter");
+    assert!(
+      without.matches(&unlinked, now)
+    );
+    assert!(
+      !without.matches(&linked, now)
+    );
+    assert!(
+      parse("contact:bob".to_string())
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn unmet_terms_and_hidden_reason_explain_a_miss()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 12, 0, 0
+      )
+      .unwrap();
+    let mut task = Task::new_pending(
+      "write report".to_string(),
+      now,
+      1
+    );
+    task.project =
+      Some("home".to_string());
+    task.tags =
+      vec!["docs".to_string()];
+    task.wait =
+      Some(now + Duration::days(3));
+    let parse = |terms: &[&str]| {
+      Filter::parse(
+        &terms
+          .iter()
+          .map(ToString::to_string)
+          .collect::<Vec<_>>(),
+        now
+      )
+      .expect("filter")
+    };
+
+    let filter = parse(&[
+      "project:work",
+      "+docs",
+      "(",
+      "+urgent",
+      "or",
+      "-docs",
+      ")",
+      "report"
+    ]);
+    assert_eq!(
+      filter.unmet_terms(&task, now),
+      vec![
+        "project:work (project is \
+         home)",
+        "(+urgent or -docs)"
+      ]
+    );
+    assert_eq!(
+      filter.hidden_reason(&task, now),
+      Some(
+        "status=waiting until \
+         2026-02-19"
+          .to_string()
+      )
+    );
+    assert_eq!(
+      parse(&["status:waiting"])
+        .hidden_reason(&task, now),
+      None
+    );
+
+    let shown = parse(&["+docs"]);
+    task.wait = None;
+    assert!(
+      shown
+        .unmet_terms(&task, now)
+        .is_empty()
+        && shown
+          .hidden_reason(&task, now)
+          .is_none()
+    );
+    assert!(shown.matches(&task, now));
+  }
+
+  #[test]
+  fn uuid_prefix_selects_one_task_or_lists_candidates()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 5, 0, 0
+      )
+      .unwrap();
+    let task = |uuid: &str, id: u64| {
+      let mut task = Task::new_pending(
+        format!("task {id}"),
+        now,
+        id
+      );
+      task.uuid =
+        uuid::Uuid::parse_str(uuid)
+          .unwrap();
+      task
+    };
+    let a = task(
+      "a1b2c3d4-0000-4000-8000-000000000001",
+      1
+    );
+    let b = task(
+      "a1b2ffff-0000-4000-8000-000000000002",
+      2
+    );
+    let tasks = [a.clone(), b.clone()];
+
+    assert!(is_uuid_prefix("A1B2-C3"));
+    assert!(!is_uuid_prefix("decade"));
+    assert!(!is_uuid_prefix("1234"));
+    assert!(!is_uuid_prefix("a1b"));
+
+    let exact = Filter::parse(
+      &["a1b2c3".to_string()],
+      now
+    )
+    .unwrap();
+    assert!(exact.matches(&a, now));
+    assert!(!exact.matches(&b, now));
+    exact
+      .ensure_unambiguous(&tasks)
+      .unwrap();
+
+    let err = Filter::parse(
+      &["a1b2".to_string()],
+      now
+    )
+    .unwrap()
+    .ensure_unambiguous(&tasks)
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains(
+      "ambiguous uuid prefix 'a1b2'"
+    ));
+    assert!(
+      err.contains("a1b2c3d4 'task 1'")
+    );
+    assert!(
+      err.contains("a1b2ffff 'task 2'")
+    );
+
+    for (uuid, shown) in [
+      (
+        "12345678-9abc-4000-8000-000000000003",
+        "123456789a"
+      ),
+      (
+        "abcdefab-cdef-4000-8000-000000000004",
+        "abcdefabcdef4"
+      )
+    ] {
+      let task = task(uuid, 3);
+      assert_eq!(task.short_uuid(), shown);
+      assert!(
+        Filter::parse(
+          &[task.short_uuid()],
+          now
+        )
+        .unwrap()
+        .matches(&task, now)
+      );
+    }
+  }
 }
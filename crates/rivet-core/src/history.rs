@@ -0,0 +1,404 @@
+use std::collections::{
+  BTreeMap,
+  BTreeSet
+};
+
+use chrono::{
+  DateTime,
+  Utc
+};
+use serde::{
+  Deserialize,
+  Serialize
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::datetime::taskwarrior_date_serde;
+use crate::task::{
+  Status,
+  Task
+};
+
+/// Fields left out of recorded changes:
+/// every write bumps `modified`,
+/// working-set ids are renumbered and
+/// the uuid is the entry's own key.
+const UNTRACKED_FIELDS: &[&str] =
+  &["modified", "id", "uuid"];
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Serialize,
+  Deserialize,
+  PartialEq,
+  Eq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryAction {
+  Created,
+  Modified,
+  Completed,
+  Deleted,
+  Reopened,
+  Archived,
+  Removed
+}
+
+impl HistoryAction {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      | Self::Created => "created",
+      | Self::Modified => "modified",
+      | Self::Completed => "completed",
+      | Self::Deleted => "deleted",
+      | Self::Reopened => "reopened",
+      | Self::Archived => "archived",
+      | Self::Removed => "removed"
+    }
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Serialize,
+  Deserialize,
+  PartialEq,
+)]
+pub struct FieldChange {
+  pub field:  String,
+  #[serde(default)]
+  pub before: Value,
+  #[serde(default)]
+  pub after:  Value
+}
+
+/// One line of `history.data`: what a
+/// single command did to one task.
+#[derive(
+  Debug,
+  Clone,
+  Serialize,
+  Deserialize,
+  PartialEq,
+)]
+pub struct HistoryEntry {
+  #[serde(
+    with = "taskwarrior_date_serde"
+  )]
+  pub at:      DateTime<Utc>,
+  pub uuid:    Uuid,
+  pub command: String,
+  pub action:  HistoryAction,
+  #[serde(default)]
+  pub changes: Vec<FieldChange>
+}
+
+/// Renders a recorded value the way
+/// `info` and the dry-run diff do.
+pub fn display_value(
+  value: &Value
+) -> String {
+  match value {
+    | Value::Null => {
+      "(none)".to_string()
+    }
+    | Value::String(text) => {
+      text.clone()
+    }
+    | Value::Array(items)
+      if items.is_empty() =>
+    {
+      "(none)".to_string()
+    }
+    | other => other.to_string()
+  }
+}
+
+fn tracked_fields(
+  task: &Task
+) -> BTreeMap<String, Value> {
+  match serde_json::to_value(task) {
+    | Ok(Value::Object(map)) => {
+      map
+        .into_iter()
+        .filter(|(key, _)| {
+          !UNTRACKED_FIELDS
+            .contains(&key.as_str())
+        })
+        .collect()
+    }
+    | _ => BTreeMap::new()
+  }
+}
+
+fn field_changes(
+  before: Option<&Task>,
+  after: Option<&Task>
+) -> Vec<FieldChange> {
+  let old = before
+    .map(tracked_fields)
+    .unwrap_or_default();
+  let new = after
+    .map(tracked_fields)
+    .unwrap_or_default();
+  let keys: BTreeSet<&String> = old
+    .keys()
+    .chain(new.keys())
+    .collect();
+  keys
+    .into_iter()
+    .filter_map(|key| {
+      let before = old
+        .get(key)
+        .cloned()
+        .unwrap_or(Value::Null);
+      let after = new
+        .get(key)
+        .cloned()
+        .unwrap_or(Value::Null);
+      let empty = |value: &Value| {
+        value.is_null()
+          || value
+            .as_array()
+            .is_some_and(Vec::is_empty)
+      };
+      if before == after
+        || (empty(&before)
+          && empty(&after))
+      {
+        return None;
+      }
+      Some(FieldChange {
+        field: key.clone(),
+        before,
+        after
+      })
+    })
+    .collect()
+}
+
+fn classify(
+  before: &Task,
+  after: &Task
+) -> HistoryAction {
+  let open = |status: &Status| {
+    matches!(
+      status,
+      Status::Pending | Status::Waiting
+    )
+  };
+  match (&before.status, &after.status)
+  {
+    | (old, Status::Completed)
+      if *old != Status::Completed =>
+    {
+      HistoryAction::Completed
+    }
+    | (old, Status::Deleted)
+      if *old != Status::Deleted =>
+    {
+      HistoryAction::Deleted
+    }
+    | (old, Status::Archived)
+      if *old != Status::Archived =>
+    {
+      HistoryAction::Archived
+    }
+    | (old, new)
+      if !open(old) && open(new) =>
+    {
+      HistoryAction::Reopened
+    }
+    | _ => HistoryAction::Modified
+  }
+}
+
+/// Compares two snapshots of the task
+/// list and describes every task that
+/// `command` created, changed or
+/// removed. Tasks whose tracked fields
+/// are unchanged produce no entry.
+pub fn diff_snapshots(
+  command: &str,
+  before: &[Task],
+  after: &[Task],
+  at: DateTime<Utc>
+) -> Vec<HistoryEntry> {
+  let before_by_uuid: BTreeMap<_, _> =
+    before
+      .iter()
+      .map(|task| (task.uuid, task))
+      .collect();
+  let after_uuids: BTreeSet<Uuid> =
+    after
+      .iter()
+      .map(|task| task.uuid)
+      .collect();
+
+  let mut entries = Vec::new();
+  for task in after {
+    let old =
+      before_by_uuid.get(&task.uuid);
+    let changes = field_changes(
+      old.copied(),
+      Some(task)
+    );
+    let action = match old {
+      | None => HistoryAction::Created,
+      | Some(_) if changes.is_empty() =>
+      {
+        continue;
+      }
+      | Some(old) => classify(old, task)
+    };
+    entries.push(HistoryEntry {
+      at,
+      uuid: task.uuid,
+      command: command.to_string(),
+      action,
+      changes
+    });
+  }
+  for task in before {
+    if !after_uuids.contains(&task.uuid)
+    {
+      entries.push(HistoryEntry {
+        at,
+        uuid: task.uuid,
+        command: command.to_string(),
+        action: HistoryAction::Removed,
+        changes: Vec::new()
+      });
+    }
+  }
+  entries
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{
+    TimeZone,
+    Utc
+  };
+  use serde_json::json;
+
+  use super::{
+    HistoryAction,
+    diff_snapshots
+  };
+  use crate::task::{
+    Status,
+    Task
+  };
+
+  #[test]
+  fn diff_snapshots_records_field_changes_per_task()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 12, 0, 0
+      )
+      .unwrap();
+    let kept = Task::new_pending(
+      "kept".to_string(),
+      now,
+      1
+    );
+    let mut edited = Task::new_pending(
+      "edited".to_string(),
+      now,
+      2
+    );
+    let gone = Task::new_pending(
+      "gone".to_string(),
+      now,
+      3
+    );
+    let before = vec![
+      kept.clone(),
+      edited.clone(),
+      gone.clone(),
+    ];
+
+    let mut touched = kept.clone();
+    touched.modified =
+      now + chrono::Duration::hours(1);
+    touched.id = Some(9);
+    edited.status = Status::Completed;
+    edited.end = Some(now);
+    edited.project =
+      Some("home".to_string());
+    let added = Task::new_pending(
+      "added".to_string(),
+      now,
+      4
+    );
+    let after = vec![
+      touched,
+      edited.clone(),
+      added.clone(),
+    ];
+
+    let entries = diff_snapshots(
+      "done", &before, &after, now
+    );
+    let actions: Vec<_> = entries
+      .iter()
+      .map(|entry| {
+        (entry.uuid, entry.action)
+      })
+      .collect();
+    assert_eq!(actions, vec![
+      (
+        edited.uuid,
+        HistoryAction::Completed
+      ),
+      (
+        added.uuid,
+        HistoryAction::Created
+      ),
+      (
+        gone.uuid,
+        HistoryAction::Removed
+      ),
+    ]);
+
+    let fields: Vec<_> = entries[0]
+      .changes
+      .iter()
+      .map(|change| {
+        (
+          change.field.as_str(),
+          change.before.clone(),
+          change.after.clone()
+        )
+      })
+      .collect();
+    assert_eq!(fields, vec![
+      (
+        "end",
+        json!(null),
+        json!("20260301T120000Z")
+      ),
+      (
+        "project",
+        json!(null),
+        json!("home")
+      ),
+      (
+        "status",
+        json!("pending"),
+        json!("completed")
+      ),
+    ]);
+    assert!(
+      entries[1].changes.iter().all(
+        |change| change.field != "tags"
+      )
+    );
+  }
+}
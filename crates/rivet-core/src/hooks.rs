@@ -20,13 +20,16 @@ use tracing::{
   warn
 };
 
+use crate::autotag::AutoTagRules;
 use crate::config::Config;
 use crate::task::Task;
 
 #[derive(Debug, Clone)]
 pub struct HookRunner {
   enabled:   bool,
-  hooks_dir: PathBuf
+  dry_run:   bool,
+  hooks_dir: PathBuf,
+  auto_tags: AutoTagRules
 }
 
 impl HookRunner {
@@ -46,18 +49,78 @@ impl HookRunner {
     );
     Self {
       enabled,
-      hooks_dir
+      dry_run: false,
+      hooks_dir,
+      auto_tags: AutoTagRules::default(
+      )
+    }
+  }
+
+  /// `autotag.*` rules run before the
+  /// on-add and on-modify scripts, even
+  /// when `hooks` is off.
+  pub fn with_auto_tags(
+    mut self,
+    rules: AutoTagRules
+  ) -> Self {
+    self.auto_tags = rules;
+    self
+  }
+
+  /// In a dry run hook scripts are
+  /// skipped, since they are arbitrary
+  /// side effects; `autotag.*` rules
+  /// still apply.
+  pub fn with_dry_run(
+    mut self,
+    dry_run: bool
+  ) -> Self {
+    self.dry_run = dry_run;
+    self
+  }
+
+  pub fn auto_tags(
+    &self
+  ) -> &AutoTagRules {
+    &self.auto_tags
+  }
+
+  /// Whether any on-launch, on-add or
+  /// on-modify script would run if
+  /// hooks were not skipped.
+  pub fn has_scripts(
+    &self
+  ) -> anyhow::Result<bool> {
+    if !self.enabled {
+      return Ok(false);
+    }
+    for event in [
+      "on-launch",
+      "on-add",
+      "on-modify"
+    ] {
+      if !self
+        .list_scripts(event)?
+        .is_empty()
+      {
+        return Ok(true);
+      }
     }
+    Ok(false)
+  }
+
+  fn runs_scripts(&self) -> bool {
+    self.enabled && !self.dry_run
   }
 
   #[instrument(skip(self))]
   pub fn run_on_launch(
     &self
   ) -> anyhow::Result<()> {
-    if !self.enabled {
+    if !self.runs_scripts() {
       debug!(
-        "hooks disabled; skipping \
-         on-launch"
+        "hooks disabled or dry run; \
+         skipping on-launch"
       );
       return Ok(());
     }
@@ -78,15 +141,16 @@ impl HookRunner {
     &self,
     task: &Task
   ) -> anyhow::Result<Task> {
-    if !self.enabled {
+    let mut current = task.clone();
+    self.auto_tags.apply(&mut current);
+    if !self.runs_scripts() {
       debug!(
-        "hooks disabled; skipping \
-         on-add"
+        "hooks disabled or dry run; \
+         skipping on-add"
       );
-      return Ok(task.clone());
+      return Ok(current);
     }
 
-    let mut current = task.clone();
     let scripts =
       self.list_scripts("on-add")?;
     debug!(
@@ -131,15 +195,16 @@ impl HookRunner {
     old: &Task,
     new: &Task
   ) -> anyhow::Result<Task> {
-    if !self.enabled {
+    let mut current = new.clone();
+    self.auto_tags.apply(&mut current);
+    if !self.runs_scripts() {
       debug!(
-        "hooks disabled; skipping \
-         on-modify"
+        "hooks disabled or dry run; \
+         skipping on-modify"
       );
-      return Ok(new.clone());
+      return Ok(current);
     }
 
-    let mut current = new.clone();
     let scripts =
       self.list_scripts("on-modify")?;
     debug!(
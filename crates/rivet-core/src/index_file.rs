@@ -0,0 +1,494 @@
+//! Optional on-disk copy of a parsed
+//! data file (`pending.data` ->
+//! `pending.index`), so a cold start
+//! can skip parsing every JSONL line.
+//!
+//! The sidecar is a compact binary
+//! encoding, since decoding JSON rather
+//! than reading the file is what
+//! dominates a cold start. It records a
+//! hash of the data file it was built
+//! from and is only trusted while that
+//! hash still matches. A sidecar that
+//! is missing, stale, from another
+//! format version or unreadable is
+//! ignored; the caller parses the data
+//! file and writes a fresh one.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::Hasher;
+use std::io::Write;
+use std::path::{
+  Path,
+  PathBuf
+};
+
+use anyhow::{
+  anyhow,
+  bail
+};
+use chrono::{
+  DateTime,
+  Utc
+};
+use tempfile::NamedTempFile;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::task::{
+  Annotation,
+  Status,
+  Task
+};
+
+/// Leads every sidecar; bump the digit
+/// whenever the row layout changes.
+const MAGIC: &[u8; 8] = b"RVTIDX02";
+
+/// Little-endian writer for the sidecar
+/// layout: fixed-width integers,
+/// strings and lists prefixed by their
+/// length, options by a 0/1 byte.
+#[derive(Default)]
+struct Encoder {
+  buf: Vec<u8>
+}
+
+impl Encoder {
+  fn u8(
+    &mut self,
+    value: u8
+  ) {
+    self.buf.push(value);
+  }
+
+  fn u64(
+    &mut self,
+    value: u64
+  ) {
+    self.buf.extend_from_slice(
+      &value.to_le_bytes()
+    );
+  }
+
+  fn len(
+    &mut self,
+    len: usize
+  ) {
+    self.u64(len as u64);
+  }
+
+  fn date(
+    &mut self,
+    value: &DateTime<Utc>
+  ) {
+    self.buf.extend_from_slice(
+      &value.timestamp().to_le_bytes()
+    );
+  }
+
+  fn str(
+    &mut self,
+    value: &str
+  ) {
+    self.len(value.len());
+    self.buf.extend_from_slice(
+      value.as_bytes()
+    );
+  }
+
+  fn uuid(
+    &mut self,
+    value: &Uuid
+  ) {
+    self.buf.extend_from_slice(
+      value.as_bytes()
+    );
+  }
+
+  fn opt<T>(
+    &mut self,
+    value: Option<&T>,
+    write: impl FnOnce(&mut Self, &T)
+  ) where
+    T: ?Sized
+  {
+    match value {
+      | None => self.u8(0),
+      | Some(value) => {
+        self.u8(1);
+        write(self, value);
+      }
+    }
+  }
+
+  fn task(
+    &mut self,
+    task: &Task
+  ) -> anyhow::Result<()> {
+    self.uuid(&task.uuid);
+    self.opt(
+      task.id.as_ref(),
+      |enc, id| enc.u64(*id)
+    );
+    self.str(&task.description);
+    self.u8(match task.status {
+      | Status::Pending => 0,
+      | Status::Completed => 1,
+      | Status::Deleted => 2,
+      | Status::Waiting => 3,
+      | Status::Archived => 4
+    });
+    self.date(&task.entry);
+    self.date(&task.modified);
+    for date in [&task.end, &task.start]
+    {
+      self
+        .opt(date.as_ref(), Self::date);
+    }
+    for text in
+      [&task.project, &task.priority]
+    {
+      self.opt(
+        text.as_deref(),
+        Self::str
+      );
+    }
+    self.len(task.tags.len());
+    for tag in &task.tags {
+      self.str(tag);
+    }
+    for date in [
+      &task.due,
+      &task.scheduled,
+      &task.wait
+    ] {
+      self
+        .opt(date.as_ref(), Self::date);
+    }
+    self.len(task.depends.len());
+    for uuid in &task.depends {
+      self.uuid(uuid);
+    }
+    for link in
+      [&task.parent, &task.contact]
+    {
+      self
+        .opt(link.as_ref(), Self::uuid);
+    }
+    self.len(task.annotations.len());
+    for note in &task.annotations {
+      self.date(&note.entry);
+      self.str(&note.description);
+    }
+    // UDAs and unknown keys are rare
+    // and free-form, so they stay
+    // JSON.
+    if task.extra.is_empty() {
+      self.str("");
+    } else {
+      self.str(&serde_json::to_string(
+        &task.extra
+      )?);
+    }
+    Ok(())
+  }
+}
+
+/// Reads what `Encoder` wrote; any
+/// short read or bad value fails the
+/// whole sidecar.
+struct Decoder<'a> {
+  buf: &'a [u8]
+}
+
+impl<'a> Decoder<'a> {
+  fn take(
+    &mut self,
+    len: usize
+  ) -> anyhow::Result<&'a [u8]> {
+    if self.buf.len() < len {
+      bail!("truncated index");
+    }
+    let (head, rest) =
+      self.buf.split_at(len);
+    self.buf = rest;
+    Ok(head)
+  }
+
+  fn array<const N: usize>(
+    &mut self
+  ) -> anyhow::Result<[u8; N]> {
+    Ok(self.take(N)?.try_into()?)
+  }
+
+  fn u8(
+    &mut self
+  ) -> anyhow::Result<u8> {
+    Ok(self.array::<1>()?[0])
+  }
+
+  fn u64(
+    &mut self
+  ) -> anyhow::Result<u64> {
+    Ok(u64::from_le_bytes(
+      self.array()?
+    ))
+  }
+
+  fn len(
+    &mut self
+  ) -> anyhow::Result<usize> {
+    let len =
+      usize::try_from(self.u64()?)?;
+    // Every item takes at least a byte,
+    // so a larger count is corruption
+    // rather than a huge allocation.
+    if len > self.buf.len() {
+      bail!("bad length in index");
+    }
+    Ok(len)
+  }
+
+  fn date(
+    &mut self
+  ) -> anyhow::Result<DateTime<Utc>> {
+    let secs =
+      i64::from_le_bytes(self.array()?);
+    DateTime::from_timestamp(secs, 0)
+      .ok_or_else(|| {
+        anyhow!(
+          "timestamp {secs} out of \
+           range"
+        )
+      })
+  }
+
+  fn str(
+    &mut self
+  ) -> anyhow::Result<String> {
+    let len = self.len()?;
+    Ok(
+      std::str::from_utf8(
+        self.take(len)?
+      )?
+      .to_string()
+    )
+  }
+
+  fn uuid(
+    &mut self
+  ) -> anyhow::Result<Uuid> {
+    Ok(Uuid::from_bytes(self.array()?))
+  }
+
+  fn opt<T>(
+    &mut self,
+    read: impl FnOnce(
+      &mut Self
+    )
+      -> anyhow::Result<T>
+  ) -> anyhow::Result<Option<T>> {
+    match self.u8()? {
+      | 0 => Ok(None),
+      | 1 => read(self).map(Some),
+      | other => {
+        bail!("bad option tag {other}")
+      }
+    }
+  }
+
+  fn list<T>(
+    &mut self,
+    mut read: impl FnMut(
+      &mut Self
+    )
+      -> anyhow::Result<
+      T
+    >
+  ) -> anyhow::Result<Vec<T>> {
+    let len = self.len()?;
+    (0..len)
+      .map(|_| read(self))
+      .collect()
+  }
+
+  fn task(
+    &mut self
+  ) -> anyhow::Result<Task> {
+    let uuid = self.uuid()?;
+    let id = self.opt(Self::u64)?;
+    let description = self.str()?;
+    let status = match self.u8()? {
+      | 0 => Status::Pending,
+      | 1 => Status::Completed,
+      | 2 => Status::Deleted,
+      | 3 => Status::Waiting,
+      | 4 => Status::Archived,
+      | other => {
+        bail!("bad status {other}")
+      }
+    };
+    let entry = self.date()?;
+    let modified = self.date()?;
+    let end = self.opt(Self::date)?;
+    let start = self.opt(Self::date)?;
+    let project =
+      self.opt(Self::str)?;
+    let priority =
+      self.opt(Self::str)?;
+    let tags = self.list(Self::str)?;
+    let due = self.opt(Self::date)?;
+    let scheduled =
+      self.opt(Self::date)?;
+    let wait = self.opt(Self::date)?;
+    let depends =
+      self.list(Self::uuid)?;
+    let parent =
+      self.opt(Self::uuid)?;
+    let contact =
+      self.opt(Self::uuid)?;
+    let annotations =
+      self.list(|dec| {
+        Ok(Annotation {
+          entry:       dec.date()?,
+          description: dec.str()?
+        })
+      })?;
+    let extra_json = self.str()?;
+    let extra = if extra_json.is_empty()
+    {
+      BTreeMap::new()
+    } else {
+      serde_json::from_str(&extra_json)?
+    };
+    Ok(Task {
+      uuid,
+      id,
+      description,
+      status,
+      entry,
+      modified,
+      end,
+      start,
+      project,
+      priority,
+      tags,
+      due,
+      scheduled,
+      wait,
+      depends,
+      parent,
+      contact,
+      annotations,
+      extra
+    })
+  }
+}
+
+/// Sidecar path for a data file.
+pub fn index_path(
+  data_path: &Path
+) -> PathBuf {
+  data_path.with_extension("index")
+}
+
+/// Tasks from the sidecar when it was
+/// built from the current contents of
+/// `data_path`; `None` means parse the
+/// data file instead.
+pub fn load(
+  data_path: &Path
+) -> Option<Vec<Task>> {
+  let path = index_path(data_path);
+  let raw = fs::read(&path).ok()?;
+  let source =
+    fs::read(data_path).ok()?;
+  match decode(&raw, &source) {
+    | Ok(Some(tasks)) => Some(tasks),
+    | Ok(None) => {
+      debug!(file = %path.display(), "discarding stale index");
+      None
+    }
+    | Err(err) => {
+      debug!(file = %path.display(), err = %format!("{err:#}"), "discarding unreadable index");
+      None
+    }
+  }
+}
+
+/// Writes the sidecar for `tasks`,
+/// which must be what `data_path` holds
+/// now.
+pub fn store(
+  data_path: &Path,
+  tasks: &[Task]
+) -> anyhow::Result<()> {
+  let source = fs::read(data_path)?;
+  let mut enc = Encoder::default();
+  enc.buf.extend_from_slice(MAGIC);
+  enc.len(source.len());
+  enc.u64(content_hash(&source));
+  enc.len(tasks.len());
+  for task in tasks {
+    enc.task(task)?;
+  }
+
+  let path = index_path(data_path);
+  let dir = path
+    .parent()
+    .unwrap_or_else(|| Path::new("."));
+  let mut temp =
+    NamedTempFile::new_in(dir)?;
+  temp.write_all(&enc.buf)?;
+  temp.flush()?;
+  temp.persist(&path).map_err(
+    |err| {
+      anyhow!(
+        "failed to persist {}: {}",
+        path.display(),
+        err
+      )
+    }
+  )?;
+  debug!(file = %path.display(), count = tasks.len(), "wrote task index");
+  Ok(())
+}
+
+/// `Ok(None)` when the sidecar is
+/// intact but describes other data.
+fn decode(
+  raw: &[u8],
+  source: &[u8]
+) -> anyhow::Result<Option<Vec<Task>>> {
+  let mut dec = Decoder {
+    buf: raw
+  };
+  if dec.take(MAGIC.len())? != MAGIC {
+    return Ok(None);
+  }
+  if dec.u64()? != source.len() as u64
+    || dec.u64()?
+      != content_hash(source)
+  {
+    return Ok(None);
+  }
+  let tasks =
+    dec.list(Decoder::task)?;
+  if !dec.buf.is_empty() {
+    bail!("trailing bytes in index");
+  }
+  Ok(Some(tasks))
+}
+
+/// std's SipHash with fixed keys. Its
+/// output may change between Rust
+/// releases; that only costs one
+/// rebuild.
+fn content_hash(bytes: &[u8]) -> u64 {
+  let mut hasher =
+    std::hash::DefaultHasher::new();
+  hasher.write(bytes);
+  hasher.finish()
+}
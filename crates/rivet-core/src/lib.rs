@@ -1,11 +1,28 @@
+pub mod agenda;
+pub mod archive;
+pub mod autotag;
+pub mod backup;
 pub mod cli;
 pub mod commands;
 pub mod config;
+pub mod config_bundle;
 pub mod datastore;
 pub mod datetime;
+pub mod dedupe;
+pub mod demo;
+pub mod doctor;
+pub mod dto;
+pub mod exit;
 pub mod filter;
+pub mod history;
 pub mod hooks;
+pub mod index_file;
+pub mod locale;
+pub mod migrate;
+pub mod recur;
 pub mod render;
+pub mod rpc;
+pub mod stats;
 pub mod task;
 
 use std::ffi::OsString;
@@ -48,6 +65,15 @@ pub fn run(
         .rc_overrides
         .into_iter()
         .map(|kv| (kv.key, kv.value))
+        .chain(cli.gc.map(|value| {
+          ("gc".to_string(), value)
+        }))
+        .chain(cli.ids.map(|value| {
+          (
+            "id.display".to_string(),
+            value
+          )
+        }))
     )
   );
 
@@ -61,9 +87,16 @@ pub fn run(
        directory"
     )?;
 
+  let backend =
+    datastore::BackendKind::parse(
+      &cfg
+        .get("data.backend")
+        .unwrap_or_default()
+    )
+    .map_err(exit::usage_error)?;
   let mut store =
-    datastore::DataStore::open(
-      &data_dir
+    datastore::DataStore::open_with(
+      &data_dir, backend
     )
     .with_context(|| {
       format!(
@@ -72,19 +105,47 @@ pub fn run(
         data_dir.display()
       )
     })?;
+  store.set_persist_index(
+    cfg
+      .get_bool("index.persist")
+      .unwrap_or(false)
+  );
+  store.set_undo_depth(
+    cfg
+      .get("undo.depth")
+      .map_or(Ok(None), |raw| {
+        datastore::parse_undo_depth(
+          &raw
+        )
+      })
+      .map_err(exit::usage_error)?
+  );
 
   let mut renderer =
     render::Renderer::new(&cfg)?;
-  let inv = cli::Invocation::parse(
+  let mut inv = cli::Invocation::parse(
     &cfg, cli.rest
-  )?;
+  )
+  .map_err(exit::usage_error)?;
+  inv.dry_run = cli.dry_run;
+  renderer.set_limit(inv.limit);
+  renderer
+    .set_width(inv.width.or(cli.width));
 
+  commands::print_launch_agenda(
+    &mut store,
+    &cfg,
+    &inv.command
+  );
   commands::dispatch(
     &mut store,
     &cfg,
     &mut renderer,
     inv
-  )?;
+  )
+  .map_err(|err| {
+    exit::apply_config(err, &cfg)
+  })?;
 
   info!("done");
   Ok(())
@@ -0,0 +1,410 @@
+//! Display names for months and
+//! weekdays in `task calendar`. Dates,
+//! tags and config keep their English
+//! keywords; only the labels follow the
+//! locale, taken from the `locale` rc
+//! key or `LC_ALL` / `LC_TIME` /
+//! `LANG`. Languages without a table
+//! fall back to English names but keep
+//! their region's first weekday.
+
+use chrono::Weekday;
+
+use crate::config::Config;
+
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+enum Language {
+  English,
+  German,
+  French,
+  Spanish,
+  Italian,
+  Portuguese,
+  Dutch
+}
+
+impl Language {
+  fn from_code(code: &str) -> Self {
+    match code {
+      | "de" => Self::German,
+      | "fr" => Self::French,
+      | "es" => Self::Spanish,
+      | "it" => Self::Italian,
+      | "pt" => Self::Portuguese,
+      | "nl" => Self::Dutch,
+      | _ => Self::English
+    }
+  }
+
+  fn months(
+    self
+  ) -> [&'static str; 12] {
+    match self {
+      | Self::English => {
+        [
+          "January",
+          "February",
+          "March",
+          "April",
+          "May",
+          "June",
+          "July",
+          "August",
+          "September",
+          "October",
+          "November",
+          "December"
+        ]
+      }
+      | Self::German => {
+        [
+          "Januar",
+          "Februar",
+          "März",
+          "April",
+          "Mai",
+          "Juni",
+          "Juli",
+          "August",
+          "September",
+          "Oktober",
+          "November",
+          "Dezember"
+        ]
+      }
+      | Self::French => {
+        [
+          "janvier",
+          "février",
+          "mars",
+          "avril",
+          "mai",
+          "juin",
+          "juillet",
+          "août",
+          "septembre",
+          "octobre",
+          "novembre",
+          "décembre"
+        ]
+      }
+      | Self::Spanish => {
+        [
+          "enero",
+          "febrero",
+          "marzo",
+          "abril",
+          "mayo",
+          "junio",
+          "julio",
+          "agosto",
+          "septiembre",
+          "octubre",
+          "noviembre",
+          "diciembre"
+        ]
+      }
+      | Self::Italian => {
+        [
+          "gennaio",
+          "febbraio",
+          "marzo",
+          "aprile",
+          "maggio",
+          "giugno",
+          "luglio",
+          "agosto",
+          "settembre",
+          "ottobre",
+          "novembre",
+          "dicembre"
+        ]
+      }
+      | Self::Portuguese => {
+        [
+          "janeiro",
+          "fevereiro",
+          "março",
+          "abril",
+          "maio",
+          "junho",
+          "julho",
+          "agosto",
+          "setembro",
+          "outubro",
+          "novembro",
+          "dezembro"
+        ]
+      }
+      | Self::Dutch => {
+        [
+          "januari",
+          "februari",
+          "maart",
+          "april",
+          "mei",
+          "juni",
+          "juli",
+          "augustus",
+          "september",
+          "oktober",
+          "november",
+          "december"
+        ]
+      }
+    }
+  }
+
+  /// Two-letter weekday names, Monday
+  /// first.
+  fn weekdays(
+    self
+  ) -> [&'static str; 7] {
+    match self {
+      | Self::English => {
+        [
+          "Mo", "Tu", "We", "Th", "Fr",
+          "Sa", "Su"
+        ]
+      }
+      | Self::German => {
+        [
+          "Mo", "Di", "Mi", "Do", "Fr",
+          "Sa", "So"
+        ]
+      }
+      | Self::French => {
+        [
+          "lu", "ma", "me", "je", "ve",
+          "sa", "di"
+        ]
+      }
+      | Self::Spanish => {
+        [
+          "lu", "ma", "mi", "ju", "vi",
+          "sá", "do"
+        ]
+      }
+      | Self::Italian => {
+        [
+          "lu", "ma", "me", "gi", "ve",
+          "sa", "do"
+        ]
+      }
+      | Self::Portuguese => {
+        [
+          "sg", "te", "qa", "qi", "sx",
+          "sá", "do"
+        ]
+      }
+      | Self::Dutch => {
+        [
+          "ma", "di", "wo", "do", "vr",
+          "za", "zo"
+        ]
+      }
+    }
+  }
+}
+
+/// Regions whose calendars start the
+/// week on Sunday (CLDR).
+const SUNDAY_FIRST_REGIONS: &[&str] = &[
+  "AG", "AS", "BR", "BS", "BT", "BW",
+  "BZ", "CA", "CO", "DM", "DO", "ET",
+  "GT", "GU", "HK", "HN", "ID", "IL",
+  "IN", "JM", "JP", "KE", "KH", "KR",
+  "LA", "MH", "MM", "MO", "MT", "MX",
+  "MZ", "NI", "NP", "PA", "PE", "PH",
+  "PK", "PR", "PT", "PY", "SA", "SG",
+  "SV", "TH", "TT", "TW", "UM", "US",
+  "VE", "VI", "WS", "YE", "ZA", "ZW"
+];
+
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct Locale {
+  language: Language,
+  region:   Option<String>
+}
+
+impl Default for Locale {
+  fn default() -> Self {
+    Self {
+      language: Language::English,
+      region:   None
+    }
+  }
+}
+
+impl Locale {
+  /// Reads `de`, `de-DE` or POSIX
+  /// names like `de_DE.UTF-8`. `C`,
+  /// `POSIX` and blanks give `None`.
+  pub fn parse(
+    raw: &str
+  ) -> Option<Self> {
+    let name = raw
+      .trim()
+      .split(['.', '@'])
+      .next()
+      .unwrap_or_default();
+    if name.is_empty()
+      || name == "C"
+      || name == "POSIX"
+    {
+      return None;
+    }
+    let mut parts =
+      name.split(['_', '-']);
+    let language = parts
+      .next()
+      .filter(|code| {
+        (2..=3).contains(&code.len())
+          && code.chars().all(|ch| {
+            ch.is_ascii_alphabetic()
+          })
+      })?
+      .to_ascii_lowercase();
+    let region = parts
+      .find(|part| {
+        part.len() == 2
+          && part.chars().all(|ch| {
+            ch.is_ascii_alphabetic()
+          })
+      })
+      .map(|part| {
+        part.to_ascii_uppercase()
+      });
+    Some(Self {
+      language: Language::from_code(
+        &language
+      ),
+      region
+    })
+  }
+
+  /// The `locale` rc key, then the
+  /// environment, then English.
+  pub fn configured(
+    cfg: &Config
+  ) -> Self {
+    cfg
+      .get("locale")
+      .and_then(|raw| Self::parse(&raw))
+      .or_else(|| {
+        ["LC_ALL", "LC_TIME", "LANG"]
+          .iter()
+          .filter_map(|key| {
+            std::env::var(key).ok()
+          })
+          .find(|value| {
+            !value.trim().is_empty()
+          })
+          .and_then(|value| {
+            Self::parse(&value)
+          })
+      })
+      .unwrap_or_default()
+  }
+
+  /// `month` is 1-12.
+  pub fn month_name(
+    &self,
+    month: u32
+  ) -> &'static str {
+    let index = month.clamp(1, 12) - 1;
+    self.language.months()
+      [index as usize]
+  }
+
+  pub fn weekday_abbrev(
+    &self,
+    day: Weekday
+  ) -> &'static str {
+    self.language.weekdays()[day
+      .num_days_from_monday()
+      as usize]
+  }
+
+  /// Sunday for regions that start the
+  /// week on it, Monday otherwise.
+  pub fn first_weekday(
+    &self
+  ) -> Weekday {
+    match &self.region {
+      | Some(region)
+        if SUNDAY_FIRST_REGIONS
+          .contains(
+            &region.as_str()
+          ) =>
+      {
+        Weekday::Sun
+      }
+      | _ => Weekday::Mon
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Weekday;
+
+  use super::Locale;
+
+  #[test]
+  fn labels_follow_the_locale() {
+    let german =
+      Locale::parse("de_DE.UTF-8")
+        .expect("german locale");
+    assert_eq!(
+      german.month_name(3),
+      "März"
+    );
+    assert_eq!(
+      german
+        .weekday_abbrev(Weekday::Sun),
+      "So"
+    );
+    assert_eq!(
+      german.first_weekday(),
+      Weekday::Mon
+    );
+
+    let american =
+      Locale::parse("en-US")
+        .expect("us locale");
+    assert_eq!(
+      american.month_name(1),
+      "January"
+    );
+    assert_eq!(
+      american.first_weekday(),
+      Weekday::Sun
+    );
+
+    let japanese =
+      Locale::parse("ja_JP")
+        .expect("japanese locale");
+    assert_eq!(
+      japanese.month_name(12),
+      "December"
+    );
+    assert_eq!(
+      japanese.first_weekday(),
+      Weekday::Sun
+    );
+
+    assert_eq!(
+      Locale::parse("C"),
+      None
+    );
+    assert_eq!(
+      Locale::parse(" "),
+      None
+    );
+  }
+}
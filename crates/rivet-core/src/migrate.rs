@@ -0,0 +1,519 @@
+use std::fs;
+use std::path::{
+  Path,
+  PathBuf
+};
+
+use anyhow::{
+  Context,
+  anyhow,
+  bail
+};
+use chrono::Utc;
+use serde::{
+  Deserialize,
+  Serialize
+};
+use tracing::info;
+
+use crate::datastore::STORE_FORMAT_VERSION;
+
+/// File in the data directory that
+/// records which store format the
+/// `*.data` files are written in.
+pub const MANIFEST_FILE: &str =
+  "manifest.json";
+
+/// Format assumed for data directories
+/// written before the manifest existed.
+pub const LEGACY_FORMAT_VERSION: &str =
+  "1.0.0";
+
+/// Data files copied aside before any
+/// migration step runs.
+const DATA_FILES: &[&str] = &[
+  "pending.data",
+  "completed.data",
+  "undo.data",
+  "redo.data",
+  "context.data",
+  "history.data",
+  "corrupt.data"
+];
+
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+)]
+pub struct StoreManifest {
+  pub format_version: String,
+  #[serde(default)]
+  pub written_by:     String
+}
+
+/// One forward step of the on-disk
+/// format. `apply` rewrites the data
+/// directory from the previous
+/// version's layout to `to`.
+pub struct Migration {
+  pub to:          &'static str,
+  pub description: &'static str,
+  pub apply:
+    fn(&Path) -> anyhow::Result<()>
+}
+
+/// Every format change since
+/// `LEGACY_FORMAT_VERSION`, oldest
+/// first. Bump `STORE_FORMAT_VERSION`
+/// and append a step here together;
+/// each step only has to understand
+/// the version right before it.
+pub const MIGRATIONS: &[Migration] =
+  &[Migration {
+    to:          "1.1.0",
+    description: "status may be \
+                  \"archived\"",
+    apply:       allow_archived_status
+  }];
+
+/// 1.0.0 lines are all valid 1.1.0
+/// lines; the bump only stops older
+/// builds from opening a store that
+/// may hold `status:"archived"`.
+fn allow_archived_status(
+  _data_dir: &Path
+) -> anyhow::Result<()> {
+  Ok(())
+}
+
+/// What `prepare_data_dir` did to the
+/// directory.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct MigrationReport {
+  pub from:    String,
+  pub to:      String,
+  pub applied: Vec<String>,
+  pub backup:  Option<PathBuf>
+}
+
+pub fn manifest_path(
+  data_dir: &Path
+) -> PathBuf {
+  data_dir.join(MANIFEST_FILE)
+}
+
+/// Reads the manifest, or `None` when
+/// the directory predates it.
+pub fn read_manifest(
+  data_dir: &Path
+) -> anyhow::Result<Option<StoreManifest>>
+{
+  let path = manifest_path(data_dir);
+  if !path.exists() {
+    return Ok(None);
+  }
+  let raw = fs::read_to_string(&path)
+    .with_context(|| {
+    format!(
+      "failed to read {}",
+      path.display()
+    )
+  })?;
+  let manifest =
+    serde_json::from_str(&raw)
+      .with_context(|| {
+        format!(
+          "failed to parse {}",
+          path.display()
+        )
+      })?;
+  Ok(Some(manifest))
+}
+
+pub fn write_manifest(
+  data_dir: &Path,
+  format_version: &str
+) -> anyhow::Result<()> {
+  let manifest = StoreManifest {
+    format_version: format_version
+      .to_string(),
+    written_by:     env!(
+      "CARGO_PKG_VERSION"
+    )
+    .to_string()
+  };
+  let path = manifest_path(data_dir);
+  fs::write(
+    &path,
+    serde_json::to_string_pretty(
+      &manifest
+    )? + "\n"
+  )
+  .with_context(|| {
+    format!(
+      "failed to write {}",
+      path.display()
+    )
+  })
+}
+
+/// Brings `data_dir` up to
+/// `STORE_FORMAT_VERSION` before the
+/// store reads it. Fresh directories
+/// just get a manifest; older ones are
+/// backed up and migrated step by step;
+/// newer ones are refused untouched.
+pub fn prepare_data_dir(
+  data_dir: &Path
+) -> anyhow::Result<MigrationReport> {
+  run_migrations(
+    data_dir,
+    STORE_FORMAT_VERSION,
+    MIGRATIONS
+  )
+}
+
+fn run_migrations(
+  data_dir: &Path,
+  target: &str,
+  steps: &[Migration]
+) -> anyhow::Result<MigrationReport> {
+  let target_key =
+    parse_version(target)?;
+  let from =
+    match read_manifest(data_dir)? {
+      | Some(manifest) => {
+        manifest.format_version
+      }
+      | None
+        if has_data_files(data_dir) =>
+      {
+        LEGACY_FORMAT_VERSION
+          .to_string()
+      }
+      | None => {
+        write_manifest(
+          data_dir, target
+        )?;
+        return Ok(MigrationReport {
+          from:    target.to_string(),
+          to:      target.to_string(),
+          applied: Vec::new(),
+          backup:  None
+        });
+      }
+    };
+  let from_key = parse_version(&from)
+    .with_context(
+    || {
+      format!(
+        "invalid format_version in {}",
+        manifest_path(data_dir)
+          .display()
+      )
+    }
+  )?;
+
+  if from_key > target_key {
+    bail!(
+      "data directory {} uses store \
+       format {from}, but this build \
+       only understands up to \
+       {target}; upgrade rivet or \
+       point it at another data \
+       directory",
+      data_dir.display()
+    );
+  }
+
+  let mut pending = Vec::new();
+  for step in steps {
+    let step_key =
+      parse_version(step.to)?;
+    if step_key > from_key
+      && step_key <= target_key
+    {
+      pending.push(step);
+    }
+  }
+
+  let mut report = MigrationReport {
+    from:    from.clone(),
+    to:      from.clone(),
+    applied: Vec::new(),
+    backup:  None
+  };
+  if !pending.is_empty() {
+    report.backup =
+      Some(backup_data_files(
+        data_dir, &from
+      )?);
+  }
+  for step in pending {
+    info!(
+      from = %report.to,
+      to = step.to,
+      step = step.description,
+      "migrating data directory"
+    );
+    (step.apply)(data_dir)
+      .with_context(|| {
+        format!(
+          "migration to store format \
+           {} ({}) failed",
+          step.to, step.description
+        )
+      })?;
+    // Record progress per step so a
+    // later failure resumes from here.
+    write_manifest(data_dir, step.to)?;
+    report.to = step.to.to_string();
+    report
+      .applied
+      .push(step.to.to_string());
+  }
+
+  // Versions without a step of their
+  // own share the previous layout.
+  if report.to != target {
+    write_manifest(data_dir, target)?;
+    report.to = target.to_string();
+  }
+  Ok(report)
+}
+
+fn has_data_files(
+  data_dir: &Path
+) -> bool {
+  DATA_FILES.iter().any(|name| {
+    data_dir.join(name).exists()
+  })
+}
+
+/// Copies the data files into
+/// `backups/migrate-<version>-<stamp>`
+/// and returns that directory.
+fn backup_data_files(
+  data_dir: &Path,
+  from: &str
+) -> anyhow::Result<PathBuf> {
+  let backup_dir = data_dir
+    .join("backups")
+    .join(format!(
+      "migrate-{from}-{}",
+      Utc::now()
+        .format("%Y%m%dT%H%M%SZ")
+    ));
+  fs::create_dir_all(&backup_dir)
+    .with_context(|| {
+      format!(
+        "failed to create {}",
+        backup_dir.display()
+      )
+    })?;
+  for name in DATA_FILES
+    .iter()
+    .copied()
+    .chain([MANIFEST_FILE])
+  {
+    let source = data_dir.join(name);
+    if source.exists() {
+      fs::copy(
+        &source,
+        backup_dir.join(name)
+      )
+      .with_context(|| {
+        format!(
+          "failed to back up {}",
+          source.display()
+        )
+      })?;
+    }
+  }
+  info!(
+    backup = %backup_dir.display(),
+    "backed up data directory before \
+     migrating"
+  );
+  Ok(backup_dir)
+}
+
+pub(crate) fn parse_version(
+  raw: &str
+) -> anyhow::Result<(u64, u64, u64)> {
+  let parts = raw
+    .trim()
+    .split('.')
+    .map(str::parse::<u64>)
+    .collect::<Result<Vec<_>, _>>()
+    .ok();
+  match parts.as_deref() {
+    | Some(&[major, minor, patch]) => {
+      Ok((major, minor, patch))
+    }
+    | _ => {
+      Err(anyhow!(
+        "invalid store format \
+         version: {raw}"
+      ))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+  use std::path::Path;
+
+  use super::{
+    Migration,
+    read_manifest,
+    run_migrations,
+    write_manifest
+  };
+
+  fn tag_pending(
+    data_dir: &Path
+  ) -> anyhow::Result<()> {
+    let path =
+      data_dir.join("pending.data");
+    let raw =
+      fs::read_to_string(&path)?;
+    fs::write(
+      path,
+      raw.replace("old", "new")
+    )?;
+    Ok(())
+  }
+
+  fn fail(
+    _data_dir: &Path
+  ) -> anyhow::Result<()> {
+    anyhow::bail!("disk on fire")
+  }
+
+  const STEPS: &[Migration] = &[
+    Migration {
+      to:          "1.1.0",
+      description: "rename old",
+      apply:       tag_pending
+    },
+    Migration {
+      to:          "2.0.0",
+      description: "explodes",
+      apply:       fail
+    }
+  ];
+
+  #[test]
+  fn fresh_and_legacy_dirs_get_a_manifest()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let report = run_migrations(
+      temp.path(),
+      "1.1.0",
+      STEPS
+    )
+    .expect("fresh");
+    assert!(report.applied.is_empty());
+    assert_eq!(
+      read_manifest(temp.path())
+        .expect("manifest")
+        .expect("written")
+        .format_version,
+      "1.1.0"
+    );
+
+    let legacy = tempfile::tempdir()
+      .expect("tempdir");
+    fs::write(
+      legacy
+        .path()
+        .join("pending.data"),
+      "old\n"
+    )
+    .expect("seed");
+    let report = run_migrations(
+      legacy.path(),
+      "1.1.0",
+      STEPS
+    )
+    .expect("migrate");
+    assert_eq!(report.from, "1.0.0");
+    assert_eq!(report.applied, [
+      "1.1.0"
+    ]);
+    let backup =
+      report.backup.expect("backup");
+    assert_eq!(
+      fs::read_to_string(
+        backup.join("pending.data")
+      )
+      .expect("backup copy"),
+      "old\n"
+    );
+    assert_eq!(
+      fs::read_to_string(
+        legacy
+          .path()
+          .join("pending.data")
+      )
+      .expect("migrated"),
+      "new\n"
+    );
+  }
+
+  #[test]
+  fn newer_formats_are_refused_and_failures_keep_progress()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    write_manifest(
+      temp.path(),
+      "3.0.0"
+    )
+    .expect("manifest");
+    let err = run_migrations(
+      temp.path(),
+      "2.0.0",
+      STEPS
+    )
+    .expect_err("newer refused");
+    assert!(
+      err
+        .to_string()
+        .contains("only understands")
+    );
+
+    write_manifest(
+      temp.path(),
+      "1.0.0"
+    )
+    .expect("manifest");
+    fs::write(
+      temp.path().join("pending.data"),
+      "old\n"
+    )
+    .expect("seed");
+    run_migrations(
+      temp.path(),
+      "2.0.0",
+      STEPS
+    )
+    .expect_err("second step fails");
+    assert_eq!(
+      read_manifest(temp.path())
+        .expect("manifest")
+        .expect("written")
+        .format_version,
+      "1.1.0"
+    );
+  }
+}
@@ -0,0 +1,953 @@
+use anyhow::anyhow;
+use chrono::{
+  DateTime,
+  Datelike,
+  Duration,
+  NaiveDate,
+  TimeZone,
+  Utc,
+  Weekday
+};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::datastore::DataStore;
+use crate::datetime::{
+  parse_weekday_name,
+  project_timezone
+};
+use crate::history::diff_snapshots;
+use crate::task::{
+  Status,
+  Task
+};
+
+/// Recurrence lives in `key:value`
+/// tags shared with the GUI (see
+/// `lib/tags.ts`).
+pub const RECUR_TAG_KEY: &str = "recur";
+pub const RECUR_TIME_TAG_KEY: &str =
+  "recur_time";
+pub const RECUR_DAYS_TAG_KEY: &str =
+  "recur_days";
+pub const RECUR_MONTHS_TAG_KEY: &str =
+  "recur_months";
+pub const RECUR_MONTH_DAY_TAG_KEY:
+  &str = "recur_day";
+/// Last local date an instance may be
+/// due on (`YYYY-MM-DD`).
+pub const RECUR_UNTIL_TAG_KEY: &str =
+  "recur_until";
+/// Total instances in a counted series.
+pub const RECUR_COUNT_TAG_KEY: &str =
+  "recur_count";
+/// Instances still to come, counting
+/// the one that carries the tag.
+pub const RECUR_LEFT_TAG_KEY: &str =
+  "recur_left";
+/// Uuid of the task that started the
+/// series, carried by every instance
+/// spawned from it.
+pub const RECUR_SERIES_TAG_KEY: &str =
+  "recur_series";
+
+/// Set on an instance completed with
+/// `task skip`: the series moves on but
+/// the instance does not use up
+/// `recur_left`.
+pub const RECUR_SKIPPED_KEY: &str =
+  "recur_skipped";
+/// History command for instances
+/// created by the lazy sweep.
+pub const RECUR_SWEEP_COMMAND: &str =
+  "recur";
+/// Default `recur.horizon`, in days.
+pub const DEFAULT_RECUR_HORIZON_DAYS:
+  i64 = 7;
+
+/// `recur.mode`: when the instance
+/// after a completed one is created.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub enum RecurMode {
+  /// As soon as the instance is
+  /// completed.
+  #[default]
+  Eager,
+  /// Once its due date comes within
+  /// `recur.horizon` days, checked each
+  /// time the store is opened (see
+  /// [`sweep`]).
+  Lazy
+}
+
+impl RecurMode {
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "eager" => Ok(Self::Eager),
+      | "lazy" => Ok(Self::Lazy),
+      | other => {
+        Err(anyhow!(
+          "invalid recur.mode \
+           '{other}': use eager or \
+           lazy"
+        ))
+      }
+    }
+  }
+
+  pub fn from_config(
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    cfg
+      .get("recur.mode")
+      .map_or(Ok(Self::Eager), |raw| {
+        Self::parse(&raw)
+      })
+  }
+}
+
+/// `recur.horizon`, falling back to
+/// [`DEFAULT_RECUR_HORIZON_DAYS`] when
+/// unset or not a whole number of days
+/// (zero or more). A count no date span
+/// can hold is an error.
+pub fn recur_horizon_days(
+  cfg: &Config
+) -> anyhow::Result<i64> {
+  let days = cfg
+    .get("recur.horizon")
+    .and_then(|raw| {
+      raw.trim().parse::<i64>().ok()
+    })
+    .filter(|days| *days >= 0)
+    .unwrap_or(
+      DEFAULT_RECUR_HORIZON_DAYS
+    );
+  if Duration::try_days(days).is_none()
+  {
+    return Err(anyhow!(
+      "recur.horizon {days} is out of \
+       range"
+    ));
+  }
+  Ok(days)
+}
+
+pub fn is_skipped(task: &Task) -> bool {
+  task
+    .extra
+    .get(RECUR_SKIPPED_KEY)
+    .and_then(
+      serde_json::Value::as_bool
+    )
+    .unwrap_or(false)
+}
+
+const MONTH_NAMES: [&str; 12] = [
+  "jan", "feb", "mar", "apr", "may",
+  "jun", "jul", "aug", "sep", "oct",
+  "nov", "dec"
+];
+
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum RecurPattern {
+  Daily,
+  Weekly,
+  Months,
+  Monthly,
+  Yearly
+}
+
+impl RecurPattern {
+  pub fn parse(
+    value: &str
+  ) -> Option<Self> {
+    match value
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "daily" => Some(Self::Daily),
+      | "weekly" => Some(Self::Weekly),
+      | "months" => Some(Self::Months),
+      | "monthly" => {
+        Some(Self::Monthly)
+      }
+      | "yearly" => Some(Self::Yearly),
+      | _ => None
+    }
+  }
+}
+
+/// A task's recurrence rule and end
+/// conditions, read from its tags.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct RecurSpec {
+  pub pattern:   RecurPattern,
+  pub days:      Vec<Weekday>,
+  pub months:    Vec<u32>,
+  pub month_day: Option<u32>,
+  pub until:     Option<NaiveDate>,
+  pub count:     Option<u32>,
+  pub left:      Option<u32>
+}
+
+impl RecurSpec {
+  pub fn from_tags(
+    tags: &[String]
+  ) -> Option<Self> {
+    let pattern = RecurPattern::parse(
+      tag_value(tags, RECUR_TAG_KEY)?
+    )?;
+    let days = tag_value(
+      tags,
+      RECUR_DAYS_TAG_KEY
+    )
+    .unwrap_or_default()
+    .split(',')
+    .filter_map(parse_weekday_name)
+    .collect();
+    let months = tag_value(
+      tags,
+      RECUR_MONTHS_TAG_KEY
+    )
+    .unwrap_or_default()
+    .split(',')
+    .filter_map(|name| {
+      MONTH_NAMES
+        .iter()
+        .position(|month| {
+          *month
+            == name
+              .trim()
+              .to_ascii_lowercase()
+        })
+        .map(|index| index as u32 + 1)
+    })
+    .collect();
+    let count = tag_value(
+      tags,
+      RECUR_COUNT_TAG_KEY
+    )
+    .and_then(|raw| raw.parse().ok())
+    .filter(|count| *count > 0);
+    Some(Self {
+      pattern,
+      days,
+      months,
+      month_day: tag_value(
+        tags,
+        RECUR_MONTH_DAY_TAG_KEY
+      )
+      .and_then(|raw| raw.parse().ok())
+      .filter(|day| {
+        (1..=31).contains(day)
+      }),
+      until: tag_value(
+        tags,
+        RECUR_UNTIL_TAG_KEY
+      )
+      .and_then(|raw| {
+        NaiveDate::parse_from_str(
+          raw, "%Y-%m-%d"
+        )
+        .ok()
+      }),
+      count,
+      // A series without its own
+      // counter yet starts full.
+      left: tag_value(
+        tags,
+        RECUR_LEFT_TAG_KEY
+      )
+      .and_then(|raw| raw.parse().ok())
+      .or(count)
+    })
+  }
+
+  /// The instance after one due at
+  /// `due`, keeping its local time of
+  /// day in the project timezone.
+  pub fn next_due(
+    &self,
+    due: DateTime<Utc>
+  ) -> Option<DateTime<Utc>> {
+    let tz = project_timezone();
+    let local = due.with_timezone(tz);
+    let date = local.date_naive();
+    let next = match self.pattern {
+      | RecurPattern::Daily => {
+        date.succ_opt()?
+      }
+      | RecurPattern::Weekly => {
+        if self.days.is_empty() {
+          date + Duration::days(7)
+        } else {
+          (1..=7)
+            .map(|offset| {
+              date
+                + Duration::days(offset)
+            })
+            .find(|day| {
+              self.days.contains(
+                &day.weekday()
+              )
+            })?
+        }
+      }
+      | RecurPattern::Monthly => {
+        self.month_after(date, 1)?
+      }
+      | RecurPattern::Yearly => {
+        self.month_after(date, 12)?
+      }
+      | RecurPattern::Months => {
+        if self.months.is_empty() {
+          self.month_after(date, 1)?
+        } else {
+          (1..=12)
+            .filter_map(|step| {
+              self
+                .month_after(date, step)
+            })
+            .find(|day| {
+              self
+                .months
+                .contains(&day.month())
+            })?
+        }
+      }
+    };
+    tz.from_local_datetime(
+      &next.and_time(local.time())
+    )
+    .earliest()
+    .map(|dt| dt.with_timezone(&Utc))
+  }
+
+  /// `date` moved `step` months ahead,
+  /// on `recur_day` when set, clamped
+  /// to the length of that month.
+  fn month_after(
+    &self,
+    date: NaiveDate,
+    step: u32
+  ) -> Option<NaiveDate> {
+    let index = date.year() * 12
+      + date.month0() as i32
+      + step as i32;
+    let (year, month) = (
+      index.div_euclid(12),
+      index.rem_euclid(12) as u32 + 1
+    );
+    let day = self
+      .month_day
+      .unwrap_or(date.day());
+    (1..=day).rev().find_map(|day| {
+      NaiveDate::from_ymd_opt(
+        year, month, day
+      )
+    })
+  }
+}
+
+pub(crate) fn tag_value<'a>(
+  tags: &'a [String],
+  key: &str
+) -> Option<&'a str> {
+  tags.iter().find_map(|tag| {
+    tag.strip_prefix(key).and_then(
+      |rest| rest.strip_prefix(':')
+    )
+  })
+}
+
+fn set_tag_value(
+  tags: &mut Vec<String>,
+  key: &str,
+  value: &str
+) {
+  tags.retain(|tag| {
+    tag_value(
+      std::slice::from_ref(tag),
+      key
+    )
+    .is_none()
+  });
+  tags.push(format!("{key}:{value}"));
+}
+
+/// The instance that follows `done`
+/// once it is completed, or `None` when
+/// it does not recur, the series has
+/// run out of `recur_count` or passed
+/// `recur_until`, or another pending
+/// instance of the series already
+/// exists (e.g. after a reopen). A
+/// skipped `done` leaves `recur_left`
+/// as it was.
+pub fn next_instance(
+  done: &Task,
+  pending: &[Task],
+  next_id: u64,
+  now: DateTime<Utc>
+) -> Option<Task> {
+  let spec =
+    RecurSpec::from_tags(&done.tags)?;
+  let counted = !is_skipped(done);
+  if counted
+    && spec
+      .left
+      .is_some_and(|left| left <= 1)
+  {
+    return None;
+  }
+
+  let series = series_of(done);
+  let series_text = series.to_string();
+  if pending.iter().any(|task| {
+    task.uuid != done.uuid
+      && (task.uuid == series
+        || tag_value(
+          &task.tags,
+          RECUR_SERIES_TAG_KEY
+        ) == Some(
+          series_text.as_str()
+        ))
+  }) {
+    return None;
+  }
+
+  let base = done.due.unwrap_or(now);
+  let next_due = spec.next_due(base)?;
+  if spec.until.is_some_and(|until| {
+    next_due
+      .with_timezone(project_timezone())
+      .date_naive()
+      > until
+  }) {
+    return None;
+  }
+  let shift = next_due - base;
+
+  let mut next = done.clone();
+  next.uuid = Uuid::new_v4();
+  next.id = Some(next_id);
+  next.status = Status::Pending;
+  next.entry = now;
+  next.modified = now;
+  next.end = None;
+  next.start = None;
+  next.due = done.due.map(|_| next_due);
+  next.scheduled =
+    done.scheduled.map(|at| at + shift);
+  next.wait =
+    done.wait.map(|at| at + shift);
+  next.annotations.clear();
+  next.extra.remove(RECUR_SKIPPED_KEY);
+  set_tag_value(
+    &mut next.tags,
+    RECUR_SERIES_TAG_KEY,
+    &series_text
+  );
+  if let Some(left) = spec.left {
+    let left = if counted {
+      left - 1
+    } else {
+      left
+    };
+    set_tag_value(
+      &mut next.tags,
+      RECUR_LEFT_TAG_KEY,
+      &left.to_string()
+    );
+  }
+  Some(next)
+}
+
+/// Position in `pending` of the
+/// instance that completing `done`
+/// spawned, when it is still untouched
+/// (never started or edited). Reopening
+/// `done` drops it so the series keeps
+/// one open instance and the
+/// `recur_left` that `done` carries.
+pub fn spawned_successor(
+  done: &Task,
+  pending: &[Task]
+) -> Option<usize> {
+  RecurSpec::from_tags(&done.tags)?;
+  let end = done.end?;
+  let series = series_of(done);
+  pending.iter().position(|task| {
+    task.uuid != done.uuid
+      && series_of(task) == series
+      && task.entry >= end
+      && task.modified == task.entry
+      && task.start.is_none()
+      && task.status == Status::Pending
+  })
+}
+
+fn series_of(task: &Task) -> Uuid {
+  tag_value(
+    &task.tags,
+    RECUR_SERIES_TAG_KEY
+  )
+  .and_then(|raw| {
+    Uuid::parse_str(raw).ok()
+  })
+  .unwrap_or(task.uuid)
+}
+
+/// `recur.mode=lazy`: for each series
+/// whose newest instance is closed,
+/// the next instance once its due date
+/// is no more than `horizon_days` away
+/// (at once when it has no due date).
+/// A series whose newest instance was
+/// deleted is over. `next_id` numbers
+/// each new task against `pending` as
+/// it grows.
+pub fn sweep(
+  pending: &mut Vec<Task>,
+  closed: &[Task],
+  horizon_days: i64,
+  now: DateTime<Utc>,
+  next_id: impl Fn(&[Task]) -> u64
+) -> usize {
+  let mut newest: std::collections::BTreeMap<
+    Uuid,
+    &Task
+  > = std::collections::BTreeMap::new();
+  for task in closed {
+    if RecurSpec::from_tags(&task.tags)
+      .is_none()
+    {
+      continue;
+    }
+    let entry = newest
+      .entry(series_of(task))
+      .or_insert(task);
+    if task.entry > entry.entry {
+      *entry = task;
+    }
+  }
+
+  // `None` when the horizon is past
+  // the last representable date.
+  let horizon =
+    Duration::try_days(horizon_days)
+      .and_then(|span| {
+        now.checked_add_signed(span)
+      });
+  let mut created = 0;
+  for last in newest.into_values() {
+    if last.status == Status::Deleted {
+      continue;
+    }
+    let id = next_id(pending);
+    let Some(next) = next_instance(
+      last, pending, id, now
+    ) else {
+      continue;
+    };
+    if next.due.is_some_and(|due| {
+      horizon.is_some_and(|horizon| {
+        due > horizon
+      })
+    }) {
+      continue;
+    }
+    pending.push(next);
+    created += 1;
+  }
+  created
+}
+
+/// Applies [`sweep`] to the store as
+/// one undo step, recorded in history
+/// as [`RECUR_SWEEP_COMMAND`]. Nothing
+/// is written when no instance is due.
+pub fn run_sweep(
+  store: &DataStore,
+  horizon_days: i64,
+  now: DateTime<Utc>
+) -> anyhow::Result<usize> {
+  let before = store.load_pending()?;
+  let completed =
+    store.load_completed()?;
+  let mut pending = before.clone();
+  let created = sweep(
+    &mut pending,
+    &completed,
+    horizon_days,
+    now,
+    |tasks| store.next_id(tasks)
+  );
+  if created == 0 {
+    return Ok(0);
+  }
+  store.push_undo_snapshot(
+    &before, &completed
+  )?;
+  store.save_pending(&pending)?;
+  store.append_history(
+    &diff_snapshots(
+      RECUR_SWEEP_COMMAND,
+      &before,
+      &pending,
+      now
+    )
+  )?;
+  info!(
+    created,
+    horizon_days,
+    "created lazy recurring instances"
+  );
+  Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{
+    TimeZone,
+    Utc,
+    Weekday
+  };
+
+  use super::{
+    RECUR_SKIPPED_KEY,
+    RecurPattern,
+    RecurSpec,
+    next_instance,
+    sweep
+  };
+  use crate::datetime::project_timezone;
+  use crate::task::{
+    Status,
+    Task
+  };
+
+  fn recurring(tags: &[&str]) -> Task {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 12, 0, 0
+      )
+      .unwrap();
+    let mut task = Task::new_pending(
+      "water plants".to_string(),
+      now,
+      1
+    );
+    task.tags = tags
+      .iter()
+      .map(|tag| tag.to_string())
+      .collect();
+    task.due = Some(
+      project_timezone()
+        .with_ymd_and_hms(
+          2026, 3, 2, 9, 0, 0
+        )
+        .unwrap()
+        .with_timezone(&Utc)
+    );
+    task
+  }
+
+  #[test]
+  fn reads_pattern_and_end_conditions()
+  {
+    let spec = RecurSpec::from_tags(
+      &recurring(&[
+        "recur:weekly",
+        "recur_days:mon,thu",
+        "recur_until:2026-06-30",
+        "recur_count:10"
+      ])
+      .tags
+    )
+    .expect("spec");
+    assert_eq!(
+      spec.pattern,
+      RecurPattern::Weekly
+    );
+    assert_eq!(spec.days, vec![
+      Weekday::Mon,
+      Weekday::Thu
+    ]);
+    assert_eq!(spec.count, Some(10));
+    assert_eq!(spec.left, Some(10));
+    assert!(
+      RecurSpec::from_tags(&[
+        "kanban:todo".to_string()
+      ])
+      .is_none()
+    );
+  }
+
+  #[test]
+  fn next_due_follows_pattern_in_local_time()
+   {
+    let tz = project_timezone();
+    let local = |y, m, d| {
+      tz.with_ymd_and_hms(
+        y, m, d, 9, 0, 0
+      )
+      .unwrap()
+      .with_timezone(&Utc)
+    };
+    let due = local(2026, 1, 31);
+    let spec = |tags: &[&str]| {
+      RecurSpec::from_tags(
+        &recurring(tags).tags
+      )
+      .expect("spec")
+    };
+
+    assert_eq!(
+      spec(&["recur:daily"])
+        .next_due(due),
+      Some(local(2026, 2, 1))
+    );
+    // 2026-01-31 is a Saturday.
+    assert_eq!(
+      spec(&[
+        "recur:weekly",
+        "recur_days:tue"
+      ])
+      .next_due(due),
+      Some(local(2026, 2, 3))
+    );
+    assert_eq!(
+      spec(&["recur:monthly"])
+        .next_due(due),
+      Some(local(2026, 2, 28))
+    );
+    assert_eq!(
+      spec(&[
+        "recur:months",
+        "recur_months:jun,dec",
+        "recur_day:15"
+      ])
+      .next_due(due),
+      Some(local(2026, 6, 15))
+    );
+    assert_eq!(
+      spec(&["recur:yearly"])
+        .next_due(due),
+      Some(local(2027, 1, 31))
+    );
+  }
+
+  #[test]
+  fn counted_series_stops_after_its_last_instance()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 2, 18, 0, 0
+      )
+      .unwrap();
+    let first = recurring(&[
+      "recur:daily",
+      "recur_count:3"
+    ]);
+
+    let second = next_instance(
+      &first,
+      &[],
+      2,
+      now
+    )
+    .expect("second");
+    assert!(second.tags.contains(
+      &"recur_left:2".to_string()
+    ));
+    assert!(second.tags.contains(
+      &format!(
+        "recur_series:{}",
+        first.uuid
+      )
+    ));
+    let third = next_instance(
+      &second,
+      &[],
+      3,
+      now
+    )
+    .expect("third");
+    assert!(third.tags.contains(
+      &"recur_left:1".to_string()
+    ));
+    assert!(
+      next_instance(
+        &third,
+        &[],
+        4,
+        now
+      )
+      .is_none()
+    );
+
+    // A pending instance of the same
+    // series means one was spawned.
+    assert!(
+      next_instance(
+        &first,
+        std::slice::from_ref(&second),
+        4,
+        now
+      )
+      .is_none()
+    );
+  }
+
+  #[test]
+  fn until_date_ends_the_series() {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 2, 18, 0, 0
+      )
+      .unwrap();
+    let task = recurring(&[
+      "recur:daily",
+      "recur_until:2026-03-03"
+    ]);
+    let next =
+      next_instance(&task, &[], 2, now)
+        .expect("next");
+    assert!(
+      next_instance(&next, &[], 3, now)
+        .is_none()
+    );
+  }
+
+  #[test]
+  fn skipped_instance_keeps_recur_left()
+  {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 2, 18, 0, 0
+      )
+      .unwrap();
+    let mut last = recurring(&[
+      "recur:daily",
+      "recur_count:3",
+      "recur_left:1"
+    ]);
+    last.extra.insert(
+      RECUR_SKIPPED_KEY.to_string(),
+      true.into()
+    );
+
+    let next =
+      next_instance(&last, &[], 2, now)
+        .expect("skip still spawns");
+    assert!(next.tags.contains(
+      &"recur_left:1".to_string()
+    ));
+    assert!(
+      !next.extra.contains_key(
+        RECUR_SKIPPED_KEY
+      )
+    );
+  }
+
+  #[test]
+  fn sweep_waits_for_the_horizon() {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 13, 0, 0
+      )
+      .unwrap();
+    let mut done =
+      recurring(&["recur:daily"]);
+    done.status = Status::Completed;
+    let closed = vec![done.clone()];
+
+    // Next due is 2026-03-03 09:00
+    // local, about two days out.
+    let mut pending = Vec::new();
+    assert_eq!(
+      sweep(
+        &mut pending,
+        &closed,
+        1,
+        now,
+        |_| 1
+      ),
+      0
+    );
+    assert_eq!(
+      sweep(
+        &mut pending,
+        &closed,
+        5,
+        now,
+        |_| 1
+      ),
+      1
+    );
+    // A horizon past the last date
+    // bounds nothing.
+    assert_eq!(
+      sweep(
+        &mut Vec::new(),
+        &closed,
+        i64::MAX,
+        now,
+        |_| 1
+      ),
+      1
+    );
+    // The new instance is pending, so
+    // a second sweep leaves it alone.
+    assert_eq!(
+      sweep(
+        &mut pending,
+        &closed,
+        5,
+        now,
+        |_| 2
+      ),
+      0
+    );
+
+    // Deleting the newest instance
+    // ends the series.
+    let mut deleted =
+      pending[0].clone();
+    deleted.status = Status::Deleted;
+    deleted.entry = now
+      + chrono::Duration::minutes(1);
+    let mut pending = Vec::new();
+    assert_eq!(
+      sweep(
+        &mut pending,
+        &[done, deleted],
+        5,
+        now,
+        |_| 1
+      ),
+      0
+    );
+  }
+}
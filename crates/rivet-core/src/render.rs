@@ -1,23 +1,794 @@
+use std::collections::BTreeMap;
 use std::io::{
   self,
   IsTerminal,
   Write
 };
 
-use anyhow::anyhow;
+use anyhow::{
+  Context,
+  anyhow
+};
 use chrono::{
   DateTime,
-  Utc
+  Datelike,
+  NaiveDate,
+  Utc,
+  Weekday
 };
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+use crate::cli::RowLimit;
 use crate::config::Config;
-use crate::datetime::format_project_date;
-use crate::task::Task;
+use crate::datetime::{
+  DateFormat,
+  HumanizeStyle,
+  month_weeks
+};
+use crate::history::{
+  HistoryAction,
+  HistoryEntry,
+  display_value
+};
+use crate::locale::Locale;
+use crate::task::{
+  TASK_ATTRIBUTES,
+  Task
+};
+
+/// Field projection for `export
+/// --columns`: task attributes plus
+/// configured UDAs (`uda.<name>.*`),
+/// in the order given.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct ExportColumns {
+  columns: Vec<String>
+}
+
+impl ExportColumns {
+  /// Parses a comma-separated list,
+  /// rejecting unknown names with the
+  /// list of valid ones.
+  pub fn parse(
+    raw: &str,
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    let mut valid: Vec<String> =
+      TASK_ATTRIBUTES
+        .iter()
+        .map(|(name, _)| {
+          name.to_string()
+        })
+        .collect();
+    for (key, _) in cfg.iter() {
+      if let Some((name, _)) = key
+        .strip_prefix("uda.")
+        .and_then(|rest| {
+          rest.rsplit_once('.')
+        })
+        && !valid
+          .iter()
+          .any(|known| known == name)
+      {
+        valid.push(name.to_string());
+      }
+    }
+
+    let mut columns = Vec::new();
+    for column in raw
+      .split(',')
+      .map(str::trim)
+      .filter(|column| {
+        !column.is_empty()
+      })
+    {
+      if !valid
+        .iter()
+        .any(|known| known == column)
+      {
+        return Err(anyhow!(
+          "unknown column '{column}' \
+           (valid: {})",
+          valid.join(", ")
+        ));
+      }
+      if !columns
+        .iter()
+        .any(|seen| seen == column)
+      {
+        columns
+          .push(column.to_string());
+      }
+    }
+    if columns.is_empty() {
+      return Err(anyhow!(
+        "--columns needs at least one \
+         column"
+      ));
+    }
+    Ok(Self {
+      columns
+    })
+  }
+
+  /// Every built-in task attribute, the
+  /// default for delimited exports.
+  pub fn attributes() -> Self {
+    Self {
+      columns: TASK_ATTRIBUTES
+        .iter()
+        .map(|(name, _)| {
+          name.to_string()
+        })
+        .collect()
+    }
+  }
+
+  pub fn columns(&self) -> &[String] {
+    &self.columns
+  }
+
+  /// The task's export object cut down
+  /// to the listed keys, each exactly
+  /// as a full export writes it; a
+  /// UDA the task does not carry is
+  /// left out.
+  pub fn project(
+    &self,
+    task: &Task
+  ) -> anyhow::Result<serde_json::Value>
+  {
+    let mut full =
+      match serde_json::to_value(task)?
+      {
+        | serde_json::Value::Object(
+          map
+        ) => map,
+        | other => return Ok(other)
+      };
+    let mut projected =
+      serde_json::Map::new();
+    for column in &self.columns {
+      if let Some(value) =
+        full.remove(column)
+      {
+        projected.insert(
+          column.clone(),
+          value
+        );
+      }
+    }
+    Ok(serde_json::Value::Object(
+      projected
+    ))
+  }
+}
+
+/// When delimited exports wrap a field
+/// in double quotes.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+)]
+pub enum QuotePolicy {
+  /// Only fields holding the
+  /// delimiter, a quote, a line break
+  /// or edge whitespace.
+  #[default]
+  Minimal,
+  Always
+}
+
+impl QuotePolicy {
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "minimal" => Ok(Self::Minimal),
+      | "always" => Ok(Self::Always),
+      | other => {
+        Err(anyhow!(
+          "unknown quoting '{other}' \
+           (expected minimal or \
+           always)"
+        ))
+      }
+    }
+  }
+}
+
+/// CSV/TSV settings shared by `export`
+/// and `import`. Quoting follows RFC
+/// 4180: a quoted field may hold the
+/// delimiter and line breaks, and a
+/// quote inside it is doubled.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub struct Delimited {
+  pub delimiter: char,
+  pub quote:     QuotePolicy
+}
+
+impl Delimited {
+  /// Candidates `detect` picks from.
+  const DETECTABLE: [char; 4] =
+    [',', ';', '\t', '|'];
+
+  pub fn new(delimiter: char) -> Self {
+    Self {
+      delimiter,
+      quote: QuotePolicy::Minimal
+    }
+  }
+
+  /// `--delimiter`: one character, or
+  /// `tab`/`\t`. Other whitespace, the
+  /// quote character and line breaks
+  /// are refused since they cannot
+  /// separate fields unambiguously.
+  pub fn parse_delimiter(
+    raw: &str
+  ) -> anyhow::Result<char> {
+    if matches!(raw, "tab" | "\\t") {
+      return Ok('\t');
+    }
+    let mut chars = raw.chars();
+    match (chars.next(), chars.next()) {
+      | (Some(ch), None)
+        if ch == '\t'
+          || !(ch.is_whitespace()
+            || ch == '"') =>
+      {
+        Ok(ch)
+      }
+      | (Some(ch), None)
+        if ch.is_whitespace() =>
+      {
+        Err(anyhow!(
+          "delimiter cannot be \
+           whitespace (use 'tab' for \
+           TSV)"
+        ))
+      }
+      | (Some('"'), None) => {
+        Err(anyhow!(
+          "delimiter cannot be the \
+           quote character"
+        ))
+      }
+      | _ => {
+        Err(anyhow!(
+          "delimiter must be a single \
+           character, got '{raw}'"
+        ))
+      }
+    }
+  }
+
+  /// The most frequent candidate
+  /// outside quotes on the header
+  /// line; `,` when none appears.
+  pub fn detect(input: &str) -> char {
+    let mut counts = [0_usize; 4];
+    let mut quoted = false;
+    for ch in input.chars() {
+      match ch {
+        | '"' => quoted = !quoted,
+        | '\n' if !quoted => break,
+        | _ if !quoted => {
+          if let Some(pos) =
+            Self::DETECTABLE
+              .iter()
+              .position(|c| *c == ch)
+          {
+            counts[pos] += 1;
+          }
+        }
+        | _ => {}
+      }
+    }
+    Self::DETECTABLE
+      .iter()
+      .zip(counts)
+      .filter(|(_, count)| *count > 0)
+      .max_by_key(|(_, count)| *count)
+      .map_or(',', |(ch, _)| *ch)
+  }
+
+  fn field<'a>(
+    &self,
+    field: &'a str
+  ) -> std::borrow::Cow<'a, str> {
+    let needs_quotes = self.quote
+      == QuotePolicy::Always
+      || field.contains(self.delimiter)
+      || field
+        .contains(['"', '\n', '\r'])
+      || field.starts_with(
+        char::is_whitespace
+      )
+      || field
+        .ends_with(char::is_whitespace);
+    if needs_quotes {
+      format!(
+        "\"{}\"",
+        field.replace('"', "\"\"")
+      )
+      .into()
+    } else {
+      field.into()
+    }
+  }
+
+  pub fn write_record<W: Write>(
+    &self,
+    out: &mut W,
+    fields: &[String]
+  ) -> io::Result<()> {
+    let mut delimiter = [0_u8; 4];
+    let delimiter = self
+      .delimiter
+      .encode_utf8(&mut delimiter);
+    for (pos, field) in
+      fields.iter().enumerate()
+    {
+      if pos > 0 {
+        out.write_all(
+          delimiter.as_bytes()
+        )?;
+      }
+      out.write_all(
+        self.field(field).as_bytes()
+      )?;
+    }
+    out.write_all(b"\n")
+  }
+
+  /// Splits `input` into records of
+  /// fields, undoing the quoting
+  /// `write_record` applies. Blank
+  /// lines are skipped.
+  pub fn parse_records(
+    &self,
+    input: &str
+  ) -> anyhow::Result<Vec<Vec<String>>>
+  {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut chars =
+      input.chars().peekable();
+    while let Some(ch) = chars.next() {
+      if quoted {
+        match ch {
+          | '"'
+            if chars.peek()
+              == Some(&'"') =>
+          {
+            chars.next();
+            field.push('"');
+          }
+          | '"' => quoted = false,
+          | '\n' => {
+            line += 1;
+            field.push(ch);
+          }
+          | _ => field.push(ch)
+        }
+        continue;
+      }
+      match ch {
+        | '"' if field.is_empty() => {
+          quoted = true;
+        }
+        | '\r'
+          if chars.peek()
+            == Some(&'\n') => {}
+        | '\n' => {
+          line += 1;
+          record.push(std::mem::take(
+            &mut field
+          ));
+          if record.iter().any(|cell| {
+            !cell.is_empty()
+          }) || record.len() > 1
+          {
+            records.push(
+              std::mem::take(
+                &mut record
+              )
+            );
+          } else {
+            record.clear();
+          }
+        }
+        | _ if ch == self.delimiter => {
+          record.push(std::mem::take(
+            &mut field
+          ));
+        }
+        | _ => field.push(ch)
+      }
+    }
+    if quoted {
+      return Err(anyhow!(
+        "unterminated quoted field at \
+         line {line}"
+      ));
+    }
+    if !field.is_empty()
+      || !record.is_empty()
+    {
+      record.push(field);
+      records.push(record);
+    }
+    Ok(records)
+  }
+}
+
+/// A projected export value as one
+/// delimited cell: nothing for null,
+/// lists of plain values (tags,
+/// depends) joined by spaces and
+/// anything else structured as JSON.
+pub fn delimited_cell(
+  value: &serde_json::Value
+) -> String {
+  use serde_json::Value;
+  match value {
+    | Value::Null => String::new(),
+    | Value::String(text) => {
+      text.clone()
+    }
+    | Value::Array(items)
+      if items
+        .iter()
+        .all(Value::is_string) =>
+    {
+      items
+        .iter()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+    | _ => value.to_string()
+  }
+}
+
+/// How table cells wider than their
+/// column are shown once the table is
+/// fitted to the output width.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+)]
+pub enum TableLayout {
+  /// One line per row; long cells are
+  /// cut with `…`.
+  #[default]
+  Compact,
+  /// Long cells wrap onto extra lines
+  /// inside their column.
+  Wrap
+}
+
+impl TableLayout {
+  pub fn parse(
+    value: &str
+  ) -> anyhow::Result<Self> {
+    match value
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "compact" => Ok(Self::Compact),
+      | "wrap" | "wrapped" => {
+        Ok(Self::Wrap)
+      }
+      | other => {
+        Err(anyhow!(
+          "invalid table.layout \
+           '{other}': use compact or \
+           wrap"
+        ))
+      }
+    }
+  }
+}
+
+/// How a table column lines up its
+/// cells and header.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+)]
+pub enum Align {
+  #[default]
+  Left,
+  Right,
+  Center
+}
+
+impl Align {
+  pub fn parse(
+    value: &str
+  ) -> anyhow::Result<Self> {
+    match value
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "left" => Ok(Self::Left),
+      | "right" => Ok(Self::Right),
+      | "center" | "centre" => {
+        Ok(Self::Center)
+      }
+      | other => {
+        Err(anyhow!(
+          "invalid alignment \
+           '{other}': use left, right \
+           or center"
+        ))
+      }
+    }
+  }
+
+  /// `cell` padded to `width` terminal
+  /// cells.
+  fn pad(
+    self,
+    cell: &str,
+    width: usize
+  ) -> String {
+    let padding = width.saturating_sub(
+      display_width(cell)
+    );
+    let (before, after) = match self {
+      | Self::Left => (0, padding),
+      | Self::Right => (padding, 0),
+      | Self::Center => {
+        (
+          padding / 2,
+          padding - padding / 2
+        )
+      }
+    };
+    format!(
+      "{}{cell}{}",
+      " ".repeat(before),
+      " ".repeat(after)
+    )
+  }
+}
+
+/// What the ID column of reports shows
+/// (`id.display`).
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Default,
+)]
+pub enum IdDisplay {
+  /// The working-set number, which
+  /// `gc` renumbers.
+  #[default]
+  Id,
+  /// The uuid prefix from
+  /// [`Task::short_uuid`].
+  Uuid
+}
+
+impl IdDisplay {
+  pub fn parse(
+    value: &str
+  ) -> anyhow::Result<Self> {
+    match value
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "id" | "short" => Ok(Self::Id),
+      | "uuid" => Ok(Self::Uuid),
+      | other => {
+        Err(anyhow!(
+          "invalid id.display \
+           '{other}': use id or uuid"
+        ))
+      }
+    }
+  }
+
+  /// `-` for a task without an id.
+  pub fn format(
+    self,
+    task: &Task
+  ) -> String {
+    match self {
+      | Self::Id => {
+        task.id.map_or_else(
+          || "-".to_string(),
+          |id| id.to_string()
+        )
+      }
+      | Self::Uuid => task.short_uuid()
+    }
+  }
+}
+
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+enum TemplatePart<P> {
+  Text(String),
+  Field(P)
+}
+
+/// A one-line format string such as
+/// `{id} {description} ({due})`.
+/// Placeholders are resolved once when
+/// parsing; `{{` and `}}` are literal
+/// braces.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct LineTemplate<P> {
+  parts: Vec<TemplatePart<P>>
+}
+
+impl<P> LineTemplate<P> {
+  pub fn parse(
+    raw: &str,
+    resolve: impl Fn(&str) -> Option<P>
+  ) -> anyhow::Result<Self> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut chars =
+      raw.chars().peekable();
+    while let Some(ch) = chars.next() {
+      match ch {
+        | '{'
+          if chars.peek()
+            == Some(&'{') =>
+        {
+          chars.next();
+          text.push('{');
+        }
+        | '}'
+          if chars.peek()
+            == Some(&'}') =>
+        {
+          chars.next();
+          text.push('}');
+        }
+        | '{' => {
+          let mut name = String::new();
+          let mut closed = false;
+          for ch in chars.by_ref() {
+            if ch == '}' {
+              closed = true;
+              break;
+            }
+            name.push(ch);
+          }
+          if !closed {
+            return Err(anyhow!(
+              "unclosed '{{' in \
+               template"
+            ));
+          }
+          let name = name.trim();
+          let field = resolve(name)
+            .ok_or_else(|| {
+              anyhow!(
+                "unknown placeholder \
+                 '{{{name}}}'"
+              )
+            })?;
+          if !text.is_empty() {
+            parts.push(
+              TemplatePart::Text(
+                std::mem::take(
+                  &mut text
+                )
+              )
+            );
+          }
+          parts.push(
+            TemplatePart::Field(field)
+          );
+        }
+        | '}' => {
+          return Err(anyhow!(
+            "unmatched '}}' in \
+             template; write '}}}}' \
+             for a literal brace"
+          ));
+        }
+        | other => text.push(other)
+      }
+    }
+    if !text.is_empty() {
+      parts
+        .push(TemplatePart::Text(text));
+    }
+    Ok(Self {
+      parts
+    })
+  }
+
+  pub fn fields(
+    &self
+  ) -> impl Iterator<Item = &P> {
+    self.parts.iter().filter_map(
+      |part| {
+        match part {
+          | TemplatePart::Field(
+            field
+          ) => Some(field),
+          | TemplatePart::Text(_) => {
+            None
+          }
+        }
+      }
+    )
+  }
+
+  pub fn render(
+    &self,
+    value: impl Fn(&P) -> String
+  ) -> String {
+    let mut line = String::new();
+    for part in &self.parts {
+      match part {
+        | TemplatePart::Text(text) => {
+          line.push_str(text);
+        }
+        | TemplatePart::Field(
+          field
+        ) => {
+          line.push_str(&value(field));
+        }
+      }
+    }
+    line
+  }
+}
 
 #[derive(Debug, Clone)]
 pub struct Renderer {
-  color: bool
+  color:        bool,
+  limit:        Option<RowLimit>,
+  report_dates: DateFormat,
+  info_dates:   Option<DateFormat>,
+  layout:       TableLayout,
+  ids:          IdDisplay,
+  humanize:     HumanizeStyle,
+  width:        Option<usize>
 }
 
 impl Renderer {
@@ -45,11 +816,114 @@ impl Renderer {
       }
     };
 
+    let humanize = cfg
+      .get("humanize")
+      .map(|raw| {
+        HumanizeStyle::parse(&raw)
+      })
+      .transpose()
+      .context("invalid humanize")?
+      .unwrap_or_default();
+    let report_dates =
+      configured_date_format(
+        cfg,
+        "dateformat.report",
+        humanize
+      )?
+      .unwrap_or_default();
+    let info_dates =
+      configured_date_format(
+        cfg,
+        "dateformat.info",
+        humanize
+      )?;
+
+    let layout = cfg
+      .get("table.layout")
+      .map(|value| {
+        TableLayout::parse(&value)
+      })
+      .transpose()?
+      .unwrap_or_default();
+    let ids = cfg
+      .get("id.display")
+      .map(|value| {
+        IdDisplay::parse(&value)
+      })
+      .transpose()?
+      .unwrap_or_default();
+
     Ok(Self {
-      color
+      color,
+      limit: None,
+      report_dates,
+      info_dates,
+      layout,
+      ids,
+      humanize,
+      width: terminal_width()
     })
   }
 
+  /// Fits tables to `width` columns
+  /// instead of the detected terminal
+  /// width; `Some(0)` turns fitting
+  /// off.
+  pub fn set_width(
+    &mut self,
+    width: Option<usize>
+  ) {
+    if let Some(width) = width {
+      self.width =
+        Some(width).filter(|w| *w > 0);
+    }
+  }
+
+  /// Columns tables are fitted to;
+  /// `None` when fitting is off.
+  pub fn width(&self) -> Option<usize> {
+    self.width
+  }
+
+  /// How the ID column identifies a
+  /// task.
+  pub fn id_display(
+    &self
+  ) -> IdDisplay {
+    self.ids
+  }
+
+  /// Date format for table cells.
+  pub fn report_date_format(
+    &self
+  ) -> &DateFormat {
+    &self.report_dates
+  }
+
+  /// Relative dates in the `humanize`
+  /// style, for columns that ask for
+  /// them whatever `dateformat` says.
+  pub fn relative_date_format(
+    &self
+  ) -> DateFormat {
+    DateFormat::Relative(self.humanize)
+  }
+
+  /// Caps table output; rows past the
+  /// limit are dropped after sorting.
+  pub fn set_limit(
+    &mut self,
+    limit: Option<RowLimit>
+  ) {
+    self.limit = limit;
+  }
+
+  pub fn limit(
+    &self
+  ) -> Option<RowLimit> {
+    self.limit
+  }
+
   #[tracing::instrument(skip(
     self, tasks, now
   ))]
@@ -58,94 +932,187 @@ impl Renderer {
     tasks: &[Task],
     now: DateTime<Utc>
   ) -> anyhow::Result<()> {
-    let mut out = io::stdout().lock();
-
-    let headers = vec![
+    let headers = [
       "ID".to_string(),
       "Due".to_string(),
       "Project".to_string(),
       "Description".to_string(),
-      "Tags".to_string(),
+      "Tags".to_string()
     ];
+    let this = &*self;
+    this.print_table(
+      &headers,
+      &[],
+      tasks,
+      |task| this.task_row(task, now)
+    )
+  }
 
-    let mut rows =
-      Vec::with_capacity(tasks.len());
-
-    for task in tasks {
-      let id = task
-        .id
-        .map(|value| value.to_string())
-        .unwrap_or_else(|| {
-          "-".to_string()
-        });
+  fn task_row(
+    &self,
+    task: &Task,
+    now: DateTime<Utc>
+  ) -> Vec<String> {
+    let id = self.ids.format(task);
 
-      let due = task
-        .due
-        .map(format_project_date)
-        .unwrap_or_default();
+    let due = task
+      .due
+      .map(|due| {
+        self
+          .report_dates
+          .format(due, now)
+      })
+      .unwrap_or_default();
 
-      let due = if let Some(task_due) =
-        task.due
-      {
-        if task_due < now {
-          self.paint(&due, "31")
-        } else {
-          due
-        }
+    let due = if let Some(task_due) =
+      task.due
+    {
+      if task_due < now {
+        self.paint(&due, "31")
       } else {
         due
-      };
+      }
+    } else {
+      due
+    };
 
-      let id = self.paint(&id, "33");
-      let project = task
-        .project
-        .clone()
-        .unwrap_or_default();
-      let tags = task
-        .tags
-        .iter()
-        .map(|tag| format!("+{tag}"))
-        .collect::<Vec<_>>()
-        .join(" ");
+    let id = self.paint(&id, "33");
+    let project = task
+      .project
+      .clone()
+      .unwrap_or_default();
+    let tags = task
+      .tags
+      .iter()
+      .map(|tag| format!("+{tag}"))
+      .collect::<Vec<_>>()
+      .join(" ");
+
+    vec![
+      id,
+      due,
+      project,
+      task.description.clone(),
+      tags,
+    ]
+  }
+
+  /// Prints `items` as a table with
+  /// `cells` giving each row. Columns
+  /// past the end of `aligns` are
+  /// left-aligned.
+  #[tracing::instrument(skip(
+    self, headers, aligns, items, cells
+  ))]
+  pub fn print_report_table<T>(
+    &mut self,
+    headers: &[String],
+    aligns: &[Align],
+    items: &[T],
+    cells: impl Fn(&T) -> Vec<String>
+  ) -> anyhow::Result<()> {
+    self.print_table(
+      headers, aligns, items, cells
+    )
+  }
 
-      rows.push(vec![
-        id,
-        due,
-        project,
-        task.description.clone(),
-        tags,
-      ]);
+  /// One line per item from a report
+  /// template instead of a table;
+  /// honours `limit:` like the table.
+  pub fn print_report_lines<T>(
+    &mut self,
+    items: &[T],
+    line: impl Fn(&T) -> String
+  ) -> anyhow::Result<()> {
+    let mut out = io::BufWriter::new(
+      io::stdout().lock()
+    );
+    let total = items.len();
+    for item in
+      &items[..self.shown_rows(total)]
+    {
+      writeln!(out, "{}", line(item))?;
     }
-
-    write_table(
-      &mut out, &headers, &rows
+    write_limit_note(
+      &mut out,
+      self.shown_rows(total),
+      total
     )?;
+    out.flush()?;
     Ok(())
   }
 
-  #[tracing::instrument(skip(
-    self, headers, rows
-  ))]
-  pub fn print_report_table(
-    &mut self,
+  /// Rows are formatted twice, once to
+  /// size the columns and once while
+  /// writing, so only one row is held
+  /// in memory at a time and output
+  /// starts before the whole table is
+  /// built.
+  fn print_table<T>(
+    &self,
     headers: &[String],
-    rows: &[Vec<String>]
+    aligns: &[Align],
+    items: &[T],
+    cells: impl Fn(&T) -> Vec<String>
   ) -> anyhow::Result<()> {
-    let mut out = io::stdout().lock();
+    let mut out = io::BufWriter::new(
+      io::stdout().lock()
+    );
+    let total = items.len();
+    let items =
+      &items[..self.shown_rows(total)];
     write_table(
-      &mut out, headers, rows
+      &mut out,
+      headers,
+      aligns,
+      || items.iter().map(&cells),
+      self.layout,
+      self.width
     )?;
+    write_limit_note(
+      &mut out,
+      items.len(),
+      total
+    )?;
+    out.flush()?;
     Ok(())
   }
 
+  fn shown_rows(
+    &self,
+    total: usize
+  ) -> usize {
+    self
+      .limit
+      .and_then(RowLimit::max_rows)
+      .map_or(total, |max| {
+        max.min(total)
+      })
+  }
+
   #[tracing::instrument(skip(
     self, task
   ))]
   pub fn print_task_info(
     &mut self,
-    task: &Task
+    task: &Task,
+    now: DateTime<Utc>
   ) -> anyhow::Result<()> {
     let mut out = io::stdout().lock();
+    let date = |dt: DateTime<Utc>| {
+      self
+        .info_dates
+        .as_ref()
+        .map_or_else(
+          || {
+            dt.format("%Y%m%dT%H%M%SZ")
+              .to_string()
+          },
+          |format| {
+            format.format(dt, now)
+          }
+        )
+    };
 
     writeln!(
       out,
@@ -196,37 +1163,33 @@ impl Renderer {
     writeln!(
       out,
       "entry     {}",
-      task
-        .entry
-        .format("%Y%m%dT%H%M%SZ")
+      date(task.entry)
     )?;
     writeln!(
       out,
       "modified  {}",
-      task
-        .modified
-        .format("%Y%m%dT%H%M%SZ")
+      date(task.modified)
     )?;
 
     if let Some(end) = task.end {
       writeln!(
         out,
         "end       {}",
-        end.format("%Y%m%dT%H%M%SZ")
+        date(end)
       )?;
     }
     if let Some(start) = task.start {
       writeln!(
         out,
         "start     {}",
-        start.format("%Y%m%dT%H%M%SZ")
+        date(start)
       )?;
     }
     if let Some(due) = task.due {
       writeln!(
         out,
         "due       {}",
-        due.format("%Y%m%dT%H%M%SZ")
+        date(due)
       )?;
     }
     if let Some(scheduled) =
@@ -235,18 +1198,233 @@ impl Renderer {
       writeln!(
         out,
         "scheduled {}",
-        scheduled
-          .format("%Y%m%dT%H%M%SZ")
+        date(scheduled)
       )?;
     }
     if let Some(wait) = task.wait {
       writeln!(
         out,
         "wait      {}",
-        wait.format("%Y%m%dT%H%M%SZ")
+        date(wait)
+      )?;
+    }
+    if let Some(parent) = task.parent {
+      writeln!(
+        out,
+        "parent    {parent}"
+      )?;
+    }
+    if let Some(contact) = task.contact
+    {
+      writeln!(
+        out,
+        "contact   {contact}"
+      )?;
+    }
+
+    Ok(())
+  }
+
+  /// Prints `entries` oldest first, one
+  /// header line per command followed
+  /// by the fields it changed.
+  pub fn print_task_history(
+    &mut self,
+    task: &Task,
+    entries: &[&HistoryEntry],
+    now: DateTime<Utc>
+  ) -> anyhow::Result<()> {
+    let mut out = io::stdout().lock();
+    writeln!(
+      out,
+      "History of {} '{}'",
+      match (self.ids, task.id) {
+        | (IdDisplay::Id, Some(id)) => {
+          id.to_string()
+        }
+        | _ => task.uuid.to_string()
+      },
+      task.description
+    )?;
+    if entries.is_empty() {
+      writeln!(
+        out,
+        "  (no recorded changes)"
+      )?;
+      return Ok(());
+    }
+
+    for entry in entries {
+      let when = self
+        .info_dates
+        .as_ref()
+        .map_or_else(
+          || {
+            entry
+              .at
+              .format("%Y%m%dT%H%M%SZ")
+              .to_string()
+          },
+          |format| {
+            format.format(entry.at, now)
+          }
+        );
+      writeln!(
+        out,
+        "{when}  {:<9}  {}",
+        entry.action.as_str(),
+        entry.command
+      )?;
+      for change in &entry.changes {
+        if entry.action
+          == HistoryAction::Created
+        {
+          writeln!(
+            out,
+            "    {}: {}",
+            change.field,
+            display_value(
+              &change.after
+            )
+          )?;
+        } else {
+          writeln!(
+            out,
+            "    {}: {} -> {}",
+            change.field,
+            display_value(
+              &change.before
+            ),
+            display_value(
+              &change.after
+            )
+          )?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Prints one month grid per entry of
+  /// `months`, titled and headed in
+  /// `locale`. Days with due tasks
+  /// carry `*N` (`*+` past nine);
+  /// overdue days are red and today
+  /// is highlighted.
+  pub fn print_calendar(
+    &mut self,
+    months: &[NaiveDate],
+    due_counts: &BTreeMap<
+      NaiveDate,
+      usize
+    >,
+    week_start: Weekday,
+    locale: &Locale,
+    today: NaiveDate
+  ) -> anyhow::Result<()> {
+    let mut out = io::stdout().lock();
+    let weekdays: Vec<String> = (0..7)
+      .map(|offset| {
+        let mut day = week_start;
+        for _ in 0..offset {
+          day = day.succ();
+        }
+        format!(
+          "{:<4}",
+          locale.weekday_abbrev(day)
+        )
+      })
+      .collect();
+    let header = weekdays
+      .join(" ")
+      .trim_end()
+      .to_string();
+    let width = header.chars().count();
+
+    for (index, first) in
+      months.iter().enumerate()
+    {
+      if index > 0 {
+        writeln!(out)?;
+      }
+      let title = format!(
+        "{} {}",
+        locale
+          .month_name(first.month()),
+        first.year()
+      );
+      writeln!(
+        out,
+        "{}",
+        format!("{title:^width$}")
+          .trim_end()
       )?;
+      writeln!(out, "{header}")?;
+      for week in
+        month_weeks(*first, week_start)
+      {
+        let cells: Vec<String> = week
+          .iter()
+          .map(|day| {
+            let Some(day) = day else {
+              return "    ".to_string();
+            };
+            let count = due_counts
+              .get(day)
+              .copied()
+              .unwrap_or(0);
+            let marker = match count {
+              | 0 => "  ".to_string(),
+              | 1..=9 => {
+                format!("*{count}")
+              }
+              | _ => "*+".to_string()
+            };
+            let cell = format!(
+              "{:>2}{marker}",
+              day.day()
+            );
+            if *day == today {
+              self.paint(&cell, "7")
+            } else if count > 0
+              && *day < today
+            {
+              self.paint(&cell, "31")
+            } else if count > 0 {
+              self.paint(&cell, "33")
+            } else {
+              cell
+            }
+          })
+          .collect();
+        writeln!(
+          out,
+          "{}",
+          cells.join(" ").trim_end()
+        )?;
+      }
     }
 
+    let total: usize = months
+      .iter()
+      .map(|first| {
+        due_counts
+          .iter()
+          .filter(|(day, _)| {
+            day.year() == first.year()
+              && day.month()
+                == first.month()
+          })
+          .map(|(_, count)| count)
+          .sum::<usize>()
+      })
+      .sum();
+    writeln!(out)?;
+    writeln!(
+      out,
+      "{total} task(s) due; *N marks \
+       the tasks due that day."
+    )?;
     Ok(())
   }
 
@@ -264,11 +1442,104 @@ impl Renderer {
   }
 }
 
-fn write_table<W: Write>(
+/// Reads `key`, falling back to the
+/// global `dateformat`; `relative`
+/// takes its style from `humanize`.
+fn configured_date_format(
+  cfg: &Config,
+  key: &str,
+  humanize: HumanizeStyle
+) -> anyhow::Result<Option<DateFormat>>
+{
+  let (source, Some(spec)) = cfg
+    .get(key)
+    .map(|spec| (key, Some(spec)))
+    .unwrap_or_else(|| {
+      (
+        "dateformat",
+        cfg.get("dateformat")
+      )
+    })
+  else {
+    return Ok(None);
+  };
+  let format = DateFormat::parse(&spec)
+    .with_context(|| {
+      format!("invalid {source}")
+    })?;
+  let DateFormat::Relative(_) = format
+  else {
+    return Ok(Some(format));
+  };
+  Ok(Some(DateFormat::Relative(
+    humanize
+  )))
+}
+
+fn write_limit_note<W: Write>(
   mut writer: W,
-  headers: &[String],
-  rows: &[Vec<String>]
+  shown: usize,
+  total: usize
 ) -> anyhow::Result<()> {
+  if shown < total {
+    writeln!(
+      writer,
+      "showing {shown} of {total}"
+    )?;
+  }
+  Ok(())
+}
+
+/// Width of the terminal on stdout, or
+/// `None` when output is piped.
+/// `COLUMNS` wins over the tty size.
+fn terminal_width() -> Option<usize> {
+  if !io::stdout().is_terminal() {
+    return None;
+  }
+  if let Some(columns) =
+    std::env::var("COLUMNS")
+      .ok()
+      .and_then(|value| {
+        value.trim().parse().ok()
+      })
+      .filter(|columns| *columns > 0)
+  {
+    return Some(columns);
+  }
+  #[cfg(unix)]
+  {
+    rustix::termios::tcgetwinsize(
+      io::stdout()
+    )
+    .ok()
+    .map(|size| {
+      usize::from(size.ws_col)
+    })
+    .filter(|columns| *columns > 0)
+  }
+  #[cfg(not(unix))]
+  {
+    None
+  }
+}
+
+/// `rows` is called twice: the first
+/// pass measures the columns, the
+/// second writes each row as it comes.
+fn write_table<W, I, R>(
+  mut writer: W,
+  headers: &[String],
+  aligns: &[Align],
+  rows: impl Fn() -> I,
+  layout: TableLayout,
+  width: Option<usize>
+) -> anyhow::Result<()>
+where
+  W: Write,
+  I: Iterator<Item = R>,
+  R: AsRef<[String]>
+{
   let column_count = headers.len();
   let mut widths =
     vec![0usize; column_count];
@@ -276,65 +1547,241 @@ fn write_table<W: Write>(
   for (idx, header) in
     headers.iter().enumerate()
   {
-    widths[idx] = widths[idx].max(
-      UnicodeWidthStr::width(
-        header.as_str()
-      )
-    );
+    widths[idx] = widths[idx]
+      .max(display_width(header));
   }
 
-  for row in rows {
+  for row in rows() {
     for (idx, cell) in
-      row.iter().enumerate()
+      row.as_ref().iter().enumerate()
     {
-      widths[idx] = widths[idx].max(
-        UnicodeWidthStr::width(
-          strip_ansi(cell).as_str()
-        )
-      );
+      widths[idx] = widths[idx]
+        .max(display_width(cell));
     }
   }
 
+  if let Some(width) = width {
+    let header_widths: Vec<usize> =
+      headers
+        .iter()
+        .map(|header| {
+          display_width(header)
+        })
+        .collect();
+    widths = fit_widths(
+      &widths,
+      &header_widths,
+      width
+    );
+  }
+
+  let align = |idx: usize| {
+    aligns
+      .get(idx)
+      .copied()
+      .unwrap_or_default()
+  };
   for idx in 0..column_count {
     write!(
       writer,
-      "{:width$} ",
-      headers[idx],
-      width = widths[idx]
+      "{} ",
+      align(idx).pad(
+        &headers[idx],
+        widths[idx]
+      )
     )?;
   }
   writeln!(writer)?;
 
-  for idx in 0..column_count {
+  for width in
+    widths.iter().take(column_count)
+  {
     write!(
       writer,
       "{:-<width$} ",
       "",
-      width = widths[idx]
+      width = *width
     )?;
   }
   writeln!(writer)?;
 
-  for row in rows {
-    for idx in 0..column_count {
-      let cell = &row[idx];
-      let visible_width =
+  for row in rows() {
+    let cells: Vec<Vec<String>> = row
+      .as_ref()
+      .iter()
+      .zip(&widths)
+      .map(|(cell, width)| {
+        fit_cell(cell, *width, layout)
+      })
+      .collect();
+    let height = cells
+      .iter()
+      .map(Vec::len)
+      .max()
+      .unwrap_or(1);
+    for line in 0..height {
+      for idx in 0..column_count {
+        let cell = cells[idx]
+          .get(line)
+          .map_or("", String::as_str);
+        write!(
+          writer,
+          "{} ",
+          align(idx)
+            .pad(cell, widths[idx])
+        )?;
+      }
+      writeln!(writer)?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Shrinks the widest columns one cell
+/// at a time until the row, with its
+/// one-space gaps, fits `available`.
+/// No column drops below its header
+/// (or four cells), so a very narrow
+/// width still overflows rather than
+/// hiding columns.
+fn fit_widths(
+  natural: &[usize],
+  headers: &[usize],
+  available: usize
+) -> Vec<usize> {
+  let floors: Vec<usize> = natural
+    .iter()
+    .zip(headers)
+    .map(|(natural, header)| {
+      (*natural).min((*header).max(4))
+    })
+    .collect();
+  let mut widths = natural.to_vec();
+  let gaps = widths.len();
+  while widths.iter().sum::<usize>()
+    + gaps
+    > available
+  {
+    let Some((idx, _)) = widths
+      .iter()
+      .enumerate()
+      .filter(|(idx, width)| {
+        **width > floors[*idx]
+      })
+      .max_by_key(|(_, width)| **width)
+    else {
+      break;
+    };
+    widths[idx] -= 1;
+  }
+  widths
+}
+
+/// Lines of `cell` for a column of
+/// `width`. Cells that fit keep their
+/// colour; cut or wrapped ones are
+/// laid out from the plain text.
+fn fit_cell(
+  cell: &str,
+  width: usize,
+  layout: TableLayout
+) -> Vec<String> {
+  let plain = strip_ansi(cell);
+  if display_width(&plain) <= width {
+    return vec![cell.to_string()];
+  }
+  match layout {
+    | TableLayout::Compact => {
+      vec![truncate_to_width(
+        &plain, width
+      )]
+    }
+    | TableLayout::Wrap => {
+      wrap_to_width(&plain, width)
+    }
+  }
+}
+
+fn truncate_to_width(
+  text: &str,
+  width: usize
+) -> String {
+  let budget = width.saturating_sub(1);
+  let mut out = String::new();
+  let mut used = 0;
+  for grapheme in text.graphemes(true) {
+    let grapheme_width =
+      UnicodeWidthStr::width(grapheme);
+    if used + grapheme_width > budget {
+      break;
+    }
+    used += grapheme_width;
+    out.push_str(grapheme);
+  }
+  out.push('…');
+  out
+}
+
+/// Word-wraps `text` at `width`,
+/// splitting words longer than a line
+/// between grapheme clusters.
+fn wrap_to_width(
+  text: &str,
+  width: usize
+) -> Vec<String> {
+  let width = width.max(1);
+  let mut lines = Vec::new();
+  let mut line = String::new();
+  let mut used = 0;
+  for word in text.split_whitespace() {
+    let word_width =
+      UnicodeWidthStr::width(word);
+    if used > 0
+      && used + 1 + word_width <= width
+    {
+      line.push(' ');
+      line.push_str(word);
+      used += 1 + word_width;
+      continue;
+    }
+    if used > 0 {
+      lines.push(std::mem::take(
+        &mut line
+      ));
+      used = 0;
+    }
+    for grapheme in word.graphemes(true)
+    {
+      let grapheme_width =
         UnicodeWidthStr::width(
-          strip_ansi(cell).as_str()
+          grapheme
         );
-      let padding = widths[idx]
-        .saturating_sub(visible_width);
-      write!(
-        writer,
-        "{}{} ",
-        cell,
-        " ".repeat(padding)
-      )?;
+      if used + grapheme_width > width
+        && used > 0
+      {
+        lines.push(std::mem::take(
+          &mut line
+        ));
+        used = 0;
+      }
+      line.push_str(grapheme);
+      used += grapheme_width;
     }
-    writeln!(writer)?;
   }
+  if used > 0 || lines.is_empty() {
+    lines.push(line);
+  }
+  lines
+}
 
-  Ok(())
+/// Terminal columns `text` occupies:
+/// colour codes count as nothing and
+/// wide CJK or emoji clusters as two.
+fn display_width(text: &str) -> usize {
+  UnicodeWidthStr::width(
+    strip_ansi(text).as_str()
+  )
 }
 
 fn strip_ansi(s: &str) -> String {
@@ -360,3 +1807,515 @@ fn strip_ansi(s: &str) -> String {
 
   out
 }
+
+#[cfg(test)]
+mod tests {
+  use unicode_width::UnicodeWidthStr;
+
+  use super::{
+    Align,
+    Delimited,
+    ExportColumns,
+    LineTemplate,
+    QuotePolicy,
+    TableLayout,
+    delimited_cell,
+    fit_widths,
+    write_table
+  };
+  use crate::config::Config;
+  use crate::task::Task;
+
+  fn render(
+    layout: TableLayout,
+    width: Option<usize>
+  ) -> String {
+    let headers = vec![
+      "ID".to_string(),
+      "Description".to_string(),
+    ];
+    let rows = [vec![
+      "1".to_string(),
+      "Water the plants on the balcony"
+        .to_string(),
+    ]];
+    let mut out = Vec::new();
+    write_table(
+      &mut out,
+      &headers,
+      &[],
+      || rows.iter(),
+      layout,
+      width
+    )
+    .expect("write table");
+    String::from_utf8(out)
+      .expect("utf8 table")
+  }
+
+  #[test]
+  fn compact_layout_cuts_long_cells_to_the_width()
+   {
+    let table = render(
+      TableLayout::Compact,
+      Some(20)
+    );
+    let lines: Vec<&str> =
+      table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+      lines[2].trim_end(),
+      "1  Water the plant…"
+    );
+    assert!(lines.iter().all(|line| {
+      line.chars().count() <= 20
+    }));
+  }
+
+  #[test]
+  fn wrap_layout_continues_cells_on_extra_lines()
+   {
+    let table = render(
+      TableLayout::Wrap,
+      Some(20)
+    );
+    let body: Vec<&str> = table
+      .lines()
+      .skip(2)
+      .map(str::trim_end)
+      .collect();
+    assert_eq!(body, vec![
+      "1  Water the plants",
+      "   on the balcony",
+    ]);
+  }
+
+  #[test]
+  fn streamed_rows_are_sized_before_the_first_is_written()
+   {
+    let headers =
+      vec!["Description".to_string()];
+    let descriptions =
+      ["short", "a much longer one"];
+    let mut out = Vec::new();
+    write_table(
+      &mut out,
+      &headers,
+      &[],
+      || {
+        descriptions.iter().map(
+          |text| vec![text.to_string()]
+        )
+      },
+      TableLayout::Compact,
+      None
+    )
+    .expect("write table");
+    let table = String::from_utf8(out)
+      .expect("utf8 table");
+    let lines: Vec<&str> =
+      table.lines().collect();
+    assert_eq!(
+      lines[2],
+      format!("{:<17} ", "short")
+    );
+    assert_eq!(
+      lines[3],
+      "a much longer one "
+    );
+  }
+
+  #[test]
+  fn tables_keep_natural_widths_without_a_limit()
+   {
+    let table = render(
+      TableLayout::Compact,
+      None
+    );
+    assert!(table.contains(
+      "Water the plants on the balcony"
+    ));
+  }
+
+  #[test]
+  fn columns_pad_to_their_alignment() {
+    let headers = vec![
+      "ID".to_string(),
+      "Pri".to_string(),
+      "Urgency".to_string(),
+    ];
+    let rows = [
+      vec![
+        "7".to_string(),
+        "H".to_string(),
+        "12.5".to_string(),
+      ],
+      vec![
+        "12".to_string(),
+        "".to_string(),
+        "3".to_string(),
+      ]
+    ];
+    let mut out = Vec::new();
+    write_table(
+      &mut out,
+      &headers,
+      &[Align::Right, Align::Center],
+      || rows.iter(),
+      TableLayout::Compact,
+      None
+    )
+    .expect("write table");
+    let table = String::from_utf8(out)
+      .expect("utf8 table");
+    let lines: Vec<&str> =
+      table.lines().collect();
+    assert_eq!(
+      lines[0],
+      "ID Pri Urgency "
+    );
+    assert_eq!(
+      lines[2],
+      " 7  H  12.5    "
+    );
+    assert_eq!(
+      lines[3],
+      "12     3       "
+    );
+    assert!(
+      Align::parse("middle").is_err()
+    );
+  }
+
+  fn render_rows(
+    descriptions: &[&str],
+    layout: TableLayout,
+    width: Option<usize>
+  ) -> Vec<String> {
+    let headers = vec![
+      "ID".to_string(),
+      "Description".to_string(),
+      "Tags".to_string(),
+    ];
+    let rows: Vec<Vec<String>> =
+      descriptions
+        .iter()
+        .enumerate()
+        .map(|(idx, description)| {
+          vec![
+            (idx + 1).to_string(),
+            description.to_string(),
+            "+home".to_string(),
+          ]
+        })
+        .collect();
+    let mut out = Vec::new();
+    write_table(
+      &mut out,
+      &headers,
+      &[],
+      || rows.iter(),
+      layout,
+      width
+    )
+    .expect("write table");
+    String::from_utf8(out)
+      .expect("utf8 table")
+      .lines()
+      .map(str::to_string)
+      .collect()
+  }
+
+  #[test]
+  fn wide_characters_keep_columns_aligned()
+   {
+    let lines = render_rows(
+      &[
+        "plain ascii",
+        "買い物リスト",
+        "🎉 party",
+        "👩‍👩‍👧 family"
+      ],
+      TableLayout::Compact,
+      None
+    );
+    let tag_columns: Vec<usize> = lines
+      .iter()
+      .skip(2)
+      .map(|line| {
+        let at = line
+          .find("+home")
+          .expect("tags cell");
+        UnicodeWidthStr::width(
+          &line[..at]
+        )
+      })
+      .collect();
+    assert_eq!(tag_columns, vec![
+      16, 16, 16, 16
+    ]);
+  }
+
+  #[test]
+  fn truncation_counts_display_width_and_keeps_clusters_whole()
+   {
+    let lines = render_rows(
+      &[
+        "買い物リストを作る",
+        "👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧"
+      ],
+      TableLayout::Compact,
+      Some(22)
+    );
+    assert_eq!(
+      lines[2].trim_end(),
+      "1  買い物リス…  +home"
+    );
+    assert_eq!(
+      lines[3].trim_end(),
+      "2  👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧👩‍👩‍👧…  +home"
+    );
+    assert!(lines.iter().all(|line| {
+      UnicodeWidthStr::width(
+        line.as_str()
+      ) <= 22
+    }));
+  }
+
+  #[test]
+  fn fit_widths_never_shrinks_below_headers()
+   {
+    assert_eq!(
+      fit_widths(&[2, 40], &[2, 11], 5),
+      vec![2, 11]
+    );
+  }
+
+  #[test]
+  fn export_columns_project_known_fields_and_udas()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let taskrc =
+      temp.path().join("taskrc");
+    std::fs::write(
+      &taskrc,
+      "uda.estimate.type=numeric\n"
+    )
+    .expect("write taskrc");
+    let cfg =
+      Config::load(Some(&taskrc))
+        .expect("load config");
+
+    let columns = ExportColumns::parse(
+      "description, \
+       estimate,due,description",
+      &cfg
+    )
+    .expect("parse");
+    assert_eq!(columns.columns(), [
+      "description",
+      "estimate",
+      "due"
+    ]);
+
+    let mut task = Task::new_pending(
+      "write report".to_string(),
+      chrono::Utc::now(),
+      1
+    );
+    task.extra.insert(
+      "estimate".into(),
+      3.into()
+    );
+    assert_eq!(
+      columns
+        .project(&task)
+        .expect("project"),
+      serde_json::json!({
+        "description": "write report",
+        "estimate": 3,
+        "due": null
+      })
+    );
+
+    let err = ExportColumns::parse(
+      "uuid,size",
+      &cfg
+    )
+    .expect_err("unknown column");
+    assert!(err.to_string().contains(
+      "unknown column 'size'"
+    ));
+    assert!(
+      err
+        .to_string()
+        .contains("estimate")
+    );
+  }
+
+  #[test]
+  fn delimited_records_quote_and_parse_back()
+   {
+    let fields = vec![
+      "plain".to_string(),
+      "a;b".to_string(),
+      "say \"hi\"".to_string(),
+      "two\nlines".to_string(),
+      " padded".to_string(),
+      String::new(),
+    ];
+    let semicolon = Delimited::new(';');
+    let mut out = Vec::new();
+    semicolon
+      .write_record(&mut out, &fields)
+      .expect("write");
+    let written =
+      String::from_utf8(out).unwrap();
+    assert_eq!(
+      written,
+      "plain;\"a;b\";\"say \
+       \"\"hi\"\"\";\"two\nlines\";\" \
+       padded\";\n"
+    );
+    assert_eq!(
+      semicolon
+        .parse_records(&written)
+        .expect("parse"),
+      vec![fields.clone()]
+    );
+
+    let always = Delimited {
+      quote: QuotePolicy::Always,
+      ..Delimited::new('\t')
+    };
+    let mut out = Vec::new();
+    always
+      .write_record(
+        &mut out,
+        &fields[..2]
+      )
+      .expect("write");
+    assert_eq!(
+      String::from_utf8(out).unwrap(),
+      "\"plain\"\t\"a;b\"\n"
+    );
+
+    assert!(
+      semicolon
+        .parse_records("a;\"open\n")
+        .is_err()
+    );
+    assert_eq!(
+      Delimited::detect(
+        "uuid;description,x;tags\na,b,\
+         c,d"
+      ),
+      ';'
+    );
+    assert_eq!(
+      Delimited::detect("uuid\tstatus"),
+      '\t'
+    );
+    assert_eq!(
+      Delimited::detect("\"a,b\"|c"),
+      '|'
+    );
+  }
+
+  #[test]
+  fn delimiters_reject_whitespace_and_quotes()
+   {
+    assert_eq!(
+      Delimited::parse_delimiter(";")
+        .unwrap(),
+      ';'
+    );
+    assert_eq!(
+      Delimited::parse_delimiter("tab")
+        .unwrap(),
+      '\t'
+    );
+    for bad in
+      [" ", "", "\"", ";;", "\n"]
+    {
+      assert!(
+        Delimited::parse_delimiter(bad)
+          .is_err(),
+        "{bad:?}"
+      );
+    }
+    assert_eq!(
+      delimited_cell(
+        &serde_json::json!(["a", "b"])
+      ),
+      "a b"
+    );
+    assert_eq!(
+      delimited_cell(
+        &serde_json::Value::Null
+      ),
+      ""
+    );
+    assert_eq!(
+      delimited_cell(
+        &serde_json::json!(
+          [{"k": 1}]
+        )
+      ),
+      "[{\"k\":1}]"
+    );
+  }
+
+  #[test]
+  fn line_templates_resolve_placeholders_and_escape_braces()
+   {
+    let resolve = |name: &str| {
+      ["id", "due"]
+        .contains(&name)
+        .then(|| name.to_string())
+    };
+    let template = LineTemplate::parse(
+      "{id} {{x}} ({ due })",
+      resolve
+    )
+    .expect("parse template");
+    assert_eq!(
+      template
+        .fields()
+        .cloned()
+        .collect::<Vec<_>>(),
+      vec!["id", "due"]
+    );
+    assert_eq!(
+      template.render(|field| {
+        if field == "id" {
+          "7".to_string()
+        } else {
+          String::new()
+        }
+      }),
+      "7 {x} ()"
+    );
+
+    for (raw, message) in [
+      (
+        "{size}",
+        "unknown placeholder '{size}'"
+      ),
+      ("{id", "unclosed '{'"),
+      ("id}", "unmatched '}'")
+    ] {
+      let err = LineTemplate::parse(
+        raw, resolve
+      )
+      .expect_err(raw);
+      assert!(
+        err
+          .to_string()
+          .contains(message),
+        "{raw}: {err}"
+      );
+    }
+  }
+}
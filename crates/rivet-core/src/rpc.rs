@@ -0,0 +1,1182 @@
+//! `task rpc`: newline-delimited
+//! JSON-RPC 2.0 on stdin/stdout for
+//! editors and other tools.
+//!
+//! Each input line is one request
+//! object, answered by one line on
+//! stdout carrying the same `id`.
+//! Requests without an `id` are
+//! notifications and get no answer.
+//! Params and results are the shared
+//! DTOs the GUI uses:
+//!
+//! - `list`: `TasksListArgs` ->
+//!   `[TaskDto]`; `query` is a filter
+//!   expression as on the command line
+//! - `add`: `TaskCreate` -> `TaskDto`
+//! - `update`: `TaskUpdateArgs` ->
+//!   `TaskDto`
+//! - `done`: `TaskIdArg` -> `TaskDto`
+//! - `delete`: `TaskIdArg` -> `null`
+//!
+//! Protocol problems use the standard
+//! JSON-RPC codes; a date param that
+//! does not parse is `-32602`. A
+//! failed command answers with its
+//! exit code as `code` and `data.kind`
+//! set to `usage`,
+//! `no_match` or `failure`.
+
+use std::collections::BTreeSet;
+use std::io::{
+  BufRead,
+  Write
+};
+
+use chrono::{
+  DateTime,
+  NaiveDate,
+  Utc
+};
+use rivet_gui_shared::{
+  TaskCreate,
+  TaskDto,
+  TaskIdArg,
+  TaskStatus,
+  TaskUpdateArgs,
+  TasksListArgs
+};
+use serde::de::DeserializeOwned;
+use serde_json::{
+  Value,
+  json
+};
+use tracing::{
+  debug,
+  info
+};
+use uuid::Uuid;
+
+use crate::cli::split_command_line;
+use crate::config::Config;
+use crate::datastore::DataStore;
+use crate::datetime::{
+  DefaultTimes,
+  parse_date_expr,
+  to_project_date
+};
+use crate::dto::{
+  apply_patch,
+  child_index,
+  parse_date,
+  priority_to_core,
+  set_detail,
+  task_to_dto,
+  view_status
+};
+use crate::exit::{
+  CommandError,
+  exit_code,
+  usage_error
+};
+use crate::filter::Filter;
+use crate::history::diff_snapshots;
+use crate::hooks::HookRunner;
+use crate::recur::{
+  RecurMode,
+  next_instance
+};
+use crate::task::{
+  CompletionPolicy,
+  DescriptionLimit,
+  Status,
+  Task,
+  TaskDefaults,
+  newly_unblocked,
+  open_descendants
+};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Why a request failed, before it is
+/// turned into an error object.
+enum Failure {
+  Protocol(i64, String),
+  Command(anyhow::Error)
+}
+
+/// Answers requests from `input` until
+/// it ends. Only I/O errors on the
+/// streams themselves stop the loop.
+pub fn serve(
+  store: &mut DataStore,
+  cfg: &Config,
+  hooks: &HookRunner,
+  input: impl BufRead,
+  mut output: impl Write
+) -> anyhow::Result<()> {
+  info!("rpc session started");
+  for line in input.lines() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    if let Some(response) = handle_line(
+      store, cfg, hooks, &line
+    ) {
+      serde_json::to_writer(
+        &mut output,
+        &response
+      )?;
+      output.write_all(b"\n")?;
+      output.flush()?;
+    }
+  }
+  info!("rpc session ended");
+  Ok(())
+}
+
+fn handle_line(
+  store: &mut DataStore,
+  cfg: &Config,
+  hooks: &HookRunner,
+  line: &str
+) -> Option<Value> {
+  let request: Value =
+    match serde_json::from_str(line) {
+      | Ok(request) => request,
+      | Err(err) => {
+        return Some(error_response(
+          Value::Null,
+          Failure::Protocol(
+            PARSE_ERROR,
+            format!(
+              "parse error: {err}"
+            )
+          )
+        ));
+      }
+    };
+  let Value::Object(mut request) =
+    request
+  else {
+    return Some(error_response(
+      Value::Null,
+      Failure::Protocol(
+        INVALID_REQUEST,
+        "request must be an object"
+          .to_string()
+      )
+    ));
+  };
+  let id = request.remove("id");
+  let method = request
+    .get("method")
+    .and_then(Value::as_str)
+    .map(ToString::to_string);
+  let params = request
+    .remove("params")
+    .unwrap_or(Value::Null);
+
+  let result = match method {
+    | Some(method) => {
+      debug!(
+        method,
+        ?id,
+        "rpc request"
+      );
+      call(
+        store, cfg, hooks, &method,
+        params
+      )
+    }
+    | None => {
+      Err(Failure::Protocol(
+        INVALID_REQUEST,
+        "missing method".to_string()
+      ))
+    }
+  };
+  let id = id?;
+  Some(match result {
+    | Ok(result) => {
+      json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": result
+      })
+    }
+    | Err(failure) => {
+      error_response(id, failure)
+    }
+  })
+}
+
+fn call(
+  store: &mut DataStore,
+  cfg: &Config,
+  hooks: &HookRunner,
+  method: &str,
+  params: Value
+) -> Result<Value, Failure> {
+  let now = Utc::now();
+  let result = match method {
+    | "list" => {
+      to_value(list(
+        store,
+        params_as(params)?,
+        now
+      ))
+    }
+    | "add" => {
+      let create: TaskCreate =
+        params_as(params)?;
+      check_dates(
+        [
+          (
+            "due",
+            create.due.as_deref()
+          ),
+          (
+            "wait",
+            create.wait.as_deref()
+          ),
+          (
+            "scheduled",
+            create.scheduled.as_deref()
+          )
+        ],
+        now
+      )?;
+      to_value(add(
+        store, cfg, hooks, create, now
+      ))
+    }
+    | "update" => {
+      let update_args: TaskUpdateArgs =
+        params_as(params)?;
+      let patch = &update_args.patch;
+      check_dates(
+        [
+          ("due", patch.due.as_ref()),
+          ("wait", patch.wait.as_ref()),
+          (
+            "scheduled",
+            patch.scheduled.as_ref()
+          )
+        ]
+        .map(
+          |(field, value)| {
+            (
+              field,
+              value.and_then(
+                Option::as_deref
+              )
+            )
+          }
+        ),
+        now
+      )?;
+      to_value(update(
+        store,
+        cfg,
+        hooks,
+        update_args,
+        now
+      ))
+    }
+    | "done" => {
+      let TaskIdArg {
+        uuid
+      } = params_as(params)?;
+      to_value(done(
+        store, cfg, hooks, uuid, now
+      ))
+    }
+    | "delete" => {
+      let TaskIdArg {
+        uuid
+      } = params_as(params)?;
+      to_value(delete(
+        store, hooks, uuid, now
+      ))
+    }
+    | other => {
+      return Err(Failure::Protocol(
+        METHOD_NOT_FOUND,
+        format!(
+          "unknown method: {other}"
+        )
+      ));
+    }
+  };
+  result.map_err(Failure::Command)
+}
+
+/// `null` params stand for `{}` so
+/// methods whose fields are all
+/// optional can omit them.
+fn params_as<T: DeserializeOwned>(
+  params: Value
+) -> Result<T, Failure> {
+  let params = if params.is_null() {
+    json!({})
+  } else {
+    params
+  };
+  serde_json::from_value(params)
+    .map_err(|err| {
+      Failure::Protocol(
+        INVALID_PARAMS,
+        format!(
+          "invalid params: {err}"
+        )
+      )
+    })
+}
+
+/// Dates that don't parse, including
+/// offsets past the calendar's range,
+/// are bad params rather than failed
+/// commands.
+fn check_dates(
+  dates: [(&str, Option<&str>); 3],
+  now: DateTime<Utc>
+) -> Result<(), Failure> {
+  for (field, value) in dates {
+    let Some(value) = value else {
+      continue;
+    };
+    if let Err(err) =
+      parse_date_expr(value, now)
+    {
+      return Err(Failure::Protocol(
+        INVALID_PARAMS,
+        format!(
+          "invalid {field}: {err:#}"
+        )
+      ));
+    }
+  }
+  Ok(())
+}
+
+fn to_value<T: serde::Serialize>(
+  result: anyhow::Result<T>
+) -> anyhow::Result<Value> {
+  Ok(serde_json::to_value(result?)?)
+}
+
+fn error_response(
+  id: Value,
+  failure: Failure
+) -> Value {
+  let error = match failure {
+    | Failure::Protocol(
+      code,
+      message
+    ) => {
+      json!({
+        "code": code,
+        "message": message
+      })
+    }
+    | Failure::Command(err) => {
+      let kind = err
+        .chain()
+        .find_map(|cause| {
+          cause.downcast_ref::<CommandError>()
+        })
+        .map_or("failure", |err| {
+          err.kind().as_str()
+        });
+      json!({
+        "code": exit_code(&err),
+        "message": format!("{err:#}"),
+        "data": { "kind": kind }
+      })
+    }
+  };
+  json!({
+    "jsonrpc": "2.0",
+    "id": id,
+    "error": error
+  })
+}
+
+fn list(
+  store: &DataStore,
+  args: TasksListArgs,
+  now: DateTime<Utc>
+) -> anyhow::Result<Vec<TaskDto>> {
+  let terms = split_command_line(
+    args.query.as_deref().unwrap_or("")
+  )
+  .map_err(usage_error)?;
+  let filter =
+    Filter::parse(&terms, now)?;
+  let completed_from = parse_day_bound(
+    args.completed_from.as_deref(),
+    "completed_from"
+  )?;
+  let completed_to = parse_day_bound(
+    args.completed_to.as_deref(),
+    "completed_to"
+  )?;
+  let limit = args
+    .limit
+    .filter(|limit| *limit > 0)
+    .unwrap_or(usize::MAX);
+
+  // Archived tasks only show up when
+  // asked for by status or query.
+  let show_archived = args.status
+    == Some(TaskStatus::Archived)
+    || filter
+      .has_explicit_status_filter();
+
+  let mut tasks =
+    store.load_pending()?;
+  tasks.extend(store.load_completed()?);
+  let mut children =
+    child_index(&tasks);
+  Ok(
+    tasks
+      .into_iter()
+      .filter(|task| {
+        (show_archived
+          || task.status
+            != Status::Archived)
+          && args.status.as_ref().is_none_or(
+          |status| {
+            view_status(task, now)
+              == *status
+          }
+        ) && args
+          .project
+          .as_ref()
+          .is_none_or(|project| {
+            task.project.as_ref()
+              == Some(project)
+          })
+          && args.tag.as_ref().is_none_or(
+            |tag| task.tags.contains(tag)
+          )
+          && args.contact.is_none_or(
+            |contact| {
+              task.contact == Some(contact)
+            }
+          )
+          && completed_within(
+            task,
+            completed_from,
+            completed_to
+          )
+          && filter
+            .matches_without_waiting_guard(
+              task, now
+            )
+      })
+      .take(limit)
+      .map(|task| {
+        let subtasks = children
+          .remove(&task.uuid)
+          .unwrap_or_default();
+        task_to_dto(task, subtasks, now)
+      })
+      .collect()
+  )
+}
+
+/// Tasks without an `end` fail as soon
+/// as either bound is set.
+fn completed_within(
+  task: &Task,
+  from: Option<NaiveDate>,
+  to: Option<NaiveDate>
+) -> bool {
+  if from.is_none() && to.is_none() {
+    return true;
+  }
+  task
+    .end
+    .map(to_project_date)
+    .is_some_and(|day| {
+      from
+        .is_none_or(|from| day >= from)
+        && to.is_none_or(|to| day <= to)
+    })
+}
+
+fn add(
+  store: &DataStore,
+  cfg: &Config,
+  hooks: &HookRunner,
+  create: TaskCreate,
+  now: DateTime<Utc>
+) -> anyhow::Result<TaskDto> {
+  let title = create.title.trim();
+  if title.is_empty() {
+    return Err(CommandError::usage(
+      "task title is required"
+    ));
+  }
+  let pending = store.load_pending()?;
+  let completed =
+    store.load_completed()?;
+  let next_id = store.next_id(&pending);
+
+  let mut task = Task::new_pending(
+    title.to_string(),
+    now,
+    next_id
+  );
+  set_detail(
+    &mut task,
+    &create.description
+  );
+  DescriptionLimit::from_config(cfg)
+    .and_then(|limit| {
+      limit.apply(&mut task, now)
+    })
+    .map_err(usage_error)?;
+  task.project = create.project;
+  task.tags = create.tags;
+  task.priority = create
+    .priority
+    .map(priority_to_core);
+  let times =
+    DefaultTimes::from_config(cfg)
+      .map_err(usage_error)?;
+  task.due = parse_date(
+    create.due.as_deref(),
+    now,
+    times.due
+  )?;
+  task.wait = parse_date(
+    create.wait.as_deref(),
+    now,
+    times.wait
+  )?;
+  task.scheduled = parse_date(
+    create.scheduled.as_deref(),
+    now,
+    times.scheduled
+  )?;
+  task.contact = create.contact;
+  TaskDefaults::from_config(
+    cfg,
+    store
+      .get_active_context()?
+      .as_deref()
+  )
+  .apply(&mut task);
+  task = hooks.apply_on_add(&task)?;
+  if task.id.is_none() {
+    task.id = Some(next_id);
+  }
+
+  store.push_undo_snapshot(
+    &pending, &completed
+  )?;
+  store
+    .add_task(pending, task.clone())?;
+  record_history(
+    store,
+    "add",
+    None,
+    Some(&task),
+    now
+  )?;
+  Ok(task_to_dto(task, Vec::new(), now))
+}
+
+fn update(
+  store: &DataStore,
+  cfg: &Config,
+  hooks: &HookRunner,
+  update: TaskUpdateArgs,
+  now: DateTime<Utc>
+) -> anyhow::Result<TaskDto> {
+  let mut pending =
+    store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+  let completed_before =
+    completed.clone();
+
+  let in_pending =
+    pending.iter().any(|task| {
+      task.uuid == update.uuid
+    });
+  let tasks = if in_pending {
+    &mut pending
+  } else {
+    &mut completed
+  };
+  let task = tasks
+    .iter_mut()
+    .find(|task| {
+      task.uuid == update.uuid
+    })
+    .ok_or_else(|| {
+      task_not_found(update.uuid)
+    })?;
+  let old = task.clone();
+  apply_patch(
+    task,
+    update.patch,
+    now,
+    &DefaultTimes::from_config(cfg)
+      .map_err(usage_error)?
+  )?;
+  // Titles that were already over the
+  // limit are left alone until edited.
+  if task.description != old.description
+  {
+    DescriptionLimit::from_config(cfg)
+      .and_then(|limit| {
+        limit.apply(task, now)
+      })
+      .map_err(usage_error)?;
+  }
+  task.modified = now;
+  *task = hooks
+    .apply_on_modify(&old, task)?;
+  let updated = task.clone();
+
+  store.push_undo_snapshot(
+    &pending_before,
+    &completed_before
+  )?;
+  if in_pending {
+    store.save_pending(&pending)?;
+  } else {
+    store.save_completed(&completed)?;
+  }
+  record_history(
+    store,
+    "update",
+    Some(&old),
+    Some(&updated),
+    now
+  )?;
+  let children = child_index(
+    &pending
+      .into_iter()
+      .chain(completed)
+      .collect::<Vec<_>>()
+  )
+  .remove(&updated.uuid)
+  .unwrap_or_default();
+  Ok(task_to_dto(
+    updated, children, now
+  ))
+}
+
+/// Completes one open task like `done`,
+/// spawning the next instance of a
+/// recurring one unless
+/// `recur.mode=lazy`. Open subtasks
+/// follow `on_complete.subtasks`;
+/// `ask` has no one to ask and leaves
+/// them open.
+fn done(
+  store: &DataStore,
+  cfg: &Config,
+  hooks: &HookRunner,
+  uuid: Uuid,
+  now: DateTime<Utc>
+) -> anyhow::Result<TaskDto> {
+  let policy =
+    CompletionPolicy::from_config(cfg)
+      .map_err(usage_error)?;
+  let recur_mode =
+    RecurMode::from_config(cfg)?;
+  let mut pending =
+    store.load_pending()?;
+  let mut completed =
+    store.load_completed()?;
+  let pending_before = pending.clone();
+  let completed_before =
+    completed.clone();
+
+  open_task_index(&pending, uuid, now)?;
+  let tree: Vec<Task> = pending
+    .iter()
+    .chain(&completed)
+    .cloned()
+    .collect();
+  let open_children = open_descendants(
+    &tree,
+    &BTreeSet::from([uuid])
+  );
+  let mut targets = vec![uuid];
+  if !open_children.is_empty()
+    && policy.cascade_subtasks(
+      open_children.len(),
+      |_| Ok(false)
+    )?
+  {
+    targets.extend(open_children);
+  }
+
+  let mut changes = Vec::new();
+  let mut finished = Vec::new();
+  for target in targets {
+    let Some(idx) =
+      pending.iter().position(|task| {
+        task.uuid == target
+      })
+    else {
+      continue;
+    };
+    let old = pending.remove(idx);
+    let mut task = old.clone();
+    task.status = Status::Completed;
+    task.end = Some(now);
+    task.start = None;
+    task.modified = now;
+    task = hooks
+      .apply_on_modify(&old, &task)?;
+    if task.status == Status::Completed
+    {
+      completed.push(task.clone());
+      finished.push(task.clone());
+    } else {
+      pending.insert(idx, task.clone());
+    }
+    changes.push((Some(old), task));
+  }
+  let Some((_, task)) =
+    changes.first().cloned()
+  else {
+    return Err(anyhow::anyhow!(
+      "task not found"
+    ));
+  };
+
+  for closed in
+    finished.iter().filter(|_| {
+      recur_mode == RecurMode::Eager
+    })
+  {
+    if let Some(next) = next_instance(
+      closed,
+      &pending,
+      store.next_id(&pending),
+      now
+    ) {
+      pending.push(next.clone());
+      changes.push((None, next));
+    }
+  }
+
+  store.push_undo_snapshot(
+    &pending_before,
+    &completed_before
+  )?;
+  store.save_pending(&pending)?;
+  store.save_completed(&completed)?;
+  for (old, new) in &changes {
+    record_history(
+      store,
+      "done",
+      old.as_ref(),
+      Some(new),
+      now
+    )?;
+  }
+  if policy.notify_unblocked {
+    for unblocked in newly_unblocked(
+      &pending, &finished
+    ) {
+      info!(
+        uuid = %unblocked.uuid,
+        description = %unblocked.description,
+        "task unblocked"
+      );
+    }
+  }
+  let children = child_index(
+    &pending
+      .into_iter()
+      .chain(completed)
+      .collect::<Vec<_>>()
+  )
+  .remove(&task.uuid)
+  .unwrap_or_default();
+  Ok(task_to_dto(task, children, now))
+}
+
+/// Soft-deletes one open task like
+/// `delete`.
+fn delete(
+  store: &DataStore,
+  hooks: &HookRunner,
+  uuid: Uuid,
+  now: DateTime<Utc>
+) -> anyhow::Result<()> {
+  let mut pending =
+    store.load_pending()?;
+  let pending_before = pending.clone();
+  let idx = open_task_index(
+    &pending, uuid, now
+  )?;
+  let old = pending[idx].clone();
+  let mut task = old.clone();
+  task.status = Status::Deleted;
+  task.start = None;
+  task.end = Some(now);
+  task.modified = now;
+  task = hooks
+    .apply_on_modify(&old, &task)?;
+  pending[idx] = task.clone();
+
+  store.push_undo_snapshot(
+    &pending_before,
+    &store.load_completed()?
+  )?;
+  store.save_pending(&pending)?;
+  record_history(
+    store,
+    "delete",
+    Some(&old),
+    Some(&task),
+    now
+  )
+}
+
+fn open_task_index(
+  pending: &[Task],
+  uuid: Uuid,
+  now: DateTime<Utc>
+) -> anyhow::Result<usize> {
+  let idx = pending
+    .iter()
+    .position(|task| task.uuid == uuid)
+    .ok_or_else(|| {
+      task_not_found(uuid)
+    })?;
+  let task = &pending[idx];
+  if task.status == Status::Pending
+    || task.is_waiting(now)
+  {
+    Ok(idx)
+  } else {
+    Err(CommandError::usage(format!(
+      "task {uuid} is not pending"
+    )))
+  }
+}
+
+fn task_not_found(
+  uuid: Uuid
+) -> anyhow::Error {
+  CommandError::no_match().context(
+    format!("task {uuid} not found")
+  )
+}
+
+/// Audit-log entry for one RPC write,
+/// with the command prefixed `rpc:`
+/// the way GUI writes use `gui:`.
+fn record_history(
+  store: &DataStore,
+  method: &str,
+  before: Option<&Task>,
+  after: Option<&Task>,
+  now: DateTime<Utc>
+) -> anyhow::Result<()> {
+  let entries = diff_snapshots(
+    &format!("rpc:{method}"),
+    before
+      .map(std::slice::from_ref)
+      .unwrap_or_default(),
+    after
+      .map(std::slice::from_ref)
+      .unwrap_or_default(),
+    now
+  );
+  store.append_history(&entries)
+}
+
+fn parse_day_bound(
+  value: Option<&str>,
+  field: &str
+) -> anyhow::Result<Option<NaiveDate>> {
+  value
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(|value| {
+      NaiveDate::parse_from_str(
+        value, "%Y-%m-%d"
+      )
+      .map_err(|_| {
+        CommandError::usage(format!(
+          "invalid {field}: {value} \
+           (expected YYYY-MM-DD)"
+        ))
+      })
+    })
+    .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{
+    Value,
+    json
+  };
+
+  use super::serve;
+  use crate::config::Config;
+  use crate::datastore::DataStore;
+  use crate::hooks::HookRunner;
+  use crate::task::Task;
+
+  fn session(
+    requests: &[Value]
+  ) -> Vec<Value> {
+    session_with("", &[], requests)
+  }
+
+  fn session_with(
+    taskrc_text: &str,
+    tasks: &[Task],
+    requests: &[Value]
+  ) -> Vec<Value> {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let taskrc =
+      temp.path().join("taskrc");
+    std::fs::write(
+      &taskrc,
+      taskrc_text
+    )
+    .expect("write taskrc");
+    let cfg =
+      Config::load(Some(&taskrc))
+        .expect("load config");
+    let mut store = DataStore::open(
+      &temp.path().join("data")
+    )
+    .expect("open store");
+    store
+      .save_pending(tasks)
+      .expect("seed tasks");
+    let hooks = HookRunner::new(
+      &cfg,
+      &store.data_dir
+    );
+    let input = requests
+      .iter()
+      .map(|request| {
+        format!("{request}\n")
+      })
+      .collect::<String>()
+      + "not json\n";
+    let mut output = Vec::new();
+    serve(
+      &mut store,
+      &cfg,
+      &hooks,
+      input.as_bytes(),
+      &mut output
+    )
+    .expect("serve");
+    String::from_utf8(output)
+      .expect("utf8")
+      .lines()
+      .map(|line| {
+        serde_json::from_str(line)
+          .expect("response json")
+      })
+      .collect()
+  }
+
+  #[test]
+  fn rpc_answers_requests_by_id_and_skips_notifications()
+   {
+    let responses = session(&[
+      json!({"jsonrpc": "2.0", "id": 1, "method": "add", "params": {
+        "title": "write report", "description": "", "project": "work",
+        "tags": ["docs"], "priority": "High", "due": null, "wait": null,
+        "scheduled": null
+      }}),
+      json!({"jsonrpc": "2.0", "method": "add", "params": {
+        "title": "quiet add", "description": "", "project": null,
+        "tags": [], "priority": null, "due": null, "wait": null,
+        "scheduled": null
+      }}),
+      json!({"jsonrpc": "2.0", "id": "q", "method": "list",
+        "params": {"query": "project:work"}})
+    ]);
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0]["id"], 1);
+    let added = &responses[0]["result"];
+    assert_eq!(
+      added["title"],
+      "write report"
+    );
+    assert_eq!(
+      added["priority"],
+      "High"
+    );
+    assert_eq!(
+      added["status"],
+      "Pending"
+    );
+
+    assert_eq!(responses[1]["id"], "q");
+    let listed = responses[1]["result"]
+      .as_array()
+      .expect("list result");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(
+      listed[0]["uuid"],
+      added["uuid"]
+    );
+    assert_eq!(
+      responses[2]["error"]["code"],
+      -32700
+    );
+    assert_eq!(
+      responses[2]["id"],
+      Value::Null
+    );
+  }
+
+  #[test]
+  fn rpc_errors_carry_kind_and_exit_code()
+   {
+    let responses = session(&[
+      json!({"jsonrpc": "2.0", "id": 1, "method": "frobnicate"}),
+      json!({"jsonrpc": "2.0", "id": 2, "method": "done",
+        "params": {"uuid": "00000000-0000-0000-0000-000000000000"}}),
+      json!({"jsonrpc": "2.0", "id": 3, "method": "done",
+        "params": {"uuid": 7}}),
+      json!({"jsonrpc": "2.0", "id": 4, "method": "list",
+        "params": {"query": "due.before:notadate"}})
+    ]);
+    assert_eq!(
+      responses[0]["error"]["code"],
+      -32601
+    );
+    assert_eq!(
+      responses[1]["error"]["code"],
+      crate::exit::EXIT_NO_MATCH
+    );
+    assert_eq!(
+      responses[1]["error"]["data"]
+        ["kind"],
+      "no_match"
+    );
+    assert_eq!(
+      responses[2]["error"]["code"],
+      -32602
+    );
+    assert_eq!(
+      responses[3]["error"]["data"]
+        ["kind"],
+      "usage"
+    );
+    assert_eq!(
+      responses[3]["error"]["code"],
+      crate::exit::EXIT_USAGE
+    );
+  }
+
+  #[test]
+  fn rpc_rejects_out_of_range_dates_as_invalid_params()
+   {
+    let responses = session(&[
+      json!({"jsonrpc": "2.0", "id": 1, "method": "add", "params": {
+        "title": "far off", "description": "", "project": null,
+        "tags": [], "priority": null, "due": "+99999999999d",
+        "wait": null, "scheduled": null
+      }}),
+      json!({"jsonrpc": "2.0", "id": 2, "method": "update", "params": {
+        "uuid": "00000000-0000-0000-0000-000000000000",
+        "patch": {"wait": "-99999999999h"}
+      }}),
+      json!({"jsonrpc": "2.0", "id": 3, "method": "list",
+        "params": {"query": ""}})
+    ]);
+    for response in &responses[..2] {
+      assert_eq!(
+        response["error"]["code"],
+        -32602
+      );
+    }
+    assert_eq!(responses[2]["id"], 3);
+    assert_eq!(
+      responses[2]["result"],
+      json!([])
+    );
+  }
+
+  #[test]
+  fn rpc_done_follows_the_subtask_policy()
+   {
+    let now = chrono::Utc::now();
+    let parent = Task::new_pending(
+      "parent".to_string(),
+      now,
+      1
+    );
+    let mut child = Task::new_pending(
+      "child".to_string(),
+      now,
+      2
+    );
+    child.parent = Some(parent.uuid);
+    let tasks = [parent.clone(), child];
+    let requests = [
+      json!({"jsonrpc": "2.0", "id": 1, "method": "done",
+        "params": {"uuid": parent.uuid}}),
+      json!({"jsonrpc": "2.0", "id": 2, "method": "list",
+        "params": {"query": "status:pending"}})
+    ];
+    let pending =
+      |responses: &[Value]| {
+        responses[1]["result"]
+          .as_array()
+          .expect("list result")
+          .len()
+      };
+
+    let blocked = session_with(
+      "on_complete.subtasks=block\n",
+      &tasks,
+      &requests
+    );
+    assert!(
+      blocked[0]["error"]["message"]
+        .as_str()
+        .expect("message")
+        .contains("1 open subtask(s)")
+    );
+    assert_eq!(pending(&blocked), 2);
+
+    let cascaded = session_with(
+      "on_complete.subtasks=cascade\n",
+      &tasks,
+      &requests
+    );
+    assert_eq!(
+      cascaded[0]["result"]["status"],
+      "Completed"
+    );
+    assert_eq!(pending(&cascaded), 0);
+
+    let warned = session_with(
+      "", &tasks, &requests
+    );
+    assert_eq!(pending(&warned), 1);
+  }
+}
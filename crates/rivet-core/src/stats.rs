@@ -0,0 +1,396 @@
+//! Usage statistics behind
+//! `task stats tags|projects`: how many
+//! tasks carry each tag or project, how
+//! many of those are still open, when
+//! one was last touched and what it
+//! tends to appear with. Deleted tasks
+//! are not counted.
+
+use std::collections::{
+  BTreeMap,
+  HashMap
+};
+
+use anyhow::anyhow;
+use chrono::{
+  DateTime,
+  Utc
+};
+use serde::Serialize;
+
+use crate::task::{
+  Status,
+  Task
+};
+
+/// Co-occurring names kept per entry.
+pub const CO_OCCURRENCE_LIMIT: usize =
+  3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoOccurrence {
+  pub name:  String,
+  pub count: usize
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageStat {
+  pub name:         String,
+  /// Tasks carrying it, open or
+  /// closed.
+  pub count:        usize,
+  /// Pending and waiting tasks only.
+  pub open:         usize,
+  /// Latest `modified` among them.
+  pub last_used:    DateTime<Utc>,
+  /// Other tags on the same tasks (the
+  /// tags used inside a project), most
+  /// frequent first.
+  pub co_occurring: Vec<CoOccurrence>
+}
+
+impl UsageStat {
+  /// No open task carries it any more.
+  pub fn is_unused(&self) -> bool {
+    self.open == 0
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub enum StatsSort {
+  /// Most used first.
+  #[default]
+  Count,
+  /// Most recently used first.
+  Recent,
+  Name
+}
+
+impl StatsSort {
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw.trim() {
+      | "count" => Ok(Self::Count),
+      | "recent" => Ok(Self::Recent),
+      | "name" => Ok(Self::Name),
+      | other => {
+        Err(anyhow!(
+          "unknown sort '{other}' \
+           (expected count, recent or \
+           name)"
+        ))
+      }
+    }
+  }
+
+  /// Ties fall back to the name so the
+  /// order is stable.
+  pub fn apply(
+    self,
+    stats: &mut [UsageStat]
+  ) {
+    stats.sort_by(|a, b| {
+      let primary = match self {
+        | Self::Count => {
+          b.count.cmp(&a.count)
+        }
+        | Self::Recent => {
+          b.last_used.cmp(&a.last_used)
+        }
+        | Self::Name => {
+          std::cmp::Ordering::Equal
+        }
+      };
+      primary.then_with(|| {
+        a.name.cmp(&b.name)
+      })
+    });
+  }
+}
+
+#[derive(Default)]
+struct Tally {
+  count:        usize,
+  open:         usize,
+  last_used:    Option<DateTime<Utc>>,
+  co_occurring: HashMap<String, usize>
+}
+
+impl Tally {
+  fn add<'a>(
+    &mut self,
+    task: &Task,
+    others: impl Iterator<Item = &'a String>
+  ) {
+    self.count += 1;
+    if matches!(
+      task.status,
+      Status::Pending | Status::Waiting
+    ) {
+      self.open += 1;
+    }
+    self.last_used = self
+      .last_used
+      .max(Some(task.modified));
+    for other in others {
+      *self
+        .co_occurring
+        .entry(other.clone())
+        .or_insert(0) += 1;
+    }
+  }
+
+  fn finish(
+    self,
+    name: String
+  ) -> UsageStat {
+    let mut co_occurring: Vec<
+      CoOccurrence
+    > = self
+      .co_occurring
+      .into_iter()
+      .map(|(name, count)| {
+        CoOccurrence {
+          name,
+          count
+        }
+      })
+      .collect();
+    co_occurring.sort_by(|a, b| {
+      b.count.cmp(&a.count).then_with(
+        || a.name.cmp(&b.name)
+      )
+    });
+    co_occurring
+      .truncate(CO_OCCURRENCE_LIMIT);
+    UsageStat {
+      name,
+      count: self.count,
+      open: self.open,
+      last_used: self
+        .last_used
+        .unwrap_or_default(),
+      co_occurring
+    }
+  }
+}
+
+fn counted(task: &&Task) -> bool {
+  task.status != Status::Deleted
+}
+
+fn finish(
+  tallies: BTreeMap<String, Tally>
+) -> Vec<UsageStat> {
+  tallies
+    .into_iter()
+    .map(|(name, tally)| {
+      tally.finish(name)
+    })
+    .collect()
+}
+
+/// One entry per tag, in name order.
+pub fn tag_stats<'a>(
+  tasks: impl IntoIterator<Item = &'a Task>
+) -> Vec<UsageStat> {
+  let mut tallies =
+    BTreeMap::<String, Tally>::new();
+  for task in
+    tasks.into_iter().filter(counted)
+  {
+    for tag in &task.tags {
+      tallies
+        .entry(tag.clone())
+        .or_default()
+        .add(
+          task,
+          task.tags.iter().filter(
+            |other| *other != tag
+          )
+        );
+    }
+  }
+  finish(tallies)
+}
+
+/// One entry per project, in name
+/// order; co-occurrence lists the
+/// project's most used tags.
+pub fn project_stats<'a>(
+  tasks: impl IntoIterator<Item = &'a Task>
+) -> Vec<UsageStat> {
+  let mut tallies =
+    BTreeMap::<String, Tally>::new();
+  for task in
+    tasks.into_iter().filter(counted)
+  {
+    if let Some(project) = &task.project
+    {
+      tallies
+        .entry(project.clone())
+        .or_default()
+        .add(task, task.tags.iter());
+    }
+  }
+  finish(tallies)
+}
+
+/// Plain tags no open task carries,
+/// in name order. `key:value` tags are
+/// left out: they drive kanban lanes,
+/// boards and recurrence rather than
+/// labelling.
+pub fn unused_tags(
+  stats: &[UsageStat]
+) -> Vec<String> {
+  let mut names: Vec<String> = stats
+    .iter()
+    .filter(|stat| {
+      stat.is_unused()
+        && !stat.name.contains(':')
+    })
+    .map(|stat| stat.name.clone())
+    .collect();
+  names.sort();
+  names
+}
+
+/// Drops `names` from every task that
+/// carries one; returns how many tasks
+/// changed.
+pub fn strip_tags(
+  tasks: &mut [Task],
+  names: &[String],
+  now: DateTime<Utc>
+) -> usize {
+  let mut changed = 0;
+  for task in tasks {
+    let before = task.tags.len();
+    task.tags.retain(|tag| {
+      !names.contains(tag)
+    });
+    if task.tags.len() != before {
+      task.modified = now;
+      changed += 1;
+    }
+  }
+  changed
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+
+  #[test]
+  fn counts_usage_recency_and_co_occurrence()
+   {
+    let now = Utc::now();
+    let task =
+      |tags: &[&str],
+       status: Status,
+       days_ago: i64| {
+        let mut task =
+          Task::new_pending(
+            "t".to_string(),
+            now,
+            1
+          );
+        task.project =
+          Some("home".to_string());
+        task.tags = tags
+          .iter()
+          .map(|tag| tag.to_string())
+          .collect();
+        task.status = status;
+        task.modified = now
+          - Duration::days(days_ago);
+        task
+      };
+    let tasks = [
+      task(
+        &["garden", "weekend"],
+        Status::Pending,
+        3
+      ),
+      task(
+        &["garden"],
+        Status::Completed,
+        1
+      ),
+      task(
+        &["old"],
+        Status::Completed,
+        30
+      ),
+      task(
+        &["gone"],
+        Status::Deleted,
+        0
+      )
+    ];
+
+    let mut tags = tag_stats(&tasks);
+    assert_eq!(
+      tags
+        .iter()
+        .map(|stat| stat.name.as_str())
+        .collect::<Vec<_>>(),
+      ["garden", "old", "weekend"]
+    );
+    let garden = &tags[0];
+    assert_eq!(
+      (garden.count, garden.open),
+      (2, 1)
+    );
+    assert_eq!(
+      garden.last_used,
+      now - Duration::days(1)
+    );
+    assert_eq!(
+      garden.co_occurring[0].name,
+      "weekend"
+    );
+    assert!(tags[1].is_unused());
+    assert_eq!(unused_tags(&tags), [
+      "old"
+    ]);
+
+    StatsSort::Recent.apply(&mut tags);
+    assert_eq!(tags[0].name, "garden");
+    assert_eq!(tags[2].name, "old");
+
+    let projects =
+      project_stats(&tasks);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].count, 3);
+    assert_eq!(
+      projects[0].co_occurring[0].name,
+      "garden"
+    );
+    assert!(
+      StatsSort::parse("size").is_err()
+    );
+
+    let mut tasks = tasks;
+    assert_eq!(
+      strip_tags(
+        &mut tasks,
+        &["old".to_string()],
+        now
+      ),
+      1
+    );
+    assert!(tasks[2].tags.is_empty());
+  }
+}
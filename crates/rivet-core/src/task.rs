@@ -1,7 +1,13 @@
-use std::collections::BTreeMap;
+use std::collections::{
+  BTreeMap,
+  BTreeSet,
+  HashSet
+};
 
 use chrono::{
   DateTime,
+  Duration,
+  NaiveDateTime,
   Utc
 };
 use serde::{
@@ -10,8 +16,36 @@ use serde::{
 };
 use uuid::Uuid;
 
+use crate::config::Config;
 use crate::datetime::taskwarrior_date_serde;
 
+/// Built-in task attributes and their
+/// value types, as reported by
+/// `_capabilities`.
+pub const TASK_ATTRIBUTES: &[(
+  &str,
+  &str
+)] = &[
+  ("uuid", "uuid"),
+  ("id", "numeric"),
+  ("description", "string"),
+  ("status", "string"),
+  ("entry", "date"),
+  ("modified", "date"),
+  ("end", "date"),
+  ("start", "date"),
+  ("project", "string"),
+  ("priority", "string"),
+  ("tags", "list"),
+  ("due", "date"),
+  ("scheduled", "date"),
+  ("wait", "date"),
+  ("depends", "list"),
+  ("parent", "uuid"),
+  ("contact", "uuid"),
+  ("annotations", "list")
+];
+
 #[derive(
   Debug,
   Clone,
@@ -19,13 +53,19 @@ use crate::datetime::taskwarrior_date_serde;
   Deserialize,
   PartialEq,
   Eq,
+  Hash,
 )]
 #[serde(rename_all = "lowercase")]
 pub enum Status {
   Pending,
   Completed,
   Deleted,
-  Waiting
+  Waiting,
+  /// Hidden from reports without being
+  /// deleted; kept in completed.data
+  /// and exported as `completed` for
+  /// Taskwarrior.
+  Archived
 }
 
 #[derive(
@@ -104,6 +144,26 @@ pub struct Task {
   #[serde(default)]
   pub depends: Vec<Uuid>,
 
+  /// Owning task for subtasks. Unlike
+  /// `depends`, this groups work for
+  /// progress reporting and never
+  /// blocks the child.
+  #[serde(
+    default,
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub parent: Option<Uuid>,
+
+  /// Contact the task waits on
+  /// (`contact:<uuid>`). Contacts live
+  /// in the GUI's store, so nothing
+  /// here checks that it exists.
+  #[serde(
+    default,
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub contact: Option<Uuid>,
+
   #[serde(default)]
   pub annotations: Vec<Annotation>,
 
@@ -134,6 +194,8 @@ impl Task {
       scheduled: None,
       wait: None,
       depends: vec![],
+      parent: None,
+      contact: None,
       annotations: vec![],
       extra: BTreeMap::new()
     }
@@ -150,3 +212,2032 @@ impl Task {
         .unwrap_or(false)
   }
 }
+
+/// Key in `extra` holding the seconds a
+/// task has spent started, summed over
+/// every stop.
+pub const FOCUS_SECONDS_KEY: &str =
+  "rivet_focus_seconds";
+
+impl Task {
+  /// Focus time recorded by earlier
+  /// stops; a running clock is not
+  /// included.
+  pub fn focus_seconds(&self) -> u64 {
+    self
+      .extra
+      .get(FOCUS_SECONDS_KEY)
+      .and_then(
+        serde_json::Value::as_u64
+      )
+      .unwrap_or(0)
+  }
+
+  /// Clears `start` and adds the time
+  /// since it to the focus total.
+  /// Returns the seconds added, or
+  /// `None` when the task was not
+  /// started.
+  pub fn stop_clock(
+    &mut self,
+    now: DateTime<Utc>
+  ) -> Option<u64> {
+    let started = self.start.take()?;
+    let elapsed = u64::try_from(
+      (now - started).num_seconds()
+    )
+    .unwrap_or(0);
+    let total =
+      self.focus_seconds() + elapsed;
+    self.extra.insert(
+      FOCUS_SECONDS_KEY.to_string(),
+      total.into()
+    );
+    Some(elapsed)
+  }
+}
+
+/// UDA holding when `task review` last
+/// went over a task, as a store date.
+/// Declare `uda.reviewed.type=date` to
+/// filter or sort on it.
+pub const REVIEWED_KEY: &str =
+  "reviewed";
+
+impl Task {
+  /// Unset for a task never reviewed
+  /// (or one with an unreadable stamp).
+  pub fn reviewed_at(
+    &self
+  ) -> Option<DateTime<Utc>> {
+    let raw = self
+      .extra
+      .get(REVIEWED_KEY)?
+      .as_str()?;
+    NaiveDateTime::parse_from_str(
+      raw,
+      "%Y%m%dT%H%M%SZ"
+    )
+    .ok()
+    .map(|naive| naive.and_utc())
+  }
+
+  pub fn mark_reviewed(
+    &mut self,
+    now: DateTime<Utc>
+  ) {
+    self.extra.insert(
+      REVIEWED_KEY.to_string(),
+      now
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+        .into()
+    );
+    self.modified = now;
+  }
+}
+
+/// Export marker for archived tasks.
+/// Taskwarrior has no archived status,
+/// so export writes them as completed
+/// with this key set and import turns
+/// them back.
+pub const ARCHIVED_EXPORT_KEY: &str =
+  "rivet_archived";
+
+/// Characters of the uuid shown by
+/// `id.display=uuid`.
+pub const UUID_PREFIX_LEN: usize = 8;
+
+impl Task {
+  /// The first [`UUID_PREFIX_LEN`]
+  /// characters of the uuid: unlike
+  /// `id`, it survives renumbering.
+  /// Grown until it holds a digit and
+  /// a letter, so it reads back as a
+  /// uuid prefix rather than an id or
+  /// a word.
+  pub fn short_uuid(&self) -> String {
+    let text =
+      self.uuid.simple().to_string();
+    let mut digit = false;
+    let mut letter = false;
+    for (len, ch) in text.char_indices()
+    {
+      digit |= ch.is_ascii_digit();
+      letter |=
+        ch.is_ascii_alphabetic();
+      if digit
+        && letter
+        && len + 1 >= UUID_PREFIX_LEN
+      {
+        return text[..=len].to_string();
+      }
+    }
+    text
+  }
+}
+
+impl Task {
+  /// The status `unarchive` restores:
+  /// completed when the task had been
+  /// finished, pending otherwise.
+  pub fn unarchived_status(
+    &self
+  ) -> Status {
+    if self.end.is_some() {
+      Status::Completed
+    } else {
+      Status::Pending
+    }
+  }
+
+  /// The Taskwarrior-compatible form of
+  /// this task for `export`.
+  pub fn into_export(mut self) -> Task {
+    if self.status == Status::Archived {
+      self.status = Status::Completed;
+      self.end = self
+        .end
+        .or(Some(self.modified));
+      self.extra.insert(
+        ARCHIVED_EXPORT_KEY.to_string(),
+        true.into()
+      );
+    }
+    self
+  }
+
+  /// Reverses [`Task::into_export`] on
+  /// an imported task.
+  pub fn restore_archived_from_export(
+    &mut self
+  ) {
+    let marked = self
+      .extra
+      .remove(ARCHIVED_EXPORT_KEY)
+      .is_some_and(|value| {
+        value.as_bool() == Some(true)
+      });
+    if marked
+      && self.status
+        == Status::Completed
+    {
+      self.status = Status::Archived;
+    }
+  }
+}
+
+/// Thresholds for the stale-task
+/// report, read from the `stale.*`
+/// config keys.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub struct StalePolicy {
+  pub days:            i64,
+  pub include_waiting: bool,
+  pub include_blocked: bool
+}
+
+impl Default for StalePolicy {
+  fn default() -> Self {
+    Self {
+      days:            14,
+      include_waiting: false,
+      include_blocked: false
+    }
+  }
+}
+
+impl StalePolicy {
+  /// Rejects a `stale.days` too large
+  /// to turn into a duration.
+  pub fn check_days(
+    days: i64
+  ) -> anyhow::Result<i64> {
+    if Duration::try_days(days)
+      .is_none()
+    {
+      anyhow::bail!(
+        "stale.days {days} is out of \
+         range"
+      );
+    }
+    Ok(days)
+  }
+
+  pub fn from_config(
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    let defaults = Self::default();
+    Ok(Self {
+      days:            cfg
+        .get("stale.days")
+        .and_then(|raw| {
+          raw.trim().parse::<i64>().ok()
+        })
+        .filter(|days| *days > 0)
+        .map(Self::check_days)
+        .transpose()?
+        .unwrap_or(defaults.days),
+      include_waiting: cfg
+        .get_bool(
+          "stale.include_waiting"
+        )
+        .unwrap_or(
+          defaults.include_waiting
+        ),
+      include_blocked: cfg
+        .get_bool(
+          "stale.include_blocked"
+        )
+        .unwrap_or(
+          defaults.include_blocked
+        )
+    })
+  }
+}
+
+/// What `done` does with open subtasks
+/// of the tasks it completes.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum SubtaskCompletion {
+  /// Leave them open and say so.
+  Warn,
+  /// Refuse to complete the parent.
+  Block,
+  /// Complete them too.
+  Cascade,
+  /// Prompt on a terminal; otherwise
+  /// behave like `Warn`.
+  Ask
+}
+
+/// `on_complete.*` settings for `done`.
+#[derive(
+  Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub struct CompletionPolicy {
+  pub subtasks: SubtaskCompletion,
+  /// `on_complete.dependents=notify`:
+  /// list tasks a completion leaves
+  /// with no open dependencies.
+  pub notify_unblocked: bool
+}
+
+impl SubtaskCompletion {
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "warn" => Ok(Self::Warn),
+      | "block" => Ok(Self::Block),
+      | "cascade" => Ok(Self::Cascade),
+      | "ask" => Ok(Self::Ask),
+      | _ => {
+        anyhow::bail!(
+          "invalid on_complete.\
+           subtasks: {raw} (expected \
+           warn, block, cascade or \
+           ask)"
+        )
+      }
+    }
+  }
+}
+
+impl CompletionPolicy {
+  /// `on_complete.subtasks` is `warn`
+  /// (default), `block`, `cascade` or
+  /// `ask`. Without it the older
+  /// `subtasks.complete_children`
+  /// (`yes`/`ask`/`no`) still applies.
+  pub fn from_config(
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    let subtasks = match cfg
+      .get("on_complete.subtasks")
+    {
+      | Some(raw) => {
+        SubtaskCompletion::parse(&raw)?
+      }
+      | None => {
+        match cfg
+          .get(
+            "subtasks.complete_children"
+          )
+          .unwrap_or_default()
+          .trim()
+          .to_ascii_lowercase()
+          .as_str()
+        {
+          | "yes" | "true" | "on"
+          | "1" => {
+            SubtaskCompletion::Cascade
+          }
+          | "ask" => {
+            SubtaskCompletion::Ask
+          }
+          | _ => SubtaskCompletion::Warn
+        }
+      }
+    };
+    let notify_unblocked = cfg
+      .get("on_complete.dependents")
+      .map(|raw| {
+        Self::parse_dependents(&raw)
+      })
+      .transpose()?
+      .unwrap_or(false);
+    Ok(Self {
+      subtasks,
+      notify_unblocked
+    })
+  }
+
+  /// `on_complete.dependents`: `notify`
+  /// or `off`.
+  pub fn parse_dependents(
+    raw: &str
+  ) -> anyhow::Result<bool> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "off" => Ok(false),
+      | "notify" => Ok(true),
+      | other => {
+        anyhow::bail!(
+          "invalid on_complete.\
+           dependents: {other} \
+           (expected notify or off)"
+        )
+      }
+    }
+  }
+
+  /// Whether completing tasks that
+  /// still have `open` open subtasks
+  /// completes those too: `cascade`
+  /// does, `warn` does not and `block`
+  /// refuses. `ask` leaves it to
+  /// `confirm`.
+  pub fn cascade_subtasks(
+    &self,
+    open: usize,
+    confirm: impl FnOnce(
+      usize
+    )
+      -> anyhow::Result<
+      bool
+    >
+  ) -> anyhow::Result<bool> {
+    match self.subtasks {
+      | SubtaskCompletion::Cascade => {
+        Ok(true)
+      }
+      | SubtaskCompletion::Ask => {
+        confirm(open)
+      }
+      | SubtaskCompletion::Warn => {
+        Ok(false)
+      }
+      | SubtaskCompletion::Block => {
+        anyhow::bail!(
+          "not completing: {open} \
+           open subtask(s) remain; \
+           complete them first or set \
+           on_complete.\
+           subtasks=cascade"
+        )
+      }
+    }
+  }
+}
+
+impl Task {
+  /// A pending task is stale when it
+  /// has not been modified for
+  /// `policy.days` and has no due or
+  /// scheduled date still ahead of
+  /// `now`.
+  pub fn is_stale(
+    &self,
+    now: DateTime<Utc>,
+    policy: &StalePolicy
+  ) -> bool {
+    if !matches!(
+      self.status,
+      Status::Pending | Status::Waiting
+    ) {
+      return false;
+    }
+    if !policy.include_waiting
+      && self.is_waiting(now)
+    {
+      return false;
+    }
+    if !policy.include_blocked
+      && !self.depends.is_empty()
+    {
+      return false;
+    }
+
+    let upcoming = |date: Option<
+      DateTime<Utc>
+    >| {
+      date
+        .is_some_and(|date| date >= now)
+    };
+    if upcoming(self.due)
+      || upcoming(self.scheduled)
+    {
+      return false;
+    }
+
+    Duration::try_days(policy.days)
+      .and_then(|span| {
+        now.checked_sub_signed(span)
+      })
+      .is_some_and(|cutoff| {
+        self.modified <= cutoff
+      })
+  }
+}
+
+/// User urgency boosts read from the
+/// Taskwarrior-style keys
+/// `urgency.user.project.<name>.
+/// coefficient` and `urgency.user.tag.
+/// <name>.coefficient`.
+#[derive(
+  Debug, Clone, Default, PartialEq,
+)]
+pub struct UrgencyCoefficients {
+  pub projects: BTreeMap<String, f64>,
+  pub tags:     BTreeMap<String, f64>
+}
+
+impl UrgencyCoefficients {
+  /// Zero and unparseable coefficients
+  /// are skipped so they never affect
+  /// ordering.
+  pub fn from_config(
+    cfg: &Config
+  ) -> Self {
+    let mut coefficients =
+      Self::default();
+    for (key, raw) in cfg.iter() {
+      let Some(rest) = key
+        .strip_prefix("urgency.user.")
+        .and_then(|rest| {
+          rest.strip_suffix(
+            ".coefficient"
+          )
+        })
+      else {
+        continue;
+      };
+      let (table, name) =
+        if let Some(name) =
+          rest.strip_prefix("project.")
+        {
+          (
+            &mut coefficients.projects,
+            name
+          )
+        } else if let Some(name) =
+          rest.strip_prefix("tag.")
+        {
+          (&mut coefficients.tags, name)
+        } else {
+          continue;
+        };
+      match raw.trim().parse::<f64>() {
+        | Ok(value)
+          if value.is_finite()
+            && value != 0.0
+            && !name.is_empty() =>
+        {
+          table.insert(
+            name.to_string(),
+            value
+          );
+        }
+        | Ok(_) => {}
+        | Err(_) => {
+          tracing::warn!(
+            key = %key,
+            value = %raw,
+            "ignoring non-numeric urgency coefficient"
+          );
+        }
+      }
+    }
+    coefficients
+  }
+
+  /// Sum of the boosts for the task's
+  /// project (including its parent
+  /// projects, so `work` covers
+  /// `work.reports`) and each of its
+  /// tags.
+  pub fn boost(
+    &self,
+    task: &Task
+  ) -> f64 {
+    let project = task
+      .project
+      .as_deref()
+      .map_or(0.0, |project| {
+        self
+          .projects
+          .iter()
+          .filter(|(name, _)| {
+            project == name.as_str()
+              || project
+                .strip_prefix(
+                  name.as_str()
+                )
+                .is_some_and(|rest| {
+                  rest.starts_with('.')
+                })
+          })
+          .map(|(_, value)| value)
+          .sum()
+      });
+    let tags: f64 = task
+      .tags
+      .iter()
+      .filter_map(|tag| {
+        self.tags.get(tag)
+      })
+      .sum();
+    project + tags
+  }
+}
+
+/// Attributes filled in on new tasks.
+/// Precedence, highest first: values
+/// given on the command, the active
+/// context's write filter
+/// (`context.<name>.write`, falling
+/// back to `context.<name>`), then
+/// `default.project` / `default.tags`.
+#[derive(
+  Debug, Clone, Default, PartialEq, Eq,
+)]
+pub struct TaskDefaults {
+  pub project:      Option<String>,
+  /// Only used when the new task has
+  /// no tags of its own.
+  pub tags:         Vec<String>,
+  /// Always added so the task stays
+  /// visible in the active context.
+  pub context_tags: Vec<String>
+}
+
+impl TaskDefaults {
+  /// Only `project:` and `+tag` terms
+  /// of the context filter are written;
+  /// anything else in it only reads.
+  /// Read filters using `or` or
+  /// parentheses need an explicit
+  /// `.write` filter.
+  pub fn from_config(
+    cfg: &Config,
+    active_context: Option<&str>
+  ) -> Self {
+    let mut defaults = Self {
+      project:      cfg
+        .get("default.project")
+        .map(|value| {
+          value.trim().to_string()
+        })
+        .filter(|value| {
+          !value.is_empty()
+        }),
+      tags:         cfg
+        .get("default.tags")
+        .map(|value| {
+          split_tag_list(&value)
+        })
+        .unwrap_or_default(),
+      context_tags: Vec::new()
+    };
+
+    let Some(name) = active_context
+    else {
+      return defaults;
+    };
+    // A read filter with alternatives
+    // has no single value to write.
+    let Some(expr) = cfg
+      .get(&format!(
+        "context.{name}.write"
+      ))
+      .or_else(|| {
+        cfg
+          .get(&format!(
+            "context.{name}"
+          ))
+          .filter(|expr| {
+            !expr
+              .split_whitespace()
+              .any(|term| {
+                term
+                  .eq_ignore_ascii_case(
+                    "or"
+                  )
+                  || term.contains([
+                    '(', ')'
+                  ])
+              })
+          })
+      })
+    else {
+      return defaults;
+    };
+    for term in expr.split_whitespace()
+    {
+      if let Some(project) = term
+        .strip_prefix("project:")
+        .or_else(|| {
+          term.strip_prefix("pro:")
+        })
+        .filter(|project| {
+          !project.is_empty()
+        })
+      {
+        defaults.project =
+          Some(project.to_string());
+      } else if let Some(tag) = term
+        .strip_prefix('+')
+        .filter(|tag| !tag.is_empty())
+        && !defaults
+          .context_tags
+          .iter()
+          .any(|existing| {
+            existing == tag
+          })
+      {
+        defaults
+          .context_tags
+          .push(tag.to_string());
+      }
+    }
+    defaults
+  }
+
+  pub fn apply(
+    &self,
+    task: &mut Task
+  ) {
+    if task.project.is_none() {
+      task.project =
+        self.project.clone();
+    }
+    let extra = if task.tags.is_empty()
+    {
+      self
+        .tags
+        .iter()
+        .chain(&self.context_tags)
+        .cloned()
+        .collect::<Vec<_>>()
+    } else {
+      self.context_tags.clone()
+    };
+    for tag in extra {
+      if !task.tags.contains(&tag) {
+        task.tags.push(tag);
+      }
+    }
+  }
+}
+
+/// What happens to a description
+/// longer than
+/// `description.max_length`.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub enum DescriptionOverflow {
+  /// Cut it to the limit with an
+  /// ellipsis and keep the full text
+  /// as an annotation.
+  #[default]
+  Truncate,
+  /// Refuse the add or update.
+  Reject
+}
+
+impl DescriptionOverflow {
+  pub fn parse(
+    raw: &str
+  ) -> Option<Self> {
+    match raw
+      .trim()
+      .to_ascii_lowercase()
+      .as_str()
+    {
+      | "truncate" => {
+        Some(Self::Truncate)
+      }
+      | "reject" => Some(Self::Reject),
+      | _ => None
+    }
+  }
+}
+
+/// `description.max_length` (in
+/// characters; unset or 0 means no
+/// limit) and `description.overflow`.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Default,
+  PartialEq,
+  Eq,
+)]
+pub struct DescriptionLimit {
+  pub max_length: Option<usize>,
+  pub overflow:   DescriptionOverflow
+}
+
+impl DescriptionLimit {
+  pub fn from_config(
+    cfg: &Config
+  ) -> anyhow::Result<Self> {
+    let max_length = match cfg
+      .get("description.max_length")
+    {
+      | Some(raw) => {
+        raw
+          .trim()
+          .parse::<usize>()
+          .map_err(|_| {
+            anyhow::anyhow!(
+              "invalid description.\
+               max_length: {raw} \
+               (expected a number of \
+               characters)"
+            )
+          })?
+      }
+      | None => 0
+    };
+    let overflow = match cfg
+      .get("description.overflow")
+    {
+      | Some(raw) => {
+        DescriptionOverflow::parse(&raw)
+          .ok_or_else(|| {
+            anyhow::anyhow!(
+              "invalid description.\
+               overflow: {raw} \
+               (expected truncate or \
+               reject)"
+            )
+          })?
+      }
+      | None => {
+        DescriptionOverflow::default()
+      }
+    };
+    Ok(Self {
+      max_length: (max_length > 0)
+        .then_some(max_length),
+      overflow
+    })
+  }
+
+  /// Enforces the limit on
+  /// `task.description`. Returns
+  /// whether it was truncated.
+  pub fn apply(
+    &self,
+    task: &mut Task,
+    now: DateTime<Utc>
+  ) -> anyhow::Result<bool> {
+    let Some(max) = self.max_length
+    else {
+      return Ok(false);
+    };
+    let length =
+      task.description.chars().count();
+    if length <= max {
+      return Ok(false);
+    }
+    if self.overflow
+      == DescriptionOverflow::Reject
+    {
+      anyhow::bail!(
+        "description is {length} \
+         characters, over \
+         description.max_length={max}"
+      );
+    }
+
+    let full = std::mem::take(
+      &mut task.description
+    );
+    let kept: String = full
+      .chars()
+      .take(max - 1)
+      .collect();
+    task.description = format!(
+      "{}\u{2026}",
+      kept.trim_end()
+    );
+    if !task.annotations.iter().any(
+      |annotation| {
+        annotation.description == full
+      }
+    ) {
+      task.annotations.push(
+        Annotation {
+          entry:       now,
+          description: full
+        }
+      );
+    }
+    Ok(true)
+  }
+}
+
+/// Splits a `default.tags`-style list
+/// on commas and whitespace; a leading
+/// `+` on each tag is optional.
+pub fn split_tag_list(
+  raw: &str
+) -> Vec<String> {
+  let mut tags: Vec<String> =
+    Vec::new();
+  for tag in raw
+    .split(|ch: char| {
+      ch == ',' || ch.is_whitespace()
+    })
+    .map(|tag| {
+      tag.trim_start_matches('+')
+    })
+    .filter(|tag| !tag.is_empty())
+  {
+    if !tags.iter().any(|t| t == tag) {
+      tags.push(tag.to_string());
+    }
+  }
+  tags
+}
+
+/// Replaces the tag `from` with `to`
+/// in place, dropping `from` instead
+/// when `to` is already there. Returns
+/// whether anything changed.
+pub fn rename_tag(
+  tags: &mut Vec<String>,
+  from: &str,
+  to: &str
+) -> bool {
+  let Some(index) = tags
+    .iter()
+    .position(|tag| tag == from)
+  else {
+    return false;
+  };
+  if from == to {
+    return false;
+  }
+  if tags.iter().any(|tag| tag == to) {
+    tags.remove(index);
+  } else {
+    tags[index] = to.to_string();
+  }
+  true
+}
+
+/// A `key:from=to` remap of one value
+/// of a `key:value` tag, as taken by
+/// `task tags remap`.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct TagRemap {
+  pub key:  String,
+  pub from: String,
+  pub to:   String
+}
+
+impl TagRemap {
+  /// `to` may repeat the key
+  /// (`kanban:working=kanban:doing`).
+  pub fn parse(
+    raw: &str
+  ) -> anyhow::Result<Self> {
+    let invalid = || {
+      anyhow::anyhow!(
+        "invalid tag remap '{raw}' \
+         (expected key:old=new)"
+      )
+    };
+    let (tag, to) = raw
+      .split_once('=')
+      .ok_or_else(invalid)?;
+    let (key, from) = tag
+      .split_once(':')
+      .ok_or_else(invalid)?;
+    let key = key.trim();
+    let to = to.trim();
+    let to = to
+      .strip_prefix(key)
+      .and_then(|rest| {
+        rest.strip_prefix(':')
+      })
+      .unwrap_or(to);
+    Self::new(key, from, to)
+      .ok_or_else(invalid)
+  }
+
+  /// `None` when a part is empty or
+  /// holds whitespace.
+  pub fn new(
+    key: &str,
+    from: &str,
+    to: &str
+  ) -> Option<Self> {
+    let parts = [
+      key.trim(),
+      from.trim(),
+      to.trim()
+    ];
+    if parts.iter().any(|part| {
+      part.is_empty()
+        || part
+          .contains(char::is_whitespace)
+    }) || parts[0].contains(':')
+    {
+      return None;
+    }
+    Some(Self {
+      key:  parts[0].to_string(),
+      from: parts[1].to_string(),
+      to:   parts[2].to_string()
+    })
+  }
+
+  pub fn from_tag(&self) -> String {
+    format!(
+      "{}:{}",
+      self.key, self.from
+    )
+  }
+
+  pub fn to_tag(&self) -> String {
+    format!("{}:{}", self.key, self.to)
+  }
+
+  /// Rewrites matching tasks, bumping
+  /// `modified`; returns how many
+  /// changed.
+  pub fn apply(
+    &self,
+    tasks: &mut [Task],
+    now: DateTime<Utc>
+  ) -> usize {
+    let (from, to) =
+      (self.from_tag(), self.to_tag());
+    let mut changed = 0;
+    for task in tasks {
+      if rename_tag(
+        &mut task.tags,
+        &from,
+        &to
+      ) {
+        task.modified = now;
+        changed += 1;
+      }
+    }
+    changed
+  }
+}
+
+/// `task uda rename old new`: moves a
+/// UDA value to a new attribute name.
+#[derive(
+  Debug, Clone, PartialEq, Eq,
+)]
+pub struct UdaRename {
+  pub from: String,
+  pub to:   String
+}
+
+impl UdaRename {
+  /// Names are letters, digits and
+  /// `_`; neither side may be a
+  /// built-in attribute.
+  pub fn new(
+    from: &str,
+    to: &str
+  ) -> anyhow::Result<Self> {
+    let (from, to) =
+      (from.trim(), to.trim());
+    for name in [from, to] {
+      if name.is_empty()
+        || !name.chars().all(|ch| {
+          ch.is_ascii_alphanumeric()
+            || ch == '_'
+        })
+      {
+        anyhow::bail!(
+          "invalid uda name '{name}' \
+           (use letters, digits and _)"
+        );
+      }
+      if TASK_ATTRIBUTES.iter().any(
+        |(builtin, _)| {
+          builtin
+            .eq_ignore_ascii_case(name)
+        }
+      ) {
+        anyhow::bail!(
+          "'{name}' is a built-in \
+           attribute, not a uda"
+        );
+      }
+    }
+    if from == to {
+      anyhow::bail!(
+        "uda rename: '{from}' is \
+         already named '{to}'"
+      );
+    }
+    Ok(Self {
+      from: from.to_string(),
+      to:   to.to_string()
+    })
+  }
+
+  /// Moves the value on every task that
+  /// has it, bumping `modified`;
+  /// returns how many changed. Fails
+  /// without touching anything when a
+  /// task already holds both names.
+  pub fn apply(
+    &self,
+    tasks: &mut [Task],
+    now: DateTime<Utc>
+  ) -> anyhow::Result<usize> {
+    let clashes = tasks
+      .iter()
+      .filter(|task| {
+        task
+          .extra
+          .contains_key(&self.from)
+          && task
+            .extra
+            .contains_key(&self.to)
+      })
+      .map(|task| task.uuid.to_string())
+      .collect::<Vec<_>>();
+    if !clashes.is_empty() {
+      anyhow::bail!(
+        "uda rename: task(s) {} \
+         already have both '{}' and \
+         '{}'",
+        clashes.join(", "),
+        self.from,
+        self.to
+      );
+    }
+    let mut changed = 0;
+    for task in tasks {
+      if let Some(value) =
+        task.extra.remove(&self.from)
+      {
+        task.extra.insert(
+          self.to.clone(),
+          value
+        );
+        task.modified = now;
+        changed += 1;
+      }
+    }
+    Ok(changed)
+  }
+}
+
+impl Task {
+  /// Taskwarrior-style urgency score;
+  /// completed, deleted and archived
+  /// tasks score zero.
+  pub fn urgency(
+    &self,
+    now: DateTime<Utc>,
+    coefficients: &UrgencyCoefficients
+  ) -> f64 {
+    if matches!(
+      self.status,
+      Status::Completed
+        | Status::Deleted
+        | Status::Archived
+    ) {
+      return 0.0;
+    }
+
+    let mut urgency = 0.0;
+
+    urgency +=
+      self.tags.len() as f64 * 0.8;
+
+    if let Some(priority) =
+      self.priority.as_deref()
+    {
+      urgency += match priority
+        .to_ascii_uppercase()
+        .as_str()
+      {
+        | "H" => 6.0,
+        | "M" => 3.9,
+        | "L" => 1.8,
+        | _ => 0.0
+      };
+    }
+
+    if self.start.is_some()
+      && !self.is_waiting(now)
+    {
+      urgency += 4.0;
+    }
+    if self.is_waiting(now) {
+      urgency -= 3.0;
+    }
+    if !self.depends.is_empty() {
+      urgency -= 5.0;
+    }
+
+    if let Some(due) = self.due {
+      let delta = due - now;
+      let days = delta.num_minutes()
+        as f64
+        / (60.0 * 24.0);
+      urgency += if days <= -1.0 {
+        9.7
+      } else if days <= 0.0 {
+        9.3
+      } else if days <= 1.0 {
+        8.8
+      } else if days <= 2.0 {
+        8.4
+      } else if days <= 7.0 {
+        6.0
+      } else {
+        3.0
+      };
+    }
+
+    urgency + coefficients.boost(self)
+  }
+}
+
+impl Task {
+  /// Renders the `task add` command
+  /// that recreates this task's
+  /// user-editable fields. Computed
+  /// fields such as uuid, entry and
+  /// status are left out,
+  /// and every argument is shell-quoted
+  /// so the line is safe to paste.
+  pub fn to_add_command(
+    &self
+  ) -> String {
+    let mut parts = vec![
+      "task".to_string(),
+      "add".to_string(),
+    ];
+
+    if let Some(project) =
+      self.project.as_deref()
+    {
+      parts.push(shell_quote(
+        &format!("project:{project}")
+      ));
+    }
+    for tag in &self.tags {
+      parts.push(shell_quote(
+        &format!("+{tag}")
+      ));
+    }
+    if let Some(priority) =
+      self.priority.as_deref()
+    {
+      parts.push(shell_quote(
+        &format!("priority:{priority}")
+      ));
+    }
+    for (key, value) in [
+      ("due", self.due),
+      ("scheduled", self.scheduled),
+      ("wait", self.wait)
+    ] {
+      if let Some(value) = value {
+        parts.push(format!(
+          "{key}:{}",
+          value
+            .format("%Y%m%dT%H%M%SZ")
+        ));
+      }
+    }
+
+    // `--` keeps description words such
+    // as `+x` or `due:x` from being
+    // read back as modifiers.
+    parts.push("--".to_string());
+    parts.push(shell_quote(
+      &self.description
+    ));
+    parts.join(" ")
+  }
+}
+
+/// Quotes a word for POSIX shells,
+/// leaving plain words untouched.
+pub fn shell_quote(
+  value: &str
+) -> String {
+  let plain = !value.is_empty()
+    && value.chars().all(|ch| {
+      ch.is_ascii_alphanumeric()
+        || "_-+=:,./@%".contains(ch)
+    });
+  if plain {
+    return value.to_string();
+  }
+
+  format!(
+    "'{}'",
+    value.replace('\'', "'\\''")
+  )
+}
+
+/// Non-deleted tasks whose `parent` is
+/// `parent`, in input order.
+pub fn subtasks_of(
+  tasks: &[Task],
+  parent: Uuid
+) -> Vec<&Task> {
+  tasks
+    .iter()
+    .filter(|task| {
+      task.parent == Some(parent)
+        && task.status
+          != Status::Deleted
+    })
+    .collect()
+}
+
+/// Open tasks anywhere below the
+/// `roots` in the subtask tree, not
+/// counting the roots themselves.
+pub fn open_descendants(
+  tasks: &[Task],
+  roots: &BTreeSet<Uuid>
+) -> Vec<Uuid> {
+  let mut found = Vec::new();
+  let mut seen = roots.clone();
+  let mut frontier: Vec<Uuid> =
+    roots.iter().copied().collect();
+  while let Some(parent) =
+    frontier.pop()
+  {
+    for child in
+      subtasks_of(tasks, parent)
+    {
+      if seen.insert(child.uuid) {
+        frontier.push(child.uuid);
+        if child.status
+          == Status::Pending
+          || child.status
+            == Status::Waiting
+        {
+          found.push(child.uuid);
+        }
+      }
+    }
+  }
+  found
+}
+
+/// Open tasks that depended on one of
+/// `finished` and now have no open
+/// dependency left.
+pub fn newly_unblocked<'a>(
+  pending: &'a [Task],
+  finished: &[Task]
+) -> Vec<&'a Task> {
+  let finished: BTreeSet<Uuid> =
+    finished
+      .iter()
+      .map(|task| task.uuid)
+      .collect();
+  let open: BTreeSet<Uuid> = pending
+    .iter()
+    .filter(|task| {
+      matches!(
+        task.status,
+        Status::Pending
+          | Status::Waiting
+      )
+    })
+    .map(|task| task.uuid)
+    .collect();
+  pending
+    .iter()
+    .filter(|task| {
+      open.contains(&task.uuid)
+        && task.depends.iter().any(
+          |uuid| {
+            finished.contains(uuid)
+          }
+        )
+        && !task.depends.iter().any(
+          |uuid| open.contains(uuid)
+        )
+    })
+    .collect()
+}
+
+/// `(completed, total)` over the
+/// subtasks of `parent`.
+pub fn subtask_progress(
+  tasks: &[Task],
+  parent: Uuid
+) -> (usize, usize) {
+  let children =
+    subtasks_of(tasks, parent);
+  let done = children
+    .iter()
+    .filter(|task| {
+      task.status == Status::Completed
+    })
+    .count();
+  (done, children.len())
+}
+
+/// Whether making `parent` the parent
+/// of `child` would close a loop, i.e.
+/// `child` already sits somewhere on
+/// `parent`'s ancestor chain.
+pub fn parent_would_cycle(
+  tasks: &[Task],
+  child: Uuid,
+  parent: Uuid
+) -> bool {
+  let mut seen = HashSet::new();
+  let mut cursor = Some(parent);
+  while let Some(uuid) = cursor {
+    if uuid == child {
+      return true;
+    }
+    if !seen.insert(uuid) {
+      return false;
+    }
+    cursor = tasks
+      .iter()
+      .find(|task| task.uuid == uuid)
+      .and_then(|task| task.parent);
+  }
+  false
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{
+    Duration,
+    TimeZone,
+    Utc
+  };
+
+  use super::{
+    CompletionPolicy,
+    DescriptionLimit,
+    DescriptionOverflow,
+    StalePolicy,
+    Status,
+    SubtaskCompletion,
+    TagRemap,
+    Task,
+    UdaRename,
+    parent_would_cycle,
+    shell_quote,
+    subtask_progress
+  };
+
+  #[test]
+  fn stopping_the_clock_accumulates_focus_time()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 9, 0, 0
+      )
+      .unwrap();
+    let mut task = Task::new_pending(
+      "Write report".to_string(),
+      now,
+      1
+    );
+    assert_eq!(
+      task.stop_clock(now),
+      None
+    );
+
+    task.start = Some(now);
+    let later =
+      now + Duration::minutes(25);
+    assert_eq!(
+      task.stop_clock(later),
+      Some(1500)
+    );
+    assert!(task.start.is_none());
+
+    task.start = Some(later);
+    task.stop_clock(
+      later + Duration::minutes(5)
+    );
+    assert_eq!(
+      task.focus_seconds(),
+      1800
+    );
+  }
+
+  #[test]
+  fn review_stamp_round_trips_and_orders_unreviewed_first()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 9, 30, 0
+      )
+      .unwrap();
+    let mut reviewed =
+      Task::new_pending(
+        "Old".to_string(),
+        now - Duration::days(3),
+        1
+      );
+    let fresh = Task::new_pending(
+      "New".to_string(),
+      now,
+      2
+    );
+    assert_eq!(
+      fresh.reviewed_at(),
+      None
+    );
+
+    reviewed.mark_reviewed(now);
+    assert_eq!(
+      reviewed.reviewed_at(),
+      Some(now)
+    );
+    assert_eq!(reviewed.modified, now);
+    assert_eq!(
+      reviewed.extra["reviewed"],
+      "20260216T093000Z"
+    );
+
+    let mut queue = [reviewed, fresh];
+    queue.sort_by_key(|task| {
+      (task.reviewed_at(), task.id)
+    });
+    assert_eq!(
+      queue[0].description,
+      "New"
+    );
+  }
+
+  #[test]
+  fn shell_quote_escapes_special_characters()
+   {
+    assert_eq!(
+      shell_quote("plain-word"),
+      "plain-word"
+    );
+    assert_eq!(shell_quote(""), "''");
+    assert_eq!(
+      shell_quote("it's $HOME"),
+      "'it'\\''s $HOME'"
+    );
+  }
+
+  #[test]
+  fn add_command_recreates_user_fields()
+  {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 5, 0, 0
+      )
+      .unwrap();
+    let mut task = Task::new_pending(
+      "Buy \"milk\" +later".to_string(),
+      now,
+      7
+    );
+    task.project =
+      Some("home chores".to_string());
+    task.tags =
+      vec!["errand".to_string()];
+    task.priority =
+      Some("H".to_string());
+    task.due = Some(now);
+
+    assert_eq!(
+      task.to_add_command(),
+      "task add 'project:home chores' \
+       +errand priority:H \
+       due:20260216T050000Z -- 'Buy \
+       \"milk\" +later'"
+    );
+  }
+
+  #[test]
+  fn stale_requires_old_modified_and_no_upcoming_dates()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 12, 0, 0
+      )
+      .single()
+      .expect("valid now");
+    let policy = StalePolicy::default();
+    let mut task = Task::new_pending(
+      "forgotten".to_string(),
+      now - chrono::Duration::days(20),
+      1
+    );
+    assert!(
+      task.is_stale(now, &policy)
+    );
+
+    task.modified =
+      now - chrono::Duration::days(3);
+    assert!(
+      !task.is_stale(now, &policy)
+    );
+
+    task.modified =
+      now - chrono::Duration::days(20);
+    task.due = Some(
+      now + chrono::Duration::days(2)
+    );
+    assert!(
+      !task.is_stale(now, &policy)
+    );
+
+    task.due = Some(
+      now - chrono::Duration::days(2)
+    );
+    assert!(
+      task.is_stale(now, &policy)
+    );
+
+    task
+      .depends
+      .push(uuid::Uuid::nil());
+    assert!(
+      !task.is_stale(now, &policy)
+    );
+    assert!(task.is_stale(
+      now,
+      &StalePolicy {
+        include_blocked: true,
+        ..policy
+      }
+    ));
+
+    task.depends.clear();
+    task.wait = Some(
+      now + chrono::Duration::days(5)
+    );
+    assert!(
+      !task.is_stale(now, &policy)
+    );
+
+    task.wait = None;
+    assert!(!task.is_stale(
+      now,
+      &StalePolicy {
+        days: i64::MAX,
+        ..policy
+      }
+    ));
+    assert!(
+      StalePolicy::check_days(
+        99_999_999_999_999
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn subtask_progress_and_parent_cycles()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 12, 0, 0
+      )
+      .unwrap();
+    let root = Task::new_pending(
+      "root".to_string(),
+      now,
+      1
+    );
+    let mut child = Task::new_pending(
+      "child".to_string(),
+      now,
+      2
+    );
+    child.parent = Some(root.uuid);
+    let mut grandchild =
+      Task::new_pending(
+        "grandchild".to_string(),
+        now,
+        3
+      );
+    grandchild.parent =
+      Some(child.uuid);
+    let mut finished =
+      Task::new_pending(
+        "finished".to_string(),
+        now,
+        4
+      );
+    finished.parent = Some(root.uuid);
+    finished.status = Status::Completed;
+    let mut dropped = Task::new_pending(
+      "dropped".to_string(),
+      now,
+      5
+    );
+    dropped.parent = Some(root.uuid);
+    dropped.status = Status::Deleted;
+
+    let tasks = vec![
+      root.clone(),
+      child.clone(),
+      grandchild.clone(),
+      finished,
+      dropped,
+    ];
+    assert_eq!(
+      subtask_progress(
+        &tasks, root.uuid
+      ),
+      (1, 2)
+    );
+    assert_eq!(
+      subtask_progress(
+        &tasks,
+        grandchild.uuid
+      ),
+      (0, 0)
+    );
+
+    assert!(parent_would_cycle(
+      &tasks,
+      root.uuid,
+      grandchild.uuid
+    ));
+    assert!(parent_would_cycle(
+      &tasks, root.uuid, root.uuid
+    ));
+    assert!(!parent_would_cycle(
+      &tasks,
+      grandchild.uuid,
+      root.uuid
+    ));
+  }
+
+  #[test]
+  fn tag_remap_rewrites_one_key_value()
+  {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 2, 9, 0, 0
+      )
+      .unwrap();
+    let remap = TagRemap::parse(
+      "kanban:working=in_progress"
+    )
+    .expect("parse");
+    assert_eq!(
+      remap,
+      TagRemap::parse(
+        "kanban:working=kanban:\
+         in_progress"
+      )
+      .expect("parse with key")
+    );
+    assert!(
+      TagRemap::parse("working=done")
+        .is_err()
+    );
+
+    let mut moved = Task::new_pending(
+      "moved".to_string(),
+      now,
+      1
+    );
+    moved.tags = vec![
+      "home".to_string(),
+      "kanban:working".to_string(),
+    ];
+    let mut both = moved.clone();
+    both.tags.push(
+      "kanban:in_progress".to_string()
+    );
+    let mut other = moved.clone();
+    other.tags =
+      vec!["board:working".to_string()];
+    let mut tasks =
+      vec![moved, both, other];
+
+    let later =
+      now + Duration::hours(1);
+    assert_eq!(
+      remap.apply(&mut tasks, later),
+      2
+    );
+    assert_eq!(tasks[0].tags, vec![
+      "home",
+      "kanban:in_progress"
+    ]);
+    assert_eq!(tasks[1].tags, vec![
+      "home",
+      "kanban:in_progress"
+    ]);
+    assert_eq!(
+      tasks[0].modified,
+      later
+    );
+    assert_eq!(tasks[2].tags, vec![
+      "board:working"
+    ]);
+  }
+
+  #[test]
+  fn uda_rename_moves_values_and_guards_names()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 2, 9, 0, 0
+      )
+      .unwrap();
+    assert!(
+      UdaRename::new("est", "due")
+        .is_err()
+    );
+    assert!(
+      UdaRename::new("Project", "x")
+        .is_err()
+    );
+    assert!(
+      UdaRename::new("est", "est")
+        .is_err()
+    );
+    assert!(
+      UdaRename::new("est", "a.b")
+        .is_err()
+    );
+    let rename =
+      UdaRename::new("est", "estimate")
+        .expect("rename");
+
+    let mut with = Task::new_pending(
+      "with".to_string(),
+      now,
+      1
+    );
+    with.extra.insert(
+      "est".to_string(),
+      3.into()
+    );
+    let without = Task::new_pending(
+      "without".to_string(),
+      now,
+      2
+    );
+    let mut tasks = vec![with, without];
+    let later =
+      now + Duration::hours(1);
+    assert_eq!(
+      rename
+        .apply(&mut tasks, later)
+        .expect("apply"),
+      1
+    );
+    assert_eq!(
+      tasks[0].extra.get("estimate"),
+      Some(&3.into())
+    );
+    assert!(
+      !tasks[0]
+        .extra
+        .contains_key("est")
+    );
+    assert_eq!(
+      tasks[0].modified,
+      later
+    );
+    assert_eq!(tasks[1].modified, now);
+
+    tasks[1].extra.insert(
+      "est".to_string(),
+      1.into()
+    );
+    tasks[1].extra.insert(
+      "estimate".to_string(),
+      2.into()
+    );
+    assert!(
+      rename
+        .apply(&mut tasks, now)
+        .is_err()
+    );
+    assert!(
+      tasks[1]
+        .extra
+        .contains_key("est")
+    );
+  }
+
+  #[test]
+  fn completion_policy_reads_on_complete_and_legacy_keys()
+   {
+    let temp = tempfile::tempdir()
+      .expect("tempdir");
+    let taskrc =
+      temp.path().join("taskrc");
+    std::fs::write(&taskrc, "")
+      .expect("write taskrc");
+    let policy =
+      |pairs: &[(&str, &str)]| {
+        let mut cfg =
+          crate::config::Config::load(
+            Some(&taskrc)
+          )
+          .expect("load config");
+        cfg.apply_overrides(
+          pairs.iter().map(
+            |(key, value)| {
+              (
+                key.to_string(),
+                value.to_string()
+              )
+            }
+          )
+        );
+        CompletionPolicy::from_config(
+          &cfg
+        )
+      };
+
+    let defaults =
+      policy(&[]).expect("defaults");
+    assert_eq!(
+      defaults.subtasks,
+      SubtaskCompletion::Warn
+    );
+    assert!(!defaults.notify_unblocked);
+    assert_eq!(
+      policy(&[(
+        "subtasks.complete_children",
+        "yes"
+      )])
+      .expect("legacy")
+      .subtasks,
+      SubtaskCompletion::Cascade
+    );
+    let configured = policy(&[
+      (
+        "subtasks.complete_children",
+        "yes"
+      ),
+      ("on_complete.subtasks", "Block"),
+      (
+        "on_complete.dependents",
+        "notify"
+      )
+    ])
+    .expect("configured");
+    assert_eq!(
+      configured.subtasks,
+      SubtaskCompletion::Block
+    );
+    assert!(
+      configured.notify_unblocked
+    );
+    assert!(
+      policy(&[(
+        "on_complete.subtasks",
+        "sometimes"
+      )])
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn description_limit_truncates_or_rejects()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 2, 16, 9, 0, 0
+      )
+      .unwrap();
+    let long = "Import the quarterly \
+                report   and more";
+    let task = || {
+      Task::new_pending(
+        long.to_string(),
+        now,
+        1
+      )
+    };
+
+    let mut unlimited = task();
+    assert!(
+      !DescriptionLimit::default()
+        .apply(&mut unlimited, now)
+        .expect("no limit")
+    );
+    assert_eq!(
+      unlimited.description,
+      long
+    );
+
+    let truncate = DescriptionLimit {
+      max_length: Some(30),
+      overflow:
+        DescriptionOverflow::Truncate
+    };
+    let mut cut = task();
+    assert!(
+      truncate
+        .apply(&mut cut, now)
+        .expect("truncates")
+    );
+    assert_eq!(
+      cut.description,
+      "Import the quarterly \
+       report\u{2026}"
+    );
+    assert_eq!(
+      cut.annotations[0].description,
+      long
+    );
+    // Re-applying to a description that
+    // already fits changes nothing.
+    assert!(
+      !truncate
+        .apply(&mut cut, now)
+        .expect("fits")
+    );
+    assert_eq!(
+      cut.annotations.len(),
+      1
+    );
+
+    let reject = DescriptionLimit {
+      max_length: Some(30),
+      overflow:
+        DescriptionOverflow::Reject
+    };
+    assert!(
+      reject
+        .apply(&mut task(), now)
+        .is_err()
+    );
+    assert_eq!(
+      DescriptionOverflow::parse(
+        " Reject "
+      ),
+      Some(DescriptionOverflow::Reject)
+    );
+    assert_eq!(
+      DescriptionOverflow::parse("cut"),
+      None
+    );
+  }
+}
@@ -1,9 +1,38 @@
-use chrono::Utc;
-use rivet_core::datastore::DataStore;
-use rivet_core::filter::Filter;
+use chrono::{
+  TimeZone,
+  Utc
+};
+use rivet_core::commands::{
+  QuickAddCorrection,
+  capabilities,
+  correct_quick_add_tokens,
+  expand_command_abbrev,
+  known_command_names
+};
+use rivet_core::config::{
+  Config,
+  ConfigSource
+};
+use rivet_core::datastore::{
+  DataStore,
+  STORE_FORMAT_VERSION
+};
+use rivet_core::filter::{
+  Filter,
+  VirtualTag
+};
+use rivet_core::history::diff_snapshots;
+use rivet_core::index_file;
+use rivet_core::migrate::{
+  read_manifest,
+  write_manifest
+};
 use rivet_core::task::{
   Status,
-  Task
+  Task,
+  TaskDefaults,
+  UrgencyCoefficients,
+  subtask_progress
 };
 use tempfile::tempdir;
 
@@ -62,3 +91,1228 @@ fn datastore_roundtrip_and_filtering() {
     1
   );
 }
+
+#[test]
+fn capabilities_describe_commands_udas_and_versions()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let taskrc =
+    temp.path().join("taskrc");
+  std::fs::write(
+    &taskrc,
+    "uda.estimate.type=numeric\nuda.\
+     estimate.label=Estimate\n"
+  )
+  .expect("write taskrc");
+  let cfg = Config::load(Some(&taskrc))
+    .expect("load config");
+
+  let caps = capabilities(&cfg);
+  assert_eq!(
+    caps["store_format_version"],
+    STORE_FORMAT_VERSION
+  );
+  assert_eq!(
+    caps["dto_version"],
+    rivet_gui_shared::DTO_VERSION
+  );
+  assert!(
+    caps["commands"]
+      .as_array()
+      .expect("commands array")
+      .iter()
+      .any(|name| {
+        name == "_capabilities"
+      })
+  );
+  assert_eq!(
+    caps["udas"][0]["name"],
+    "estimate"
+  );
+  assert_eq!(
+    caps["udas"][0]["type"],
+    "numeric"
+  );
+  assert!(
+    VirtualTag::NAMES.iter().all(
+      |name| {
+        VirtualTag::parse(name)
+          .is_some()
+      }
+    )
+  );
+}
+
+#[test]
+fn original_command_prefixes_win_over_newer_commands()
+ {
+  let known = known_command_names();
+  assert_eq!(
+    expand_command_abbrev(
+      "sta", &known
+    ),
+    Some("start")
+  );
+  assert_eq!(
+    expand_command_abbrev(
+      "stal", &known
+    ),
+    Some("stale")
+  );
+  assert_eq!(
+    expand_command_abbrev(
+      "stat", &known
+    ),
+    Some("stats")
+  );
+  assert_eq!(
+    expand_command_abbrev("d", &known),
+    None
+  );
+  for token in ["ex", "exp"] {
+    assert_eq!(
+      expand_command_abbrev(
+        token, &known
+      ),
+      Some("export")
+    );
+  }
+  assert_eq!(
+    expand_command_abbrev(
+      "expl", &known
+    ),
+    Some("explain")
+  );
+}
+
+#[test]
+fn datastore_index_serves_lookups_and_sees_external_writes()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  let now = Utc::now();
+
+  let mut first = Task::new_pending(
+    "first".to_string(),
+    now,
+    1
+  );
+  first.tags = vec!["home".to_string()];
+  first.project =
+    Some("rivet".to_string());
+  let second = Task::new_pending(
+    "second".to_string(),
+    now,
+    2
+  );
+  store
+    .save_pending(&[
+      first.clone(),
+      second.clone()
+    ])
+    .expect("save pending");
+
+  let index = store
+    .pending_index()
+    .expect("pending index");
+  assert_eq!(
+    index
+      .get(&second.uuid)
+      .map(|task| task.id),
+    Some(Some(2))
+  );
+  assert_eq!(
+    index.with_tag("home").count(),
+    1
+  );
+  assert_eq!(
+    index.in_project("rivet").count(),
+    1
+  );
+  assert_eq!(
+    index
+      .with_status(&Status::Pending)
+      .count(),
+    2
+  );
+  let filter = Filter::parse(
+    &[
+      "+home".to_string(),
+      "project:rivet".to_string()
+    ],
+    now
+  )
+  .expect("filter");
+  let candidates = index
+    .candidates(&filter.index_keys());
+  assert_eq!(candidates.len(), 1);
+  assert_eq!(
+    candidates[0].uuid,
+    first.uuid
+  );
+  let either = Filter::parse(
+    &[
+      "+home".to_string(),
+      "or".to_string(),
+      "+work".to_string()
+    ],
+    now
+  )
+  .expect("filter");
+  assert!(
+    either.index_keys().is_empty()
+  );
+  assert_eq!(
+    index
+      .candidates(&either.index_keys())
+      .len(),
+    2
+  );
+
+  let external = Task::new_pending(
+    "written by another process"
+      .to_string(),
+    now,
+    7
+  );
+  std::fs::write(
+    &store.pending_path,
+    format!(
+      "{}\n",
+      serde_json::to_string(&external)
+        .expect("serialize")
+    )
+  )
+  .expect("external write");
+
+  let reloaded = store
+    .load_pending()
+    .expect("reload pending");
+  assert_eq!(reloaded.len(), 1);
+  assert_eq!(reloaded[0].id, Some(7));
+}
+
+#[test]
+fn dry_run_stages_writes_without_touching_disk()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let mut store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  let now = Utc::now();
+  let task = Task::new_pending(
+    "keep me".to_string(),
+    now,
+    1
+  );
+  store
+    .save_pending(&[task])
+    .expect("save pending");
+  let on_disk =
+    std::fs::read_to_string(
+      &store.pending_path
+    )
+    .expect("read pending");
+
+  store.set_dry_run(true);
+  store
+    .push_current_undo_snapshot()
+    .expect("undo snapshot");
+  store
+    .save_pending(&[])
+    .expect("staged save");
+
+  assert!(
+    store
+      .load_pending()
+      .expect("load staged")
+      .is_empty()
+  );
+  assert_eq!(
+    std::fs::read_to_string(
+      &store.pending_path
+    )
+    .expect("read pending"),
+    on_disk
+  );
+  assert_eq!(
+    std::fs::read_to_string(
+      &store.undo_path
+    )
+    .expect("read undo"),
+    ""
+  );
+}
+
+#[test]
+fn redo_reapplies_undone_bulk_writes_until_a_new_write()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let mut store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  store.set_undo_depth(Some(2));
+  let now = Utc::now();
+  let descriptions =
+    |store: &DataStore| {
+      store
+        .load_pending()
+        .expect("load pending")
+        .into_iter()
+        .map(|task| task.description)
+        .collect::<Vec<_>>()
+    };
+  // One snapshot per command, however
+  // many tasks it touches.
+  let write =
+    |store: &DataStore,
+     names: &[&str]| {
+      store
+        .push_current_undo_snapshot()
+        .expect("undo snapshot");
+      let tasks = names
+        .iter()
+        .zip(1..)
+        .map(|(name, id)| {
+          Task::new_pending(
+            (*name).to_string(),
+            now,
+            id
+          )
+        })
+        .collect::<Vec<_>>();
+      store
+        .save_pending(&tasks)
+        .expect("save pending");
+    };
+
+  write(&store, &["a"]);
+  write(&store, &["a", "b", "c"]);
+  write(&store, &["a", "b", "c", "d"]);
+  assert_eq!(
+    store
+      .undo_redo_depths()
+      .expect("depths"),
+    (2, 0)
+  );
+
+  assert!(store.undo().expect("undo"));
+  assert!(store.undo().expect("undo"));
+  assert!(!store.undo().expect("undo"));
+  assert_eq!(descriptions(&store), [
+    "a"
+  ]);
+
+  assert!(store.redo().expect("redo"));
+  assert_eq!(descriptions(&store), [
+    "a", "b", "c"
+  ]);
+  assert_eq!(
+    store
+      .undo_redo_depths()
+      .expect("depths"),
+    (1, 1)
+  );
+
+  write(&store, &["x"]);
+  assert!(!store.redo().expect("redo"));
+  assert!(store.undo().expect("undo"));
+  assert_eq!(descriptions(&store), [
+    "a", "b", "c"
+  ]);
+}
+
+#[test]
+fn persisted_index_is_reused_and_rebuilt_when_stale_or_corrupt()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let open = || {
+    let mut store =
+      DataStore::open(temp.path())
+        .expect("open datastore");
+    store.set_persist_index(true);
+    store
+  };
+  let as_json = |tasks: &[Task]| {
+    serde_json::to_value(tasks)
+      .expect("serialize")
+  };
+  let now = Utc::now();
+  let mut task = Task::new_pending(
+    "indexed".to_string(),
+    now,
+    1
+  );
+  task.tags = vec!["home".to_string()];
+  task.due = Some(now);
+  task.extra.insert(
+    "estimate".to_string(),
+    serde_json::json!(3)
+  );
+
+  let store = open();
+  store
+    .save_pending(&[task])
+    .expect("save pending");
+  let sidecar = index_file::index_path(
+    &store.pending_path
+  );
+  let written = std::fs::read(&sidecar)
+    .expect("sidecar written");
+  let expected = as_json(
+    &store
+      .load_pending()
+      .expect("load pending")
+  );
+  assert_eq!(
+    index_file::load(
+      &store.pending_path
+    )
+    .map(|tasks| as_json(&tasks)),
+    Some(expected.clone())
+  );
+
+  std::fs::write(&sidecar, b"garbage")
+    .expect("corrupt sidecar");
+  assert_eq!(
+    as_json(
+      &open()
+        .load_pending()
+        .expect("load past corruption")
+    ),
+    expected
+  );
+  assert_eq!(
+    std::fs::read(&sidecar)
+      .expect("sidecar rebuilt"),
+    written
+  );
+
+  let external = Task::new_pending(
+    "written without the index"
+      .to_string(),
+    now,
+    9
+  );
+  std::fs::write(
+    &store.pending_path,
+    format!(
+      "{}\n",
+      serde_json::to_string(&external)
+        .expect("serialize")
+    )
+  )
+  .expect("external write");
+  assert!(
+    index_file::load(
+      &store.pending_path
+    )
+    .is_none()
+  );
+  let reloaded = open()
+    .load_pending()
+    .expect("load past stale index");
+  assert_eq!(reloaded.len(), 1);
+  assert_eq!(reloaded[0].id, Some(9));
+  assert!(
+    index_file::load(
+      &store.pending_path
+    )
+    .is_some()
+  );
+}
+
+#[test]
+fn subtask_parent_links_round_trip() {
+  let temp =
+    tempdir().expect("tempdir");
+  let store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  let now = Utc::now();
+  let parent = Task::new_pending(
+    "ship release".to_string(),
+    now,
+    1
+  );
+  let mut child = Task::new_pending(
+    "write notes".to_string(),
+    now,
+    2
+  );
+  child.parent = Some(parent.uuid);
+  store
+    .save_pending(&[
+      parent.clone(),
+      child
+    ])
+    .expect("save pending");
+
+  let pending = store
+    .load_pending()
+    .expect("load pending");
+  assert_eq!(
+    pending[1].parent,
+    Some(parent.uuid)
+  );
+  assert_eq!(
+    subtask_progress(
+      &pending,
+      parent.uuid
+    ),
+    (0, 1)
+  );
+  let raw = std::fs::read_to_string(
+    &store.pending_path
+  )
+  .expect("read pending");
+  assert_eq!(
+    raw.matches("\"parent\"").count(),
+    1
+  );
+}
+
+#[test]
+fn history_log_appends_and_prunes() {
+  let temp =
+    tempdir().expect("tempdir");
+  let mut store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  let now = Utc
+    .with_ymd_and_hms(
+      2026, 3, 1, 12, 0, 0
+    )
+    .single()
+    .expect("valid now");
+  let kept = Task::new_pending(
+    "kept".to_string(),
+    now,
+    1
+  );
+  let purged = Task::new_pending(
+    "purged".to_string(),
+    now,
+    2
+  );
+  let entries = diff_snapshots(
+    "add",
+    &[],
+    &[kept.clone(), purged.clone()],
+    now
+  );
+  store
+    .append_history(&entries[..1])
+    .expect("append first");
+  store
+    .append_history(&entries[1..])
+    .expect("append second");
+  assert_eq!(
+    store
+      .load_history()
+      .expect("load history"),
+    entries
+  );
+
+  store.set_dry_run(true);
+  store
+    .append_history(&entries)
+    .expect("dry-run append");
+  store.set_dry_run(false);
+  assert_eq!(
+    store
+      .prune_history(|entry| {
+        entry.uuid != purged.uuid
+      })
+      .expect("prune"),
+    1
+  );
+  let remaining = store
+    .load_history()
+    .expect("reload history");
+  assert_eq!(remaining.len(), 1);
+  assert_eq!(
+    remaining[0].uuid,
+    kept.uuid
+  );
+}
+
+#[test]
+fn urgency_user_coefficients_stack_project_and_tag_boosts()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let taskrc =
+    temp.path().join("taskrc");
+  std::fs::write(
+    &taskrc,
+    [
+      "urgency.user.project.work.\
+       coefficient=5",
+      "urgency.user.tag.urgent.\
+       coefficient=8",
+      "urgency.user.tag.later.\
+       coefficient=-2.5",
+      "urgency.user.tag.noop.\
+       coefficient=0",
+      "urgency.user.tag.bad.\
+       coefficient=lots",
+      "urgency.user.keyword.x.\
+       coefficient=9"
+    ]
+    .join("\n")
+  )
+  .expect("write taskrc");
+  let cfg = Config::load(Some(&taskrc))
+    .expect("load config");
+  let coefficients =
+    UrgencyCoefficients::from_config(
+      &cfg
+    );
+  assert_eq!(
+    coefficients.tags.len(),
+    2
+  );
+
+  let now = Utc
+    .with_ymd_and_hms(
+      2026, 3, 1, 12, 0, 0
+    )
+    .unwrap();
+  let mut task = Task::new_pending(
+    "file report".to_string(),
+    now,
+    1
+  );
+  task.project =
+    Some("work.reports".to_string());
+  task.tags = vec![
+    "urgent".to_string(),
+    "later".to_string(),
+    "noop".to_string(),
+    "bad".to_string(),
+  ];
+
+  let base = task.urgency(
+    now,
+    &UrgencyCoefficients::default()
+  );
+  let boosted =
+    task.urgency(now, &coefficients);
+  assert!(
+    (boosted - base - 10.5).abs()
+      < 1e-9
+  );
+
+  task.project =
+    Some("workshop".to_string());
+  assert!(
+    (coefficients.boost(&task) - 5.5)
+      .abs()
+      < 1e-9
+  );
+
+  task.status = Status::Completed;
+  assert_eq!(
+    task.urgency(now, &coefficients),
+    0.0
+  );
+}
+
+#[test]
+fn task_defaults_yield_to_context_and_explicit_values()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let taskrc =
+    temp.path().join("taskrc");
+  std::fs::write(
+    &taskrc,
+    [
+      "default.project=home",
+      "default.tags=+chores, errands",
+      "context.work=project:work \
+       +office due.before:eow",
+      "context.either=+office or \
+       +remote",
+      "context.focus=+deep",
+      "context.focus.write=pro:focus \
+       +deep"
+    ]
+    .join("\n")
+  )
+  .expect("write taskrc");
+  let cfg = Config::load(Some(&taskrc))
+    .expect("load config");
+
+  let now = Utc
+    .with_ymd_and_hms(
+      2026, 3, 1, 12, 0, 0
+    )
+    .unwrap();
+  let new_task = || {
+    Task::new_pending(
+      "task".to_string(),
+      now,
+      1
+    )
+  };
+
+  let plain = TaskDefaults::from_config(
+    &cfg, None
+  );
+  let mut task = new_task();
+  plain.apply(&mut task);
+  assert_eq!(
+    task.project.as_deref(),
+    Some("home")
+  );
+  assert_eq!(task.tags, vec![
+    "chores", "errands"
+  ]);
+
+  let mut explicit = new_task();
+  explicit.project =
+    Some("garden".to_string());
+  explicit.tags =
+    vec!["outside".to_string()];
+  plain.apply(&mut explicit);
+  assert_eq!(
+    explicit.project.as_deref(),
+    Some("garden")
+  );
+  assert_eq!(explicit.tags, vec![
+    "outside"
+  ]);
+
+  let work = TaskDefaults::from_config(
+    &cfg,
+    Some("work")
+  );
+  let mut task = new_task();
+  task.tags = vec!["call".to_string()];
+  work.apply(&mut task);
+  assert_eq!(
+    task.project.as_deref(),
+    Some("work")
+  );
+  assert_eq!(task.tags, vec![
+    "call", "office"
+  ]);
+
+  let either =
+    TaskDefaults::from_config(
+      &cfg,
+      Some("either")
+    );
+  assert_eq!(either, plain);
+
+  let focus = TaskDefaults::from_config(
+    &cfg,
+    Some("focus")
+  );
+  assert_eq!(
+    focus.project.as_deref(),
+    Some("focus")
+  );
+  assert_eq!(focus.context_tags, vec![
+    "deep"
+  ]);
+}
+
+#[test]
+fn quick_add_typos_snap_to_known_keys_and_values()
+ {
+  let now = Utc
+    .with_ymd_and_hms(
+      2026, 3, 1, 12, 0, 0
+    )
+    .unwrap();
+  let mut known = Task::new_pending(
+    "known".to_string(),
+    now,
+    1
+  );
+  known.project =
+    Some("home".to_string());
+  known.tags = vec!["work".to_string()];
+
+  let words = |line: &str| {
+    line
+      .split_whitespace()
+      .map(str::to_string)
+      .collect::<Vec<_>>()
+  };
+
+  let (args, fixes) =
+    correct_quick_add_tokens(
+      &words(
+        "Buy milk projetc:hmoe +wrok \
+         due:tomorrow"
+      ),
+      std::slice::from_ref(&known)
+    );
+  assert_eq!(
+    args,
+    words(
+      "Buy milk project:home +work \
+       due:tomorrow"
+    )
+  );
+  assert_eq!(fixes, vec![
+    QuickAddCorrection {
+      original:  "projetc:hmoe".into(),
+      corrected: "project:home".into()
+    },
+    QuickAddCorrection {
+      original:  "+wrok".into(),
+      corrected: "+work".into()
+    },
+  ]);
+
+  // Far-off words, new projects and
+  // anything after `--` stay as typed.
+  let line = words(
+    "Call: dentist project:garden \
+     +errands -- projetc:hmoe"
+  );
+  let (args, fixes) =
+    correct_quick_add_tokens(
+      &line,
+      std::slice::from_ref(&known)
+    );
+  assert_eq!(args, line);
+  assert!(fixes.is_empty());
+}
+
+#[test]
+fn compact_ids_renumbers_open_tasks_and_drops_closed_ids()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  let now = Utc::now();
+
+  let mut pending: Vec<Task> = [
+    (2, Status::Pending),
+    (5, Status::Deleted),
+    (7, Status::Waiting),
+    (9, Status::Pending)
+  ]
+  .into_iter()
+  .map(|(id, status)| {
+    let mut task = Task::new_pending(
+      format!("task {id}"),
+      now,
+      id
+    );
+    task.status = status;
+    task
+  })
+  .collect();
+  let mut done = pending.remove(0);
+  done.status = Status::Completed;
+  store
+    .save_pending(&pending)
+    .expect("save pending");
+  store
+    .save_completed(&[done])
+    .expect("save completed");
+
+  assert!(
+    store
+      .compact_ids()
+      .expect("compact ids")
+  );
+  let ids: Vec<(String, Option<u64>)> =
+    store
+      .load_pending()
+      .expect("load pending")
+      .into_iter()
+      .map(|task| {
+        (task.description, task.id)
+      })
+      .collect();
+  assert_eq!(ids, vec![
+    ("task 7".to_string(), Some(1)),
+    ("task 9".to_string(), Some(2)),
+    ("task 5".to_string(), None),
+  ]);
+  assert_eq!(
+    store
+      .load_completed()
+      .expect("load completed")[0]
+      .id,
+    None
+  );
+  assert!(
+    !store
+      .compact_ids()
+      .expect("second compact")
+  );
+}
+
+#[test]
+fn verbose_setting_filters_tokens_like_taskwarrior()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let taskrc =
+    temp.path().join("taskrc");
+  let load = |verbose: &str| {
+    std::fs::write(
+      &taskrc,
+      format!("verbose={verbose}\n")
+    )
+    .expect("write taskrc");
+    Config::load(Some(&taskrc))
+      .expect("load config")
+  };
+
+  assert!(load("on").verbose("nag"));
+  assert!(!load("off").verbose("nag"));
+  assert!(
+    !load("nothing").verbose("nag")
+  );
+  assert!(
+    load("blank,nag").verbose("nag")
+  );
+  assert!(
+    !load("blank,footnote")
+      .verbose("nag")
+  );
+}
+
+#[test]
+fn corrupt_lines_are_quarantined_instead_of_failing_loads()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let now = Utc::now();
+  let good = serde_json::to_string(
+    &Task::new_pending(
+      "Still readable".to_string(),
+      now,
+      1
+    )
+  )
+  .expect("serialize task");
+  std::fs::write(
+    temp.path().join("pending.data"),
+    [
+      good.as_str(),
+      "{\"uuid\": oops",
+      ""
+    ]
+    .join("\n")
+  )
+  .expect("write pending");
+  std::fs::write(
+    temp.path().join("history.data"),
+    "not json\n"
+  )
+  .expect("write history");
+
+  let mut store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  store.set_dry_run(true);
+  assert_eq!(
+    store
+      .load_pending()
+      .expect("load pending")
+      .len(),
+    1
+  );
+  assert!(
+    store
+      .load_quarantined()
+      .expect("load quarantine")
+      .is_empty(),
+    "dry runs leave the sidecar alone"
+  );
+
+  store.set_dry_run(false);
+  store.invalidate_cache();
+  assert_eq!(
+    store
+      .load_pending()
+      .expect("load pending")
+      .len(),
+    1
+  );
+  assert!(
+    store
+      .load_history()
+      .expect("load history")
+      .is_empty()
+  );
+  store.invalidate_cache();
+  store
+    .load_pending()
+    .expect("reload pending");
+
+  let quarantined = store
+    .load_quarantined()
+    .expect("load quarantine");
+  let seen: Vec<(&str, usize)> =
+    quarantined
+      .iter()
+      .map(|entry| {
+        (
+          entry.file.as_str(),
+          entry.line
+        )
+      })
+      .collect();
+  assert_eq!(seen, vec![
+    ("pending.data", 2),
+    ("history.data", 1)
+  ]);
+  assert_eq!(
+    quarantined[0].raw,
+    "{\"uuid\": oops"
+  );
+
+  let pending = store
+    .load_pending()
+    .expect("load pending");
+  store
+    .save_pending(&pending)
+    .expect("save pending");
+  assert!(
+    !std::fs::read_to_string(
+      temp.path().join("pending.data")
+    )
+    .expect("read pending")
+    .contains("oops")
+  );
+}
+
+#[test]
+fn config_sources_track_defaults_files_and_overrides()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let taskrc =
+    temp.path().join("taskrc");
+  let include =
+    temp.path().join("extra.rc");
+  std::fs::write(
+    &taskrc,
+    [
+      "# comment",
+      "color=off",
+      &format!(
+        "include {}",
+        include.display()
+      ),
+      "weekstart=monday"
+    ]
+    .join("\n")
+  )
+  .expect("write taskrc");
+  std::fs::write(
+    &include,
+    "weekstart=sunday\n"
+  )
+  .expect("write include");
+
+  let mut cfg =
+    Config::load(Some(&taskrc))
+      .expect("load config");
+  assert_eq!(
+    cfg.source("default.command"),
+    Some(&ConfigSource::Default)
+  );
+  assert_eq!(
+    cfg.source("color"),
+    Some(&ConfigSource::File {
+      path: taskrc.clone(),
+      line: 2
+    })
+  );
+  assert_eq!(
+    cfg.source("weekstart"),
+    Some(&ConfigSource::File {
+      path: taskrc.clone(),
+      line: 4
+    })
+  );
+
+  cfg.apply_overrides([(
+    "rc.color".to_string(),
+    "on".to_string()
+  )]);
+  assert_eq!(
+    cfg.get("color").as_deref(),
+    Some("on")
+  );
+  assert_eq!(
+    cfg.source("color"),
+    Some(&ConfigSource::Override)
+  );
+  assert_eq!(
+    cfg.source("missing"),
+    None
+  );
+}
+
+#[test]
+fn archived_tasks_hide_from_filters_and_export_as_completed()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let mut store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  store.set_persist_index(true);
+  let now = Utc::now();
+  let mut task = Task::new_pending(
+    "old chores".to_string(),
+    now,
+    1
+  );
+  task.tags = vec!["home".to_string()];
+  task.id = None;
+  task.status = Status::Archived;
+
+  let matches = |terms: &[&str]| {
+    let terms: Vec<String> = terms
+      .iter()
+      .map(ToString::to_string)
+      .collect();
+    Filter::parse(&terms, now)
+      .expect("parse filter")
+      .matches(&task, now)
+  };
+  assert!(!matches(&["+home"]));
+  assert!(matches(&[
+    "+home",
+    "status:archived"
+  ]));
+  assert!(matches(&["+ARCHIVED"]));
+  assert!(!matches(&[
+    "status:completed"
+  ]));
+
+  store
+    .save_completed(&[task.clone()])
+    .expect("save completed");
+  assert_eq!(
+    read_manifest(temp.path())
+      .expect("read manifest")
+      .expect("manifest written")
+      .format_version,
+    STORE_FORMAT_VERSION
+  );
+  assert_ne!(
+    STORE_FORMAT_VERSION, "1.0.0",
+    "archived tasks need a store \
+     format 1.0.0 builds refuse"
+  );
+  let reloaded =
+    DataStore::open(temp.path())
+      .expect("reopen datastore")
+      .load_completed()
+      .expect("load completed");
+  assert_eq!(
+    reloaded[0].status,
+    Status::Archived
+  );
+  assert_eq!(
+    task.unarchived_status(),
+    Status::Pending
+  );
+
+  let mut exported =
+    task.clone().into_export();
+  assert_eq!(
+    exported.status,
+    Status::Completed
+  );
+  assert!(exported.end.is_some());
+  assert_eq!(
+    exported
+      .extra
+      .get("rivet_archived"),
+    Some(&serde_json::json!(true))
+  );
+  exported
+    .restore_archived_from_export();
+  assert_eq!(
+    exported.status,
+    Status::Archived
+  );
+  assert!(
+    !exported
+      .extra
+      .contains_key("rivet_archived")
+  );
+}
+
+#[test]
+fn pre_archive_stores_migrate_to_the_current_format()
+ {
+  let temp =
+    tempdir().expect("tempdir");
+  let store =
+    DataStore::open(temp.path())
+      .expect("open datastore");
+  let now = Utc::now();
+  store
+    .add_task(
+      vec![],
+      Task::new_pending(
+        "from 1.0.0".to_string(),
+        now,
+        1
+      )
+    )
+    .expect("add task");
+  drop(store);
+  write_manifest(temp.path(), "1.0.0")
+    .expect("downgrade manifest");
+
+  let store =
+    DataStore::open(temp.path())
+      .expect("reopen datastore");
+  let pending = store
+    .load_pending()
+    .expect("load pending");
+  assert_eq!(pending.len(), 1);
+  assert_eq!(
+    pending[0].description,
+    "from 1.0.0"
+  );
+  assert_eq!(
+    read_manifest(temp.path())
+      .expect("read manifest")
+      .expect("manifest written")
+      .format_version,
+    STORE_FORMAT_VERSION
+  );
+  assert!(
+    temp
+      .path()
+      .join("backups")
+      .read_dir()
+      .expect("migration backup")
+      .flatten()
+      .any(|entry| {
+        entry
+          .file_name()
+          .to_string_lossy()
+          .starts_with("migrate-1.0.0-")
+      })
+  );
+}
@@ -1,9 +1,30 @@
 use serde::{
   Deserialize,
+  Deserializer,
   Serialize
 };
 use uuid::Uuid;
 
+/// Keeps an explicit `null` apart from
+/// an absent field: `Some(None)` clears
+/// the value, `None` leaves it alone.
+fn double_option<'de, T, D>(
+  deserializer: D
+) -> Result<Option<Option<T>>, D::Error>
+where
+  T: Deserialize<'de>,
+  D: Deserializer<'de>
+{
+  Option::<T>::deserialize(deserializer)
+    .map(Some)
+}
+
+/// Version of the DTO shapes exchanged
+/// between the GUI, the Tauri backend
+/// and external clients. Additive
+/// changes bump the minor version.
+pub const DTO_VERSION: &str = "1.8.0";
+
 #[derive(
   Debug,
   Clone,
@@ -16,7 +37,8 @@ pub enum TaskStatus {
   Pending,
   Completed,
   Deleted,
-  Waiting
+  Waiting,
+  Archived
 }
 
 #[derive(
@@ -41,31 +63,182 @@ pub enum TaskPriority {
   PartialEq,
 )]
 pub struct TaskDto {
-  pub uuid:        Uuid,
-  pub id:          Option<u64>,
+  pub uuid:          Uuid,
+  pub id:            Option<u64>,
   #[serde(default)]
-  pub title:       String,
+  pub title:         String,
   #[serde(default)]
-  pub description: String,
-  pub status:      TaskStatus,
-  pub project:     Option<String>,
-  pub tags:        Vec<String>,
-  pub priority:    Option<TaskPriority>,
-  pub due:         Option<String>,
-  pub wait:        Option<String>,
-  pub scheduled:   Option<String>,
-  pub created:     Option<String>,
-  pub modified:    Option<String>
+  pub description:   String,
+  pub status:        TaskStatus,
+  pub project:       Option<String>,
+  pub tags:          Vec<String>,
+  pub priority: Option<TaskPriority>,
+  pub due:           Option<String>,
+  pub wait:          Option<String>,
+  pub scheduled:     Option<String>,
+  pub created:       Option<String>,
+  pub modified:      Option<String>,
+  /// When the task was completed
+  /// (`end`); absent while open.
+  #[serde(default)]
+  pub completed:     Option<String>,
+  /// When the running clock was
+  /// started; absent while stopped.
+  #[serde(default)]
+  pub started:       Option<String>,
+  /// Focus time from earlier stops, in
+  /// seconds.
+  #[serde(default)]
+  pub focus_seconds: u64,
+  #[serde(default)]
+  pub parent:        Option<Uuid>,
+  #[serde(default)]
+  pub children:      Vec<Uuid>,
+  /// Contact the task waits on.
+  #[serde(default)]
+  pub contact:       Option<Uuid>
 }
 
 #[derive(
   Debug, Clone, Serialize, Deserialize,
 )]
 pub struct TasksListArgs {
-  pub query:   Option<String>,
-  pub status:  Option<TaskStatus>,
-  pub project: Option<String>,
-  pub tag:     Option<String>
+  pub query:          Option<String>,
+  pub status: Option<TaskStatus>,
+  pub project:        Option<String>,
+  pub tag:            Option<String>,
+  /// Caps the result; `0` or absent
+  /// means unlimited.
+  #[serde(default)]
+  pub limit:          Option<usize>,
+  /// Inclusive `YYYY-MM-DD` bounds on
+  /// the completion day in the project
+  /// timezone; tasks without an `end`
+  /// are dropped when either is set.
+  #[serde(default)]
+  pub completed_from: Option<String>,
+  #[serde(default)]
+  pub completed_to:   Option<String>,
+  /// Only tasks linked to this
+  /// contact.
+  #[serde(default)]
+  pub contact:        Option<Uuid>
+}
+
+/// `tasks_list` response: the matches
+/// by urgency, cut to `limit`, and how
+/// many matched before the cut.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TasksListPage {
+  pub tasks: Vec<TaskDto>,
+  pub total: usize
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TasksStaleArgs {
+  pub days:            Option<i64>,
+  pub include_waiting: Option<bool>,
+  pub include_blocked: Option<bool>
+}
+
+/// `threshold` overrides
+/// `dedupe.threshold` (1-100).
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TasksDedupeArgs {
+  pub threshold: Option<u32>
+}
+
+/// Likely duplicates of one task; the
+/// first (oldest) task is the one kept.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TaskDedupeGroup {
+  pub score:  u32,
+  pub reason: String,
+  pub tasks:  Vec<TaskDto>
+}
+
+/// Resolves each group (kept uuid
+/// first): with `merge` the others are
+/// folded into the kept task before
+/// they are deleted.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TasksDedupeApplyArgs {
+  pub groups: Vec<Vec<Uuid>>,
+  pub merge:  bool
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TasksDedupeApplyResult {
+  pub removed: usize
+}
+
+/// Rename one value of a `key:value`
+/// tag on every task (`key:from` ->
+/// `key:to`).
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TagsRemapArgs {
+  pub key:  String,
+  pub from: String,
+  pub to:   String
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TagsRemapResult {
+  pub changed: usize
+}
+
+/// One row of `tags_stats`: how many
+/// tasks carry `tag`, how many of them
+/// are open and when one was last
+/// modified.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TagUsageDto {
+  pub tag:       String,
+  pub count:     usize,
+  pub open:      usize,
+  pub last_used: String
+}
+
+/// Outcome of `tags_cleanup_unused`:
+/// the tags dropped and how many tasks
+/// lost one.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct TagsCleanupResult {
+  pub removed: Vec<String>,
+  pub changed: usize
+}
+
+/// Outcome of `demo_seed` (tasks
+/// added) or `demo_clear` (tasks
+/// removed). `board_id` is the kanban
+/// board the demo tasks are tagged
+/// with.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct DemoDataResult {
+  pub tasks:    usize,
+  pub board_id: String
 }
 
 #[derive(
@@ -79,7 +252,9 @@ pub struct TaskCreate {
   pub priority:    Option<TaskPriority>,
   pub due:         Option<String>,
   pub wait:        Option<String>,
-  pub scheduled:   Option<String>
+  pub scheduled:   Option<String>,
+  #[serde(default)]
+  pub contact:     Option<Uuid>
 }
 
 #[derive(
@@ -98,7 +273,14 @@ pub struct TaskPatch {
     Option<Option<TaskPriority>>,
   pub due: Option<Option<String>>,
   pub wait: Option<Option<String>>,
-  pub scheduled: Option<Option<String>>
+  pub scheduled: Option<Option<String>>,
+  /// `null` unlinks the contact.
+  #[serde(
+    default,
+    deserialize_with = "double_option",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub contact:     Option<Option<Uuid>>
 }
 
 #[derive(
@@ -116,6 +298,38 @@ pub struct TaskUpdateArgs {
   pub patch: TaskPatch
 }
 
+#[derive(
+  Debug,
+  Clone,
+  Serialize,
+  Deserialize,
+  PartialEq,
+)]
+pub struct TaskFieldChangeDto {
+  pub field:  String,
+  pub before: Option<String>,
+  pub after:  Option<String>
+}
+
+/// One audit-log record for a task;
+/// `action` is `created`, `modified`,
+/// `completed`, `deleted`, `reopened`
+/// or `removed`.
+#[derive(
+  Debug,
+  Clone,
+  Serialize,
+  Deserialize,
+  PartialEq,
+)]
+pub struct TaskHistoryEntryDto {
+  pub at:      String,
+  pub command: String,
+  pub action:  String,
+  #[serde(default)]
+  pub changes: Vec<TaskFieldChangeDto>
+}
+
 #[derive(
   Debug,
   Clone,
@@ -193,19 +407,46 @@ pub struct ContactDto {
   pub source_kind:      String,
   pub remote_id:        Option<String>,
   pub link_group_id:    Option<String>,
+  /// Freeform labels such as work or
+  /// vendors, kept like task tags.
+  #[serde(default)]
+  pub categories:       Vec<String>,
   pub created_at:       String,
-  pub updated_at:       String
+  pub updated_at:       String,
+  /// Newest logged call or email,
+  /// filled in by `contacts_list` from
+  /// the interaction log; never stored
+  /// on the contact itself.
+  #[serde(
+    default,
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub last_contacted:   Option<String>
 }
 
 #[derive(
   Debug, Clone, Serialize, Deserialize,
 )]
 pub struct ContactsListArgs {
-  pub query:         Option<String>,
-  pub limit:         Option<usize>,
-  pub cursor:        Option<String>,
-  pub source:        Option<String>,
-  pub updated_after: Option<String>
+  pub query: Option<String>,
+  pub limit: Option<usize>,
+  pub cursor: Option<String>,
+  pub source: Option<String>,
+  pub updated_after: Option<String>,
+  /// Only contacts carrying this
+  /// category (case-insensitive).
+  #[serde(default)]
+  pub category: Option<String>,
+  /// `name` (the default) or `recent`:
+  /// most recently contacted first,
+  /// never-contacted contacts last.
+  #[serde(default)]
+  pub sort: Option<String>,
+  /// Only contacts with a logged
+  /// interaction in the last N days.
+  #[serde(default)]
+  pub contacted_within_days:
+    Option<u32>
 }
 
 #[derive(
@@ -214,7 +455,26 @@ pub struct ContactsListArgs {
 pub struct ContactsListResult {
   pub contacts:    Vec<ContactDto>,
   pub next_cursor: Option<String>,
-  pub total:       usize
+  pub total:       usize,
+  /// Category counts over the contacts
+  /// matching every filter except
+  /// `category`, most used first.
+  #[serde(default)]
+  pub categories:
+    Vec<ContactCategoryCount>
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Serialize,
+  Deserialize,
+  PartialEq,
+  Eq,
+)]
+pub struct ContactCategoryCount {
+  pub category: String,
+  pub count:    usize
 }
 
 #[derive(
@@ -243,7 +503,9 @@ pub struct ContactCreate {
   pub source_id:        Option<String>,
   pub source_kind:      Option<String>,
   pub remote_id:        Option<String>,
-  pub link_group_id:    Option<String>
+  pub link_group_id:    Option<String>,
+  #[serde(default)]
+  pub categories:       Vec<String>
 }
 
 #[derive(
@@ -285,7 +547,8 @@ pub struct ContactPatch {
     Option<Option<String>>,
   pub remote_id: Option<Option<String>>,
   pub link_group_id:
-    Option<Option<String>>
+    Option<Option<String>>,
+  pub categories: Option<Vec<String>>
 }
 
 #[derive(
@@ -310,6 +573,34 @@ pub struct ContactsDeleteBulkArgs {
   pub ids: Vec<Uuid>
 }
 
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactsDeleteBulkResult {
+  pub deleted:      usize,
+  pub undo_id:      Option<String>,
+  /// Tasks still linked to a deleted
+  /// contact; the links are kept so
+  /// undoing the delete restores them.
+  #[serde(default)]
+  pub linked_tasks: usize
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactsDeleteUndoArgs {
+  pub undo_id: Option<String>
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactsDeleteUndoResult {
+  pub restored: usize,
+  pub undo_id:  String
+}
+
 #[derive(
   Debug, Clone, Serialize, Deserialize,
 )]
@@ -324,7 +615,73 @@ pub struct ContactOpenActionArgs {
 )]
 pub struct ContactOpenActionResult {
   pub launched: bool,
-  pub url:      String
+  pub url:      String,
+  /// False when `[contacts].
+  /// log_interactions` is off.
+  #[serde(default)]
+  pub logged:   bool
+}
+
+/// One call or email started from the
+/// contacts workspace, kept in the
+/// interaction log.
+#[derive(
+  Debug,
+  Clone,
+  PartialEq,
+  Eq,
+  Serialize,
+  Deserialize,
+)]
+pub struct ContactInteraction {
+  pub id:         Uuid,
+  pub contact_id: Uuid,
+  /// `email` or `phone`.
+  pub kind:       String,
+  pub value:      String,
+  pub at:         String
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactInteractionsArgs {
+  /// All contacts when absent.
+  #[serde(default)]
+  pub contact_id: Option<Uuid>
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactInteractionsResult {
+  /// Newest first.
+  pub interactions:
+    Vec<ContactInteraction>
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactInteractionsPruneArgs
+{
+  /// Drop entries older than this many
+  /// days; every matching entry when
+  /// absent.
+  #[serde(default)]
+  pub older_than_days: Option<u32>,
+  /// Limit pruning to one contact.
+  #[serde(default)]
+  pub contact_id:      Option<Uuid>
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactInteractionsPruneResult
+{
+  pub removed:   usize,
+  pub remaining: usize
 }
 
 #[derive(
@@ -356,6 +713,11 @@ pub struct ContactsDedupePreviewResult {
   Debug, Clone, Serialize, Deserialize,
 )]
 pub struct ContactImportConflict {
+  /// Position of `imported` in the
+  /// parsed file, the key for a
+  /// per-conflict decision on commit.
+  #[serde(default)]
+  pub row:      usize,
   pub imported: ContactDto,
   pub existing: ContactDto,
   pub score:    u32,
@@ -394,7 +756,25 @@ pub struct ContactsImportCommitArgs {
   pub source:    String,
   pub file_name: Option<String>,
   pub content:   String,
-  pub mode:      String
+  pub mode:      String,
+  /// Overrides `mode` for individual
+  /// conflicts.
+  #[serde(default)]
+  pub decisions:
+    Vec<ContactImportDecision>
+}
+
+/// What to do with one import conflict:
+/// `merge` into the existing contact,
+/// `skip` the row, or `link` both
+/// records through a shared
+/// `link_group_id`.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactImportDecision {
+  pub row:      usize,
+  pub decision: String
 }
 
 #[derive(
@@ -405,11 +785,27 @@ pub struct ContactsImportCommitResult {
   pub created:   usize,
   pub updated:   usize,
   pub skipped:   usize,
+  /// Conflicts kept as a separate
+  /// contact linked to the existing
+  /// one.
+  #[serde(default)]
+  pub linked:    usize,
   pub failed:    usize,
   pub conflicts: usize,
   pub errors:    Vec<String>
 }
 
+/// Contacts `contacts_unlink` changed:
+/// the one taken out of its link group
+/// and, when it was the last other
+/// member, the one left behind.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactsUnlinkResult {
+  pub contacts: Vec<ContactDto>
+}
+
 #[derive(
   Debug, Clone, Serialize, Deserialize,
 )]
@@ -422,9 +818,24 @@ pub struct ContactsMergeArgs {
   Debug, Clone, Serialize, Deserialize,
 )]
 pub struct ContactsMergeResult {
+  pub merged:         ContactDto,
+  pub removed_ids:    Vec<Uuid>,
+  pub undo_id:        String,
+  /// Tasks moved from a removed
+  /// contact to the merged one.
+  #[serde(default)]
+  pub relinked_tasks: usize
+}
+
+/// What `contacts_merge` would produce
+/// for the same args; nothing is
+/// written.
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ContactsMergePreviewResult {
   pub merged:      ContactDto,
-  pub removed_ids: Vec<Uuid>,
-  pub undo_id:     String
+  pub removed_ids: Vec<Uuid>
 }
 
 #[derive(
@@ -725,3 +1136,46 @@ mod dictionary_contract_tests {
     assert_eq!(back, payload);
   }
 }
+
+#[cfg(test)]
+mod task_contract_tests {
+  use super::TaskPatch;
+
+  #[test]
+  fn task_patch_tells_null_contact_from_absent()
+   {
+    let unlink: TaskPatch =
+      serde_json::from_value(
+        serde_json::json!({
+          "contact": null
+        })
+      )
+      .expect("deserialize unlink");
+    assert_eq!(
+      unlink.contact,
+      Some(None)
+    );
+
+    let untouched: TaskPatch =
+      serde_json::from_value(
+        serde_json::json!({
+          "title": "call back"
+        })
+      )
+      .expect("deserialize patch");
+    assert_eq!(untouched.contact, None);
+
+    let uuid = uuid::Uuid::new_v4();
+    let link: TaskPatch =
+      serde_json::from_value(
+        serde_json::json!({
+          "contact": uuid
+        })
+      )
+      .expect("deserialize link");
+    assert_eq!(
+      link.contact,
+      Some(Some(uuid))
+    );
+  }
+}
@@ -1,5 +1,6 @@
 include!("commands/common.rs");
 include!("commands/config.rs");
+include!("commands/permissions.rs");
 include!("commands/tasks.rs");
 include!("commands/dictionary.rs");
 include!("commands/contacts.rs");
@@ -7,3 +8,4 @@ include!("commands/window.rs");
 include!(
   "commands/external_calendar.rs"
 );
+include!("commands/calendar_sync.rs");
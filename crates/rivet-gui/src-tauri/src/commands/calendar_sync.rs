@@ -0,0 +1,1144 @@
+const CALENDAR_SYNC_EVENT: &str =
+  "external-calendar-synced";
+const CALENDAR_SYNC_STATE_FILE: &str =
+  "calendar_sync_state.json";
+/// How often the scheduler looks for
+/// sources whose refresh interval has
+/// passed.
+const CALENDAR_SYNC_TICK: Duration =
+  Duration::from_secs(30);
+const CALENDAR_SYNC_ACTIVITY_EVENT:
+  &str =
+  "external-calendar-sync-activity";
+/// `[external_calendar] max_concurrent`
+/// default: fetches in flight at once,
+/// manual and background together.
+const DEFAULT_SYNC_MAX_CONCURRENT:
+  usize = 2;
+/// `[external_calendar] min_interval`
+/// default, in seconds between two
+/// fetches from the same host.
+const DEFAULT_SYNC_MIN_INTERVAL_SECS:
+  u64 = 5;
+/// Longer `min_interval` values are
+/// clamped to a day so the next start
+/// time always fits in an `Instant`.
+const MAX_SYNC_MIN_INTERVAL_SECS: u64 =
+  24 * 60 * 60;
+
+/// Sources the frontend wants kept in
+/// sync, plus the persisted outcome of
+/// the last attempt per source id.
+pub struct CalendarSyncScheduler {
+  sources: parking_lot::Mutex<
+    Vec<ExternalCalendarSourceArg>
+  >,
+  records: parking_lot::Mutex<
+    BTreeMap<
+      String,
+      CalendarSyncRecord
+    >
+  >,
+  wake:       tokio::sync::Notify,
+  activity: parking_lot::Mutex<
+    CalendarSyncActivity
+  >,
+  slot_freed: tokio::sync::Notify
+}
+
+/// Fetch limits from
+/// `[external_calendar]` in rivet.toml,
+/// read again for every queued sync.
+#[derive(
+  Debug, Clone, Copy, PartialEq,
+)]
+struct CalendarSyncLimits {
+  max_concurrent: usize,
+  min_interval:   Duration
+}
+
+impl CalendarSyncLimits {
+  fn load() -> Self {
+    let path =
+      resolve_config_path("rivet.toml");
+    let section =
+      std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|raw| {
+          toml::from_str::<toml::Value>(
+            &raw
+          )
+          .ok()
+        })
+        .and_then(|root| {
+          root
+            .get("external_calendar")
+            .cloned()
+        });
+    Self::from_section(section.as_ref())
+  }
+
+  fn from_section(
+    section: Option<&toml::Value>
+  ) -> Self {
+    let int = |key: &str| {
+      section
+        .and_then(|section| {
+          section.get(key)
+        })
+        .and_then(
+          toml::Value::as_integer
+        )
+    };
+    Self {
+      max_concurrent: int(
+        "max_concurrent"
+      )
+      .and_then(|count| {
+        usize::try_from(count).ok()
+      })
+      .filter(|count| *count > 0)
+      .unwrap_or(
+        DEFAULT_SYNC_MAX_CONCURRENT
+      ),
+      min_interval:   Duration::from_secs(
+        int("min_interval")
+          .and_then(|secs| {
+            u64::try_from(secs).ok()
+          })
+          .unwrap_or(
+            DEFAULT_SYNC_MIN_INTERVAL_SECS
+          )
+          .min(MAX_SYNC_MIN_INTERVAL_SECS)
+      )
+    }
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarSyncState {
+  Idle,
+  Queued,
+  Running
+}
+
+/// Syncs waiting for a slot and in
+/// flight. A source can be listed twice
+/// when a manual sync queues behind a
+/// background one.
+#[derive(Debug, Default)]
+struct CalendarSyncActivity {
+  queued:       Vec<String>,
+  /// `(source id, host)` per fetch.
+  running:      Vec<(String, String)>,
+  host_started: BTreeMap<
+    String,
+    std::time::Instant
+  >
+}
+
+impl CalendarSyncActivity {
+  fn state(
+    &self,
+    calendar_id: &str
+  ) -> CalendarSyncState {
+    if self
+      .running
+      .iter()
+      .any(|(id, _)| id == calendar_id)
+    {
+      CalendarSyncState::Running
+    } else if self
+      .queued
+      .iter()
+      .any(|id| id == calendar_id)
+    {
+      CalendarSyncState::Queued
+    } else {
+      CalendarSyncState::Idle
+    }
+  }
+
+  /// Moves one queued `calendar_id` to
+  /// running when a slot is free and
+  /// `host` is neither busy nor fetched
+  /// within `min_interval`. Otherwise
+  /// says how long to wait: `Some` when
+  /// only the host gap holds it back,
+  /// `None` until a running sync ends.
+  fn try_start(
+    &mut self,
+    calendar_id: &str,
+    host: &str,
+    limits: CalendarSyncLimits,
+    now: std::time::Instant
+  ) -> Result<(), Option<Duration>> {
+    if self.running.len()
+      >= limits.max_concurrent
+      || self
+        .running
+        .iter()
+        .any(|(_, busy)| busy == host)
+    {
+      return Err(None);
+    }
+    if let Some(started) =
+      self.host_started.get(host)
+    {
+      let ready =
+        *started + limits.min_interval;
+      if ready > now {
+        return Err(Some(ready - now));
+      }
+    }
+    if let Some(index) = self
+      .queued
+      .iter()
+      .position(|id| id == calendar_id)
+    {
+      self.queued.remove(index);
+    }
+    self.running.push((
+      calendar_id.to_string(),
+      host.to_string()
+    ));
+    self
+      .host_started
+      .insert(host.to_string(), now);
+    Ok(())
+  }
+
+  fn finish(
+    &mut self,
+    calendar_id: &str,
+    host: &str
+  ) {
+    if let Some(index) = self
+      .running
+      .iter()
+      .position(|(id, busy)| {
+        id == calendar_id
+          && busy == host
+      })
+    {
+      self.running.remove(index);
+    }
+  }
+
+  fn snapshot(
+    &self
+  ) -> ExternalCalendarSyncActivity {
+    let unique = |ids: Vec<&String>| {
+      ids
+        .into_iter()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+    };
+    ExternalCalendarSyncActivity {
+      queued:  unique(
+        self.queued.iter().collect()
+      ),
+      running: unique(
+        self
+          .running
+          .iter()
+          .map(|(id, _)| id)
+          .collect()
+      )
+    }
+  }
+}
+
+/// A fetch holding one of the
+/// `max_concurrent` slots; dropping it
+/// frees the slot for queued syncs.
+struct CalendarSyncSlot<'a> {
+  scheduler: &'a CalendarSyncScheduler,
+  app:         &'a tauri::AppHandle,
+  calendar_id: String,
+  host:        String
+}
+
+impl Drop for CalendarSyncSlot<'_> {
+  fn drop(&mut self) {
+    self
+      .scheduler
+      .activity
+      .lock()
+      .finish(
+        &self.calendar_id,
+        &self.host
+      );
+    self
+      .scheduler
+      .slot_freed
+      .notify_waiters();
+    self
+      .scheduler
+      .emit_activity(self.app);
+  }
+}
+
+#[derive(
+  Debug,
+  Clone,
+  Default,
+  PartialEq,
+  Serialize,
+  Deserialize,
+)]
+struct CalendarSyncRecord {
+  #[serde(default)]
+  last_sync:    Option<DateTime<Utc>>,
+  last_attempt: DateTime<Utc>,
+  #[serde(default)]
+  last_error:   Option<String>
+}
+
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct ExternalCalendarScheduleArg {
+  pub sources:
+    Vec<ExternalCalendarSourceArg>
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalCalendarSyncStatus {
+  pub calendar_id: String,
+  pub last_sync:   Option<String>,
+  pub last_error:  Option<String>,
+  /// `None` when the source is not
+  /// auto-synced (disabled, imported
+  /// or `refresh_minutes = 0`).
+  pub next_sync:   Option<String>,
+  pub state:       CalendarSyncState
+}
+
+/// Payload of the
+/// `external-calendar-sync-activity`
+/// event, sent whenever a sync is
+/// queued, starts or ends.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalCalendarSyncActivity
+{
+  pub queued:  Vec<String>,
+  pub running: Vec<String>
+}
+
+/// Payload of the
+/// `external-calendar-synced` event,
+/// sent after every background sync.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalCalendarSyncEvent {
+  pub calendar_id: String,
+  pub name:        String,
+  pub synced_at:   String,
+  pub result:
+    Option<ExternalCalendarSyncResult>,
+  pub error:       Option<String>
+}
+
+impl CalendarSyncScheduler {
+  /// Starts with no sources; the
+  /// frontend pushes them with
+  /// `external_calendar_schedule` once
+  /// it has loaded its settings.
+  pub fn load() -> Self {
+    Self {
+      sources:
+        parking_lot::Mutex::new(
+          Vec::new()
+        ),
+      records:
+        parking_lot::Mutex::new(
+          load_calendar_sync_records()
+        ),
+      wake:
+        tokio::sync::Notify::new(),
+      activity:
+        parking_lot::Mutex::new(
+          CalendarSyncActivity::default(
+          )
+        ),
+      slot_freed:
+        tokio::sync::Notify::new()
+    }
+  }
+
+  /// Marks a sync as waiting; pair it
+  /// with `acquire`.
+  fn enqueue(
+    &self,
+    app: &tauri::AppHandle,
+    calendar_id: &str
+  ) {
+    self
+      .activity
+      .lock()
+      .queued
+      .push(calendar_id.to_string());
+    self.emit_activity(app);
+  }
+
+  /// Waits until `source` may fetch
+  /// under the configured limits.
+  async fn acquire<'a>(
+    &'a self,
+    app: &'a tauri::AppHandle,
+    source: &ExternalCalendarSourceArg
+  ) -> CalendarSyncSlot<'a> {
+    let host =
+      calendar_host(&source.location);
+    let limits =
+      CalendarSyncLimits::load();
+    loop {
+      // Registered before the check so
+      // a slot freed in between still
+      // wakes this waiter.
+      let freed =
+        self.slot_freed.notified();
+      tokio::pin!(freed);
+      freed.as_mut().enable();
+      let started =
+        self.activity.lock().try_start(
+          &source.id,
+          &host,
+          limits,
+          std::time::Instant::now()
+        );
+      match started {
+        | Ok(()) => break,
+        | Err(Some(gap)) => {
+          tokio::select! {
+            () = tokio::time::sleep(gap) => {}
+            () = &mut freed => {}
+          }
+        }
+        | Err(None) => freed.await
+      }
+    }
+    self.emit_activity(app);
+    CalendarSyncSlot {
+      scheduler: self,
+      app,
+      calendar_id: source.id.clone(),
+      host
+    }
+  }
+
+  fn emit_activity(
+    &self,
+    app: &tauri::AppHandle
+  ) {
+    let activity =
+      self.activity.lock().snapshot();
+    if let Err(error) = app.emit(
+      CALENDAR_SYNC_ACTIVITY_EVENT,
+      &activity
+    ) {
+      warn!(
+        error = %error,
+        "failed emitting calendar sync activity"
+      );
+    }
+  }
+
+  fn statuses(
+    &self
+  ) -> Vec<ExternalCalendarSyncStatus>
+  {
+    let sources = self.sources.lock();
+    let records = self.records.lock();
+    let activity = self.activity.lock();
+    sources
+      .iter()
+      .map(|source| {
+        let record =
+          records.get(&source.id);
+        ExternalCalendarSyncStatus {
+          calendar_id: source
+            .id
+            .clone(),
+          last_sync:   record
+            .and_then(|record| {
+              record.last_sync
+            })
+            .map(|at| at.to_rfc3339()),
+          last_error:  record.and_then(
+            |record| {
+              record.last_error.clone()
+            }
+          ),
+          next_sync:
+            next_calendar_sync(
+              source, record
+            )
+            .map(|at| at.to_rfc3339()),
+          state:       activity
+            .state(&source.id)
+        }
+      })
+      .collect()
+  }
+
+  /// Stores the outcome of one sync
+  /// (manual or scheduled) so the next
+  /// background attempt waits a full
+  /// refresh interval.
+  fn record(
+    &self,
+    calendar_id: &str,
+    outcome: Result<(), String>,
+    at: DateTime<Utc>
+  ) {
+    let mut records =
+      self.records.lock();
+    let entry = records
+      .entry(calendar_id.to_string())
+      .or_default();
+    entry.last_attempt = at;
+    match outcome {
+      | Ok(()) => {
+        entry.last_sync = Some(at);
+        entry.last_error = None;
+      }
+      | Err(error) => {
+        entry.last_error = Some(error);
+      }
+    }
+    if let Err(error) =
+      save_calendar_sync_records(
+        &records
+      )
+    {
+      warn!(
+        error = %error,
+        "failed saving calendar sync state"
+      );
+    }
+  }
+}
+
+/// When `source` is next due: one
+/// refresh interval after the last
+/// attempt, or now if it was never
+/// tried.
+fn next_calendar_sync(
+  source: &ExternalCalendarSourceArg,
+  record: Option<&CalendarSyncRecord>
+) -> Option<DateTime<Utc>> {
+  if !source.enabled
+    || source.imported_ics_file
+    || source.refresh_minutes == 0
+  {
+    return None;
+  }
+  let Some(record) = record else {
+    return Some(DateTime::UNIX_EPOCH);
+  };
+  Some(
+    record.last_attempt
+      + chrono::TimeDelta::minutes(
+        i64::from(
+          source.refresh_minutes
+        )
+      )
+  )
+}
+
+/// Sources due at `now`, longest
+/// overdue first.
+fn due_calendar_sources(
+  sources: &[ExternalCalendarSourceArg],
+  records: &BTreeMap<
+    String,
+    CalendarSyncRecord
+  >,
+  now: DateTime<Utc>
+) -> Vec<ExternalCalendarSourceArg> {
+  let mut due: Vec<(
+    DateTime<Utc>,
+    &ExternalCalendarSourceArg
+  )> = sources
+    .iter()
+    .filter_map(|source| {
+      next_calendar_sync(
+        source,
+        records.get(&source.id)
+      )
+      .filter(|at| *at <= now)
+      .map(|at| (at, source))
+    })
+    .collect();
+  due.sort_by_key(|(at, _)| *at);
+  due
+    .into_iter()
+    .map(|(_, source)| source.clone())
+    .collect()
+}
+
+/// Host of a source location, so
+/// fetches to one server can be spaced
+/// out; the whole location when it does
+/// not parse as a URL.
+fn calendar_host(
+  location: &str
+) -> String {
+  let trimmed = location.trim();
+  reqwest::Url::parse(trimmed)
+    .ok()
+    .and_then(|url| {
+      url
+        .host_str()
+        .map(str::to_ascii_lowercase)
+    })
+    .unwrap_or_else(|| {
+      trimmed.to_ascii_lowercase()
+    })
+}
+
+fn calendar_sync_state_path()
+-> std::path::PathBuf {
+  resolve_gui_data_dir()
+    .join(CALENDAR_SYNC_STATE_FILE)
+}
+
+fn load_calendar_sync_records()
+-> BTreeMap<String, CalendarSyncRecord>
+{
+  let path = calendar_sync_state_path();
+  let Ok(raw) =
+    std::fs::read_to_string(&path)
+  else {
+    return BTreeMap::new();
+  };
+  serde_json::from_str(&raw)
+    .unwrap_or_else(|error| {
+      warn!(
+        path = %path.display(),
+        error = %error,
+        "ignoring unreadable calendar sync state"
+      );
+      BTreeMap::new()
+    })
+}
+
+fn save_calendar_sync_records(
+  records: &BTreeMap<
+    String,
+    CalendarSyncRecord
+  >
+) -> anyhow::Result<()> {
+  let path = calendar_sync_state_path();
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)
+      .with_context(|| {
+        format!(
+          "failed creating {}",
+          parent.display()
+        )
+      })?;
+  }
+  let raw =
+    serde_json::to_string_pretty(
+      records
+    )
+    .context(
+      "failed encoding calendar sync \
+       state"
+    )?;
+  std::fs::write(&path, raw)
+    .with_context(|| {
+      format!(
+        "failed writing {}",
+        path.display()
+      )
+    })
+}
+
+#[tauri::command]
+#[instrument(skip(scheduler, args), fields(request_id = ?request_id, sources = args.sources.len()))]
+pub async fn external_calendar_schedule(
+  scheduler: State<
+    '_,
+    CalendarSyncScheduler
+  >,
+  args: ExternalCalendarScheduleArg,
+  request_id: Option<String>
+) -> Result<
+  Vec<ExternalCalendarSyncStatus>,
+  String
+> {
+  info!(request_id = ?request_id, sources = args.sources.len(), "external_calendar_schedule command invoked");
+  {
+    let ids: BTreeSet<&str> = args
+      .sources
+      .iter()
+      .map(|source| source.id.as_str())
+      .collect();
+    let mut records =
+      scheduler.records.lock();
+    let before = records.len();
+    records.retain(|id, _| {
+      ids.contains(id.as_str())
+    });
+    if records.len() != before
+      && let Err(error) =
+        save_calendar_sync_records(
+          &records
+        )
+    {
+      warn!(
+        error = %error,
+        "failed saving calendar sync state"
+      );
+    }
+  }
+  *scheduler.sources.lock() =
+    args.sources;
+  scheduler.wake.notify_one();
+  Ok(scheduler.statuses())
+}
+
+/// Runs for the life of the app,
+/// syncing due sources whether or not
+/// the calendar view is open.
+pub fn spawn_calendar_sync_scheduler(
+  app: tauri::AppHandle
+) {
+  tauri::async_runtime::spawn(
+    async move {
+      loop {
+        run_due_calendar_syncs(&app)
+          .await;
+        let scheduler = app
+          .state::<CalendarSyncScheduler>(
+          );
+        tokio::select! {
+          () = tokio::time::sleep(CALENDAR_SYNC_TICK) => {}
+          () = scheduler.wake.notified() => {}
+        }
+      }
+    }
+  );
+}
+
+async fn run_due_calendar_syncs(
+  app: &tauri::AppHandle
+) {
+  if command_permissions()
+    .check(
+      CommandCategory::CalendarImport,
+      "external_calendar_schedule"
+    )
+    .is_err()
+  {
+    return;
+  }
+  let scheduler = app
+    .state::<CalendarSyncScheduler>(
+  );
+  let due = {
+    let sources =
+      scheduler.sources.lock();
+    let records =
+      scheduler.records.lock();
+    let activity =
+      scheduler.activity.lock();
+    due_calendar_sources(
+      &sources,
+      &records,
+      Utc::now()
+    )
+    .into_iter()
+    .filter(|source| {
+      activity.state(&source.id)
+        == CalendarSyncState::Idle
+    })
+    .collect::<Vec<_>>()
+  };
+
+  // Each due source queues for a slot;
+  // `[external_calendar]` limits decide
+  // how many fetch at once.
+  for source in due {
+    scheduler.enqueue(app, &source.id);
+    let app = app.clone();
+    tauri::async_runtime::spawn(
+      async move {
+        run_scheduled_calendar_sync(
+          &app, source
+        )
+        .await;
+      }
+    );
+  }
+}
+
+async fn run_scheduled_calendar_sync(
+  app: &tauri::AppHandle,
+  source: ExternalCalendarSourceArg
+) {
+  let scheduler = app
+    .state::<CalendarSyncScheduler>(
+  );
+  let _slot = scheduler
+    .acquire(app, &source)
+    .await;
+  // The list may have changed while
+  // this sync was queued.
+  let still_scheduled = scheduler
+    .sources
+    .lock()
+    .iter()
+    .any(|current| {
+      current.id == source.id
+        && next_calendar_sync(
+          current, None
+        )
+        .is_some()
+    });
+  if !still_scheduled {
+    return;
+  }
+
+  let state = app.state::<AppState>();
+  let outcome = sync_remote_calendar(
+    &state, &source
+  )
+  .await;
+  let synced_at = Utc::now();
+  let event = match outcome {
+    | Ok(result) => {
+      info!(
+        calendar_id = %source.id,
+        created = result.created,
+        updated = result.updated,
+        deleted = result.deleted,
+        "background calendar sync done"
+      );
+      scheduler.record(
+        &source.id,
+        Ok(()),
+        synced_at
+      );
+      ExternalCalendarSyncEvent {
+        calendar_id: source.id.clone(),
+        name:        source
+          .name
+          .clone(),
+        synced_at:   synced_at
+          .to_rfc3339(),
+        result:      Some(result),
+        error:       None
+      }
+    }
+    | Err(error) => {
+      warn!(
+        calendar_id = %source.id,
+        error = %error,
+        "background calendar sync failed"
+      );
+      scheduler.record(
+        &source.id,
+        Err(error.to_string()),
+        synced_at
+      );
+      ExternalCalendarSyncEvent {
+        calendar_id: source.id.clone(),
+        name:        source
+          .name
+          .clone(),
+        synced_at:   synced_at
+          .to_rfc3339(),
+        result:      None,
+        error:       Some(
+          error.to_string()
+        )
+      }
+    }
+  };
+  if let Err(error) = app
+    .emit(CALENDAR_SYNC_EVENT, &event)
+  {
+    warn!(
+      error = %error,
+      "failed emitting calendar sync event"
+    );
+  }
+}
+
+#[cfg(test)]
+mod calendar_sync_tests {
+  use super::*;
+
+  fn source(
+    id: &str,
+    refresh_minutes: u32
+  ) -> ExternalCalendarSourceArg {
+    ExternalCalendarSourceArg {
+      id: id.to_string(),
+      name: id.to_string(),
+      color: "#336699".to_string(),
+      location:
+        "https://example.com/cal.ics"
+          .to_string(),
+      refresh_minutes,
+      enabled: true,
+      imported_ics_file: false,
+      read_only: true,
+      show_reminders: false,
+      offline_support: false
+    }
+  }
+
+  fn attempted(
+    at: DateTime<Utc>
+  ) -> CalendarSyncRecord {
+    CalendarSyncRecord {
+      last_sync:    Some(at),
+      last_attempt: at,
+      last_error:   None
+    }
+  }
+
+  #[test]
+  fn sync_slots_cap_concurrency_and_space_out_hosts()
+   {
+    let limits = CalendarSyncLimits {
+      max_concurrent: 2,
+      min_interval:
+        Duration::from_secs(5)
+    };
+    let now = std::time::Instant::now();
+    let secs = Duration::from_secs;
+    let mut activity =
+      CalendarSyncActivity::default();
+    for id in ["a", "b", "c", "d"] {
+      activity
+        .queued
+        .push(id.to_string());
+    }
+
+    assert_eq!(
+      activity.try_start(
+        "a",
+        "one.example",
+        limits,
+        now
+      ),
+      Ok(())
+    );
+    // One fetch per host at a time.
+    assert_eq!(
+      activity.try_start(
+        "b",
+        "one.example",
+        limits,
+        now
+      ),
+      Err(None)
+    );
+    assert_eq!(
+      activity.try_start(
+        "c",
+        "two.example",
+        limits,
+        now
+      ),
+      Ok(())
+    );
+    // Both slots taken.
+    assert_eq!(
+      activity.try_start(
+        "d",
+        "three.example",
+        limits,
+        now
+      ),
+      Err(None)
+    );
+    assert_eq!(
+      activity.state("a"),
+      CalendarSyncState::Running
+    );
+    assert_eq!(
+      activity.state("b"),
+      CalendarSyncState::Queued
+    );
+    assert_eq!(
+      activity.state("z"),
+      CalendarSyncState::Idle
+    );
+
+    activity.finish("a", "one.example");
+    assert_eq!(
+      activity.try_start(
+        "b",
+        "one.example",
+        limits,
+        now + secs(2)
+      ),
+      Err(Some(secs(3)))
+    );
+    assert_eq!(
+      activity.try_start(
+        "b",
+        "one.example",
+        limits,
+        now + secs(5)
+      ),
+      Ok(())
+    );
+    let snapshot = activity.snapshot();
+    assert_eq!(snapshot.queued, vec![
+      "d"
+    ]);
+    assert_eq!(snapshot.running, vec![
+      "b", "c"
+    ]);
+  }
+
+  #[test]
+  fn sync_limits_read_the_external_calendar_section()
+   {
+    let defaults =
+      CalendarSyncLimits::from_section(
+        None
+      );
+    assert_eq!(
+      defaults.max_concurrent,
+      DEFAULT_SYNC_MAX_CONCURRENT
+    );
+    let section =
+      toml::from_str::<toml::Value>(
+        "max_concurrent = \
+         4\nmin_interval = 0"
+      )
+      .expect("toml");
+    assert_eq!(
+      CalendarSyncLimits::from_section(
+        Some(&section)
+      ),
+      CalendarSyncLimits {
+        max_concurrent: 4,
+        min_interval:   Duration::ZERO
+      }
+    );
+    let zero =
+      toml::from_str::<toml::Value>(
+        "max_concurrent = 0"
+      )
+      .expect("toml");
+    assert_eq!(
+      CalendarSyncLimits::from_section(
+        Some(&zero)
+      ),
+      defaults
+    );
+    let huge =
+      toml::from_str::<toml::Value>(
+        "min_interval = \
+         9223372036854775807"
+      )
+      .expect("toml");
+    assert_eq!(
+      CalendarSyncLimits::from_section(
+        Some(&huge)
+      )
+      .min_interval,
+      Duration::from_secs(
+        MAX_SYNC_MIN_INTERVAL_SECS
+      )
+    );
+    assert_eq!(
+      calendar_host(
+        " webcal://Cal.Example.com/a.\
+         ics"
+      ),
+      "cal.example.com"
+    );
+    assert_eq!(
+      calendar_host("not a url"),
+      "not a url"
+    );
+  }
+
+  #[test]
+  fn due_calendar_sources_respect_refresh_and_order_by_lateness()
+   {
+    let now = Utc
+      .with_ymd_and_hms(
+        2026, 3, 1, 12, 0, 0
+      )
+      .single()
+      .expect("valid time");
+    let minutes = |value: i64| {
+      now
+        - chrono::TimeDelta::minutes(
+          value
+        )
+    };
+    let mut disabled = source("off", 5);
+    disabled.enabled = false;
+    let mut imported =
+      source("file", 5);
+    imported.imported_ics_file = true;
+    let sources = vec![
+      source("fresh", 30),
+      source("late", 30),
+      source("later", 15),
+      source("never", 60),
+      source("manual", 0),
+      disabled,
+      imported,
+    ];
+    let records = BTreeMap::from([
+      (
+        "fresh".to_string(),
+        attempted(minutes(10))
+      ),
+      (
+        "late".to_string(),
+        attempted(minutes(31))
+      ),
+      (
+        "later".to_string(),
+        attempted(minutes(45))
+      ),
+      (
+        "manual".to_string(),
+        attempted(minutes(600))
+      )
+    ]);
+
+    let due: Vec<String> =
+      due_calendar_sources(
+        &sources, &records, now
+      )
+      .into_iter()
+      .map(|source| source.id)
+      .collect();
+    assert_eq!(due, vec![
+      "never", "later", "late"
+    ]);
+    assert_eq!(
+      next_calendar_sync(
+        &sources[0],
+        records.get("fresh")
+      ),
+      Some(minutes(-20))
+    );
+  }
+}
@@ -19,21 +19,57 @@ use chrono_tz::Tz;
 use ical::IcalParser;
 use ical::parser::ical::component::IcalEvent;
 use ical::property::Property;
-use rivet_core::datetime::project_timezone;
+use rivet_core::config_bundle::{
+  self,
+  BundleFile,
+  ChangeKind,
+  ConfigBundle,
+  ConflictPolicy,
+  TAG_SCHEMA_PATH
+};
+use rivet_core::datetime::{
+  DefaultTimes,
+  parse_date_expr,
+  project_timezone
+};
+use rivet_core::demo::DEMO_BOARD_ID;
+use rivet_core::task::{
+  DescriptionLimit,
+  DescriptionOverflow,
+  TagRemap,
+  TaskDefaults,
+  split_tag_list
+};
 use rivet_gui_shared::{
+  DemoDataResult,
+  TagUsageDto,
+  TagsCleanupResult,
+  TagsRemapArgs,
+  TagsRemapResult,
   TaskCreate,
+  TaskDedupeGroup,
   TaskDto,
+  TaskHistoryEntryDto,
   TaskIdArg,
   TaskPatch,
   TaskStatus,
   TaskUpdateArgs,
-  TasksListArgs
+  TasksDedupeApplyArgs,
+  TasksDedupeApplyResult,
+  TasksDedupeArgs,
+  TasksListArgs,
+  TasksListPage,
+  TasksStaleArgs
 };
 use serde::{
   Deserialize,
   Serialize
 };
 use tauri::State;
+use tauri::{
+  Emitter,
+  Manager
+};
 use tracing::{
   error,
   info,
@@ -49,6 +85,10 @@ const CAL_EVENT_TAG_KEY: &str =
   "cal_event";
 const CAL_COLOR_TAG_KEY: &str =
   "cal_color";
+const CAL_DURATION_TAG_KEY: &str =
+  "cal_duration";
+const CAL_ALL_DAY_TAG_KEY: &str =
+  "cal_allday";
 const RECUR_TAG_KEY: &str = "recur";
 const RECUR_TIME_TAG_KEY: &str =
   "recur_time";
@@ -58,6 +98,10 @@ const RECUR_MONTHS_TAG_KEY: &str =
   "recur_months";
 const RECUR_MONTH_DAY_TAG_KEY: &str =
   "recur_day";
+const RECUR_UNTIL_TAG_KEY: &str =
+  "recur_until";
+const RECUR_COUNT_TAG_KEY: &str =
+  "recur_count";
 
 fn err_to_string(
   err: anyhow::Error
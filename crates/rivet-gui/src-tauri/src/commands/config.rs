@@ -10,6 +10,92 @@ pub struct ConfigApplyArg {
   pub updates: Vec<ConfigEntryUpdateArg>
 }
 
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  Serialize,
+  PartialEq,
+  Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigUpdateStatus {
+  Applied,
+  /// The file already held the value.
+  Unchanged,
+  Rejected,
+  /// Valid, but not written because
+  /// another update in the same call
+  /// was rejected.
+  Skipped
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigUpdateResult {
+  pub section:   String,
+  pub key:       String,
+  pub status:    ConfigUpdateStatus,
+  pub reason:    Option<String>,
+  pub previous:  Option<serde_json::Value>,
+  /// What the file holds after the
+  /// call.
+  pub effective: Option<serde_json::Value>
+}
+
+/// `config_apply_updates` response: one
+/// result per update, in order, and the
+/// snapshot the file holds afterwards.
+/// Updates are all-or-nothing; `saved`
+/// is false when nothing was written.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigApplyResult {
+  pub saved:   bool,
+  pub results: Vec<ConfigUpdateResult>,
+  pub config:  serde_json::Value
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigBundleExportArg {
+  /// Kanban boards and external
+  /// calendar sources from the UI's
+  /// local storage.
+  #[serde(default)]
+  pub gui: Option<serde_json::Value>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfigBundleImportArg {
+  pub bundle:      String,
+  /// `ask` (default) reports conflicts
+  /// without writing; `overwrite` or
+  /// `keep` settles them.
+  #[serde(default)]
+  pub on_conflict: Option<String>
+}
+
+/// `config_bundle_import` response.
+/// File lists hold bundle keys
+/// (`rivet.toml`, `tags.toml`);
+/// `backups` holds the copies taken of
+/// replaced files.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigBundleImportResult {
+  /// False when `ask` found conflicts
+  /// and nothing was written.
+  pub applied:   bool,
+  pub conflicts: Vec<String>,
+  pub written:   Vec<String>,
+  pub kept:      Vec<String>,
+  pub unchanged: Vec<String>,
+  pub backups:   Vec<String>,
+  /// Bundled files the GUI does not
+  /// use (the taskrc).
+  pub skipped:   Vec<String>,
+  /// The bundle's GUI section for the
+  /// UI to apply.
+  pub gui:       Option<serde_json::Value>
+}
+
 #[derive(
   Debug,
   Clone,
@@ -34,6 +120,224 @@ pub struct MapHealthResult {
   pub error:           Option<String>
 }
 
+/// `[backup]` from rivet.toml: how many
+/// daily snapshots to keep, or `None`
+/// when `daily` is off (the default).
+pub fn gui_daily_backup_keep() -> Option<usize>
+{
+  let path =
+    resolve_config_path("rivet.toml");
+  let section =
+    std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|raw| {
+        toml::from_str::<toml::Value>(
+          &raw
+        )
+        .ok()
+      })?
+      .get("backup")?
+      .clone();
+  if !section
+    .get("daily")
+    .and_then(toml::Value::as_bool)
+    .unwrap_or(false)
+  {
+    return None;
+  }
+  Some(
+    section
+      .get("keep")
+      .and_then(toml::Value::as_integer)
+      .and_then(|keep| {
+        usize::try_from(keep).ok()
+      })
+      .filter(|keep| *keep > 0)
+      .unwrap_or(7)
+  )
+}
+
+/// `[auto_archive] days` from
+/// rivet.toml; `None` (off) unless a
+/// positive number of days is set. An
+/// out-of-range count is logged and
+/// ignored.
+pub fn gui_auto_archive_days() -> Option<i64>
+{
+  let path =
+    resolve_config_path("rivet.toml");
+  std::fs::read_to_string(&path)
+    .ok()
+    .and_then(|raw| {
+      toml::from_str::<toml::Value>(
+        &raw
+      )
+      .ok()
+    })?
+    .get("auto_archive")?
+    .get("days")?
+    .as_integer()
+    .filter(|days| *days > 0)
+    .and_then(|days| {
+      rivet_core::archive::check_days(days)
+        .map_err(|err| {
+          warn!(error = %err, "ignoring [auto_archive] days");
+        })
+        .ok()
+    })
+}
+
+/// `[recur] mode` and `horizon` from
+/// rivet.toml, read like the CLI's
+/// `recur.mode` and `recur.horizon`.
+/// An invalid setting is logged and
+/// the default used.
+pub fn gui_recur_settings()
+-> (rivet_core::recur::RecurMode, i64) {
+  use rivet_core::recur::{
+    DEFAULT_RECUR_HORIZON_DAYS,
+    RecurMode
+  };
+
+  let path =
+    resolve_config_path("rivet.toml");
+  let section = std::fs::read_to_string(
+    &path
+  )
+  .ok()
+  .and_then(|raw| {
+    toml::from_str::<toml::Value>(&raw)
+      .ok()
+  })
+  .and_then(|root| {
+    root.get("recur").cloned()
+  });
+  let Some(section) = section else {
+    return (
+      RecurMode::Eager,
+      DEFAULT_RECUR_HORIZON_DAYS
+    );
+  };
+
+  let mode = section
+    .get("mode")
+    .and_then(toml::Value::as_str)
+    .map_or(
+      Ok(RecurMode::Eager),
+      RecurMode::parse
+    )
+    .unwrap_or_else(|err| {
+      warn!(error = %err, "ignoring [recur] mode");
+      RecurMode::Eager
+    });
+  let horizon = match section
+    .get("horizon")
+    .and_then(toml::Value::as_integer)
+  {
+    | Some(days)
+      if days >= 0
+        && chrono::Duration::try_days(
+          days
+        )
+        .is_some() =>
+    {
+      days
+    }
+    | Some(days) => {
+      warn!(
+        days,
+        "ignoring out-of-range [recur] \
+         horizon"
+      );
+      DEFAULT_RECUR_HORIZON_DAYS
+    }
+    | None => DEFAULT_RECUR_HORIZON_DAYS
+  };
+  (mode, horizon)
+}
+
+/// `[on_complete]` from rivet.toml, read
+/// like the taskrc `on_complete.*` keys.
+/// A bad value is logged and the
+/// default kept.
+pub fn gui_completion_policy()
+-> rivet_core::task::CompletionPolicy {
+  use rivet_core::task::{
+    CompletionPolicy,
+    SubtaskCompletion
+  };
+
+  let defaults = CompletionPolicy {
+    subtasks:         SubtaskCompletion::Warn,
+    notify_unblocked: false
+  };
+  let path =
+    resolve_config_path("rivet.toml");
+  let section = std::fs::read_to_string(
+    &path
+  )
+  .ok()
+  .and_then(|raw| {
+    toml::from_str::<toml::Value>(&raw)
+      .ok()
+  })
+  .and_then(|root| {
+    root.get("on_complete").cloned()
+  });
+  let Some(section) = section else {
+    return defaults;
+  };
+
+  let subtasks = section
+    .get("subtasks")
+    .and_then(toml::Value::as_str)
+    .map_or(
+      Ok(defaults.subtasks),
+      SubtaskCompletion::parse
+    )
+    .unwrap_or_else(|err| {
+      warn!(error = %err, "ignoring [on_complete] subtasks");
+      defaults.subtasks
+    });
+  let notify_unblocked = section
+    .get("dependents")
+    .and_then(toml::Value::as_str)
+    .map_or(
+      Ok(defaults.notify_unblocked),
+      CompletionPolicy::parse_dependents
+    )
+    .unwrap_or_else(|err| {
+      warn!(error = %err, "ignoring [on_complete] dependents");
+      defaults.notify_unblocked
+    });
+  CompletionPolicy {
+    subtasks,
+    notify_unblocked
+  }
+}
+
+/// `[app] seed_demo` from rivet.toml;
+/// off unless set to `true`.
+pub fn gui_seed_demo() -> bool {
+  let path =
+    resolve_config_path("rivet.toml");
+  std::fs::read_to_string(&path)
+    .ok()
+    .and_then(|raw| {
+      toml::from_str::<toml::Value>(
+        &raw
+      )
+      .ok()
+    })
+    .and_then(|root| {
+      root
+        .get("app")?
+        .get("seed_demo")?
+        .as_bool()
+    })
+    .unwrap_or(false)
+}
+
 fn candidate_config_paths(
   rel_path: &str
 ) -> Vec<std::path::PathBuf> {
@@ -337,43 +641,203 @@ fn apply_single_update(
   output
 }
 
+/// The value at `section` (dotted, as
+/// in a `[a.b]` header) and `key`.
+fn config_value_at<'a>(
+  root: &'a toml::Value,
+  section: &str,
+  key: &str
+) -> Option<&'a toml::Value> {
+  section
+    .split('.')
+    .chain(key.split('.'))
+    .map(str::trim)
+    .try_fold(root, |value, part| {
+      value.get(part)
+    })
+}
+
+fn toml_kind(
+  value: &toml::Value
+) -> &'static str {
+  match value {
+    | toml::Value::Boolean(_) => {
+      "boolean"
+    }
+    | toml::Value::Integer(_)
+    | toml::Value::Float(_) => "number",
+    | toml::Value::String(_) => "string",
+    | toml::Value::Array(_) => "array",
+    | toml::Value::Table(_) => "table",
+    | toml::Value::Datetime(_) => {
+      "datetime"
+    }
+  }
+}
+
+fn json_kind(
+  value: &serde_json::Value
+) -> &'static str {
+  match value {
+    | serde_json::Value::Bool(_) => {
+      "boolean"
+    }
+    | serde_json::Value::Number(_) => {
+      "number"
+    }
+    | serde_json::Value::String(_) => {
+      "string"
+    }
+    | serde_json::Value::Array(_) => {
+      "array"
+    }
+    | serde_json::Value::Object(_) => {
+      "table"
+    }
+    | serde_json::Value::Null => "null"
+  }
+}
+
+/// Range and enum checks for keys the
+/// settings UI writes; anything else
+/// only has to keep its type.
+fn check_known_config_value(
+  section: &str,
+  key: &str,
+  value: &serde_json::Value
+) -> Result<(), String> {
+  let int_in = |min: i64, max: i64| {
+    match value.as_i64() {
+      | Some(number)
+        if (min..=max)
+          .contains(&number) =>
+      {
+        Ok(())
+      }
+      | _ => {
+        Err(format!(
+          "expected a whole number \
+           from {min} to {max}"
+        ))
+      }
+    }
+  };
+  match (section, key) {
+    | (
+      "notifications.due",
+      "pre_notify_minutes"
+    ) => int_in(1, 43_200),
+    | (
+      "notifications.due",
+      "scan_interval_seconds"
+    ) => int_in(1, 86_400),
+    | (
+      "external_calendar",
+      "max_concurrent"
+    ) => int_in(1, 16),
+    | (
+      "external_calendar",
+      "min_interval"
+    ) => int_in(0, 3_600),
+    | ("ui.theme", "mode") => {
+      match value.as_str().map(|mode| {
+        mode.trim().to_ascii_lowercase()
+      }) {
+        | Some(mode)
+          if matches!(
+            mode.as_str(),
+            "day"
+              | "night"
+              | "light"
+              | "dark"
+          ) =>
+        {
+          Ok(())
+        }
+        | _ => {
+          Err("expected day, night, \
+               light or dark"
+            .to_string())
+        }
+      }
+    }
+    | ("time", "timezone") => {
+      match value.as_str().map(|zone| {
+        zone.trim().parse::<chrono_tz::Tz>()
+      }) {
+        | Some(Ok(_)) => Ok(()),
+        | _ => {
+          Err("expected an IANA \
+               timezone name"
+            .to_string())
+        }
+      }
+    }
+    | _ => Ok(())
+  }
+}
+
+/// The TOML literal to write for one
+/// update, or why it is refused.
+fn validate_config_update(
+  update: &ConfigEntryUpdateArg,
+  previous: Option<&toml::Value>
+) -> Result<String, String> {
+  ensure_valid_config_identifier(
+    "section",
+    &update.section,
+  )
+  .map_err(|err| err.to_string())?;
+  ensure_valid_config_identifier(
+    "key",
+    &update.key,
+  )
+  .map_err(|err| err.to_string())?;
+  let literal =
+    json_to_toml_literal(&update.value)
+      .map_err(|err| err.to_string())?;
+  if let Some(previous) = previous {
+    let expected = toml_kind(previous);
+    let got = json_kind(&update.value);
+    if expected == "table" {
+      return Err(format!(
+        "{} is a section, not a value",
+        update.key.trim()
+      ));
+    }
+    if expected != got {
+      return Err(format!(
+        "expected a {expected}, got a \
+         {got}"
+      ));
+    }
+  }
+  check_known_config_value(
+    update.section.trim(),
+    update.key.trim(),
+    &update.value,
+  )?;
+  Ok(literal)
+}
+
 fn write_toml_updates(
   rel_path: &str,
   updates: &[ConfigEntryUpdateArg]
-) -> anyhow::Result<
-  serde_json::Value
-> {
+) -> anyhow::Result<ConfigApplyResult>
+{
   if updates.is_empty() {
-    return read_toml_snapshot(rel_path);
-  }
-
-  for update in updates {
-    ensure_valid_config_identifier(
-      "section",
-      &update.section,
-    )?;
-    ensure_valid_config_identifier(
-      "key",
-      &update.key,
-    )?;
+    return Ok(ConfigApplyResult {
+      saved:   false,
+      results: Vec::new(),
+      config:  with_effective_settings(
+        read_toml_snapshot(rel_path)?,
+      )
+    });
   }
 
   let path =
     resolve_config_path(rel_path);
-  if let Some(parent) = path.parent()
-    && !parent.as_os_str().is_empty()
-  {
-    std::fs::create_dir_all(parent)
-      .map_err(anyhow::Error::new)
-      .with_context(|| {
-        format!(
-          "failed to create config directory {}",
-          parent.display()
-        )
-      })?;
-  }
-
-  let mut raw =
+  let raw =
     if path.exists() {
       std::fs::read_to_string(&path)
         .map_err(anyhow::Error::new)
@@ -386,29 +850,187 @@ fn write_toml_updates(
     } else {
       String::new()
     };
+  let current = toml::from_str::<
+    toml::Value,
+  >(&raw)
+  .map_err(anyhow::Error::new)
+  .with_context(|| {
+    format!(
+      "failed to parse TOML {}",
+      path.display()
+    )
+  })?;
 
+  let mut next = raw.clone();
+  let mut results = Vec::new();
   for update in updates {
-    let literal = json_to_toml_literal(
-      &update.value
-    )?;
-    raw = apply_single_update(
-      &raw,
-      update.section.trim(),
-      update.key.trim(),
-      literal.as_str(),
+    let section = update.section.trim();
+    let key = update.key.trim();
+    let previous = config_value_at(
+      &current, section, key,
     );
+    let (status, reason) =
+      match validate_config_update(
+        update, previous,
+      ) {
+        | Ok(literal) => {
+          next = apply_single_update(
+            &next,
+            section,
+            key,
+            literal.as_str(),
+          );
+          let unchanged = previous
+            .and_then(|value| {
+              serde_json::to_value(value)
+                .ok()
+            })
+            .is_some_and(|value| {
+              value == update.value
+            });
+          if unchanged {
+            (
+              ConfigUpdateStatus::Unchanged,
+              None
+            )
+          } else {
+            (
+              ConfigUpdateStatus::Applied,
+              None
+            )
+          }
+        }
+        | Err(reason) => {
+          (
+            ConfigUpdateStatus::Rejected,
+            Some(reason)
+          )
+        }
+      };
+    results.push(ConfigUpdateResult {
+      section: section.to_string(),
+      key: key.to_string(),
+      status,
+      reason,
+      previous: previous.and_then(
+        |value| {
+          serde_json::to_value(value)
+            .ok()
+        },
+      ),
+      effective: None
+    });
+  }
+
+  // Every edit is line-based, so check
+  // the whole file still parses before
+  // it replaces the old one.
+  let mut rejected = results
+    .iter()
+    .any(|result| {
+      result.status
+        == ConfigUpdateStatus::Rejected
+    });
+  if !rejected
+    && let Err(err) =
+      toml::from_str::<toml::Value>(
+        &next
+      )
+  {
+    rejected = true;
+    for result in &mut results {
+      result.status =
+        ConfigUpdateStatus::Rejected;
+      result.reason = Some(format!(
+        "result would not be valid \
+         TOML: {}",
+        err.message()
+      ));
+    }
+  }
+  if rejected {
+    for result in &mut results {
+      if result.status
+        != ConfigUpdateStatus::Rejected
+      {
+        result.status =
+          ConfigUpdateStatus::Skipped;
+        result.reason = Some(
+          "not saved: another update \
+           was rejected"
+            .to_string()
+        );
+      }
+    }
+  }
+
+  let saved = !rejected && next != raw;
+  if saved {
+    write_config_file(&path, &next)?;
+  }
+
+  let written = if saved {
+    toml::from_str::<toml::Value>(&next)
+      .map_err(anyhow::Error::new)?
+  } else {
+    current
+  };
+  for result in &mut results {
+    result.effective = config_value_at(
+      &written,
+      &result.section,
+      &result.key,
+    )
+    .and_then(|value| {
+      serde_json::to_value(value).ok()
+    });
   }
+  let config = serde_json::to_value(
+    &written,
+  )
+  .map_err(anyhow::Error::new)?;
 
-  std::fs::write(&path, raw)
+  Ok(ConfigApplyResult {
+    saved,
+    results,
+    config: with_effective_settings(
+      config,
+    )
+  })
+}
+
+/// Writes through a sibling temp file
+/// and a rename, so a failed write
+/// leaves the old config in place.
+fn write_config_file(
+  path: &std::path::Path,
+  contents: &str
+) -> anyhow::Result<()> {
+  if let Some(parent) = path.parent()
+    && !parent.as_os_str().is_empty()
+  {
+    std::fs::create_dir_all(parent)
+      .map_err(anyhow::Error::new)
+      .with_context(|| {
+        format!(
+          "failed to create config directory {}",
+          parent.display()
+        )
+      })?;
+  }
+  let temp =
+    path.with_extension("toml.tmp");
+  std::fs::write(&temp, contents)
+    .and_then(|()| {
+      std::fs::rename(&temp, path)
+    })
     .map_err(anyhow::Error::new)
     .with_context(|| {
       format!(
         "failed to write config file {}",
         path.display()
       )
-    })?;
-
-  read_toml_snapshot(rel_path)
+    })
 }
 
 fn map_base_url_from_snapshot(
@@ -612,8 +1234,38 @@ pub async fn config_snapshot(
 ) -> Result<serde_json::Value, String>
 {
   tracing::info!(request_id = ?request_id, "config_snapshot command invoked");
-  read_toml_snapshot("rivet.toml")
-    .map_err(err_to_string)
+  // Without a readable rivet.toml the
+  // UI still needs the timezone the
+  // backend parses dates in.
+  let snapshot =
+    read_toml_snapshot("rivet.toml")
+      .unwrap_or_else(|err| {
+        tracing::warn!(error = %err, "config_snapshot falling back to defaults");
+        serde_json::json!({})
+      });
+  Ok(with_effective_settings(snapshot))
+}
+
+/// Adds `effective`: values the backend
+/// resolved rather than read verbatim.
+/// `timezone` is the one dates are
+/// parsed and bucketed in
+/// (`RIVET_TIMEZONE`, then rivet.toml,
+/// then the built-in default).
+fn with_effective_settings(
+  mut snapshot: serde_json::Value
+) -> serde_json::Value {
+  if let Some(map) =
+    snapshot.as_object_mut()
+  {
+    map.insert(
+      "effective".to_string(),
+      serde_json::json!({
+        "timezone": project_timezone().name()
+      })
+    );
+  }
+  snapshot
 }
 
 #[tauri::command]
@@ -624,7 +1276,7 @@ pub async fn tag_schema_snapshot(
 {
   tracing::info!(request_id = ?request_id, "tag_schema_snapshot command invoked");
   read_toml_snapshot(
-    "crates/rivet-gui/ui/assets/tags.toml",
+    TAG_SCHEMA_PATH,
   )
   .map_err(err_to_string)
 }
@@ -634,18 +1286,264 @@ pub async fn tag_schema_snapshot(
 pub async fn config_apply_updates(
   args: ConfigApplyArg,
   request_id: Option<String>
-) -> Result<serde_json::Value, String>
+) -> Result<ConfigApplyResult, String>
 {
   tracing::info!(
     request_id = ?request_id,
     updates = args.updates.len(),
     "config_apply_updates command invoked"
   );
-  write_toml_updates(
+  ensure_permitted(
+    CommandCategory::ConfigWrite,
+    "config_apply_updates"
+  )?;
+  if args.updates.iter().any(|update| {
+    update.section.trim() == "permissions"
+  }) {
+    return Err(
+      "permission denied: [permissions] \
+       can only be changed by editing \
+       rivet.toml"
+        .to_string()
+    );
+  }
+  let result = write_toml_updates(
     "rivet.toml",
     &args.updates,
   )
-  .map_err(err_to_string)
+  .map_err(err_to_string)?;
+  for rejected in
+    result.results.iter().filter(
+      |entry| {
+        entry.status
+          == ConfigUpdateStatus::Rejected
+      }
+    )
+  {
+    tracing::warn!(
+      request_id = ?request_id,
+      section = %rejected.section,
+      key = %rejected.key,
+      reason = ?rejected.reason,
+      "config update rejected"
+    );
+  }
+  Ok(result)
+}
+
+/// The files the GUI bundles, resolved
+/// like every other config read.
+fn gui_bundle_targets()
+-> Vec<(BundleFile, std::path::PathBuf)> {
+  vec![
+    (
+      BundleFile::RivetToml,
+      resolve_config_path("rivet.toml")
+    ),
+    (
+      BundleFile::TagSchema,
+      resolve_config_path(
+        TAG_SCHEMA_PATH
+      )
+    ),
+  ]
+}
+
+fn permissions_table(
+  text: &str
+) -> Option<toml::Value> {
+  toml::from_str::<toml::Value>(text)
+    .ok()?
+    .get("permissions")
+    .cloned()
+}
+
+fn import_config_bundle(
+  raw: &str,
+  policy: ConflictPolicy,
+  targets: &[(
+    BundleFile,
+    std::path::PathBuf
+  )],
+  now: DateTime<Utc>
+) -> anyhow::Result<ConfigBundleImportResult>
+{
+  let bundle = ConfigBundle::parse(raw)?;
+  let changes = bundle.plan(targets)?;
+
+  // Like config_apply_updates, an
+  // import may not loosen or tighten
+  // [permissions].
+  if let Some(change) =
+    changes.iter().find(|change| {
+      change.file
+        == BundleFile::RivetToml
+    })
+  {
+    let current =
+      std::fs::read_to_string(
+        &change.path
+      )
+      .unwrap_or_default();
+    if permissions_table(&current)
+      != permissions_table(
+        &bundle.files[change.file.key()]
+      )
+    {
+      anyhow::bail!(
+        "permission denied: the bundle \
+         changes [permissions], which \
+         can only be changed by \
+         editing rivet.toml"
+      );
+    }
+  }
+
+  let keys = |kind: ChangeKind| {
+    changes
+      .iter()
+      .filter(|change| change.kind == kind)
+      .map(|change| {
+        change.file.key().to_string()
+      })
+      .collect::<Vec<_>>()
+  };
+  let conflicts =
+    keys(ChangeKind::Conflict);
+  let skipped = bundle
+    .files
+    .keys()
+    .filter(|key| {
+      !changes.iter().any(|change| {
+        change.file.key() == *key
+      })
+    })
+    .cloned()
+    .collect();
+  if policy == ConflictPolicy::Ask
+    && !conflicts.is_empty()
+  {
+    return Ok(ConfigBundleImportResult {
+      applied: false,
+      conflicts,
+      written: Vec::new(),
+      kept: Vec::new(),
+      unchanged: Vec::new(),
+      backups: Vec::new(),
+      skipped,
+      gui: bundle.gui
+    });
+  }
+
+  let report = config_bundle::apply(
+    &changes,
+    now,
+    |_| Ok(policy == ConflictPolicy::Overwrite)
+  )?;
+  let key_of = |path: &std::path::PathBuf| {
+    changes
+      .iter()
+      .find(|change| &change.path == path)
+      .map(|change| {
+        change.file.key().to_string()
+      })
+      .unwrap_or_default()
+  };
+  Ok(ConfigBundleImportResult {
+    applied: true,
+    conflicts,
+    written: report
+      .written
+      .iter()
+      .map(key_of)
+      .collect(),
+    kept: report
+      .kept
+      .iter()
+      .map(key_of)
+      .collect(),
+    unchanged: report
+      .unchanged
+      .iter()
+      .map(key_of)
+      .collect(),
+    backups: report
+      .backups
+      .iter()
+      .map(|(_, copy)| {
+        copy.display().to_string()
+      })
+      .collect(),
+    skipped,
+    gui: bundle.gui
+  })
+}
+
+/// Bundles `rivet.toml`, the tag schema
+/// and the UI's `gui` section into the
+/// JSON `task config export` writes.
+#[tauri::command]
+#[instrument(skip(args), fields(request_id = ?request_id))]
+pub async fn config_bundle_export(
+  args: ConfigBundleExportArg,
+  request_id: Option<String>
+) -> Result<String, String> {
+  tracing::info!(
+    request_id = ?request_id,
+    "config_bundle_export command invoked"
+  );
+  let mut bundle = ConfigBundle::collect(
+    &gui_bundle_targets(),
+    Utc::now()
+  )
+  .map_err(err_to_string)?;
+  bundle.gui = args.gui;
+  bundle.to_json().map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(args), fields(request_id = ?request_id, on_conflict = ?args.on_conflict))]
+pub async fn config_bundle_import(
+  args: ConfigBundleImportArg,
+  request_id: Option<String>
+) -> Result<ConfigBundleImportResult, String>
+{
+  tracing::info!(
+    request_id = ?request_id,
+    "config_bundle_import command invoked"
+  );
+  ensure_permitted(
+    CommandCategory::ConfigWrite,
+    "config_bundle_import"
+  )?;
+  let policy = ConflictPolicy::parse(
+    args
+      .on_conflict
+      .as_deref()
+      .unwrap_or("ask")
+  )
+  .map_err(err_to_string)?;
+  let result = import_config_bundle(
+    &args.bundle,
+    policy,
+    &gui_bundle_targets(),
+    Utc::now()
+  );
+  match result.as_ref() {
+    | Ok(imported) => {
+      tracing::info!(
+        request_id = ?request_id,
+        applied = imported.applied,
+        written = ?imported.written,
+        kept = ?imported.kept,
+        "config_bundle_import completed"
+      );
+    }
+    | Err(err) => {
+      tracing::error!(request_id = ?request_id, error = %err, "config_bundle_import command failed");
+    }
+  }
+  result.map_err(err_to_string)
 }
 
 #[tauri::command]
@@ -692,6 +1590,25 @@ mod config_tests {
     );
   }
 
+  #[test]
+  fn config_snapshot_reports_effective_timezone()
+  {
+    let snapshot =
+      with_effective_settings(
+        serde_json::json!({
+          "time": {"timezone": "Not/AZone"}
+        }),
+      );
+    assert_eq!(
+      snapshot["effective"]["timezone"],
+      project_timezone().name()
+    );
+    assert_eq!(
+      snapshot["time"]["timezone"],
+      "Not/AZone"
+    );
+  }
+
   #[test]
   fn catalog_source_count_handles_sources_field()
   {
@@ -723,6 +1640,233 @@ mod config_tests {
       .contains("unsupported martin URL scheme"));
   }
 
+  #[test]
+  fn config_bundle_import_asks_before_replacing_files()
+  {
+    let dir = std::env::temp_dir().join(
+      format!(
+        "rivet_bundle_test_{}",
+        Uuid::new_v4()
+      ),
+    );
+    std::fs::create_dir_all(&dir)
+      .expect("create temp dir");
+    let rivet_toml = dir.join("rivet.toml");
+    std::fs::write(
+      &rivet_toml,
+      "[ui]\n",
+    )
+    .expect("write config");
+    let targets = vec![
+      (BundleFile::RivetToml, rivet_toml.clone()),
+      (BundleFile::TagSchema, dir.join("tags.toml")),
+    ];
+    let bundle = serde_json::json!({
+      "format": "rivet-config-bundle",
+      "version": 1,
+      "created": "2026-03-04T12:00:00Z",
+      "files": {
+        "rivet.toml": "[calendar]\nweek_start = \"sunday\"\n",
+        "tags.toml": "version = 1\n",
+        "taskrc": "color=off\n"
+      },
+      "gui": {"kanban_boards": []}
+    })
+    .to_string();
+    let now = Utc::now();
+
+    let asked = import_config_bundle(
+      &bundle,
+      ConflictPolicy::Ask,
+      &targets,
+      now,
+    )
+    .expect("ask");
+    assert!(!asked.applied);
+    assert_eq!(asked.conflicts, ["rivet.toml"]);
+    assert_eq!(asked.skipped, ["taskrc"]);
+    assert!(!dir.join("tags.toml").exists());
+
+    let kept = import_config_bundle(
+      &bundle,
+      ConflictPolicy::Keep,
+      &targets,
+      now,
+    )
+    .expect("keep");
+    assert!(kept.applied);
+    assert_eq!(kept.written, ["tags.toml"]);
+    assert_eq!(kept.kept, ["rivet.toml"]);
+    assert_eq!(
+      kept.gui,
+      Some(serde_json::json!({"kanban_boards": []}))
+    );
+
+    let replaced = import_config_bundle(
+      &bundle,
+      ConflictPolicy::Overwrite,
+      &targets,
+      now,
+    )
+    .expect("overwrite");
+    assert_eq!(replaced.written, ["rivet.toml"]);
+    assert_eq!(replaced.unchanged, ["tags.toml"]);
+    assert_eq!(replaced.backups.len(), 1);
+    assert!(
+      std::fs::read_to_string(&rivet_toml)
+        .expect("read")
+        .contains("sunday")
+    );
+
+    let locked = bundle.replace(
+      "[calendar]",
+      "[permissions]\\nread_only = true\\n[calendar]",
+    );
+    let err = import_config_bundle(
+      &locked,
+      ConflictPolicy::Overwrite,
+      &targets,
+      now,
+    )
+    .expect_err("permissions change");
+    assert!(
+      err.to_string().contains("[permissions]")
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn config_updates_are_all_or_nothing()
+  {
+    let dir = std::env::temp_dir().join(
+      format!(
+        "rivet_config_test_{}",
+        Uuid::new_v4()
+      ),
+    );
+    std::fs::create_dir_all(&dir)
+      .expect("create temp dir");
+    let path = dir.join("rivet.toml");
+    let original = "[notifications.due]\n\
+                    enabled = false\n\
+                    pre_notify_minutes = 15\n";
+    std::fs::write(&path, original)
+      .expect("write config");
+    let rel = path.to_str().expect("utf-8 path");
+    let update = |section: &str,
+                  key: &str,
+                  value: serde_json::Value| {
+      ConfigEntryUpdateArg {
+        section: section.to_string(),
+        key: key.to_string(),
+        value
+      }
+    };
+
+    let result = write_toml_updates(
+      rel,
+      &[
+        update(
+          "notifications.due",
+          "enabled",
+          serde_json::json!(true),
+        ),
+        update(
+          "notifications.due",
+          "pre_notify_minutes",
+          serde_json::json!("soon"),
+        ),
+      ],
+    )
+    .expect("apply");
+    assert!(!result.saved);
+    assert_eq!(
+      result.results[0].status,
+      ConfigUpdateStatus::Skipped
+    );
+    assert_eq!(
+      result.results[1].status,
+      ConfigUpdateStatus::Rejected
+    );
+    assert_eq!(
+      result.results[1].reason.as_deref(),
+      Some("expected a number, got a string")
+    );
+    assert_eq!(
+      result.results[0].effective,
+      Some(serde_json::json!(false))
+    );
+    assert_eq!(
+      std::fs::read_to_string(&path)
+        .expect("read config"),
+      original
+    );
+
+    let result = write_toml_updates(
+      rel,
+      &[
+        update(
+          "notifications.due",
+          "enabled",
+          serde_json::json!(true),
+        ),
+        update(
+          "notifications.due",
+          "pre_notify_minutes",
+          serde_json::json!(15),
+        ),
+        update(
+          "ui.theme",
+          "mode",
+          serde_json::json!("night"),
+        ),
+      ],
+    )
+    .expect("apply");
+    assert!(result.saved);
+    assert_eq!(
+      result
+        .results
+        .iter()
+        .map(|entry| entry.status)
+        .collect::<Vec<_>>(),
+      vec![
+        ConfigUpdateStatus::Applied,
+        ConfigUpdateStatus::Unchanged,
+        ConfigUpdateStatus::Applied,
+      ]
+    );
+    assert_eq!(
+      result.results[2].previous,
+      None
+    );
+    assert_eq!(
+      result.config["ui"]["theme"]["mode"],
+      "night"
+    );
+    assert_eq!(
+      result.config["notifications"]["due"]["enabled"],
+      true
+    );
+
+    let result = write_toml_updates(
+      rel,
+      &[update(
+        "notifications.due",
+        "pre_notify_minutes",
+        serde_json::json!(0),
+      )],
+    )
+    .expect("apply");
+    assert_eq!(
+      result.results[0].reason.as_deref(),
+      Some(
+        "expected a whole number from 1 to 43200"
+      )
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+
   #[tokio::test]
   async fn map_health_unreachable_maps_error()
   {
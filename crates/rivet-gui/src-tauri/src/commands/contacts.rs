@@ -7,17 +7,24 @@ use std::path::{
   PathBuf
 };
 use std::sync::{
+  Arc,
   Mutex,
   OnceLock
 };
 
 use rivet_gui_shared::{
+  ContactCategoryCount,
   ContactCreate,
   ContactDto,
   ContactFieldValue,
   ContactIdentityFingerprint,
   ContactIdArg,
   ContactImportBatch,
+  ContactInteraction,
+  ContactInteractionsArgs,
+  ContactInteractionsPruneArgs,
+  ContactInteractionsPruneResult,
+  ContactInteractionsResult,
   ContactImportConflict,
   ContactsDedupeDecideArgs,
   ContactsDedupeDecideResult,
@@ -27,6 +34,9 @@ use rivet_gui_shared::{
   ContactsDedupePreviewArgs,
   ContactsDedupePreviewResult,
   ContactsDeleteBulkArgs,
+  ContactsDeleteBulkResult,
+  ContactsDeleteUndoArgs,
+  ContactsDeleteUndoResult,
   ContactsImportCommitArgs,
   ContactsImportCommitResult,
   ContactsImportPreviewArgs,
@@ -34,9 +44,11 @@ use rivet_gui_shared::{
   ContactsListArgs,
   ContactsListResult,
   ContactsMergeArgs,
+  ContactsMergePreviewResult,
   ContactsMergeResult,
   ContactsMergeUndoArgs,
   ContactsMergeUndoResult,
+  ContactsUnlinkResult,
   DedupDecision,
   MergeAudit,
   ContactUpdateArgs,
@@ -52,10 +64,14 @@ const CONTACTS_IMPORT_BATCHES_FILE:
   &str = "contacts_import_batches.data";
 const CONTACTS_MERGE_UNDO_FILE:
   &str = "contacts_merge_undo.data";
+const CONTACTS_DELETE_UNDO_FILE:
+  &str = "contacts_delete_undo.data";
 const CONTACTS_MERGE_AUDIT_FILE:
   &str = "contacts_merge_audit.data";
 const CONTACTS_DEDUPE_DECISIONS_FILE:
   &str = "contacts_dedupe_decisions.data";
+const CONTACTS_INTERACTIONS_FILE: &str =
+  "contacts_interactions.data";
 const CONTACTS_IMPORT_ERRORS_DIR:
   &str = "contacts_import_errors";
 
@@ -67,6 +83,8 @@ const CONTACTS_MAX_FIELD_KIND_LEN: usize = 64;
 const CONTACTS_MAX_FIELD_VALUE_LEN: usize = 512;
 const CONTACTS_MAX_MULTI_FIELDS: usize = 32;
 const CONTACTS_MAX_ADDRESSES: usize = 8;
+const CONTACTS_MAX_CATEGORIES: usize = 32;
+const CONTACTS_MAX_CATEGORY_LEN: usize = 64;
 const CONTACTS_MAX_AVATAR_DATA_URL_LEN: usize =
   2_000_000;
 
@@ -77,6 +95,16 @@ struct ContactsMergeUndoEntry {
   created_at:      String,
 }
 
+/// Contacts removed by one bulk delete,
+/// kept whole so undo can re-insert
+/// them with fields and links intact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContactsDeleteUndoEntry {
+  undo_id:    String,
+  contacts:   Vec<ContactDto>,
+  created_at: String,
+}
+
 fn contacts_lock() -> &'static Mutex<()> {
   static LOCK: OnceLock<Mutex<()>> =
     OnceLock::new();
@@ -224,12 +252,16 @@ fn ensure_contacts_store() -> anyhow::Result<
     dir.join(CONTACTS_IMPORT_BATCHES_FILE);
   let merge_undo =
     dir.join(CONTACTS_MERGE_UNDO_FILE);
+  let delete_undo =
+    dir.join(CONTACTS_DELETE_UNDO_FILE);
   let merge_audit =
     dir.join(CONTACTS_MERGE_AUDIT_FILE);
   let dedupe_decisions =
     dir.join(
       CONTACTS_DEDUPE_DECISIONS_FILE,
     );
+  let interactions =
+    dir.join(CONTACTS_INTERACTIONS_FILE);
   let import_errors_dir =
     dir.join(CONTACTS_IMPORT_ERRORS_DIR);
 
@@ -238,8 +270,10 @@ fn ensure_contacts_store() -> anyhow::Result<
     &deleted,
     &batches,
     &merge_undo,
+    &delete_undo,
     &merge_audit,
     &dedupe_decisions,
+    &interactions,
   ] {
     if !path.exists() {
       std::fs::write(path, "")
@@ -281,6 +315,19 @@ fn contacts_merge_audit_path(
   Ok(parent.join(CONTACTS_MERGE_AUDIT_FILE))
 }
 
+fn contacts_delete_undo_path(
+  contacts_path: &Path
+) -> anyhow::Result<PathBuf> {
+  let Some(parent) = contacts_path.parent()
+  else {
+    anyhow::bail!(
+      "failed to resolve contacts data \
+       directory"
+    );
+  };
+  Ok(parent.join(CONTACTS_DELETE_UNDO_FILE))
+}
+
 fn contacts_dedupe_decisions_path(
   contacts_path: &Path
 ) -> anyhow::Result<PathBuf> {
@@ -296,6 +343,69 @@ fn contacts_dedupe_decisions_path(
   ))
 }
 
+fn contacts_interactions_path(
+  contacts_path: &Path
+) -> anyhow::Result<PathBuf> {
+  let Some(parent) = contacts_path.parent()
+  else {
+    anyhow::bail!(
+      "failed to resolve contacts data \
+       directory"
+    );
+  };
+  Ok(parent.join(
+    CONTACTS_INTERACTIONS_FILE
+  ))
+}
+
+/// `[contacts].log_interactions` in
+/// rivet.toml; on unless set to false.
+fn interaction_logging_enabled() -> bool {
+  let path =
+    resolve_config_path("rivet.toml");
+  std::fs::read_to_string(&path)
+    .ok()
+    .and_then(|raw| {
+      toml::from_str::<toml::Value>(&raw)
+        .ok()
+    })
+    .and_then(|value| {
+      value
+        .get("contacts")?
+        .get("log_interactions")?
+        .as_bool()
+    })
+    .unwrap_or(true)
+}
+
+/// Newest interaction time per contact.
+fn last_contacted_by_contact(
+  interactions: &[ContactInteraction]
+) -> HashMap<Uuid, String> {
+  let mut newest =
+    HashMap::<Uuid, String>::new();
+  for interaction in interactions {
+    let entry = newest
+      .entry(interaction.contact_id)
+      .or_default();
+    if parse_interaction_time(
+      &interaction.at,
+    ) > parse_interaction_time(entry)
+    {
+      entry.clone_from(&interaction.at);
+    }
+  }
+  newest
+}
+
+fn parse_interaction_time(
+  raw: &str
+) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(raw)
+    .ok()
+    .map(|at| at.with_timezone(&Utc))
+}
+
 fn load_jsonl<T>(
   path: &Path
 ) -> anyhow::Result<Vec<T>>
@@ -607,6 +717,10 @@ fn build_contact_search_blob(
       &field.value,
     ));
   }
+  for category in &contact.categories {
+    haystacks
+      .push(normalize_text(category));
+  }
   haystacks.join(" ")
 }
 
@@ -639,8 +753,146 @@ fn build_contact_fingerprint(
   }
 }
 
+#[derive(Debug, Default)]
+struct NameStopwordsCache {
+  path:     Option<PathBuf>,
+  revision: Option<u128>,
+  words:    Arc<HashSet<String>>,
+}
+
+/// Tokens dropped from dedupe name and
+/// organization keys, read from
+/// `[contacts.dedupe].name_stopwords`
+/// in rivet.toml and reloaded when the
+/// file changes.
+fn name_key_stopwords(
+) -> Arc<HashSet<String>> {
+  static CACHE: OnceLock<
+    Mutex<NameStopwordsCache>,
+  > = OnceLock::new();
+  let path =
+    resolve_config_path("rivet.toml");
+  let revision = file_revision(&path);
+  let mut cache = CACHE
+    .get_or_init(|| {
+      Mutex::new(
+        NameStopwordsCache::default(),
+      )
+    })
+    .lock()
+    .unwrap_or_else(
+      std::sync::PoisonError::into_inner,
+    );
+  if cache.path.as_deref()
+    == Some(path.as_path())
+    && cache.revision == revision
+  {
+    return Arc::clone(&cache.words);
+  }
+
+  let words = std::fs::read_to_string(
+    &path,
+  )
+  .ok()
+  .and_then(|raw| {
+    toml::from_str::<toml::Value>(&raw)
+      .map_err(|err| {
+        tracing::warn!(path = %path.display(), error = %err, "failed to parse rivet.toml for contact stopwords");
+      })
+      .ok()
+  })
+  .and_then(|value| {
+    value
+      .get("contacts")?
+      .get("dedupe")?
+      .get("name_stopwords")?
+      .as_array()
+      .cloned()
+  })
+  .unwrap_or_default()
+  .iter()
+  .filter_map(|word| word.as_str())
+  .map(stopword_token)
+  .filter(|word| !word.is_empty())
+  .collect::<HashSet<_>>();
+
+  *cache = NameStopwordsCache {
+    path: Some(path),
+    revision,
+    words: Arc::new(words),
+  };
+  Arc::clone(&cache.words)
+}
+
+fn stopword_token(
+  value: &str
+) -> String {
+  normalize_text(value)
+    .trim_matches(|ch: char| {
+      !ch.is_alphanumeric()
+    })
+    .to_string()
+}
+
+/// Drops honorifics, generational
+/// suffixes and company forms from an
+/// already normalized key so "acme
+/// inc." and "acme llc" compare equal.
+/// Keys made only of stopwords are kept
+/// as-is.
+fn strip_name_stopwords(
+  normalized: &str,
+  stopwords: &HashSet<String>,
+) -> String {
+  if stopwords.is_empty() {
+    return normalized.to_string();
+  }
+  let kept = normalized
+    .split_whitespace()
+    .map(|token| {
+      token.trim_matches(|ch: char| {
+        !ch.is_alphanumeric()
+      })
+    })
+    .filter(|token| {
+      !token.is_empty()
+        && !stopwords.contains(*token)
+    })
+    .collect::<Vec<_>>();
+  if kept.is_empty() {
+    normalized.to_string()
+  } else {
+    kept.join(" ")
+  }
+}
+
+fn contact_org_key(
+  contact: &ContactDto,
+  stopwords: &HashSet<String>,
+) -> String {
+  strip_name_stopwords(
+    &normalize_text(
+      contact
+        .organization
+        .as_deref()
+        .unwrap_or_default(),
+    ),
+    stopwords,
+  )
+}
+
 fn contact_name_key(
   contact: &ContactDto
+) -> String {
+  contact_name_key_with(
+    contact,
+    &name_key_stopwords(),
+  )
+}
+
+fn contact_name_key_with(
+  contact: &ContactDto,
+  stopwords: &HashSet<String>,
 ) -> String {
   let mut parts = Vec::<String>::new();
   if let Some(given) =
@@ -663,12 +915,18 @@ fn contact_name_key(
   }
 
   if parts.is_empty() {
-    return normalize_text(
-      &contact.display_name,
+    return strip_name_stopwords(
+      &normalize_text(
+        &contact.display_name,
+      ),
+      stopwords,
     );
   }
 
-  parts.join(" ")
+  strip_name_stopwords(
+    &parts.join(" "),
+    stopwords,
+  )
 }
 
 fn contact_domains(
@@ -809,6 +1067,22 @@ fn ensure_contact_defaults(
     }
   }
 
+  let mut seen_categories =
+    HashSet::new();
+  contact.categories = contact
+    .categories
+    .iter()
+    .map(|category| {
+      category.trim().to_string()
+    })
+    .filter(|category| {
+      !category.is_empty()
+        && seen_categories.insert(
+          category.to_lowercase(),
+        )
+    })
+    .collect();
+
   if contact
     .source_id
     .trim()
@@ -1047,6 +1321,27 @@ fn validate_contact(
     }
   }
 
+  if contact.categories.len()
+    > CONTACTS_MAX_CATEGORIES
+  {
+    anyhow::bail!(
+      "categories exceed maximum \
+       count {}",
+      CONTACTS_MAX_CATEGORIES
+    );
+  }
+  for category in &contact.categories {
+    if category.len()
+      > CONTACTS_MAX_CATEGORY_LEN
+    {
+      anyhow::bail!(
+        "category exceeds {} \
+         characters",
+        CONTACTS_MAX_CATEGORY_LEN
+      );
+    }
+  }
+
   for email in &contact.emails {
     let token = email.value.trim();
     if token.is_empty() {
@@ -1107,9 +1402,11 @@ fn from_create_payload(
     remote_id: create.remote_id,
     link_group_id: create
       .link_group_id,
+    categories: create.categories,
     created_at: existing_created_at
       .unwrap_or_else(now_iso),
     updated_at: now_iso(),
+    last_contacted: None,
   }
 }
 
@@ -1217,6 +1514,11 @@ fn apply_contact_patch(
     contact.link_group_id =
       link_group_id;
   }
+  if let Some(categories) =
+    patch.categories
+  {
+    contact.categories = categories;
+  }
 
   contact.updated_at = now_iso();
 }
@@ -1344,8 +1646,40 @@ fn contacts_list_internal(
   };
 
   let mut contacts = base_contacts;
+  let last_contacted =
+    last_contacted_by_contact(
+      &load_jsonl::<ContactInteraction>(
+        &contacts_interactions_path(
+          &contacts_path,
+        )?,
+      )?,
+    );
+  for contact in &mut contacts {
+    contact.last_contacted =
+      last_contacted
+        .get(&contact.id)
+        .cloned();
+  }
+  let contacted_after = args
+    .contacted_within_days
+    .map(|days| {
+      Utc::now()
+        - chrono::Duration::days(
+          i64::from(days),
+        )
+    });
 
   contacts.retain(|contact| {
+    if let Some(after) = contacted_after
+      && contact
+        .last_contacted
+        .as_deref()
+        .and_then(parse_interaction_time)
+        .is_none_or(|at| at < after)
+    {
+      return false;
+    }
+
     if let Some(source) =
       args.source.as_deref()
       && contact.source_kind
@@ -1374,6 +1708,51 @@ fn contacts_list_internal(
     true
   });
 
+  let categories =
+    category_counts(&contacts);
+  if let Some(category) = args
+    .category
+    .as_deref()
+    .map(str::trim)
+    .filter(|token| {
+      !token.is_empty()
+    })
+  {
+    contacts.retain(|contact| {
+      contact_has_category(
+        contact, category,
+      )
+    });
+  }
+
+  match args
+    .sort
+    .as_deref()
+    .map(str::trim)
+    .unwrap_or("name")
+  {
+    | "" | "name" => {}
+    | "recent" => {
+      // Stable, so ties keep name order.
+      contacts.sort_by_key(|contact| {
+        std::cmp::Reverse(
+          contact
+            .last_contacted
+            .as_deref()
+            .and_then(
+              parse_interaction_time,
+            ),
+        )
+      });
+    }
+    | other => {
+      anyhow::bail!(
+        "unsupported contacts sort: \
+         {other}"
+      );
+    }
+  }
+
   let total = contacts.len();
   let limit = args
     .limit
@@ -1405,12 +1784,74 @@ fn contacts_list_internal(
     contacts: page,
     next_cursor,
     total,
+    categories,
   })
 }
 
+fn contact_has_category(
+  contact: &ContactDto,
+  category: &str,
+) -> bool {
+  contact.categories.iter().any(
+    |value| {
+      value.to_lowercase()
+        == category.to_lowercase()
+    },
+  )
+}
+
+/// Facet counts keyed case-insensitively
+/// under the first spelling seen; most
+/// used first, then by name.
+fn category_counts(
+  contacts: &[ContactDto]
+) -> Vec<ContactCategoryCount> {
+  let mut counts =
+    Vec::<ContactCategoryCount>::new();
+  let mut positions =
+    HashMap::<String, usize>::new();
+  for contact in contacts {
+    for category in &contact.categories
+    {
+      let key = category.to_lowercase();
+      if let Some(position) =
+        positions.get(&key)
+      {
+        counts[*position].count += 1;
+      } else {
+        positions
+          .insert(key, counts.len());
+        counts.push(
+          ContactCategoryCount {
+            category: category.clone(),
+            count:    1,
+          },
+        );
+      }
+    }
+  }
+  counts.sort_by(|left, right| {
+    right
+      .count
+      .cmp(&left.count)
+      .then_with(|| {
+        left
+          .category
+          .to_lowercase()
+          .cmp(
+            &right
+              .category
+              .to_lowercase(),
+          )
+      })
+  });
+  counts
+}
+
 fn score_pair(
   left: &ContactDto,
   right: &ContactDto,
+  stopwords: &HashSet<String>,
 ) -> Option<(u32, String)> {
   let left_emails = left
     .emails
@@ -1478,23 +1919,21 @@ fn score_pair(
     ));
   }
 
-  let left_name = contact_name_key(left);
+  let left_name =
+    contact_name_key_with(
+      left, stopwords,
+    );
   let right_name =
-    contact_name_key(right);
+    contact_name_key_with(
+      right, stopwords,
+    );
   if !left_name.is_empty()
     && left_name == right_name
   {
-    let left_org = normalize_text(
-      left
-        .organization
-        .as_deref()
-        .unwrap_or_default(),
-    );
-    let right_org = normalize_text(
-      right
-        .organization
-        .as_deref()
-        .unwrap_or_default(),
+    let left_org =
+      contact_org_key(left, stopwords);
+    let right_org = contact_org_key(
+      right, stopwords,
     );
 
     if !left_org.is_empty()
@@ -1540,17 +1979,11 @@ fn score_pair(
         &right_name,
       );
     if similarity >= 0.80 {
-      let left_org = normalize_text(
-        left
-          .organization
-          .as_deref()
-          .unwrap_or_default(),
+      let left_org = contact_org_key(
+        left, stopwords,
       );
-      let right_org = normalize_text(
-        right
-          .organization
-          .as_deref()
-          .unwrap_or_default(),
+      let right_org = contact_org_key(
+        right, stopwords,
       );
 
       if !left_org.is_empty()
@@ -1683,6 +2116,7 @@ fn dedupe_groups(
     return Vec::new();
   }
 
+  let stopwords = name_key_stopwords();
   let mut dsu = Dsu::new(items.len());
   let mut pair_scores = HashMap::<
     (usize, usize),
@@ -1697,6 +2131,7 @@ fn dedupe_groups(
         score_pair(
           &items[left],
           &items[right],
+          &stopwords,
         )
       {
         if score >= 60 {
@@ -1891,6 +2326,84 @@ fn merge_optional_text(
   }
 }
 
+/// Puts both contacts in one link
+/// group, reusing `existing`'s group
+/// when it already has one.
+fn link_contacts(
+  existing: &mut ContactDto,
+  incoming: &mut ContactDto,
+) {
+  let group = existing
+    .link_group_id
+    .clone()
+    .filter(|value| {
+      !value.trim().is_empty()
+    })
+    .unwrap_or_else(|| {
+      Uuid::new_v4().to_string()
+    });
+  if existing.link_group_id.as_deref()
+    != Some(group.as_str())
+  {
+    existing.link_group_id =
+      Some(group.clone());
+    existing.updated_at = now_iso();
+  }
+  incoming.link_group_id = Some(group);
+}
+
+/// Takes contact `id` out of its link
+/// group. A group left with a single
+/// member is dissolved, so unlinking
+/// an import-time link restores both
+/// records to how they were. Returns
+/// the contacts that changed.
+fn unlink_contact(
+  contacts: &mut [ContactDto],
+  id: Uuid,
+) -> anyhow::Result<Vec<ContactDto>> {
+  let index = contacts
+    .iter()
+    .position(|contact| contact.id == id)
+    .ok_or_else(|| {
+      anyhow::anyhow!(
+        "contact not found"
+      )
+    })?;
+  let Some(group) = contacts[index]
+    .link_group_id
+    .take()
+    .filter(|value| {
+      !value.trim().is_empty()
+    })
+  else {
+    anyhow::bail!(
+      "contact is not linked"
+    );
+  };
+  let now = now_iso();
+  contacts[index].updated_at =
+    now.clone();
+  let mut changed =
+    vec![contacts[index].clone()];
+
+  let remaining = contacts
+    .iter()
+    .enumerate()
+    .filter(|(_, contact)| {
+      contact.link_group_id.as_deref()
+        == Some(group.as_str())
+    })
+    .map(|(position, _)| position)
+    .collect::<Vec<_>>();
+  if let [last] = remaining[..] {
+    contacts[last].link_group_id = None;
+    contacts[last].updated_at = now;
+    changed.push(contacts[last].clone());
+  }
+  Ok(changed)
+}
+
 fn merge_contact_records(
   target: &mut ContactDto,
   source: &ContactDto,
@@ -1982,9 +2495,110 @@ fn merge_contact_records(
       source.link_group_id.clone();
   }
 
+  for category in &source.categories {
+    if !contact_has_category(
+      target, category,
+    ) {
+      target
+        .categories
+        .push(category.clone());
+    }
+  }
+
   target.updated_at = now_iso();
 }
 
+struct ContactMergePlan {
+  merged:           ContactDto,
+  removed_ids:      Vec<Uuid>,
+  removed_contacts: Vec<ContactDto>,
+}
+
+/// Folds the selected contacts into the
+/// target without touching the store;
+/// `contacts_merge` and
+/// `contacts_merge_preview` share it so
+/// the preview matches the commit.
+fn plan_contact_merge(
+  contacts: &[ContactDto],
+  ids: &BTreeSet<Uuid>,
+  target_id: Option<Uuid>,
+) -> anyhow::Result<ContactMergePlan> {
+  if ids.len() < 2 {
+    anyhow::bail!(
+      "merge requires at least two \
+       contacts"
+    );
+  }
+
+  let selected = contacts
+    .iter()
+    .filter(|contact| {
+      ids.contains(&contact.id)
+    })
+    .cloned()
+    .collect::<Vec<_>>();
+
+  if selected.len() < 2 {
+    anyhow::bail!(
+      "failed to find selected \
+       contacts"
+    );
+  }
+
+  let target_id = target_id
+    .or_else(|| {
+      selected
+        .iter()
+        .max_by(|left, right| {
+          left
+            .updated_at
+            .cmp(&right.updated_at)
+        })
+        .map(|contact| contact.id)
+    })
+    .ok_or_else(|| {
+      anyhow::anyhow!(
+        "invalid merge target"
+      )
+    })?;
+
+  let mut merged = selected
+    .iter()
+    .find(|contact| {
+      contact.id == target_id
+    })
+    .cloned()
+    .unwrap_or_else(|| {
+      selected[0].clone()
+    });
+
+  let removed_contacts = selected
+    .iter()
+    .filter(|item| item.id != merged.id)
+    .cloned()
+    .collect::<Vec<_>>();
+  let removed_ids = removed_contacts
+    .iter()
+    .map(|item| item.id)
+    .collect::<Vec<_>>();
+
+  for contact in &removed_contacts {
+    merge_contact_records(
+      &mut merged,
+      contact,
+    );
+  }
+  ensure_contact_defaults(&mut merged);
+  validate_contact(&merged)?;
+
+  Ok(ContactMergePlan {
+    merged,
+    removed_ids,
+    removed_contacts,
+  })
+}
+
 fn normalize_vcard_kind(
   token: &str,
   fallback: &str,
@@ -2171,6 +2785,7 @@ fn parse_vcard_contacts(
       ),
       remote_id:     None,
       link_group_id: None,
+      categories:    Vec::new(),
     };
 
     for line in card {
@@ -2333,6 +2948,13 @@ fn parse_vcard_contacts(
               Some(value);
           }
         }
+        | "CATEGORIES" => {
+          create.categories.extend(
+            parse_vcard_categories(
+              &value,
+            ),
+          );
+        }
         | _ => {}
       }
     }
@@ -2364,8 +2986,44 @@ fn parse_vcard_contacts(
   (contacts, errors)
 }
 
-fn import_source_kind(
-  source: &str,
+/// `CATEGORIES` is a comma list where a
+/// literal comma is escaped as `\,`.
+fn parse_vcard_categories(
+  value: &str
+) -> Vec<String> {
+  let mut categories = Vec::new();
+  let mut current = String::new();
+  let mut chars = value.chars();
+  while let Some(ch) = chars.next() {
+    match ch {
+      | '\\' => {
+        if let Some(next) = chars.next()
+        {
+          current.push(next);
+        }
+      }
+      | ',' => {
+        categories.push(
+          std::mem::take(&mut current),
+        );
+      }
+      | _ => current.push(ch),
+    }
+  }
+  categories.push(current);
+  categories
+    .into_iter()
+    .map(|category| {
+      category.trim().to_string()
+    })
+    .filter(|category| {
+      !category.is_empty()
+    })
+    .collect()
+}
+
+fn import_source_kind(
+  source: &str,
 ) -> String {
   let token =
     source.trim().to_ascii_lowercase();
@@ -2389,6 +3047,7 @@ fn find_best_match(
   incoming: &ContactDto,
   existing: &[ContactDto],
 ) -> Option<(usize, u32, String)> {
+  let stopwords = name_key_stopwords();
   let mut best: Option<
     (usize, u32, String),
   > = None;
@@ -2396,7 +3055,9 @@ fn find_best_match(
     existing.iter().enumerate()
   {
     if let Some((score, reason)) =
-      score_pair(incoming, item)
+      score_pair(
+        incoming, item, &stopwords,
+      )
       && score >= 60
     {
       let replace = best
@@ -2433,6 +3094,43 @@ pub async fn contacts_list(
   result.map_err(err_to_string)
 }
 
+#[tauri::command]
+#[instrument(fields(request_id = ?request_id, id = %args.id))]
+pub async fn contact_get(
+  args: ContactIdArg,
+  request_id: Option<String>,
+) -> Result<Option<ContactDto>, String> {
+  info!(request_id = ?request_id, id = %args.id, "contact_get command invoked");
+  let result = (|| -> anyhow::Result<
+    Option<ContactDto>,
+  > {
+    let _guard = contacts_lock()
+      .lock()
+      .map_err(|_| {
+        anyhow::anyhow!(
+          "contacts store lock poisoned"
+        )
+      })?;
+    let (contacts_path, ..) =
+      ensure_contacts_store()?;
+    Ok(
+      load_contacts_cached(
+        &contacts_path,
+      )?
+      .into_iter()
+      .find(|contact| {
+        contact.id == args.id
+      }),
+    )
+  })();
+  if let Err(err) =
+    result.as_ref()
+  {
+    error!(request_id = ?request_id, error = %err, "contact_get command failed");
+  }
+  result.map_err(err_to_string)
+}
+
 #[tauri::command]
 #[instrument(fields(request_id = ?request_id, display_name = ?args.display_name, source = ?args.source_kind))]
 pub async fn contact_add(
@@ -2440,6 +3138,10 @@ pub async fn contact_add(
   request_id: Option<String>,
 ) -> Result<ContactDto, String> {
   info!(request_id = ?request_id, "contact_add command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contact_add"
+  )?;
 
   let result = (|| -> anyhow::Result<
     ContactDto,
@@ -2504,6 +3206,10 @@ pub async fn contact_update(
   request_id: Option<String>,
 ) -> Result<ContactDto, String> {
   info!(request_id = ?request_id, id = %args.id, "contact_update command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contact_update"
+  )?;
 
   let result = (|| -> anyhow::Result<
     ContactDto,
@@ -2572,13 +3278,41 @@ pub async fn contact_update(
   result.map_err(err_to_string)
 }
 
+/// Deleting a contact keeps the task
+/// links (the delete may be undone);
+/// the GUI flags them instead.
+fn warn_linked_tasks(
+  state: &AppState,
+  ids: &[Uuid],
+  request_id: Option<&str>,
+) -> usize {
+  match state
+    .open_tasks_linked_to(ids)
+  {
+    | Ok(0) => 0,
+    | Ok(count) => {
+      warn!(request_id = ?request_id, count, "deleted contacts are still linked to open tasks");
+      count
+    }
+    | Err(err) => {
+      warn!(request_id = ?request_id, error = %err, "could not count tasks linked to deleted contacts");
+      0
+    }
+  }
+}
+
 #[tauri::command]
-#[instrument(fields(request_id = ?request_id, id = %args.id))]
+#[instrument(skip(state), fields(request_id = ?request_id, id = %args.id))]
 pub async fn contact_delete(
+  state: State<'_, AppState>,
   args: ContactIdArg,
   request_id: Option<String>,
 ) -> Result<(), String> {
   info!(request_id = ?request_id, id = %args.id, "contact_delete command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsDelete,
+    "contact_delete"
+  )?;
 
   let result = (|| -> anyhow::Result<
     ()
@@ -2634,21 +3368,32 @@ pub async fn contact_delete(
     result.as_ref()
   {
     error!(request_id = ?request_id, error = %err, "contact_delete command failed");
+  } else {
+    warn_linked_tasks(
+      &state,
+      &[args.id],
+      request_id.as_deref(),
+    );
   }
 
   result.map_err(err_to_string)
 }
 
 #[tauri::command]
-#[instrument(fields(request_id = ?request_id, count = args.ids.len()))]
-pub async fn contacts_delete_bulk(
-  args: ContactsDeleteBulkArgs,
+#[instrument(fields(request_id = ?request_id, id = %args.id))]
+pub async fn contacts_unlink(
+  args: ContactIdArg,
   request_id: Option<String>,
-) -> Result<usize, String> {
-  info!(request_id = ?request_id, count = args.ids.len(), "contacts_delete_bulk command invoked");
+) -> Result<ContactsUnlinkResult, String>
+{
+  info!(request_id = ?request_id, id = %args.id, "contacts_unlink command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_unlink"
+  )?;
 
   let result = (|| -> anyhow::Result<
-    usize
+    ContactsUnlinkResult,
   > {
     let _guard = contacts_lock()
       .lock()
@@ -2659,55 +3404,261 @@ pub async fn contacts_delete_bulk(
       })?;
     let (
       contacts_path,
-      deleted_path,
+      _deleted,
       _batches,
       _undo,
     ) = ensure_contacts_store()?;
 
-    let contacts =
+    let mut contacts =
       load_contacts_cached(
         &contacts_path,
       )?;
-    let ids = args
-      .ids
-      .into_iter()
-      .collect::<HashSet<_>>();
+    let changed = unlink_contact(
+      &mut contacts,
+      args.id,
+    )?;
+    save_jsonl(
+      &contacts_path,
+      &contacts,
+    )?;
+    set_contacts_cache(
+      &contacts_path,
+      &contacts,
+    )?;
 
-    let mut kept = Vec::<ContactDto>::new();
-    let mut removed = Vec::<ContactDto>::new();
-    for contact in contacts {
-      if ids.contains(&contact.id) {
-        removed.push(contact);
-      } else {
-        kept.push(contact);
-      }
+    Ok(ContactsUnlinkResult {
+      contacts: changed,
+    })
+  })();
+
+  if let Err(err) =
+    result.as_ref()
+  {
+    error!(request_id = ?request_id, error = %err, "contacts_unlink command failed");
+  }
+
+  result.map_err(err_to_string)
+}
+
+fn delete_contacts_bulk(
+  args: ContactsDeleteBulkArgs,
+) -> anyhow::Result<ContactsDeleteBulkResult> {
+  let _guard = contacts_lock()
+    .lock()
+    .map_err(|_| {
+      anyhow::anyhow!(
+        "contacts store lock poisoned"
+      )
+    })?;
+  let (
+    contacts_path,
+    deleted_path,
+    _batches,
+    _undo,
+  ) = ensure_contacts_store()?;
+
+  let contacts =
+    load_contacts_cached(
+      &contacts_path,
+    )?;
+  let ids = args
+    .ids
+    .into_iter()
+    .collect::<HashSet<_>>();
+
+  let mut kept = Vec::<ContactDto>::new();
+  let mut removed = Vec::<ContactDto>::new();
+  for contact in contacts {
+    if ids.contains(&contact.id) {
+      removed.push(contact);
+    } else {
+      kept.push(contact);
     }
+  }
 
-    for item in &removed {
-      append_jsonl(
-        &deleted_path,
-        item,
+  for item in &removed {
+    append_jsonl(
+      &deleted_path,
+      item,
+    )?;
+  }
+
+  let deleted_count = removed.len();
+  sort_contacts(&mut kept);
+  save_jsonl(
+    &contacts_path,
+    &kept,
+  )?;
+  set_contacts_cache(
+    &contacts_path,
+    &kept,
+  )?;
+
+  let undo_id = if removed.is_empty() {
+    None
+  } else {
+    let undo_id =
+      Uuid::new_v4().to_string();
+    append_jsonl(
+      &contacts_delete_undo_path(
+        &contacts_path,
+      )?,
+      &ContactsDeleteUndoEntry {
+        undo_id: undo_id.clone(),
+        contacts: removed,
+        created_at: now_iso(),
+      },
+    )?;
+    Some(undo_id)
+  };
+
+  Ok(ContactsDeleteBulkResult {
+    deleted: deleted_count,
+    undo_id,
+    linked_tasks: 0,
+  })
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, count = args.ids.len()))]
+pub async fn contacts_delete_bulk(
+  state: State<'_, AppState>,
+  args: ContactsDeleteBulkArgs,
+  request_id: Option<String>,
+) -> Result<
+  ContactsDeleteBulkResult,
+  String,
+> {
+  info!(request_id = ?request_id, count = args.ids.len(), "contacts_delete_bulk command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsDelete,
+    "contacts_delete_bulk"
+  )?;
+  let requested_ids = args.ids.clone();
+
+  let mut result =
+    delete_contacts_bulk(args);
+
+  if let Err(err) =
+    result.as_ref()
+  {
+    error!(request_id = ?request_id, error = %err, "contacts_delete_bulk command failed");
+  }
+  if let Ok(deleted) = result.as_mut()
+  {
+    deleted.linked_tasks =
+      warn_linked_tasks(
+        &state,
+        &requested_ids,
+        request_id.as_deref(),
+      );
+  }
+
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(fields(request_id = ?request_id, undo_id = ?args.undo_id))]
+pub async fn contacts_delete_undo(
+  args: ContactsDeleteUndoArgs,
+  request_id: Option<String>,
+) -> Result<
+  ContactsDeleteUndoResult,
+  String,
+> {
+  info!(request_id = ?request_id, undo_id = ?args.undo_id, "contacts_delete_undo command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_delete_undo"
+  )?;
+
+  let result = (|| -> anyhow::Result<
+    ContactsDeleteUndoResult,
+  > {
+    let _guard = contacts_lock()
+      .lock()
+      .map_err(|_| {
+        anyhow::anyhow!(
+          "contacts store lock poisoned"
+        )
+      })?;
+
+    let (contacts_path, ..) =
+      ensure_contacts_store()?;
+    let undo_path =
+      contacts_delete_undo_path(
+        &contacts_path,
+      )?;
+
+    let mut entries = load_jsonl::<
+      ContactsDeleteUndoEntry,
+    >(&undo_path)?;
+
+    let selected_index = if let Some(
+      requested,
+    ) = args.undo_id.as_deref()
+    {
+      entries
+        .iter()
+        .position(|entry| {
+          entry.undo_id
+            == requested
+        })
+    } else if entries.is_empty() {
+      None
+    } else {
+      Some(entries.len() - 1)
+    };
+
+    let Some(index) = selected_index
+    else {
+      anyhow::bail!(
+        "no delete undo snapshot \
+         available"
+      );
+    };
+
+    let entry = entries.remove(index);
+    let mut contacts =
+      load_contacts_cached(
+        &contacts_path,
       )?;
+    // Contacts edited back into the
+    // store since the delete win over
+    // their snapshot.
+    let present = contacts
+      .iter()
+      .map(|contact| contact.id)
+      .collect::<HashSet<_>>();
+    let mut restored = 0usize;
+    for contact in entry.contacts {
+      if !present.contains(&contact.id) {
+        contacts.push(contact);
+        restored += 1;
+      }
     }
+    sort_contacts(&mut contacts);
 
-    let deleted_count = removed.len();
-    sort_contacts(&mut kept);
     save_jsonl(
       &contacts_path,
-      &kept,
+      &contacts,
     )?;
     set_contacts_cache(
       &contacts_path,
-      &kept,
+      &contacts,
     )?;
+    save_jsonl(&undo_path, &entries)?;
 
-    Ok(deleted_count)
+    Ok(ContactsDeleteUndoResult {
+      restored,
+      undo_id: entry.undo_id,
+    })
   })();
 
   if let Err(err) =
     result.as_ref()
   {
-    error!(request_id = ?request_id, error = %err, "contacts_delete_bulk command failed");
+    error!(request_id = ?request_id, error = %err, "contacts_delete_undo command failed");
   }
 
   result.map_err(err_to_string)
@@ -2800,6 +3751,10 @@ pub async fn contacts_dedupe_decide(
   String,
 > {
   info!(request_id = ?request_id, group_id = %args.candidate_group_id, decision = %args.decision, "contacts_dedupe_decide command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_dedupe_decide"
+  )?;
 
   let result = (|| -> anyhow::Result<
     ContactsDedupeDecideResult,
@@ -2911,7 +3866,8 @@ pub async fn contact_open_action(
       .action
       .trim()
       .to_ascii_lowercase();
-    let url = if action == "mailto"
+    let (kind, value) = if action
+      == "mailto"
       || action == "email"
     {
       let value = args
@@ -2940,7 +3896,7 @@ pub async fn contact_open_action(
             "contact has no email"
           )
         })?;
-      format!("mailto:{value}")
+      ("email", value)
     } else if action == "tel"
       || action == "phone"
     {
@@ -2970,16 +3926,48 @@ pub async fn contact_open_action(
             "contact has no phone"
           )
         })?;
-      format!("tel:{value}")
+      ("phone", value)
     } else {
       anyhow::bail!(
         "unsupported action: {action}"
       );
     };
+    let url = if kind == "email" {
+      format!("mailto:{value}")
+    } else {
+      format!("tel:{value}")
+    };
+
+    // Opening the link is a read, but
+    // recording it writes to the
+    // contacts store; with writes off
+    // the link still opens unlogged.
+    let logged =
+      interaction_logging_enabled()
+        && ensure_permitted(
+          CommandCategory::ContactsWrite,
+          "contact_open_action",
+        )
+        .is_ok();
+    if logged {
+      append_jsonl(
+        &contacts_interactions_path(
+          &contacts_path,
+        )?,
+        &ContactInteraction {
+          id: Uuid::new_v4(),
+          contact_id: contact.id,
+          kind: kind.to_string(),
+          value,
+          at: now_iso(),
+        },
+      )?;
+    }
 
     Ok(ContactOpenActionResult {
       launched: false,
       url,
+      logged,
     })
   })();
 
@@ -2992,6 +3980,153 @@ pub async fn contact_open_action(
   result.map_err(err_to_string)
 }
 
+#[tauri::command]
+#[instrument(fields(request_id = ?request_id, contact_id = ?args.contact_id))]
+pub async fn contacts_interactions(
+  args: ContactInteractionsArgs,
+  request_id: Option<String>,
+) -> Result<ContactInteractionsResult, String>
+{
+  info!(request_id = ?request_id, contact_id = ?args.contact_id, "contacts_interactions command invoked");
+
+  let result = (|| -> anyhow::Result<
+    ContactInteractionsResult,
+  > {
+    let _guard = contacts_lock()
+      .lock()
+      .map_err(|_| {
+        anyhow::anyhow!(
+          "contacts store lock poisoned"
+        )
+      })?;
+    let (
+      contacts_path,
+      _deleted,
+      _batches,
+      _undo,
+    ) = ensure_contacts_store()?;
+
+    let mut interactions =
+      load_jsonl::<ContactInteraction>(
+        &contacts_interactions_path(
+          &contacts_path,
+        )?,
+      )?;
+    interactions.retain(|interaction| {
+      args.contact_id.is_none_or(|id| {
+        interaction.contact_id == id
+      })
+    });
+    interactions.sort_by_key(
+      |interaction| {
+        std::cmp::Reverse(
+          parse_interaction_time(
+            &interaction.at,
+          ),
+        )
+      },
+    );
+
+    Ok(ContactInteractionsResult {
+      interactions,
+    })
+  })();
+
+  if let Err(err) =
+    result.as_ref()
+  {
+    error!(request_id = ?request_id, error = %err, "contacts_interactions command failed");
+  }
+
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(fields(request_id = ?request_id, older_than_days = ?args.older_than_days, contact_id = ?args.contact_id))]
+pub async fn contacts_interactions_prune(
+  args: ContactInteractionsPruneArgs,
+  request_id: Option<String>,
+) -> Result<
+  ContactInteractionsPruneResult,
+  String,
+> {
+  info!(request_id = ?request_id, older_than_days = ?args.older_than_days, contact_id = ?args.contact_id, "contacts_interactions_prune command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_interactions_prune"
+  )?;
+
+  let result = (|| -> anyhow::Result<
+    ContactInteractionsPruneResult,
+  > {
+    let _guard = contacts_lock()
+      .lock()
+      .map_err(|_| {
+        anyhow::anyhow!(
+          "contacts store lock poisoned"
+        )
+      })?;
+    let (
+      contacts_path,
+      _deleted,
+      _batches,
+      _undo,
+    ) = ensure_contacts_store()?;
+
+    let path =
+      contacts_interactions_path(
+        &contacts_path,
+      )?;
+    let interactions =
+      load_jsonl::<ContactInteraction>(
+        &path,
+      )?;
+    let cutoff =
+      args.older_than_days.map(|days| {
+        Utc::now()
+          - chrono::Duration::days(
+            i64::from(days),
+          )
+      });
+    let before = interactions.len();
+    let kept = interactions
+      .into_iter()
+      .filter(|interaction| {
+        let selected = args
+          .contact_id
+          .is_none_or(|id| {
+            interaction.contact_id == id
+          })
+          && cutoff.is_none_or(
+            |cutoff| {
+              parse_interaction_time(
+                &interaction.at,
+              )
+              .is_none_or(|at| {
+                at < cutoff
+              })
+            },
+          );
+        !selected
+      })
+      .collect::<Vec<_>>();
+    save_jsonl(&path, &kept)?;
+
+    Ok(ContactInteractionsPruneResult {
+      removed:   before - kept.len(),
+      remaining: kept.len(),
+    })
+  })();
+
+  if let Err(err) =
+    result.as_ref()
+  {
+    error!(request_id = ?request_id, error = %err, "contacts_interactions_prune command failed");
+  }
+
+  result.map_err(err_to_string)
+}
+
 #[tauri::command]
 #[instrument(fields(request_id = ?request_id, source = %args.source, file_name = ?args.file_name))]
 pub async fn contacts_import_preview(
@@ -3039,7 +4174,9 @@ pub async fn contacts_import_preview(
 
     let mut conflicts =
       Vec::<ContactImportConflict>::new();
-    for contact in &mut imported {
+    for (row, contact) in
+      imported.iter_mut().enumerate()
+    {
       contact.source_kind =
         source_kind.clone();
       contact.source_id = format!(
@@ -3059,6 +4196,7 @@ pub async fn contacts_import_preview(
         if score >= 80 {
           conflicts.push(
             ContactImportConflict {
+              row,
               imported:
                 contact.clone(),
               existing:
@@ -3108,6 +4246,10 @@ pub async fn contacts_import_commit(
   String,
 > {
   info!(request_id = ?request_id, source = %args.source, mode = %args.mode, "contacts_import_commit command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_import_commit"
+  )?;
 
   let result = (|| -> anyhow::Result<
     ContactsImportCommitResult,
@@ -3148,13 +4290,29 @@ pub async fn contacts_import_commit(
       .mode
       .trim()
       .to_ascii_lowercase();
+    let decisions = args
+      .decisions
+      .iter()
+      .map(|entry| {
+        (
+          entry.row,
+          entry
+            .decision
+            .trim()
+            .to_ascii_lowercase(),
+        )
+      })
+      .collect::<HashMap<_, _>>();
 
     let mut created = 0_usize;
     let mut updated = 0_usize;
     let mut skipped = 0_usize;
+    let mut linked = 0_usize;
     let mut conflicts = 0_usize;
 
-    for mut incoming in imported {
+    for (row, mut incoming) in
+      imported.into_iter().enumerate()
+    {
       incoming.source_kind =
         source_kind.clone();
       incoming.source_id = format!(
@@ -3174,24 +4332,55 @@ pub async fn contacts_import_commit(
       {
         if score >= 80 {
           conflicts += 1;
-          if mode == "upsert" {
-            merge_contact_records(
-              &mut existing[index],
-              &incoming,
-            );
-            ensure_contact_defaults(
-              &mut existing[index],
+          let decision = decisions
+            .get(&row)
+            .map(String::as_str)
+            .unwrap_or(
+              if mode == "upsert" {
+                "merge"
+              } else {
+                "skip"
+              },
             );
-            validate_contact(
-              &existing[index],
-            )?;
-            updated += 1;
-          } else if mode == "safe"
-            || mode == "review"
-          {
-            skipped += 1;
-          } else {
-            skipped += 1;
+          match decision {
+            | "merge" => {
+              merge_contact_records(
+                &mut existing[index],
+                &incoming,
+              );
+              ensure_contact_defaults(
+                &mut existing[index],
+              );
+              validate_contact(
+                &existing[index],
+              )?;
+              updated += 1;
+            }
+            | "link" => {
+              link_contacts(
+                &mut existing[index],
+                &mut incoming,
+              );
+              ensure_contact_defaults(
+                &mut incoming,
+              );
+              validate_contact(
+                &incoming,
+              )?;
+              existing.push(incoming);
+              linked += 1;
+            }
+            | "skip" => {
+              skipped += 1;
+            }
+            | other => {
+              anyhow::bail!(
+                "unknown import decision \
+                 '{other}' for row \
+                 {row}; use merge, skip \
+                 or link"
+              );
+            }
           }
           continue;
         }
@@ -3225,9 +4414,12 @@ pub async fn contacts_import_commit(
         imported_at: now_iso(),
         total_rows: created
           + updated
+          + linked
           + skipped
           + errors.len(),
-        valid_rows: created + updated,
+        valid_rows: created
+          + updated
+          + linked,
         skipped_rows: skipped
           + errors.len(),
       },
@@ -3238,6 +4430,7 @@ pub async fn contacts_import_commit(
       created,
       updated,
       skipped,
+      linked,
       failed: errors.len(),
       conflicts,
       errors,
@@ -3253,222 +4446,227 @@ pub async fn contacts_import_commit(
   result.map_err(err_to_string)
 }
 
-#[tauri::command]
-#[instrument(fields(request_id = ?request_id, count = args.ids.len(), target = ?args.target_id))]
-pub async fn contacts_merge(
+fn merge_contacts(
   args: ContactsMergeArgs,
-  request_id: Option<String>,
-) -> Result<ContactsMergeResult, String> {
-  info!(request_id = ?request_id, count = args.ids.len(), target = ?args.target_id, "contacts_merge command invoked");
+  request_id: Option<&str>,
+) -> anyhow::Result<ContactsMergeResult> {
+  let _guard = contacts_lock()
+    .lock()
+    .map_err(|_| {
+      anyhow::anyhow!(
+        "contacts store lock poisoned"
+      )
+    })?;
 
-  let result = (|| -> anyhow::Result<
-    ContactsMergeResult,
-  > {
-    if args.ids.len() < 2 {
-      anyhow::bail!(
-        "merge requires at least \
-         two contacts"
-      );
-    }
+  let (
+    contacts_path,
+    deleted_path,
+    _batches,
+    undo_path,
+  ) = ensure_contacts_store()?;
 
-    let _guard = contacts_lock()
-      .lock()
-      .map_err(|_| {
-        anyhow::anyhow!(
-          "contacts store lock poisoned"
-        )
-      })?;
+  let mut contacts =
+    load_contacts_cached(
+      &contacts_path,
+    )?;
 
-    let (
-      contacts_path,
-      deleted_path,
-      _batches,
-      undo_path,
-    ) = ensure_contacts_store()?;
+  let before = contacts.clone();
+  let ids = args
+    .ids
+    .into_iter()
+    .collect::<BTreeSet<_>>();
+  let ContactMergePlan {
+    merged,
+    removed_ids,
+    removed_contacts,
+  } = plan_contact_merge(
+    &contacts,
+    &ids,
+    args.target_id,
+  )?;
 
-    let mut contacts =
-      load_contacts_cached(
-        &contacts_path,
-      )?;
+  contacts.retain(|contact| {
+    !ids.contains(&contact.id)
+      || contact.id == merged.id
+  });
 
-    let before = contacts.clone();
-    let ids = args
-      .ids
-      .into_iter()
-      .collect::<BTreeSet<_>>();
+  if let Some(slot) = contacts
+    .iter_mut()
+    .find(|contact| {
+      contact.id == merged.id
+    })
+  {
+    *slot = merged.clone();
+  } else {
+    contacts.push(merged.clone());
+  }
+  sort_contacts(&mut contacts);
 
-    let selected = contacts
-      .iter()
-      .filter(|contact| {
-        ids.contains(&contact.id)
-      })
-      .cloned()
-      .collect::<Vec<_>>();
+  save_jsonl(
+    &contacts_path,
+    &contacts,
+  )?;
+  set_contacts_cache(
+    &contacts_path,
+    &contacts,
+  )?;
+  for removed in &removed_contacts {
+    append_jsonl(
+      &deleted_path,
+      removed,
+    )?;
+  }
 
-    if selected.len() < 2 {
-      anyhow::bail!(
-        "failed to find selected \
-         contacts"
-      );
-    }
+  let undo_id =
+    Uuid::new_v4().to_string();
+  append_jsonl(
+    &undo_path,
+    &ContactsMergeUndoEntry {
+      undo_id: undo_id.clone(),
+      contacts_before: before,
+      created_at: now_iso(),
+    },
+  )?;
+  let merge_audit_path =
+    contacts_merge_audit_path(
+      &contacts_path,
+    )?;
+  append_jsonl(
+    &merge_audit_path,
+    &MergeAudit {
+      undo_id: undo_id.clone(),
+      target_contact_id: merged.id,
+      source_contact_ids:
+        removed_ids.clone(),
+      merge_payload:
+        merged.clone(),
+      operator:
+        request_id
+          .unwrap_or("user")
+          .to_string(),
+      created_at: now_iso(),
+    },
+  )?;
+  let mut decision_group_ids =
+    ids.iter()
+      .map(|id| id.to_string())
+      .collect::<Vec<_>>();
+  decision_group_ids.sort();
+  let decisions_path =
+    contacts_dedupe_decisions_path(
+      &contacts_path,
+    )?;
+  append_jsonl(
+    &decisions_path,
+    &DedupDecision {
+      candidate_group_id: format!(
+        "group:{}",
+        decision_group_ids.join(",")
+      ),
+      decision: "merged".to_string(),
+      actor:
+        request_id
+          .unwrap_or("user")
+          .to_string(),
+      decided_at: now_iso(),
+    },
+  )?;
 
-    let target_id = args
-      .target_id
-      .or_else(|| {
-        selected
-          .iter()
-          .max_by(|left, right| {
-            left.updated_at.cmp(
-              &right.updated_at,
-            )
-          })
-          .map(|contact| {
-            contact.id
-          })
-      })
-      .ok_or_else(|| {
-        anyhow::anyhow!(
-          "invalid merge target"
-        )
-      })?;
+  Ok(ContactsMergeResult {
+    merged,
+    removed_ids,
+    undo_id,
+    relinked_tasks: 0,
+  })
+}
 
-    let mut merged = selected
-      .iter()
-      .find(|contact| {
-        contact.id == target_id
-      })
-      .cloned()
-      .unwrap_or_else(|| {
-        selected[0].clone()
-      });
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, count = args.ids.len(), target = ?args.target_id))]
+pub async fn contacts_merge(
+  state: State<'_, AppState>,
+  args: ContactsMergeArgs,
+  request_id: Option<String>,
+) -> Result<ContactsMergeResult, String> {
+  info!(request_id = ?request_id, count = args.ids.len(), target = ?args.target_id, "contacts_merge command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_merge"
+  )?;
 
-    let removed_ids = selected
-      .iter()
-      .map(|item| item.id)
-      .filter(|id| {
-        *id != merged.id
-      })
-      .collect::<Vec<_>>();
-    let removed_contacts = selected
-      .iter()
-      .filter(|item| {
-        item.id != merged.id
-      })
-      .cloned()
-      .collect::<Vec<_>>();
+  let mut result = merge_contacts(
+    args,
+    request_id.as_deref(),
+  );
 
-    for contact in selected {
-      if contact.id == merged.id {
-        continue;
+  if let Err(err) =
+    result.as_ref()
+  {
+    error!(request_id = ?request_id, error = %err, "contacts_merge command failed");
+  }
+  // The contacts write already landed,
+  // so a relink failure only leaves
+  // tasks pointing at a removed id.
+  if let Ok(merge) = result.as_mut() {
+    match state.relink_contacts(
+      &merge.removed_ids,
+      merge.merged.id,
+    ) {
+      | Ok(count) => {
+        merge.relinked_tasks = count;
+      }
+      | Err(err) => {
+        warn!(request_id = ?request_id, error = %err, "contacts_merge could not relink tasks");
       }
-      merge_contact_records(
-        &mut merged,
-        &contact,
-      );
     }
-    ensure_contact_defaults(
-      &mut merged,
-    );
-    validate_contact(&merged)?;
-
-    contacts.retain(|contact| {
-      !ids.contains(&contact.id)
-        || contact.id == merged.id
-    });
+  }
 
-    if let Some(slot) = contacts
-      .iter_mut()
-      .find(|contact| {
-        contact.id == merged.id
-      })
-    {
-      *slot = merged.clone();
-    } else {
-      contacts.push(merged.clone());
-    }
-    sort_contacts(&mut contacts);
+  result.map_err(err_to_string)
+}
 
-    save_jsonl(
-      &contacts_path,
-      &contacts,
-    )?;
-    set_contacts_cache(
-      &contacts_path,
-      &contacts,
-    )?;
-    for removed in &removed_contacts {
-      append_jsonl(
-        &deleted_path,
-        removed,
-      )?;
-    }
+#[tauri::command]
+#[instrument(fields(request_id = ?request_id, count = args.ids.len(), target = ?args.target_id))]
+pub async fn contacts_merge_preview(
+  args: ContactsMergeArgs,
+  request_id: Option<String>,
+) -> Result<ContactsMergePreviewResult, String> {
+  info!(request_id = ?request_id, count = args.ids.len(), target = ?args.target_id, "contacts_merge_preview command invoked");
 
-    let undo_id =
-      Uuid::new_v4().to_string();
-    append_jsonl(
-      &undo_path,
-      &ContactsMergeUndoEntry {
-        undo_id: undo_id.clone(),
-        contacts_before: before,
-        created_at: now_iso(),
-      },
-    )?;
-    let merge_audit_path =
-      contacts_merge_audit_path(
-        &contacts_path,
-      )?;
-    append_jsonl(
-      &merge_audit_path,
-      &MergeAudit {
-        undo_id: undo_id.clone(),
-        target_contact_id: merged.id,
-        source_contact_ids:
-          removed_ids.clone(),
-        merge_payload:
-          merged.clone(),
-        operator:
-          request_id.clone().unwrap_or_else(
-            || "user".to_string(),
-          ),
-        created_at: now_iso(),
-      },
-    )?;
-    let mut decision_group_ids =
-      ids.iter()
-        .map(|id| id.to_string())
-        .collect::<Vec<_>>();
-    decision_group_ids.sort();
-    let decisions_path =
-      contacts_dedupe_decisions_path(
+  let result = (|| -> anyhow::Result<
+    ContactsMergePreviewResult,
+  > {
+    let _guard = contacts_lock()
+      .lock()
+      .map_err(|_| {
+        anyhow::anyhow!(
+          "contacts store lock poisoned"
+        )
+      })?;
+
+    let (contacts_path, ..) =
+      ensure_contacts_store()?;
+    let contacts =
+      load_contacts_cached(
         &contacts_path,
       )?;
-    append_jsonl(
-      &decisions_path,
-      &DedupDecision {
-        candidate_group_id: format!(
-          "group:{}",
-          decision_group_ids.join(",")
-        ),
-        decision: "merged".to_string(),
-        actor:
-          request_id.clone().unwrap_or_else(
-            || "user".to_string(),
-          ),
-        decided_at: now_iso(),
-      },
+    let ids = args
+      .ids
+      .into_iter()
+      .collect::<BTreeSet<_>>();
+    let plan = plan_contact_merge(
+      &contacts,
+      &ids,
+      args.target_id,
     )?;
-
-    Ok(ContactsMergeResult {
-      merged,
-      removed_ids,
-      undo_id,
+    Ok(ContactsMergePreviewResult {
+      merged:      plan.merged,
+      removed_ids: plan.removed_ids,
     })
   })();
 
   if let Err(err) =
     result.as_ref()
   {
-    error!(request_id = ?request_id, error = %err, "contacts_merge command failed");
+    error!(request_id = ?request_id, error = %err, "contacts_merge_preview command failed");
   }
 
   result.map_err(err_to_string)
@@ -3484,6 +4682,10 @@ pub async fn contacts_merge_undo(
   String,
 > {
   info!(request_id = ?request_id, undo_id = ?args.undo_id, "contacts_merge_undo command invoked");
+  ensure_permitted(
+    CommandCategory::ContactsWrite,
+    "contacts_merge_undo"
+  )?;
 
   let result = (|| -> anyhow::Result<
     ContactsMergeUndoResult,
@@ -3610,6 +4812,7 @@ pub async fn contacts_merge_undo(
 #[cfg(test)]
 mod tests {
   use super::*;
+  use rivet_gui_shared::ContactImportDecision;
   use std::future::Future;
   use std::sync::{
     Mutex,
@@ -3665,6 +4868,7 @@ mod tests {
           Some("local".to_string()),
         remote_id: None,
         link_group_id: None,
+        categories: Vec::new(),
       },
       None,
       None,
@@ -3708,6 +4912,7 @@ mod tests {
         Some("local".to_string()),
       remote_id: None,
       link_group_id: None,
+      categories: Vec::new(),
     }
   }
 
@@ -3885,7 +5090,11 @@ mod tests {
       None,
     );
     let score =
-      score_pair(&left, &right)
+      score_pair(
+        &left,
+        &right,
+        &HashSet::new(),
+      )
         .expect("pair score")
         .0;
     assert_eq!(score, 100);
@@ -4148,17 +5357,15 @@ mod tests {
         1
       );
 
-      let merge = run_async(
-        contacts_merge(
-          ContactsMergeArgs {
-            ids: vec![
-              left.id,
-              right.id,
-            ],
-            target_id: Some(left.id),
-          },
-          Some("tester".to_string()),
-        ),
+      let merge = merge_contacts(
+        ContactsMergeArgs {
+          ids: vec![
+            left.id,
+            right.id,
+          ],
+          target_id: Some(left.id),
+        },
+        Some("tester"),
       )
       .expect("merge");
       assert_eq!(merge.merged.id, left.id);
@@ -4258,6 +5465,9 @@ mod tests {
             cursor: None,
             source: None,
             updated_after: None,
+            category: None,
+            sort: None,
+            contacted_within_days: None,
           },
           None,
         ),
@@ -4308,6 +5518,211 @@ mod tests {
     });
   }
 
+  #[test]
+  fn bulk_delete_undo_restores_fields_and_links()
+  {
+    with_temp_contacts_dir(|| {
+      let mut create = make_contact_create(
+        "Avery Stone",
+        "avery@example.com",
+        "+1 555 0110",
+      );
+      create.notes =
+        Some("met at the expo".to_string());
+      create.link_group_id =
+        Some("group-avery".to_string());
+      let linked = run_async(
+        contact_add(create, None),
+      )
+      .expect("add linked");
+      let other = run_async(
+        contact_add(
+          make_contact_create(
+            "Blair Holt",
+            "blair@example.com",
+            "+1 555 0120",
+          ),
+          None,
+        ),
+      )
+      .expect("add other");
+
+      let deleted = delete_contacts_bulk(
+        ContactsDeleteBulkArgs {
+          ids: vec![
+            linked.id, other.id,
+          ],
+        },
+      )
+      .expect("bulk delete");
+      assert_eq!(deleted.deleted, 2);
+      let undo_id = deleted
+        .undo_id
+        .expect("undo id");
+
+      let undo = run_async(
+        contacts_delete_undo(
+          ContactsDeleteUndoArgs {
+            undo_id: Some(
+              undo_id.clone(),
+            ),
+          },
+          None,
+        ),
+      )
+      .expect("delete undo");
+      assert_eq!(undo.restored, 2);
+      assert_eq!(undo.undo_id, undo_id);
+
+      let (contacts_path, ..) =
+        ensure_contacts_store()
+          .expect("store");
+      let contacts = load_jsonl::<
+        ContactDto,
+      >(&contacts_path)
+      .expect("load contacts");
+      let restored = contacts
+        .iter()
+        .find(|contact| {
+          contact.id == linked.id
+        })
+        .expect("restored contact");
+      assert_eq!(
+        serde_json::to_value(restored)
+          .expect("restored json"),
+        serde_json::to_value(&linked)
+          .expect("linked json")
+      );
+
+      assert!(
+        run_async(contacts_delete_undo(
+          ContactsDeleteUndoArgs {
+            undo_id: Some(undo_id),
+          },
+          None,
+        ))
+        .is_err()
+      );
+    });
+  }
+
+  #[test]
+  fn import_link_decision_keeps_both_and_unlink_reverts()
+  {
+    with_temp_contacts_dir(|| {
+      let existing = run_async(
+        contact_add(
+          make_contact_create(
+            "Avery Stone",
+            "avery@example.com",
+            "+1 555 0110",
+          ),
+          None,
+        ),
+      )
+      .expect("add existing");
+      let content = [
+        "BEGIN:VCARD",
+        "VERSION:3.0",
+        "FN:Avery Stone",
+        "EMAIL:avery@example.com",
+        "END:VCARD",
+        "",
+      ]
+      .join("\n");
+
+      let preview = run_async(
+        contacts_import_preview(
+          ContactsImportPreviewArgs {
+            source: "generic_vcard"
+              .to_string(),
+            file_name: None,
+            content: content.clone(),
+          },
+          None,
+        ),
+      )
+      .expect("preview");
+      assert_eq!(
+        preview.conflicts.len(),
+        1
+      );
+      let row = preview.conflicts[0].row;
+
+      let result = run_async(
+        contacts_import_commit(
+          ContactsImportCommitArgs {
+            source: "generic_vcard"
+              .to_string(),
+            file_name: None,
+            content,
+            mode: "safe".to_string(),
+            decisions: vec![
+              ContactImportDecision {
+                row,
+                decision: "Link"
+                  .to_string(),
+              },
+            ],
+          },
+          None,
+        ),
+      )
+      .expect("commit");
+      assert_eq!(result.linked, 1);
+      assert_eq!(result.created, 0);
+      assert_eq!(result.skipped, 0);
+
+      let (contacts_path, ..) =
+        ensure_contacts_store()
+          .expect("store");
+      let contacts = load_jsonl::<
+        ContactDto,
+      >(&contacts_path)
+      .expect("load contacts");
+      assert_eq!(contacts.len(), 2);
+      let group = contacts[0]
+        .link_group_id
+        .clone()
+        .expect("linked");
+      assert_eq!(
+        contacts[1].link_group_id,
+        Some(group)
+      );
+
+      let imported = contacts
+        .iter()
+        .find(|contact| {
+          contact.id != existing.id
+        })
+        .expect("imported contact");
+      let unlinked = run_async(
+        contacts_unlink(
+          ContactIdArg {
+            id: imported.id,
+          },
+          None,
+        ),
+      )
+      .expect("unlink");
+      assert_eq!(
+        unlinked.contacts.len(),
+        2
+      );
+      let contacts = load_jsonl::<
+        ContactDto,
+      >(&contacts_path)
+      .expect("reload contacts");
+      assert!(contacts.iter().all(
+        |contact| {
+          contact
+            .link_group_id
+            .is_none()
+        }
+      ));
+    });
+  }
+
   #[test]
   fn parse_vcard_preserves_primary_flags()
   {
@@ -4344,6 +5759,212 @@ mod tests {
     );
   }
 
+  #[test]
+  fn parse_vcard_maps_categories()
+  {
+    let payload = concat!(
+      "BEGIN:VCARD\n",
+      "VERSION:3.0\n",
+      "FN:Example Vendor\n",
+      "EMAIL:sales@example.com\n",
+      "CATEGORIES:Vendors, Work,work\n",
+      "CATEGORIES:Parts\\, Tools\n",
+      "END:VCARD\n"
+    );
+    let (contacts, errors) =
+      parse_vcard_contacts(
+        payload,
+        "generic_vcard",
+      );
+    assert!(errors.is_empty());
+    assert_eq!(
+      contacts[0].categories,
+      vec![
+        "Vendors".to_string(),
+        "Work".to_string(),
+        "Parts, Tools".to_string(),
+      ]
+    );
+  }
+
+  #[test]
+  fn contacts_list_filters_and_counts_categories()
+  {
+    with_temp_contacts_dir(|| {
+      for (name, email, categories) in [
+        (
+          "Avery Stone",
+          "avery@example.com",
+          vec!["Work", "Friends"],
+        ),
+        (
+          "Blair Holt",
+          "blair@example.com",
+          vec!["work"],
+        ),
+        (
+          "Casey Lin",
+          "casey@example.com",
+          Vec::new(),
+        ),
+      ] {
+        let mut create =
+          make_contact_create(
+            name, email, "",
+          );
+        create.categories = categories
+          .into_iter()
+          .map(str::to_string)
+          .collect();
+        run_async(contact_add(
+          create, None,
+        ))
+        .expect("add contact");
+      }
+
+      let listed = run_async(
+        contacts_list(
+          ContactsListArgs {
+            query: None,
+            limit: None,
+            cursor: None,
+            source: None,
+            updated_after: None,
+            category: Some(
+              "WORK".to_string(),
+            ),
+            sort: None,
+            contacted_within_days: None,
+          },
+          None,
+        ),
+      )
+      .expect("contacts list");
+      assert_eq!(listed.total, 2);
+      assert_eq!(
+        listed.categories,
+        vec![
+          ContactCategoryCount {
+            category: "Work"
+              .to_string(),
+            count:    2,
+          },
+          ContactCategoryCount {
+            category: "Friends"
+              .to_string(),
+            count:    1,
+          },
+        ]
+      );
+    });
+  }
+
+  #[test]
+  fn open_actions_log_interactions_for_recency_sort()
+  {
+    with_temp_contacts_dir(|| {
+      let mut ids = Vec::new();
+      for (name, email) in [
+        ("Avery Stone", "avery@example.com"),
+        ("Blair Holt", "blair@example.com"),
+      ] {
+        let contact = run_async(
+          contact_add(
+            make_contact_create(
+              name, email, "",
+            ),
+            None,
+          ),
+        )
+        .expect("add contact");
+        ids.push(contact.id);
+      }
+
+      let opened = run_async(
+        contact_open_action(
+          ContactOpenActionArgs {
+            id:     ids[1],
+            action: "email".to_string(),
+            value:  None,
+          },
+          None,
+        ),
+      )
+      .expect("open action");
+      assert_eq!(
+        opened.url,
+        "mailto:blair@example.com"
+      );
+
+      let list = |sort: &str,
+                  within: Option<u32>| {
+        run_async(contacts_list(
+          ContactsListArgs {
+            query: None,
+            limit: None,
+            cursor: None,
+            source: None,
+            updated_after: None,
+            category: None,
+            sort: Some(sort.to_string()),
+            contacted_within_days: within,
+          },
+          None,
+        ))
+        .expect("contacts list")
+      };
+      if opened.logged {
+        let recent = list("recent", None);
+        assert_eq!(
+          recent
+            .contacts
+            .iter()
+            .map(|contact| contact.id)
+            .collect::<Vec<_>>(),
+          vec![ids[1], ids[0]]
+        );
+        assert!(
+          recent.contacts[0]
+            .last_contacted
+            .is_some()
+        );
+        assert_eq!(
+          list("name", Some(7)).total,
+          1
+        );
+
+        let pruned = run_async(
+          contacts_interactions_prune(
+            ContactInteractionsPruneArgs {
+              older_than_days: None,
+              contact_id:      Some(
+                ids[1],
+              ),
+            },
+            None,
+          ),
+        )
+        .expect("prune");
+        assert_eq!(pruned.removed, 1);
+      }
+      assert!(
+        run_async(contacts_interactions(
+          ContactInteractionsArgs {
+            contact_id: None,
+          },
+          None,
+        ))
+        .expect("interactions")
+        .interactions
+        .is_empty()
+      );
+      assert_eq!(
+        list("name", Some(7)).total,
+        0
+      );
+    });
+  }
+
   #[test]
   fn parses_gmail_fixture_with_expected_labels()
   {
@@ -4389,4 +6010,80 @@ mod tests {
       )
     );
   }
+
+  #[test]
+  fn name_stopwords_fold_company_forms_and_honorifics()
+  {
+    let payload = include_str!(
+      "fixtures/contacts_org_stopwords.vcf"
+    );
+    let (contacts, errors) =
+      parse_vcard_contacts(
+        payload,
+        "generic_vcard",
+      );
+    assert!(errors.is_empty());
+    assert_eq!(contacts.len(), 4);
+
+    let stopwords = [
+      "Dr.", "Jr", "Inc.", "LLC",
+      "corp",
+    ]
+    .into_iter()
+    .map(stopword_token)
+    .collect::<HashSet<_>>();
+    let keys = contacts
+      .iter()
+      .map(|contact| {
+        contact_name_key_with(
+          contact, &stopwords,
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      keys,
+      vec![
+        "acme",
+        "acme",
+        "jane smith",
+        "jane smith"
+      ]
+    );
+    assert_eq!(
+      score_pair(
+        &contacts[0],
+        &contacts[1],
+        &stopwords,
+      )
+      .map(|(score, _)| score),
+      Some(70)
+    );
+    assert_eq!(
+      score_pair(
+        &contacts[2],
+        &contacts[3],
+        &stopwords,
+      )
+      .map(|(score, _)| score),
+      Some(70)
+    );
+
+    let unfiltered = HashSet::new();
+    assert_ne!(
+      contact_name_key_with(
+        &contacts[0],
+        &unfiltered,
+      ),
+      contact_name_key_with(
+        &contacts[1],
+        &unfiltered,
+      )
+    );
+    assert_eq!(
+      strip_name_stopwords(
+        "inc", &stopwords,
+      ),
+      "inc"
+    );
+  }
 }
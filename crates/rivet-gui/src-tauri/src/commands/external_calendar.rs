@@ -41,6 +41,13 @@ pub struct ExternalCalendarImportCachedArg {
   pub cache_id: String
 }
 
+#[derive(
+  Debug, Clone, Serialize, Deserialize,
+)]
+pub struct CalendarParseDateArg {
+  pub input: String
+}
+
 #[derive(Debug, Clone)]
 struct ExternalCalendarEvent {
   uid:         String,
@@ -75,9 +82,14 @@ pub struct ExternalCalendarCacheEntry {
 }
 
 #[tauri::command]
-#[instrument(skip(state), fields(request_id = ?request_id, calendar_id = %args.id, name = %args.name, enabled = args.enabled))]
+#[instrument(skip(app, state, scheduler), fields(request_id = ?request_id, calendar_id = %args.id, name = %args.name, enabled = args.enabled))]
 pub async fn external_calendar_sync(
+  app: tauri::AppHandle,
   state: State<'_, AppState>,
+  scheduler: State<
+    '_,
+    CalendarSyncScheduler
+  >,
   args: ExternalCalendarSourceArg,
   request_id: Option<String>
 ) -> Result<
@@ -85,6 +97,10 @@ pub async fn external_calendar_sync(
   String
 > {
   info!(request_id = ?request_id, calendar_id = %args.id, "external_calendar_sync command invoked");
+  ensure_permitted(
+    CommandCategory::CalendarImport,
+    "external_calendar_sync"
+  )?;
   if args.imported_ics_file {
     return Err(
       "Imported ICS calendars are \
@@ -106,14 +122,44 @@ pub async fn external_calendar_sync(
     });
   }
 
+  // Waits its turn behind background
+  // syncs and other manual ones.
+  scheduler.enqueue(&app, &args.id);
+  let slot = scheduler
+    .acquire(&app, &args)
+    .await;
+  let result =
+    sync_remote_calendar(&state, &args)
+      .await
+      .map_err(err_to_string);
+  scheduler.record(
+    &args.id,
+    result.as_ref().map(|_| ()).map_err(
+      Clone::clone
+    ),
+    Utc::now()
+  );
+  drop(slot);
+  result
+}
+
+/// Fetches, caches and applies one
+/// remote source; shared by
+/// `external_calendar_sync` and the
+/// background scheduler.
+async fn sync_remote_calendar(
+  state: &AppState,
+  args: &ExternalCalendarSourceArg
+) -> anyhow::Result<
+  ExternalCalendarSyncResult
+> {
   let ics_text = fetch_ics_document(
     args.location.as_str()
   )
-  .await
-  .map_err(err_to_string)?;
+  .await?;
   if let Err(error) =
     cache_ics_payload(
-      &args,
+      args,
       &ics_text,
       "remote_sync",
     )
@@ -125,12 +171,10 @@ pub async fn external_calendar_sync(
     );
   }
   let events =
-    parse_ics_events(&ics_text, &args)
-      .map_err(err_to_string)?;
+    parse_ics_events(&ics_text, args)?;
   apply_external_calendar_events(
-    &state, &args, events
+    state, args, events
   )
-  .map_err(err_to_string)
 }
 
 #[tauri::command]
@@ -144,6 +188,10 @@ pub async fn external_calendar_import_ics(
   String
 > {
   info!(request_id = ?request_id, calendar_id = %args.source.id, "external_calendar_import_ics command invoked");
+  ensure_permitted(
+    CommandCategory::CalendarImport,
+    "external_calendar_import_ics"
+  )?;
   if args
     .ics_text
     .trim()
@@ -197,6 +245,37 @@ pub async fn external_calendar_cache_list(
     .map_err(err_to_string)
 }
 
+/// Resolves a calendar "jump to date"
+/// entry with the same parser as task
+/// dates (`2027-03-14`, `tomorrow`,
+/// `march 2027`, `2027`, `+10d`...)
+/// and returns the local date in the
+/// project timezone as `YYYY-MM-DD`.
+#[tauri::command]
+#[instrument(skip(args), fields(request_id = ?request_id, input = %args.input))]
+pub async fn calendar_parse_date(
+  args: CalendarParseDateArg,
+  request_id: Option<String>
+) -> Result<String, String> {
+  info!(
+    request_id = ?request_id,
+    "calendar_parse_date command invoked"
+  );
+  parse_date_expr(&args.input, Utc::now())
+    .map(|parsed| {
+      parsed
+        .with_timezone(
+          project_timezone()
+        )
+        .format("%Y-%m-%d")
+        .to_string()
+    })
+    .map_err(|err| {
+      warn!(request_id = ?request_id, input = %args.input, error = %err, "calendar_parse_date rejected input");
+      err_to_string(err)
+    })
+}
+
 #[tauri::command]
 #[instrument(skip(state, args), fields(request_id = ?request_id, calendar_id = %args.source.id, cache_id = %args.cache_id))]
 pub async fn external_calendar_import_cached(
@@ -213,6 +292,10 @@ pub async fn external_calendar_import_cached(
     cache_id = %args.cache_id,
     "external_calendar_import_cached command invoked"
   );
+  ensure_permitted(
+    CommandCategory::CalendarImport,
+    "external_calendar_import_cached"
+  )?;
   let cache = load_cache_record(
     &args.cache_id
   )
@@ -277,7 +360,12 @@ fn apply_external_calendar_events(
       status: None,
       project: None,
       tag: None,
-  })?;
+      limit: None,
+      completed_from: None,
+      completed_to: None,
+      contact: None,
+  })?
+  .tasks;
   let mut existing_by_uid =
     BTreeMap::<String, TaskDto>::new();
   for task in all_tasks {
@@ -348,7 +436,11 @@ fn apply_external_calendar_events(
           },
         };
 
-        state.update(update)?;
+        state.update(
+          update,
+          &gui_default_times(),
+          &gui_description_limit()
+        )?;
         updated =
           updated.saturating_add(1);
       }
@@ -382,9 +474,15 @@ fn apply_external_calendar_events(
           ),
           wait:        None,
           scheduled:   None,
+          contact:     None,
         };
 
-        state.add(create)?;
+        state.add(
+          create,
+          &TaskDefaults::default(),
+          &gui_default_times(),
+          &gui_description_limit()
+        )?;
         created =
           created.saturating_add(1);
       }
@@ -767,7 +865,13 @@ fn normalize_ical_event(
     "DTSTART"
   )?;
   let due_utc =
-    parse_ics_dtstart(dtstart_prop)?;
+    parse_ics_datetime(dtstart_prop)?;
+  let all_day =
+    is_ics_date_only(dtstart_prop);
+  let duration_minutes =
+    event_duration_minutes(
+      event, due_utc, all_day
+    );
 
   let mut tags = vec![
     format!(
@@ -787,6 +891,17 @@ fn normalize_ical_event(
     ),
   ];
 
+  if duration_minutes > 0 {
+    tags.push(format!(
+      "{CAL_DURATION_TAG_KEY}:{duration_minutes}"
+    ));
+  }
+  if all_day {
+    tags.push(format!(
+      "{CAL_ALL_DAY_TAG_KEY}:true"
+    ));
+  }
+
   if let Some(rrule) = property_value(
     &event.properties,
     "RRULE"
@@ -807,7 +922,43 @@ fn normalize_ical_event(
   })
 }
 
-fn parse_ics_dtstart(
+/// Length of the event in minutes,
+/// stored as a duration rather than an
+/// end time so recurring copies keep
+/// it. All-day events without `DTEND`
+/// last one day; timed ones without it
+/// are points (0).
+fn event_duration_minutes(
+  event: &IcalEvent,
+  start: DateTime<Utc>,
+  all_day: bool
+) -> i64 {
+  let end = find_property(
+    &event.properties,
+    "DTEND"
+  )
+  .and_then(parse_ics_datetime);
+  match end {
+    | Some(end) if end > start => {
+      (end - start).num_minutes()
+    }
+    | _ if all_day => 24 * 60,
+    | _ => 0
+  }
+}
+
+fn is_ics_date_only(
+  property: &Property
+) -> bool {
+  property
+    .value
+    .as_deref()
+    .is_some_and(|raw| {
+      raw.trim().len() == 8
+    })
+}
+
+fn parse_ics_datetime(
   property: &Property
 ) -> Option<DateTime<Utc>> {
   let raw =
@@ -1040,6 +1191,38 @@ fn append_rrule_tags(
       );
     }
   }
+
+  if let Some(count) = rule_map
+    .get("COUNT")
+    .and_then(|value| {
+      value.trim().parse::<u32>().ok()
+    })
+    .filter(|count| *count > 0)
+  {
+    push_tag_unique(
+      tags,
+      format!(
+        "{RECUR_COUNT_TAG_KEY}:{count}"
+      )
+    );
+  } else if let Some(until) = rule_map
+    .get("UNTIL")
+    .and_then(|value| {
+      NaiveDate::parse_from_str(
+        value.get(..8)?,
+        "%Y%m%d"
+      )
+      .ok()
+    })
+  {
+    push_tag_unique(
+      tags,
+      format!(
+        "{RECUR_UNTIL_TAG_KEY}:{}",
+        until.format("%Y-%m-%d")
+      )
+    );
+  }
 }
 
 fn parse_rrule(
@@ -1448,11 +1631,15 @@ fn is_calendar_managed_tag(
       if key == CAL_SOURCE_TAG_KEY
       || key == CAL_EVENT_TAG_KEY
       || key == CAL_COLOR_TAG_KEY
+      || key == CAL_DURATION_TAG_KEY
+      || key == CAL_ALL_DAY_TAG_KEY
       || key == RECUR_TAG_KEY
       || key == RECUR_TIME_TAG_KEY
       || key == RECUR_DAYS_TAG_KEY
       || key == RECUR_MONTHS_TAG_KEY
       || key == RECUR_MONTH_DAY_TAG_KEY
+      || key == RECUR_UNTIL_TAG_KEY
+      || key == RECUR_COUNT_TAG_KEY
   )
 }
 
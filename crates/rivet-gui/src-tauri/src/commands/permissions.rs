@@ -0,0 +1,283 @@
+/// Groups of commands that
+/// `[permissions]` in rivet.toml can
+/// switch off, e.g. for a read-only
+/// wall display. Reads are never gated.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+)]
+enum CommandCategory {
+  TasksWrite,
+  TasksDelete,
+  ContactsWrite,
+  ContactsDelete,
+  CalendarImport,
+  ConfigWrite
+}
+
+impl CommandCategory {
+  const ALL: [Self; 6] = [
+    Self::TasksWrite,
+    Self::TasksDelete,
+    Self::ContactsWrite,
+    Self::ContactsDelete,
+    Self::CalendarImport,
+    Self::ConfigWrite
+  ];
+
+  fn as_str(self) -> &'static str {
+    match self {
+      | Self::TasksWrite => "tasks_write",
+      | Self::TasksDelete => {
+        "tasks_delete"
+      }
+      | Self::ContactsWrite => {
+        "contacts_write"
+      }
+      | Self::ContactsDelete => {
+        "contacts_delete"
+      }
+      | Self::CalendarImport => {
+        "calendar_import"
+      }
+      | Self::ConfigWrite => {
+        "config_write"
+      }
+    }
+  }
+
+  fn parse(raw: &str) -> Option<Self> {
+    let wanted = raw.trim();
+    Self::ALL.into_iter().find(
+      |category| {
+        category.as_str() == wanted
+      }
+    )
+  }
+}
+
+#[derive(
+  Debug, Clone, Default, PartialEq, Eq,
+)]
+struct CommandPermissions {
+  disabled: BTreeSet<CommandCategory>
+}
+
+impl CommandPermissions {
+  /// `read_only = true` disables every
+  /// category; otherwise `disabled`
+  /// lists category names. Unknown
+  /// names are logged and ignored.
+  fn from_section(
+    section: Option<&toml::Value>
+  ) -> Self {
+    let Some(section) = section else {
+      return Self::default();
+    };
+    if section
+      .get("read_only")
+      .and_then(toml::Value::as_bool)
+      .unwrap_or(false)
+    {
+      return Self {
+        disabled: CommandCategory::ALL
+          .into_iter()
+          .collect()
+      };
+    }
+
+    let mut disabled = BTreeSet::new();
+    for name in section
+      .get("disabled")
+      .and_then(toml::Value::as_array)
+      .into_iter()
+      .flatten()
+      .filter_map(toml::Value::as_str)
+    {
+      match CommandCategory::parse(name)
+      {
+        | Some(category) => {
+          disabled.insert(category);
+        }
+        | None => {
+          warn!(
+            category = name,
+            "ignoring unknown \
+             [permissions] category"
+          );
+        }
+      }
+    }
+    Self { disabled }
+  }
+
+  fn check(
+    &self,
+    category: CommandCategory,
+    command: &str
+  ) -> Result<(), String> {
+    if self.disabled.contains(&category)
+    {
+      warn!(
+        command,
+        category = category.as_str(),
+        "command rejected by \
+         [permissions]"
+      );
+      return Err(format!(
+        "permission denied: {command} \
+         is disabled ({} is off in \
+         [permissions])",
+        category.as_str()
+      ));
+    }
+    Ok(())
+  }
+}
+
+/// Read once per process so a running
+/// window cannot widen its own access
+/// by editing rivet.toml.
+fn command_permissions()
+-> &'static CommandPermissions {
+  static PERMISSIONS: OnceLock<
+    CommandPermissions,
+  > = OnceLock::new();
+  PERMISSIONS.get_or_init(|| {
+    let section = std::fs::read_to_string(
+      resolve_config_path("rivet.toml")
+    )
+    .ok()
+    .and_then(|raw| {
+      toml::from_str::<toml::Value>(
+        &raw
+      )
+      .ok()
+    })
+    .and_then(|value| {
+      value.get("permissions").cloned()
+    });
+    let permissions =
+      CommandPermissions::from_section(
+        section.as_ref()
+      );
+    info!(
+      disabled = ?permissions.disabled,
+      "loaded command permissions"
+    );
+    permissions
+  })
+}
+
+fn ensure_permitted(
+  category: CommandCategory,
+  command: &str
+) -> Result<(), String> {
+  command_permissions()
+    .check(category, command)
+}
+
+#[derive(
+  Debug, Clone, Serialize,
+)]
+pub struct PermissionsSnapshot {
+  pub disabled: Vec<String>
+}
+
+#[tauri::command]
+#[instrument(fields(request_id = ?request_id))]
+pub async fn permissions_snapshot(
+  request_id: Option<String>
+) -> Result<PermissionsSnapshot, String>
+{
+  info!(request_id = ?request_id, "permissions_snapshot command invoked");
+  Ok(PermissionsSnapshot {
+    disabled: command_permissions()
+      .disabled
+      .iter()
+      .map(|category| {
+        category.as_str().to_string()
+      })
+      .collect()
+  })
+}
+
+#[cfg(test)]
+mod permissions_tests {
+  use super::*;
+
+  fn section(
+    raw: &str
+  ) -> toml::Value {
+    toml::from_str(raw)
+      .expect("permissions toml")
+  }
+
+  #[test]
+  fn permissions_read_only_disables_every_category()
+   {
+    let permissions =
+      CommandPermissions::from_section(
+        Some(&section(
+          "read_only = true"
+        ))
+      );
+    for category in
+      CommandCategory::ALL
+    {
+      assert!(
+        permissions
+          .check(category, "cmd")
+          .is_err()
+      );
+    }
+    assert!(
+      CommandPermissions::from_section(
+        None
+      )
+      .disabled
+      .is_empty()
+    );
+  }
+
+  #[test]
+  fn permissions_disable_listed_categories_only()
+   {
+    let permissions =
+      CommandPermissions::from_section(
+        Some(&section(
+          "disabled = [\"tasks_delete\", \
+           \"bogus\"]"
+        ))
+      );
+    let err = permissions
+      .check(
+        CommandCategory::TasksDelete,
+        "task_delete"
+      )
+      .expect_err("delete disabled");
+    assert!(
+      err.starts_with(
+        "permission denied: \
+         task_delete"
+      )
+    );
+    assert!(
+      permissions
+        .check(
+          CommandCategory::TasksWrite,
+          "task_add"
+        )
+        .is_ok()
+    );
+    assert_eq!(
+      permissions.disabled.len(),
+      1
+    );
+  }
+}
@@ -4,7 +4,7 @@ pub async fn tasks_list(
   state: State<'_, AppState>,
   args: TasksListArgs,
   request_id: Option<String>
-) -> Result<Vec<TaskDto>, String> {
+) -> Result<TasksListPage, String> {
   info!(
       request_id = ?request_id,
       status = ?args.status,
@@ -20,6 +20,72 @@ pub async fn tasks_list(
   result.map_err(err_to_string)
 }
 
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, days = ?args.days))]
+pub async fn tasks_stale(
+  state: State<'_, AppState>,
+  args: TasksStaleArgs,
+  request_id: Option<String>
+) -> Result<Vec<TaskDto>, String> {
+  info!(
+      request_id = ?request_id,
+      days = ?args.days,
+      include_waiting = ?args.include_waiting,
+      include_blocked = ?args.include_blocked,
+      "tasks_stale command invoked"
+  );
+  let result = state.stale(args);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "tasks_stale command failed");
+  }
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, threshold = ?args.threshold))]
+pub async fn tasks_dedupe_preview(
+  state: State<'_, AppState>,
+  args: TasksDedupeArgs,
+  request_id: Option<String>
+) -> Result<Vec<TaskDedupeGroup>, String> {
+  info!(request_id = ?request_id, threshold = ?args.threshold, "tasks_dedupe_preview command invoked");
+  let result = state.dedupe_preview(args);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "tasks_dedupe_preview command failed");
+  }
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(state, args), fields(request_id = ?request_id, groups = args.groups.len(), merge = args.merge))]
+pub async fn tasks_dedupe_apply(
+  state: State<'_, AppState>,
+  args: TasksDedupeApplyArgs,
+  request_id: Option<String>
+) -> Result<TasksDedupeApplyResult, String> {
+  info!(
+    request_id = ?request_id,
+    groups = args.groups.len(),
+    merge = args.merge,
+    "tasks_dedupe_apply command invoked"
+  );
+  ensure_permitted(
+    CommandCategory::TasksDelete,
+    "tasks_dedupe_apply"
+  )?;
+  let result = state.dedupe_apply(&args);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "tasks_dedupe_apply command failed");
+  }
+  result
+    .map(|removed| {
+      TasksDedupeApplyResult {
+        removed
+      }
+    })
+    .map_err(err_to_string)
+}
+
 #[tauri::command]
 #[instrument(skip(state), fields(request_id = ?request_id, title_len = args.title.len(), description_len = args.description.len()))]
 pub async fn task_add(
@@ -38,13 +104,194 @@ pub async fn task_add(
     has_due = args.due.is_some(),
     "task_add command invoked"
   );
-  let result = state.add(args);
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "task_add"
+  )?;
+  let result = state.add(
+    args,
+    &gui_task_defaults(),
+    &gui_default_times(),
+    &gui_description_limit()
+  );
   if let Err(err) = result.as_ref() {
     error!(request_id = ?request_id, error = %err, "task_add command failed");
   }
   result.map_err(err_to_string)
 }
 
+/// `[defaults]` from rivet.toml, the
+/// GUI counterpart of the CLI's
+/// `default.project` / `default.tags`.
+/// Read on every add so edits apply
+/// without a restart.
+fn gui_task_defaults() -> TaskDefaults {
+  let path =
+    resolve_config_path("rivet.toml");
+  let Some(section) =
+    std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|raw| {
+        toml::from_str::<toml::Value>(
+          &raw
+        )
+        .ok()
+      })
+      .and_then(|value| {
+        value.get("defaults").cloned()
+      })
+  else {
+    return TaskDefaults::default();
+  };
+
+  let project = section
+    .get("project")
+    .and_then(toml::Value::as_str)
+    .map(str::trim)
+    .filter(|project| {
+      !project.is_empty()
+    })
+    .map(ToString::to_string);
+  let tags = match section.get("tags") {
+    | Some(toml::Value::String(raw)) => {
+      split_tag_list(raw)
+    }
+    | Some(toml::Value::Array(items)) => {
+      split_tag_list(
+        &items
+          .iter()
+          .filter_map(toml::Value::as_str)
+          .collect::<Vec<_>>()
+          .join(",")
+      )
+    }
+    | _ => Vec::new()
+  };
+  TaskDefaults {
+    project,
+    tags,
+    context_tags: Vec::new()
+  }
+}
+
+/// `[time]` `due_default_time`,
+/// `wait_default_time` and
+/// `scheduled_default_time` from
+/// rivet.toml, the GUI counterpart of
+/// the CLI's `<field>.default_time`.
+/// Invalid values fall back to midnight
+/// with a warning.
+fn gui_default_times() -> DefaultTimes {
+  let path =
+    resolve_config_path("rivet.toml");
+  let Some(section) =
+    std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|raw| {
+        toml::from_str::<toml::Value>(
+          &raw
+        )
+        .ok()
+      })
+      .and_then(|value| {
+        value.get("time").cloned()
+      })
+  else {
+    return DefaultTimes::default();
+  };
+
+  let value = |key: &str| {
+    section
+      .get(key)
+      .and_then(toml::Value::as_str)
+      .map(ToString::to_string)
+  };
+  DefaultTimes::from_values(
+    value("due_default_time"),
+    value("wait_default_time"),
+    value("scheduled_default_time")
+  )
+  .unwrap_or_else(|err| {
+    warn!(error = %err, "ignoring [time] default times");
+    DefaultTimes::default()
+  })
+}
+
+/// `[tasks.title]` `max_length` and
+/// `overflow` from rivet.toml, the GUI
+/// counterpart of the CLI's
+/// `description.max_length` /
+/// `description.overflow` (the GUI
+/// title is the task description).
+/// Invalid values fall back to no limit
+/// with a warning.
+fn gui_description_limit()
+-> DescriptionLimit {
+  let path =
+    resolve_config_path("rivet.toml");
+  let Some(section) =
+    std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|raw| {
+        toml::from_str::<toml::Value>(
+          &raw
+        )
+        .ok()
+      })
+      .and_then(|value| {
+        value
+          .get("tasks")?
+          .get("title")
+          .cloned()
+      })
+  else {
+    return DescriptionLimit::default();
+  };
+
+  let max_length = match section
+    .get("max_length")
+  {
+    | None => 0,
+    | Some(value) => {
+      match value
+        .as_integer()
+        .and_then(|raw| {
+          usize::try_from(raw).ok()
+        }) {
+        | Some(max) => max,
+        | None => {
+          warn!(value = %value, "ignoring invalid [tasks.title] max_length");
+          return DescriptionLimit::default();
+        }
+      }
+    }
+  };
+  let overflow = match section
+    .get("overflow")
+    .and_then(toml::Value::as_str)
+  {
+    | None => {
+      DescriptionOverflow::default()
+    }
+    | Some(raw) => {
+      match DescriptionOverflow::parse(
+        raw
+      ) {
+        | Some(overflow) => overflow,
+        | None => {
+          warn!(value = %raw, "ignoring invalid [tasks.title] overflow");
+          DescriptionOverflow::default()
+        }
+      }
+    }
+  };
+  DescriptionLimit {
+    max_length: (max_length > 0)
+      .then_some(max_length),
+    overflow
+  }
+}
+
 #[tauri::command]
 #[instrument(skip(state), fields(request_id = ?request_id, uuid = %args.uuid))]
 pub async fn task_update(
@@ -53,7 +300,15 @@ pub async fn task_update(
   request_id: Option<String>
 ) -> Result<TaskDto, String> {
   info!(request_id = ?request_id, uuid = %args.uuid, "task_update command invoked");
-  let result = state.update(args);
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "task_update"
+  )?;
+  let result = state.update(
+    args,
+    &gui_default_times(),
+    &gui_description_limit()
+  );
   if let Err(err) = result.as_ref() {
     error!(request_id = ?request_id, error = %err, "task_update command failed");
   }
@@ -68,13 +323,59 @@ pub async fn task_done(
   request_id: Option<String>
 ) -> Result<TaskDto, String> {
   info!(request_id = ?request_id, uuid = %args.uuid, "task_done command invoked");
-  let result = state.done(args.uuid);
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "task_done"
+  )?;
+  let result = state.done(
+    args.uuid,
+    gui_recur_settings().0,
+    gui_completion_policy()
+  );
   if let Err(err) = result.as_ref() {
     error!(request_id = ?request_id, error = %err, "task_done command failed");
   }
   result.map_err(err_to_string)
 }
 
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, uuid = %args.uuid))]
+pub async fn task_start(
+  state: State<'_, AppState>,
+  args: TaskIdArg,
+  request_id: Option<String>
+) -> Result<TaskDto, String> {
+  info!(request_id = ?request_id, uuid = %args.uuid, "task_start command invoked");
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "task_start"
+  )?;
+  let result = state.start(args.uuid);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "task_start command failed");
+  }
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, uuid = %args.uuid))]
+pub async fn task_stop(
+  state: State<'_, AppState>,
+  args: TaskIdArg,
+  request_id: Option<String>
+) -> Result<TaskDto, String> {
+  info!(request_id = ?request_id, uuid = %args.uuid, "task_stop command invoked");
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "task_stop"
+  )?;
+  let result = state.stop(args.uuid);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "task_stop command failed");
+  }
+  result.map_err(err_to_string)
+}
+
 #[tauri::command]
 #[instrument(skip(state), fields(request_id = ?request_id, uuid = %args.uuid))]
 pub async fn task_uncomplete(
@@ -83,6 +384,10 @@ pub async fn task_uncomplete(
   request_id: Option<String>
 ) -> Result<TaskDto, String> {
   info!(request_id = ?request_id, uuid = %args.uuid, "task_uncomplete command invoked");
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "task_uncomplete"
+  )?;
   let result = state.uncomplete(args.uuid);
   if let Err(err) = result.as_ref() {
     error!(request_id = ?request_id, error = %err, "task_uncomplete command failed");
@@ -98,6 +403,10 @@ pub async fn task_delete(
   request_id: Option<String>
 ) -> Result<(), String> {
   info!(request_id = ?request_id, uuid = %args.uuid, "task_delete command invoked");
+  ensure_permitted(
+    CommandCategory::TasksDelete,
+    "task_delete"
+  )?;
   let result = state.delete(args.uuid);
   if let Err(err) = result.as_ref() {
     error!(request_id = ?request_id, error = %err, "task_delete command failed");
@@ -105,6 +414,144 @@ pub async fn task_delete(
   result.map_err(err_to_string)
 }
 
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, uuid = %args.uuid))]
+pub async fn task_history(
+  state: State<'_, AppState>,
+  args: TaskIdArg,
+  request_id: Option<String>
+) -> Result<Vec<TaskHistoryEntryDto>, String> {
+  info!(request_id = ?request_id, uuid = %args.uuid, "task_history command invoked");
+  let result = state.history(args.uuid);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "task_history command failed");
+  }
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id, key = %args.key))]
+pub async fn tags_remap(
+  state: State<'_, AppState>,
+  args: TagsRemapArgs,
+  request_id: Option<String>
+) -> Result<TagsRemapResult, String> {
+  info!(request_id = ?request_id, key = %args.key, from = %args.from, to = %args.to, "tags_remap command invoked");
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "tags_remap"
+  )?;
+  let remap = TagRemap::new(
+    &args.key, &args.from, &args.to
+  )
+  .ok_or_else(|| {
+    format!(
+      "invalid tag remap: {}:{} -> {}",
+      args.key, args.from, args.to
+    )
+  })?;
+  let result = state.remap_tag(&remap);
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "tags_remap command failed");
+  }
+  result
+    .map(|changed| TagsRemapResult {
+      changed
+    })
+    .map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id))]
+pub async fn tags_stats(
+  state: State<'_, AppState>,
+  request_id: Option<String>
+) -> Result<Vec<TagUsageDto>, String> {
+  info!(request_id = ?request_id, "tags_stats command invoked");
+  let result = state.tag_stats();
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "tags_stats command failed");
+  }
+  result.map_err(err_to_string)
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id))]
+pub async fn tags_cleanup_unused(
+  state: State<'_, AppState>,
+  request_id: Option<String>
+) -> Result<TagsCleanupResult, String> {
+  info!(request_id = ?request_id, "tags_cleanup_unused command invoked");
+  ensure_permitted(
+    CommandCategory::TasksWrite,
+    "tags_cleanup_unused"
+  )?;
+  let result =
+    state.cleanup_unused_tags();
+  match result.as_ref() {
+    | Ok(cleanup) => {
+      info!(request_id = ?request_id, removed = cleanup.removed.len(), changed = cleanup.changed, "tags_cleanup_unused completed");
+    }
+    | Err(err) => {
+      error!(request_id = ?request_id, error = %err, "tags_cleanup_unused command failed");
+    }
+  }
+  result.map_err(err_to_string)
+}
+
+/// Seeds the demo tasks when
+/// `[app] seed_demo` is on and the
+/// store is still empty; a no-op
+/// otherwise. Called once at startup.
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id))]
+pub async fn demo_seed(
+  state: State<'_, AppState>,
+  request_id: Option<String>
+) -> Result<DemoDataResult, String> {
+  info!(request_id = ?request_id, "demo_seed command invoked");
+  let tasks = if gui_seed_demo() {
+    ensure_permitted(
+      CommandCategory::TasksWrite,
+      "demo_seed"
+    )?;
+    state.seed_demo().map_err(|err| {
+      error!(request_id = ?request_id, error = %err, "demo_seed command failed");
+      err_to_string(err)
+    })?
+  } else {
+    0
+  };
+  Ok(DemoDataResult {
+    tasks,
+    board_id: DEMO_BOARD_ID.to_string()
+  })
+}
+
+#[tauri::command]
+#[instrument(skip(state), fields(request_id = ?request_id))]
+pub async fn demo_clear(
+  state: State<'_, AppState>,
+  request_id: Option<String>
+) -> Result<DemoDataResult, String> {
+  info!(request_id = ?request_id, "demo_clear command invoked");
+  ensure_permitted(
+    CommandCategory::TasksDelete,
+    "demo_clear"
+  )?;
+  let result = state.clear_demo();
+  if let Err(err) = result.as_ref() {
+    error!(request_id = ?request_id, error = %err, "demo_clear command failed");
+  }
+  result
+    .map(|tasks| DemoDataResult {
+      tasks,
+      board_id: DEMO_BOARD_ID
+        .to_string()
+    })
+    .map_err(err_to_string)
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UiLogArg {
   pub event:  String,
@@ -501,6 +501,62 @@ fn main() {
       }
     };
 
+  if let Some(keep) =
+    commands::gui_daily_backup_keep()
+  {
+    match state.daily_backup(keep) {
+      | Ok(Some(path)) => {
+        info!(snapshot = %path.display(), keep, "wrote daily backup");
+      }
+      | Ok(None) => {}
+      | Err(err) => {
+        warn!(error = %err, "daily backup failed");
+      }
+    }
+  }
+
+  if let Some(days) =
+    commands::gui_auto_archive_days()
+  {
+    match state.auto_archive(days) {
+      | Ok(0) => {}
+      | Ok(archived) => {
+        info!(
+          archived,
+          days,
+          "auto-archived completed \
+           tasks"
+        );
+      }
+      | Err(err) => {
+        warn!(error = %err, "auto-archive failed");
+      }
+    }
+  }
+
+  let (recur_mode, recur_horizon) =
+    commands::gui_recur_settings();
+  if recur_mode
+    == rivet_core::recur::RecurMode::Lazy
+  {
+    match state
+      .recur_sweep(recur_horizon)
+    {
+      | Ok(0) => {}
+      | Ok(created) => {
+        info!(
+          created,
+          horizon_days = recur_horizon,
+          "created lazy recurring \
+           instances"
+        );
+      }
+      | Err(err) => {
+        warn!(error = %err, "recurrence sweep failed");
+      }
+    }
+  }
+
   tauri::Builder::default()
     .setup(|app| {
       configure_main_window_icon(app);
@@ -510,42 +566,71 @@ fn main() {
       install_signal_handlers(
         app.handle().clone()
       );
+      commands::spawn_calendar_sync_scheduler(
+        app.handle().clone()
+      );
       Ok(())
     })
     .manage(state)
+    .manage(
+      commands::CalendarSyncScheduler::load(
+      )
+    )
     .invoke_handler(
       tauri::generate_handler![
         commands::config_snapshot,
         commands::config_apply_updates,
+        commands::config_bundle_export,
+        commands::config_bundle_import,
+        commands::permissions_snapshot,
         commands::tag_schema_snapshot,
         commands::map_health,
         commands::tasks_list,
+        commands::tasks_stale,
+        commands::tasks_dedupe_preview,
+        commands::tasks_dedupe_apply,
         commands::task_add,
         commands::task_update,
         commands::task_done,
         commands::task_uncomplete,
+        commands::task_start,
+        commands::task_stop,
         commands::task_delete,
+        commands::task_history,
+        commands::tags_remap,
+        commands::tags_stats,
+        commands::tags_cleanup_unused,
+        commands::demo_seed,
+        commands::demo_clear,
         commands::dictionary_languages,
         commands::dictionary_search,
         commands::dictionary_entry,
         commands::contacts_list,
+        commands::contact_get,
         commands::contact_add,
         commands::contact_update,
         commands::contact_delete,
         commands::contacts_delete_bulk,
+        commands::contacts_unlink,
+        commands::contacts_delete_undo,
         commands::contacts_dedupe_preview,
         commands::contacts_dedupe_candidates,
         commands::contacts_dedupe_decide,
         commands::contact_open_action,
+        commands::contacts_interactions,
+        commands::contacts_interactions_prune,
         commands::contacts_import_preview,
         commands::contacts_import_commit,
         commands::contacts_merge,
+        commands::contacts_merge_preview,
         commands::contacts_merge_undo,
         commands::ui_log,
         commands::external_calendar_sync,
+        commands::external_calendar_schedule,
         commands::external_calendar_import_ics,
         commands::external_calendar_cache_list,
         commands::external_calendar_import_cached,
+        commands::calendar_parse_date,
         commands::window_minimize,
         commands::window_toggle_maximize,
         commands::window_close,
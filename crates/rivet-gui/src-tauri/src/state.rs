@@ -1,36 +1,92 @@
+use std::collections::{
+  BTreeSet,
+  HashMap
+};
 use std::path::PathBuf;
 
 use anyhow::Context;
-use chrono::Utc;
+use chrono::{
+  NaiveDate,
+  Utc
+};
 use parking_lot::Mutex;
-use rivet_core::datastore::DataStore;
-use rivet_core::datetime::parse_date_expr;
+use rivet_core::datastore::{
+  DataStore,
+  IndexKey
+};
+use rivet_core::datetime::{
+  DefaultTimes,
+  parse_date_expr_at,
+  to_project_date
+};
+use rivet_core::dto::{
+  apply_patch,
+  child_index,
+  detail,
+  format_dto_date,
+  priority_to_core,
+  set_detail,
+  task_to_dto,
+  view_status
+};
+use rivet_core::filter::VirtualTag;
+use rivet_core::history::{
+  diff_snapshots,
+  display_value
+};
+use rivet_core::recur::{
+  self,
+  RecurMode,
+  next_instance,
+  spawned_successor
+};
 use rivet_core::task::{
+  CompletionPolicy,
+  DescriptionLimit,
+  StalePolicy,
   Status,
-  Task
+  TagRemap,
+  Task,
+  TaskDefaults,
+  UrgencyCoefficients,
+  newly_unblocked,
+  open_descendants
+};
+use rivet_core::{
+  archive,
+  backup,
+  dedupe,
+  demo,
+  stats
 };
 use rivet_gui_shared::{
+  TagUsageDto,
+  TagsCleanupResult,
   TaskCreate,
+  TaskDedupeGroup,
   TaskDto,
-  TaskPatch,
-  TaskPriority,
+  TaskFieldChangeDto,
+  TaskHistoryEntryDto,
   TaskStatus,
   TaskUpdateArgs,
-  TasksListArgs
+  TasksDedupeApplyArgs,
+  TasksDedupeArgs,
+  TasksListArgs,
+  TasksListPage,
+  TasksStaleArgs
 };
 use serde_json::Value;
 use tracing::{
   debug,
-  instrument,
-  warn
+  info,
+  instrument
 };
 use uuid::Uuid;
 
 const KANBAN_LANE_KEY: &str = "kanban";
 const DEFAULT_KANBAN_LANE: &str =
   "todo";
-const RIVET_DETAIL_KEY: &str =
-  "rivet_description";
+const BOARD_TAG_KEY: &str = "board";
 
 pub struct AppState {
   store: Mutex<DataStore>
@@ -54,88 +110,692 @@ impl AppState {
     })
   }
 
+  /// Automatic `[backup] daily`
+  /// snapshot; see
+  /// `backup::ensure_daily_snapshot`.
   #[instrument(skip(self))]
-  pub fn list(
+  pub fn daily_backup(
     &self,
-    args: TasksListArgs
-  ) -> anyhow::Result<Vec<TaskDto>> {
+    keep: usize
+  ) -> anyhow::Result<Option<PathBuf>>
+  {
+    let store = self.store.lock();
+    Ok(
+      backup::ensure_daily_snapshot(
+        &store.data_dir,
+        keep,
+        Utc::now()
+      )?
+      .map(|summary| summary.path)
+    )
+  }
+
+  /// `[recur] mode = "lazy"` on launch;
+  /// see `recur::run_sweep`.
+  #[instrument(skip(self))]
+  pub fn recur_sweep(
+    &self,
+    horizon_days: i64
+  ) -> anyhow::Result<usize> {
+    let store = self.store.lock();
+    recur::run_sweep(
+      &store,
+      horizon_days,
+      Utc::now()
+    )
+  }
+
+  /// `[auto_archive] days` on launch;
+  /// see `archive::run`.
+  #[instrument(skip(self))]
+  pub fn auto_archive(
+    &self,
+    days: i64
+  ) -> anyhow::Result<usize> {
+    let store = self.store.lock();
+    archive::run(
+      &store,
+      days,
+      Utc::now()
+    )
+  }
+
+  /// `[app] seed_demo` first-run data;
+  /// see `demo::seed_if_empty`.
+  #[instrument(skip(self))]
+  pub fn seed_demo(
+    &self
+  ) -> anyhow::Result<usize> {
+    let store = self.store.lock();
+    demo::seed_if_empty(
+      &store,
+      Utc::now()
+    )
+  }
+
+  /// Removes every demo task, recording
+  /// the removals in the audit log.
+  #[instrument(skip(self))]
+  pub fn clear_demo(
+    &self
+  ) -> anyhow::Result<usize> {
+    let now = Utc::now();
+    let store = self.store.lock();
+    let mut pending =
+      store.load_pending()?;
+    let mut completed =
+      store.load_completed()?;
+    let removed = demo::remove_demo(
+      &mut pending,
+      &mut completed
+    );
+    if removed.is_empty() {
+      return Ok(0);
+    }
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+    store.append_history(
+      &diff_snapshots(
+        "gui:demo_clear",
+        &removed,
+        &[],
+        now
+      )
+    )?;
+    Ok(removed.len())
+  }
+
+  /// Applies `remap` to every task as
+  /// one write per data file; see
+  /// `task tags remap`.
+  #[instrument(skip(self))]
+  pub fn remap_tag(
+    &self,
+    remap: &TagRemap
+  ) -> anyhow::Result<usize> {
+    let now = Utc::now();
+    let store = self.store.lock();
+    let mut pending =
+      store.load_pending()?;
+    let mut completed =
+      store.load_completed()?;
+    let pending_before =
+      pending.clone();
+    let completed_before =
+      completed.clone();
+    let changed = remap
+      .apply(&mut pending, now)
+      + remap
+        .apply(&mut completed, now);
+    if changed == 0 {
+      return Ok(0);
+    }
+    // Same as `task tags remap`: one
+    // undo step for the whole remap.
+    store.push_undo_snapshot(
+      &pending_before,
+      &completed_before
+    )?;
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+    let before: Vec<Task> =
+      pending_before
+        .into_iter()
+        .chain(completed_before)
+        .collect();
+    let after: Vec<Task> = pending
+      .into_iter()
+      .chain(completed)
+      .collect();
+    store.append_history(
+      &diff_snapshots(
+        "gui:tags_remap",
+        &before,
+        &after,
+        now
+      )
+    )?;
+    Ok(changed)
+  }
+
+  /// Counts open tasks still linked to
+  /// any of `contacts`.
+  #[instrument(skip(self))]
+  pub fn open_tasks_linked_to(
+    &self,
+    contacts: &[Uuid]
+  ) -> anyhow::Result<usize> {
+    let store = self.store.lock();
+    Ok(
+      store
+        .load_pending()?
+        .iter()
+        .filter(|task| {
+          matches!(
+            task.status,
+            Status::Pending
+              | Status::Waiting
+          ) && task.contact.is_some_and(
+            |contact| {
+              contacts
+                .contains(&contact)
+            }
+          )
+        })
+        .count()
+    )
+  }
+
+  /// Points tasks linked to any of
+  /// `from` at `to`, after a contacts
+  /// merge.
+  #[instrument(skip(self))]
+  pub fn relink_contacts(
+    &self,
+    from: &[Uuid],
+    to: Uuid
+  ) -> anyhow::Result<usize> {
+    let now = Utc::now();
+    let store = self.store.lock();
+    let mut pending =
+      store.load_pending()?;
+    let mut completed =
+      store.load_completed()?;
+    let before: Vec<Task> = pending
+      .iter()
+      .chain(&completed)
+      .cloned()
+      .collect();
+    let mut changed = 0;
+    for task in pending
+      .iter_mut()
+      .chain(completed.iter_mut())
+    {
+      if task.contact.is_some_and(
+        |contact| {
+          from.contains(&contact)
+        }
+      ) {
+        task.contact = Some(to);
+        task.modified = now;
+        changed += 1;
+      }
+    }
+    if changed == 0 {
+      return Ok(0);
+    }
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+    let after: Vec<Task> = pending
+      .into_iter()
+      .chain(completed)
+      .collect();
+    store.append_history(
+      &diff_snapshots(
+        "gui:contacts_merge",
+        &before,
+        &after,
+        now
+      )
+    )?;
+    Ok(changed)
+  }
+
+  /// Per-tag usage across both data
+  /// files, most used first; see
+  /// `task stats tags`.
+  #[instrument(skip(self))]
+  pub fn tag_stats(
+    &self
+  ) -> anyhow::Result<Vec<TagUsageDto>>
+  {
     let store = self.store.lock();
     let mut tasks =
       store.load_pending()?;
     tasks
       .extend(store.load_completed()?);
-    let now = Utc::now();
+    let mut usage =
+      stats::tag_stats(&tasks);
+    stats::StatsSort::Count
+      .apply(&mut usage);
+    Ok(
+      usage
+        .into_iter()
+        .map(|stat| {
+          TagUsageDto {
+            tag:       stat.name,
+            count:     stat.count,
+            open:      stat.open,
+            last_used: format_dto_date(
+              stat.last_used
+            )
+          }
+        })
+        .collect()
+    )
+  }
 
-    let filtered = tasks
+  /// Removes every plain tag that no
+  /// open task carries (see
+  /// `stats::unused_tags`) from the
+  /// closed tasks still holding it.
+  #[instrument(skip(self))]
+  pub fn cleanup_unused_tags(
+    &self
+  ) -> anyhow::Result<TagsCleanupResult>
+  {
+    let now = Utc::now();
+    let store = self.store.lock();
+    let mut pending =
+      store.load_pending()?;
+    let mut completed =
+      store.load_completed()?;
+    let before: Vec<Task> = pending
+      .iter()
+      .chain(&completed)
+      .cloned()
+      .collect();
+    let removed = stats::unused_tags(
+      &stats::tag_stats(&before)
+    );
+    let changed = stats::strip_tags(
+      &mut pending,
+      &removed,
+      now
+    ) + stats::strip_tags(
+      &mut completed,
+      &removed,
+      now
+    );
+    if changed == 0 {
+      return Ok(TagsCleanupResult {
+        removed: Vec::new(),
+        changed
+      });
+    }
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+    let after: Vec<Task> = pending
       .into_iter()
-      .filter(|task| {
-        if let Some(status) =
-          args.status.as_ref()
-          && task_status_for_view(
-            task, now
-          ) != *status
-        {
-          return false;
-        }
+      .chain(completed)
+      .collect();
+    store.append_history(
+      &diff_snapshots(
+        "gui:tags_cleanup",
+        &before,
+        &after,
+        now
+      )
+    )?;
+    Ok(TagsCleanupResult {
+      removed,
+      changed
+    })
+  }
 
-        if let Some(project) =
-          args.project.as_ref()
-          && task.project.as_deref()
-            != Some(project.as_str())
-        {
-          return false;
-        }
+  #[instrument(skip(self))]
+  /// Matching tasks by urgency, so a
+  /// `limit` keeps the most urgent.
+  pub fn list(
+    &self,
+    args: TasksListArgs
+  ) -> anyhow::Result<TasksListPage> {
+    let store = self.store.lock();
+    let pending_index =
+      store.pending_index()?;
+    let completed_index =
+      store.completed_index()?;
+    let children = child_index(
+      pending_index
+        .tasks()
+        .iter()
+        .chain(completed_index.tasks())
+    );
+    let now = Utc::now();
+    let (virtual_tags, text_query) =
+      split_query_virtual_tags(
+        args
+          .query
+          .as_deref()
+          .unwrap_or("")
+      );
 
-        if let Some(tag) =
-          args.tag.as_ref()
-          && !task
-            .tags
-            .iter()
-            .any(|t| t == tag)
-        {
-          return false;
+    let limit = args
+      .limit
+      .filter(|limit| *limit > 0)
+      .unwrap_or(usize::MAX);
+    let completed_from =
+      parse_completed_bound(
+        args.completed_from.as_deref(),
+        "completed_from"
+      )?;
+    let completed_to =
+      parse_completed_bound(
+        args.completed_to.as_deref(),
+        "completed_to"
+      )?;
+
+    // Archived tasks only show up when
+    // asked for by status or query.
+    let show_archived = args.status
+      == Some(TaskStatus::Archived)
+      || virtual_tags.iter().any(
+        |(_, virtual_tag)| {
+          matches!(
+            virtual_tag,
+            VirtualTag::Archived
+          )
         }
+      );
 
-        if let Some(query) =
-          args.query.as_ref()
-        {
-          let q =
-            query.to_ascii_lowercase();
-          let title_match = task
-            .description
-            .to_ascii_lowercase()
-            .contains(&q);
-          let description_match =
-            task_detail_description(
-              task
-            )
-            .as_deref()
-            .is_some_and(
-              |description| {
-                description
-                  .to_ascii_lowercase()
-                  .contains(&q)
-              }
-            );
-          if !title_match
-            && !description_match
+    let keys = list_index_keys(&args);
+    let mut matched: Vec<&Task> =
+      pending_index
+        .candidates(&keys)
+        .into_iter()
+        .chain(
+          completed_index
+            .candidates(&keys)
+        )
+        .filter(|task| {
+          if !show_archived
+            && task.status
+              == Status::Archived
           {
             return false;
           }
-        }
 
-        true
+          if let Some(status) =
+            args.status.as_ref()
+            && view_status(task, now)
+              != *status
+          {
+            return false;
+          }
+
+          if let Some(project) =
+            args.project.as_ref()
+            && task.project.as_deref()
+              != Some(project.as_str())
+          {
+            return false;
+          }
+
+          if let Some(tag) =
+            args.tag.as_ref()
+            && !task
+              .tags
+              .iter()
+              .any(|t| t == tag)
+          {
+            return false;
+          }
+
+          if args.contact.is_some()
+            && task.contact
+              != args.contact
+          {
+            return false;
+          }
+
+          if completed_from.is_some()
+            || completed_to.is_some()
+          {
+            let Some(day) = task
+              .end
+              .map(to_project_date)
+            else {
+              return false;
+            };
+            if completed_from
+              .is_some_and(|from| {
+                day < from
+              })
+              || completed_to
+                .is_some_and(|to| {
+                  day > to
+                })
+            {
+              return false;
+            }
+          }
+
+          if !virtual_tags.iter().all(
+            |(include, virtual_tag)| {
+              virtual_tag
+                .matches(task, now)
+                == *include
+            }
+          ) {
+            return false;
+          }
+
+          if !text_query.is_empty() {
+            let q = text_query
+              .to_ascii_lowercase();
+            let title_match = task
+              .description
+              .to_ascii_lowercase()
+              .contains(&q);
+            let description_match =
+              detail(task)
+                .as_deref()
+                .is_some_and(
+                  |description| {
+                    description
+                    .to_ascii_lowercase(
+                    )
+                    .contains(&q)
+                  }
+                );
+            if !title_match
+              && !description_match
+            {
+              return false;
+            }
+          }
+
+          true
+        })
+        .collect();
+
+    let coefficients =
+      UrgencyCoefficients::default();
+    matched.sort_by(|a, b| {
+      b.urgency(now, &coefficients)
+        .total_cmp(
+          &a.urgency(
+            now,
+            &coefficients
+          )
+        )
+        .then(a.id.cmp(&b.id))
+        .then(a.entry.cmp(&b.entry))
+    });
+    let total = matched.len();
+    let tasks = matched
+      .into_iter()
+      .take(limit)
+      .map(|task| {
+        let subtasks = children
+          .get(&task.uuid)
+          .cloned()
+          .unwrap_or_default();
+        task_to_dto(
+          task.clone(),
+          subtasks,
+          now
+        )
       })
-      .map(task_to_dto)
       .collect();
 
-    Ok(filtered)
+    Ok(TasksListPage {
+      tasks,
+      total
+    })
+  }
+
+  #[instrument(skip(self))]
+  pub fn stale(
+    &self,
+    args: TasksStaleArgs
+  ) -> anyhow::Result<Vec<TaskDto>> {
+    let store = self.store.lock();
+    let now = Utc::now();
+    let defaults =
+      StalePolicy::default();
+    let policy = StalePolicy {
+      days:            args
+        .days
+        .filter(|days| *days > 0)
+        .map(StalePolicy::check_days)
+        .transpose()?
+        .unwrap_or(defaults.days),
+      include_waiting: args
+        .include_waiting
+        .unwrap_or(
+          defaults.include_waiting
+        ),
+      include_blocked: args
+        .include_blocked
+        .unwrap_or(
+          defaults.include_blocked
+        )
+    };
+
+    let mut tasks: Vec<Task> = store
+      .pending_index()?
+      .tasks()
+      .iter()
+      .filter(|task| {
+        task.is_stale(now, &policy)
+      })
+      .cloned()
+      .collect();
+    tasks.sort_by_key(|task| {
+      (task.modified, task.id)
+    });
+    let mut children =
+      load_child_index(&store)?;
+
+    Ok(
+      tasks
+        .into_iter()
+        .map(|task| {
+          let subtasks = children
+            .remove(&task.uuid)
+            .unwrap_or_default();
+          task_to_dto(
+            task, subtasks, now
+          )
+        })
+        .collect()
+    )
+  }
+
+  /// Likely-duplicate open tasks; see
+  /// `task dedupe`.
+  #[instrument(skip(self))]
+  pub fn dedupe_preview(
+    &self,
+    args: TasksDedupeArgs
+  ) -> anyhow::Result<
+    Vec<TaskDedupeGroup>
+  > {
+    let store = self.store.lock();
+    let threshold = args
+      .threshold
+      .filter(|value| {
+        (1..=100).contains(value)
+      })
+      .unwrap_or(
+        dedupe::DEFAULT_THRESHOLD
+      );
+    let pending =
+      store.load_pending()?;
+    let groups = dedupe::find_groups(
+      &pending, threshold
+    );
+    let mut children =
+      load_child_index(&store)?;
+
+    Ok(
+      groups
+        .into_iter()
+        .map(|group| {
+          TaskDedupeGroup {
+            score:  group.score,
+            reason: group
+              .reason
+              .to_string(),
+            tasks:  group
+              .tasks
+              .iter()
+              .filter_map(|uuid| {
+                pending.iter().find(
+                  |task| {
+                    task.uuid == *uuid
+                  }
+                )
+              })
+              .map(|task| {
+                let subtasks = children
+                  .remove(&task.uuid)
+                  .unwrap_or_default();
+                task_to_dto(
+                  task.clone(),
+                  subtasks,
+                  Utc::now()
+                )
+              })
+              .collect()
+          }
+        })
+        .collect()
+    )
+  }
+
+  /// Merges or deletes the chosen
+  /// duplicate groups as one write; see
+  /// `task dedupe merge`.
+  #[instrument(skip(self))]
+  pub fn dedupe_apply(
+    &self,
+    args: &TasksDedupeApplyArgs
+  ) -> anyhow::Result<usize> {
+    let now = Utc::now();
+    let store = self.store.lock();
+    let mut pending =
+      store.load_pending()?;
+    let before = pending.clone();
+    let removed = dedupe::apply_groups(
+      &mut pending,
+      &args.groups,
+      args.merge,
+      now
+    );
+    if removed == 0 {
+      return Ok(0);
+    }
+    store.save_pending(&pending)?;
+    store.append_history(
+      &diff_snapshots(
+        "gui:tasks_dedupe",
+        &before,
+        &pending,
+        now
+      )
+    )?;
+    Ok(removed as usize)
   }
 
   #[instrument(skip(self))]
   pub fn add(
     &self,
-    create: TaskCreate
+    create: TaskCreate,
+    defaults: &TaskDefaults,
+    times: &DefaultTimes,
+    limit: &DescriptionLimit
   ) -> anyhow::Result<TaskDto> {
     let now = Utc::now();
     let store = self.store.lock();
@@ -156,12 +816,17 @@ impl AppState {
       now,
       next_id
     );
-    set_task_detail_description(
+    limit.apply(&mut task, now)?;
+    set_detail(
       &mut task,
       &create.description
     );
     task.project = create.project;
     task.tags = create.tags;
+    task.contact = create.contact;
+    apply_task_defaults(
+      &mut task, defaults
+    );
     ensure_default_kanban_lane_tag(
       &mut task.tags
     );
@@ -170,21 +835,25 @@ impl AppState {
       .map(priority_to_core);
 
     if let Some(due) = create.due {
-      task.due = Some(parse_date_expr(
-        &due, now
-      )?);
+      task.due =
+        Some(parse_date_expr_at(
+          &due, now, times.due
+        )?);
     }
     if let Some(wait) = create.wait {
-      let parsed =
-        parse_date_expr(&wait, now)?;
+      let parsed = parse_date_expr_at(
+        &wait, now, times.wait
+      )?;
       task.wait = Some(parsed);
     }
     if let Some(scheduled) =
       create.scheduled
     {
       task.scheduled =
-        Some(parse_date_expr(
-          &scheduled, now
+        Some(parse_date_expr_at(
+          &scheduled,
+          now,
+          times.scheduled
         )?);
     }
 
@@ -193,21 +862,34 @@ impl AppState {
       t.id.unwrap_or(u64::MAX)
     });
     store.save_pending(&pending)?;
+    record_history(
+      &store,
+      "add",
+      None,
+      Some(&task),
+      now
+    )?;
 
-    Ok(task_to_dto(task))
+    Ok(task_to_dto(
+      task,
+      Vec::new(),
+      now
+    ))
   }
 
   #[instrument(skip(self))]
   pub fn update(
     &self,
-    update: TaskUpdateArgs
+    update: TaskUpdateArgs,
+    times: &DefaultTimes,
+    limit: &DescriptionLimit
   ) -> anyhow::Result<TaskDto> {
     let now = Utc::now();
     let store = self.store.lock();
     let mut pending =
       store.load_pending()?;
 
-    let updated_task = {
+    let (previous, updated_task) = {
       let task = pending
         .iter_mut()
         .find(|task| {
@@ -219,23 +901,54 @@ impl AppState {
           )
         })?;
 
+      let previous = task.clone();
       apply_patch(
         task,
         update.patch,
-        now
+        now,
+        times
       )?;
+      // Titles already over the limit
+      // are left alone until edited.
+      if previous.description
+        != task.description
+      {
+        limit.apply(task, now)?;
+      }
       task.modified = now;
-      task.clone()
+      (previous, task.clone())
     };
 
     store.save_pending(&pending)?;
-    Ok(task_to_dto(updated_task))
+    record_history(
+      &store,
+      "update",
+      Some(&previous),
+      Some(&updated_task),
+      now
+    )?;
+    let children = task_children(
+      &store,
+      &updated_task
+    )?;
+    Ok(task_to_dto(
+      updated_task,
+      children,
+      now
+    ))
   }
 
+  /// Completes the task, and its open
+  /// subtasks under
+  /// `on_complete.subtasks=cascade`.
+  /// There is no prompt, so `ask`
+  /// leaves them open like `warn`.
   #[instrument(skip(self))]
   pub fn done(
     &self,
-    uuid: Uuid
+    uuid: Uuid,
+    recur_mode: RecurMode,
+    policy: CompletionPolicy
   ) -> anyhow::Result<TaskDto> {
     let now = Utc::now();
     let store = self.store.lock();
@@ -244,28 +957,200 @@ impl AppState {
     let mut completed =
       store.load_completed()?;
 
-    let idx = pending
+    if !pending
       .iter()
-      .position(|task| {
-        task.uuid == uuid
-      })
+      .any(|task| task.uuid == uuid)
+    {
+      anyhow::bail!("task not found");
+    }
+    let tree: Vec<Task> = pending
+      .iter()
+      .chain(&completed)
+      .cloned()
+      .collect();
+    let open_children =
+      open_descendants(
+        &tree,
+        &BTreeSet::from([uuid])
+      );
+    let mut targets = vec![uuid];
+    if !open_children.is_empty()
+      && policy.cascade_subtasks(
+        open_children.len(),
+        |_| Ok(false)
+      )?
+    {
+      targets.extend(open_children);
+    }
+
+    let mut changes = Vec::new();
+    let mut finished = Vec::new();
+    for target in targets {
+      let Some(idx) =
+        pending.iter().position(
+          |task| task.uuid == target
+        )
+      else {
+        continue;
+      };
+      let mut task =
+        pending.remove(idx);
+      let previous = task.clone();
+      task.stop_clock(now);
+      task.status = Status::Completed;
+      task.end = Some(now);
+      task.modified = now;
+      completed.push(task.clone());
+      finished.push(task.clone());
+      changes
+        .push((Some(previous), task));
+    }
+    let task = finished
+      .first()
+      .cloned()
+      .context("task not found")?;
+
+    // Under `[recur] mode = "lazy"` the
+    // launch sweep creates the next
+    // instance once it is due.
+    if recur_mode == RecurMode::Eager {
+      for closed in &finished {
+        if let Some(next) =
+          next_instance(
+            closed,
+            &pending,
+            store.next_id(&pending),
+            now
+          )
+        {
+          pending.push(next.clone());
+          changes.push((None, next));
+        }
+      }
+    }
+
+    store.save_pending(&pending)?;
+    store.save_completed(&completed)?;
+    for (previous, next) in &changes {
+      record_history(
+        &store,
+        "done",
+        previous.as_ref(),
+        Some(next),
+        now
+      )?;
+    }
+    if policy.notify_unblocked {
+      for unblocked in newly_unblocked(
+        &pending, &finished
+      ) {
+        info!(
+          uuid = %unblocked.uuid,
+          description = %unblocked.description,
+          "task unblocked"
+        );
+      }
+    }
+
+    let children =
+      task_children(&store, &task)?;
+    Ok(task_to_dto(task, children, now))
+  }
+
+  /// Starts the task's clock; a task
+  /// already running keeps its original
+  /// start.
+  #[instrument(skip(self))]
+  pub fn start(
+    &self,
+    uuid: Uuid
+  ) -> anyhow::Result<TaskDto> {
+    self.update_clock(
+      uuid,
+      "start",
+      |task, now| {
+        if task.status
+          != Status::Pending
+          || task.is_waiting(now)
+        {
+          anyhow::bail!(
+            "only pending tasks can \
+             be started"
+          );
+        }
+        if task.start.is_none() {
+          task.start = Some(now);
+        }
+        Ok(())
+      }
+    )
+  }
+
+  /// Stops the task's clock and adds
+  /// the elapsed time to its focus
+  /// total.
+  #[instrument(skip(self))]
+  pub fn stop(
+    &self,
+    uuid: Uuid
+  ) -> anyhow::Result<TaskDto> {
+    self.update_clock(
+      uuid,
+      "stop",
+      |task, now| {
+        task.stop_clock(now);
+        Ok(())
+      }
+    )
+  }
+
+  fn update_clock(
+    &self,
+    uuid: Uuid,
+    op: &str,
+    change: impl FnOnce(
+      &mut Task,
+      chrono::DateTime<Utc>
+    )
+      -> anyhow::Result<()>
+  ) -> anyhow::Result<TaskDto> {
+    let now = Utc::now();
+    let store = self.store.lock();
+    let mut pending =
+      store.load_pending()?;
+    let task = pending
+      .iter_mut()
+      .find(|task| task.uuid == uuid)
       .ok_or_else(|| {
         anyhow::anyhow!(
           "task not found"
         )
       })?;
 
-    let mut task = pending.remove(idx);
-    task.status = Status::Completed;
-    task.end = Some(now);
-    task.modified = now;
-
-    completed.push(task.clone());
-
-    store.save_pending(&pending)?;
-    store.save_completed(&completed)?;
+    let previous = task.clone();
+    change(task, now)?;
+    let changed =
+      task.start != previous.start;
+    if changed {
+      task.modified = now;
+    }
+    let updated = task.clone();
+    if changed {
+      store.save_pending(&pending)?;
+      record_history(
+        &store,
+        op,
+        Some(&previous),
+        Some(&updated),
+        now
+      )?;
+    }
 
-    Ok(task_to_dto(task))
+    let children =
+      task_children(&store, &updated)?;
+    Ok(task_to_dto(
+      updated, children, now
+    ))
   }
 
   #[instrument(skip(self))]
@@ -293,9 +1178,30 @@ impl AppState {
 
     let mut task =
       completed.remove(idx);
+    let previous = task.clone();
+    // Drop the untouched instance its
+    // completion spawned so the series
+    // has one open instance again.
+    if let Some(successor) =
+      spawned_successor(&task, &pending)
+    {
+      pending.remove(successor);
+    }
     task.status = Status::Pending;
     task.end = None;
     task.modified = now;
+    // Keep the old id unless a task
+    // added since has reused it.
+    let id_taken =
+      task.id.is_none_or(|id| {
+        pending
+          .iter()
+          .any(|t| t.id == Some(id))
+      });
+    if id_taken {
+      task.id =
+        Some(store.next_id(&pending));
+    }
     pending.push(task.clone());
     pending.sort_by_key(|t| {
       t.id.unwrap_or(u64::MAX)
@@ -303,8 +1209,17 @@ impl AppState {
 
     store.save_pending(&pending)?;
     store.save_completed(&completed)?;
+    record_history(
+      &store,
+      "uncomplete",
+      Some(&previous),
+      Some(&task),
+      now
+    )?;
 
-    Ok(task_to_dto(task))
+    let children =
+      task_children(&store, &task)?;
+    Ok(task_to_dto(task, children, now))
   }
 
   #[instrument(skip(self))]
@@ -320,9 +1235,18 @@ impl AppState {
       .iter_mut()
       .find(|task| task.uuid == uuid)
     {
+      let previous = task.clone();
       task.status = Status::Deleted;
       task.modified = now;
+      let deleted = task.clone();
       store.save_pending(&pending)?;
+      record_history(
+        &store,
+        "delete",
+        Some(&previous),
+        Some(&deleted),
+        now
+      )?;
       debug!(
         uuid = %uuid,
         "marked pending task deleted"
@@ -332,12 +1256,22 @@ impl AppState {
 
     let mut completed =
       store.load_completed()?;
-    let before = completed.len();
+    let removed = completed
+      .iter()
+      .find(|task| task.uuid == uuid)
+      .cloned();
     completed
       .retain(|task| task.uuid != uuid);
-    if completed.len() != before {
+    if removed.is_some() {
       store
         .save_completed(&completed)?;
+      record_history(
+        &store,
+        "delete",
+        removed.as_ref(),
+        None,
+        now
+      )?;
       debug!(
         uuid = %uuid,
         "removed completed task"
@@ -347,6 +1281,83 @@ impl AppState {
 
     anyhow::bail!("task not found")
   }
+
+  #[instrument(skip(self))]
+  pub fn history(
+    &self,
+    uuid: Uuid
+  ) -> anyhow::Result<
+    Vec<TaskHistoryEntryDto>
+  > {
+    let store = self.store.lock();
+    let value = |value: &Value| {
+      (!value.is_null())
+        .then(|| display_value(value))
+    };
+    Ok(
+      store
+        .load_history()?
+        .into_iter()
+        .filter(|entry| {
+          entry.uuid == uuid
+        })
+        .map(|entry| {
+          TaskHistoryEntryDto {
+            at:      format_dto_date(
+              entry.at
+            ),
+            command: entry.command,
+            action:  entry
+              .action
+              .as_str()
+              .to_string(),
+            changes: entry
+              .changes
+              .iter()
+              .map(|change| {
+                TaskFieldChangeDto {
+                  field:  change
+                    .field
+                    .clone(),
+                  before: value(
+                    &change.before
+                  ),
+                  after:  value(
+                    &change.after
+                  )
+                }
+              })
+              .collect()
+          }
+        })
+        .collect()
+    )
+  }
+}
+
+/// Appends the audit-log entry for one
+/// GUI write. Commands are prefixed
+/// with `gui:` so the CLI `history`
+/// output shows where a change came
+/// from.
+fn record_history(
+  store: &DataStore,
+  op: &str,
+  before: Option<&Task>,
+  after: Option<&Task>,
+  now: chrono::DateTime<Utc>
+) -> anyhow::Result<()> {
+  let entries = diff_snapshots(
+    &format!("gui:{op}"),
+    before
+      .map(std::slice::from_ref)
+      .unwrap_or_default(),
+    after
+      .map(std::slice::from_ref)
+      .unwrap_or_default(),
+    now
+  );
+  store.append_history(&entries)
 }
 
 fn resolve_gui_data_dir() -> PathBuf {
@@ -365,229 +1376,147 @@ fn resolve_gui_data_dir() -> PathBuf {
   PathBuf::from(".rivet_gui_data")
 }
 
-fn priority_to_core(
-  priority: TaskPriority
-) -> String {
-  match priority {
-    | TaskPriority::Low => {
-      "L".to_string()
-    }
-    | TaskPriority::Medium => {
-      "M".to_string()
-    }
-    | TaskPriority::High => {
-      "H".to_string()
-    }
+/// Index lookups the list arguments
+/// pin down. A waiting view matches
+/// pending tasks with a future `wait`
+/// too, so it has no status key.
+fn list_index_keys(
+  args: &TasksListArgs
+) -> Vec<IndexKey> {
+  let mut keys = Vec::new();
+  if let Some(tag) = args.tag.as_ref() {
+    keys
+      .push(IndexKey::Tag(tag.clone()));
   }
-}
-
-fn priority_from_core(
-  priority: Option<String>
-) -> Option<TaskPriority> {
-  match priority.as_deref() {
-    | Some("L") | Some("low") => {
-      Some(TaskPriority::Low)
-    }
-    | Some("M") | Some("med")
-    | Some("medium") => {
-      Some(TaskPriority::Medium)
-    }
-    | Some("H") | Some("high") => {
-      Some(TaskPriority::High)
-    }
-    | _ => None
+  if let Some(project) =
+    args.project.as_ref()
+  {
+    keys.push(IndexKey::Project(
+      project.clone()
+    ));
   }
-}
-
-fn map_status(
-  status: Status
-) -> TaskStatus {
-  match status {
-    | Status::Pending => {
-      TaskStatus::Pending
+  let status = match &args.status {
+    | Some(TaskStatus::Pending) => {
+      Some(Status::Pending)
     }
-    | Status::Completed => {
-      TaskStatus::Completed
+    | Some(TaskStatus::Completed) => {
+      Some(Status::Completed)
     }
-    | Status::Deleted => {
-      TaskStatus::Deleted
+    | Some(TaskStatus::Deleted) => {
+      Some(Status::Deleted)
     }
-    | Status::Waiting => {
-      TaskStatus::Waiting
+    | Some(TaskStatus::Archived) => {
+      Some(Status::Archived)
     }
+    | Some(TaskStatus::Waiting)
+    | None => None
+  };
+  if let Some(status) = status {
+    keys.push(IndexKey::Status(status));
   }
+  keys
 }
 
-fn task_status_for_view(
-  task: &Task,
-  now: chrono::DateTime<Utc>
-) -> TaskStatus {
-  if task.status == Status::Pending
-    && task
-      .wait
-      .map(|wait| wait > now)
-      .unwrap_or(false)
-  {
-    return TaskStatus::Waiting;
-  }
-  map_status(task.status.clone())
+fn load_child_index(
+  store: &DataStore
+) -> anyhow::Result<
+  HashMap<Uuid, Vec<Uuid>>
+> {
+  let mut tasks =
+    store.load_pending()?;
+  tasks.extend(store.load_completed()?);
+  Ok(child_index(&tasks))
 }
 
-fn task_to_dto(task: Task) -> TaskDto {
-  let status = task_status_for_view(
-    &task,
-    Utc::now()
-  );
-  let title = task.description.clone();
-  let description =
-    task_detail_description(&task)
-      .unwrap_or_default();
-
-  TaskDto {
-    uuid: task.uuid,
-    id: task.id,
-    title,
-    description,
-    status,
-    project: task.project,
-    tags: task.tags,
-    priority: priority_from_core(
-      task.priority
-    ),
-    due: task.due.map(|d| {
-      d.format("%Y%m%dT%H%M%SZ")
-        .to_string()
-    }),
-    wait: task.wait.map(|d| {
-      d.format("%Y%m%dT%H%M%SZ")
-        .to_string()
-    }),
-    scheduled: task.scheduled.map(
-      |d| {
-        d.format("%Y%m%dT%H%M%SZ")
-          .to_string()
-      }
-    ),
-    created: Some(
-      task
-        .entry
-        .format("%Y%m%dT%H%M%SZ")
-        .to_string()
-    ),
-    modified: Some(
-      task
-        .modified
-        .format("%Y%m%dT%H%M%SZ")
-        .to_string()
-    )
-  }
+fn task_children(
+  store: &DataStore,
+  task: &Task
+) -> anyhow::Result<Vec<Uuid>> {
+  Ok(
+    load_child_index(store)?
+      .remove(&task.uuid)
+      .unwrap_or_default()
+  )
 }
 
-fn apply_patch(
-  task: &mut Task,
-  patch: TaskPatch,
-  now: chrono::DateTime<Utc>
-) -> anyhow::Result<()> {
-  if let Some(title) = patch.title {
-    let trimmed = title.trim();
-    if trimmed.is_empty() {
-      warn!(uuid = %task.uuid, "rejected empty task title update");
-      anyhow::bail!(
-        "task title is required"
-      );
-    }
-    task.description =
-      trimmed.to_string();
-  }
-
-  if let Some(description) =
-    patch.description
-  {
-    set_task_detail_description(
-      task,
-      &description
-    );
-  }
-  if let Some(project) = patch.project {
-    task.project = project;
-  }
-  if let Some(tags) = patch.tags {
-    task.tags = tags;
-  }
-  if let Some(priority) = patch.priority
-  {
-    task.priority =
-      priority.map(priority_to_core);
-  }
-
-  if let Some(due) = patch.due {
-    task.due = due
-      .as_deref()
-      .map(|value| {
-        parse_date_expr(value, now)
-      })
-      .transpose()?;
-  }
-  if let Some(wait) = patch.wait {
-    task.wait = wait
-      .as_deref()
-      .map(|value| {
-        parse_date_expr(value, now)
-      })
-      .transpose()?;
-  }
-  if let Some(scheduled) =
-    patch.scheduled
-  {
-    task.scheduled = scheduled
-      .as_deref()
-      .map(|value| {
-        parse_date_expr(value, now)
+fn parse_completed_bound(
+  value: Option<&str>,
+  field: &str
+) -> anyhow::Result<Option<NaiveDate>> {
+  value
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(|value| {
+      NaiveDate::parse_from_str(
+        value, "%Y-%m-%d"
+      )
+      .with_context(|| {
+        format!(
+          "invalid {field}: {value} \
+           (expected YYYY-MM-DD)"
+        )
       })
-      .transpose()?;
-  }
+    })
+    .transpose()
+}
 
-  if task
-    .wait
-    .map(|wait| wait <= now)
-    .unwrap_or(false)
-    && task.status == Status::Waiting
-  {
-    task.status = Status::Pending;
-  }
+/// Pulls `+VIRTUAL`/`-VIRTUAL` terms
+/// out of a list query so they are
+/// evaluated by the core filter; the
+/// remaining words stay a plain text
+/// search.
+fn split_query_virtual_tags(
+  query: &str
+) -> (Vec<(bool, VirtualTag)>, String) {
+  let mut virtual_tags = Vec::new();
+  let mut words = Vec::new();
 
-  debug!(uuid = %task.uuid, id = ?task.id, "task patch applied");
+  for word in query.split_whitespace() {
+    let parsed = word
+      .strip_prefix('+')
+      .and_then(VirtualTag::parse)
+      .map(|tag| (true, tag))
+      .or_else(|| {
+        word
+          .strip_prefix('-')
+          .and_then(VirtualTag::parse)
+          .map(|tag| (false, tag))
+      });
+    match parsed {
+      | Some(entry) => {
+        virtual_tags.push(entry)
+      }
+      | None => words.push(word)
+    }
+  }
 
-  Ok(())
+  (virtual_tags, words.join(" "))
 }
 
-fn task_detail_description(
-  task: &Task
-) -> Option<String> {
-  task
-    .extra
-    .get(RIVET_DETAIL_KEY)
-    .and_then(Value::as_str)
-    .map(str::trim)
-    .filter(|value| !value.is_empty())
-    .map(ToString::to_string)
-}
-
-fn set_task_detail_description(
+/// Board and lane tags are added by the
+/// UI itself, so they do not count as
+/// tags the user chose when deciding
+/// whether default tags apply.
+fn apply_task_defaults(
   task: &mut Task,
-  description: &str
+  defaults: &TaskDefaults
 ) {
-  let trimmed = description.trim();
-  if trimmed.is_empty() {
-    task.extra.remove(RIVET_DETAIL_KEY);
-  } else {
-    task.extra.insert(
-      RIVET_DETAIL_KEY.to_string(),
-      Value::String(
-        trimmed.to_string()
+  let (layout, chosen): (
+    Vec<String>,
+    Vec<String>
+  ) = std::mem::take(&mut task.tags)
+    .into_iter()
+    .partition(|tag| {
+      tag.split_once(':').is_some_and(
+        |(key, _)| {
+          key == KANBAN_LANE_KEY
+            || key == BOARD_TAG_KEY
+        }
       )
-    );
-  }
+    });
+  task.tags = chosen;
+  defaults.apply(task);
+  task.tags.extend(layout);
 }
 
 fn ensure_default_kanban_lane_tag(
@@ -611,6 +1540,78 @@ fn ensure_default_kanban_lane_tag(
 mod tests {
   use super::*;
 
+  #[test]
+  fn split_query_virtual_tags_separates_text_terms()
+   {
+    let (virtual_tags, text) =
+      split_query_virtual_tags(
+        "+OVERDUE call -TAGGED mom \
+         +home"
+      );
+    assert_eq!(text, "call mom +home");
+    assert_eq!(virtual_tags.len(), 2);
+    assert!(virtual_tags[0].0);
+    assert!(matches!(
+      virtual_tags[0].1,
+      VirtualTag::Overdue
+    ));
+    assert!(!virtual_tags[1].0);
+    assert!(matches!(
+      virtual_tags[1].1,
+      VirtualTag::Tagged
+    ));
+  }
+
+  #[test]
+  fn task_defaults_ignore_board_and_lane_tags()
+   {
+    let defaults = TaskDefaults {
+      project:      Some(
+        "home".to_string()
+      ),
+      tags:         vec![
+        "chores".to_string(),
+      ],
+      context_tags: Vec::new()
+    };
+    let mut task = Task::new_pending(
+      "Sweep".to_string(),
+      Utc::now(),
+      1
+    );
+    task.tags = vec![
+      "board:house".to_string(),
+      "kanban:todo".to_string(),
+    ];
+    apply_task_defaults(
+      &mut task, &defaults
+    );
+    assert_eq!(
+      task.project.as_deref(),
+      Some("home")
+    );
+    assert_eq!(task.tags, vec![
+      "chores",
+      "board:house",
+      "kanban:todo"
+    ]);
+
+    let mut tagged = Task::new_pending(
+      "Mow".to_string(),
+      Utc::now(),
+      2
+    );
+    tagged.tags =
+      vec!["area:garden".to_string()];
+    apply_task_defaults(
+      &mut tagged,
+      &defaults
+    );
+    assert_eq!(tagged.tags, vec![
+      "area:garden"
+    ]);
+  }
+
   #[test]
   fn ensure_default_kanban_lane_tag_adds_default_when_missing()
    {
@@ -0,0 +1,48 @@
+import { describe, expect, it, vi } from "vitest";
+
+import { InvokeTimeoutError, isInvokeTimeout, retryWithBackoff, withTimeout } from "./invoke";
+
+describe("invoke", () => {
+  it("rejects with a typed timeout error once the budget runs out", async () => {
+    const never = () => new Promise<string>(() => {});
+    const error = await withTimeout(never, 5, () => new InvokeTimeoutError("tasks_list", 5, "req-1")).catch((caught) => caught);
+    expect(isInvokeTimeout(error)).toBe(true);
+    expect(error).toMatchObject({ command: "tasks_list", timeoutMs: 5, requestId: "req-1" });
+    await expect(withTimeout(async () => "ok", 50, () => new Error("late"))).resolves.toBe("ok");
+  });
+
+  it("retries transport failures with doubling backoff", async () => {
+    const onRetry = vi.fn();
+    let calls = 0;
+    const result = await retryWithBackoff(
+      async () => {
+        calls += 1;
+        if (calls < 3) {
+          throw new InvokeTimeoutError("tasks_list", 5, `req-${calls}`);
+        }
+        return "tasks";
+      },
+      { retries: 2, backoffMs: 1 },
+      onRetry
+    );
+    expect(result).toBe("tasks");
+    expect(onRetry.mock.calls.map(([, attempt, delay]) => [attempt, delay])).toEqual([
+      [1, 1],
+      [2, 2]
+    ]);
+  });
+
+  it("gives up after the last retry and never retries backend errors", async () => {
+    const timeouts = vi.fn(async () => {
+      throw new InvokeTimeoutError("tasks_list", 5, "req");
+    });
+    await expect(retryWithBackoff(timeouts, { retries: 1, backoffMs: 0 }, () => {})).rejects.toBeInstanceOf(InvokeTimeoutError);
+    expect(timeouts).toHaveBeenCalledTimes(2);
+
+    const denied = vi.fn(async () => {
+      throw "permission denied: tasks.write";
+    });
+    await expect(retryWithBackoff(denied, { retries: 3, backoffMs: 0 }, () => {})).rejects.toBe("permission denied: tasks.write");
+    expect(denied).toHaveBeenCalledTimes(1);
+  });
+});
@@ -0,0 +1,75 @@
+// Timeout and retry policy shared by every backend call in `tauri.ts`.
+
+export interface InvokeOptions {
+  // Per-attempt budget; the call fails with `InvokeTimeoutError` once it runs out.
+  timeoutMs?: number;
+  // Extra attempts after the first. Only for idempotent reads.
+  retries?: number;
+  // Delay before the first retry, doubled for each one after it.
+  backoffMs?: number;
+}
+
+export const DEFAULT_TIMEOUT_MS = 30_000;
+export const EXTERNAL_CALENDAR_TIMEOUT_MS = 90_000;
+
+// Reads that are safe to repeat ride out a short IPC hiccup.
+export const IDEMPOTENT_READ: InvokeOptions = { retries: 2, backoffMs: 250 };
+
+export class InvokeTimeoutError extends Error {
+  readonly command: string;
+  readonly timeoutMs: number;
+  readonly requestId: string;
+
+  constructor(command: string, timeoutMs: number, requestId: string) {
+    super(`invoke timeout (${command}) after ${timeoutMs}ms request_id=${requestId}`);
+    this.name = "InvokeTimeoutError";
+    this.command = command;
+    this.timeoutMs = timeoutMs;
+    this.requestId = requestId;
+  }
+}
+
+export function isInvokeTimeout(error: unknown): error is InvokeTimeoutError {
+  return error instanceof InvokeTimeoutError;
+}
+
+// Backend commands reject with their error string; those are answers, not hiccups,
+// so only timeouts and transport failures are worth another attempt.
+export function isRetryableInvokeError(error: unknown): boolean {
+  return typeof error !== "string";
+}
+
+export async function withTimeout<R>(run: () => Promise<R>, timeoutMs: number, onTimeout: () => Error): Promise<R> {
+  let timeoutId: ReturnType<typeof setTimeout> | null = null;
+  const timeout = new Promise<never>((_, reject) => {
+    timeoutId = setTimeout(() => reject(onTimeout()), timeoutMs);
+  });
+  try {
+    return await Promise.race([run(), timeout]);
+  } finally {
+    if (timeoutId !== null) {
+      clearTimeout(timeoutId);
+    }
+  }
+}
+
+export async function retryWithBackoff<R>(
+  attempt: (attemptIndex: number) => Promise<R>,
+  options: Pick<InvokeOptions, "retries" | "backoffMs">,
+  onRetry: (error: unknown, attemptIndex: number, delayMs: number) => void
+): Promise<R> {
+  const retries = Math.max(0, options.retries ?? 0);
+  const backoffMs = Math.max(0, options.backoffMs ?? 0);
+  for (let index = 0; ; index += 1) {
+    try {
+      return await attempt(index);
+    } catch (error) {
+      if (index >= retries || !isRetryableInvokeError(error)) {
+        throw error;
+      }
+      const delayMs = backoffMs * 2 ** index;
+      onRetry(error, index + 1, delayMs);
+      await new Promise((resolve) => setTimeout(resolve, delayMs));
+    }
+  }
+}
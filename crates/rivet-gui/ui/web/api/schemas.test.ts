@@ -6,6 +6,8 @@ import {
   ContactUpdateArgsSchema,
   ContactsDedupeDecideResultSchema,
   ContactsDedupePreviewResultSchema,
+  ContactsDeleteBulkResultSchema,
+  ContactsDeleteUndoResultSchema,
   ContactsImportCommitResultSchema,
   ContactsImportPreviewResultSchema,
   ContactsListResultSchema,
@@ -16,12 +18,15 @@ import {
   ExternalCalendarSourceSchema,
   ExternalCalendarSyncResultSchema,
   MapHealthResultSchema,
+  PermissionsSnapshotSchema,
   RivetRuntimeConfigSchema,
   TagSchemaSchema,
   TaskCreateSchema,
   TaskDtoArraySchema,
   TaskDtoSchema,
-  TaskUpdateArgsSchema
+  TaskHistoryEntryArraySchema,
+  TaskUpdateArgsSchema,
+  TasksListPageSchema
 } from "./schemas";
 
 describe("tauri command contract schemas", () => {
@@ -39,11 +44,57 @@ describe("tauri command contract schemas", () => {
       wait: null,
       scheduled: null,
       created: "2026-02-20T10:11:12Z",
-      modified: "2026-02-20T10:11:12Z"
+      modified: "2026-02-20T10:11:12Z",
+      completed: null,
+      started: null,
+      focus_seconds: 0,
+      parent: null,
+      children: ["6b1f7a52-3c3d-4d0e-9a43-0c5e5f0f6a11"],
+      contact: null
     };
 
     expect(TaskDtoSchema.parse(task)).toEqual(task);
     expect(TaskDtoArraySchema.parse([task])).toEqual([task]);
+    expect(TasksListPageSchema.parse({ tasks: [task], total: 3 })).toEqual({ tasks: [task], total: 3 });
+  });
+
+  it("defaults subtask links, completion and focus time for payloads without them", () => {
+    const parsed = TaskDtoSchema.parse({
+      uuid: "0f84cb8d-6239-4ae4-9f89-3680af7bd836",
+      id: null,
+      title: "Legacy task",
+      description: "",
+      status: "Pending",
+      project: null,
+      tags: [],
+      priority: null,
+      due: null,
+      wait: null,
+      scheduled: null,
+      created: null,
+      modified: null
+    });
+
+    expect(parsed.parent).toBeNull();
+    expect(parsed.children).toEqual([]);
+    expect(parsed.completed).toBeNull();
+    expect(parsed.started).toBeNull();
+    expect(parsed.focus_seconds).toBe(0);
+  });
+
+  it("accepts task history entries and defaults missing changes", () => {
+    const parsed = TaskHistoryEntryArraySchema.parse([
+      {
+        at: "20260301T120000Z",
+        command: "gui:update",
+        action: "modified",
+        changes: [{ field: "project", before: null, after: "home" }]
+      },
+      { at: "20260301T130000Z", command: "purge", action: "removed" }
+    ]);
+
+    expect(parsed[0].changes[0].after).toBe("home");
+    expect(parsed[1].changes).toEqual([]);
   });
 
   it("rejects invalid task status values", () => {
@@ -137,12 +188,18 @@ describe("tauri command contract schemas", () => {
       source_kind: "local",
       remote_id: null,
       link_group_id: null,
+      categories: ["work"],
       created_at: "2026-02-26T00:00:00Z",
       updated_at: "2026-02-26T00:00:00Z"
     };
 
     expect(ContactDtoSchema.parse(contact)).toEqual(contact);
-    expect(ContactsListResultSchema.parse({ contacts: [contact], next_cursor: null, total: 1 })).toEqual({ contacts: [contact], next_cursor: null, total: 1 });
+    const legacyContact: Partial<typeof contact> = { ...contact };
+    delete legacyContact.categories;
+    expect(ContactDtoSchema.parse(legacyContact).categories).toEqual([]);
+    const listed = { contacts: [contact], next_cursor: null, total: 1, categories: [{ category: "work", count: 1 }] };
+    expect(ContactsListResultSchema.parse(listed)).toEqual(listed);
+    expect(ContactsListResultSchema.parse({ contacts: [], next_cursor: null, total: 0 }).categories).toEqual([]);
     expect(ContactCreateSchema.parse({
       display_name: "Alex Morgan",
       avatar_data_url: null,
@@ -162,7 +219,8 @@ describe("tauri command contract schemas", () => {
       source_id: "local",
       source_kind: "local",
       remote_id: null,
-      link_group_id: null
+      link_group_id: null,
+      categories: []
     })).toBeTruthy();
     expect(ContactUpdateArgsSchema.parse({ id: contact.id, patch: { source_file_name: "import.vcf", import_batch_id: "batch-1" } })).toEqual({
       id: contact.id,
@@ -204,6 +262,14 @@ describe("tauri command contract schemas", () => {
       restored: 1,
       undo_id: "undo-1"
     })).toBeTruthy();
+    expect(ContactsDeleteBulkResultSchema.parse({
+      deleted: 0,
+      undo_id: null
+    })).toBeTruthy();
+    expect(ContactsDeleteUndoResultSchema.parse({
+      restored: 2,
+      undo_id: "undo-2"
+    })).toBeTruthy();
   });
 
   it("accepts runtime config and tag schema passthrough fields", () => {
@@ -246,6 +312,13 @@ describe("tauri command contract schemas", () => {
     expect(TagSchemaSchema.parse(tags)).toMatchObject(tags);
   });
 
+  it("keeps known permission categories and drops unknown ones", () => {
+    expect(PermissionsSnapshotSchema.parse({ disabled: ["tasks_delete", "bogus"] })).toEqual({
+      disabled: ["tasks_delete"]
+    });
+    expect(PermissionsSnapshotSchema.parse({})).toEqual({ disabled: [] });
+  });
+
   it("accepts dictionary search and entry payloads", () => {
     const search = {
       query: "rivet",
@@ -1,6 +1,6 @@
 import { z } from "zod";
 
-export const TaskStatusSchema = z.enum(["Pending", "Completed", "Deleted", "Waiting"]);
+export const TaskStatusSchema = z.enum(["Pending", "Completed", "Deleted", "Waiting", "Archived"]);
 export const TaskPrioritySchema = z.enum(["Low", "Medium", "High"]);
 
 export const TaskDtoSchema = z.object({
@@ -16,11 +16,22 @@ export const TaskDtoSchema = z.object({
   wait: z.string().nullable(),
   scheduled: z.string().nullable(),
   created: z.string().nullable(),
-  modified: z.string().nullable()
+  modified: z.string().nullable(),
+  completed: z.string().nullable().default(null),
+  started: z.string().nullable().default(null),
+  focus_seconds: z.number().int().nonnegative().default(0),
+  parent: z.string().nullable().default(null),
+  children: z.array(z.string()).default([]),
+  contact: z.string().nullable().default(null)
 });
 
 export const TaskDtoArraySchema = z.array(TaskDtoSchema);
 
+export const TasksListPageSchema = z.object({
+  tasks: TaskDtoArraySchema,
+  total: z.number().int().nonnegative()
+});
+
 export const TaskCreateSchema = z.object({
   title: z.string().min(1),
   description: z.string(),
@@ -29,7 +40,8 @@ export const TaskCreateSchema = z.object({
   priority: TaskPrioritySchema.nullable(),
   due: z.string().nullable(),
   wait: z.string().nullable(),
-  scheduled: z.string().nullable()
+  scheduled: z.string().nullable(),
+  contact: z.string().nullable().optional()
 });
 
 export const TaskPatchSchema = z.object({
@@ -40,7 +52,8 @@ export const TaskPatchSchema = z.object({
   priority: TaskPrioritySchema.nullable().optional(),
   due: z.string().nullable().optional(),
   wait: z.string().nullable().optional(),
-  scheduled: z.string().nullable().optional()
+  scheduled: z.string().nullable().optional(),
+  contact: z.string().nullable().optional()
 });
 
 export const TaskUpdateArgsSchema = z.object({
@@ -48,6 +61,56 @@ export const TaskUpdateArgsSchema = z.object({
   patch: TaskPatchSchema
 });
 
+export const TaskFieldChangeSchema = z.object({
+  field: z.string(),
+  before: z.string().nullable(),
+  after: z.string().nullable()
+});
+
+export const TaskHistoryEntrySchema = z.object({
+  at: z.string(),
+  command: z.string(),
+  action: z.string(),
+  changes: z.array(TaskFieldChangeSchema).default([])
+});
+
+export const TaskHistoryEntryArraySchema = z.array(TaskHistoryEntrySchema);
+
+export const TaskDedupeGroupArraySchema = z.array(
+  z.object({
+    score: z.number().int().min(0).max(100),
+    reason: z.string(),
+    tasks: TaskDtoArraySchema
+  })
+);
+
+export const TasksDedupeApplyResultSchema = z.object({
+  removed: z.number().int().min(0)
+});
+
+export const TagsRemapResultSchema = z.object({
+  changed: z.number().int().min(0)
+});
+
+export const TagUsageArraySchema = z.array(
+  z.object({
+    tag: z.string(),
+    count: z.number().int().min(0),
+    open: z.number().int().min(0),
+    last_used: z.string()
+  })
+);
+
+export const TagsCleanupResultSchema = z.object({
+  removed: z.array(z.string()),
+  changed: z.number().int().min(0)
+});
+
+export const DemoDataResultSchema = z.object({
+  tasks: z.number().int().min(0),
+  board_id: z.string().min(1)
+});
+
 export const ContactFieldValueSchema = z.object({
   value: z.string(),
   kind: z.string(),
@@ -84,16 +147,24 @@ export const ContactDtoSchema = z.object({
   source_kind: z.string(),
   remote_id: z.string().nullable(),
   link_group_id: z.string().nullable(),
+  categories: z.array(z.string()).default([]),
   created_at: z.string(),
-  updated_at: z.string()
+  updated_at: z.string(),
+  last_contacted: z.string().nullable().optional().default(null)
 });
 
 export const ContactDtoArraySchema = z.array(ContactDtoSchema);
 
+export const ContactCategoryCountSchema = z.object({
+  category: z.string(),
+  count: z.number().int().min(0)
+});
+
 export const ContactsListResultSchema = z.object({
   contacts: ContactDtoArraySchema,
   next_cursor: z.string().nullable(),
-  total: z.number().int().min(0)
+  total: z.number().int().min(0),
+  categories: z.array(ContactCategoryCountSchema).default([])
 });
 
 export const ContactCreateSchema = z.object({
@@ -115,7 +186,8 @@ export const ContactCreateSchema = z.object({
   source_id: z.string().nullable(),
   source_kind: z.string().nullable(),
   remote_id: z.string().nullable(),
-  link_group_id: z.string().nullable()
+  link_group_id: z.string().nullable(),
+  categories: z.array(z.string())
 });
 
 export const ContactPatchSchema = z.object({
@@ -137,7 +209,8 @@ export const ContactPatchSchema = z.object({
   source_id: z.string().nullable().optional(),
   source_kind: z.string().nullable().optional(),
   remote_id: z.string().nullable().optional(),
-  link_group_id: z.string().nullable().optional()
+  link_group_id: z.string().nullable().optional(),
+  categories: z.array(z.string()).optional()
 });
 
 export const ContactUpdateArgsSchema = z.object({
@@ -165,10 +238,29 @@ export const ContactsDedupeDecideResultSchema = z.object({
 
 export const ContactOpenActionResultSchema = z.object({
   launched: z.boolean(),
-  url: z.string().min(1)
+  url: z.string().min(1),
+  logged: z.boolean().default(false)
+});
+
+export const ContactInteractionSchema = z.object({
+  id: z.string().min(1),
+  contact_id: z.string().min(1),
+  kind: z.string(),
+  value: z.string(),
+  at: z.string().min(1)
+});
+
+export const ContactInteractionsResultSchema = z.object({
+  interactions: z.array(ContactInteractionSchema)
+});
+
+export const ContactInteractionsPruneResultSchema = z.object({
+  removed: z.number().int().nonnegative(),
+  remaining: z.number().int().nonnegative()
 });
 
 export const ContactImportConflictSchema = z.object({
+  row: z.number().int().min(0).default(0),
   imported: ContactDtoSchema,
   existing: ContactDtoSchema,
   score: z.number().int().min(0),
@@ -192,6 +284,7 @@ export const ContactsImportCommitResultSchema = z.object({
   created: z.number().int().min(0),
   updated: z.number().int().min(0),
   skipped: z.number().int().min(0),
+  linked: z.number().int().min(0).default(0),
   failed: z.number().int().min(0),
   conflicts: z.number().int().min(0),
   errors: z.array(z.string())
@@ -200,7 +293,13 @@ export const ContactsImportCommitResultSchema = z.object({
 export const ContactsMergeResultSchema = z.object({
   merged: ContactDtoSchema,
   removed_ids: z.array(z.string().min(1)),
-  undo_id: z.string().min(1)
+  undo_id: z.string().min(1),
+  relinked_tasks: z.number().int().min(0).default(0)
+});
+
+export const ContactsMergePreviewResultSchema = z.object({
+  merged: ContactDtoSchema,
+  removed_ids: z.array(z.string().min(1))
 });
 
 export const ContactsMergeUndoResultSchema = z.object({
@@ -208,6 +307,21 @@ export const ContactsMergeUndoResultSchema = z.object({
   undo_id: z.string().min(1)
 });
 
+export const ContactsDeleteBulkResultSchema = z.object({
+  deleted: z.number().int().min(0),
+  undo_id: z.string().min(1).nullable(),
+  linked_tasks: z.number().int().min(0).default(0)
+});
+
+export const ContactsDeleteUndoResultSchema = z.object({
+  restored: z.number().int().min(0),
+  undo_id: z.string().min(1)
+});
+
+export const ContactsUnlinkResultSchema = z.object({
+  contacts: ContactDtoArraySchema
+});
+
 export const ExternalCalendarSourceSchema = z.object({
   id: z.string().min(1),
   name: z.string(),
@@ -230,6 +344,29 @@ export const ExternalCalendarSyncResultSchema = z.object({
   refresh_minutes: z.number().int().min(0)
 });
 
+export const ExternalCalendarSyncStatusArraySchema = z.array(
+  z.object({
+    calendar_id: z.string(),
+    last_sync: z.string().nullable(),
+    last_error: z.string().nullable(),
+    next_sync: z.string().nullable(),
+    state: z.enum(["idle", "queued", "running"])
+  })
+);
+
+export const ExternalCalendarSyncActivitySchema = z.object({
+  queued: z.array(z.string()),
+  running: z.array(z.string())
+});
+
+export const ExternalCalendarSyncEventSchema = z.object({
+  calendar_id: z.string(),
+  name: z.string(),
+  synced_at: z.string(),
+  result: ExternalCalendarSyncResultSchema.nullable(),
+  error: z.string().nullable()
+});
+
 export const ExternalCalendarCacheEntrySchema = z.object({
   cache_id: z.string().min(1),
   name: z.string(),
@@ -299,6 +436,24 @@ export const DictionaryEntrySchema = z.object({
   source_table: z.string()
 });
 
+export const CommandCategorySchema = z.enum([
+  "tasks_write",
+  "tasks_delete",
+  "contacts_write",
+  "contacts_delete",
+  "calendar_import",
+  "config_write"
+]);
+
+export const PermissionsSnapshotSchema = z.object({
+  disabled: z.array(z.string()).default([]).transform((names) =>
+    names.flatMap((name) => {
+      const parsed = CommandCategorySchema.safeParse(name);
+      return parsed.success ? [parsed.data] : [];
+    })
+  )
+});
+
 export const MapHealthResultSchema = z.object({
   base_url: z.string(),
   catalog_url: z.string(),
@@ -313,6 +468,7 @@ export const TagKeySchema = z.object({
   id: z.string(),
   label: z.string().optional(),
   selection: z.string().optional(),
+  group: z.string().optional(),
   color: z.string().optional(),
   allow_custom_values: z.boolean().optional(),
   values: z.array(z.string()).optional()
@@ -323,6 +479,12 @@ export const TagSchemaSchema = z.object({
   keys: z.array(TagKeySchema).optional()
 }).passthrough();
 
+export const CalendarMarkerConfigSchema = z.object({
+  shape: z.string().optional(),
+  color: z.string().optional(),
+  label: z.string().optional()
+}).passthrough();
+
 export const RivetRuntimeConfigSchema = z.object({
   version: z.number().int().optional(),
   mode: z.string().optional(),
@@ -335,14 +497,42 @@ export const RivetRuntimeConfigSchema = z.object({
     file_prefix: z.string().optional()
   }).passthrough().optional(),
   time: z.object({
-    timezone: z.string().optional()
+    timezone: z.string().optional(),
+    dateformat: z.string().optional()
   }).passthrough().optional(),
   notifications: z.object({
     due: z.object({
       enabled: z.boolean().optional(),
       pre_notify_enabled: z.boolean().optional(),
       pre_notify_minutes: z.number().int().optional(),
-      scan_interval_seconds: z.number().int().optional()
+      scan_interval_seconds: z.number().int().optional(),
+      quiet_hours: z.string().optional()
+    }).passthrough().optional()
+  }).passthrough().optional(),
+  agenda: z.object({
+    on_launch: z.boolean().optional()
+  }).passthrough().optional(),
+  capture: z.object({
+    tag: z.string().optional(),
+    tags: z.array(z.string()).optional(),
+    project: z.string().optional(),
+    priority: z.string().optional()
+  }).passthrough().optional(),
+  stale: z.object({
+    days: z.number().int().optional(),
+    include_waiting: z.boolean().optional(),
+    include_blocked: z.boolean().optional()
+  }).passthrough().optional(),
+  dedupe: z.object({
+    threshold: z.number().int().optional()
+  }).passthrough().optional(),
+  focus: z.object({
+    pomodoro_minutes: z.number().optional()
+  }).passthrough().optional(),
+  tasks: z.object({
+    title: z.object({
+      max_length: z.number().int().optional(),
+      overflow: z.string().optional()
     }).passthrough().optional()
   }).passthrough().optional(),
   ui: z.object({
@@ -355,6 +545,13 @@ export const RivetRuntimeConfigSchema = z.object({
       contacts: z.boolean().optional(),
       dictionary: z.boolean().optional(),
       map: z.boolean().optional()
+    }).passthrough().optional(),
+    task_list: z.object({
+      row_actions: z.array(z.string()).optional(),
+      id_display: z.string().optional()
+    }).passthrough().optional(),
+    task_editor: z.object({
+      draft_autosave_seconds: z.number().optional()
     }).passthrough().optional()
   }).passthrough().optional(),
   map: z.object({
@@ -391,9 +588,11 @@ export const RivetRuntimeConfigSchema = z.object({
   calendar: z.object({
     version: z.number().int().optional(),
     timezone: z.string().optional(),
+    locale: z.string().optional(),
     policies: z.object({
       week_start: z.string().optional(),
       red_dot_limit: z.number().int().optional(),
+      day_marker_limit: z.number().int().optional(),
       task_list_limit: z.number().int().optional(),
       task_list_window_days: z.number().int().optional()
     }).passthrough().optional(),
@@ -410,11 +609,66 @@ export const RivetRuntimeConfigSchema = z.object({
     toggles: z.object({
       de_emphasize_past_periods: z.boolean().optional(),
       filter_tasks_before_now: z.boolean().optional(),
-      hide_past_markers: z.boolean().optional()
+      hide_past_markers: z.boolean().optional(),
+      show_week_numbers: z.boolean().optional(),
+      show_due_markers: z.boolean().optional(),
+      show_scheduled_markers: z.boolean().optional()
+    }).passthrough().optional(),
+    markers: z.object({
+      kanban: CalendarMarkerConfigSchema.optional(),
+      external: CalendarMarkerConfigSchema.optional(),
+      unassigned: CalendarMarkerConfigSchema.optional(),
+      scheduled: CalendarMarkerConfigSchema.optional(),
+      tags: z.record(z.string(), CalendarMarkerConfigSchema).optional()
     }).passthrough().optional()
+  }).passthrough().optional(),
+  effective: z.object({
+    timezone: z.string().optional()
   }).passthrough().optional()
 }).passthrough();
 
+export const ConfigUpdateResultSchema = z.object({
+  section: z.string(),
+  key: z.string(),
+  status: z.enum(["applied", "unchanged", "rejected", "skipped"]),
+  reason: z.string().nullable(),
+  previous: z.unknown(),
+  effective: z.unknown()
+});
+
+export const ConfigApplyResultSchema = z.object({
+  saved: z.boolean(),
+  results: z.array(ConfigUpdateResultSchema),
+  config: RivetRuntimeConfigSchema
+});
+
+export const ConfigBundleImportResultSchema = z.object({
+  applied: z.boolean(),
+  conflicts: z.array(z.string()),
+  written: z.array(z.string()),
+  kept: z.array(z.string()),
+  unchanged: z.array(z.string()),
+  backups: z.array(z.string()),
+  skipped: z.array(z.string()),
+  gui: z.unknown().nullable()
+});
+
+export const KanbanBoardDefSchema = z
+  .object({
+    id: z.string().min(1),
+    name: z.string().min(1),
+    color: z.string()
+  })
+  .passthrough();
+
+// The `gui` section of a settings bundle; either list may be absent.
+export const GuiSettingsBundleSchema = z
+  .object({
+    kanban_boards: z.array(KanbanBoardDefSchema).optional(),
+    external_calendars: z.array(ExternalCalendarSourceSchema).optional()
+  })
+  .passthrough();
+
 export function describeSchemaError(prefix: string, error: z.ZodError): string {
   const firstIssue = error.issues[0];
   if (!firstIssue) {
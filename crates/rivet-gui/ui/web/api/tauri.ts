@@ -1,34 +1,69 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { z } from "zod";
 import type { ZodType } from "zod";
 
+import { contactHasCategory, countContactCategories, normalizeCategories, parseVcardCategories } from "../lib/contactCategories";
+import { contactedWithinDays, lastContactedByContact, sortContactsByRecency } from "../lib/contactInteractions";
 import { logger, setLoggerBridge } from "../lib/logger";
 import {
+  DEFAULT_TIMEOUT_MS,
+  EXTERNAL_CALENDAR_TIMEOUT_MS,
+  IDEMPOTENT_READ,
+  InvokeTimeoutError,
+  retryWithBackoff,
+  withTimeout,
+  type InvokeOptions
+} from "./invoke";
+import { DEMO_BOARD_ID, isDemoTask } from "../lib/tags";
+import { taskUrgency } from "../lib/taskSort";
+import {
+  ConfigApplyResultSchema,
+  ConfigBundleImportResultSchema,
   ContactCreateSchema,
   ContactDtoArraySchema,
   ContactDtoSchema,
+  ContactInteractionSchema,
+  ContactInteractionsPruneResultSchema,
+  ContactInteractionsResultSchema,
   ContactOpenActionResultSchema,
   ContactUpdateArgsSchema,
   ContactsDedupeDecideResultSchema,
   ContactsDedupePreviewResultSchema,
+  ContactsDeleteBulkResultSchema,
+  ContactsDeleteUndoResultSchema,
   ContactsImportCommitResultSchema,
   ContactsImportPreviewResultSchema,
   ContactsListResultSchema,
+  ContactsMergePreviewResultSchema,
   ContactsMergeResultSchema,
   ContactsMergeUndoResultSchema,
+  ContactsUnlinkResultSchema,
+  DemoDataResultSchema,
   DictionaryEntrySchema,
   MapHealthResultSchema,
+  PermissionsSnapshotSchema,
   DictionarySearchArgsSchema,
   DictionarySearchResultSchema,
   ExternalCalendarCacheEntryArraySchema,
   ExternalCalendarSourceSchema,
+  ExternalCalendarSyncActivitySchema,
+  ExternalCalendarSyncEventSchema,
   ExternalCalendarSyncResultSchema,
+  ExternalCalendarSyncStatusArraySchema,
   RivetRuntimeConfigSchema,
   TagSchemaSchema,
+  TagUsageArraySchema,
+  TagsCleanupResultSchema,
+  TagsRemapResultSchema,
   TaskCreateSchema,
+  TaskDedupeGroupArraySchema,
   TaskDtoArraySchema,
   TaskDtoSchema,
+  TaskHistoryEntryArraySchema,
   TaskUpdateArgsSchema,
+  TasksDedupeApplyResultSchema,
+  TasksListPageSchema,
   describeSchemaError
 } from "./schemas";
 import type {
@@ -36,6 +71,11 @@ import type {
   ContactFieldValue,
   ContactDto,
   ContactIdArg,
+  ContactInteraction,
+  ContactInteractionsArgs,
+  ContactInteractionsPruneArgs,
+  ContactInteractionsPruneResult,
+  ContactInteractionsResult,
   ContactOpenActionArgs,
   ContactOpenActionResult,
   ContactUpdateArgs,
@@ -44,6 +84,9 @@ import type {
   ContactsDedupeDecideResult,
   ContactsDedupePreviewResult,
   ContactsDeleteBulkArgs,
+  ContactsDeleteBulkResult,
+  ContactsDeleteUndoArgs,
+  ContactsDeleteUndoResult,
   ContactsImportCommitArgs,
   ContactsImportCommitResult,
   ContactsImportPreviewArgs,
@@ -51,22 +94,40 @@ import type {
   ContactsListArgs,
   ContactsListResult,
   ContactsMergeArgs,
+  ContactsMergePreviewResult,
   ContactsMergeResult,
   ContactsMergeUndoArgs,
   ContactsMergeUndoResult,
+  ContactsUnlinkResult,
+  DemoDataResult,
   DictionaryEntry,
   DictionaryEntryArgs,
   MapHealthArgs,
   MapHealthResult,
+  PermissionsSnapshot,
   DictionarySearchArgs,
   DictionarySearchResult,
   ExternalCalendarCacheEntry,
   ExternalCalendarSource,
+  ExternalCalendarSyncActivity,
+  ExternalCalendarSyncEvent,
   ExternalCalendarSyncResult,
+  ExternalCalendarSyncStatus,
+  TagUsage,
+  TagsCleanupResult,
+  TagsRemapArgs,
+  TagsRemapResult,
   TaskCreate,
+  TaskDedupeGroup,
   TaskDto,
+  TaskHistoryEntry,
   TaskIdArg,
+  TasksDedupeApplyArgs,
+  TasksDedupeApplyResult,
+  TasksDedupeArgs,
   TasksListArgs,
+  TasksListPage,
+  TasksStaleArgs,
   TaskUpdateArgs
 } from "../types/core";
 import type { RivetRuntimeConfig, TagSchema } from "../types/config";
@@ -75,9 +136,9 @@ const MOCK_TASKS_KEY = "rivet.mock.tasks";
 const MOCK_CONTACTS_KEY = "rivet.mock.contacts";
 const MOCK_CONTACTS_DEDUPE_DECISIONS_KEY = "rivet.mock.contacts.dedupe.decisions";
 const MOCK_CONTACTS_MERGE_UNDO_KEY = "rivet.mock.contacts.merge.undo";
+const MOCK_CONTACTS_DELETE_UNDO_KEY = "rivet.mock.contacts.delete.undo";
+const MOCK_CONTACTS_INTERACTIONS_KEY = "rivet.mock.contacts.interactions";
 const MOCK_DICTIONARY_DATA_KEY = "rivet.mock.dictionary.entries";
-const DEFAULT_TIMEOUT_MS = 30_000;
-const EXTERNAL_CALENDAR_TIMEOUT_MS = 90_000;
 const DEFAULT_TASK_QUERY: TasksListArgs = {
   query: null,
   status: null,
@@ -89,7 +150,8 @@ const DEFAULT_CONTACTS_QUERY: ContactsListArgs = {
   limit: 200,
   cursor: null,
   source: null,
-  updated_after: null
+  updated_after: null,
+  category: null
 };
 const DEFAULT_DICTIONARY_QUERY: DictionarySearchArgs = {
   language: null,
@@ -121,6 +183,41 @@ export interface ConfigEntryUpdate {
   value: string | number | boolean;
 }
 
+export type ConfigUpdateStatus = "applied" | "unchanged" | "rejected" | "skipped";
+
+export interface ConfigUpdateResult {
+  section: string;
+  key: string;
+  status: ConfigUpdateStatus;
+  reason: string | null;
+  previous: unknown;
+  // What rivet.toml holds for the key after the call.
+  effective: unknown;
+}
+
+// Updates are all-or-nothing: one rejected value leaves the file untouched and
+// marks the rest "skipped".
+export interface ConfigApplyResult {
+  saved: boolean;
+  results: ConfigUpdateResult[];
+  config: RivetRuntimeConfig;
+}
+
+export type ConfigBundleConflictPolicy = "ask" | "overwrite" | "keep";
+
+// File lists name bundle entries ("rivet.toml", "tags.toml"). With "ask" and
+// conflicts nothing is written and `applied` is false.
+export interface ConfigBundleImportResult {
+  applied: boolean;
+  conflicts: string[];
+  written: string[];
+  kept: string[];
+  unchanged: string[];
+  backups: string[];
+  skipped: string[];
+  gui: unknown;
+}
+
 function resolveRuntimeTransportMode(): RuntimeTransportMode {
   const raw = String(import.meta.env.VITE_RIVET_UI_RUNTIME_MODE ?? "auto").trim().toLowerCase();
   if (raw === "tauri") {
@@ -181,10 +278,60 @@ function parseStoredTasks(): TaskDto[] {
   return parseWithSchema("mock.tasks", readLocalStorageJson(MOCK_TASKS_KEY), TaskDtoArraySchema);
 }
 
+function mockTagUsage(tasks: TaskDto[]): TagUsage[] {
+  const usage = new Map<string, TagUsage>();
+  for (const task of tasks) {
+    if (task.status === "Deleted") {
+      continue;
+    }
+    const open = task.status === "Pending" || task.status === "Waiting";
+    for (const tag of task.tags) {
+      const entry = usage.get(tag) ?? { tag, count: 0, open: 0, last_used: "" };
+      entry.count += 1;
+      entry.open += open ? 1 : 0;
+      const modified = task.modified ?? "";
+      if (modified > entry.last_used) {
+        entry.last_used = modified;
+      }
+      usage.set(tag, entry);
+    }
+  }
+  return [...usage.values()].sort((a, b) => b.count - a.count || a.tag.localeCompare(b.tag));
+}
+
+// The mock only groups open tasks whose normalized titles match exactly; the
+// backend also scores near matches and tag overlap.
+function mockDuplicateGroups(tasks: TaskDto[]): TaskDedupeGroup[] {
+  const groups = new Map<string, TaskDto[]>();
+  for (const task of tasks) {
+    if (task.status !== "Pending" && task.status !== "Waiting") {
+      continue;
+    }
+    const title = task.title.toLowerCase().replace(/[^\p{L}\p{N}]+/gu, " ").trim();
+    if (!title) {
+      continue;
+    }
+    const key = `${(task.project ?? "").toLowerCase()}|${task.parent ?? ""}|${title}`;
+    groups.set(key, [...(groups.get(key) ?? []), task]);
+  }
+  return [...groups.values()]
+    .filter((group) => group.length > 1)
+    .map((group) => ({
+      score: 100,
+      reason: "same description",
+      tasks: [...group].sort((a, b) => (a.created ?? "").localeCompare(b.created ?? ""))
+    }));
+}
+
 function parseStoredContacts(): ContactDto[] {
   return parseWithSchema("mock.contacts", readLocalStorageJson(MOCK_CONTACTS_KEY), ContactDtoArraySchema);
 }
 
+function parseStoredContactInteractions(): ContactInteraction[] {
+  const raw = readLocalStorageJson(MOCK_CONTACTS_INTERACTIONS_KEY);
+  return Array.isArray(raw) ? parseWithSchema("mock.contacts.interactions", raw, z.array(ContactInteractionSchema)) : [];
+}
+
 function parseStoredDictionaryEntries(): DictionaryEntry[] {
   return parseWithSchema("mock.dictionary.entries", readLocalStorageJson(MOCK_DICTIONARY_DATA_KEY), z.array(DictionaryEntrySchema));
 }
@@ -194,6 +341,10 @@ type MockMergeUndoEntry = {
   undo_id: string;
   contacts_before: ContactDto[];
 };
+type MockDeleteUndoEntry = {
+  undo_id: string;
+  contacts: ContactDto[];
+};
 function parseStoredDedupeDecisions(): DedupeDecisionMap {
   const raw = readLocalStorageJson(MOCK_CONTACTS_DEDUPE_DECISIONS_KEY);
   if (!raw || typeof raw !== "object" || Array.isArray(raw)) {
@@ -228,6 +379,26 @@ function parseStoredMergeUndoEntries(): MockMergeUndoEntry[] {
     }));
 }
 
+function parseStoredDeleteUndoEntries(): MockDeleteUndoEntry[] {
+  const raw = readLocalStorageJson(MOCK_CONTACTS_DELETE_UNDO_KEY);
+  if (!Array.isArray(raw)) {
+    return [];
+  }
+  return raw
+    .filter((entry): entry is MockDeleteUndoEntry => {
+      if (!entry || typeof entry !== "object" || Array.isArray(entry)) {
+        return false;
+      }
+      const undo_id = (entry as { undo_id?: unknown }).undo_id;
+      const contacts = (entry as { contacts?: unknown }).contacts;
+      return typeof undo_id === "string" && Array.isArray(contacts);
+    })
+    .map((entry) => ({
+      undo_id: entry.undo_id,
+      contacts: parseWithSchema("mock.contacts_delete_undo.contacts", entry.contacts, ContactDtoArraySchema)
+    }));
+}
+
 function writeStorageJson(key: string, value: unknown): void {
   if (typeof window === "undefined") {
     return;
@@ -243,6 +414,10 @@ function writeStoredContacts(contacts: ContactDto[]): void {
   writeStorageJson(MOCK_CONTACTS_KEY, contacts);
 }
 
+function writeStoredContactInteractions(interactions: ContactInteraction[]): void {
+  writeStorageJson(MOCK_CONTACTS_INTERACTIONS_KEY, interactions);
+}
+
 function writeStoredDictionaryEntries(entries: DictionaryEntry[]): void {
   writeStorageJson(MOCK_DICTIONARY_DATA_KEY, entries);
 }
@@ -255,6 +430,10 @@ function writeStoredMergeUndoEntries(entries: MockMergeUndoEntry[]): void {
   writeStorageJson(MOCK_CONTACTS_MERGE_UNDO_KEY, entries);
 }
 
+function writeStoredDeleteUndoEntries(entries: MockDeleteUndoEntry[]): void {
+  writeStorageJson(MOCK_CONTACTS_DELETE_UNDO_KEY, entries);
+}
+
 function makeMockTask(input: TaskCreate): TaskDto {
   const now = new Date().toISOString();
   return {
@@ -270,10 +449,24 @@ function makeMockTask(input: TaskCreate): TaskDto {
     wait: input.wait,
     scheduled: input.scheduled,
     created: now,
-    modified: now
+    modified: now,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: input.contact ?? null
   };
 }
 
+function stopMockClock(task: TaskDto, nowMs: number): TaskDto {
+  if (!task.started) {
+    return task;
+  }
+  const elapsed = Math.max(0, Math.floor((nowMs - Date.parse(task.started)) / 1000));
+  return { ...task, started: null, focus_seconds: task.focus_seconds + elapsed };
+}
+
 function makeMockContact(input: ContactCreate): ContactDto {
   const now = new Date().toISOString();
   const firstEmail = input.emails.find((item) => item.value.trim().length > 0)?.value ?? "";
@@ -301,6 +494,7 @@ function makeMockContact(input: ContactCreate): ContactDto {
     source_kind: input.source_kind ?? "local",
     remote_id: input.remote_id,
     link_group_id: input.link_group_id,
+    categories: normalizeCategories(input.categories),
     created_at: now,
     updated_at: now
   };
@@ -410,7 +604,8 @@ function contactSearchMatches(contact: ContactDto, query: string): boolean {
     contact.notes ?? "",
     contact.organization ?? "",
     ...contact.emails.map((item) => item.value),
-    ...contact.phones.map((item) => item.value)
+    ...contact.phones.map((item) => item.value),
+    ...contact.categories
   ].join(" ");
 
   return normalize(fields).includes(q);
@@ -502,6 +697,7 @@ function parseMockVcardContacts(
     let organization = "";
     let title = "";
     let birthday: string | null = null;
+    const categories: string[] = [];
     const emails: ContactFieldValue[] = [];
     const phones: ContactFieldValue[] = [];
     const websites: ContactFieldValue[] = [];
@@ -595,6 +791,10 @@ function parseMockVcardContacts(
         title = value;
         continue;
       }
+      if (upper.startsWith("CATEGORIES")) {
+        categories.push(...parseVcardCategories(trimmed.slice(colonIndex + 1)));
+        continue;
+      }
       if (upper.startsWith("BDAY")) {
         birthday = value || null;
       }
@@ -636,7 +836,8 @@ function parseMockVcardContacts(
       source_id: `import:${sourceKind}`,
       source_kind: sourceKind,
       remote_id: null,
-      link_group_id: null
+      link_group_id: null,
+      categories
     }));
   }
 
@@ -667,6 +868,25 @@ function mockMergeFields(current: ContactFieldValue[], incoming: ContactFieldVal
   return out;
 }
 
+// Mirrors `plan_contact_merge`: the target defaults to the most recently
+// updated contact, and the preview and the commit share this plan.
+function planMockMerge(contacts: ContactDto[], payload: ContactsMergeArgs) {
+  const ids = new Set(payload.ids);
+  const selected = contacts.filter((entry) => ids.has(entry.id));
+  if (selected.length < 2) {
+    throw new Error("need at least two contacts to merge");
+  }
+  const newest = selected.reduce((best, entry) => (entry.updated_at > best.updated_at ? entry : best));
+  let merged = selected.find((entry) => entry.id === (payload.target_id ?? newest.id)) ?? selected[0]!;
+  const removed = selected.filter((entry) => entry.id !== merged.id).map((entry) => entry.id);
+  for (const entry of selected) {
+    if (entry.id !== merged.id) {
+      merged = mergeMockContact(merged, entry);
+    }
+  }
+  return { selected, merged, removed };
+}
+
 function mergeMockContact(existing: ContactDto, incoming: ContactDto): ContactDto {
   return {
     ...existing,
@@ -689,6 +909,7 @@ function mergeMockContact(existing: ContactDto, incoming: ContactDto): ContactDt
     source_kind: existing.source_kind || incoming.source_kind,
     remote_id: existing.remote_id || incoming.remote_id,
     link_group_id: existing.link_group_id || incoming.link_group_id,
+    categories: normalizeCategories([...existing.categories, ...incoming.categories]),
     updated_at: new Date().toISOString()
   };
 }
@@ -745,10 +966,10 @@ function bestMockConflict(
   return null;
 }
 
-async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
-  const requestId = crypto.randomUUID();
+async function invokeCommand<R>(command: string, args?: unknown, options: InvokeOptions = {}): Promise<R> {
+  let requestId = crypto.randomUUID();
   const startedAt = performance.now();
-  const timeoutMs = command.startsWith("external_calendar_") ? EXTERNAL_CALENDAR_TIMEOUT_MS : DEFAULT_TIMEOUT_MS;
+  const timeoutMs = options.timeoutMs ?? DEFAULT_TIMEOUT_MS;
   const instrumentCommand = command !== "ui_log";
   if (instrumentCommand && verboseInvokeLogging) {
     logger.debug("invoke.start", `${command} request_id=${requestId}`);
@@ -765,7 +986,60 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
 
     switch (command) {
       case "tasks_list": {
-        return parseStoredTasks() as R;
+        const payload = args as TasksListArgs | undefined;
+        const limit = payload?.limit ?? 0;
+        const from = payload?.completed_from ?? null;
+        const to = payload?.completed_to ?? null;
+        const tasks = parseStoredTasks()
+          // Archived tasks only come back when asked for, like the backend.
+          .filter((task) => (payload?.status ? task.status === payload.status : task.status !== "Archived"))
+          .filter((task) => !payload?.contact || task.contact === payload.contact)
+          .filter((task) => {
+            if (!from && !to) {
+              return true;
+            }
+            const day = task.completed ? new Date(task.completed).toISOString().slice(0, 10) : null;
+            return day !== null && (!from || day >= from) && (!to || day <= to);
+          });
+        // Most urgent first, so `limit` keeps the same tasks as the backend.
+        const nowMs = Date.now();
+        tasks.sort((left, right) => taskUrgency(right, nowMs) - taskUrgency(left, nowMs));
+        const page: TasksListPage = { tasks: limit > 0 ? tasks.slice(0, limit) : tasks, total: tasks.length };
+        return page as R;
+      }
+      case "tasks_stale": {
+        const payload = args as TasksStaleArgs;
+        const nowMs = Date.now();
+        const cutoffMs = nowMs - (payload.days ?? 14) * 24 * 60 * 60 * 1000;
+        const upcoming = (value: string | null) => value !== null && Date.parse(value) >= nowMs;
+        return parseStoredTasks()
+          .filter((task) => task.status === "Pending" || (payload.include_waiting === true && task.status === "Waiting"))
+          .filter((task) => !upcoming(task.due) && !upcoming(task.scheduled))
+          .filter((task) => task.modified !== null && Date.parse(task.modified) <= cutoffMs)
+          .sort((left, right) => Date.parse(left.modified ?? "") - Date.parse(right.modified ?? "")) as R;
+      }
+      case "tasks_dedupe_preview":
+        return mockDuplicateGroups(parseStoredTasks()) as R;
+      case "tasks_dedupe_apply": {
+        const payload = args as TasksDedupeApplyArgs;
+        const now = new Date().toISOString();
+        let tasks = parseStoredTasks();
+        let removed = 0;
+        for (const [keptUuid, ...others] of payload.groups) {
+          const duplicates = tasks.filter((task) => others.includes(task.uuid));
+          removed += duplicates.length;
+          tasks = tasks
+            .filter((task) => !others.includes(task.uuid))
+            .map((task) => {
+              if (task.uuid !== keptUuid || !payload.merge) {
+                return task;
+              }
+              const tags = [...new Set([...task.tags, ...duplicates.flatMap((duplicate) => duplicate.tags)])];
+              return { ...task, tags, modified: now };
+            });
+        }
+        writeStoredTasks(tasks);
+        return { removed } as R;
       }
       case "task_add": {
         const payload = args as TaskCreate;
@@ -781,10 +1055,12 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
           if (entry.uuid !== payload.uuid) {
             return entry;
           }
+          const now = new Date().toISOString();
           return {
-            ...entry,
+            ...stopMockClock(entry, Date.parse(now)),
             status: "Completed" as const,
-            modified: new Date().toISOString()
+            modified: now,
+            completed: now
           };
         });
         writeStoredTasks(tasks);
@@ -794,6 +1070,26 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
         }
         return target as R;
       }
+      case "task_start":
+      case "task_stop": {
+        const payload = args as TaskIdArg;
+        const nowMs = Date.now();
+        const tasks = parseStoredTasks().map((entry) => {
+          if (entry.uuid !== payload.uuid) {
+            return entry;
+          }
+          if (command === "task_start") {
+            return entry.started ? entry : { ...entry, started: new Date(nowMs).toISOString() };
+          }
+          return stopMockClock(entry, nowMs);
+        });
+        writeStoredTasks(tasks);
+        const target = tasks.find((entry) => entry.uuid === payload.uuid);
+        if (!target) {
+          throw new Error(`task not found: ${payload.uuid}`);
+        }
+        return target as R;
+      }
       case "task_uncomplete": {
         const payload = args as TaskIdArg;
         const tasks = parseStoredTasks().map((entry) => {
@@ -803,7 +1099,8 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
           return {
             ...entry,
             status: "Pending" as const,
-            modified: new Date().toISOString()
+            modified: new Date().toISOString(),
+            completed: null
           };
         });
         writeStoredTasks(tasks);
@@ -819,6 +1116,53 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
         writeStoredTasks(tasks);
         return undefined as R;
       }
+      case "task_history":
+        return [] as R;
+      case "tags_remap": {
+        const payload = args as TagsRemapArgs;
+        const from = `${payload.key}:${payload.from}`;
+        const to = `${payload.key}:${payload.to}`;
+        let changed = 0;
+        const tasks = parseStoredTasks().map((entry) => {
+          if (!entry.tags.includes(from)) {
+            return entry;
+          }
+          changed += 1;
+          const tags = entry.tags.includes(to)
+            ? entry.tags.filter((tag) => tag !== from)
+            : entry.tags.map((tag) => (tag === from ? to : tag));
+          return { ...entry, tags, modified: new Date().toISOString() };
+        });
+        writeStoredTasks(tasks);
+        return { changed } as R;
+      }
+      case "tags_stats":
+        return mockTagUsage(parseStoredTasks()) as R;
+      case "tags_cleanup_unused": {
+        const removed = mockTagUsage(parseStoredTasks())
+          .filter((usage) => usage.open === 0 && !usage.tag.includes(":"))
+          .map((usage) => usage.tag)
+          .sort();
+        let changed = 0;
+        const tasks = parseStoredTasks().map((entry) => {
+          const tags = entry.tags.filter((tag) => !removed.includes(tag));
+          if (tags.length === entry.tags.length) {
+            return entry;
+          }
+          changed += 1;
+          return { ...entry, tags, modified: new Date().toISOString() };
+        });
+        writeStoredTasks(tasks);
+        return { removed: changed > 0 ? removed : [], changed } as R;
+      }
+      case "demo_seed":
+        return { tasks: 0, board_id: DEMO_BOARD_ID } as R;
+      case "demo_clear": {
+        const tasks = parseStoredTasks();
+        const kept = tasks.filter((entry) => !isDemoTask(entry.tags));
+        writeStoredTasks(kept);
+        return { tasks: tasks.length - kept.length, board_id: DEMO_BOARD_ID } as R;
+      }
       case "task_update": {
         const payload = args as TaskUpdateArgs;
         const tasks = parseStoredTasks().map((entry) => {
@@ -836,6 +1180,7 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
             due: typeof payload.patch.due === "undefined" ? entry.due : payload.patch.due,
             wait: typeof payload.patch.wait === "undefined" ? entry.wait : payload.patch.wait,
             scheduled: typeof payload.patch.scheduled === "undefined" ? entry.scheduled : payload.patch.scheduled,
+            contact: typeof payload.patch.contact === "undefined" ? entry.contact : payload.patch.contact,
             modified: new Date().toISOString()
           };
         });
@@ -848,14 +1193,21 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
       }
       case "contacts_list": {
         const payload = (args ?? DEFAULT_CONTACTS_QUERY) as ContactsListArgs;
-        const all = parseStoredContacts();
-        const filtered = all.filter((contact) => contactSearchMatches(contact, payload.query ?? ""));
+        const lastContacted = lastContactedByContact(parseStoredContactInteractions());
+        const all = parseStoredContacts().map((contact) => ({ ...contact, last_contacted: lastContacted.get(contact.id) ?? null }));
+        const withinDays = payload.contacted_within_days ?? null;
+        const matched = all.filter((contact) => contactSearchMatches(contact, payload.query ?? "")
+          && (withinDays === null || contactedWithinDays(contact, withinDays, Date.now())));
+        const categories = countContactCategories(matched);
+        const category = payload.category?.trim() ?? "";
+        const categoryMatched = category ? matched.filter((contact) => contactHasCategory(contact, category)) : matched;
+        const filtered = payload.sort === "recent" ? sortContactsByRecency(categoryMatched) : categoryMatched;
         const total = filtered.length;
         const limit = Math.max(1, payload.limit ?? 200);
         const offset = Number(payload.cursor ?? "0") || 0;
         const contacts = filtered.slice(offset, offset + limit);
         const next_cursor = offset + contacts.length < total ? String(offset + contacts.length) : null;
-        return { contacts, next_cursor, total } as R;
+        return { contacts, next_cursor, total, categories } as R;
       }
       case "contact_add": {
         const payload = args as ContactCreate;
@@ -892,6 +1244,7 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
             source_kind: typeof payload.patch.source_kind === "undefined" ? entry.source_kind : (payload.patch.source_kind ?? ""),
             remote_id: typeof payload.patch.remote_id === "undefined" ? entry.remote_id : payload.patch.remote_id,
             link_group_id: typeof payload.patch.link_group_id === "undefined" ? entry.link_group_id : payload.patch.link_group_id,
+            categories: payload.patch.categories ? normalizeCategories(payload.patch.categories) : entry.categories,
             updated_at: new Date().toISOString()
           };
         });
@@ -902,20 +1255,77 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
         }
         return target as R;
       }
+      case "contact_get": {
+        const payload = args as ContactIdArg;
+        return (parseStoredContacts().find((entry) => entry.id === payload.id) ?? null) as R;
+      }
       case "contact_delete": {
         const payload = args as ContactIdArg;
         const contacts = parseStoredContacts().filter((entry) => entry.id !== payload.id);
         writeStoredContacts(contacts);
         return undefined as R;
       }
+      case "contacts_unlink": {
+        const payload = args as ContactIdArg;
+        const contacts = parseStoredContacts();
+        const target = contacts.find((entry) => entry.id === payload.id);
+        if (!target) {
+          throw new Error(`contact not found: ${payload.id}`);
+        }
+        const groupId = target.link_group_id;
+        if (!groupId) {
+          throw new Error("contact is not linked");
+        }
+        const now = new Date().toISOString();
+        const changed = [{ ...target, link_group_id: null, updated_at: now }];
+        const remaining = contacts.filter((entry) => entry.id !== target.id && entry.link_group_id === groupId);
+        if (remaining.length === 1) {
+          changed.push({ ...remaining[0]!, link_group_id: null, updated_at: now });
+        }
+        const byId = new Map(changed.map((entry) => [entry.id, entry]));
+        writeStoredContacts(contacts.map((entry) => byId.get(entry.id) ?? entry));
+        return { contacts: changed } as R;
+      }
       case "contacts_delete_bulk": {
         const payload = args as ContactsDeleteBulkArgs;
         const ids = new Set(payload.ids);
         const contacts = parseStoredContacts();
         const kept = contacts.filter((entry) => !ids.has(entry.id));
-        const deleted = contacts.length - kept.length;
+        const removed = contacts.filter((entry) => ids.has(entry.id));
         writeStoredContacts(kept);
-        return deleted as R;
+        if (removed.length === 0) {
+          return { deleted: 0, undo_id: null, linked_tasks: 0 } as R;
+        }
+        const linked_tasks = parseStoredTasks().filter(
+          (task) => (task.status === "Pending" || task.status === "Waiting") && task.contact !== null && ids.has(task.contact)
+        ).length;
+
+        const snapshots = parseStoredDeleteUndoEntries();
+        const undo_id = crypto.randomUUID();
+        snapshots.push({ undo_id, contacts: removed });
+        writeStoredDeleteUndoEntries(snapshots.slice(-20));
+        return { deleted: removed.length, undo_id, linked_tasks } as R;
+      }
+      case "contacts_delete_undo": {
+        const payload = args as ContactsDeleteUndoArgs;
+        const snapshots = parseStoredDeleteUndoEntries();
+        const index = payload?.undo_id
+          ? snapshots.findIndex((entry) => entry.undo_id === payload.undo_id)
+          : snapshots.length - 1;
+        const [entry] = index >= 0 ? snapshots.splice(index, 1) : [];
+        if (!entry) {
+          throw new Error("no delete undo snapshot available");
+        }
+
+        const contacts = parseStoredContacts();
+        const present = new Set(contacts.map((contact) => contact.id));
+        const restored = entry.contacts.filter((contact) => !present.has(contact.id));
+        writeStoredContacts([...contacts, ...restored]);
+        writeStoredDeleteUndoEntries(snapshots);
+        return {
+          restored: restored.length,
+          undo_id: entry.undo_id
+        } as R;
       }
       case "contacts_dedupe_preview":
       case "contacts_dedupe_candidates": {
@@ -960,14 +1370,43 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
       }
       case "contact_open_action": {
         const payload = args as ContactOpenActionArgs;
-        const url = payload.action === "tel" || payload.action === "phone"
-          ? `tel:${payload.value ?? ""}`
-          : `mailto:${payload.value ?? ""}`;
+        const phone = payload.action === "tel" || payload.action === "phone";
+        const url = phone ? `tel:${payload.value ?? ""}` : `mailto:${payload.value ?? ""}`;
+        writeStoredContactInteractions([
+          ...parseStoredContactInteractions(),
+          {
+            id: crypto.randomUUID(),
+            contact_id: payload.id,
+            kind: phone ? "phone" : "email",
+            value: payload.value ?? "",
+            at: new Date().toISOString()
+          }
+        ]);
         return {
           launched: false,
-          url
+          url,
+          logged: true
         } as R;
       }
+      case "contacts_interactions": {
+        const payload = args as ContactInteractionsArgs;
+        const interactions = parseStoredContactInteractions()
+          .filter((entry) => !payload.contact_id || entry.contact_id === payload.contact_id)
+          .sort((a, b) => Date.parse(b.at) - Date.parse(a.at));
+        return { interactions } as R;
+      }
+      case "contacts_interactions_prune": {
+        const payload = args as ContactInteractionsPruneArgs;
+        const cutoff = payload.older_than_days === null ? null : Date.now() - payload.older_than_days * 24 * 60 * 60 * 1000;
+        const all = parseStoredContactInteractions();
+        const kept = all.filter((entry) => {
+          const selected = (!payload.contact_id || entry.contact_id === payload.contact_id)
+            && (cutoff === null || !(Date.parse(entry.at) >= cutoff));
+          return !selected;
+        });
+        writeStoredContactInteractions(kept);
+        return { removed: all.length - kept.length, remaining: kept.length } as R;
+      }
       case "contacts_import_preview": {
         const payload = args as ContactsImportPreviewArgs;
         const source = normalizeMockSource(payload.source);
@@ -975,12 +1414,13 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
         const parsed = parseMockVcardContacts(payload.content, source, payload.file_name ?? null, batch_id);
         const existing = parseStoredContacts();
         const conflicts = parsed.contacts
-          .map((imported) => {
+          .map((imported, row) => {
             const match = bestMockConflict(imported, existing);
             if (!match || match.score < 80) {
               return null;
             }
             return {
+              row,
               imported,
               existing: existing[match.index]!,
               score: match.score,
@@ -1006,24 +1446,28 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
         const batch_id = crypto.randomUUID();
         const parsed = parseMockVcardContacts(payload.content, source, payload.file_name ?? null, batch_id);
         const mode = String(payload.mode ?? "safe").trim().toLowerCase();
+        const decisions = new Map((payload.decisions ?? []).map((entry) => [entry.row, entry.decision]));
         const contacts = parseStoredContacts();
         let created = 0;
         let updated = 0;
         let skipped = 0;
+        let linked = 0;
         let conflicts = 0;
 
-        for (const incoming of parsed.contacts) {
+        for (const [row, incoming] of parsed.contacts.entries()) {
           const match = bestMockConflict(incoming, contacts);
           if (match && match.score >= 80) {
             conflicts += 1;
-            if (mode === "upsert") {
-              const current = contacts[match.index];
-              if (current) {
-                contacts[match.index] = mergeMockContact(current, incoming);
-                updated += 1;
-              } else {
-                skipped += 1;
-              }
+            const decision = decisions.get(row) ?? (mode === "upsert" ? "merge" : "skip");
+            const current = contacts[match.index];
+            if (decision === "merge" && current) {
+              contacts[match.index] = mergeMockContact(current, incoming);
+              updated += 1;
+            } else if (decision === "link" && current) {
+              const groupId = current.link_group_id || crypto.randomUUID();
+              contacts[match.index] = { ...current, link_group_id: groupId };
+              contacts.unshift({ ...incoming, link_group_id: groupId });
+              linked += 1;
             } else {
               skipped += 1;
             }
@@ -1040,28 +1484,22 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
           created,
           updated,
           skipped,
+          linked,
           failed: parsed.errors.length,
           conflicts,
           errors: parsed.errors
         } as R;
       }
+      case "contacts_merge_preview": {
+        const { merged, removed } = planMockMerge(parseStoredContacts(), args as ContactsMergeArgs);
+        return {
+          merged,
+          removed_ids: removed
+        } as R;
+      }
       case "contacts_merge": {
-        const payload = args as ContactsMergeArgs;
         const contacts = parseStoredContacts();
-        const ids = new Set(payload.ids);
-        const selected = contacts.filter((entry) => ids.has(entry.id));
-        if (selected.length < 2) {
-          throw new Error("need at least two contacts to merge");
-        }
-        const targetId = payload.target_id ?? selected[0]!.id;
-        let merged = selected.find((entry) => entry.id === targetId) ?? selected[0]!;
-        const removed = selected.filter((entry) => entry.id !== merged.id).map((entry) => entry.id);
-        for (const entry of selected) {
-          if (entry.id === merged.id) {
-            continue;
-          }
-          merged = mergeMockContact(merged, entry);
-        }
+        const { selected, merged, removed } = planMockMerge(contacts, args as ContactsMergeArgs);
 
         const next = contacts
           .filter((entry) => !removed.includes(entry.id))
@@ -1081,10 +1519,23 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
         decisions[decisionGroupId] = "merged";
         writeStoredDedupeDecisions(decisions);
 
+        let relinked_tasks = 0;
+        const now = new Date().toISOString();
+        writeStoredTasks(
+          parseStoredTasks().map((task) => {
+            if (task.contact === null || !removed.includes(task.contact)) {
+              return task;
+            }
+            relinked_tasks += 1;
+            return { ...task, contact: merged.id, modified: now };
+          })
+        );
+
         return {
           merged,
           removed_ids: removed,
-          undo_id
+          undo_id,
+          relinked_tasks
         } as R;
       }
       case "contacts_merge_undo": {
@@ -1236,15 +1687,73 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
               max_connection_retries: 5,
               retry_backoff_ms: 750
             }
+          },
+          effective: {
+            timezone: "America/Mexico_City"
           }
         } as R;
       }
+      case "config_bundle_export": {
+        const payload = args as { gui: unknown };
+        return JSON.stringify(
+          { format: "rivet-config-bundle", version: 1, created: new Date().toISOString(), files: {}, gui: payload.gui },
+          null,
+          2
+        ) as R;
+      }
+      case "config_bundle_import": {
+        const payload = args as { bundle: string };
+        const bundle = JSON.parse(payload.bundle) as { gui?: unknown };
+        return {
+          applied: true,
+          conflicts: [],
+          written: [],
+          kept: [],
+          unchanged: [],
+          backups: [],
+          skipped: [],
+          gui: bundle.gui ?? null
+        } as R;
+      }
       case "config_apply_updates": {
-        return {} as R;
+        const payload = args as { updates: ConfigEntryUpdate[] };
+        return {
+          saved: payload.updates.length > 0,
+          results: payload.updates.map((update) => ({
+            section: update.section,
+            key: update.key,
+            status: "applied",
+            reason: null,
+            previous: null,
+            effective: update.value
+          })),
+          config: {}
+        } as R;
+      }
+      case "permissions_snapshot": {
+        return { disabled: [] } as R;
       }
       case "external_calendar_cache_list": {
         return [] as R;
       }
+      case "external_calendar_schedule": {
+        // The mock never syncs in the background.
+        const payload = args as { sources: ExternalCalendarSource[] };
+        return payload.sources.map((source) => ({
+          calendar_id: source.id,
+          last_sync: null,
+          last_error: null,
+          next_sync: null,
+          state: "idle"
+        })) as R;
+      }
+      case "calendar_parse_date": {
+        const input = (args as { input: string }).input.trim();
+        if (!/^\d{4}-\d{2}-\d{2}$/.test(input)) {
+          throw new Error(`unsupported date expression: ${input}`);
+        }
+        return input as R;
+      }
       case "external_calendar_import_cached": {
         const payload = args as { source: ExternalCalendarSource; cache_id: string };
         return {
@@ -1267,15 +1776,22 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
     }
   };
 
-  let timeoutId: number | null = null;
-  const timeout = new Promise<never>((_, reject) => {
-    timeoutId = window.setTimeout(() => {
-      reject(new Error(`invoke timeout (${command}) after ${timeoutMs}ms request_id=${requestId}`));
-    }, timeoutMs);
-  });
-
   try {
-    const result = await Promise.race([run(), timeout]);
+    const result = await retryWithBackoff(
+      async (attemptIndex) => {
+        if (attemptIndex > 0) {
+          requestId = crypto.randomUUID();
+        }
+        return withTimeout(run, timeoutMs, () => new InvokeTimeoutError(command, timeoutMs, requestId));
+      },
+      options,
+      (error, attempt, delayMs) => {
+        if (instrumentCommand) {
+          const message = error instanceof Error ? error.message : String(error);
+          logger.debug("invoke.retry", `${command} attempt=${attempt} delay_ms=${delayMs} error=${message}`);
+        }
+      }
+    );
     const elapsed = Math.round((performance.now() - startedAt) * 100) / 100;
     if (instrumentCommand && verboseInvokeLogging) {
       logger.info("invoke.success", `${command} request_id=${requestId} duration_ms=${elapsed}`);
@@ -1295,10 +1811,6 @@ async function invokeCommand<R>(command: string, args?: unknown): Promise<R> {
       });
     }
     throw error;
-  } finally {
-    if (timeoutId !== null) {
-      window.clearTimeout(timeoutId);
-    }
   }
 }
 
@@ -1311,13 +1823,32 @@ setLoggerBridge(async (event, detail) => {
 }, "warn");
 
 export async function healthCheck(): Promise<void> {
-  const response = await invokeCommand<unknown>("tasks_list", DEFAULT_TASK_QUERY);
-  parseWithSchema("tasks_list healthcheck", response, TaskDtoArraySchema);
+  const response = await invokeCommand<unknown>("tasks_list", DEFAULT_TASK_QUERY, IDEMPOTENT_READ);
+  parseWithSchema("tasks_list healthcheck", response, TasksListPageSchema);
+}
+
+export async function listTasksPage(args: TasksListArgs = DEFAULT_TASK_QUERY): Promise<TasksListPage> {
+  const response = await invokeCommand<unknown>("tasks_list", args, IDEMPOTENT_READ);
+  return parseWithSchema("tasks_list response", response, TasksListPageSchema);
 }
 
 export async function listTasks(args: TasksListArgs = DEFAULT_TASK_QUERY): Promise<TaskDto[]> {
-  const response = await invokeCommand<unknown>("tasks_list", args);
-  return parseWithSchema("tasks_list response", response, TaskDtoArraySchema);
+  return (await listTasksPage(args)).tasks;
+}
+
+export async function listStaleTasks(args: TasksStaleArgs): Promise<TaskDto[]> {
+  const response = await invokeCommand<unknown>("tasks_stale", args, IDEMPOTENT_READ);
+  return parseWithSchema("tasks_stale response", response, TaskDtoArraySchema);
+}
+
+export async function listDuplicateTasks(args: TasksDedupeArgs): Promise<TaskDedupeGroup[]> {
+  const response = await invokeCommand<unknown>("tasks_dedupe_preview", args, IDEMPOTENT_READ);
+  return parseWithSchema("tasks_dedupe_preview response", response, TaskDedupeGroupArraySchema);
+}
+
+export async function resolveDuplicateTasks(args: TasksDedupeApplyArgs): Promise<TasksDedupeApplyResult> {
+  const response = await invokeCommand<unknown>("tasks_dedupe_apply", args);
+  return parseWithSchema("tasks_dedupe_apply response", response, TasksDedupeApplyResultSchema);
 }
 
 export async function addTask(args: TaskCreate): Promise<TaskDto> {
@@ -1343,15 +1874,60 @@ export async function uncompleteTask(uuid: string): Promise<TaskDto> {
   return parseWithSchema("task_uncomplete response", response, TaskDtoSchema);
 }
 
+export async function startTask(uuid: string): Promise<TaskDto> {
+  const response = await invokeCommand<unknown>("task_start", { uuid });
+  return parseWithSchema("task_start response", response, TaskDtoSchema);
+}
+
+export async function stopTask(uuid: string): Promise<TaskDto> {
+  const response = await invokeCommand<unknown>("task_stop", { uuid });
+  return parseWithSchema("task_stop response", response, TaskDtoSchema);
+}
+
 export async function deleteTask(uuid: string): Promise<void> {
   return invokeCommand<void>("task_delete", { uuid });
 }
 
+export async function taskHistory(uuid: string): Promise<TaskHistoryEntry[]> {
+  const response = await invokeCommand<unknown>("task_history", { uuid }, IDEMPOTENT_READ);
+  return parseWithSchema("task_history response", response, TaskHistoryEntryArraySchema);
+}
+
+export async function remapTags(args: TagsRemapArgs): Promise<TagsRemapResult> {
+  const response = await invokeCommand<unknown>("tags_remap", args);
+  return parseWithSchema("tags_remap response", response, TagsRemapResultSchema);
+}
+
+export async function loadTagStats(): Promise<TagUsage[]> {
+  const response = await invokeCommand<unknown>("tags_stats", undefined, IDEMPOTENT_READ);
+  return parseWithSchema("tags_stats response", response, TagUsageArraySchema);
+}
+
+export async function cleanupUnusedTags(): Promise<TagsCleanupResult> {
+  const response = await invokeCommand<unknown>("tags_cleanup_unused");
+  return parseWithSchema("tags_cleanup_unused response", response, TagsCleanupResultSchema);
+}
+
+export async function seedDemoData(): Promise<DemoDataResult> {
+  const response = await invokeCommand<unknown>("demo_seed");
+  return parseWithSchema("demo_seed response", response, DemoDataResultSchema);
+}
+
+export async function clearDemoData(): Promise<DemoDataResult> {
+  const response = await invokeCommand<unknown>("demo_clear");
+  return parseWithSchema("demo_clear response", response, DemoDataResultSchema);
+}
+
 export async function listContacts(args: ContactsListArgs = DEFAULT_CONTACTS_QUERY): Promise<ContactsListResult> {
-  const response = await invokeCommand<unknown>("contacts_list", args);
+  const response = await invokeCommand<unknown>("contacts_list", args, IDEMPOTENT_READ);
   return parseWithSchema("contacts_list response", response, ContactsListResultSchema);
 }
 
+export async function getContact(id: string): Promise<ContactDto | null> {
+  const response = await invokeCommand<unknown>("contact_get", { id }, IDEMPOTENT_READ);
+  return parseWithSchema("contact_get response", response, ContactDtoSchema.nullable());
+}
+
 export async function addContact(args: ContactCreate): Promise<ContactDto> {
   const payload = parseWithSchema("contact_add args", args, ContactCreateSchema);
   const response = await invokeCommand<unknown>("contact_add", payload);
@@ -1368,18 +1944,28 @@ export async function deleteContact(id: string): Promise<void> {
   return invokeCommand<void>("contact_delete", { id });
 }
 
-export async function deleteContactsBulk(args: ContactsDeleteBulkArgs): Promise<number> {
+export async function deleteContactsBulk(args: ContactsDeleteBulkArgs): Promise<ContactsDeleteBulkResult> {
   const response = await invokeCommand<unknown>("contacts_delete_bulk", args);
-  return Number(response);
+  return parseWithSchema("contacts_delete_bulk response", response, ContactsDeleteBulkResultSchema);
+}
+
+export async function undoContactsDelete(args: ContactsDeleteUndoArgs): Promise<ContactsDeleteUndoResult> {
+  const response = await invokeCommand<unknown>("contacts_delete_undo", args);
+  return parseWithSchema("contacts_delete_undo response", response, ContactsDeleteUndoResultSchema);
+}
+
+export async function unlinkContact(args: ContactIdArg): Promise<ContactsUnlinkResult> {
+  const response = await invokeCommand<unknown>("contacts_unlink", args);
+  return parseWithSchema("contacts_unlink response", response, ContactsUnlinkResultSchema);
 }
 
 export async function previewContactsDedupe(args: ContactsDedupePreviewArgs): Promise<ContactsDedupePreviewResult> {
-  const response = await invokeCommand<unknown>("contacts_dedupe_preview", args);
+  const response = await invokeCommand<unknown>("contacts_dedupe_preview", args, IDEMPOTENT_READ);
   return parseWithSchema("contacts_dedupe_preview response", response, ContactsDedupePreviewResultSchema);
 }
 
 export async function listContactsDedupeCandidates(args: ContactsDedupePreviewArgs): Promise<ContactsDedupePreviewResult> {
-  const response = await invokeCommand<unknown>("contacts_dedupe_candidates", args);
+  const response = await invokeCommand<unknown>("contacts_dedupe_candidates", args, IDEMPOTENT_READ);
   return parseWithSchema("contacts_dedupe_candidates response", response, ContactsDedupePreviewResultSchema);
 }
 
@@ -1393,6 +1979,16 @@ export async function openContactAction(args: ContactOpenActionArgs): Promise<Co
   return parseWithSchema("contact_open_action response", response, ContactOpenActionResultSchema);
 }
 
+export async function listContactInteractions(args: ContactInteractionsArgs): Promise<ContactInteractionsResult> {
+  const response = await invokeCommand<unknown>("contacts_interactions", args, IDEMPOTENT_READ);
+  return parseWithSchema("contacts_interactions response", response, ContactInteractionsResultSchema);
+}
+
+export async function pruneContactInteractions(args: ContactInteractionsPruneArgs): Promise<ContactInteractionsPruneResult> {
+  const response = await invokeCommand<unknown>("contacts_interactions_prune", args);
+  return parseWithSchema("contacts_interactions_prune response", response, ContactInteractionsPruneResultSchema);
+}
+
 export async function previewContactsImport(args: ContactsImportPreviewArgs): Promise<ContactsImportPreviewResult> {
   const response = await invokeCommand<unknown>("contacts_import_preview", args);
   return parseWithSchema("contacts_import_preview response", response, ContactsImportPreviewResultSchema);
@@ -1403,6 +1999,11 @@ export async function commitContactsImport(args: ContactsImportCommitArgs): Prom
   return parseWithSchema("contacts_import_commit response", response, ContactsImportCommitResultSchema);
 }
 
+export async function previewContactsMerge(args: ContactsMergeArgs): Promise<ContactsMergePreviewResult> {
+  const response = await invokeCommand<unknown>("contacts_merge_preview", args);
+  return parseWithSchema("contacts_merge_preview response", response, ContactsMergePreviewResultSchema);
+}
+
 export async function mergeContacts(args: ContactsMergeArgs): Promise<ContactsMergeResult> {
   const response = await invokeCommand<unknown>("contacts_merge", args);
   return parseWithSchema("contacts_merge response", response, ContactsMergeResultSchema);
@@ -1415,21 +2016,61 @@ export async function undoContactsMerge(args: ContactsMergeUndoArgs): Promise<Co
 
 export async function syncExternalCalendar(source: ExternalCalendarSource): Promise<ExternalCalendarSyncResult> {
   const payload = parseWithSchema("external_calendar_sync args", source, ExternalCalendarSourceSchema);
-  const response = await invokeCommand<unknown>("external_calendar_sync", payload);
+  const response = await invokeCommand<unknown>("external_calendar_sync", payload, { timeoutMs: EXTERNAL_CALENDAR_TIMEOUT_MS });
   return parseWithSchema("external_calendar_sync response", response, ExternalCalendarSyncResultSchema);
 }
 
+// Hands the configured sources to the backend scheduler, which syncs each
+// enabled remote source every `refresh_minutes` whether or not the calendar
+// view is open.
+export async function scheduleExternalCalendars(sources: ExternalCalendarSource[]): Promise<ExternalCalendarSyncStatus[]> {
+  const payload = {
+    sources: sources.map((source) => parseWithSchema("external_calendar_schedule args source", source, ExternalCalendarSourceSchema))
+  };
+  const response = await invokeCommand<unknown>("external_calendar_schedule", payload, IDEMPOTENT_READ);
+  return parseWithSchema("external_calendar_schedule response", response, ExternalCalendarSyncStatusArraySchema);
+}
+
+// Subscribes to background sync results; resolves to the unsubscribe function.
+export async function onExternalCalendarSynced(handler: (event: ExternalCalendarSyncEvent) => void): Promise<() => void> {
+  if (!isTauriRuntime()) {
+    return () => {};
+  }
+  return listen<unknown>("external-calendar-synced", (event) => {
+    try {
+      handler(parseWithSchema("external-calendar-synced event", event.payload, ExternalCalendarSyncEventSchema));
+    } catch (error) {
+      logger.warn("external_calendar.sync_event.invalid", String(error));
+    }
+  });
+}
+
+// Subscribes to queued/running changes of manual and background syncs, which
+// share the `[external_calendar] max_concurrent` fetch slots.
+export async function onExternalCalendarSyncActivity(handler: (activity: ExternalCalendarSyncActivity) => void): Promise<() => void> {
+  if (!isTauriRuntime()) {
+    return () => {};
+  }
+  return listen<unknown>("external-calendar-sync-activity", (event) => {
+    try {
+      handler(parseWithSchema("external-calendar-sync-activity event", event.payload, ExternalCalendarSyncActivitySchema));
+    } catch (error) {
+      logger.warn("external_calendar.sync_activity.invalid", String(error));
+    }
+  });
+}
+
 export async function importExternalCalendarIcs(source: ExternalCalendarSource, icsText: string): Promise<ExternalCalendarSyncResult> {
   const payload = {
     source: parseWithSchema("external_calendar_import_ics args source", source, ExternalCalendarSourceSchema),
     ics_text: icsText
   };
-  const response = await invokeCommand<unknown>("external_calendar_import_ics", payload);
+  const response = await invokeCommand<unknown>("external_calendar_import_ics", payload, { timeoutMs: EXTERNAL_CALENDAR_TIMEOUT_MS });
   return parseWithSchema("external_calendar_import_ics response", response, ExternalCalendarSyncResultSchema);
 }
 
 export async function listExternalCalendarCache(): Promise<ExternalCalendarCacheEntry[]> {
-  const response = await invokeCommand<unknown>("external_calendar_cache_list");
+  const response = await invokeCommand<unknown>("external_calendar_cache_list", undefined, { ...IDEMPOTENT_READ, timeoutMs: EXTERNAL_CALENDAR_TIMEOUT_MS });
   return parseWithSchema("external_calendar_cache_list response", response, ExternalCalendarCacheEntryArraySchema);
 }
 
@@ -1438,23 +2079,29 @@ export async function importExternalCalendarCached(source: ExternalCalendarSourc
     source: parseWithSchema("external_calendar_import_cached args source", source, ExternalCalendarSourceSchema),
     cache_id: cacheId
   };
-  const response = await invokeCommand<unknown>("external_calendar_import_cached", payload);
+  const response = await invokeCommand<unknown>("external_calendar_import_cached", payload, { timeoutMs: EXTERNAL_CALENDAR_TIMEOUT_MS });
   return parseWithSchema("external_calendar_import_cached response", response, ExternalCalendarSyncResultSchema);
 }
 
+// Parsed by the backend with the task date grammar; resolves to YYYY-MM-DD.
+export async function parseCalendarDate(input: string): Promise<string> {
+  const response = await invokeCommand<unknown>("calendar_parse_date", { input });
+  return parseWithSchema("calendar_parse_date response", response, z.string().regex(/^\d{4}-\d{2}-\d{2}$/));
+}
+
 export async function listDictionaryLanguages(): Promise<string[]> {
-  const response = await invokeCommand<unknown>("dictionary_languages");
+  const response = await invokeCommand<unknown>("dictionary_languages", undefined, IDEMPOTENT_READ);
   return parseWithSchema("dictionary_languages response", response, z.array(z.string()));
 }
 
 export async function searchDictionary(args: DictionarySearchArgs): Promise<DictionarySearchResult> {
   const payload = parseWithSchema("dictionary_search args", args, DictionarySearchArgsSchema);
-  const response = await invokeCommand<unknown>("dictionary_search", payload);
+  const response = await invokeCommand<unknown>("dictionary_search", payload, IDEMPOTENT_READ);
   return parseWithSchema("dictionary_search response", response, DictionarySearchResultSchema);
 }
 
 export async function loadDictionaryEntry(args: DictionaryEntryArgs): Promise<DictionaryEntry | null> {
-  const response = await invokeCommand<unknown>("dictionary_entry", args);
+  const response = await invokeCommand<unknown>("dictionary_entry", args, IDEMPOTENT_READ);
   if (response == null) {
     return null;
   }
@@ -1462,13 +2109,13 @@ export async function loadDictionaryEntry(args: DictionaryEntryArgs): Promise<Di
 }
 
 export async function mapHealth(args: MapHealthArgs = DEFAULT_MAP_HEALTH_QUERY): Promise<MapHealthResult> {
-  const response = await invokeCommand<unknown>("map_health", args);
+  const response = await invokeCommand<unknown>("map_health", args, IDEMPOTENT_READ);
   return parseWithSchema("map_health response", response, MapHealthResultSchema);
 }
 
 export async function loadConfigSnapshot(): Promise<RivetRuntimeConfig> {
   try {
-    const response = await invokeCommand<unknown>("config_snapshot");
+    const response = await invokeCommand<unknown>("config_snapshot", undefined, IDEMPOTENT_READ);
     return parseWithSchema("config_snapshot response", response, RivetRuntimeConfigSchema);
   } catch (error) {
     logger.warn("config_snapshot", String(error));
@@ -1476,17 +2123,40 @@ export async function loadConfigSnapshot(): Promise<RivetRuntimeConfig> {
   }
 }
 
-export async function applyConfigUpdates(updates: ConfigEntryUpdate[]): Promise<RivetRuntimeConfig> {
+// The backend enforces these; the UI only uses them to hide actions, so a
+// failed read falls back to showing everything.
+export async function loadPermissions(): Promise<PermissionsSnapshot> {
+  try {
+    const response = await invokeCommand<unknown>("permissions_snapshot", undefined, IDEMPOTENT_READ);
+    return parseWithSchema("permissions_snapshot response", response, PermissionsSnapshotSchema);
+  } catch (error) {
+    logger.warn("permissions_snapshot", String(error));
+    return { disabled: [] };
+  }
+}
+
+export async function applyConfigUpdates(updates: ConfigEntryUpdate[]): Promise<ConfigApplyResult> {
   const payload = {
     updates
   };
   const response = await invokeCommand<unknown>("config_apply_updates", payload);
-  return parseWithSchema("config_apply_updates response", response, RivetRuntimeConfigSchema);
+  return parseWithSchema("config_apply_updates response", response, ConfigApplyResultSchema);
+}
+
+// rivet.toml, the tag schema and `gui` as the JSON `task config export` writes.
+export async function exportConfigBundle(gui: unknown): Promise<string> {
+  const response = await invokeCommand<unknown>("config_bundle_export", { gui });
+  return parseWithSchema("config_bundle_export response", response, z.string());
+}
+
+export async function importConfigBundle(bundle: string, onConflict: ConfigBundleConflictPolicy): Promise<ConfigBundleImportResult> {
+  const response = await invokeCommand<unknown>("config_bundle_import", { bundle, on_conflict: onConflict });
+  return parseWithSchema("config_bundle_import response", response, ConfigBundleImportResultSchema);
 }
 
 export async function loadTagSchemaSnapshot(): Promise<TagSchema> {
   try {
-    const response = await invokeCommand<unknown>("tag_schema_snapshot");
+    const response = await invokeCommand<unknown>("tag_schema_snapshot", undefined, IDEMPOTENT_READ);
     return parseWithSchema("tag_schema_snapshot response", response, TagSchemaSchema);
   } catch (error) {
     logger.warn("tag_schema_snapshot", String(error));
@@ -1,4 +1,4 @@
-import { Profiler, memo, useCallback, useEffect, useState } from "react";
+import { Profiler, memo, useCallback, useEffect, useMemo, useState } from "react";
 import type { ProfilerOnRenderCallback } from "react";
 
 import AddIcon from "@mui/icons-material/Add";
@@ -13,6 +13,7 @@ import MenuBookIcon from "@mui/icons-material/MenuBook";
 import MenuIcon from "@mui/icons-material/Menu";
 import SettingsIcon from "@mui/icons-material/Settings";
 import ViewKanbanIcon from "@mui/icons-material/ViewKanban";
+import Alert from "@mui/material/Alert";
 import AppBar from "@mui/material/AppBar";
 import Button from "@mui/material/Button";
 import Divider from "@mui/material/Divider";
@@ -22,12 +23,16 @@ import List from "@mui/material/List";
 import ListItemButton from "@mui/material/ListItemButton";
 import ListItemIcon from "@mui/material/ListItemIcon";
 import ListItemText from "@mui/material/ListItemText";
+import Snackbar from "@mui/material/Snackbar";
 import Stack from "@mui/material/Stack";
 import Toolbar from "@mui/material/Toolbar";
+import Tooltip from "@mui/material/Tooltip";
 import Typography from "@mui/material/Typography";
 
 import { AddTaskDialog } from "../components/AddTaskDialog";
 import { DiagnosticsPanel } from "../components/DiagnosticsPanel";
+import { FocusTimerIndicator } from "../components/FocusTimerIndicator";
+import { QuickCaptureDialog } from "../components/QuickCaptureDialog";
 import { SettingsDialog } from "../components/SettingsDialog";
 import { CalendarWorkspace } from "../features/calendar/CalendarWorkspace";
 import { ContactsWorkspace } from "../features/contacts/ContactsWorkspace";
@@ -35,8 +40,14 @@ import { DictionaryWorkspace } from "../features/dictionary/DictionaryWorkspace"
 import { KanbanWorkspace } from "../features/kanban/KanbanWorkspace";
 import { MapWorkspace } from "../features/map/MapWorkspace";
 import { TasksWorkspace } from "../features/tasks/TasksWorkspace";
+import { resolveCalendarConfig } from "../lib/calendar";
+import { captureTag } from "../lib/capture";
 import { logger } from "../lib/logger";
-import { useDiagnosticsSlice, useSettingsSlice, useShellSlice } from "../store/slices";
+import { isCommandAllowed, isReadOnly } from "../lib/permissions";
+import { resolveDraftAutosaveMs } from "../lib/taskDraft";
+import { resolveTitleLimit } from "../lib/titleLimit";
+import { describeTimezoneSource, timezoneAbbreviation, timezoneNeedsAttention } from "../lib/timezone";
+import { useDiagnosticsSlice, useQuickAddVocabulary, useSettingsSlice, useShellSlice } from "../store/slices";
 
 const TasksWorkspaceMemo = memo(TasksWorkspace);
 const KanbanWorkspaceMemo = memo(KanbanWorkspace);
@@ -59,12 +70,24 @@ export function AppShell() {
     openAddTaskDialog,
     closeAddTaskDialog,
     createTask,
+    quickCaptureOpen,
+    openQuickCapture,
+    closeQuickCapture,
+    captureTask,
+    disabledCommands,
     loading,
     runtimeConfig,
     dictionaryLanguages,
     tagSchema,
     tagColorMap,
-    kanbanBoards
+    kanbanBoards,
+    taskTemplates,
+    deleteTaskTemplate,
+    toast,
+    dismissToast,
+    externalCalendars,
+    scheduleExternalCalendarSync,
+    subscribeExternalCalendarSync
   } = useShellSlice();
   const {
     settingsOpen,
@@ -72,14 +95,21 @@ export function AppShell() {
     closeSettings,
     dueConfig,
     duePermission,
+    configUpdateResults,
     setThemeFollowSystem,
     setDueNotificationsEnabled,
     setDuePreNotifyEnabled,
     setDuePreNotifyMinutes,
     requestDueNotificationPermission,
-    scanDueNotifications
+    scanDueNotifications,
+    demoTaskCount,
+    clearDemoData,
+    exportSettings,
+    importSettings
   } = useSettingsSlice();
   const { commandFailures, clearCommandFailures } = useDiagnosticsSlice();
+  const quickAddVocabulary = useQuickAddVocabulary();
+  const canAddTasks = isCommandAllowed(disabledCommands, "tasks_write");
 
   const [diagnosticsOpen, setDiagnosticsOpen] = useState(false);
   const [dictionaryTaskSplitOpen, setDictionaryTaskSplitOpen] = useState(false);
@@ -104,8 +134,36 @@ export function AppShell() {
     void bootstrap();
   }, [bootstrap]);
 
+  // Calendar sources live in local storage; the backend scheduler syncs them
+  // in the background and reports each run as an event.
+  useEffect(() => {
+    void scheduleExternalCalendarSync();
+  }, [externalCalendars, scheduleExternalCalendarSync]);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    void subscribeExternalCalendarSync().then((stop) => {
+      if (cancelled) {
+        stop();
+      } else {
+        unlisten = stop;
+      }
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [subscribeExternalCalendarSync]);
+
   const runtimeMode = runtimeConfig?.app?.mode ?? runtimeConfig?.mode ?? "prod";
   const loggingDirectory = runtimeConfig?.logging?.directory ?? "logs";
+  const calendarConfig = useMemo(() => resolveCalendarConfig(runtimeConfig), [runtimeConfig]);
+  const draftAutosaveMs = useMemo(() => resolveDraftAutosaveMs(runtimeConfig), [runtimeConfig]);
+  const titleLimit = useMemo(() => resolveTitleLimit(runtimeConfig), [runtimeConfig]);
+  const timezoneTooltip = calendarConfig.backend_timezone && calendarConfig.backend_timezone !== calendarConfig.timezone
+    ? `${describeTimezoneSource(calendarConfig)}; the backend uses ${calendarConfig.backend_timezone}`
+    : describeTimezoneSource(calendarConfig);
   const isDevMode = runtimeMode === "dev";
   const verboseRenderProfiling = String(import.meta.env.VITE_RIVET_PROFILE_VERBOSE ?? "").trim() === "1";
   const contactsFeatureEnabled = runtimeConfig?.ui?.features?.contacts ?? true;
@@ -217,8 +275,16 @@ export function AppShell() {
       const isMeta = event.metaKey || event.ctrlKey;
       const key = event.key.toLowerCase();
 
+      if (isMeta && event.shiftKey && key === "n") {
+        event.preventDefault();
+        if (canAddTasks) {
+          openQuickCapture();
+        }
+        return;
+      }
+
       if (isMeta && key === "n") {
-        if (activeTab === "contacts" && contactsFeatureEnabled) {
+        if (!canAddTasks || (activeTab === "contacts" && contactsFeatureEnabled)) {
           return;
         }
         event.preventDefault();
@@ -281,7 +347,7 @@ export function AppShell() {
 
     window.addEventListener("keydown", handler);
     return () => window.removeEventListener("keydown", handler);
-  }, [activeTab, closeSettings, contactsFeatureEnabled, dictionaryFeatureEnabled, mapFeatureEnabled, openAddTaskDialog, openSettings, setActiveTab, settingsOpen]);
+  }, [activeTab, canAddTasks, closeSettings, contactsFeatureEnabled, dictionaryFeatureEnabled, mapFeatureEnabled, openAddTaskDialog, openQuickCapture, openSettings, setActiveTab, settingsOpen]);
 
   return (
     <div className="flex h-screen min-h-screen flex-col overflow-hidden">
@@ -304,20 +370,33 @@ export function AppShell() {
           </Typography>
           <div className="ml-auto" />
           <Stack direction="row" spacing={1} alignItems="center">
+            <FocusTimerIndicator />
             <Typography variant="caption" color="text.secondary">
               mode: {runtimeMode}
             </Typography>
             <Typography variant="caption" color="text.secondary">
               logs: {loggingDirectory}
             </Typography>
-            <Button
-              variant="outlined"
-              size="small"
-              startIcon={<AddIcon fontSize="small" />}
-              onClick={() => openAddTaskDialog()}
-            >
-              Add Task
-            </Button>
+            <Tooltip title={timezoneTooltip}>
+              <Typography variant="caption" color={timezoneNeedsAttention(calendarConfig) ? "warning.main" : "text.secondary"}>
+                tz: {calendarConfig.timezone} ({timezoneAbbreviation(Date.now(), calendarConfig.timezone)})
+              </Typography>
+            </Tooltip>
+            {isReadOnly(disabledCommands) ? (
+              <Typography variant="caption" color="warning.main">
+                read-only
+              </Typography>
+            ) : null}
+            {canAddTasks ? (
+              <Button
+                variant="outlined"
+                size="small"
+                startIcon={<AddIcon fontSize="small" />}
+                onClick={() => openAddTaskDialog()}
+              >
+                Add Task
+              </Button>
+            ) : null}
             <Button
               variant="outlined"
               size="small"
@@ -437,16 +516,33 @@ export function AppShell() {
         tagSchema={tagSchema}
         tagColorMap={tagColorMap}
         kanbanBoards={kanbanBoards}
+        templates={taskTemplates}
+        timezone={calendarConfig.timezone}
+        draftAutosaveMs={draftAutosaveMs}
+        titleLimit={titleLimit}
+        onDeleteTemplate={deleteTaskTemplate}
         onClose={closeAddTaskDialog}
         onSubmit={createTask}
       />
 
+      <QuickCaptureDialog
+        open={quickCaptureOpen}
+        busy={loading}
+        captureTag={captureTag(runtimeConfig)}
+        vocabulary={quickAddVocabulary}
+        onClose={closeQuickCapture}
+        onCapture={captureTask}
+      />
+
       <SettingsDialog
         open={settingsOpen}
         runtimeMode={runtimeMode}
         loggingDirectory={loggingDirectory}
         dueConfig={dueConfig}
         duePermission={duePermission}
+        configUpdateResults={configUpdateResults}
+        demoTaskCount={demoTaskCount}
+        canClearDemoData={isCommandAllowed(disabledCommands, "tasks_delete")}
         themeFollowSystem={themeFollowSystem}
         onClose={closeSettings}
         onToggleThemeFollowSystem={setThemeFollowSystem}
@@ -456,8 +552,32 @@ export function AppShell() {
         onRequestPermission={() => {
           void requestDueNotificationPermission();
         }}
+        onClearDemoData={() => {
+          void clearDemoData();
+        }}
+        canImportSettings={isCommandAllowed(disabledCommands, "config_write")}
+        onExportSettings={() => {
+          void exportSettings();
+        }}
+        onImportSettings={importSettings}
       />
 
+      <Snackbar
+        key={toast?.id}
+        open={toast !== null}
+        autoHideDuration={6000}
+        anchorOrigin={{ vertical: "bottom", horizontal: "center" }}
+        onClose={(_event, reason) => {
+          if (reason !== "clickaway") {
+            dismissToast();
+          }
+        }}
+      >
+        <Alert severity={toast?.severity ?? "info"} variant="filled" onClose={dismissToast}>
+          {toast?.message}
+        </Alert>
+      </Snackbar>
+
       <DiagnosticsPanel
         open={isDevMode && diagnosticsOpen}
         failures={commandFailures}
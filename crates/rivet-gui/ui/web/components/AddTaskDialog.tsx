@@ -12,11 +12,17 @@ import Stack from "@mui/material/Stack";
 import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
-import { defaultKanbanLane, isSingleSelectKey, removeTagsForKey, splitTags, tagColorStyle } from "../lib/tags";
+import { addTagWithSelection, boardIdFromTaskTags, defaultKanbanLane, recurrenceFromTags, splitTags, tagColorStyle } from "../lib/tags";
+import { loadTaskDraft } from "../lib/storage";
+import { taskDraftKey } from "../lib/taskDraft";
+import { templateDueIso, templateFormTags } from "../lib/templates";
+import { titleLengthHint, titleRejected, type TitleLimit } from "../lib/titleLimit";
 import type { TagSchema } from "../types/config";
-import type { TaskCreate } from "../types/core";
-import type { AddTaskDialogContext, KanbanBoardDef, RecurrenceDraft } from "../types/ui";
+import type { TaskCreate, TaskPriority } from "../types/core";
+import type { AddTaskDialogContext, KanbanBoardDef, RecurrenceDraft, TaskDraftFields, TaskTemplate } from "../types/ui";
 import { buildTaskCreateWithTagSchema } from "../store/useAppStore";
+import { TaskDraftNotice } from "./TaskDraftNotice";
+import { useTaskDraftAutosave } from "./useTaskDraftAutosave";
 
 interface AddTaskDialogProps {
   open: boolean;
@@ -25,8 +31,13 @@ interface AddTaskDialogProps {
   tagSchema: TagSchema | null;
   tagColorMap: Record<string, string>;
   kanbanBoards: KanbanBoardDef[];
+  templates: TaskTemplate[];
+  timezone: string;
+  draftAutosaveMs: number | null;
+  titleLimit: TitleLimit | null;
+  onDeleteTemplate: (templateId: string) => void;
   onClose: () => void;
-  onSubmit: (input: TaskCreate) => Promise<void>;
+  onSubmit: (input: TaskCreate) => Promise<boolean>;
 }
 
 const EMPTY_RECURRENCE: RecurrenceDraft = {
@@ -34,7 +45,11 @@ const EMPTY_RECURRENCE: RecurrenceDraft = {
   time: "",
   days: [],
   months: [],
-  monthDay: ""
+  monthDay: "",
+  ends: "never",
+  until: "",
+  count: "",
+  remaining: null
 };
 
 export function AddTaskDialog(props: AddTaskDialogProps) {
@@ -48,7 +63,41 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
   const [pickerValue, setPickerValue] = useState<string>("");
   const [boardId, setBoardId] = useState<string>(props.context.boardId ?? "");
   const [recurrence, setRecurrence] = useState<RecurrenceDraft>(EMPTY_RECURRENCE);
+  const [priority, setPriority] = useState<TaskPriority | null>(null);
+  const [templateId, setTemplateId] = useState("");
   const [error, setError] = useState<string | null>(null);
+  const [draftBaseline, setDraftBaseline] = useState<TaskDraftFields | null>(null);
+  const [draftSavedAt, setDraftSavedAt] = useState<number | null>(null);
+
+  const draftFields = useMemo((): TaskDraftFields => ({
+    title,
+    description,
+    project,
+    due,
+    customTagInput,
+    selectedTags,
+    boardId,
+    priority,
+    recurrence
+  }), [title, description, project, due, customTagInput, selectedTags, boardId, priority, recurrence]);
+  const draft = useTaskDraftAutosave({
+    key: props.open ? taskDraftKey(null) : null,
+    fields: draftFields,
+    baseline: draftBaseline,
+    intervalMs: props.draftAutosaveMs
+  });
+
+  const applyDraftFields = (fields: TaskDraftFields) => {
+    setTitle(fields.title);
+    setDescription(fields.description);
+    setProject(fields.project);
+    setDue(fields.due);
+    setCustomTagInput(fields.customTagInput);
+    setSelectedTags([...fields.selectedTags]);
+    setBoardId(fields.boardId);
+    setPriority(fields.priority);
+    setRecurrence(fields.recurrence);
+  };
 
   const keyOptions = useMemo(() => {
     return (props.tagSchema?.keys ?? []).filter((entry) => entry.id !== "board");
@@ -65,25 +114,74 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
     }
     const firstKey = keyOptions[0];
     const firstValue = firstKey?.values?.[0] ?? "";
-    setTitle("");
-    setDescription("");
-    setProject("");
-    setDue("");
-    setCustomTagInput("");
-    setSelectedTags([]);
+    const baseline: TaskDraftFields = {
+      title: "",
+      description: "",
+      project: "",
+      due: "",
+      customTagInput: "",
+      selectedTags: [],
+      boardId: props.context.boardId ?? "",
+      priority: null,
+      recurrence: EMPTY_RECURRENCE
+    };
+    const stored = props.draftAutosaveMs === null ? null : loadTaskDraft(taskDraftKey(null));
+    applyDraftFields(stored
+      ? {
+        ...stored,
+        boardId: props.context.lockBoardSelection ? baseline.boardId : stored.boardId,
+        recurrence: props.context.allowRecurrence ? stored.recurrence : EMPTY_RECURRENCE
+      }
+      : baseline);
+    setDraftBaseline(baseline);
+    setDraftSavedAt(stored?.saved_at ?? null);
     setPickerKey(firstKey?.id ?? "");
     setPickerValue(firstValue);
-    setBoardId(props.context.boardId ?? "");
-    setRecurrence(EMPTY_RECURRENCE);
+    setTemplateId("");
     setError(null);
-  }, [props.open, props.context.boardId, keyOptions]);
+  }, [props.open, props.context.boardId, props.context.lockBoardSelection, props.context.allowRecurrence, props.draftAutosaveMs, keyOptions]);
+
+  const discardDraft = () => {
+    draft.discard();
+    if (draftBaseline) {
+      applyDraftFields(draftBaseline);
+    }
+    setTemplateId("");
+    setDraftSavedAt(null);
+  };
+
+  const applyTemplate = (id: string) => {
+    setTemplateId(id);
+    const template = props.templates.find((entry) => entry.id === id);
+    if (!template) {
+      return;
+    }
+    setTitle(template.title);
+    setDescription(template.description);
+    setProject(template.project ?? "");
+    setDue(templateDueIso(template, Date.now(), props.timezone) ?? "");
+    setSelectedTags(templateFormTags(template));
+    setPriority(template.priority);
+    const templateBoard = boardIdFromTaskTags(template.tags);
+    if (!props.context.lockBoardSelection && templateBoard && props.kanbanBoards.some((board) => board.id === templateBoard)) {
+      setBoardId(templateBoard);
+    }
+    setRecurrence(props.context.allowRecurrence ? recurrenceFromTags(template.tags) : EMPTY_RECURRENCE);
+    setError(null);
+  };
 
   const canSave = useMemo(() => title.trim().length > 0 && !props.busy, [title, props.busy]);
 
+  // Escape and backdrop clicks keep the draft; only Cancel drops it.
   const handleClose = () => {
     props.onClose();
   };
 
+  const handleCancel = () => {
+    draft.clear();
+    props.onClose();
+  };
+
   const handleAddCustomTags = () => {
     const values = splitTags(customTagInput);
     if (values.length === 0) {
@@ -106,30 +204,27 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
       return;
     }
     const tag = `${pickerKey}:${pickerValue}`;
-    setSelectedTags((prev) => {
-      const next = [...prev];
-      if (isSingleSelectKey(props.tagSchema, pickerKey)) {
-        removeTagsForKey(next, pickerKey);
-      }
-      if (!next.includes(tag)) {
-        next.push(tag);
-      }
-      return next;
-    });
+    setSelectedTags((prev) => addTagWithSelection(prev, tag, props.tagSchema));
   };
 
+  const titleHint = titleLengthHint(title, props.titleLimit);
+
   const handleSave = async () => {
     if (!title.trim()) {
       setError("Title is required.");
       return;
     }
+    if (titleRejected(title, props.titleLimit)) {
+      setError(`Title is longer than ${props.titleLimit?.maxLength} characters.`);
+      return;
+    }
 
     const task = buildTaskCreateWithTagSchema(
       {
         title: title.trim(),
         description: description.trim(),
         project: project.trim() ? project.trim() : null,
-        priority: null,
+        priority,
         due: due.trim() ? due.trim() : null,
         wait: null,
         scheduled: null,
@@ -142,7 +237,9 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
       props.tagSchema
     );
 
-    await props.onSubmit(task);
+    if (await props.onSubmit(task)) {
+      draft.clear();
+    }
   };
 
   const laneName = defaultKanbanLane(props.tagSchema);
@@ -154,6 +251,41 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
       <DialogContent dividers className="max-h-[calc(100vh-160px)]">
         <Stack spacing={2.25}>
           {error ? <Typography color="error">{error}</Typography> : null}
+          {draftSavedAt !== null ? <TaskDraftNotice savedAt={draftSavedAt} onDiscard={discardDraft} /> : null}
+
+          {props.templates.length > 0 ? (
+            <Stack direction="row" spacing={1} alignItems="center">
+              <TextField
+                select
+                label="From template"
+                value={templateId}
+                onChange={(event) => applyTemplate(event.target.value)}
+                className="flex-1"
+              >
+                <MenuItem value="">None</MenuItem>
+                {props.templates.map((template) => (
+                  <MenuItem key={template.id} value={template.id}>
+                    {template.name}
+                  </MenuItem>
+                ))}
+              </TextField>
+              <Button
+                color="error"
+                disabled={!templateId}
+                onClick={() => {
+                  props.onDeleteTemplate(templateId);
+                  setTemplateId("");
+                }}
+              >
+                Delete template
+              </Button>
+            </Stack>
+          ) : null}
+          {priority ? (
+            <Typography variant="caption" color="text.secondary">
+              Priority from template: {priority}
+            </Typography>
+          ) : null}
 
           <TextField
             label="Title"
@@ -165,6 +297,8 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
                 setError(null);
               }
             }}
+            error={titleRejected(title, props.titleLimit)}
+            helperText={titleHint?.message}
           />
 
           <TextField
@@ -376,6 +510,40 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
                   />
                 </>
               ) : null}
+
+              {recurrence.pattern !== "none" ? (
+                <Stack direction="row" spacing={1}>
+                  <TextField
+                    select
+                    label="Ends"
+                    value={recurrence.ends}
+                    onChange={(event) => setRecurrence((prev) => ({ ...prev, ends: event.target.value as RecurrenceDraft["ends"] }))}
+                    sx={{ minWidth: 180 }}
+                  >
+                    <MenuItem value="never">Never</MenuItem>
+                    <MenuItem value="until">On date</MenuItem>
+                    <MenuItem value="count">After N occurrences</MenuItem>
+                  </TextField>
+                  {recurrence.ends === "until" ? (
+                    <TextField
+                      type="date"
+                      label="Until"
+                      value={recurrence.until}
+                      InputLabelProps={{ shrink: true }}
+                      onChange={(event) => setRecurrence((prev) => ({ ...prev, until: event.target.value }))}
+                    />
+                  ) : null}
+                  {recurrence.ends === "count" ? (
+                    <TextField
+                      type="number"
+                      label="Occurrences"
+                      value={recurrence.count}
+                      inputProps={{ min: 1 }}
+                      onChange={(event) => setRecurrence((prev) => ({ ...prev, count: event.target.value, remaining: null }))}
+                    />
+                  ) : null}
+                </Stack>
+              ) : null}
             </>
           ) : (
             <Typography variant="caption" color="text.secondary">
@@ -385,7 +553,7 @@ export function AddTaskDialog(props: AddTaskDialogProps) {
         </Stack>
       </DialogContent>
       <DialogActions>
-        <Button onClick={handleClose} disabled={props.busy}>
+        <Button onClick={handleCancel} disabled={props.busy}>
           Cancel
         </Button>
         <Button onClick={handleSave} disabled={!canSave} variant="contained">
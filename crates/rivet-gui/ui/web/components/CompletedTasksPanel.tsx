@@ -0,0 +1,112 @@
+import { useState } from "react";
+
+import Button from "@mui/material/Button";
+import FormControlLabel from "@mui/material/FormControlLabel";
+import List from "@mui/material/List";
+import ListItem from "@mui/material/ListItem";
+import ListItemButton from "@mui/material/ListItemButton";
+import ListItemText from "@mui/material/ListItemText";
+import ListSubheader from "@mui/material/ListSubheader";
+import Paper from "@mui/material/Paper";
+import Stack from "@mui/material/Stack";
+import Switch from "@mui/material/Switch";
+import TextField from "@mui/material/TextField";
+import Typography from "@mui/material/Typography";
+
+import { groupTasksByCompletionDay, type CompletedRange } from "../lib/completed";
+import { formatTaskDate, type DateFormat } from "../lib/dateFormat";
+import type { TaskDto } from "../types/core";
+
+interface CompletedTasksPanelProps {
+  tasks: TaskDto[];
+  range: CompletedRange;
+  timezone: string;
+  dateFormat: DateFormat;
+  nowUtcMs: number;
+  selectedTaskId: string | null;
+  busy: boolean;
+  onRangeChange: (range: CompletedRange) => void;
+  onSelect: (taskId: string) => void;
+  onReopen?: (taskId: string) => void;
+  onRefresh: () => void;
+}
+
+export function CompletedTasksPanel(props: CompletedTasksPanelProps) {
+  const [groupByDay, setGroupByDay] = useState(true);
+
+  const renderRow = (task: TaskDto) => (
+    <ListItem
+      key={task.uuid}
+      disablePadding
+      secondaryAction={props.onReopen ? (
+        <Button size="small" disabled={props.busy} onClick={() => props.onReopen?.(task.uuid)}>
+          Reopen
+        </Button>
+      ) : undefined}
+    >
+      <ListItemButton selected={task.uuid === props.selectedTaskId} onClick={() => props.onSelect(task.uuid)}>
+        <ListItemText
+          primary={task.title || "Untitled Task"}
+          secondary={`${task.project ?? "No project"} · ${
+            task.completed ? formatTaskDate(task.completed, props.dateFormat, props.timezone, props.nowUtcMs) : "completion time unknown"
+          }`}
+        />
+      </ListItemButton>
+    </ListItem>
+  );
+
+  return (
+    <Paper variant="outlined" className="p-3">
+      <Stack spacing={1}>
+        <Stack direction="row" justifyContent="space-between" alignItems="center">
+          <Typography variant="subtitle2">Completed ({props.tasks.length})</Typography>
+          <Button size="small" onClick={props.onRefresh}>
+            Refresh
+          </Button>
+        </Stack>
+        <Stack direction="row" spacing={1}>
+          <TextField
+            size="small"
+            type="date"
+            label="From"
+            value={props.range.from}
+            onChange={(event) => props.onRangeChange({ ...props.range, from: event.target.value })}
+            InputLabelProps={{ shrink: true }}
+            fullWidth
+          />
+          <TextField
+            size="small"
+            type="date"
+            label="To"
+            value={props.range.to}
+            onChange={(event) => props.onRangeChange({ ...props.range, to: event.target.value })}
+            InputLabelProps={{ shrink: true }}
+            fullWidth
+          />
+        </Stack>
+        <FormControlLabel
+          control={<Switch size="small" checked={groupByDay} onChange={(event) => setGroupByDay(event.target.checked)} />}
+          label="Group by day"
+        />
+        {props.tasks.length === 0 ? (
+          <Typography variant="body2">Nothing completed in this range.</Typography>
+        ) : (
+          <List dense className="max-h-64 overflow-auto py-0">
+            {groupByDay
+              ? groupTasksByCompletionDay(props.tasks, props.timezone).map((group) => (
+                <li key={group.day ?? "unknown"}>
+                  <ul className="p-0">
+                    <ListSubheader className="leading-8">
+                      {group.day ?? "Unknown day"} ({group.tasks.length})
+                    </ListSubheader>
+                    {group.tasks.map(renderRow)}
+                  </ul>
+                </li>
+              ))
+              : props.tasks.map(renderRow)}
+          </List>
+        )}
+      </Stack>
+    </Paper>
+  );
+}
@@ -0,0 +1,53 @@
+import type { ReactNode } from "react";
+
+import Button from "@mui/material/Button";
+import Dialog from "@mui/material/Dialog";
+import DialogActions from "@mui/material/DialogActions";
+import DialogContent from "@mui/material/DialogContent";
+import DialogTitle from "@mui/material/DialogTitle";
+import Typography from "@mui/material/Typography";
+
+export interface ConfirmOptions {
+  title: string;
+  body: ReactNode;
+  confirmLabel?: string;
+  cancelLabel?: string;
+  destructive?: boolean;
+}
+
+interface ConfirmDialogProps extends ConfirmOptions {
+  open: boolean;
+  onResult: (confirmed: boolean) => void;
+}
+
+// Escape and backdrop clicks count as cancel. Destructive prompts focus Cancel
+// so a stray Enter never deletes anything.
+export function ConfirmDialog(props: ConfirmDialogProps) {
+  return (
+    <Dialog
+      open={props.open}
+      onClose={() => props.onResult(false)}
+      maxWidth="xs"
+      fullWidth
+      aria-labelledby="confirm-dialog-title"
+    >
+      <DialogTitle id="confirm-dialog-title">{props.title}</DialogTitle>
+      <DialogContent dividers>
+        {typeof props.body === "string" ? <Typography variant="body2">{props.body}</Typography> : props.body}
+      </DialogContent>
+      <DialogActions>
+        <Button autoFocus={props.destructive} onClick={() => props.onResult(false)}>
+          {props.cancelLabel ?? "Cancel"}
+        </Button>
+        <Button
+          autoFocus={!props.destructive}
+          variant="contained"
+          color={props.destructive ? "error" : "primary"}
+          onClick={() => props.onResult(true)}
+        >
+          {props.confirmLabel ?? "Confirm"}
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+}
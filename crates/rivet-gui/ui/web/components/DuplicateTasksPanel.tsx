@@ -0,0 +1,91 @@
+import Button from "@mui/material/Button";
+import Chip from "@mui/material/Chip";
+import List from "@mui/material/List";
+import ListItemButton from "@mui/material/ListItemButton";
+import ListItemText from "@mui/material/ListItemText";
+import Paper from "@mui/material/Paper";
+import Stack from "@mui/material/Stack";
+import Typography from "@mui/material/Typography";
+
+import type { TaskDedupeGroup } from "../types/core";
+
+interface DuplicateTasksPanelProps {
+  groups: TaskDedupeGroup[];
+  threshold: number;
+  selectedTaskId: string | null;
+  busy: boolean;
+  onSelect: (taskId: string) => void;
+  onRefresh: () => void;
+  // Omitted when deletes are disabled; the panel then only lists groups.
+  onResolve?: (groups: TaskDedupeGroup[], merge: boolean) => void;
+}
+
+export function DuplicateTasksPanel(props: DuplicateTasksPanelProps) {
+  const { onResolve } = props;
+  return (
+    <Paper variant="outlined" className="p-3">
+      <Stack spacing={1}>
+        <Stack direction="row" justifyContent="space-between" alignItems="center">
+          <Typography variant="subtitle2">Possible Duplicates ({props.groups.length})</Typography>
+          <Stack direction="row" spacing={1}>
+            {onResolve && props.groups.length > 1 ? (
+              <Button size="small" disabled={props.busy} onClick={() => onResolve(props.groups, true)}>
+                Merge All
+              </Button>
+            ) : null}
+            <Button size="small" onClick={props.onRefresh}>
+              Refresh
+            </Button>
+          </Stack>
+        </Stack>
+        <Typography variant="caption" color="text.secondary">
+          Open tasks in the same project scoring {props.threshold}+ on title and tag similarity. Merging keeps the
+          oldest task and folds tags, annotations and dates from the rest into it.
+        </Typography>
+        {props.groups.length === 0 ? (
+          <Typography variant="body2">No duplicates found.</Typography>
+        ) : (
+          <Stack spacing={1} className="max-h-64 overflow-auto">
+            {props.groups.map((group) => {
+              const kept = group.tasks[0];
+              return (
+                <Paper key={kept?.uuid ?? group.reason} variant="outlined" className="p-2">
+                  <Stack direction="row" justifyContent="space-between" alignItems="center">
+                    <Typography variant="caption" color="text.secondary">
+                      Score {group.score} · {group.reason}
+                    </Typography>
+                    {onResolve ? (
+                      <Stack direction="row" spacing={0.5}>
+                        <Button size="small" disabled={props.busy} onClick={() => onResolve([group], true)}>
+                          Merge
+                        </Button>
+                        <Button size="small" color="error" disabled={props.busy} onClick={() => onResolve([group], false)}>
+                          Delete Extras
+                        </Button>
+                      </Stack>
+                    ) : null}
+                  </Stack>
+                  <List dense className="py-0">
+                    {group.tasks.map((task) => (
+                      <ListItemButton
+                        key={task.uuid}
+                        selected={task.uuid === props.selectedTaskId}
+                        onClick={() => props.onSelect(task.uuid)}
+                      >
+                        <ListItemText
+                          primary={task.title || "Untitled Task"}
+                          secondary={`${task.project ?? "No project"}${task.tags.length > 0 ? ` · ${task.tags.join(" ")}` : ""}`}
+                        />
+                        {task.uuid === kept?.uuid ? <Chip size="small" label="keep" /> : null}
+                      </ListItemButton>
+                    ))}
+                  </List>
+                </Paper>
+              );
+            })}
+          </Stack>
+        )}
+      </Stack>
+    </Paper>
+  );
+}
@@ -0,0 +1,211 @@
+import { useState } from "react";
+import type { MouseEvent } from "react";
+
+import Button from "@mui/material/Button";
+import Chip from "@mui/material/Chip";
+import Dialog from "@mui/material/Dialog";
+import DialogActions from "@mui/material/DialogActions";
+import DialogContent from "@mui/material/DialogContent";
+import DialogTitle from "@mui/material/DialogTitle";
+import Menu from "@mui/material/Menu";
+import MenuItem from "@mui/material/MenuItem";
+import Paper from "@mui/material/Paper";
+import Stack from "@mui/material/Stack";
+import TextField from "@mui/material/TextField";
+import Typography from "@mui/material/Typography";
+
+import { useConfirm } from "./useConfirm";
+import type { FacetBulkAction } from "../types/ui";
+
+interface FacetEntry {
+  value: string;
+  count: number;
+}
+
+interface FacetPanelProps {
+  projectFacets: FacetEntry[];
+  tagFacets: FacetEntry[];
+  // Tags only closed tasks still carry; see `unusedTagNames`.
+  unusedTags: string[];
+  selectedCount: number;
+  busy: boolean;
+  onNavigate: (facet: FacetBulkAction["facet"], value: string) => void;
+  onAction: (action: FacetBulkAction) => void;
+  onCleanupUnusedTags: () => void;
+}
+
+interface FacetMenuTarget {
+  anchor: HTMLElement;
+  facet: FacetBulkAction["facet"];
+  entry: FacetEntry;
+}
+
+export function FacetPanel(props: FacetPanelProps) {
+  const [menuTarget, setMenuTarget] = useState<FacetMenuTarget | null>(null);
+  const [renameTarget, setRenameTarget] = useState<Omit<FacetMenuTarget, "anchor"> | null>(null);
+  const [renameInput, setRenameInput] = useState("");
+  const { requestConfirm, confirmDialog } = useConfirm();
+
+  const openMenu = (event: MouseEvent<HTMLElement>, facet: FacetBulkAction["facet"], entry: FacetEntry) => {
+    event.preventDefault();
+    setMenuTarget({ anchor: event.currentTarget, facet, entry });
+  };
+
+  const closeMenu = () => setMenuTarget(null);
+
+  const applyToSelected = () => {
+    if (!menuTarget) {
+      return;
+    }
+    props.onAction({ facet: menuTarget.facet, value: menuTarget.entry.value, operation: "apply" });
+    closeMenu();
+  };
+
+  const removeFromAll = async () => {
+    if (!menuTarget) {
+      return;
+    }
+    const { facet, entry } = menuTarget;
+    closeMenu();
+    const label = facet === "tag" ? `tag "${entry.value}"` : `project "${entry.value}"`;
+    const confirmed = await requestConfirm({
+      title: facet === "tag" ? "Remove Tag" : "Remove Project",
+      body: `Remove ${label} from ${entry.count} task(s)?`,
+      confirmLabel: "Remove",
+      destructive: true
+    });
+    if (!confirmed) {
+      return;
+    }
+    props.onAction({ facet, value: entry.value, operation: "remove" });
+  };
+
+  const startRename = () => {
+    if (!menuTarget) {
+      return;
+    }
+    setRenameTarget({ facet: menuTarget.facet, entry: menuTarget.entry });
+    setRenameInput(menuTarget.entry.value);
+    closeMenu();
+  };
+
+  const submitRename = async () => {
+    if (!renameTarget) {
+      return;
+    }
+    const renameTo = renameInput.trim();
+    if (!renameTo || renameTo === renameTarget.entry.value) {
+      setRenameTarget(null);
+      return;
+    }
+    const confirmed = await requestConfirm({
+      title: renameTarget.facet === "tag" ? "Rename Tag" : "Rename Project",
+      body: `Rename "${renameTarget.entry.value}" to "${renameTo}" on ${renameTarget.entry.count} task(s)?`,
+      confirmLabel: "Rename"
+    });
+    if (!confirmed) {
+      return;
+    }
+    props.onAction({
+      facet: renameTarget.facet,
+      value: renameTarget.entry.value,
+      operation: "rename",
+      renameTo
+    });
+    setRenameTarget(null);
+  };
+
+  const cleanupUnused = async () => {
+    const confirmed = await requestConfirm({
+      title: "Clean Up Unused Tags",
+      body: `Remove ${props.unusedTags.length} tag(s) no open task uses from the closed tasks that still carry them? ${props.unusedTags.join(", ")}`,
+      confirmLabel: "Clean up",
+      destructive: true
+    });
+    if (!confirmed) {
+      return;
+    }
+    props.onCleanupUnusedTags();
+  };
+
+  const renderFacetGroup = (facet: FacetBulkAction["facet"], label: string, entries: FacetEntry[]) => (
+    <Stack spacing={0.75}>
+      <Typography variant="caption" color="text.secondary">
+        {label}
+      </Typography>
+      <Stack direction="row" spacing={0.75} flexWrap="wrap" useFlexGap>
+        {entries.length > 0 ? entries.map((entry) => (
+          <Chip
+            key={entry.value}
+            size="small"
+            variant="outlined"
+            label={`${entry.value} (${entry.count})`}
+            disabled={props.busy}
+            onClick={() => props.onNavigate(facet, entry.value)}
+            onContextMenu={(event) => openMenu(event, facet, entry)}
+          />
+        )) : <Typography variant="body2">None</Typography>}
+      </Stack>
+    </Stack>
+  );
+
+  return (
+    <Paper variant="outlined" className="p-3">
+      <Stack spacing={1.2}>
+        <Typography variant="subtitle2">Facets</Typography>
+        <Typography variant="caption" color="text.secondary">
+          Click to filter. Right-click for bulk actions.
+        </Typography>
+        {renderFacetGroup("project", "Projects", props.projectFacets)}
+        {renderFacetGroup("tag", "Tags", props.tagFacets)}
+        {props.unusedTags.length > 0 ? (
+          <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+            <Typography variant="caption" color="text.secondary" title={props.unusedTags.join(", ")}>
+              Unused tags ({props.unusedTags.length})
+            </Typography>
+            <Button size="small" disabled={props.busy} onClick={() => void cleanupUnused()}>
+              Clean up
+            </Button>
+          </Stack>
+        ) : null}
+      </Stack>
+
+      <Menu open={menuTarget !== null} anchorEl={menuTarget?.anchor ?? null} onClose={closeMenu}>
+        <MenuItem disabled={props.selectedCount === 0} onClick={applyToSelected}>
+          {menuTarget?.facet === "project" ? "Move selected tasks here" : "Add to selected tasks"} ({props.selectedCount})
+        </MenuItem>
+        <MenuItem onClick={() => void removeFromAll()}>
+          {menuTarget?.facet === "project" ? "Clear project from all" : "Remove from all"}
+        </MenuItem>
+        <MenuItem onClick={startRename}>Rename...</MenuItem>
+      </Menu>
+
+      <Dialog open={renameTarget !== null} onClose={() => setRenameTarget(null)} maxWidth="xs" fullWidth>
+        <DialogTitle>Rename {renameTarget?.facet === "project" ? "Project" : "Tag"}</DialogTitle>
+        <DialogContent>
+          <TextField
+            autoFocus
+            fullWidth
+            size="small"
+            margin="dense"
+            label="New name"
+            value={renameInput}
+            onChange={(event) => setRenameInput(event.target.value)}
+            onKeyDown={(event) => {
+              if (event.key === "Enter") {
+                void submitRename();
+              }
+            }}
+          />
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={() => setRenameTarget(null)}>Cancel</Button>
+          <Button variant="contained" disabled={!renameInput.trim()} onClick={() => void submitRename()}>
+            Rename
+          </Button>
+        </DialogActions>
+      </Dialog>
+      {confirmDialog}
+    </Paper>
+  );
+}
@@ -0,0 +1,46 @@
+import Box from "@mui/material/Box";
+import Typography from "@mui/material/Typography";
+
+import type { FieldDiffRow } from "../lib/fieldDiff";
+
+interface FieldCompareTableProps {
+  rows: FieldDiffRow[];
+  leftLabel: string;
+  rightLabel: string;
+}
+
+// Two-column field comparison; rows whose values differ are highlighted.
+export function FieldCompareTable(props: FieldCompareTableProps) {
+  return (
+    <Box
+      role="table"
+      sx={{ display: "grid", gridTemplateColumns: "minmax(6rem, auto) 1fr 1fr", columnGap: 1.5, rowGap: 0.25 }}
+    >
+      <Box role="row" sx={{ display: "contents" }}>
+        <Typography role="columnheader" variant="caption" color="text.secondary">Field</Typography>
+        <Typography role="columnheader" variant="subtitle2" noWrap>{props.leftLabel}</Typography>
+        <Typography role="columnheader" variant="subtitle2" noWrap>{props.rightLabel}</Typography>
+      </Box>
+      {props.rows.map((row) => {
+        const cellSx = {
+          px: 0.5,
+          py: 0.25,
+          borderRadius: 0.5,
+          whiteSpace: "pre-wrap",
+          overflowWrap: "anywhere",
+          bgcolor: row.differs ? "warning.light" : undefined,
+          color: row.differs ? "warning.contrastText" : undefined
+        } as const;
+        return (
+          <Box key={row.key} role="row" sx={{ display: "contents" }}>
+            <Typography role="rowheader" variant="caption" color="text.secondary" sx={{ py: 0.25 }}>
+              {row.label}{row.differs ? " *" : ""}
+            </Typography>
+            <Typography role="cell" variant="body2" sx={cellSx}>{row.left || "—"}</Typography>
+            <Typography role="cell" variant="body2" sx={cellSx}>{row.right || "—"}</Typography>
+          </Box>
+        );
+      })}
+    </Box>
+  );
+}
@@ -0,0 +1,62 @@
+import { useEffect, useMemo, useState } from "react";
+
+import StopIcon from "@mui/icons-material/Stop";
+import IconButton from "@mui/material/IconButton";
+import Stack from "@mui/material/Stack";
+import Tooltip from "@mui/material/Tooltip";
+import Typography from "@mui/material/Typography";
+
+import {
+  formatCountdown,
+  formatFocusDuration,
+  pomodoroRemainingMs,
+  resolveFocusConfig,
+  totalFocusSeconds
+} from "../lib/focus";
+import { useCommandAllowed, useFocusTimerSlice } from "../store/slices";
+
+// Lives in the header and ticks on its own so the rest of the shell does not
+// re-render every second.
+export function FocusTimerIndicator() {
+  const { runningTask, runtimeConfig, stopTaskClock } = useFocusTimerSlice();
+  const canWrite = useCommandAllowed("tasks_write");
+  const config = useMemo(() => resolveFocusConfig(runtimeConfig), [runtimeConfig]);
+  const [nowUtcMs, setNowUtcMs] = useState(() => Date.now());
+  const runningUuid = runningTask?.uuid ?? null;
+
+  useEffect(() => {
+    if (!runningUuid) {
+      return;
+    }
+    setNowUtcMs(Date.now());
+    const intervalId = window.setInterval(() => setNowUtcMs(Date.now()), 1000);
+    return () => window.clearInterval(intervalId);
+  }, [runningUuid]);
+
+  if (!runningTask) {
+    return null;
+  }
+
+  const remaining = pomodoroRemainingMs(runningTask, config, nowUtcMs);
+  const total = formatFocusDuration(totalFocusSeconds(runningTask, nowUtcMs));
+  const label = remaining === null
+    ? total
+    : remaining > 0
+      ? `${formatCountdown(remaining)} left`
+      : "break time";
+
+  return (
+    <Stack direction="row" spacing={0.5} alignItems="center">
+      <Tooltip title={`Focusing on "${runningTask.title || "Untitled Task"}" (${total} in total)`}>
+        <Typography variant="caption" color={remaining === 0 ? "warning.main" : "primary.main"}>
+          focus: {label}
+        </Typography>
+      </Tooltip>
+      {canWrite ? (
+        <IconButton size="small" aria-label="Stop focus timer" onClick={() => void stopTaskClock(runningTask.uuid)}>
+          <StopIcon fontSize="inherit" />
+        </IconButton>
+      ) : null}
+    </Stack>
+  );
+}
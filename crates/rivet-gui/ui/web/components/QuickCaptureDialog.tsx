@@ -0,0 +1,82 @@
+import { useEffect, useMemo, useState } from "react";
+
+import Button from "@mui/material/Button";
+import Dialog from "@mui/material/Dialog";
+import DialogActions from "@mui/material/DialogActions";
+import DialogContent from "@mui/material/DialogContent";
+import DialogTitle from "@mui/material/DialogTitle";
+import Stack from "@mui/material/Stack";
+import TextField from "@mui/material/TextField";
+import Typography from "@mui/material/Typography";
+
+import { applyQuickAddSuggestion, quickAddSuggestions, type QuickAddVocabulary } from "../lib/capture";
+
+interface QuickCaptureDialogProps {
+  open: boolean;
+  busy: boolean;
+  captureTag: string;
+  vocabulary: QuickAddVocabulary;
+  onClose: () => void;
+  onCapture: (text: string) => Promise<void>;
+}
+
+// One field, Enter to save: everything else is sorted out later from the inbox.
+export function QuickCaptureDialog(props: QuickCaptureDialogProps) {
+  const [text, setText] = useState("");
+  const suggestions = useMemo(() => quickAddSuggestions(text, props.vocabulary), [text, props.vocabulary]);
+
+  useEffect(() => {
+    if (props.open) {
+      setText("");
+    }
+  }, [props.open]);
+
+  const submit = () => {
+    if (props.busy || text.trim().length === 0) {
+      return;
+    }
+    void props.onCapture(text);
+  };
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} maxWidth="sm" fullWidth aria-labelledby="quick-capture-title">
+      <DialogTitle id="quick-capture-title">Quick Capture</DialogTitle>
+      <DialogContent>
+        <TextField
+          autoFocus
+          fullWidth
+          margin="dense"
+          label="What's on your mind?"
+          value={text}
+          onChange={(event) => setText(event.target.value)}
+          onKeyDown={(event) => {
+            if (event.key === "Enter") {
+              event.preventDefault();
+              submit();
+            }
+          }}
+        />
+        {suggestions.map((entry) => (
+          <Stack key={entry.original} direction="row" spacing={1} alignItems="center">
+            <Typography variant="body2">
+              Did you mean <code>{entry.suggestion}</code> for <code>{entry.original}</code>?
+            </Typography>
+            <Button size="small" onClick={() => setText(applyQuickAddSuggestion(text, entry))}>
+              Use it
+            </Button>
+          </Stack>
+        ))}
+        <Typography variant="caption" color="text.secondary">
+          Saved with +{props.captureTag}; <code>project:name</code>, <code>+tag</code> and <code>pri:H</code> are applied. Press
+          Enter to capture, Escape to cancel.
+        </Typography>
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={props.onClose}>Cancel</Button>
+        <Button variant="contained" disabled={props.busy || text.trim().length === 0} onClick={submit}>
+          Capture
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+}
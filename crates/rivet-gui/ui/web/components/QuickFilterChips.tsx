@@ -0,0 +1,32 @@
+import Chip from "@mui/material/Chip";
+import Stack from "@mui/material/Stack";
+
+import { QUICK_FILTERS, type QuickFilter } from "../lib/quickFilters";
+
+interface QuickFilterChipsProps {
+  active: QuickFilter[];
+  // Tasks each chip would leave with the other active chips applied.
+  counts: Record<QuickFilter, number>;
+  onToggle: (filter: QuickFilter) => void;
+}
+
+export function QuickFilterChips(props: QuickFilterChipsProps) {
+  return (
+    <Stack direction="row" spacing={0.75} flexWrap="wrap" useFlexGap>
+      {QUICK_FILTERS.map((filter) => {
+        const selected = props.active.includes(filter.id);
+        return (
+          <Chip
+            key={filter.id}
+            size="small"
+            color={selected ? "primary" : "default"}
+            variant={selected ? "filled" : "outlined"}
+            label={`${filter.label} (${props.counts[filter.id]})`}
+            aria-pressed={selected}
+            onClick={() => props.onToggle(filter.id)}
+          />
+        );
+      })}
+    </Stack>
+  );
+}
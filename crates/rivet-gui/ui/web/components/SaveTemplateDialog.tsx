@@ -0,0 +1,86 @@
+import { useEffect, useState } from "react";
+
+import Button from "@mui/material/Button";
+import Checkbox from "@mui/material/Checkbox";
+import Dialog from "@mui/material/Dialog";
+import DialogActions from "@mui/material/DialogActions";
+import DialogContent from "@mui/material/DialogContent";
+import DialogTitle from "@mui/material/DialogTitle";
+import FormControlLabel from "@mui/material/FormControlLabel";
+import Stack from "@mui/material/Stack";
+import TextField from "@mui/material/TextField";
+import Typography from "@mui/material/Typography";
+
+import type { TaskDto } from "../types/core";
+import type { TaskTemplate } from "../types/ui";
+
+interface SaveTemplateDialogProps {
+  open: boolean;
+  task: TaskDto | null;
+  templates: TaskTemplate[];
+  onClose: () => void;
+  onSave: (name: string, includeDue: boolean) => void;
+}
+
+export function SaveTemplateDialog(props: SaveTemplateDialogProps) {
+  const [name, setName] = useState("");
+  const [includeDue, setIncludeDue] = useState(false);
+
+  useEffect(() => {
+    if (props.open) {
+      setName(props.task?.title ?? "");
+      setIncludeDue(false);
+    }
+  }, [props.open, props.task]);
+
+  const trimmed = name.trim();
+  const replaces = props.templates.some((entry) => entry.name.trim().toLowerCase() === trimmed.toLowerCase());
+  const submit = () => {
+    if (trimmed) {
+      props.onSave(trimmed, includeDue && Boolean(props.task?.due));
+    }
+  };
+
+  return (
+    <Dialog open={props.open} onClose={props.onClose} maxWidth="xs" fullWidth aria-labelledby="save-template-title">
+      <DialogTitle id="save-template-title">Save as template</DialogTitle>
+      <DialogContent dividers>
+        <Stack spacing={1.5}>
+          <TextField
+            autoFocus
+            size="small"
+            label="Template name"
+            value={name}
+            onChange={(event) => setName(event.target.value)}
+            onKeyDown={(event) => {
+              if (event.key === "Enter") {
+                submit();
+              }
+            }}
+            helperText={replaces ? "Replaces the template with this name." : " "}
+          />
+          <FormControlLabel
+            control={(
+              <Checkbox
+                size="small"
+                checked={includeDue}
+                disabled={!props.task?.due}
+                onChange={(event) => setIncludeDue(event.target.checked)}
+              />
+            )}
+            label="Keep the due date, relative to the day it is used"
+          />
+          <Typography variant="caption" color="text.secondary">
+            Title, description, project, priority and tags are kept. Status, timestamps, lane and series progress are not.
+          </Typography>
+        </Stack>
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={props.onClose}>Cancel</Button>
+        <Button variant="contained" disabled={!trimmed} onClick={submit}>
+          Save
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+}
@@ -1,3 +1,6 @@
+import { useRef } from "react";
+import type { ChangeEvent } from "react";
+
 import Button from "@mui/material/Button";
 import Dialog from "@mui/material/Dialog";
 import DialogActions from "@mui/material/DialogActions";
@@ -9,6 +12,9 @@ import Switch from "@mui/material/Switch";
 import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
+import { useConfirm } from "./useConfirm";
+import type { ConfigBundleConflictPolicy, ConfigUpdateResult } from "../api/tauri";
+import { describeConfigUpdate } from "../lib/configUpdates";
 import type { DueNotificationPermission } from "../lib/notifications";
 import type { DueNotificationConfig } from "../types/ui";
 
@@ -19,12 +25,19 @@ interface SettingsDialogProps {
   themeFollowSystem: boolean;
   dueConfig: DueNotificationConfig;
   duePermission: DueNotificationPermission;
+  configUpdateResults: ConfigUpdateResult[];
+  demoTaskCount: number;
+  canClearDemoData: boolean;
   onClose: () => void;
   onToggleThemeFollowSystem: (enabled: boolean) => void;
   onToggleEnabled: (enabled: boolean) => void;
   onTogglePreEnabled: (enabled: boolean) => void;
   onPreMinutesChange: (minutes: number) => void;
   onRequestPermission: () => void;
+  onClearDemoData: () => void;
+  canImportSettings: boolean;
+  onExportSettings: () => void;
+  onImportSettings: (bundle: string, onConflict: ConfigBundleConflictPolicy) => Promise<string[]>;
 }
 
 function permissionLabel(permission: DueNotificationPermission): string {
@@ -41,6 +54,37 @@ function permissionLabel(permission: DueNotificationPermission): string {
 }
 
 export function SettingsDialog(props: SettingsDialogProps) {
+  const { requestConfirm, confirmDialog } = useConfirm();
+  const importInputRef = useRef<HTMLInputElement | null>(null);
+
+  const clearDemoData = async () => {
+    const confirmed = await requestConfirm({
+      title: "Clear Demo Data",
+      body: `Remove the ${props.demoTaskCount} example task(s) and the demo board? Your own tasks are not touched.`,
+      confirmLabel: "Clear",
+      destructive: true
+    });
+    if (confirmed) {
+      props.onClearDemoData();
+    }
+  };
+
+  const importSettings = async (file: File) => {
+    const bundle = await file.text();
+    const conflicts = await props.onImportSettings(bundle, "ask");
+    if (conflicts.length === 0) {
+      return;
+    }
+    const overwrite = await requestConfirm({
+      title: "Replace Settings",
+      body: `These differ from ${file.name}: ${conflicts.join(", ")}. Replace them with the bundled versions? Files are backed up first.`,
+      confirmLabel: "Replace",
+      cancelLabel: "Keep Mine",
+      destructive: true
+    });
+    await props.onImportSettings(bundle, overwrite ? "overwrite" : "keep");
+  };
+
   return (
     <Dialog open={props.open} onClose={props.onClose} maxWidth="sm" fullWidth>
       <DialogTitle>Settings</DialogTitle>
@@ -113,11 +157,83 @@ export function SettingsDialog(props: SettingsDialogProps) {
               </Button>
             </Stack>
           </Stack>
+
+          {props.configUpdateResults.length > 0 ? (
+            <Stack spacing={0.5}>
+              <Typography variant="subtitle2">Last Saved Change</Typography>
+              {props.configUpdateResults.map((result) => (
+                <Typography
+                  key={`${result.section}.${result.key}`}
+                  variant="caption"
+                  color={result.status === "rejected" ? "error" : "text.secondary"}
+                >
+                  {describeConfigUpdate(result)}
+                </Typography>
+              ))}
+            </Stack>
+          ) : null}
+
+          <Stack spacing={1}>
+            <Typography variant="subtitle2">Settings Bundle</Typography>
+            <Typography variant="body2" color="text.secondary">
+              rivet.toml, the tag schema, kanban boards and external calendars in one file, the same format as task
+              config export.
+            </Typography>
+            <Stack direction="row" spacing={1}>
+              <Button variant="outlined" size="small" onClick={props.onExportSettings}>
+                Export Settings
+              </Button>
+              <Button
+                variant="outlined"
+                size="small"
+                disabled={!props.canImportSettings}
+                onClick={() => importInputRef.current?.click()}
+              >
+                Import Settings
+              </Button>
+              <input
+                ref={importInputRef}
+                type="file"
+                accept=".json,application/json"
+                className="hidden"
+                onChange={(event: ChangeEvent<HTMLInputElement>) => {
+                  const file = event.target.files?.[0];
+                  if (file) {
+                    void importSettings(file);
+                  }
+                  event.target.value = "";
+                }}
+              />
+            </Stack>
+          </Stack>
+
+          {props.demoTaskCount > 0 ? (
+            <Stack spacing={1}>
+              <Typography variant="subtitle2">Demo Data</Typography>
+              <Typography variant="body2" color="text.secondary">
+                {props.demoTaskCount} example task(s) seeded by [app] seed_demo.
+              </Typography>
+              <Stack direction="row" spacing={1}>
+                <Button
+                  variant="outlined"
+                  size="small"
+                  color="error"
+                  disabled={!props.canClearDemoData}
+                  onClick={() => {
+                    void clearDemoData();
+                  }}
+                >
+                  Clear Demo Data
+                </Button>
+              </Stack>
+            </Stack>
+          ) : null}
         </Stack>
       </DialogContent>
       <DialogActions>
         <Button onClick={props.onClose}>Close</Button>
       </DialogActions>
+      {confirmDialog}
     </Dialog>
   );
 }
@@ -0,0 +1,68 @@
+import Button from "@mui/material/Button";
+import List from "@mui/material/List";
+import ListItemButton from "@mui/material/ListItemButton";
+import ListItemText from "@mui/material/ListItemText";
+import Paper from "@mui/material/Paper";
+import Stack from "@mui/material/Stack";
+import Typography from "@mui/material/Typography";
+
+import { parseTaskDueUtcMs } from "../lib/calendar";
+import type { TaskDto } from "../types/core";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+interface StaleTasksPanelProps {
+  tasks: TaskDto[];
+  days: number;
+  nowUtcMs: number;
+  selectedTaskId: string | null;
+  onSelect: (taskId: string) => void;
+  onRefresh: () => void;
+}
+
+function idleDays(task: TaskDto, nowUtcMs: number): number | null {
+  const modifiedUtcMs = task.modified ? parseTaskDueUtcMs(task.modified) : null;
+  if (modifiedUtcMs === null) {
+    return null;
+  }
+  return Math.floor((nowUtcMs - modifiedUtcMs) / DAY_MS);
+}
+
+export function StaleTasksPanel(props: StaleTasksPanelProps) {
+  return (
+    <Paper variant="outlined" className="p-3">
+      <Stack spacing={1}>
+        <Stack direction="row" justifyContent="space-between" alignItems="center">
+          <Typography variant="subtitle2">Stale Tasks ({props.tasks.length})</Typography>
+          <Button size="small" onClick={props.onRefresh}>
+            Refresh
+          </Button>
+        </Stack>
+        <Typography variant="caption" color="text.secondary">
+          Pending tasks untouched for {props.days}+ days with nothing due or scheduled ahead.
+        </Typography>
+        {props.tasks.length === 0 ? (
+          <Typography variant="body2">Nothing stale.</Typography>
+        ) : (
+          <List dense className="max-h-48 overflow-auto py-0">
+            {props.tasks.map((task) => {
+              const days = idleDays(task, props.nowUtcMs);
+              return (
+                <ListItemButton
+                  key={task.uuid}
+                  selected={task.uuid === props.selectedTaskId}
+                  onClick={() => props.onSelect(task.uuid)}
+                >
+                  <ListItemText
+                    primary={task.title || "Untitled Task"}
+                    secondary={`${task.project ?? "No project"} · ${days === null ? "never modified" : `idle ${days}d`}`}
+                  />
+                </ListItemButton>
+              );
+            })}
+          </List>
+        )}
+      </Stack>
+    </Paper>
+  );
+}
@@ -13,6 +13,9 @@ export function StatusChip(props: StatusChipProps) {
   if (props.status === "Deleted") {
     return <Chip size="small" color="error" label="Deleted" />;
   }
+  if (props.status === "Archived") {
+    return <Chip size="small" color="default" label="Archived" />;
+  }
   if (props.status === "Waiting") {
     return <Chip size="small" color="warning" label="Waiting" />;
   }
@@ -0,0 +1,101 @@
+import { useMemo } from "react";
+
+import Button from "@mui/material/Button";
+import Dialog from "@mui/material/Dialog";
+import DialogActions from "@mui/material/DialogActions";
+import DialogContent from "@mui/material/DialogContent";
+import DialogTitle from "@mui/material/DialogTitle";
+import Stack from "@mui/material/Stack";
+import Typography from "@mui/material/Typography";
+
+import { FieldCompareTable } from "./FieldCompareTable";
+import { countDifferences, diffFields, mergeDescriptions, type FieldSpec } from "../lib/fieldDiff";
+import { taskDisplayId, type TaskIdDisplay } from "../lib/taskId";
+import type { TaskDto } from "../types/core";
+
+interface TaskCompareDialogProps {
+  open: boolean;
+  tasks: [TaskDto, TaskDto] | null;
+  idDisplay: TaskIdDisplay;
+  canWrite: boolean;
+  canDelete: boolean;
+  formatDate: (raw: string) => string;
+  onClose: () => void;
+  onMergeDescriptions: (uuid: string, description: string) => void;
+  onDelete: (uuid: string) => void;
+}
+
+function taskCompareSpecs(formatDate: (raw: string) => string): FieldSpec<TaskDto>[] {
+  const date = (raw: string | null) => (raw ? formatDate(raw) : null);
+  return [
+    { key: "title", label: "Title", value: (task) => task.title },
+    { key: "description", label: "Description", value: (task) => task.description },
+    { key: "status", label: "Status", value: (task) => task.status },
+    { key: "project", label: "Project", value: (task) => task.project },
+    { key: "tags", label: "Tags", value: (task) => task.tags },
+    { key: "priority", label: "Priority", value: (task) => task.priority },
+    { key: "due", label: "Due", value: (task) => date(task.due) },
+    { key: "scheduled", label: "Scheduled", value: (task) => date(task.scheduled) },
+    { key: "wait", label: "Wait", value: (task) => date(task.wait) },
+    { key: "created", label: "Created", value: (task) => date(task.created) }
+  ];
+}
+
+function taskLabel(task: TaskDto, idDisplay: TaskIdDisplay): string {
+  const id = taskDisplayId(task, idDisplay);
+  const title = task.title || "Untitled Task";
+  return id ? `${id} · ${title}` : title;
+}
+
+export function TaskCompareDialog(props: TaskCompareDialogProps) {
+  const { tasks, formatDate } = props;
+  const rows = useMemo(
+    () => (tasks ? diffFields(tasks[0], tasks[1], taskCompareSpecs(formatDate)) : []),
+    [tasks, formatDate]
+  );
+  const differences = countDifferences(rows);
+
+  return (
+    <Dialog open={props.open && tasks !== null} onClose={props.onClose} maxWidth="md" fullWidth aria-labelledby="task-compare-title">
+      <DialogTitle id="task-compare-title">Compare tasks</DialogTitle>
+      <DialogContent dividers>
+        {tasks ? (
+          <Stack spacing={2}>
+            <Typography variant="caption" color="text.secondary">
+              {differences === 0 ? "These tasks have identical fields." : `${differences} field(s) differ.`}
+            </Typography>
+            <FieldCompareTable
+              rows={rows}
+              leftLabel={taskLabel(tasks[0], props.idDisplay)}
+              rightLabel={taskLabel(tasks[1], props.idDisplay)}
+            />
+            <Stack direction={{ xs: "column", sm: "row" }} spacing={2}>
+              {tasks.map((task, index) => {
+                const other = tasks[1 - index];
+                const merged = mergeDescriptions(task.description, other.description);
+                return (
+                  <Stack key={task.uuid} direction="row" spacing={1} sx={{ flex: 1 }}>
+                    <Button
+                      size="small"
+                      variant="outlined"
+                      disabled={!props.canWrite || merged === task.description.trim()}
+                      onClick={() => props.onMergeDescriptions(task.uuid, merged)}
+                    >
+                      Merge descriptions here
+                    </Button>
+                    <Button size="small" color="error" disabled={!props.canDelete} onClick={() => props.onDelete(task.uuid)}>
+                      Delete this task
+                    </Button>
+                  </Stack>
+                );
+              })}
+            </Stack>
+          </Stack>
+        ) : null}
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={props.onClose}>Close</Button>
+      </DialogActions>
+    </Dialog>
+  );
+}
@@ -1,25 +1,99 @@
+import { useEffect, useState } from "react";
+
 import Button from "@mui/material/Button";
 import Divider from "@mui/material/Divider";
+import Link from "@mui/material/Link";
+import Menu from "@mui/material/Menu";
+import MenuItem from "@mui/material/MenuItem";
 import Paper from "@mui/material/Paper";
 import Stack from "@mui/material/Stack";
+import TextField from "@mui/material/TextField";
+import Tooltip from "@mui/material/Tooltip";
 import Typography from "@mui/material/Typography";
 
 import { StatusChip } from "./StatusChip";
 import { TagChip } from "./TagChip";
-import type { TaskDto } from "../types/core";
+import { formatTaskDate, formatTaskDateWithZone, humanizeTaskDate, type DateFormat, type HumanizeStyle } from "../lib/dateFormat";
+import { logger } from "../lib/logger";
+import { SNOOZE_PRESETS, snoozedUntilUtcMs, type SnoozePreset } from "../lib/snooze";
+import { recurrenceProgress } from "../lib/tags";
+import { dueTimezoneMismatch } from "../lib/timezone";
+import { formatFocusDuration, totalFocusSeconds } from "../lib/focus";
+import { taskAddCommand } from "../lib/taskCommand";
+import { taskDisplayId, type TaskIdDisplay } from "../lib/taskId";
+import type { ContactDto, TaskDto, TaskHistoryEntry } from "../types/core";
 
 interface TaskDetailsPanelProps {
   task: TaskDto | null;
+  history: TaskHistoryEntry[];
   busy: boolean;
   onEdit: (taskId: string) => void;
   onDone: (taskId: string) => void;
   onUndone: (taskId: string) => void;
   onDelete: (taskId: string) => void;
+  onAppend: (taskId: string, text: string) => void;
+  onSnooze: (taskId: string, preset: SnoozePreset) => void;
+  onStart: (taskId: string) => void;
+  onStop: (taskId: string) => void;
+  onSaveTemplate: (taskId: string) => void;
+  idDisplay: TaskIdDisplay;
   canMarkDone: boolean;
+  canWrite: boolean;
+  canDelete: boolean;
   doneBlockedMessage: string | null;
+  dateFormat: DateFormat;
+  humanizeStyle: HumanizeStyle;
+  timezone: string;
+  backendTimezone: string | null;
+  dueDefaultTime: string;
+  nowUtcMs: number;
+  // Why the list filters hide the task; empty when it is listed.
+  visibilityReasons: string[];
+  // The task's contact: undefined while loading, null once deleted.
+  linkedContact: ContactDto | null | undefined;
+  onOpenContact: (contact: ContactDto) => void;
+  onContactAction: (contact: ContactDto, action: "mailto" | "tel") => void;
+  onClearContact: (taskId: string) => void;
 }
 
 export function TaskDetailsPanel(props: TaskDetailsPanelProps) {
+  const [copyStatus, setCopyStatus] = useState<string | null>(null);
+  const [appendInput, setAppendInput] = useState("");
+  const [snoozeAnchor, setSnoozeAnchor] = useState<HTMLElement | null>(null);
+  const taskUuid = props.task?.uuid ?? null;
+  const snoozedUntil = props.task ? snoozedUntilUtcMs(props.task, props.nowUtcMs) : null;
+  const seriesProgress = props.task ? recurrenceProgress(props.task.tags) : null;
+  const dueEnteredIn = props.task?.due ? dueTimezoneMismatch(props.task.due, props.timezone, props.backendTimezone, props.dueDefaultTime) : null;
+  const focusSeconds = props.task ? totalFocusSeconds(props.task, props.nowUtcMs) : 0;
+  const canSnooze = props.task?.status === "Pending" || props.task?.status === "Waiting";
+  const displayId = props.task ? taskDisplayId(props.task, props.idDisplay) : null;
+
+  useEffect(() => {
+    setCopyStatus(null);
+    setAppendInput("");
+    setSnoozeAnchor(null);
+  }, [taskUuid]);
+
+  const submitAppend = () => {
+    const text = appendInput.trim();
+    if (!props.task || !text) {
+      return;
+    }
+    props.onAppend(props.task.uuid, text);
+    setAppendInput("");
+  };
+
+  const copyAsCommand = async (task: TaskDto) => {
+    const command = taskAddCommand(task);
+    try {
+      await navigator.clipboard.writeText(command);
+      setCopyStatus("Copied task add command.");
+    } catch (error) {
+      logger.warn("task.copy_command", String(error));
+      setCopyStatus(command);
+    }
+  };
+
   return (
     <Paper className="min-h-[420px] p-4">
       <Typography variant="h6" gutterBottom>
@@ -28,9 +102,25 @@ export function TaskDetailsPanel(props: TaskDetailsPanelProps) {
       {props.task ? (
         <Stack spacing={2}>
           <Stack direction="row" alignItems="center" justifyContent="space-between" spacing={2}>
-            <Typography variant="subtitle1">{props.task.title || "Untitled Task"}</Typography>
+            <Typography variant="subtitle1">
+              {displayId ? (
+                <Typography component="span" variant="body2" color="text.secondary" className="mr-2 font-mono" title={props.task.uuid}>
+                  {displayId}
+                </Typography>
+              ) : null}
+              {props.task.title || "Untitled Task"}
+            </Typography>
             <StatusChip status={props.task.status} />
           </Stack>
+          <Tooltip
+            title={props.visibilityReasons.length > 0
+              ? props.visibilityReasons.map((reason) => <div key={reason}>{reason}</div>)
+              : "Matches the current filters"}
+          >
+            <Typography variant="caption" color={props.visibilityReasons.length > 0 ? "warning.main" : "text.secondary"} className="self-start">
+              {props.visibilityReasons.length > 0 ? "Hidden from the task list" : "Shown in the task list"}
+            </Typography>
+          </Tooltip>
           <Divider />
           <Stack spacing={1}>
             <Typography variant="caption" color="text.secondary">
@@ -44,12 +134,98 @@ export function TaskDetailsPanel(props: TaskDetailsPanelProps) {
             </Typography>
             <Typography variant="body2">{props.task.project || "None"}</Typography>
           </Stack>
+          {props.task.contact ? (
+            <Stack spacing={1}>
+              <Typography variant="caption" color="text.secondary">
+                Contact
+              </Typography>
+              {props.linkedContact ? (
+                <Stack direction="row" alignItems="center" spacing={1}>
+                  <Link component="button" variant="body2" onClick={() => props.onOpenContact(props.linkedContact!)}>
+                    {props.linkedContact.display_name || "Unnamed contact"}
+                  </Link>
+                  <Button
+                    size="small"
+                    disabled={props.linkedContact.emails.length === 0}
+                    onClick={() => props.onContactAction(props.linkedContact!, "mailto")}
+                  >
+                    Email
+                  </Button>
+                  <Button
+                    size="small"
+                    disabled={props.linkedContact.phones.length === 0}
+                    onClick={() => props.onContactAction(props.linkedContact!, "tel")}
+                  >
+                    Call
+                  </Button>
+                </Stack>
+              ) : props.linkedContact === null ? (
+                <Stack direction="row" alignItems="center" spacing={1}>
+                  <Typography variant="body2" color="warning.main">
+                    Linked contact was deleted
+                  </Typography>
+                  {props.canWrite ? (
+                    <Button size="small" disabled={props.busy} onClick={() => props.onClearContact(props.task!.uuid)}>
+                      Clear link
+                    </Button>
+                  ) : null}
+                </Stack>
+              ) : (
+                <Typography variant="body2" color="text.secondary">
+                  Loading contact...
+                </Typography>
+              )}
+            </Stack>
+          ) : null}
           <Stack spacing={1}>
             <Typography variant="caption" color="text.secondary">
               Due
             </Typography>
-            <Typography variant="body2">{props.task.due || "No due date"}</Typography>
+            <Typography variant="body2">{props.task.due
+                ? formatTaskDateWithZone(props.task.due, props.dateFormat, props.timezone, props.nowUtcMs)
+                : "No due date"}</Typography>
+            {props.task.due && props.dateFormat.kind === "codes" ? (
+              <Typography variant="caption" color="text.secondary">
+                {humanizeTaskDate(props.task.due, props.nowUtcMs, props.humanizeStyle)}
+              </Typography>
+            ) : null}
+            {dueEnteredIn ? (
+              <Typography variant="caption" color="warning.main">
+                Looks like a date-only due entered in {dueEnteredIn}; it may show a day off here.
+              </Typography>
+            ) : null}
           </Stack>
+          {snoozedUntil !== null ? (
+            <Stack spacing={1}>
+              <Typography variant="caption" color="text.secondary">
+                Snoozed until
+              </Typography>
+              <Typography variant="body2">
+                {formatTaskDate(new Date(snoozedUntil).toISOString(), props.dateFormat, props.timezone, props.nowUtcMs)}
+              </Typography>
+            </Stack>
+          ) : null}
+          {props.task.started || focusSeconds > 0 ? (
+            <Stack spacing={1}>
+              <Typography variant="caption" color="text.secondary">
+                Focus time
+              </Typography>
+              <Typography variant="body2">
+                {formatFocusDuration(focusSeconds)}
+                {props.task.started ? " (running)" : ""}
+              </Typography>
+            </Stack>
+          ) : null}
+          {seriesProgress ? (
+            <Stack spacing={1}>
+              <Typography variant="caption" color="text.secondary">
+                Recurrence
+              </Typography>
+              <Typography variant="body2">
+                Occurrence {seriesProgress.occurrence} of {seriesProgress.total}
+              </Typography>
+            </Stack>
+          ) : null}
           <Stack spacing={1}>
             <Typography variant="caption" color="text.secondary">
               Tags
@@ -60,45 +236,154 @@ export function TaskDetailsPanel(props: TaskDetailsPanelProps) {
                 : <Typography variant="body2">No tags</Typography>}
             </Stack>
           </Stack>
+          {props.canWrite ? (
+            <Stack direction="row" spacing={1}>
+              <TextField
+                size="small"
+                fullWidth
+                label="Append to title"
+                value={appendInput}
+                disabled={props.busy}
+                onChange={(event) => setAppendInput(event.target.value)}
+                onKeyDown={(event) => {
+                  if (event.key === "Enter") {
+                    submitAppend();
+                  }
+                }}
+              />
+              <Button variant="outlined" disabled={props.busy || !appendInput.trim()} onClick={submitAppend}>
+                Append
+              </Button>
+            </Stack>
+          ) : null}
           <Divider />
           <Stack direction="row" spacing={1}>
+            {props.canWrite ? (
+              <>
+                <Button
+                  variant="outlined"
+                  disabled={props.busy}
+                  onClick={() => props.onEdit(props.task!.uuid)}
+                >
+                  Edit
+                </Button>
+                <Button
+                  variant="contained"
+                  color="success"
+                  disabled={props.busy || props.task.status === "Completed" || !props.canMarkDone}
+                  onClick={() => props.onDone(props.task!.uuid)}
+                >
+                  Done
+                </Button>
+                <Button
+                  variant="outlined"
+                  color="warning"
+                  disabled={props.busy || props.task.status !== "Completed"}
+                  onClick={() => props.onUndone(props.task!.uuid)}
+                >
+                  Reopen
+                </Button>
+                <Button
+                  variant="outlined"
+                  disabled={props.busy || !canSnooze}
+                  onClick={(event) => setSnoozeAnchor(event.currentTarget)}
+                >
+                  Snooze
+                </Button>
+                {props.task.started ? (
+                  <Button variant="outlined" disabled={props.busy} onClick={() => props.onStop(props.task!.uuid)}>
+                    Stop
+                  </Button>
+                ) : (
+                  <Button
+                    variant="outlined"
+                    disabled={props.busy || props.task.status !== "Pending"}
+                    onClick={() => props.onStart(props.task!.uuid)}
+                  >
+                    Start
+                  </Button>
+                )}
+                <Menu anchorEl={snoozeAnchor} open={snoozeAnchor !== null} onClose={() => setSnoozeAnchor(null)}>
+                  {SNOOZE_PRESETS.map((preset) => (
+                    <MenuItem
+                      key={preset.id}
+                      onClick={() => {
+                        setSnoozeAnchor(null);
+                        props.onSnooze(props.task!.uuid, preset.id);
+                      }}
+                    >
+                      {preset.label}
+                    </MenuItem>
+                  ))}
+                </Menu>
+              </>
+            ) : null}
+            {props.canDelete ? (
+              <Button
+                variant="outlined"
+                color="error"
+                disabled={props.busy}
+                onClick={() => props.onDelete(props.task!.uuid)}
+              >
+                Delete
+              </Button>
+            ) : null}
             <Button
-              variant="outlined"
-              disabled={props.busy}
-              onClick={() => props.onEdit(props.task!.uuid)}
-            >
-              Edit
-            </Button>
-            <Button
-              variant="contained"
-              color="success"
-              disabled={props.busy || props.task.status === "Completed" || !props.canMarkDone}
-              onClick={() => props.onDone(props.task!.uuid)}
-            >
-              Done
-            </Button>
-            <Button
-              variant="outlined"
-              color="warning"
-              disabled={props.busy || props.task.status !== "Completed"}
-              onClick={() => props.onUndone(props.task!.uuid)}
+              variant="text"
+              onClick={() => void copyAsCommand(props.task!)}
             >
-              Uncomplete
+              Copy as command
             </Button>
             <Button
-              variant="outlined"
-              color="error"
-              disabled={props.busy}
-              onClick={() => props.onDelete(props.task!.uuid)}
+              variant="text"
+              onClick={() => props.onSaveTemplate(props.task!.uuid)}
             >
-              Delete
+              Save as template
             </Button>
           </Stack>
+          {copyStatus ? (
+            <Typography variant="caption" color="text.secondary" className="break-all font-mono">
+              {copyStatus}
+            </Typography>
+          ) : null}
           {props.doneBlockedMessage ? (
             <Typography variant="caption" color="warning.main">
               {props.doneBlockedMessage}
             </Typography>
           ) : null}
+          <Divider />
+          <Stack spacing={1}>
+            <Typography variant="caption" color="text.secondary">
+              History
+            </Typography>
+            {props.history.length > 0 ? (
+              [...props.history].reverse().map((entry, index) => (
+                <Stack key={`${entry.at}-${entry.command}-${index}`} spacing={0.25}>
+                  <Typography variant="body2">
+                    {formatTaskDate(entry.at, props.dateFormat, props.timezone, props.nowUtcMs)}
+                    {" · "}
+                    {entry.action}
+                    {" · "}
+                    <span className="font-mono">{entry.command}</span>
+                  </Typography>
+                  {entry.changes.map((change) => (
+                    <Typography
+                      key={change.field}
+                      variant="caption"
+                      color="text.secondary"
+                      className="break-all pl-3 font-mono"
+                    >
+                      {entry.action === "created"
+                        ? `${change.field}: ${change.after ?? "(none)"}`
+                        : `${change.field}: ${change.before ?? "(none)"} -> ${change.after ?? "(none)"}`}
+                    </Typography>
+                  ))}
+                </Stack>
+              ))
+            ) : (
+              <Typography variant="body2">No recorded history</Typography>
+            )}
+          </Stack>
         </Stack>
       ) : (
         <Typography variant="body2" color="text.secondary">
@@ -0,0 +1,22 @@
+import Alert from "@mui/material/Alert";
+import Button from "@mui/material/Button";
+
+interface TaskDraftNoticeProps {
+  savedAt: number;
+  onDiscard: () => void;
+}
+
+export function TaskDraftNotice(props: TaskDraftNoticeProps) {
+  return (
+    <Alert
+      severity="info"
+      action={(
+        <Button color="inherit" size="small" onClick={props.onDiscard}>
+          Discard draft
+        </Button>
+      )}
+    >
+      Restored an unsaved draft from {new Date(props.savedAt).toLocaleString()}.
+    </Alert>
+  );
+}
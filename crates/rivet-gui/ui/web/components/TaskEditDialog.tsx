@@ -13,19 +13,23 @@ import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
 import {
+  addTagWithSelection,
   BOARD_TAG_KEY,
   boardIdFromTaskTags,
   collectTagsForSubmit,
   defaultKanbanLane,
-  isSingleSelectKey,
   recurrenceFromTags,
-  removeTagsForKey,
   splitTags,
   tagColorStyle
 } from "../lib/tags";
+import { loadTaskDraft } from "../lib/storage";
+import { taskDraftKey } from "../lib/taskDraft";
+import { titleLengthHint, titleRejected, type TitleLimit } from "../lib/titleLimit";
 import type { TagSchema } from "../types/config";
 import type { TaskDto, TaskPatch } from "../types/core";
-import type { KanbanBoardDef, RecurrenceDraft } from "../types/ui";
+import type { KanbanBoardDef, RecurrenceDraft, TaskDraftFields } from "../types/ui";
+import { TaskDraftNotice } from "./TaskDraftNotice";
+import { useTaskDraftAutosave } from "./useTaskDraftAutosave";
 
 interface TaskEditDialogProps {
   open: boolean;
@@ -34,6 +38,8 @@ interface TaskEditDialogProps {
   tagSchema: TagSchema | null;
   tagColorMap: Record<string, string>;
   kanbanBoards: KanbanBoardDef[];
+  draftAutosaveMs: number | null;
+  titleLimit: TitleLimit | null;
   onClose: () => void;
   onSubmit: (uuid: string, patch: TaskPatch) => Promise<boolean>;
 }
@@ -43,7 +49,11 @@ const EMPTY_RECURRENCE: RecurrenceDraft = {
   time: "",
   days: [],
   months: [],
-  monthDay: ""
+  monthDay: "",
+  ends: "never",
+  until: "",
+  count: "",
+  remaining: null
 };
 
 export function TaskEditDialog(props: TaskEditDialogProps) {
@@ -58,6 +68,39 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
   const [boardId, setBoardId] = useState<string>("");
   const [recurrence, setRecurrence] = useState<RecurrenceDraft>(EMPTY_RECURRENCE);
   const [error, setError] = useState<string | null>(null);
+  const [draftBaseline, setDraftBaseline] = useState<TaskDraftFields | null>(null);
+  const [draftSavedAt, setDraftSavedAt] = useState<number | null>(null);
+
+  // Priority is not edited here; it rides along so add and edit drafts share a shape.
+  const priority = props.task?.priority ?? null;
+  const draftFields = useMemo((): TaskDraftFields => ({
+    title,
+    description,
+    project,
+    due,
+    customTagInput,
+    selectedTags,
+    boardId,
+    priority,
+    recurrence
+  }), [title, description, project, due, customTagInput, selectedTags, boardId, priority, recurrence]);
+  const draft = useTaskDraftAutosave({
+    key: props.open && props.task ? taskDraftKey(props.task.uuid) : null,
+    fields: draftFields,
+    baseline: draftBaseline,
+    intervalMs: props.draftAutosaveMs
+  });
+
+  const applyDraftFields = (fields: TaskDraftFields) => {
+    setTitle(fields.title);
+    setDescription(fields.description);
+    setProject(fields.project);
+    setDue(fields.due);
+    setCustomTagInput(fields.customTagInput);
+    setSelectedTags([...fields.selectedTags]);
+    setBoardId(fields.boardId);
+    setRecurrence(fields.recurrence);
+  };
 
   const keyOptions = useMemo(() => {
     return (props.tagSchema?.keys ?? []).filter((entry) => entry.id !== BOARD_TAG_KEY);
@@ -74,18 +117,38 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
     }
     const firstKey = keyOptions[0];
     const firstValue = firstKey?.values?.[0] ?? "";
-    setTitle(props.task.title);
-    setDescription(props.task.description);
-    setProject(props.task.project ?? "");
-    setDue(props.task.due ?? "");
-    setCustomTagInput("");
-    setSelectedTags([...props.task.tags]);
+    const baseline: TaskDraftFields = {
+      title: props.task.title,
+      description: props.task.description,
+      project: props.task.project ?? "",
+      due: props.task.due ?? "",
+      customTagInput: "",
+      selectedTags: [...props.task.tags],
+      boardId: boardIdFromTaskTags(props.task.tags) ?? "",
+      priority: props.task.priority,
+      recurrence: recurrenceFromTags(props.task.tags)
+    };
+    const stored = props.draftAutosaveMs === null ? null : loadTaskDraft(taskDraftKey(props.task.uuid));
+    applyDraftFields(stored ?? baseline);
+    setDraftBaseline(baseline);
+    setDraftSavedAt(stored?.saved_at ?? null);
     setPickerKey(firstKey?.id ?? "");
     setPickerValue(firstValue);
-    setBoardId(boardIdFromTaskTags(props.task.tags) ?? "");
-    setRecurrence(recurrenceFromTags(props.task.tags));
     setError(null);
-  }, [props.open, props.task, keyOptions]);
+  }, [props.open, props.task, props.draftAutosaveMs, keyOptions]);
+
+  const discardDraft = () => {
+    draft.discard();
+    if (draftBaseline) {
+      applyDraftFields(draftBaseline);
+    }
+    setDraftSavedAt(null);
+  };
+
+  const handleCancel = () => {
+    draft.clear();
+    props.onClose();
+  };
 
   const canSave = useMemo(() => props.task !== null && title.trim().length > 0 && !props.busy, [props.task, title, props.busy]);
 
@@ -111,18 +174,13 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
       return;
     }
     const tag = `${pickerKey}:${pickerValue}`;
-    setSelectedTags((prev) => {
-      const next = [...prev];
-      if (isSingleSelectKey(props.tagSchema, pickerKey)) {
-        removeTagsForKey(next, pickerKey);
-      }
-      if (!next.includes(tag)) {
-        next.push(tag);
-      }
-      return next;
-    });
+    setSelectedTags((prev) => addTagWithSelection(prev, tag, props.tagSchema));
   };
 
+  const titleHint = titleLengthHint(title, props.titleLimit);
+  // The backend leaves an unchanged over-limit title alone.
+  const titleChanged = title.trim() !== (props.task?.title ?? "");
+
   const handleSave = async () => {
     if (!props.task) {
       return;
@@ -131,6 +189,10 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
       setError("Title is required.");
       return;
     }
+    if (titleChanged && titleRejected(title, props.titleLimit)) {
+      setError(`Title is longer than ${props.titleLimit?.maxLength} characters.`);
+      return;
+    }
 
     const tags = collectTagsForSubmit({
       selectedTags,
@@ -152,6 +214,7 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
 
     const ok = await props.onSubmit(props.task.uuid, patch);
     if (ok) {
+      draft.clear();
       props.onClose();
     }
   };
@@ -162,6 +225,7 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
       <DialogContent dividers className="max-h-[calc(100vh-160px)]">
         <Stack spacing={2.25}>
           {error ? <Typography color="error">{error}</Typography> : null}
+          {draftSavedAt !== null ? <TaskDraftNotice savedAt={draftSavedAt} onDiscard={discardDraft} /> : null}
 
           <TextField
             label="Title"
@@ -173,6 +237,8 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
                 setError(null);
               }
             }}
+            error={titleChanged && titleRejected(title, props.titleLimit)}
+            helperText={titleHint?.message}
           />
 
           <TextField
@@ -375,10 +441,44 @@ export function TaskEditDialog(props: TaskEditDialogProps) {
               />
             </>
           ) : null}
+
+          {recurrence.pattern !== "none" ? (
+            <Stack direction="row" spacing={1}>
+              <TextField
+                select
+                label="Ends"
+                value={recurrence.ends}
+                onChange={(event) => setRecurrence((prev) => ({ ...prev, ends: event.target.value as RecurrenceDraft["ends"] }))}
+                sx={{ minWidth: 180 }}
+              >
+                <MenuItem value="never">Never</MenuItem>
+                <MenuItem value="until">On date</MenuItem>
+                <MenuItem value="count">After N occurrences</MenuItem>
+              </TextField>
+              {recurrence.ends === "until" ? (
+                <TextField
+                  type="date"
+                  label="Until"
+                  value={recurrence.until}
+                  InputLabelProps={{ shrink: true }}
+                  onChange={(event) => setRecurrence((prev) => ({ ...prev, until: event.target.value }))}
+                />
+              ) : null}
+              {recurrence.ends === "count" ? (
+                <TextField
+                  type="number"
+                  label="Occurrences"
+                  value={recurrence.count}
+                  inputProps={{ min: 1 }}
+                  onChange={(event) => setRecurrence((prev) => ({ ...prev, count: event.target.value, remaining: null }))}
+                />
+              ) : null}
+            </Stack>
+          ) : null}
         </Stack>
       </DialogContent>
       <DialogActions>
-        <Button onClick={props.onClose} disabled={props.busy}>
+        <Button onClick={handleCancel} disabled={props.busy}>
           Cancel
         </Button>
         <Button onClick={handleSave} disabled={!canSave} variant="contained">
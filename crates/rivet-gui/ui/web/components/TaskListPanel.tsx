@@ -1,40 +1,144 @@
-import { useMemo, useRef } from "react";
+import { useMemo, useRef, useState } from "react";
+import type { ReactNode } from "react";
 
 import { useVirtualizer } from "@tanstack/react-virtual";
+import CheckIcon from "@mui/icons-material/Check";
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import EditOutlinedIcon from "@mui/icons-material/EditOutlined";
+import MoreVertIcon from "@mui/icons-material/MoreVert";
+import SnoozeIcon from "@mui/icons-material/Snooze";
+import Button from "@mui/material/Button";
 import Checkbox from "@mui/material/Checkbox";
+import Chip from "@mui/material/Chip";
+import CircularProgress from "@mui/material/CircularProgress";
+import ExpandLessIcon from "@mui/icons-material/ExpandLess";
+import ExpandMoreIcon from "@mui/icons-material/ExpandMore";
+import IconButton from "@mui/material/IconButton";
 import List from "@mui/material/List";
 import ListItemButton from "@mui/material/ListItemButton";
 import ListItemText from "@mui/material/ListItemText";
+import Menu from "@mui/material/Menu";
+import MenuItem from "@mui/material/MenuItem";
 import Paper from "@mui/material/Paper";
 import Stack from "@mui/material/Stack";
+import Tooltip from "@mui/material/Tooltip";
 import Typography from "@mui/material/Typography";
+import { useTheme } from "@mui/material/styles";
+import useMediaQuery from "@mui/material/useMediaQuery";
 
 import { StatusChip } from "./StatusChip";
+import { externalCalendarColorForTask } from "../lib/calendar";
+import type { DueBucket, DueBucketGroup } from "../lib/dueBuckets";
+import { TASK_ROW_ACTION_LABELS, type TaskRowAction } from "../lib/rowActions";
+import { SNOOZE_PRESETS, type SnoozePreset } from "../lib/snooze";
+import { subtaskProgress } from "../lib/subtasks";
+import { taskDisplayId, type TaskIdDisplay } from "../lib/taskId";
 import type { TaskDto } from "../types/core";
+import type { PendingTaskOp } from "../types/ui";
 
 interface TaskListPanelProps {
   tasks: TaskDto[];
+  groups?: DueBucketGroup[] | null;
+  calendarColors?: Record<string, string>;
+  tasksById?: ReadonlyMap<string, TaskDto>;
+  pendingTaskOps?: Readonly<Record<string, PendingTaskOp>>;
   selectedTaskId: string | null;
   selectMode: boolean;
   selectedTaskIds: string[];
   onTaskClick: (taskId: string, index: number, modifiers: { ctrlOrMeta: boolean; shift: boolean }) => void;
+  onReopen?: (taskId: string) => void;
+  rowActions?: TaskRowActions;
+  // Leading identifier per row; rows stay unnumbered without it.
+  idDisplay?: TaskIdDisplay;
+  // Shown under the title, above the rows.
+  toolbar?: ReactNode;
 }
 
+// One-click actions on each row; they reuse the details panel callbacks.
+export interface TaskRowActions {
+  actionsFor: (task: TaskDto) => TaskRowAction[];
+  onAction: (taskId: string, action: Exclude<TaskRowAction, "snooze">) => void;
+  onSnooze: (taskId: string, preset: SnoozePreset) => void;
+}
+
+type RowMenu = { taskId: string; anchor: HTMLElement; actions: TaskRowAction[]; snoozeOnly: boolean };
+
+const ROW_ACTION_ICONS: Record<TaskRowAction, ReactNode> = {
+  done: <CheckIcon fontSize="small" />,
+  edit: <EditOutlinedIcon fontSize="small" />,
+  snooze: <SnoozeIcon fontSize="small" />,
+  delete: <DeleteOutlineIcon fontSize="small" />
+};
+
+type TaskListRow =
+  | { kind: "header"; bucket: DueBucket; label: string; count: number; collapsed: boolean }
+  | { kind: "task"; task: TaskDto; taskIndex: number };
+
 export function TaskListPanel(props: TaskListPanelProps) {
   const parentRef = useRef<HTMLDivElement | null>(null);
+  const [collapsedBuckets, setCollapsedBuckets] = useState<Set<DueBucket>>(() => new Set());
+  const [rowMenu, setRowMenu] = useState<RowMenu | null>(null);
+  const theme = useTheme();
+  const compactActions = useMediaQuery(theme.breakpoints.down("lg"));
   const selectedTaskSet = useMemo(() => new Set(props.selectedTaskIds), [props.selectedTaskIds]);
+  const rows = useMemo<TaskListRow[]>(() => {
+    if (!props.groups) {
+      return props.tasks.map((task, taskIndex) => ({ kind: "task", task, taskIndex }));
+    }
+    const next: TaskListRow[] = [];
+    let taskIndex = 0;
+    for (const group of props.groups) {
+      const collapsed = collapsedBuckets.has(group.bucket);
+      next.push({ kind: "header", bucket: group.bucket, label: group.label, count: group.tasks.length, collapsed });
+      for (const task of group.tasks) {
+        if (!collapsed) {
+          next.push({ kind: "task", task, taskIndex });
+        }
+        taskIndex += 1;
+      }
+    }
+    return next;
+  }, [collapsedBuckets, props.groups, props.tasks]);
   const virtualizer = useVirtualizer({
-    count: props.tasks.length,
+    count: rows.length,
     getScrollElement: () => parentRef.current,
-    getItemKey: (index) => props.tasks[index]?.uuid ?? index,
-    estimateSize: () => 128,
+    getItemKey: (index) => {
+      const row = rows[index];
+      if (!row) {
+        return index;
+      }
+      return row.kind === "header" ? `bucket:${row.bucket}` : row.task.uuid;
+    },
+    estimateSize: (index) => (rows[index]?.kind === "header" ? 40 : 128),
     overscan: 10
   });
 
+  const toggleBucket = (bucket: DueBucket) => {
+    setCollapsedBuckets((previous) => {
+      const next = new Set(previous);
+      if (next.has(bucket)) {
+        next.delete(bucket);
+      } else {
+        next.add(bucket);
+      }
+      return next;
+    });
+  };
+
+  const runRowAction = (taskId: string, action: TaskRowAction, anchor: HTMLElement, actions: TaskRowAction[]) => {
+    if (action === "snooze") {
+      setRowMenu({ taskId, anchor, actions, snoozeOnly: true });
+      return;
+    }
+    setRowMenu(null);
+    props.rowActions?.onAction(taskId, action);
+  };
+
   return (
     <Paper className="min-h-[420px] overflow-hidden">
       <div className="border-b border-current/10 px-4 py-3">
         <Typography variant="h6">Tasks</Typography>
+        {props.toolbar ? <div className="mt-2">{props.toolbar}</div> : null}
       </div>
       <div ref={parentRef} className="max-h-[calc(100vh-220px)] overflow-auto">
         {props.tasks.length === 0 ? (
@@ -47,29 +151,66 @@ export function TaskListPanel(props: TaskListPanelProps) {
             }}
           >
             {virtualizer.getVirtualItems().map((item) => {
-              const task = props.tasks[item.index];
-              if (!task) {
+              const row = rows[item.index];
+              if (!row) {
                 return null;
               }
+              if (row.kind === "header") {
+                return (
+                  <ListItemButton
+                    key={`bucket:${row.bucket}`}
+                    onClick={() => toggleBucket(row.bucket)}
+                    className="!absolute !left-0 !right-0 !px-4 !py-2"
+                    data-index={item.index}
+                    ref={virtualizer.measureElement}
+                    aria-expanded={!row.collapsed}
+                    sx={{
+                      transform: `translateY(${item.start}px)`,
+                      bgcolor: "action.hover"
+                    }}
+                  >
+                    <Stack direction="row" spacing={1} alignItems="center" className="w-full">
+                      {row.collapsed ? <ExpandMoreIcon fontSize="small" /> : <ExpandLessIcon fontSize="small" />}
+                      <Typography variant="subtitle2" className="flex-1">
+                        {row.label}
+                      </Typography>
+                      <Typography variant="caption" color="text.secondary">
+                        {row.count}
+                      </Typography>
+                    </Stack>
+                  </ListItemButton>
+                );
+              }
+              const { task, taskIndex } = row;
               const isSelected = props.selectMode
                 ? selectedTaskSet.has(task.uuid)
                 : task.uuid === props.selectedTaskId;
+              const calendarColor = externalCalendarColorForTask(task, props.calendarColors ?? {});
+              const progress = props.tasksById ? subtaskProgress(task, props.tasksById) : null;
+              const pendingOp = props.pendingTaskOps?.[task.uuid];
+              const displayId = props.idDisplay ? taskDisplayId(task, props.idDisplay) : null;
+              // Bulk selection owns clicks on the row, so the per-row actions step aside.
+              const actions = props.rowActions && !props.selectMode && !pendingOp
+                ? props.rowActions.actionsFor(task)
+                : [];
               return (
                 <ListItemButton
                   key={task.uuid}
                   selected={isSelected}
                   onClick={(event) => {
-                    props.onTaskClick(task.uuid, item.index, {
+                    props.onTaskClick(task.uuid, taskIndex, {
                       ctrlOrMeta: event.ctrlKey || event.metaKey,
                       shift: event.shiftKey
                     });
                   }}
-                  className="!absolute !left-0 !right-0 !items-start !px-4 !py-3"
+                  className="group !absolute !left-0 !right-0 !items-start !px-4 !py-3"
                   data-index={item.index}
                   ref={virtualizer.measureElement}
                   sx={{
-                    transform: `translateY(${item.start}px)`
+                    transform: `translateY(${item.start}px)`,
+                    opacity: pendingOp ? 0.6 : 1
                   }}
+                  aria-busy={pendingOp ? true : undefined}
                 >
                   <Stack spacing={1} className="w-full">
                     <Stack direction="row" justifyContent="space-between" alignItems="center" spacing={2}>
@@ -83,6 +224,18 @@ export function TaskListPanel(props: TaskListPanelProps) {
                             sx={{ p: 0.25 }}
                           />
                         ) : null}
+                        {calendarColor ? (
+                          <span
+                            aria-hidden
+                            className="inline-block h-2.5 w-2.5 shrink-0 rounded-full"
+                            style={{ backgroundColor: calendarColor }}
+                          />
+                        ) : null}
+                        {displayId ? (
+                          <Typography variant="caption" color="text.secondary" className="shrink-0 font-mono">
+                            {displayId}
+                          </Typography>
+                        ) : null}
                         <Typography
                           variant="subtitle2"
                           sx={{
@@ -94,7 +247,71 @@ export function TaskListPanel(props: TaskListPanelProps) {
                           {task.title || "Untitled Task"}
                         </Typography>
                       </Stack>
-                      <StatusChip status={task.status} />
+                      <Stack direction="row" spacing={1} alignItems="center" className="shrink-0">
+                        {pendingOp ? <CircularProgress size={14} aria-label={`Saving ${pendingOp}`} /> : null}
+                        {progress ? (
+                          <Chip
+                            size="small"
+                            variant="outlined"
+                            label={`${progress.done}/${progress.total}`}
+                            title={`${progress.done} of ${progress.total} subtasks done`}
+                            color={progress.done === progress.total ? "success" : "default"}
+                          />
+                        ) : null}
+                        {actions.length > 0 && compactActions ? (
+                          <IconButton
+                            size="small"
+                            aria-label="Task actions"
+                            onMouseDown={(event) => event.stopPropagation()}
+                            onClick={(event) => {
+                              event.stopPropagation();
+                              setRowMenu({ taskId: task.uuid, anchor: event.currentTarget, actions, snoozeOnly: false });
+                            }}
+                          >
+                            <MoreVertIcon fontSize="small" />
+                          </IconButton>
+                        ) : null}
+                        {actions.length > 0 && !compactActions ? (
+                          <Stack
+                            direction="row"
+                            className={isSelected
+                              ? "opacity-100"
+                              : "opacity-0 transition-opacity group-hover:opacity-100 group-focus-within:opacity-100"}
+                          >
+                            {actions.map((action) => (
+                              <Tooltip key={action} title={TASK_ROW_ACTION_LABELS[action]}>
+                                <IconButton
+                                  size="small"
+                                  aria-label={TASK_ROW_ACTION_LABELS[action]}
+                                  color={action === "delete" ? "error" : action === "done" ? "success" : "default"}
+                                  onMouseDown={(event) => event.stopPropagation()}
+                                  onClick={(event) => {
+                                    event.stopPropagation();
+                                    runRowAction(task.uuid, action, event.currentTarget, actions);
+                                  }}
+                                >
+                                  {ROW_ACTION_ICONS[action]}
+                                </IconButton>
+                              </Tooltip>
+                            ))}
+                          </Stack>
+                        ) : null}
+                        <StatusChip status={task.status} />
+                        {props.onReopen && task.status === "Completed" && !props.selectMode ? (
+                          <Button
+                            size="small"
+                            color="warning"
+                            disabled={Boolean(pendingOp)}
+                            onMouseDown={(event) => event.stopPropagation()}
+                            onClick={(event) => {
+                              event.stopPropagation();
+                              props.onReopen?.(task.uuid);
+                            }}
+                          >
+                            Reopen
+                          </Button>
+                        ) : null}
+                      </Stack>
                     </Stack>
                     <ListItemText
                       primary={task.project ?? "No project"}
@@ -115,6 +332,28 @@ export function TaskListPanel(props: TaskListPanelProps) {
           </List>
         )}
       </div>
+      <Menu anchorEl={rowMenu?.anchor} open={rowMenu !== null} onClose={() => setRowMenu(null)}>
+        {rowMenu?.snoozeOnly
+          ? SNOOZE_PRESETS.map((preset) => (
+            <MenuItem
+              key={preset.id}
+              onClick={() => {
+                setRowMenu(null);
+                props.rowActions?.onSnooze(rowMenu.taskId, preset.id);
+              }}
+            >
+              {preset.label}
+            </MenuItem>
+          ))
+          : rowMenu?.actions.map((action) => (
+            <MenuItem
+              key={action}
+              onClick={() => runRowAction(rowMenu.taskId, action, rowMenu.anchor, rowMenu.actions)}
+            >
+              {TASK_ROW_ACTION_LABELS[action]}
+            </MenuItem>
+          ))}
+      </Menu>
     </Paper>
   );
 }
@@ -0,0 +1,31 @@
+import { useCallback, useRef, useState } from "react";
+import type { ReactNode } from "react";
+
+import { ConfirmDialog, type ConfirmOptions } from "./ConfirmDialog";
+
+// Promise-based wrapper: render `confirmDialog` once and await
+// `requestConfirm(...)` wherever a yes/no answer is needed.
+export function useConfirm(): {
+  requestConfirm: (options: ConfirmOptions) => Promise<boolean>;
+  confirmDialog: ReactNode;
+} {
+  const [options, setOptions] = useState<ConfirmOptions | null>(null);
+  const resolverRef = useRef<((confirmed: boolean) => void) | null>(null);
+
+  const requestConfirm = useCallback((next: ConfirmOptions) => {
+    resolverRef.current?.(false);
+    setOptions(next);
+    return new Promise<boolean>((resolve) => {
+      resolverRef.current = resolve;
+    });
+  }, []);
+
+  const settle = (confirmed: boolean) => {
+    resolverRef.current?.(confirmed);
+    resolverRef.current = null;
+    setOptions(null);
+  };
+
+  const confirmDialog = options ? <ConfirmDialog open {...options} onResult={settle} /> : null;
+  return { requestConfirm, confirmDialog };
+}
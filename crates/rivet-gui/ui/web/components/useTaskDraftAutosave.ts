@@ -0,0 +1,68 @@
+import { useCallback, useEffect, useRef } from "react";
+
+import { clearTaskDraft, saveTaskDraft } from "../lib/storage";
+import { draftFields, sameDraftFields } from "../lib/taskDraft";
+import type { TaskDraftFields } from "../types/ui";
+
+interface TaskDraftAutosaveOptions {
+  // Null while the dialog is closed.
+  key: string | null;
+  fields: TaskDraftFields;
+  // What the form holds when nothing was typed; matching it removes the draft.
+  baseline: TaskDraftFields | null;
+  intervalMs: number | null;
+}
+
+// Writes the dialog's fields to local storage every `intervalMs`, when the
+// page unloads and when the dialog closes. `clear` drops the draft and stops
+// writing until the dialog opens again; call it after a save or on Cancel.
+// `discard` only drops what is stored, for a form that keeps being edited.
+export function useTaskDraftAutosave(options: TaskDraftAutosaveOptions): { clear: () => void; discard: () => void } {
+  const fieldsRef = useRef(options.fields);
+  fieldsRef.current = options.fields;
+  const keyRef = useRef<string | null>(null);
+  const clearedRef = useRef(false);
+  const { key, baseline, intervalMs } = options;
+
+  useEffect(() => {
+    if (!key || !baseline || intervalMs === null) {
+      return;
+    }
+    keyRef.current = key;
+    clearedRef.current = false;
+    let lastWritten = JSON.stringify(draftFields(baseline));
+    const flush = () => {
+      const fields = draftFields(fieldsRef.current);
+      const serialized = JSON.stringify(fields);
+      if (clearedRef.current || serialized === lastWritten) {
+        return;
+      }
+      lastWritten = serialized;
+      if (sameDraftFields(fields, baseline)) {
+        clearTaskDraft(key);
+      } else {
+        saveTaskDraft(key, { ...fields, saved_at: Date.now() });
+      }
+    };
+    const timer = window.setInterval(flush, intervalMs);
+    window.addEventListener("beforeunload", flush);
+    return () => {
+      window.clearInterval(timer);
+      window.removeEventListener("beforeunload", flush);
+      flush();
+    };
+  }, [key, baseline, intervalMs]);
+
+  const discard = useCallback(() => {
+    if (keyRef.current) {
+      clearTaskDraft(keyRef.current);
+    }
+  }, []);
+
+  const clear = useCallback(() => {
+    clearedRef.current = true;
+    discard();
+  }, [discard]);
+
+  return { clear, discard };
+}
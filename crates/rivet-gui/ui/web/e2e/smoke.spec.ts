@@ -61,6 +61,7 @@ test("tasks smoke: add, complete, delete", async ({ page }) => {
   await expect(page.getByText("Completed").first()).toBeVisible();
 
   await page.getByRole("button", { name: /^Delete$/ }).click();
+  await page.getByRole("dialog").getByRole("button", { name: "Delete" }).click();
   await expect(page.getByText("Smoke Task")).toHaveCount(0);
 });
 
@@ -153,8 +154,8 @@ test("tasks parity: edit flow and bulk filtered actions", async ({ page }) => {
   await expect(page.getByText("Completed").first()).toBeVisible();
 
   await page.getByLabel("Search").fill("Bulk");
-  page.once("dialog", (dialog) => dialog.accept());
   await page.getByRole("button", { name: /Delete Filtered/i }).click();
+  await page.getByRole("dialog").getByRole("button", { name: "Delete" }).click();
   await expect(page.getByText("Bulk A Edited")).toHaveCount(0);
   await expect(page.getByText("Bulk B")).toHaveCount(0);
 });
@@ -169,6 +170,13 @@ test("keyboard shortcuts: add task and settings", async ({ page }) => {
   await page.getByLabel("Enable OS due notifications").click();
   await page.getByRole("button", { name: "Close" }).click();
   await expect(page.getByRole("heading", { name: "Settings" })).toHaveCount(0);
+
+  await page.keyboard.press("Control+Shift+N");
+  await expect(page.getByRole("heading", { name: "Quick Capture" })).toBeVisible();
+  await page.getByLabel("What's on your mind?").fill("Smoke capture");
+  await page.keyboard.press("Enter");
+  await expect(page.getByRole("heading", { name: "Quick Capture" })).toHaveCount(0);
+  await expect(page.getByRole("button", { name: /Smoke capture/i })).toBeVisible();
 });
 
 test("contacts smoke: create, search, update, and bulk delete", async ({ page }) => {
@@ -0,0 +1,104 @@
+import type { CSSProperties, ReactNode } from "react";
+
+import type { CalendarDayLayout, CalendarDueTaskEntry } from "../../types/ui";
+
+interface CalendarTimeGridProps {
+  days: Date[];
+  layouts: CalendarDayLayout[];
+  hourStart: number;
+  hourEnd: number;
+  // Local minute-of-day for the "now" line, or null when today is not shown.
+  nowMinuteByDay: (number | null)[];
+  // Minutes from the top of each column to shade as past (0 = none).
+  pastMinuteByDay: number[];
+  renderDayHeader?: (day: Date, index: number) => ReactNode;
+  formatRange: (entry: CalendarDueTaskEntry) => string;
+}
+
+function markerStyle(entry: CalendarDueTaskEntry): CSSProperties {
+  return { ["--marker-color" as string]: entry.marker.color };
+}
+
+export function CalendarTimeGrid(props: CalendarTimeGridProps) {
+  const hours = Array.from({ length: props.hourEnd - props.hourStart + 1 }, (_, offset) => props.hourStart + offset);
+  const rangeStart = props.hourStart * 60;
+  const rangeMinutes = hours.length * 60;
+  const percentOf = (minute: number) => `${Math.max(0, Math.min(1, (minute - rangeStart) / rangeMinutes)) * 100}%`;
+  const gridStyle = { ["--grid-days" as string]: props.days.length } as CSSProperties;
+
+  return (
+    <div className="calendar-time-grid" style={gridStyle}>
+      {props.renderDayHeader ? (
+        <div className="calendar-time-grid-row">
+          <span />
+          {props.days.map((day, index) => (
+            <div key={day.getTime()} className="min-w-0">
+              {props.renderDayHeader?.(day, index)}
+            </div>
+          ))}
+        </div>
+      ) : null}
+
+      <div className="calendar-time-grid-row calendar-all-day-row">
+        <span className="calendar-hour-label">all day</span>
+        {props.layouts.map((layout, index) => (
+          <div key={props.days[index]?.getTime() ?? index} className="calendar-all-day-cell">
+            {layout.allDay.map((entry) => (
+              <span
+                key={`${entry.task.uuid}-${entry.kind}-${entry.dueUtcMs}`}
+                className={`calendar-all-day-chip ${entry.kind}`}
+                style={markerStyle(entry)}
+                title={`${entry.task.title}\n${props.formatRange(entry)}`}
+              >
+                {entry.task.title}
+              </span>
+            ))}
+          </div>
+        ))}
+      </div>
+
+      <div className="calendar-time-grid-body">
+        <div className="calendar-time-grid-row">
+          <div>
+            {hours.map((hour) => (
+              <div key={hour} className="calendar-time-slot">
+                <span className="calendar-hour-label">{String(hour).padStart(2, "0")}:00</span>
+              </div>
+            ))}
+          </div>
+          {props.layouts.map((layout, index) => {
+            const nowMinute = props.nowMinuteByDay[index] ?? null;
+            const pastMinute = props.pastMinuteByDay[index] ?? 0;
+            return (
+              <div key={props.days[index]?.getTime() ?? index} className="calendar-time-column">
+                {hours.map((hour) => (
+                  <div key={hour} className="calendar-time-slot" />
+                ))}
+                {pastMinute > rangeStart ? <div className="calendar-past-shade" style={{ height: percentOf(pastMinute) }} /> : null}
+                {nowMinute !== null && nowMinute >= rangeStart && nowMinute <= rangeStart + rangeMinutes ? (
+                  <div className="calendar-now-line" style={{ top: percentOf(nowMinute) }} />
+                ) : null}
+                {layout.blocks.map((block) => (
+                  <div
+                    key={`${block.entry.task.uuid}-${block.entry.kind}-${block.entry.dueUtcMs}`}
+                    className={`calendar-event-block ${block.entry.kind}`}
+                    style={{
+                      ...markerStyle(block.entry),
+                      top: `${block.top}%`,
+                      height: `${block.height}%`,
+                      left: `${(block.column / block.columns) * 100}%`,
+                      width: `calc(${100 / block.columns}% - 2px)`
+                    }}
+                    title={`${block.entry.task.title}\n${props.formatRange(block.entry)}`}
+                  >
+                    <span className="truncate">{block.entry.task.title}</span>
+                  </div>
+                ))}
+              </div>
+            );
+          })}
+        </div>
+      </div>
+    </div>
+  );
+}
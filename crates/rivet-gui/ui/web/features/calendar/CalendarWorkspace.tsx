@@ -1,4 +1,4 @@
-import { useEffect, useMemo, useRef, useState } from "react";
+import { Fragment, useEffect, useMemo, useRef, useState } from "react";
 import type { ChangeEvent } from "react";
 
 import AddIcon from "@mui/icons-material/Add";
@@ -21,19 +21,31 @@ import Stack from "@mui/material/Stack";
 import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
+import { ConfirmDialog } from "../../components/ConfirmDialog";
 import { TagChip } from "../../components/TagChip";
+import { CalendarTimeGrid } from "./CalendarTimeGrid";
 import {
   addDays,
   calendarDateFromIso,
   calendarDateToIso,
+  calendarLegendEntries,
   calendarMonthGridStart,
+  calendarDrillView,
+  calendarJumpYears,
+  calendarMonthJump,
   calendarTitleForView,
+  capMarkers,
   collectCalendarDueTasks,
   entriesForDate,
   firstDayOfMonth,
-  formatDueDateTime,
+  scheduledLegendEntry,
+  formatEntrySpan,
+  formatIsoWeek,
+  isoWeekForRow,
+  layoutCalendarDay,
   markersForDate,
   monthWeekStarts,
+  moveCalendarFocus,
   periodStats,
   periodTasks,
   quarterMonths,
@@ -42,39 +54,70 @@ import {
   toCalendarDate,
   weekdayLabels
 } from "../../lib/calendar";
-import { CAL_SOURCE_TAG_KEY, firstTagValue } from "../../lib/tags";
+import { humanize, resolveHumanizeStyle, type HumanizeStyle } from "../../lib/dateFormat";
+import { formatCalendarLabel, monthKeyOf, monthLabel } from "../../lib/locale";
+import { CAL_SOURCE_TAG_KEY, MONTH_KEYS, firstTagValue, normalizeTagValue } from "../../lib/tags";
 import { useBoardColorMap, useExternalCalendarColorMap } from "../../store/useAppStore";
-import { useCalendarWorkspaceSlice } from "../../store/slices";
-import type { ExternalCalendarCacheEntry, ExternalCalendarSource } from "../../types/core";
-import type { CalendarTaskMarker, CalendarViewMode } from "../../types/ui";
+import { useCalendarWorkspaceSlice, useCommandAllowed } from "../../store/slices";
+import type { ExternalCalendarCacheEntry, ExternalCalendarSource, ExternalCalendarSyncStatus } from "../../types/core";
+import type { CalendarDueTaskEntry, CalendarTaskMarker, CalendarViewMode } from "../../types/ui";
 
-function MarkerDots(props: { markers: CalendarTaskMarker[]; limit: number }) {
+function MarkerDots(props: { markers: CalendarTaskMarker[]; limit: number; onOverflow?: () => void }) {
   if (props.markers.length === 0) {
     return null;
   }
-  const capped = props.markers.slice(0, props.limit);
-  const overflow = props.markers.length - capped.length;
+  const { shown, overflow } = capMarkers(props.markers, props.limit);
+  const onOverflow = props.onOverflow;
   return (
     <div className="calendar-markers">
-      {capped.map((marker, index) => (
+      {shown.map((marker, index) => (
         <span
           key={`${marker.shape}-${marker.color}-${index}`}
           className={`calendar-marker ${marker.shape}`}
           style={{ ["--marker-color" as string]: marker.color }}
         />
       ))}
-      {overflow > 0 ? <span className="calendar-overflow">+{overflow}</span> : null}
+      {overflow > 0 && onOverflow ? (
+        <span
+          role="button"
+          tabIndex={0}
+          className="calendar-overflow actionable"
+          title={`${props.markers.length} tasks; open the day`}
+          onClick={(event) => {
+            event.stopPropagation();
+            onOverflow();
+          }}
+          onKeyDown={(event) => {
+            if (event.key === "Enter" || event.key === " ") {
+              event.preventDefault();
+              event.stopPropagation();
+              onOverflow();
+            }
+          }}
+        >
+          +{overflow}
+        </span>
+      ) : overflow > 0 ? (
+        <span className="calendar-overflow">+{overflow}</span>
+      ) : null}
     </div>
   );
 }
 
 function ExternalCalendarCard(props: {
   source: ExternalCalendarSource;
+  syncStatus: ExternalCalendarSyncStatus | undefined;
+  nowUtcMs: number;
+  humanizeStyle: HumanizeStyle;
   busy: boolean;
+  canSync: boolean;
   onSync: () => void;
   onEdit: () => void;
   onDelete: () => void;
 }) {
+  const lastSyncUtcMs = props.syncStatus?.last_sync ? Date.parse(props.syncStatus.last_sync) : null;
+  const nextSyncUtcMs = props.syncStatus?.next_sync ? Date.parse(props.syncStatus.next_sync) : null;
+  const syncState = props.syncStatus?.state ?? "idle";
   return (
     <Paper variant="outlined" className="p-2">
       <Stack spacing={0.9}>
@@ -99,12 +142,28 @@ function ExternalCalendarCard(props: {
               imported:file
             </Typography>
           ) : null}
+          {syncState !== "idle" ? (
+            <Typography variant="caption" color="primary" className="rounded-md border border-current/15 px-1.5 py-0.5">
+              {syncState === "running" ? "syncing" : "queued"}
+            </Typography>
+          ) : null}
         </Stack>
+        {lastSyncUtcMs !== null || nextSyncUtcMs !== null ? (
+          <Typography variant="caption" color="text.secondary">
+            {lastSyncUtcMs !== null ? `synced ${humanize(lastSyncUtcMs, props.nowUtcMs, props.humanizeStyle)}` : "never synced"}
+            {nextSyncUtcMs !== null ? ` · next ${nextSyncUtcMs <= props.nowUtcMs ? "soon" : humanize(nextSyncUtcMs, props.nowUtcMs, props.humanizeStyle)}` : ""}
+          </Typography>
+        ) : null}
+        {props.syncStatus?.last_error ? (
+          <Typography variant="caption" color="error" className="truncate" title={props.syncStatus.last_error}>
+            last sync failed: {props.syncStatus.last_error}
+          </Typography>
+        ) : null}
         <Stack direction="row" spacing={0.75}>
           <Button
             size="small"
             variant="outlined"
-            disabled={props.busy || props.source.imported_ics_file}
+            disabled={props.busy || syncState !== "idle" || !props.canSync || props.source.imported_ics_file}
             onClick={props.onSync}
             startIcon={<SyncIcon fontSize="small" />}
           >
@@ -132,9 +191,12 @@ export function CalendarWorkspace() {
     externalCalendars,
     externalBusy,
     externalLastSync,
+    externalSyncStatus,
     error,
     setCalendarView,
     shiftCalendarFocus,
+    setCalendarFocusDateIso,
+    jumpCalendarToInput,
     setCalendarTaskFilter,
     setCalendarConfigToggle,
     navigateCalendar,
@@ -147,14 +209,19 @@ export function CalendarWorkspace() {
     listExternalCalendarCachedEntries,
     importExternalCalendarFromCache
   } = useCalendarWorkspaceSlice();
+  const canImportCalendars = useCommandAllowed("calendar_import");
 
   const boardColorMap = useBoardColorMap();
   const calendarColorMap = useExternalCalendarColorMap();
   const config = useMemo(() => resolveCalendarConfig(runtimeConfig), [runtimeConfig]);
+  const humanizeStyle = useMemo(() => resolveHumanizeStyle(runtimeConfig), [runtimeConfig]);
+  const legend = useMemo(() => calendarLegendEntries(config.markers), [config.markers]);
+  const scheduledLegend = useMemo(() => scheduledLegendEntry(config.markers), [config.markers]);
   const focus = useMemo(() => calendarDateFromIso(calendarFocusDateIso), [calendarFocusDateIso]);
+  const [showWeekNumbers, setShowWeekNumbers] = useState(config.toggles.show_week_numbers);
   const title = useMemo(
-    () => calendarTitleForView(calendarView, focus, config.policies.week_start),
-    [calendarView, focus, config.policies.week_start]
+    () => calendarTitleForView(calendarView, focus, config.policies.week_start, showWeekNumbers, config.locale),
+    [calendarView, focus, config.policies.week_start, showWeekNumbers, config.locale]
   );
 
   const allDueEntries = useMemo(() => {
@@ -170,7 +237,7 @@ export function CalendarWorkspace() {
   const calendarNameMap = useMemo(() => {
     const map = new Map<string, string>();
     for (const source of externalCalendars) {
-      map.set(source.id, source.name);
+      map.set(normalizeTagValue(source.id), source.name);
     }
     return map;
   }, [externalCalendars]);
@@ -202,6 +269,25 @@ export function CalendarWorkspace() {
     () => ({ year: nowLocal.year, month: nowLocal.month, day: nowLocal.day }),
     [nowLocal.day, nowLocal.month, nowLocal.year]
   );
+  const jumpYears = useMemo(() => calendarJumpYears(focus, todayLocal), [focus, todayLocal]);
+  const [jumpInput, setJumpInput] = useState("");
+  const [jumpError, setJumpError] = useState<string | null>(null);
+  const [jumpBusy, setJumpBusy] = useState(false);
+
+  const submitJump = async () => {
+    setJumpBusy(true);
+    const error = await jumpCalendarToInput(jumpInput);
+    setJumpBusy(false);
+    setJumpError(error);
+    if (!error) {
+      setJumpInput("");
+    }
+  };
+
+  const jumpToMonth = (year: number, month: number) => {
+    setCalendarFocusDateIso(calendarDateToIso(calendarMonthJump(focus, year, month)));
+  };
+
   const todayMonthStart = useMemo(
     () => firstDayOfMonth(nowLocal.year, nowLocal.month),
     [nowLocal.month, nowLocal.year]
@@ -211,10 +297,12 @@ export function CalendarWorkspace() {
     setDeEmphasizePastPeriods(config.toggles.de_emphasize_past_periods);
     setFilterTasksBeforeNow(config.toggles.filter_tasks_before_now);
     setHidePastMarkers(config.toggles.hide_past_markers);
+    setShowWeekNumbers(config.toggles.show_week_numbers);
   }, [
     config.toggles.de_emphasize_past_periods,
     config.toggles.filter_tasks_before_now,
-    config.toggles.hide_past_markers
+    config.toggles.hide_past_markers,
+    config.toggles.show_week_numbers
   ]);
 
   const markerEntries = useMemo(() => {
@@ -249,6 +337,7 @@ export function CalendarWorkspace() {
   const [cacheSelection, setCacheSelection] = useState<string>("");
   const [cacheBusy, setCacheBusy] = useState(false);
   const importInputRef = useRef<HTMLInputElement | null>(null);
+  const rootRef = useRef<HTMLDivElement | null>(null);
 
   useEffect(() => {
     const id = window.setInterval(() => {
@@ -257,6 +346,57 @@ export function CalendarWorkspace() {
     return () => window.clearInterval(id);
   }, []);
 
+  useEffect(() => {
+    const handler = (event: KeyboardEvent) => {
+      const root = rootRef.current;
+      if (!root || root.offsetParent === null) {
+        return;
+      }
+      if (event.metaKey || event.ctrlKey || event.altKey) {
+        return;
+      }
+      const target = event.target as HTMLElement | null;
+      const isEditable = Boolean(
+        target
+          && (target.tagName === "INPUT"
+            || target.tagName === "TEXTAREA"
+            || target.tagName === "SELECT"
+            || target.isContentEditable)
+      );
+      if (isEditable || document.querySelector(".MuiModal-root:not(.MuiModal-hidden)")) {
+        return;
+      }
+
+      if (event.key === "ArrowLeft" || event.key === "ArrowRight" || event.key === "ArrowUp" || event.key === "ArrowDown") {
+        event.preventDefault();
+        navigateCalendar(calendarDateToIso(moveCalendarFocus(focus, calendarView, event.key)), calendarView);
+        return;
+      }
+      if (event.key === "PageUp" || event.key === "PageDown") {
+        event.preventDefault();
+        shiftCalendarFocus(event.key === "PageUp" ? -1 : 1);
+        return;
+      }
+      if (event.key === "Enter") {
+        // A focused button already handles Enter itself.
+        const drill = calendarDrillView(calendarView);
+        if (!drill || target?.tagName === "BUTTON" || target?.getAttribute("role") === "button") {
+          return;
+        }
+        event.preventDefault();
+        navigateCalendar(calendarDateToIso(focus), drill);
+        return;
+      }
+      if (event.key === "t") {
+        event.preventDefault();
+        navigateCalendar(calendarDateToIso(todayLocal), calendarView);
+      }
+    };
+
+    window.addEventListener("keydown", handler);
+    return () => window.removeEventListener("keydown", handler);
+  }, [calendarView, focus, navigateCalendar, shiftCalendarFocus, todayLocal]);
+
   const openAddSource = () => {
     setSourceEditor(openNewExternalCalendar());
     setSourceEditorError(null);
@@ -301,15 +441,16 @@ export function CalendarWorkspace() {
             .map((entry) => entry.marker);
           const isCurrentMonth = year === nowLocal.year && month === nowLocal.month;
           const isPastMonth = monthStart.getTime() < todayMonthStart.getTime();
+          const isFocused = focus.getUTCMonth() === monthIndex;
           return (
             <button
               key={month}
               type="button"
-              className={`calendar-period-card ${isCurrentMonth ? "calendar-current-month" : ""} ${deEmphasizePastPeriods && isPastMonth ? "calendar-past-muted" : ""}`}
+              className={`calendar-period-card ${isCurrentMonth ? "calendar-current-month" : ""} ${isFocused ? "calendar-focused-cell" : ""} ${deEmphasizePastPeriods && isPastMonth ? "calendar-past-muted" : ""}`}
               onClick={() => navigateCalendar(calendarDateToIso(monthStart), "month")}
             >
               <div className="calendar-period-title">
-                {monthStart.toLocaleString("en-US", { month: "long", timeZone: "UTC" })}
+                {monthLabel(monthKeyOf(monthStart), config.locale)}
               </div>
               <div className="calendar-period-count">{monthEntries.length} tasks</div>
               <MarkerDots markers={monthMarkers} limit={config.policies.red_dot_limit} />
@@ -335,15 +476,16 @@ export function CalendarWorkspace() {
             .map((entry) => entry.marker);
           const isCurrentMonth = year === nowLocal.year && month === nowLocal.month;
           const isPastMonth = monthStart.getTime() < todayMonthStart.getTime();
+          const isFocused = focus.getUTCMonth() === month - 1;
           return (
             <button
               key={month}
               type="button"
-              className={`calendar-period-card ${isCurrentMonth ? "calendar-current-month" : ""} ${deEmphasizePastPeriods && isPastMonth ? "calendar-past-muted" : ""}`}
+              className={`calendar-period-card ${isCurrentMonth ? "calendar-current-month" : ""} ${isFocused ? "calendar-focused-cell" : ""} ${deEmphasizePastPeriods && isPastMonth ? "calendar-past-muted" : ""}`}
               onClick={() => navigateCalendar(calendarDateToIso(monthStart), "month")}
             >
               <div className="calendar-period-title">
-                {monthStart.toLocaleString("en-US", { month: "long", timeZone: "UTC" })}
+                {monthLabel(monthKeyOf(monthStart), config.locale)}
               </div>
               <div className="calendar-period-count">{monthEntries.length} tasks</div>
               <MarkerDots markers={monthMarkers} limit={config.policies.red_dot_limit} />
@@ -359,14 +501,15 @@ export function CalendarWorkspace() {
     const weekStarts = monthWeekStarts(focus, config.policies.week_start);
     return (
       <Stack spacing={1.25}>
-        <div className="calendar-weekday-row">
-          {weekdayLabels(config.policies.week_start).map((label) => (
+        <div className={`calendar-weekday-row ${showWeekNumbers ? "with-week-numbers" : ""}`}>
+          {showWeekNumbers ? <div className="calendar-weekday">Wk</div> : null}
+          {weekdayLabels(config.policies.week_start, config.locale).map((label) => (
             <div key={label} className="calendar-weekday">
               {label}
             </div>
           ))}
         </div>
-        <div className="calendar-month-grid">
+        <div className={`calendar-month-grid ${showWeekNumbers ? "with-week-numbers" : ""}`}>
           {Array.from({ length: 42 }).map((_, offset) => {
             const day = addDays(gridStart, offset);
             const markers = markersForDate(markerEntries, day);
@@ -375,16 +518,27 @@ export function CalendarWorkspace() {
               && day.getUTCMonth() === todayLocal.getUTCMonth()
               && day.getUTCDate() === todayLocal.getUTCDate();
             const isPastDay = day.getTime() < todayLocal.getTime();
+            const rowWeek = showWeekNumbers && offset % 7 === 0 ? isoWeekForRow(day, config.policies.week_start) : null;
             return (
-              <button
-                key={calendarDateToIso(day)}
-                type="button"
-                className={`calendar-day-cell ${outside ? "outside" : ""} ${markers.length > 0 ? "has-tasks" : ""} ${isCurrentDay ? "calendar-current-day" : ""} ${deEmphasizePastPeriods && isPastDay ? "calendar-past-muted" : ""}`}
-                onClick={() => navigateCalendar(calendarDateToIso(day), "day")}
-              >
-                <div className="calendar-day-label">{day.getUTCDate()}</div>
-                <MarkerDots markers={markers} limit={config.policies.red_dot_limit} />
-              </button>
+              <Fragment key={calendarDateToIso(day)}>
+                {rowWeek ? (
+                  <div className="calendar-week-number" title={`ISO week ${rowWeek.week} of ${rowWeek.year}`}>
+                    {formatIsoWeek(rowWeek)}
+                  </div>
+                ) : null}
+                <button
+                  type="button"
+                  className={`calendar-day-cell ${outside ? "outside" : ""} ${markers.length > 0 ? "has-tasks" : ""} ${isCurrentDay ? "calendar-current-day" : ""} ${day.getTime() === focus.getTime() ? "calendar-focused-cell" : ""} ${deEmphasizePastPeriods && isPastDay ? "calendar-past-muted" : ""}`}
+                  onClick={() => navigateCalendar(calendarDateToIso(day), "day")}
+                >
+                  <div className="calendar-day-label">{day.getUTCDate()}</div>
+                  <MarkerDots
+                    markers={markers}
+                    limit={config.policies.day_marker_limit}
+                    onOverflow={() => navigateCalendar(calendarDateToIso(day), "day")}
+                  />
+                </button>
+              </Fragment>
             );
           })}
         </div>
@@ -398,8 +552,9 @@ export function CalendarWorkspace() {
                 size="small"
                 onClick={() => navigateCalendar(calendarDateToIso(weekStartDay), "week")}
               >
-                {weekStartDay.toLocaleString("en-US", { month: "short", day: "2-digit", timeZone: "UTC" })} -{" "}
-                {weekEndDay.toLocaleString("en-US", { month: "short", day: "2-digit", timeZone: "UTC" })}
+                {showWeekNumbers ? `${formatIsoWeek(isoWeekForRow(weekStartDay, config.policies.week_start))} · ` : null}
+                {formatCalendarLabel(weekStartDay, config.locale, { month: "short", day: "2-digit" })} -{" "}
+                {formatCalendarLabel(weekEndDay, config.locale, { month: "short", day: "2-digit" })}
               </Button>
             );
           })}
@@ -408,79 +563,69 @@ export function CalendarWorkspace() {
     );
   };
 
+  const pastMinuteFor = (day: Date) => {
+    if (!deEmphasizePastPeriods || day.getTime() > todayLocal.getTime()) {
+      return 0;
+    }
+    return day.getTime() < todayLocal.getTime() ? 24 * 60 : nowLocal.hour * 60 + nowLocal.minute;
+  };
+  const nowMinuteFor = (day: Date) => (day.getTime() === todayLocal.getTime() ? nowLocal.hour * 60 + nowLocal.minute : null);
+  const formatRange = (entry: CalendarDueTaskEntry) =>
+    `${entry.kind === "scheduled" ? "Scheduled" : "Due"} ${formatEntrySpan(entry, config.timezone)}`;
+  const formatListRange = (entry: CalendarDueTaskEntry) => `${formatRange(entry)} · ${humanize(entry.dueUtcMs, nowUtcMs, humanizeStyle)}`;
+
   const renderWeekView = () => {
     const start = startOfWeek(focus, config.policies.week_start);
+    const days = Array.from({ length: 7 }, (_, offset) => addDays(start, offset));
     return (
-      <div className="calendar-period-grid week">
-        {Array.from({ length: 7 }).map((_, offset) => {
-          const day = addDays(start, offset);
+      <CalendarTimeGrid
+        days={days}
+        layouts={days.map((day) => layoutCalendarDay(allDueEntries, day, config.day_view.hour_start, config.day_view.hour_end))}
+        hourStart={config.day_view.hour_start}
+        hourEnd={config.day_view.hour_end}
+        nowMinuteByDay={days.map(nowMinuteFor)}
+        pastMinuteByDay={days.map(pastMinuteFor)}
+        formatRange={formatRange}
+        renderDayHeader={(day) => {
           const dayEntries = entriesForDate(allDueEntries, day);
           const dayMarkers = entriesForDate(markerEntries, day).map((entry) => entry.marker);
-          const isCurrentDay = day.getUTCFullYear() === todayLocal.getUTCFullYear()
-            && day.getUTCMonth() === todayLocal.getUTCMonth()
-            && day.getUTCDate() === todayLocal.getUTCDate();
+          const isCurrentDay = day.getTime() === todayLocal.getTime();
           const isPastDay = day.getTime() < todayLocal.getTime();
           return (
             <button
-              key={calendarDateToIso(day)}
               type="button"
-              className={`calendar-week-card ${dayEntries.length > 0 ? "has-tasks" : ""} ${isCurrentDay ? "calendar-current-day" : ""} ${deEmphasizePastPeriods && isPastDay ? "calendar-past-muted" : ""}`}
+              className={`calendar-week-card w-full ${dayEntries.length > 0 ? "has-tasks" : ""} ${isCurrentDay ? "calendar-current-day" : ""} ${day.getTime() === focus.getTime() ? "calendar-focused-cell" : ""} ${deEmphasizePastPeriods && isPastDay ? "calendar-past-muted" : ""}`}
               onClick={() => navigateCalendar(calendarDateToIso(day), "day")}
             >
               <div className="calendar-week-card-head">
-                <span>{day.toLocaleString("en-US", { weekday: "short", day: "2-digit", timeZone: "UTC" })}</span>
+                <span>{formatCalendarLabel(day, config.locale, { weekday: "short", day: "2-digit" })}</span>
                 <span className="calendar-period-count">{dayEntries.length}</span>
               </div>
-              <MarkerDots markers={dayMarkers} limit={config.policies.red_dot_limit} />
-              <Stack spacing={0.5}>
-                {dayEntries.slice(0, 5).map((entry) => (
-                  <Typography key={`${entry.task.uuid}-${entry.dueUtcMs}`} variant="caption" className="truncate text-left">
-                    {entry.task.title}
-                  </Typography>
-                ))}
-                {dayEntries.length > 5 ? (
-                  <Typography variant="caption" color="text.secondary" className="text-left">
-                    +{dayEntries.length - 5} more
-                  </Typography>
-                ) : null}
-              </Stack>
+              <MarkerDots
+                markers={dayMarkers}
+                limit={config.policies.day_marker_limit}
+                onOverflow={() => navigateCalendar(calendarDateToIso(day), "day")}
+              />
             </button>
           );
-        })}
-      </div>
+        }}
+      />
     );
   };
 
   const renderDayView = () => {
     const dayEntries = entriesForDate(allDueEntries, focus).sort((a, b) => a.dueUtcMs - b.dueUtcMs);
-    const dayMarkerEntries = entriesForDate(markerEntries, focus);
-    const isFocusToday = focus.getUTCFullYear() === todayLocal.getUTCFullYear()
-      && focus.getUTCMonth() === todayLocal.getUTCMonth()
-      && focus.getUTCDate() === todayLocal.getUTCDate();
-    const focusBeforeToday = focus.getTime() < todayLocal.getTime();
-    const hourStart = config.day_view.hour_start;
-    const hourEnd = config.day_view.hour_end;
-    const nowHourFloat = nowLocal.hour + nowLocal.minute / 60;
-    const rawOffset = (nowHourFloat - hourStart) / (hourEnd - hourStart + 1);
-    const nowLineOffset = Math.max(0, Math.min(1, rawOffset)) * 100;
     return (
       <div className="calendar-day-view">
-        <div className="calendar-day-hours">
-          {isFocusToday && nowHourFloat >= hourStart && nowHourFloat <= hourEnd + 1 ? (
-            <div className="calendar-now-line" style={{ top: `${nowLineOffset}%` }} />
-          ) : null}
-          {Array.from({ length: config.day_view.hour_end - config.day_view.hour_start + 1 }).map((_, offset) => {
-            const hour = config.day_view.hour_start + offset;
-            const markers = dayMarkerEntries.filter((entry) => entry.dueLocal.hour === hour).map((entry) => entry.marker);
-            const pastHour = deEmphasizePastPeriods && (focusBeforeToday || (isFocusToday && hour < nowLocal.hour));
-            return (
-              <div key={hour} className={`calendar-hour-row ${pastHour ? "calendar-past-muted" : ""}`}>
-                <span className="calendar-hour-label">{String(hour).padStart(2, "0")}:00</span>
-                <MarkerDots markers={markers} limit={config.policies.red_dot_limit} />
-              </div>
-            );
-          })}
-        </div>
+        <CalendarTimeGrid
+          days={[focus]}
+          layouts={[layoutCalendarDay(allDueEntries, focus, config.day_view.hour_start, config.day_view.hour_end)]}
+          hourStart={config.day_view.hour_start}
+          hourEnd={config.day_view.hour_end}
+          nowMinuteByDay={[nowMinuteFor(focus)]}
+          pastMinuteByDay={[pastMinuteFor(focus)]}
+          formatRange={formatRange}
+        />
         <div className="calendar-day-list">
           {dayEntries.length === 0 ? (
             <Typography variant="body2" color="text.secondary">
@@ -488,11 +633,11 @@ export function CalendarWorkspace() {
             </Typography>
           ) : (
             dayEntries.map((entry) => (
-              <Paper key={`${entry.task.uuid}-${entry.dueUtcMs}`} variant="outlined" className="p-2">
+              <Paper key={`${entry.task.uuid}-${entry.kind}-${entry.dueUtcMs}`} variant="outlined" className="p-2">
                 <Stack spacing={0.75}>
                   <Typography variant="subtitle2">{entry.task.title}</Typography>
                   <Typography variant="caption" color="text.secondary">
-                    {formatDueDateTime(entry.dueUtcMs, config.timezone)}
+                    {formatListRange(entry)}
                   </Typography>
                   <Stack direction="row" spacing={0.75} flexWrap="wrap" useFlexGap>
                     {entry.task.tags.slice(0, 5).map((tag) => (
@@ -520,7 +665,7 @@ export function CalendarWorkspace() {
   }
 
   return (
-    <div className="grid h-full min-h-0 grid-cols-[280px_minmax(0,1fr)_340px] gap-3 p-3">
+    <div ref={rootRef} className="grid h-full min-h-0 grid-cols-[280px_minmax(0,1fr)_340px] gap-3 p-3">
       <Paper className="min-h-0 p-3">
         <Stack spacing={1.5} className="h-full min-h-0">
           <Typography variant="h6">Calendar Views</Typography>
@@ -555,6 +700,59 @@ export function CalendarWorkspace() {
               Next
             </Button>
           </Stack>
+          <form
+            onSubmit={(event) => {
+              event.preventDefault();
+              void submitJump();
+            }}
+          >
+            <Stack direction="row" spacing={1} alignItems="flex-start">
+              <TextField
+                label="Jump to date"
+                size="small"
+                value={jumpInput}
+                placeholder="2027-03-14, march 2027"
+                error={jumpError !== null}
+                helperText={jumpError ?? undefined}
+                onChange={(event) => {
+                  setJumpInput(event.target.value);
+                  setJumpError(null);
+                }}
+              />
+              <Button type="submit" size="small" variant="outlined" disabled={jumpBusy}>
+                Go
+              </Button>
+            </Stack>
+          </form>
+          <Stack direction="row" spacing={1}>
+            <TextField
+              select
+              label="Month"
+              size="small"
+              className="flex-1"
+              value={focus.getUTCMonth() + 1}
+              onChange={(event) => jumpToMonth(focus.getUTCFullYear(), Number(event.target.value))}
+            >
+              {MONTH_KEYS.map((key, index) => (
+                <MenuItem key={key} value={index + 1}>
+                  {monthLabel(key, config.locale)}
+                </MenuItem>
+              ))}
+            </TextField>
+            <TextField
+              select
+              label="Year"
+              size="small"
+              value={focus.getUTCFullYear()}
+              onChange={(event) => jumpToMonth(Number(event.target.value), focus.getUTCMonth() + 1)}
+            >
+              {jumpYears.map((year) => (
+                <MenuItem key={year} value={year}>
+                  {year}
+                </MenuItem>
+              ))}
+            </TextField>
+          </Stack>
           <Typography variant="caption" color="text.secondary">
             timezone: {config.timezone}
           </Typography>
@@ -562,7 +760,9 @@ export function CalendarWorkspace() {
             focus date: {calendarDateToIso(focus)}
           </Typography>
           <Typography variant="caption" color="text.secondary">
-            due tasks: {allDueEntries.length}
+            due: {allDueEntries.filter((entry) => entry.kind === "due").length}
+            {" · "}
+            scheduled: {allDueEntries.filter((entry) => entry.kind === "scheduled").length}
           </Typography>
           <FormControlLabel
             control={(
@@ -590,12 +790,65 @@ export function CalendarWorkspace() {
             )}
             label="Hide past task markers"
           />
+          <FormControlLabel
+            control={(
+              <Checkbox
+                checked={showWeekNumbers}
+                onChange={(event) => {
+                  const checked = event.target.checked;
+                  setShowWeekNumbers(checked);
+                  setCalendarConfigToggle("show_week_numbers", checked);
+                }}
+              />
+            )}
+            label="Show ISO week numbers"
+          />
+          <FormControlLabel
+            control={(
+              <Checkbox
+                checked={config.toggles.show_due_markers}
+                onChange={(event) => setCalendarConfigToggle("show_due_markers", event.target.checked)}
+              />
+            )}
+            label="Show due markers"
+          />
+          <FormControlLabel
+            control={(
+              <Checkbox
+                checked={config.toggles.show_scheduled_markers}
+                onChange={(event) => setCalendarConfigToggle("show_scheduled_markers", event.target.checked)}
+              />
+            )}
+            label="Show scheduled markers"
+          />
 
           <Stack spacing={0.7}>
             <Typography variant="caption" color="text.secondary">Marker legend</Typography>
-            <div className="calendar-legend-row"><span className="calendar-marker triangle" style={{ ["--marker-color" as string]: "var(--mui-palette-primary-main)" }} /> Kanban board task</div>
-            <div className="calendar-legend-row"><span className="calendar-marker circle" style={{ ["--marker-color" as string]: "#d64545" }} /> External calendar task</div>
-            <div className="calendar-legend-row"><span className="calendar-marker square" style={{ ["--marker-color" as string]: "#7f8691" }} /> Unassigned task</div>
+            {config.toggles.show_due_markers ? (
+              <>
+                <Typography variant="caption" color="text.secondary">Due: when a task has to be done</Typography>
+                {legend.map((entry) => (
+                  <div key={entry.key} className="calendar-legend-row">
+                    <span className={`calendar-marker ${entry.marker.shape}`} style={{ ["--marker-color" as string]: entry.marker.color }} /> {entry.label}
+                  </div>
+                ))}
+              </>
+            ) : null}
+            {config.toggles.show_scheduled_markers ? (
+              <>
+                <Typography variant="caption" color="text.secondary">
+                  Scheduled: when you plan to start; dashed in day and week views
+                </Typography>
+                <div className="calendar-legend-row">
+                  <span
+                    className={`calendar-marker ${scheduledLegend.marker.shape}`}
+                    style={{ ["--marker-color" as string]: scheduledLegend.marker.color }}
+                  />
+                  {" "}
+                  {scheduledLegend.label}
+                </div>
+              </>
+            ) : null}
           </Stack>
 
           <Box className="min-h-0 overflow-y-auto pr-1">
@@ -608,6 +861,7 @@ export function CalendarWorkspace() {
                   {externalBusy ? "Syncing..." : "Sync Enabled"}
                 </Button>
                 <Button
+                  disabled={!canImportCalendars}
                   size="small"
                   variant="outlined"
                   onClick={() => importInputRef.current?.click()}
@@ -615,6 +869,7 @@ export function CalendarWorkspace() {
                   Import ICS File
                 </Button>
                 <Button
+                  disabled={!canImportCalendars}
                   size="small"
                   variant="outlined"
                   onClick={() => {
@@ -658,7 +913,11 @@ export function CalendarWorkspace() {
                   <ExternalCalendarCard
                     key={source.id}
                     source={source}
+                    syncStatus={externalSyncStatus[source.id]}
+                    nowUtcMs={nowUtcMs}
+                    humanizeStyle={humanizeStyle}
                     busy={externalBusy}
+                    canSync={canImportCalendars}
                     onSync={() => void syncExternalCalendarSource(source.id)}
                     onEdit={() => openEditSource(source)}
                     onDelete={() => setDeleteTarget(source)}
@@ -730,11 +989,11 @@ export function CalendarWorkspace() {
                   </Typography>
                 ) : (
                   visiblePeriodEntries.map((entry) => (
-                    <Paper key={`${entry.task.uuid}-${entry.dueUtcMs}`} variant="outlined" className="p-2">
+                    <Paper key={`${entry.task.uuid}-${entry.kind}-${entry.dueUtcMs}`} variant="outlined" className="p-2">
                       <Stack spacing={0.75}>
                         <Typography variant="subtitle2">{entry.task.title}</Typography>
                         <Typography variant="caption" color="text.secondary">
-                          {formatDueDateTime(entry.dueUtcMs, config.timezone)}
+                          {formatListRange(entry)}
                         </Typography>
                         {entry.task.project ? (
                           <Typography variant="caption" className="rounded-md border border-current/15 px-1.5 py-0.5">
@@ -854,30 +1113,19 @@ export function CalendarWorkspace() {
         </DialogActions>
       </Dialog>
 
-      <Dialog open={Boolean(deleteTarget)} onClose={() => setDeleteTarget(null)} maxWidth="xs" fullWidth>
-        <DialogTitle>Delete External Calendar</DialogTitle>
-        <DialogContent dividers>
-          <Typography variant="body2">
-            Delete external calendar &apos;{deleteTarget?.name ?? ""}&apos;?
-          </Typography>
-        </DialogContent>
-        <DialogActions>
-          <Button onClick={() => setDeleteTarget(null)}>Cancel</Button>
-          <Button
-            color="error"
-            variant="contained"
-            onClick={() => {
-              if (!deleteTarget) {
-                return;
-              }
-              deleteExternalCalendarSource(deleteTarget.id);
-              setDeleteTarget(null);
-            }}
-          >
-            Delete
-          </Button>
-        </DialogActions>
-      </Dialog>
+      <ConfirmDialog
+        open={Boolean(deleteTarget)}
+        title="Delete External Calendar"
+        body={`Delete external calendar '${deleteTarget?.name ?? ""}'?`}
+        confirmLabel="Delete"
+        destructive
+        onResult={(confirmed) => {
+          if (confirmed && deleteTarget) {
+            deleteExternalCalendarSource(deleteTarget.id);
+          }
+          setDeleteTarget(null);
+        }}
+      />
 
       <Dialog open={cacheDialogOpen} onClose={() => setCacheDialogOpen(false)} maxWidth="sm" fullWidth>
         <DialogTitle>Import Cached ICS</DialogTitle>
@@ -916,7 +1164,7 @@ export function CalendarWorkspace() {
           </Button>
           <Button
             variant="contained"
-            disabled={cacheEntries.length === 0 || !cacheSelection || externalBusy || cacheBusy}
+            disabled={!canImportCalendars || cacheEntries.length === 0 || !cacheSelection || externalBusy || cacheBusy}
             onClick={() => {
               const selected = cacheEntries.find((entry) => entry.cache_id === cacheSelection);
               if (!selected) {
@@ -11,16 +11,43 @@ import Alert from "@mui/material/Alert";
 import Avatar from "@mui/material/Avatar";
 import Button from "@mui/material/Button";
 import Checkbox from "@mui/material/Checkbox";
+import Chip from "@mui/material/Chip";
+import Dialog from "@mui/material/Dialog";
+import DialogActions from "@mui/material/DialogActions";
+import DialogContent from "@mui/material/DialogContent";
+import DialogTitle from "@mui/material/DialogTitle";
 import Divider from "@mui/material/Divider";
 import IconButton from "@mui/material/IconButton";
 import MenuItem from "@mui/material/MenuItem";
 import Paper from "@mui/material/Paper";
+import Snackbar from "@mui/material/Snackbar";
 import Stack from "@mui/material/Stack";
 import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
+import { FieldCompareTable } from "../../components/FieldCompareTable";
+import { CONTACTED_WITHIN_DAYS_OPTIONS, lastContactedLabel } from "../../lib/contactInteractions";
+import { diffFields, type FieldSpec } from "../../lib/fieldDiff";
+import { openTasksForContact } from "../../store/selectors";
+import { useCommandAllowed } from "../../store/slices";
+import { useAppStore } from "../../store/useAppStore";
 import { useContactsStore } from "../../store/useContactsStore";
-import type { ContactDto, ContactFieldValue } from "../../types/core";
+import type { ContactDto, ContactFieldValue, ContactsSortMode } from "../../types/core";
+
+const CONTACT_COMPARE_SPECS: FieldSpec<ContactDto>[] = [
+  { key: "display_name", label: "Name", value: (contact) => contact.display_name },
+  { key: "given_name", label: "Given name", value: (contact) => contact.given_name },
+  { key: "family_name", label: "Family name", value: (contact) => contact.family_name },
+  { key: "nickname", label: "Nickname", value: (contact) => contact.nickname },
+  { key: "emails", label: "Emails", value: (contact) => contact.emails.map((item) => item.value) },
+  { key: "phones", label: "Phones", value: (contact) => contact.phones.map((item) => item.value) },
+  { key: "websites", label: "Websites", value: (contact) => contact.websites.map((item) => item.value) },
+  { key: "organization", label: "Organization", value: (contact) => contact.organization },
+  { key: "title", label: "Title", value: (contact) => contact.title },
+  { key: "birthday", label: "Birthday", value: (contact) => contact.birthday },
+  { key: "categories", label: "Categories", value: (contact) => contact.categories },
+  { key: "notes", label: "Notes", value: (contact) => contact.notes }
+];
 
 function fieldTemplate(kind: string): ContactFieldValue {
   return {
@@ -89,77 +116,24 @@ function possibleDuplicateText(draftName: string, draftEmails: string[], draftPh
   return `Possible duplicate: ${match.display_name || "Unnamed Contact"}`;
 }
 
-function mergePreview(contacts: ContactDto[], preferredTargetId: string | null): ContactDto | null {
-  if (contacts.length < 2) {
-    return null;
-  }
-
-  const byRecency = [...contacts].sort((left, right) => right.updated_at.localeCompare(left.updated_at));
-  const target = contacts.find((contact) => contact.id === preferredTargetId) ?? byRecency[0];
-
-  const mergeFields = (current: ContactFieldValue[], incoming: ContactFieldValue[]): ContactFieldValue[] => {
-    const seen = new Set(current.map((item) => `${item.kind}:${item.value}`.toLowerCase()));
-    const next = [...current];
-    for (const item of incoming) {
-      const key = `${item.kind}:${item.value}`.toLowerCase();
-      if (!seen.has(key) && item.value.trim()) {
-        next.push({ ...item, is_primary: false });
-        seen.add(key);
-      }
-    }
-    const primaryIndex = next.findIndex((item) => item.is_primary);
-    if (primaryIndex === -1 && next.length > 0) {
-      next[0] = { ...next[0], is_primary: true };
-    } else {
-      for (let index = 0; index < next.length; index += 1) {
-        next[index] = { ...next[index], is_primary: index === primaryIndex };
-      }
-    }
-    return next;
-  };
-
-  const merged: ContactDto = {
-    ...target,
-    phones: [...target.phones],
-    emails: [...target.emails],
-    websites: [...target.websites]
-  };
-
-  for (const contact of contacts) {
-    if (contact.id === target.id) {
-      continue;
-    }
-    if (!merged.display_name.trim() && contact.display_name.trim()) {
-      merged.display_name = contact.display_name;
-    }
-    merged.given_name = merged.given_name || contact.given_name;
-    merged.family_name = merged.family_name || contact.family_name;
-    merged.nickname = merged.nickname || contact.nickname;
-    merged.notes = merged.notes || contact.notes;
-    merged.organization = merged.organization || contact.organization;
-    merged.title = merged.title || contact.title;
-    merged.birthday = merged.birthday || contact.birthday;
-    merged.avatar_data_url = merged.avatar_data_url || contact.avatar_data_url;
-    merged.import_batch_id = merged.import_batch_id || contact.import_batch_id;
-    merged.source_file_name = merged.source_file_name || contact.source_file_name;
-    if (merged.addresses.length === 0 && contact.addresses.length > 0) {
-      merged.addresses = contact.addresses;
-    }
-    merged.phones = mergeFields(merged.phones, contact.phones);
-    merged.emails = mergeFields(merged.emails, contact.emails);
-    merged.websites = mergeFields(merged.websites, contact.websites);
-  }
-
-  return merged;
-}
-
 export function ContactsWorkspace() {
+  const canWriteContacts = useCommandAllowed("contacts_write");
+  const canDeleteContacts = useCommandAllowed("contacts_delete");
+  const canWriteTasks = useCommandAllowed("tasks_write");
+  const tasks = useAppStore((state) => state.tasks);
+  const createTask = useAppStore((state) => state.createTask);
+  const selectTask = useAppStore((state) => state.selectTask);
+  const setActiveTab = useAppStore((state) => state.setActiveTab);
   const {
     bootstrap,
     loading,
     error,
     query,
     sourceFilter,
+    categoryFilter,
+    categoryFacets,
+    sortMode,
+    contactedWithinDays,
     contacts,
     selectedContactId,
     selectionMode,
@@ -169,12 +143,18 @@ export function ContactsWorkspace() {
     dedupe,
     importPreview,
     importCommitResult,
+    importDecisions,
     mergeUndoResult,
+    lastDeleteUndo,
+    mergePreview,
     formDraft,
     dirty,
     editorMode,
     setQuery,
     setSourceFilter,
+    setCategoryFilter,
+    setSortMode,
+    setContactedWithinDays,
     selectContact,
     toggleSelectionMode,
     toggleSelected,
@@ -188,15 +168,22 @@ export function ContactsWorkspace() {
     updateSelectedFromDraft,
     removeSelectedContact,
     removeBulkSelected,
+    undoLastDelete,
+    dismissDeleteUndo,
     refreshDedupe,
     linkSelectedContacts,
     unlinkSelectedContacts,
+    previewMergeSelected,
+    clearMergePreview,
     mergeSelected,
     undoLastMerge,
     decideDedupeGroup,
     previewImport,
+    setImportDecision,
     commitImport,
     openAction,
+    exportInteractions,
+    pruneInteractions,
     loadMoreContacts
   } = useContactsStore();
 
@@ -208,7 +195,10 @@ export function ContactsWorkspace() {
   const [searchInput, setSearchInput] = useState(query);
   const [importSource, setImportSource] = useState("gmail_export");
   const [importMode, setImportMode] = useState<"safe" | "upsert" | "review">("safe");
+  const [pruneOlderThanDays, setPruneOlderThanDays] = useState<number | null>(365);
+  const [pruneMessage, setPruneMessage] = useState<string | null>(null);
   const [selectedDedupeGroupId, setSelectedDedupeGroupId] = useState<string | null>(null);
+  const [mergeDialogOpen, setMergeDialogOpen] = useState(false);
 
   useEffect(() => {
     void bootstrap();
@@ -230,6 +220,11 @@ export function ContactsWorkspace() {
     [contacts, selectedContactId]
   );
 
+  const linkedTasks = useMemo(
+    () => (selectedContact ? openTasksForContact(tasks, selectedContact.id) : []),
+    [selectedContact, tasks]
+  );
+
   const selectionSet = useMemo(() => new Set(selectionIds), [selectionIds]);
 
   const selectedDedupeGroup = useMemo(
@@ -237,12 +232,31 @@ export function ContactsWorkspace() {
     [dedupe, selectedDedupeGroupId]
   );
 
-  const dedupePreviewContact = useMemo(() => {
-    if (!selectedDedupeGroup) {
-      return null;
+  const mergeCandidates = useMemo(() => {
+    const known = new Map<string, ContactDto>();
+    for (const contact of [...contacts, ...(dedupe?.groups.flatMap((group) => group.contacts) ?? [])]) {
+      known.set(contact.id, contact);
     }
-    return mergePreview(selectedDedupeGroup.contacts, selectedContactId);
-  }, [selectedDedupeGroup, selectedContactId]);
+    return [...new Set(selectionIds)]
+      .map((id) => known.get(id))
+      .filter((contact): contact is ContactDto => Boolean(contact));
+  }, [contacts, dedupe, selectionIds]);
+
+  const mergeCompareRows = useMemo(
+    () => (mergeCandidates.length === 2 ? diffFields(mergeCandidates[0], mergeCandidates[1], CONTACT_COMPARE_SPECS) : null),
+    [mergeCandidates]
+  );
+
+  const openMergeDialog = () => {
+    clearMergePreview();
+    setMergeDialogOpen(true);
+    void previewMergeSelected();
+  };
+
+  const closeMergeDialog = () => {
+    setMergeDialogOpen(false);
+    clearMergePreview();
+  };
 
   const duplicateWarning = useMemo(() => {
     return possibleDuplicateText(
@@ -456,6 +470,60 @@ export function ContactsWorkspace() {
             ))}
           </TextField>
 
+          <Stack direction="row" spacing={1}>
+            <TextField
+              select
+              size="small"
+              label="Sort"
+              value={sortMode}
+              onChange={(event) => {
+                void setSortMode(event.target.value as ContactsSortMode);
+              }}
+              className="flex-1"
+            >
+              <MenuItem value="name">Name</MenuItem>
+              <MenuItem value="recent">Recently contacted</MenuItem>
+            </TextField>
+            <TextField
+              select
+              size="small"
+              label="Contacted within"
+              value={contactedWithinDays === null ? "" : String(contactedWithinDays)}
+              onChange={(event) => {
+                const value = event.target.value;
+                void setContactedWithinDays(value.length > 0 ? Number(value) : null);
+              }}
+              className="flex-1"
+            >
+              <MenuItem value="">Any time</MenuItem>
+              {CONTACTED_WITHIN_DAYS_OPTIONS.map((days) => (
+                <MenuItem key={days} value={String(days)}>
+                  {days} days
+                </MenuItem>
+              ))}
+            </TextField>
+          </Stack>
+
+          {categoryFacets.length > 0 ? (
+            <Stack direction="row" spacing={0.5} flexWrap="wrap" useFlexGap>
+              {categoryFacets.map((facet) => {
+                const active = categoryFilter?.toLowerCase() === facet.category.toLowerCase();
+                return (
+                  <Chip
+                    key={facet.category}
+                    size="small"
+                    label={`${facet.category} (${facet.count})`}
+                    color={active ? "primary" : "default"}
+                    variant={active ? "filled" : "outlined"}
+                    onClick={() => {
+                      void setCategoryFilter(active ? null : facet.category);
+                    }}
+                  />
+                );
+              })}
+            </Stack>
+          ) : null}
+
           <Stack direction="row" spacing={1} flexWrap="wrap" useFlexGap>
             <Button size="small" variant={selectionMode ? "contained" : "outlined"} onClick={toggleSelectionMode}>
               {selectionMode ? "Exit Select" : "Select"}
@@ -468,7 +536,7 @@ export function ContactsWorkspace() {
               variant="outlined"
               color="error"
               startIcon={<DeleteIcon fontSize="small" />}
-              disabled={selectionIds.length === 0}
+              disabled={!canDeleteContacts || selectionIds.length === 0}
               onClick={() => {
                 void removeBulkSelected();
               }}
@@ -502,14 +570,13 @@ export function ContactsWorkspace() {
             <Button
               size="small"
               variant="outlined"
-              disabled={selectionIds.length < 2}
-              onClick={() => {
-                void mergeSelected();
-              }}
+              disabled={!canWriteContacts || selectionIds.length < 2}
+              onClick={openMergeDialog}
             >
               Merge Selected
             </Button>
             <Button
+              disabled={!canWriteContacts}
               size="small"
               variant="outlined"
               startIcon={<RedoIcon fontSize="small" />}
@@ -531,12 +598,66 @@ export function ContactsWorkspace() {
             </Button>
           </Stack>
 
+          <Stack spacing={1}>
+            <Typography variant="subtitle2">Interaction Log</Typography>
+            <Stack direction="row" spacing={1} alignItems="center">
+              <Button
+                size="small"
+                variant="outlined"
+                onClick={() => {
+                  void exportInteractions();
+                }}
+              >
+                Export Log
+              </Button>
+              <TextField
+                select
+                size="small"
+                label="Prune"
+                value={pruneOlderThanDays === null ? "" : String(pruneOlderThanDays)}
+                onChange={(event) => {
+                  const value = event.target.value;
+                  setPruneOlderThanDays(value.length > 0 ? Number(value) : null);
+                }}
+                className="flex-1"
+              >
+                <MenuItem value="">All entries</MenuItem>
+                {CONTACTED_WITHIN_DAYS_OPTIONS.map((days) => (
+                  <MenuItem key={days} value={String(days)}>
+                    Older than {days} days
+                  </MenuItem>
+                ))}
+              </TextField>
+              <Button
+                size="small"
+                variant="outlined"
+                color="error"
+                disabled={!canWriteContacts}
+                onClick={() => {
+                  void pruneInteractions(pruneOlderThanDays).then((result) => {
+                    if (result) {
+                      setPruneMessage(`Removed ${result.removed} entries, ${result.remaining} remaining.`);
+                    }
+                  });
+                }}
+              >
+                Prune Log
+              </Button>
+            </Stack>
+            {pruneMessage ? (
+              <Typography variant="caption" color="text.secondary">
+                {pruneMessage}
+              </Typography>
+            ) : null}
+          </Stack>
+
           <Divider />
 
           <Stack spacing={1}>
             <Typography variant="subtitle2">Import</Typography>
             <Stack direction="row" spacing={1}>
               <Button
+                disabled={!canWriteContacts}
                 size="small"
                 variant="outlined"
                 startIcon={<UploadFileIcon fontSize="small" />}
@@ -592,9 +713,27 @@ export function ContactsWorkspace() {
                 preview rows: {importPreview.total_rows} valid: {importPreview.valid_rows} duplicates: {importPreview.potential_duplicates}
               </Typography>
             ) : null}
+            {importPreview?.conflicts.map((conflict) => (
+              <TextField
+                key={conflict.row}
+                select
+                size="small"
+                label={`${conflict.imported.display_name} ~ ${conflict.existing.display_name} (${conflict.reason})`}
+                value={importDecisions[conflict.row] ?? "mode"}
+                onChange={(event) => {
+                  const value = event.target.value;
+                  setImportDecision(conflict.row, value === "merge" || value === "skip" || value === "link" ? value : null);
+                }}
+              >
+                <MenuItem value="mode">Use import mode</MenuItem>
+                <MenuItem value="merge">Merge into existing</MenuItem>
+                <MenuItem value="skip">Skip</MenuItem>
+                <MenuItem value="link">Keep both, linked</MenuItem>
+              </TextField>
+            ))}
             {importCommitResult ? (
               <Typography variant="caption" color="text.secondary">
-                import result: +{importCommitResult.created} ~{importCommitResult.updated} skip: {importCommitResult.skipped} fail: {importCommitResult.failed}
+                import result: +{importCommitResult.created} ~{importCommitResult.updated} linked: {importCommitResult.linked} skip: {importCommitResult.skipped} fail: {importCommitResult.failed}
               </Typography>
             ) : null}
             {importErrorSummary ? (
@@ -691,6 +830,11 @@ export function ContactsWorkspace() {
                               {contact.emails[0]?.value ?? contact.phones[0]?.value ?? "No email/phone"}
                             </Typography>
                           </Stack>
+                          {contact.last_contacted ? (
+                            <Typography variant="caption" color="text.secondary" className="shrink-0">
+                              {lastContactedLabel(contact.last_contacted, Date.now())}
+                            </Typography>
+                          ) : null}
                         </Stack>
                       </Paper>
                     </div>
@@ -762,6 +906,7 @@ export function ContactsWorkspace() {
                 Reset Form
               </Button>
               <Button
+                disabled={!canWriteContacts}
                 size="small"
                 variant="contained"
                 startIcon={<PersonAddIcon fontSize="small" />}
@@ -776,7 +921,7 @@ export function ContactsWorkspace() {
               <Button
                 size="small"
                 variant="outlined"
-                disabled={!selectedContact}
+                disabled={!canWriteContacts || !selectedContact}
                 onClick={() => {
                   if (!draftValidationError) {
                     void updateSelectedFromDraft();
@@ -789,7 +934,7 @@ export function ContactsWorkspace() {
                 size="small"
                 variant="outlined"
                 color="error"
-                disabled={!selectedContact}
+                disabled={!canDeleteContacts || !selectedContact}
                 onClick={() => {
                   void removeSelectedContact();
                 }}
@@ -855,6 +1000,14 @@ export function ContactsWorkspace() {
               />
             </Stack>
 
+            <TextField
+              label="Categories"
+              helperText="Comma-separated"
+              value={formDraft.categories.join(",")}
+              onChange={(event) => updateDraftField("categories", event.target.value.split(","))}
+              size="small"
+            />
+
             <TextField
               label="Notes"
               value={formDraft.notes ?? ""}
@@ -1001,7 +1154,7 @@ export function ContactsWorkspace() {
             </Stack>
 
             {selectedContact ? (
-              <Stack direction="row" spacing={1}>
+              <Stack direction="row" spacing={1} alignItems="center">
                 <Button
                   size="small"
                   variant="outlined"
@@ -1022,6 +1175,49 @@ export function ContactsWorkspace() {
                 >
                   Call
                 </Button>
+                <Typography variant="caption" color="text.secondary">
+                  {lastContactedLabel(selectedContact.last_contacted, Date.now()) ?? "Never contacted"}
+                </Typography>
+              </Stack>
+            ) : null}
+
+            {selectedContact ? (
+              <Stack spacing={0.5}>
+                <Stack direction="row" spacing={1} alignItems="center" justifyContent="space-between">
+                  <Typography variant="subtitle2">Open tasks ({linkedTasks.length})</Typography>
+                  <Button
+                    size="small"
+                    disabled={!canWriteTasks}
+                    onClick={() => {
+                      void createTask({
+                        title: `Follow up with ${selectedContact.display_name || "contact"}`,
+                        description: "",
+                        project: null,
+                        tags: [],
+                        priority: null,
+                        due: null,
+                        wait: null,
+                        scheduled: null,
+                        contact: selectedContact.id
+                      });
+                    }}
+                  >
+                    Add follow-up task
+                  </Button>
+                </Stack>
+                {linkedTasks.map((task) => (
+                  <Button
+                    key={task.uuid}
+                    size="small"
+                    className="justify-start normal-case"
+                    onClick={() => {
+                      selectTask(task.uuid);
+                      setActiveTab("tasks");
+                    }}
+                  >
+                    {task.title || "Untitled Task"}
+                  </Button>
+                ))}
               </Stack>
             ) : null}
           </Stack>
@@ -1059,22 +1255,25 @@ export function ContactsWorkspace() {
                           onClick={() => {
                             setSelectedDedupeGroupId(group.group_id);
                             setSelectionIds(group.contacts.map((contact) => contact.id));
+                            void previewMergeSelected();
                           }}
                         >
                           Preview Merge
                         </Button>
                         <Button
+                          disabled={!canWriteContacts}
                           size="small"
                           variant="contained"
                           onClick={() => {
                             setSelectionIds(group.contacts.map((contact) => contact.id));
                             selectContact(group.contacts[0]?.id ?? null);
-                            void mergeSelected();
+                            openMergeDialog();
                           }}
                         >
                           Merge Group
                         </Button>
                         <Button
+                          disabled={!canWriteContacts}
                           size="small"
                           variant="outlined"
                           color="warning"
@@ -1123,6 +1322,7 @@ export function ContactsWorkspace() {
                             onClick={() => {
                               setSelectionIds(selectedDedupeGroup.contacts.map((item) => item.id));
                               selectContact(contact.id);
+                              void previewMergeSelected();
                             }}
                           >
                             Use As Merge Target
@@ -1131,9 +1331,9 @@ export function ContactsWorkspace() {
                       </Paper>
                     ))}
                   </Stack>
-                  {dedupePreviewContact ? (
+                  {mergePreview ? (
                     <Alert severity="info">
-                      Merge preview: {dedupePreviewContact.display_name || "Unnamed Contact"} · emails {dedupePreviewContact.emails.length} · phones {dedupePreviewContact.phones.length}
+                      Merge preview: {mergePreview.merged.display_name || "Unnamed Contact"} · emails {mergePreview.merged.emails.length} · phones {mergePreview.merged.phones.length}
                     </Alert>
                   ) : null}
                 </Stack>
@@ -1145,6 +1345,109 @@ export function ContactsWorkspace() {
         {error ? <Alert severity="error">{error}</Alert> : null}
         {loading ? <Alert severity="info">Working…</Alert> : null}
       </Stack>
+
+      <Dialog open={mergeDialogOpen} onClose={closeMergeDialog} maxWidth="sm" fullWidth>
+        <DialogTitle>Merge Contacts</DialogTitle>
+        <DialogContent dividers>
+          <Stack spacing={2}>
+            <TextField
+              select
+              size="small"
+              label="Keep fields from"
+              value={mergePreview?.merged.id ?? selectedContactId ?? ""}
+              onChange={(event) => {
+                selectContact(event.target.value);
+                void previewMergeSelected();
+              }}
+            >
+              {mergeCandidates.map((contact) => (
+                <MenuItem key={contact.id} value={contact.id}>
+                  {contact.display_name || "Unnamed Contact"}
+                </MenuItem>
+              ))}
+            </TextField>
+            {mergeCompareRows ? (
+              <FieldCompareTable
+                rows={mergeCompareRows}
+                leftLabel={mergeCandidates[0].display_name || "Unnamed Contact"}
+                rightLabel={mergeCandidates[1].display_name || "Unnamed Contact"}
+              />
+            ) : null}
+            {mergePreview ? (
+              <Stack spacing={0.5}>
+                <Typography variant="subtitle2">{mergePreview.merged.display_name || "Unnamed Contact"}</Typography>
+                <Typography variant="caption" color="text.secondary">
+                  emails: {mergePreview.merged.emails.map((item) => item.value).join(", ") || "-"}
+                </Typography>
+                <Typography variant="caption" color="text.secondary">
+                  phones: {mergePreview.merged.phones.map((item) => item.value).join(", ") || "-"}
+                </Typography>
+                <Typography variant="caption" color="text.secondary">
+                  org: {mergePreview.merged.organization ?? "-"}
+                </Typography>
+                <Alert severity="warning">
+                  Removes {mergePreview.removed_ids.length} contact(s):{" "}
+                  {mergePreview.removed_ids
+                    .map((id) => mergeCandidates.find((contact) => contact.id === id)?.display_name || id)
+                    .join(", ")}
+                </Alert>
+              </Stack>
+            ) : (
+              <Typography variant="body2" color="text.secondary">
+                Building preview…
+              </Typography>
+            )}
+          </Stack>
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={closeMergeDialog}>Cancel</Button>
+          <Button
+            variant="contained"
+            disabled={!canWriteContacts || !mergePreview || loading}
+            onClick={() => {
+              setMergeDialogOpen(false);
+              void mergeSelected();
+            }}
+          >
+            Merge
+          </Button>
+        </DialogActions>
+      </Dialog>
+
+      <Snackbar
+        key={lastDeleteUndo?.undoId}
+        open={lastDeleteUndo !== null}
+        autoHideDuration={10000}
+        anchorOrigin={{ vertical: "bottom", horizontal: "center" }}
+        onClose={(_event, reason) => {
+          if (reason !== "clickaway") {
+            dismissDeleteUndo();
+          }
+        }}
+      >
+        <Alert
+          severity="info"
+          variant="filled"
+          onClose={dismissDeleteUndo}
+          action={(
+            <Button
+              color="inherit"
+              size="small"
+              disabled={!canWriteContacts || loading}
+              onClick={() => {
+                void undoLastDelete();
+              }}
+            >
+              Undo
+            </Button>
+          )}
+        >
+          Deleted {lastDeleteUndo?.deleted ?? 0} contact(s)
+          {lastDeleteUndo?.linkedTasks
+            ? `; ${lastDeleteUndo.linkedTasks} open task(s) still link to them`
+            : ""}
+        </Alert>
+      </Snackbar>
     </div>
   );
 }
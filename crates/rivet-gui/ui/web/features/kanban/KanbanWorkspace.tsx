@@ -21,14 +21,35 @@ import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
 import { TagChip } from "../../components/TagChip";
+import { useConfirm } from "../../components/useConfirm";
 import { canManuallyCompleteTask } from "../../lib/calendar";
-import { boardIdFromTaskTags, humanizeLane, kanbanLaneFromTask } from "../../lib/tags";
+import {
+  KANBAN_BOARD_LIST_SORTS,
+  KANBAN_CARD_SORTS,
+  KANBAN_LANE_SORTS,
+  boardCardSort,
+  boardLaneSort,
+  sortBoards,
+  sortLanes
+} from "../../lib/kanbanSort";
+import { confirmCompleteSubtasks } from "../../lib/subtasks";
+import {
+  KANBAN_TAG_KEY,
+  boardIdFromTaskTags,
+  humanizeLane,
+  isKanbanRailTag,
+  kanbanLaneFromTask,
+  unknownTagValues
+} from "../../lib/tags";
+import { placeInOrder, sortTasks } from "../../lib/taskSort";
 import {
   useKanbanColumns,
-  useKanbanViewData
+  useKanbanViewData,
+  useTasksById
 } from "../../store/useAppStore";
-import { useKanbanWorkspaceSlice } from "../../store/slices";
+import { useCommandAllowed, useKanbanWorkspaceSlice } from "../../store/slices";
 import type { TaskDto } from "../../types/core";
+import type { KanbanBoardListSort, KanbanCardSort, KanbanLaneSort } from "../../types/ui";
 
 function KanbanCard(props: {
   task: TaskDto;
@@ -39,25 +60,40 @@ function KanbanCard(props: {
   onMove: (lane: string) => void;
   onMoveBoard: (boardId: string) => void;
   canDone: boolean;
+  canWrite: boolean;
+  canDelete: boolean;
   onDone: () => void;
   onUndone: () => void;
   onDelete: () => void;
   onDragStart: (event: DragEvent<HTMLDivElement>) => void;
   onDragEnd: () => void;
+  // Set while cards sort manually: dropping on this card places the dragged
+  // one before it.
+  onDropBefore?: (taskId: string) => void;
 }) {
   const lane = kanbanLaneFromTask(props.task.tags, props.columns, props.columns[0] ?? "todo");
   const laneIndex = props.columns.findIndex((entry) => entry === lane);
   const nextLane = props.columns[(laneIndex + 1) % props.columns.length] ?? lane;
   const showActionMove = props.columns.length > 1 && nextLane !== lane;
-  const canMoveBoard = props.boardOptions.length > 1;
+  const canMoveBoard = props.canWrite && props.boardOptions.length > 1;
 
   return (
     <Paper
-      draggable
+      draggable={props.canWrite}
       onDragStart={props.onDragStart}
       onDragEnd={props.onDragEnd}
+      onDrop={props.onDropBefore
+        ? (event) => {
+            const taskId = event.dataTransfer.getData("text/plain");
+            if (taskId) {
+              event.preventDefault();
+              event.stopPropagation();
+              props.onDropBefore?.(taskId);
+            }
+          }
+        : undefined}
       data-testid={`kanban-card-${props.task.uuid}`}
-      className="cursor-grab active:cursor-grabbing"
+      className={props.canWrite ? "cursor-grab active:cursor-grabbing" : undefined}
       sx={{
         p: 1.25
       }}
@@ -107,12 +143,12 @@ function KanbanCard(props: {
           </>
         ) : null}
         <Stack direction="row" spacing={1} flexWrap="wrap" useFlexGap>
-          {showActionMove ? (
+          {props.canWrite && showActionMove ? (
             <Button size="small" variant="outlined" startIcon={<DriveFileMoveIcon fontSize="small" />} onClick={() => props.onMove(nextLane)}>
               {humanizeLane(nextLane)}
             </Button>
           ) : null}
-          {(props.task.status === "Pending" || props.task.status === "Waiting") ? (
+          {props.canWrite && (props.task.status === "Pending" || props.task.status === "Waiting") ? (
             <Button
               size="small"
               variant="contained"
@@ -124,26 +160,104 @@ function KanbanCard(props: {
               Done
             </Button>
           ) : null}
-          {props.task.status === "Completed" ? (
+          {props.canWrite && props.task.status === "Completed" ? (
             <Button size="small" variant="outlined" color="warning" onClick={props.onUndone}>
               Uncomplete
             </Button>
           ) : null}
-          <Button size="small" variant="outlined" color="error" onClick={props.onDelete}>
-            Delete
-          </Button>
+          {props.canDelete ? (
+            <Button size="small" variant="outlined" color="error" onClick={props.onDelete}>
+              Delete
+            </Button>
+          ) : null}
         </Stack>
       </Stack>
     </Paper>
   );
 }
 
+const RAIL_TARGET_LIMIT = 12;
+
+// Side-rail drop targets: a card dropped here gets that project or tag and
+// keeps its lane.
+function KanbanDropRail(props: {
+  projects: string[];
+  tags: string[];
+  dragging: boolean;
+  onDropProject: (taskId: string, project: string) => void;
+  onDropTag: (taskId: string, tag: string) => void;
+}) {
+  const [overTarget, setOverTarget] = useState<string | null>(null);
+
+  const renderTarget = (id: string, label: string, onDrop: (taskId: string) => void) => (
+    <Box
+      key={id}
+      data-testid={`kanban-rail-${id}`}
+      onDragOver={(event) => {
+        event.preventDefault();
+        setOverTarget(id);
+      }}
+      onDragLeave={() => setOverTarget((current) => (current === id ? null : current))}
+      onDrop={(event) => {
+        event.preventDefault();
+        setOverTarget(null);
+        const taskId = event.dataTransfer.getData("text/plain");
+        if (taskId) {
+          onDrop(taskId);
+        }
+      }}
+      sx={{
+        border: "1px dashed",
+        borderColor: overTarget === id ? "primary.main" : props.dragging ? "primary.light" : "divider",
+        borderRadius: 1.5,
+        px: 1,
+        py: 0.5,
+        backgroundColor: overTarget === id ? "action.hover" : "transparent"
+      }}
+    >
+      <Typography variant="caption">{label}</Typography>
+    </Box>
+  );
+
+  return (
+    <Paper className="p-4">
+      <Stack spacing={1.25}>
+        <Typography variant="h6">Organize</Typography>
+        <Typography variant="caption" color="text.secondary">
+          Drop a card on a project to move it there, or on a tag to add that tag. Its lane stays the same.
+        </Typography>
+        <Typography variant="subtitle2">Set project</Typography>
+        {props.projects.length === 0 ? (
+          <Typography variant="caption" color="text.secondary">
+            No projects on this board
+          </Typography>
+        ) : (
+          <Stack direction="row" spacing={0.75} flexWrap="wrap" useFlexGap>
+            {props.projects.map((project) => renderTarget(`project:${project}`, `→ project:${project}`, (taskId) => props.onDropProject(taskId, project)))}
+          </Stack>
+        )}
+        <Typography variant="subtitle2">Add tag</Typography>
+        {props.tags.length === 0 ? (
+          <Typography variant="caption" color="text.secondary">
+            No tags on this board
+          </Typography>
+        ) : (
+          <Stack direction="row" spacing={0.75} flexWrap="wrap" useFlexGap>
+            {props.tags.map((tag) => renderTarget(`tag:${tag}`, `+ ${tag}`, (taskId) => props.onDropTag(taskId, tag)))}
+          </Stack>
+        )}
+      </Stack>
+    </Paper>
+  );
+}
+
 export function KanbanWorkspace() {
   const {
     error,
     loading,
     openAddTaskDialog,
     boards,
+    boardSort,
     activeBoardId,
     compactCards,
     draggingTaskId,
@@ -153,14 +267,21 @@ export function KanbanWorkspace() {
     createBoard,
     renameBoard,
     deleteBoard,
+    setBoardSort,
+    moveBoard,
+    setBoardView,
+    setCardOrder,
     toggleCompact,
     setDragging,
     setDragOver,
     moveTask,
     moveTaskToBoard,
+    dropOnProject,
+    dropOnTag,
     markTaskDone,
     markTaskUndone,
     removeTask,
+    remapTagValue,
     setStatusFilter,
     setProjectFilter,
     setTagFilter,
@@ -168,9 +289,12 @@ export function KanbanWorkspace() {
     setDueFilter,
     clearFilters
   } = useKanbanWorkspaceSlice();
+  const canWrite = useCommandAllowed("tasks_write");
+  const canDelete = useCommandAllowed("tasks_delete");
 
   const columns = useKanbanColumns();
   const { visibleTasks: tasks, projectFacets, tagFacets } = useKanbanViewData();
+  const tasksById = useTasksById();
   const [nowUtcMs, setNowUtcMs] = useState(() => Date.now());
 
   const activeBoard = boards.find((entry) => entry.id === activeBoardId) ?? null;
@@ -179,6 +303,8 @@ export function KanbanWorkspace() {
   const [createDraft, setCreateDraft] = useState("");
   const [renameOpen, setRenameOpen] = useState(false);
   const [renameDraft, setRenameDraft] = useState("");
+  const [draggingBoardId, setDraggingBoardId] = useState<string | null>(null);
+  const { requestConfirm, confirmDialog } = useConfirm();
 
   useEffect(() => {
     const intervalId = window.setInterval(() => {
@@ -187,13 +313,41 @@ export function KanbanWorkspace() {
     return () => window.clearInterval(intervalId);
   }, []);
 
+  const laneSort = boardLaneSort(activeBoard);
+  const cardSort = boardCardSort(activeBoard);
+  const cardOrder = activeBoard?.card_order;
+  const sortedBoards = useMemo(() => sortBoards(boards, boardSort), [boards, boardSort]);
+
   const tasksByLane = useMemo(() => {
     const fallbackLane = columns[0] ?? "todo";
-    return columns.map((column) => ({
+    const sorted = sortTasks(tasks, cardSort, { nowUtcMs, manualOrder: cardOrder });
+    return sortLanes(columns, laneSort).map((column) => ({
       column,
-      tasks: tasks.filter((task) => kanbanLaneFromTask(task.tags, columns, fallbackLane) === column)
+      tasks: sorted.filter((task) => kanbanLaneFromTask(task.tags, columns, fallbackLane) === column)
     }));
-  }, [columns, tasks]);
+  }, [columns, tasks, laneSort, cardSort, cardOrder, nowUtcMs]);
+
+  // Lane values the tag schema no longer lists (usually a renamed value); those
+  // cards fall back to the first lane until remapped.
+  const unknownLanes = useMemo(
+    () => unknownTagValues(tasks.map((task) => task.tags), KANBAN_TAG_KEY, columns),
+    [tasks, columns]
+  );
+  const staleLane = unknownLanes[0] ?? null;
+  const [remapTarget, setRemapTarget] = useState("");
+  const remapTo = columns.includes(remapTarget) ? remapTarget : (columns[0] ?? "");
+
+  // Manual drops rewrite the board's order from what is on screen, so cards
+  // hidden by filters keep their relative place at the end.
+  const placeCard = (taskId: string, lane: string, anchorId: string | null, position: "before" | "after") => {
+    const shown = tasksByLane.flatMap((entry) => entry.tasks.map((task) => task.uuid));
+    const rest = (cardOrder ?? []).filter((uuid) => !shown.includes(uuid));
+    setCardOrder(placeInOrder([...shown, ...rest], taskId, anchorId, position));
+    const task = tasksById.get(taskId);
+    if (task && kanbanLaneFromTask(task.tags, columns, columns[0] ?? "todo") !== lane) {
+      void moveTask(taskId, lane);
+    }
+  };
 
   const handleCreateBoard = () => {
     if (!createDraft.trim()) {
@@ -204,6 +358,43 @@ export function KanbanWorkspace() {
     setCreateOpen(false);
   };
 
+  const handleDeleteBoard = async () => {
+    if (!activeBoard) {
+      return;
+    }
+    const confirmed = await requestConfirm({
+      title: "Delete Board",
+      body: `Delete board "${activeBoard.name}"? Its tasks are kept and simply leave the board.`,
+      confirmLabel: "Delete",
+      destructive: true
+    });
+    if (confirmed) {
+      void deleteBoard();
+    }
+  };
+
+  const handleCompleteTask = async (task: TaskDto) => {
+    const completeSubtasks = await confirmCompleteSubtasks(task, tasksById, (message) => requestConfirm({
+      title: "Complete Subtasks",
+      body: message,
+      confirmLabel: "Complete All",
+      cancelLabel: "Only This Task"
+    }));
+    void markTaskDone(task.uuid, { completeSubtasks });
+  };
+
+  const handleDeleteTask = async (task: TaskDto) => {
+    const confirmed = await requestConfirm({
+      title: "Delete Task",
+      body: `Delete "${task.title || "Untitled Task"}"?`,
+      confirmLabel: "Delete",
+      destructive: true
+    });
+    if (confirmed) {
+      void removeTask(task.uuid);
+    }
+  };
+
   const handleRenameBoard = () => {
     if (!renameDraft.trim()) {
       return;
@@ -244,7 +435,7 @@ export function KanbanWorkspace() {
               startIcon={<DeleteIcon fontSize="small" />}
               disabled={!activeBoard}
               onClick={() => {
-                void deleteBoard();
+                void handleDeleteBoard();
               }}
             >
               Delete
@@ -276,11 +467,44 @@ export function KanbanWorkspace() {
             Add Task To Board
           </Button>
 
+          <TextField
+            select
+            size="small"
+            label="Sort boards"
+            value={boardSort}
+            onChange={(event) => setBoardSort(event.target.value as KanbanBoardListSort)}
+          >
+            {KANBAN_BOARD_LIST_SORTS.map((entry) => (
+              <MenuItem key={entry.id} value={entry.id}>
+                {entry.label}
+              </MenuItem>
+            ))}
+          </TextField>
+
           <Stack spacing={1} className="min-h-0 overflow-y-auto pr-1">
-            {boards.map((board) => (
+            {sortedBoards.map((board) => (
               <Button
                 key={board.id}
                 variant="outlined"
+                draggable={boardSort === "custom"}
+                onDragStart={(event) => {
+                  event.dataTransfer.setData("application/x-rivet-board", board.id);
+                  event.dataTransfer.effectAllowed = "move";
+                  setDraggingBoardId(board.id);
+                }}
+                onDragOver={(event) => {
+                  if (draggingBoardId) {
+                    event.preventDefault();
+                  }
+                }}
+                onDrop={(event) => {
+                  event.preventDefault();
+                  if (draggingBoardId) {
+                    moveBoard(draggingBoardId, board.id);
+                  }
+                  setDraggingBoardId(null);
+                }}
+                onDragEnd={() => setDraggingBoardId(null)}
                 onClick={() => setActiveBoard(board.id)}
                 className="!justify-start"
                 sx={{
@@ -308,10 +532,78 @@ export function KanbanWorkspace() {
             <Typography variant="h6">
               {activeBoard ? `Kanban: ${activeBoard.name}` : "Kanban"}
             </Typography>
-            <Typography variant="caption" color="text.secondary">
-              cards: {tasks.length}
-            </Typography>
+            <Stack direction="row" spacing={1} alignItems="center">
+              <TextField
+                select
+                size="small"
+                label="Lanes"
+                value={laneSort}
+                disabled={!activeBoard}
+                onChange={(event) => setBoardView({ lane_sort: event.target.value as KanbanLaneSort })}
+                sx={{ minWidth: 140 }}
+              >
+                {KANBAN_LANE_SORTS.map((entry) => (
+                  <MenuItem key={entry.id} value={entry.id}>
+                    {entry.label}
+                  </MenuItem>
+                ))}
+              </TextField>
+              <TextField
+                select
+                size="small"
+                label="Cards"
+                value={cardSort}
+                disabled={!activeBoard}
+                onChange={(event) => setBoardView({ card_sort: event.target.value as KanbanCardSort })}
+                sx={{ minWidth: 140 }}
+              >
+                {KANBAN_CARD_SORTS.map((entry) => (
+                  <MenuItem key={entry.id} value={entry.id}>
+                    {entry.label}
+                  </MenuItem>
+                ))}
+              </TextField>
+              <Typography variant="caption" color="text.secondary">
+                cards: {tasks.length}
+              </Typography>
+            </Stack>
           </Stack>
+          {staleLane && canWrite ? (
+            <Alert
+              severity="warning"
+              action={(
+                <Stack direction="row" spacing={1} alignItems="center">
+                  <TextField
+                    select
+                    size="small"
+                    label="Remap to"
+                    value={remapTo}
+                    onChange={(event) => setRemapTarget(event.target.value)}
+                    sx={{ minWidth: 140 }}
+                  >
+                    {columns.map((column) => (
+                      <MenuItem key={column} value={column}>
+                        {humanizeLane(column)}
+                      </MenuItem>
+                    ))}
+                  </TextField>
+                  <Button
+                    size="small"
+                    disabled={!remapTo}
+                    onClick={() => {
+                      void remapTagValue(KANBAN_TAG_KEY, staleLane.value, remapTo);
+                    }}
+                  >
+                    Remap
+                  </Button>
+                </Stack>
+              )}
+            >
+              {staleLane.count} card(s) use lane "{staleLane.value}", which the tag schema no longer lists, and are
+              shown in {humanizeLane(columns[0] ?? "todo")}. Remap rewrites {KANBAN_TAG_KEY}:{staleLane.value} on every
+              task.
+            </Alert>
+          ) : null}
           <div className="grid min-h-0 flex-1 grid-cols-3 gap-2">
             {tasksByLane.map((entry) => (
               <Box
@@ -324,8 +616,12 @@ export function KanbanWorkspace() {
                 onDrop={(event) => {
                   event.preventDefault();
                   const taskId = event.dataTransfer.getData("text/plain");
-                  if (taskId) {
-                    void moveTask(taskId, entry.column);
+                  if (taskId && canWrite) {
+                    if (cardSort === "manual") {
+                      placeCard(taskId, entry.column, entry.tasks.at(-1)?.uuid ?? null, "after");
+                    } else {
+                      void moveTask(taskId, entry.column);
+                    }
                   }
                   setDragging(null);
                   setDragOver(null);
@@ -368,14 +664,16 @@ export function KanbanWorkspace() {
                             void moveTaskToBoard(task.uuid, boardId, entry.column);
                           }}
                           canDone={canManuallyCompleteTask(task, nowUtcMs)}
+                          canWrite={canWrite}
+                          canDelete={canDelete}
                           onDone={() => {
-                            void markTaskDone(task.uuid);
+                            void handleCompleteTask(task);
                           }}
                           onUndone={() => {
                             void markTaskUndone(task.uuid);
                           }}
                           onDelete={() => {
-                            void removeTask(task.uuid);
+                            void handleDeleteTask(task);
                           }}
                           onDragStart={(event) => {
                             event.dataTransfer.setData("text/plain", task.uuid);
@@ -386,6 +684,13 @@ export function KanbanWorkspace() {
                             setDragging(null);
                             setDragOver(null);
                           }}
+                          onDropBefore={canWrite && cardSort === "manual"
+                            ? (taskId) => {
+                                placeCard(taskId, entry.column, task.uuid, "before");
+                                setDragging(null);
+                                setDragOver(null);
+                              }
+                            : undefined}
                         />
                       ))
                     )}
@@ -403,6 +708,25 @@ export function KanbanWorkspace() {
       </Paper>
 
       <Stack spacing={2} className="min-h-0">
+        {canWrite ? (
+          <KanbanDropRail
+            projects={projectFacets
+              .map((entry) => entry.value)
+              .filter((value) => value.length > 0)
+              .slice(0, RAIL_TARGET_LIMIT)}
+            tags={tagFacets
+              .map((entry) => entry.value)
+              .filter(isKanbanRailTag)
+              .slice(0, RAIL_TARGET_LIMIT)}
+            dragging={draggingTaskId !== null}
+            onDropProject={(taskId, project) => {
+              void dropOnProject(taskId, project);
+            }}
+            onDropTag={(taskId, tag) => {
+              void dropOnTag(taskId, tag);
+            }}
+          />
+        ) : null}
         <Paper className="p-4">
           <Stack spacing={1}>
             <Typography variant="h6">Kanban Summary</Typography>
@@ -426,6 +750,7 @@ export function KanbanWorkspace() {
               <MenuItem value="Waiting">Waiting</MenuItem>
               <MenuItem value="Completed">Completed</MenuItem>
               <MenuItem value="Deleted">Deleted</MenuItem>
+              <MenuItem value="Archived">Archived</MenuItem>
             </TextField>
             <TextField
               select
@@ -525,6 +850,7 @@ export function KanbanWorkspace() {
           </Button>
         </DialogActions>
       </Dialog>
+      {confirmDialog}
     </div>
   );
 }
@@ -2,22 +2,49 @@ import { useEffect, useMemo, useState } from "react";
 
 import Alert from "@mui/material/Alert";
 import Button from "@mui/material/Button";
+import FormControlLabel from "@mui/material/FormControlLabel";
 import MenuItem from "@mui/material/MenuItem";
 import Paper from "@mui/material/Paper";
 import Stack from "@mui/material/Stack";
+import Switch from "@mui/material/Switch";
 import TextField from "@mui/material/TextField";
 import Typography from "@mui/material/Typography";
 
+import { FacetPanel } from "../../components/FacetPanel";
+import { QuickFilterChips } from "../../components/QuickFilterChips";
+import { SaveTemplateDialog } from "../../components/SaveTemplateDialog";
+import { TaskCompareDialog } from "../../components/TaskCompareDialog";
 import { TaskEditDialog } from "../../components/TaskEditDialog";
 import { TaskDetailsPanel } from "../../components/TaskDetailsPanel";
+import { useConfirm } from "../../components/useConfirm";
+import { CompletedTasksPanel } from "../../components/CompletedTasksPanel";
+import { DuplicateTasksPanel } from "../../components/DuplicateTasksPanel";
+import { StaleTasksPanel } from "../../components/StaleTasksPanel";
 import { TaskListPanel } from "../../components/TaskListPanel";
-import { canManuallyCompleteTask, isCalendarEventTask } from "../../lib/calendar";
+import { captureTag, isInboxTask } from "../../lib/capture";
+import { canManuallyCompleteTask, isCalendarEventTask, resolveCalendarConfig } from "../../lib/calendar";
+import { formatTaskDate, resolveDateFormat, resolveHumanizeStyle } from "../../lib/dateFormat";
+import { groupTasksByDue } from "../../lib/dueBuckets";
+import { explainQuickFilters } from "../../lib/quickFilters";
+import { resolveRowActions, rowActionsFor } from "../../lib/rowActions";
+import { resolveIdDisplay } from "../../lib/taskId";
+import { snoozePatch, snoozeUntilUtcMs, type SnoozePreset } from "../../lib/snooze";
+import { resolveDraftAutosaveMs } from "../../lib/taskDraft";
+import { resolveTitleLimit } from "../../lib/titleLimit";
+import { confirmCompleteSubtasks } from "../../lib/subtasks";
 import { pushTagUnique, splitTags } from "../../lib/tags";
 import {
+  useAppStore,
+  useExternalCalendarColorMap,
   useSelectedTask,
-  useTaskViewData
+  useTaskViewData,
+  useTasksById
 } from "../../store/useAppStore";
-import { useTaskWorkspaceSlice } from "../../store/slices";
+import { useContactsStore } from "../../store/useContactsStore";
+import { explainTaskFilters, facetBulkPatches, unusedTagNames } from "../../store/selectors";
+import { useCommandAllowed, useTaskWorkspaceSlice } from "../../store/slices";
+import type { ContactDto, TaskDedupeGroup, TaskDto } from "../../types/core";
+import type { FacetBulkAction } from "../../types/ui";
 
 export function TasksWorkspace() {
   const {
@@ -27,6 +54,26 @@ export function TasksWorkspace() {
     tagSchema,
     tagColorMap,
     kanbanBoards,
+    runtimeConfig,
+    groupByDue,
+    toggleGroupByDue,
+    taskTemplates,
+    saveTaskAsTemplate,
+    staleTasks,
+    loadStaleTasks,
+    duplicateGroups,
+    loadDuplicateGroups,
+    resolveDuplicates,
+    tagUsage,
+    loadTagUsage,
+    cleanupUnusedTags,
+    completedTasks,
+    completedRange,
+    loadCompletedTasks,
+    setCompletedRange,
+    taskHistory,
+    loadTaskHistory,
+    pendingTaskOps,
     filters,
     setSearchFilter,
     setStatusFilter,
@@ -34,33 +81,67 @@ export function TasksWorkspace() {
     setTagFilter,
     setPriorityFilter,
     setDueFilter,
+    quickFilters,
+    toggleQuickFilter,
     clearFilters,
     selectTask,
     updateTask,
     markTaskDone,
     markTaskUndone,
+    startTaskClock,
+    stopTaskClock,
     removeTask,
     markTasksDoneBulk,
     markTasksUndoneBulk,
-    removeTasksBulk
+    removeTasksBulk,
+    updateTasksBulk
   } = useTaskWorkspaceSlice();
 
-  const { visibleTasks, projectFacets, tagFacets } = useTaskViewData();
+  const [nowUtcMs, setNowUtcMs] = useState(() => Date.now());
+  const calendarConfig = useMemo(() => resolveCalendarConfig(runtimeConfig), [runtimeConfig]);
+  const { visibleTasks, projectFacets, tagFacets, quickFilterCounts } = useTaskViewData(
+    nowUtcMs,
+    calendarConfig.timezone
+  );
+  const canWrite = useCommandAllowed("tasks_write");
+  const canDelete = useCommandAllowed("tasks_delete");
   const selectedTask = useSelectedTask();
+  const visibilityReasons = useMemo(
+    () => selectedTask
+      ? [
+        ...explainTaskFilters(selectedTask, filters),
+        ...explainQuickFilters(selectedTask, quickFilters, nowUtcMs, calendarConfig.timezone)
+      ]
+      : [],
+    [calendarConfig.timezone, filters, nowUtcMs, quickFilters, selectedTask]
+  );
+  const linkedContactId = selectedTask?.contact ?? null;
+  const linkedContact = useContactsStore((state) => (linkedContactId ? state.linkedContacts[linkedContactId] : undefined));
+  const setActiveTab = useAppStore((state) => state.setActiveTab);
+  useEffect(() => {
+    if (linkedContactId) {
+      void useContactsStore.getState().resolveLinkedContact(linkedContactId);
+    }
+  }, [linkedContactId]);
+  const { requestConfirm, confirmDialog } = useConfirm();
   const [searchInput, setSearchInput] = useState(filters.search);
   const [editOpen, setEditOpen] = useState(false);
+  const [templateOpen, setTemplateOpen] = useState(false);
+  const [compareIds, setCompareIds] = useState<[string, string] | null>(null);
   const [selectMode, setSelectMode] = useState(false);
   const [selectedTaskIds, setSelectedTaskIds] = useState<string[]>([]);
   const [lastSelectedIndex, setLastSelectedIndex] = useState<number | null>(null);
   const [bulkProjectInput, setBulkProjectInput] = useState("");
   const [bulkTagInput, setBulkTagInput] = useState("");
-  const [nowUtcMs, setNowUtcMs] = useState(() => Date.now());
 
   useEffect(() => {
     if (!selectedTask && editOpen) {
       setEditOpen(false);
     }
-  }, [selectedTask, editOpen]);
+    if (!selectedTask && templateOpen) {
+      setTemplateOpen(false);
+    }
+  }, [selectedTask, editOpen, templateOpen]);
 
   useEffect(() => {
     const timeout = window.setTimeout(() => {
@@ -76,6 +157,27 @@ export function TasksWorkspace() {
     return () => window.clearInterval(intervalId);
   }, []);
 
+  useEffect(() => {
+    void loadStaleTasks();
+  }, [loadStaleTasks, runtimeConfig, visibleTasks]);
+
+  useEffect(() => {
+    void loadDuplicateGroups();
+  }, [loadDuplicateGroups, runtimeConfig, visibleTasks]);
+
+  useEffect(() => {
+    void loadTagUsage();
+  }, [loadTagUsage, visibleTasks]);
+
+  useEffect(() => {
+    void loadCompletedTasks();
+  }, [loadCompletedTasks, completedRange, visibleTasks]);
+
+  const selectedTaskModified = selectedTask?.modified ?? null;
+  useEffect(() => {
+    void loadTaskHistory(selectedTaskId);
+  }, [loadTaskHistory, selectedTaskId, selectedTaskModified]);
+
   useEffect(() => {
     const visibleIdSet = new Set(visibleTasks.map((task) => task.uuid));
     setSelectedTaskIds((previous) => {
@@ -90,7 +192,32 @@ export function TasksWorkspace() {
     }
   }, [lastSelectedIndex, visibleTasks]);
 
-  const visibleTaskIds = useMemo(() => visibleTasks.map((task) => task.uuid), [visibleTasks]);
+  const calendarColorMap = useExternalCalendarColorMap();
+  const tasksById = useTasksById();
+  const dateFormat = useMemo(() => resolveDateFormat(runtimeConfig), [runtimeConfig]);
+  const unusedTags = useMemo(() => unusedTagNames(tagUsage), [tagUsage]);
+  const humanizeStyle = useMemo(() => resolveHumanizeStyle(runtimeConfig), [runtimeConfig]);
+  const configuredRowActions = useMemo(() => resolveRowActions(runtimeConfig), [runtimeConfig]);
+  const idDisplay = useMemo(() => resolveIdDisplay(runtimeConfig), [runtimeConfig]);
+  const draftAutosaveMs = useMemo(() => resolveDraftAutosaveMs(runtimeConfig), [runtimeConfig]);
+  const titleLimit = useMemo(() => resolveTitleLimit(runtimeConfig), [runtimeConfig]);
+  const inboxTag = captureTag(runtimeConfig);
+  const inboxCount = useMemo(
+    () => [...tasksById.values()].filter((task) => isInboxTask(task, inboxTag)).length,
+    [inboxTag, tasksById]
+  );
+  const inboxActive = filters.tag === inboxTag && filters.status === "Pending";
+  const dueGroups = useMemo(
+    () => (groupByDue
+      ? groupTasksByDue(visibleTasks, nowUtcMs, calendarConfig.timezone, calendarConfig.policies.week_start)
+      : null),
+    [calendarConfig.timezone, calendarConfig.policies.week_start, groupByDue, nowUtcMs, visibleTasks]
+  );
+  const listTasks = useMemo(
+    () => (dueGroups ? dueGroups.flatMap((group) => group.tasks) : visibleTasks),
+    [dueGroups, visibleTasks]
+  );
+  const visibleTaskIds = useMemo(() => listTasks.map((task) => task.uuid), [listTasks]);
   const selectedTaskSet = useMemo(() => new Set(selectedTaskIds), [selectedTaskIds]);
   const selectedTasks = useMemo(
     () => visibleTasks.filter((task) => selectedTaskSet.has(task.uuid)),
@@ -114,6 +241,16 @@ export function TasksWorkspace() {
     .filter((task) => task.status === "Completed")
     .map((task) => task.uuid);
   const selectedDeleteIds = selectedTasks.map((task) => task.uuid);
+  const compareTasks = useMemo((): [TaskDto, TaskDto] | null => {
+    const left = compareIds ? tasksById.get(compareIds[0]) : undefined;
+    const right = compareIds ? tasksById.get(compareIds[1]) : undefined;
+    return left && right ? [left, right] : null;
+  }, [compareIds, tasksById]);
+  const formatCompareDate = useMemo(
+    () => (raw: string) => formatTaskDate(raw, dateFormat, calendarConfig.timezone, nowUtcMs),
+    [calendarConfig.timezone, dateFormat, nowUtcMs]
+  );
+  const facetTargetIds = selectMode ? selectedDeleteIds : selectedTaskId ? [selectedTaskId] : [];
 
   const doneBlockedMessage = selectedTask
     && (selectedTask.status === "Pending" || selectedTask.status === "Waiting")
@@ -200,20 +337,128 @@ export function TasksWorkspace() {
     setBulkTagInput("");
   };
 
+  const confirmBulkDelete = async (uuids: string[], scope: "filtered" | "selected") => {
+    const confirmed = await requestConfirm({
+      title: scope === "filtered" ? "Delete Filtered Tasks" : "Delete Selected Tasks",
+      body: `Delete ${uuids.length} ${scope} task(s)? This cannot be undone.`,
+      confirmLabel: "Delete",
+      destructive: true
+    });
+    if (confirmed) {
+      void removeTasksBulk(uuids);
+    }
+  };
+
+  const confirmDelete = async (uuid: string) => {
+    const task = tasksById.get(uuid);
+    const confirmed = await requestConfirm({
+      title: "Delete Task",
+      body: `Delete "${task?.title || "Untitled Task"}"?`,
+      confirmLabel: "Delete",
+      destructive: true
+    });
+    if (confirmed) {
+      void removeTask(uuid);
+    }
+  };
+
+  const confirmResolveDuplicates = async (groups: TaskDedupeGroup[], merge: boolean) => {
+    const extra = groups.reduce((count, group) => count + group.tasks.length - 1, 0);
+    const confirmed = await requestConfirm({
+      title: merge ? "Merge Duplicates" : "Delete Duplicates",
+      body: merge
+        ? `Merge ${extra} duplicate task(s) into the oldest task of ${groups.length} group(s)? The duplicates are deleted.`
+        : `Delete ${extra} duplicate task(s) from ${groups.length} group(s) without merging their details?`,
+      confirmLabel: merge ? "Merge" : "Delete",
+      destructive: !merge
+    });
+    if (confirmed) {
+      void resolveDuplicates(groups.map((group) => group.tasks.map((task) => task.uuid)), merge);
+    }
+  };
+
+  const completeTask = async (uuid: string) => {
+    const completeSubtasks = await confirmCompleteSubtasks(tasksById.get(uuid), tasksById, (message) => requestConfirm({
+      title: "Complete Subtasks",
+      body: message,
+      confirmLabel: "Complete All",
+      cancelLabel: "Only This Task"
+    }));
+    void markTaskDone(uuid, { completeSubtasks });
+  };
+
+  const snoozeTask = (uuid: string, preset: SnoozePreset) => {
+    const task = visibleTasks.find((entry) => entry.uuid === uuid);
+    if (!task) {
+      return;
+    }
+    void updateTask(uuid, snoozePatch(task, snoozeUntilUtcMs(preset, Date.now(), calendarConfig.timezone)));
+  };
+
+  const applyFacetAction = (action: FacetBulkAction) => {
+    const updates = facetBulkPatches(visibleTasks, facetTargetIds, action);
+    if (updates.length === 0) {
+      return;
+    }
+    if (action.operation === "rename") {
+      if (action.facet === "tag" && filters.tag === action.value) {
+        setTagFilter(action.renameTo ?? "");
+      } else if (action.facet === "project" && filters.project === action.value) {
+        setProjectFilter(action.renameTo ?? "");
+      }
+    }
+    void updateTasksBulk(updates);
+  };
+
   return (
     <div className="grid h-full min-h-0 grid-cols-[minmax(0,1fr)_360px] gap-3 p-3">
       <TaskListPanel
-        tasks={visibleTasks}
+        tasks={listTasks}
+        groups={dueGroups}
+        calendarColors={calendarColorMap}
+        tasksById={tasksById}
+        pendingTaskOps={pendingTaskOps}
         selectedTaskId={selectedTaskId}
         selectMode={selectMode}
         selectedTaskIds={selectedTaskIds}
         onTaskClick={handleTaskClick}
+        onReopen={canWrite ? markTaskUndone : undefined}
+        idDisplay={idDisplay}
+        toolbar={<QuickFilterChips active={quickFilters} counts={quickFilterCounts} onToggle={toggleQuickFilter} />}
+        rowActions={{
+          actionsFor: (task) => rowActionsFor(task, configuredRowActions, { canWrite, canDelete, nowUtcMs }),
+          onAction: (uuid, action) => {
+            if (action === "done") {
+              void completeTask(uuid);
+            } else if (action === "delete") {
+              void confirmDelete(uuid);
+            } else {
+              selectTask(uuid);
+              setEditOpen(true);
+            }
+          },
+          onSnooze: snoozeTask
+        }}
       />
 
       <Stack spacing={2} className="min-h-0">
         <Paper className="p-4">
           <Stack spacing={2}>
             <Typography variant="h6">Task Filters</Typography>
+            <Button
+              variant={inboxActive ? "contained" : "outlined"}
+              onClick={() => {
+                if (inboxActive) {
+                  setTagFilter("");
+                  setStatusFilter("all");
+                  return;
+                }
+                setTagFilter(inboxTag);
+                setStatusFilter("Pending");
+              }}
+            >
+              Inbox ({inboxCount})
+            </Button>
             <TextField
               label="Search"
               value={searchInput}
@@ -232,6 +477,7 @@ export function TasksWorkspace() {
               <MenuItem value="Waiting">Waiting</MenuItem>
               <MenuItem value="Completed">Completed</MenuItem>
               <MenuItem value="Deleted">Deleted</MenuItem>
+              <MenuItem value="Archived">Archived</MenuItem>
             </TextField>
             <TextField
               select
@@ -285,6 +531,10 @@ export function TasksWorkspace() {
               <MenuItem value="has_due">Has due</MenuItem>
               <MenuItem value="no_due">No due</MenuItem>
             </TextField>
+            <FormControlLabel
+              control={<Switch size="small" checked={groupByDue} onChange={toggleGroupByDue} />}
+              label="Group by due"
+            />
             <Button
               variant="outlined"
               onClick={() => {
@@ -323,7 +573,7 @@ export function TasksWorkspace() {
               <Button
                 variant="contained"
                 color="success"
-                disabled={loading || doneCandidateIds.length === 0}
+                disabled={!canWrite || loading || doneCandidateIds.length === 0}
                 onClick={() => {
                   void markTasksDoneBulk(doneCandidateIds);
                 }}
@@ -333,7 +583,7 @@ export function TasksWorkspace() {
               <Button
                 variant="outlined"
                 color="warning"
-                disabled={loading || undoneCandidateIds.length === 0}
+                disabled={!canWrite || loading || undoneCandidateIds.length === 0}
                 onClick={() => {
                   void markTasksUndoneBulk(undoneCandidateIds);
                 }}
@@ -343,12 +593,9 @@ export function TasksWorkspace() {
               <Button
                 variant="outlined"
                 color="error"
-                disabled={loading || deleteCandidateIds.length === 0}
+                disabled={!canDelete || loading || deleteCandidateIds.length === 0}
                 onClick={() => {
-                  if (!window.confirm(`Delete ${deleteCandidateIds.length} filtered task(s)? This cannot be undone.`)) {
-                    return;
-                  }
-                  void removeTasksBulk(deleteCandidateIds);
+                  void confirmBulkDelete(deleteCandidateIds, "filtered");
                 }}
               >
                 Delete Filtered ({deleteCandidateIds.length})
@@ -367,7 +614,7 @@ export function TasksWorkspace() {
                     <Button
                       variant="contained"
                       color="success"
-                      disabled={loading || selectedDoneIds.length === 0}
+                      disabled={!canWrite || loading || selectedDoneIds.length === 0}
                       onClick={() => {
                         void markTasksDoneBulk(selectedDoneIds);
                       }}
@@ -377,7 +624,7 @@ export function TasksWorkspace() {
                     <Button
                       variant="outlined"
                       color="warning"
-                      disabled={loading || selectedUndoneIds.length === 0}
+                      disabled={!canWrite || loading || selectedUndoneIds.length === 0}
                       onClick={() => {
                         void markTasksUndoneBulk(selectedUndoneIds);
                       }}
@@ -387,16 +634,20 @@ export function TasksWorkspace() {
                     <Button
                       variant="outlined"
                       color="error"
-                      disabled={loading || selectedDeleteIds.length === 0}
+                      disabled={!canDelete || loading || selectedDeleteIds.length === 0}
                       onClick={() => {
-                        if (!window.confirm(`Delete ${selectedDeleteIds.length} selected task(s)? This cannot be undone.`)) {
-                          return;
-                        }
-                        void removeTasksBulk(selectedDeleteIds);
+                        void confirmBulkDelete(selectedDeleteIds, "selected");
                       }}
                     >
                       Delete Selected ({selectedDeleteIds.length})
                     </Button>
+                    <Button
+                      variant="outlined"
+                      disabled={selectedDeleteIds.length !== 2}
+                      onClick={() => setCompareIds([selectedDeleteIds[0], selectedDeleteIds[1]])}
+                    >
+                      Compare
+                    </Button>
                   </Stack>
                   <TextField
                     size="small"
@@ -407,7 +658,7 @@ export function TasksWorkspace() {
                   />
                   <Button
                     variant="outlined"
-                    disabled={loading || selectedDeleteIds.length === 0}
+                    disabled={!canWrite || loading || selectedDeleteIds.length === 0}
                     onClick={() => {
                       void applyProjectToSelected();
                     }}
@@ -423,7 +674,7 @@ export function TasksWorkspace() {
                   />
                   <Button
                     variant="outlined"
-                    disabled={loading || selectedDeleteIds.length === 0 || splitTags(bulkTagInput).length === 0}
+                    disabled={!canWrite || loading || selectedDeleteIds.length === 0 || splitTags(bulkTagInput).length === 0}
                     onClick={() => {
                       void applyTagToSelected();
                     }}
@@ -438,15 +689,123 @@ export function TasksWorkspace() {
           </Stack>
         </Paper>
 
+        <FacetPanel
+          projectFacets={projectFacets}
+          tagFacets={tagFacets}
+          unusedTags={unusedTags}
+          selectedCount={facetTargetIds.length}
+          busy={loading}
+          onNavigate={(facet, value) => {
+            if (facet === "tag") {
+              setTagFilter(value);
+            } else {
+              setProjectFilter(value);
+            }
+          }}
+          onAction={applyFacetAction}
+          onCleanupUnusedTags={() => {
+            void cleanupUnusedTags();
+          }}
+        />
+
+        <StaleTasksPanel
+          tasks={staleTasks}
+          days={runtimeConfig?.stale?.days ?? 14}
+          nowUtcMs={nowUtcMs}
+          selectedTaskId={selectedTaskId}
+          onSelect={selectTask}
+          onRefresh={() => {
+            void loadStaleTasks();
+          }}
+        />
+
+        <DuplicateTasksPanel
+          groups={duplicateGroups}
+          threshold={runtimeConfig?.dedupe?.threshold ?? 80}
+          selectedTaskId={selectedTaskId}
+          busy={loading}
+          onSelect={selectTask}
+          onRefresh={() => {
+            void loadDuplicateGroups();
+          }}
+          onResolve={canDelete
+            ? (groups, merge) => {
+              void confirmResolveDuplicates(groups, merge);
+            }
+            : undefined}
+        />
+
+        <CompletedTasksPanel
+          tasks={completedTasks}
+          range={completedRange}
+          timezone={calendarConfig.timezone}
+          dateFormat={dateFormat}
+          nowUtcMs={nowUtcMs}
+          selectedTaskId={selectedTaskId}
+          busy={loading}
+          onRangeChange={setCompletedRange}
+          onSelect={selectTask}
+          onReopen={canWrite
+            ? (uuid) => {
+              void markTaskUndone(uuid).then(() => loadCompletedTasks());
+            }
+            : undefined}
+          onRefresh={() => {
+            void loadCompletedTasks();
+          }}
+        />
+
         <TaskDetailsPanel
           task={selectedTask}
-          busy={loading}
+          history={taskHistory}
+          busy={loading || Boolean(selectedTask && pendingTaskOps[selectedTask.uuid])}
           onEdit={() => setEditOpen(true)}
-          onDone={markTaskDone}
+          onDone={(uuid) => {
+            void completeTask(uuid);
+          }}
           onUndone={markTaskUndone}
-          onDelete={removeTask}
+          canWrite={canWrite}
+          canDelete={canDelete}
+          onDelete={(uuid) => {
+            void confirmDelete(uuid);
+          }}
+          onAppend={(uuid, text) => {
+            const task = visibleTasks.find((entry) => entry.uuid === uuid);
+            if (!task) {
+              return;
+            }
+            void updateTask(uuid, { title: `${task.title} ${text}`.trim() });
+          }}
+          onSnooze={snoozeTask}
+          onStart={(uuid) => {
+            void startTaskClock(uuid);
+          }}
+          onStop={(uuid) => {
+            void stopTaskClock(uuid);
+          }}
+          onSaveTemplate={() => setTemplateOpen(true)}
+          idDisplay={idDisplay}
           canMarkDone={canSelectedTaskBeDone}
           doneBlockedMessage={doneBlockedMessage}
+          dateFormat={dateFormat}
+          humanizeStyle={humanizeStyle}
+          timezone={calendarConfig.timezone}
+          backendTimezone={calendarConfig.backend_timezone}
+          dueDefaultTime={calendarConfig.due_default_time}
+          nowUtcMs={nowUtcMs}
+          visibilityReasons={visibilityReasons}
+          linkedContact={linkedContact}
+          onOpenContact={(contact: ContactDto) => {
+            setActiveTab("contacts");
+            void useContactsStore.getState().showContact(contact);
+          }}
+          onContactAction={(contact, action) => {
+            const value = action === "mailto" ? contact.emails[0]?.value : contact.phones[0]?.value;
+            void useContactsStore.getState().openAction({ id: contact.id, action, value: value ?? null });
+          }}
+          onClearContact={(uuid) => {
+            void updateTask(uuid, { contact: null });
+          }}
         />
       </Stack>
 
@@ -457,12 +816,42 @@ export function TasksWorkspace() {
         tagSchema={tagSchema}
         tagColorMap={tagColorMap}
         kanbanBoards={kanbanBoards}
+        draftAutosaveMs={draftAutosaveMs}
+        titleLimit={titleLimit}
         onClose={() => setEditOpen(false)}
         onSubmit={async (uuid, patch) => {
           const updated = await updateTask(uuid, patch);
           return updated !== null;
         }}
       />
+      <SaveTemplateDialog
+        open={templateOpen}
+        task={selectedTask}
+        templates={taskTemplates}
+        onClose={() => setTemplateOpen(false)}
+        onSave={(name, includeDue) => {
+          if (selectedTask) {
+            saveTaskAsTemplate(selectedTask.uuid, name, includeDue);
+          }
+          setTemplateOpen(false);
+        }}
+      />
+      <TaskCompareDialog
+        open={compareIds !== null}
+        tasks={compareTasks}
+        idDisplay={idDisplay}
+        canWrite={canWrite && !loading}
+        canDelete={canDelete && !loading}
+        formatDate={formatCompareDate}
+        onClose={() => setCompareIds(null)}
+        onMergeDescriptions={(uuid, description) => {
+          void updateTask(uuid, { description });
+        }}
+        onDelete={(uuid) => {
+          void confirmDelete(uuid);
+        }}
+      />
+      {confirmDialog}
     </div>
   );
 }
@@ -0,0 +1,82 @@
+import { describe, expect, it } from "vitest";
+
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+import { agendaCounts, agendaMessage, inQuietHours, resolveQuietHours } from "./agenda";
+
+const TZ = "America/Mexico_City";
+// Tuesday 2026-02-17 06:00 local.
+const NOW = Date.UTC(2026, 1, 17, 12, 0, 0);
+
+function makeTask(uuid: string, due: string | null, status: TaskDto["status"] = "Pending"): TaskDto {
+  return {
+    uuid,
+    id: null,
+    title: uuid,
+    description: "",
+    status,
+    project: null,
+    tags: [],
+    priority: null,
+    due,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null
+  };
+}
+
+function quietConfig(quiet_hours: string): RivetRuntimeConfig {
+  return { notifications: { due: { quiet_hours } } };
+}
+
+describe("agendaCounts", () => {
+  it("counts pending tasks due today and overdue", () => {
+    const counts = agendaCounts(
+      [
+        makeTask("late", "20260210T180000Z"),
+        makeTask("earlier-today", "20260217T110000Z"),
+        makeTask("today", "20260217T230000Z"),
+        makeTask("tomorrow", "20260218T180000Z"),
+        makeTask("waiting", "20260217T230000Z", "Waiting"),
+        makeTask("done", "20260217T230000Z", "Completed"),
+        makeTask("none", null)
+      ],
+      NOW,
+      TZ,
+      "monday"
+    );
+    expect(counts).toEqual({ overdue: 2, today: 1 });
+    expect(agendaMessage(counts)).toBe("1 due today, 2 overdue.");
+    expect(agendaMessage({ overdue: 0, today: 0 })).toBe("Nothing due today.");
+  });
+});
+
+describe("quiet hours", () => {
+  it("parses HH:MM-HH:MM and rejects anything else", () => {
+    expect(resolveQuietHours(null)).toBeNull();
+    expect(resolveQuietHours(quietConfig(""))).toBeNull();
+    expect(resolveQuietHours(quietConfig("22:00-07:30"))).toEqual({ startMinute: 1320, endMinute: 450 });
+    expect(resolveQuietHours(quietConfig("25:00-07:00"))).toBeNull();
+    expect(resolveQuietHours(quietConfig("09:00-09:00"))).toBeNull();
+    expect(resolveQuietHours(quietConfig("late"))).toBeNull();
+  });
+
+  it("checks the window in the given timezone, wrapping past midnight", () => {
+    const overnight = resolveQuietHours(quietConfig("22:00-07:00"));
+    expect(inQuietHours(overnight, NOW, TZ)).toBe(true);
+    expect(inQuietHours(overnight, NOW, "UTC")).toBe(false);
+    expect(inQuietHours(overnight, Date.UTC(2026, 1, 17, 13, 0, 0), TZ)).toBe(false);
+
+    const lunch = resolveQuietHours(quietConfig("12:00-13:00"));
+    expect(inQuietHours(lunch, NOW, "UTC")).toBe(true);
+    expect(inQuietHours(lunch, NOW, TZ)).toBe(false);
+    expect(inQuietHours(null, NOW, TZ)).toBe(false);
+  });
+});
@@ -0,0 +1,97 @@
+import { zonedDateTimeParts } from "./calendar";
+import { dueBucketFor } from "./dueBuckets";
+import { logger } from "./logger";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+import type { CalendarWeekStart } from "../types/ui";
+
+export interface AgendaCounts {
+  overdue: number;
+  // Due later today; overdue tasks are not counted again.
+  today: number;
+}
+
+// Minutes after local midnight; a window whose end is before its start wraps
+// past midnight (22:00-07:00).
+export interface QuietHours {
+  startMinute: number;
+  endMinute: number;
+}
+
+export function resolveAgendaOnLaunch(config: RivetRuntimeConfig | null): boolean {
+  return config?.agenda?.on_launch ?? false;
+}
+
+// Same counting as `task agenda`: pending tasks only, bucketed like the due groups.
+export function agendaCounts(
+  tasks: readonly TaskDto[],
+  nowUtcMs: number,
+  timezone: string,
+  weekStart: CalendarWeekStart | string
+): AgendaCounts {
+  const counts: AgendaCounts = { overdue: 0, today: 0 };
+  for (const task of tasks) {
+    if (task.status !== "Pending") {
+      continue;
+    }
+    const bucket = dueBucketFor(task, nowUtcMs, timezone, weekStart);
+    if (bucket === "overdue") {
+      counts.overdue += 1;
+    } else if (bucket === "today") {
+      counts.today += 1;
+    }
+  }
+  return counts;
+}
+
+export function agendaMessage(counts: AgendaCounts): string {
+  const parts: string[] = [];
+  if (counts.today > 0) {
+    parts.push(`${counts.today} due today`);
+  }
+  if (counts.overdue > 0) {
+    parts.push(`${counts.overdue} overdue`);
+  }
+  return parts.length === 0 ? "Nothing due today." : `${parts.join(", ")}.`;
+}
+
+function parseClockMinutes(raw: string): number | null {
+  const match = raw.trim().match(/^(\d{1,2}):(\d{2})$/);
+  if (!match) {
+    return null;
+  }
+  const hours = Number(match[1]);
+  const minutes = Number(match[2]);
+  if (hours > 23 || minutes > 59) {
+    return null;
+  }
+  return hours * 60 + minutes;
+}
+
+// `[notifications.due] quiet_hours = "HH:MM-HH:MM"`; empty or unset means none.
+export function resolveQuietHours(config: RivetRuntimeConfig | null): QuietHours | null {
+  const raw = config?.notifications?.due?.quiet_hours?.trim();
+  if (!raw) {
+    return null;
+  }
+  const [start, end, ...rest] = raw.split("-");
+  const startMinute = start === undefined ? null : parseClockMinutes(start);
+  const endMinute = end === undefined ? null : parseClockMinutes(end);
+  if (rest.length > 0 || startMinute === null || endMinute === null || startMinute === endMinute) {
+    logger.warn("config.notifications.due.quiet_hours", `invalid value '${raw}' (expected HH:MM-HH:MM)`);
+    return null;
+  }
+  return { startMinute, endMinute };
+}
+
+export function inQuietHours(window: QuietHours | null, nowUtcMs: number, timezone: string): boolean {
+  if (!window) {
+    return false;
+  }
+  const parts = zonedDateTimeParts(nowUtcMs, timezone);
+  const minute = parts.hour * 60 + parts.minute;
+  if (window.startMinute < window.endMinute) {
+    return minute >= window.startMinute && minute < window.endMinute;
+  }
+  return minute >= window.startMinute || minute < window.endMinute;
+}
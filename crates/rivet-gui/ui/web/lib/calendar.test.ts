@@ -0,0 +1,293 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  buildExternalCalendarColorMap,
+  calendarDateToIso,
+  calendarDrillView,
+  calendarJumpYears,
+  calendarMonthJump,
+  calendarLegendEntries,
+  calendarTitleForView,
+  capMarkers,
+  clampCalendarJump,
+  collectCalendarDueTasks,
+  entriesForDate,
+  externalCalendarColorForTask,
+  formatEntrySpan,
+  formatIsoWeek,
+  isoWeekForRow,
+  isoWeekOf,
+  layoutCalendarDay,
+  markerForTask,
+  periodStats,
+  moveCalendarFocus,
+  resolveCalendarConfig,
+  resolveMarkerScheme,
+  scheduledLegendEntry,
+  toCalendarDate
+} from "./calendar";
+import { CALENDAR_UNAFFILIATED_COLOR } from "./tags";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { ExternalCalendarSource, TaskDto } from "../types/core";
+
+describe("isoWeekOf", () => {
+  it("assigns early January days to the previous ISO year", () => {
+    expect(isoWeekOf(toCalendarDate(2021, 1, 1))).toEqual({ year: 2020, week: 53 });
+    expect(isoWeekOf(toCalendarDate(2027, 1, 3))).toEqual({ year: 2026, week: 53 });
+  });
+
+  it("assigns late December days to the next ISO year", () => {
+    expect(isoWeekOf(toCalendarDate(2024, 12, 30))).toEqual({ year: 2025, week: 1 });
+  });
+
+  it("numbers a mid-year date", () => {
+    expect(isoWeekOf(toCalendarDate(2026, 2, 16))).toEqual({ year: 2026, week: 8 });
+    expect(formatIsoWeek({ year: 2026, week: 8 })).toBe("W08");
+  });
+});
+
+describe("isoWeekForRow", () => {
+  it("labels sunday-first rows by the ISO week of their monday", () => {
+    const sundayRow = toCalendarDate(2024, 12, 29);
+    expect(isoWeekForRow(sundayRow, "sunday")).toEqual({ year: 2025, week: 1 });
+    expect(isoWeekForRow(toCalendarDate(2024, 12, 30), "monday")).toEqual({ year: 2025, week: 1 });
+  });
+});
+
+describe("calendarTitleForView", () => {
+  it("includes the ISO week in week view only when enabled", () => {
+    const focus = toCalendarDate(2026, 2, 18);
+    expect(calendarTitleForView("week", focus, "monday")).toBe("Week View 2026-02-16 - 2026-02-22");
+    expect(calendarTitleForView("week", focus, "monday", true)).toBe("Week View 2026-W08 (2026-02-16 - 2026-02-22)");
+  });
+});
+
+describe("externalCalendarColorForTask", () => {
+  const source = (id: string, color: string) => ({ id, color }) as ExternalCalendarSource;
+  const task = (tags: string[]) => ({ tags }) as TaskDto;
+
+  it("resolves colors by the stamped cal_source tag", () => {
+    const colors = buildExternalCalendarColorMap([source("Work Cal", "#1E88E5"), source("home", "")]);
+    expect(externalCalendarColorForTask(task(["cal_source:Work_Cal", "cal_color:d64545"]), colors)).toBe("#1e88e5");
+    expect(externalCalendarColorForTask(task(["cal_source:home", "cal_color:43a047"]), colors)).toBe("#43a047");
+    expect(externalCalendarColorForTask(task(["project"]), colors)).toBeNull();
+  });
+
+  it("falls back to the unaffiliated color once the source is deleted", () => {
+    const colors = buildExternalCalendarColorMap([]);
+    expect(externalCalendarColorForTask(task(["cal_source:gone", "cal_color:d64545"]), colors)).toBe(
+      CALENDAR_UNAFFILIATED_COLOR
+    );
+  });
+});
+
+describe("resolveMarkerScheme", () => {
+  const task = (tags: string[]) => ({ tags }) as TaskDto;
+
+  it("keeps the built-in shapes and per-task colors without config", () => {
+    const scheme = resolveMarkerScheme(null);
+    expect(markerForTask(task(["board:b1"]), scheme, { b1: "#123456" }, {})).toEqual({ shape: "triangle", color: "#123456" });
+    expect(markerForTask(task([]), scheme, {}, {})).toEqual({ shape: "square", color: CALENDAR_UNAFFILIATED_COLOR });
+    expect(calendarLegendEntries(scheme).map((entry) => entry.marker.shape)).toEqual(["triangle", "circle", "square"]);
+  });
+
+  it("applies configured categories and tag rules, tag rules first", () => {
+    const scheme = resolveMarkerScheme({
+      calendar: {
+        markers: {
+          kanban: { shape: "ring", color: "0072B2" },
+          unassigned: { shape: "hexagon", label: "Loose" },
+          tags: { Birthday: { shape: "diamond", color: "#e69f00" } }
+        }
+      }
+    } as RivetRuntimeConfig);
+    expect(markerForTask(task(["board:b1"]), scheme, { b1: "#123456" }, {})).toEqual({ shape: "ring", color: "#0072b2" });
+    expect(markerForTask(task(["board:b1", "birthday"]), scheme, {}, {})).toEqual({ shape: "diamond", color: "#e69f00" });
+    expect(markerForTask(task([]), scheme, {}, {}).shape).toBe("square");
+    expect(calendarLegendEntries(scheme).map((entry) => entry.label)).toEqual([
+      "Kanban board task",
+      "External calendar task",
+      "Loose",
+      "Tagged Birthday"
+    ]);
+  });
+});
+
+describe("scheduled vs due entries", () => {
+  const task = (title: string, due: string | null, scheduled: string | null, tags: string[] = []) =>
+    ({ uuid: title, title, status: "Pending", due, scheduled, tags }) as TaskDto;
+  const collect = (tasks: TaskDto[], calendar: RivetRuntimeConfig["calendar"] = {}) =>
+    collectCalendarDueTasks(
+      tasks,
+      resolveCalendarConfig({ calendar: { timezone: "UTC", ...calendar } } as RivetRuntimeConfig),
+      { b1: "#123456" },
+      {}
+    );
+
+  it("places a task at both its scheduled start and its due date", () => {
+    const entries = collect([
+      task("report", "2026-03-12T17:00:00Z", "2026-03-10T09:00:00Z", ["board:b1", "cal_duration:60"])
+    ]);
+    expect(entries.map((entry) => [entry.kind, entry.dueUtcMs, entry.endUtcMs, entry.marker])).toEqual([
+      ["scheduled", Date.UTC(2026, 2, 10, 9), null, { shape: "ring", color: "#123456" }],
+      ["due", Date.UTC(2026, 2, 12, 17), Date.UTC(2026, 2, 12, 18), { shape: "triangle", color: "#123456" }]
+    ]);
+    expect(periodStats(entries).total).toBe(1);
+  });
+
+  it("toggles each kind independently and styles scheduled markers from config", () => {
+    const tasks = [task("report", "2026-03-12T17:00:00Z", "2026-03-10T09:00:00Z"), task("plan", null, "2026-03-11T09:00:00Z")];
+    expect(collect(tasks, { toggles: { show_scheduled_markers: false } }).map((entry) => entry.task.title)).toEqual(["report"]);
+    expect(collect(tasks, { toggles: { show_due_markers: false } }).map((entry) => entry.kind)).toEqual(["scheduled", "scheduled"]);
+
+    const calendar = { markers: { scheduled: { shape: "diamond", color: "#009e73", label: "Planned start" } } };
+    expect(collect(tasks, calendar)[0]?.marker).toEqual({ shape: "diamond", color: "#009e73" });
+    const scheme = resolveMarkerScheme({ calendar } as RivetRuntimeConfig);
+    expect(scheduledLegendEntry(scheme)).toEqual({
+      key: "scheduled",
+      label: "Planned start",
+      marker: { shape: "diamond", color: "#009e73" }
+    });
+  });
+});
+
+describe("layoutCalendarDay", () => {
+  const event = (title: string, due: string, tags: string[] = []) =>
+    ({ uuid: title, title, status: "Pending", due, tags }) as TaskDto;
+  const entries = collectCalendarDueTasks(
+    [
+      event("standup", "2026-03-10T09:00:00Z", ["cal_duration:60"]),
+      event("review", "2026-03-10T09:30:00Z", ["cal_duration:60"]),
+      event("call", "2026-03-10T11:00:00Z"),
+      event("offsite", "2026-03-09T00:00:00Z", ["cal_duration:4320", "cal_allday:true"]),
+      event("late shift", "2026-03-09T22:00:00Z", ["cal_duration:180"])
+    ],
+    resolveCalendarConfig({ calendar: { timezone: "UTC" } } as RivetRuntimeConfig),
+    {},
+    {}
+  );
+
+  it("bands all-day events and lays overlapping blocks side by side", () => {
+    const layout = layoutCalendarDay(entries, toCalendarDate(2026, 3, 10), 0, 23);
+    expect(layout.allDay.map((entry) => entry.task.title)).toEqual(["offsite"]);
+    expect(
+      layout.blocks.map((block) => [block.entry.task.title, block.startMinute, block.endMinute, block.column, block.columns])
+    ).toEqual([
+      ["late shift", 0, 60, 0, 1],
+      ["standup", 540, 600, 0, 2],
+      ["review", 570, 630, 1, 2],
+      ["call", 660, 690, 0, 1]
+    ]);
+  });
+
+  it("shows multi-day events on every day they cover and clips to visible hours", () => {
+    const titles = (year: number, month: number, day: number) =>
+      entriesForDate(entries, toCalendarDate(year, month, day)).map((entry) => entry.task.title);
+    expect(titles(2026, 3, 11)).toEqual(["offsite"]);
+    expect(titles(2026, 3, 12)).toEqual([]);
+    const clipped = layoutCalendarDay(entries, toCalendarDate(2026, 3, 10), 10, 18);
+    expect(clipped.blocks.map((block) => [block.entry.task.title, block.startMinute, block.endMinute])).toEqual([
+      ["review", 600, 630],
+      ["call", 660, 690]
+    ]);
+  });
+
+  it("formats ranges for timed and all-day entries", () => {
+    const byTitle = (title: string) => {
+      const found = entries.find((entry) => entry.task.title === title);
+      if (!found) {
+        throw new Error(`missing ${title}`);
+      }
+      return found;
+    };
+    expect(formatEntrySpan(byTitle("standup"), "UTC")).toBe("2026-03-10 09:00 - 10:00 (UTC)");
+    expect(formatEntrySpan(byTitle("late shift"), "UTC")).toBe("2026-03-09 22:00 - 2026-03-10 01:00 (UTC)");
+    expect(formatEntrySpan(byTitle("offsite"), "UTC")).toBe("2026-03-09 - 2026-03-11, all day");
+  });
+});
+
+describe("calendar keyboard focus", () => {
+  it("moves by day or week in day grids and by month in period cards", () => {
+    const focus = toCalendarDate(2026, 1, 31);
+    const moved = (view: Parameters<typeof moveCalendarFocus>[1], key: Parameters<typeof moveCalendarFocus>[2]) =>
+      moveCalendarFocus(focus, view, key).toISOString().slice(0, 10);
+    expect(moved("month", "ArrowRight")).toBe("2026-02-01");
+    expect(moved("month", "ArrowUp")).toBe("2026-01-24");
+    expect(moved("week", "ArrowDown")).toBe("2026-02-07");
+    expect(moved("day", "ArrowUp")).toBe("2026-01-30");
+    expect(moved("year", "ArrowDown").slice(0, 7)).toBe("2026-04");
+    expect(moved("quarter", "ArrowLeft").slice(0, 7)).toBe("2025-12");
+  });
+
+  it("drills one level down and stops at the day view", () => {
+    expect(calendarDrillView("year")).toBe("month");
+    expect(calendarDrillView("month")).toBe("day");
+    expect(calendarDrillView("day")).toBeNull();
+  });
+});
+
+describe("day marker cap", () => {
+  it("caps drawn markers per day without touching the count", () => {
+    const marker = { shape: "dot", color: "#ff0000" } as ReturnType<typeof markerForTask>;
+    const markers = Array.from({ length: 9 }, () => marker);
+    expect(capMarkers(markers, 6)).toEqual({ shown: markers.slice(0, 6), overflow: 3 });
+    expect(capMarkers(markers.slice(0, 2), 6).overflow).toBe(0);
+  });
+
+  it("defaults to six and never exceeds red_dot_limit", () => {
+    const limit = (policies: object) =>
+      resolveCalendarConfig({ calendar: { policies } } as RivetRuntimeConfig).policies.day_marker_limit;
+    expect(limit({})).toBe(6);
+    expect(limit({ day_marker_limit: 12 })).toBe(12);
+    expect(limit({ day_marker_limit: 12, red_dot_limit: 4 })).toBe(4);
+    expect(limit({ day_marker_limit: 0 })).toBe(6);
+  });
+});
+
+describe("resolveCalendarConfig timezone", () => {
+  it("prefers configured zones, then the backend, then the default", () => {
+    const backend = { effective: { timezone: "America/Mexico_City" } };
+    const resolved = (config: object) => {
+      const effective = resolveCalendarConfig(config as RivetRuntimeConfig);
+      return [effective.timezone, effective.timezone_source];
+    };
+    expect(resolved({ ...backend, time: { timezone: "UTC" }, timezone: "Europe/Madrid" })).toEqual([
+      "UTC",
+      "time.timezone"
+    ]);
+    expect(resolved({ ...backend, timezone: "Not/AZone" })).toEqual(["America/Mexico_City", "backend"]);
+    expect(resolved({})[1]).toBe("default");
+  });
+
+  it("normalizes the due default time and falls back to midnight", () => {
+    const dueDefault = (raw: string) =>
+      resolveCalendarConfig({ time: { due_default_time: raw } } as RivetRuntimeConfig).due_default_time;
+    expect(dueDefault("23:59")).toBe("23:59");
+    expect(dueDefault("9:00am")).toBe("09:00");
+    expect(dueDefault("12:30pm")).toBe("12:30");
+    expect(dueDefault("late")).toBe("00:00");
+  });
+});
+
+describe("calendar jumps", () => {
+  const today = toCalendarDate(2026, 2, 17);
+
+  it("clamps jumps to a century either side of today", () => {
+    expect(clampCalendarJump(toCalendarDate(2027, 3, 1), today)).toEqual({ date: toCalendarDate(2027, 3, 1), clamped: false });
+    const late = clampCalendarJump(toCalendarDate(2400, 6, 1), today);
+    expect([calendarDateToIso(late.date), late.clamped]).toEqual(["2126-12-31", true]);
+    const early = clampCalendarJump(toCalendarDate(1800, 6, 1), today);
+    expect([calendarDateToIso(early.date), early.clamped]).toEqual(["1926-01-01", true]);
+  });
+
+  it("keeps the day of month when switching month or year", () => {
+    expect(calendarDateToIso(calendarMonthJump(toCalendarDate(2026, 1, 31), 2027, 2))).toBe("2027-02-28");
+    expect(calendarDateToIso(calendarMonthJump(toCalendarDate(2026, 1, 14), 2030, 7))).toBe("2030-07-14");
+  });
+
+  it("offers years around today and always the focused year", () => {
+    const years = calendarJumpYears(today, today);
+    expect([years[0], years[years.length - 1], years.length]).toEqual([2016, 2036, 21]);
+    expect(calendarJumpYears(toCalendarDate(2050, 1, 1), today).at(-1)).toBe(2050);
+  });
+});
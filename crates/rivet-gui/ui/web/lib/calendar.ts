@@ -1,28 +1,102 @@
-import type { TaskDto, TaskStatus } from "../types/core";
-import type { RivetRuntimeConfig } from "../types/config";
+import type { ExternalCalendarSource, TaskDto, TaskStatus } from "../types/core";
+import type { CalendarMarkerConfig, RivetRuntimeConfig } from "../types/config";
 import type {
+  CalendarDayLayout,
   CalendarDueTaskEntry,
+  CalendarLegendEntry,
+  CalendarMarkerCategory,
+  CalendarMarkerScheme,
+  CalendarMarkerShape,
+  CalendarMarkerStyle,
   CalendarStats,
   CalendarTaskMarker,
+  CalendarTimedBlock,
   CalendarViewMode,
   CalendarWeekStart,
   EffectiveCalendarConfig,
+  IsoWeek,
+  TimezoneSource,
   ZonedDateTimeParts
 } from "../types/ui";
-import { BOARD_TAG_KEY, CAL_COLOR_TAG_KEY, CAL_SOURCE_TAG_KEY, firstTagValue, normalizeMarkerColor } from "./tags";
+import {
+  BOARD_TAG_KEY,
+  CAL_ALL_DAY_TAG_KEY,
+  CAL_COLOR_TAG_KEY,
+  CAL_DURATION_TAG_KEY,
+  CAL_SOURCE_TAG_KEY,
+  CALENDAR_UNAFFILIATED_COLOR,
+  firstTagValue,
+  normalizeMarkerColor,
+  normalizeTagValue
+} from "./tags";
+import { DEFAULT_LOCALE, formatCalendarLabel, localeWeekStart, monthLabel, monthKeyOf, resolveLocale, weekdayKeyOf, weekdayLabel } from "./locale";
 
 const DAY_MS = 24 * 60 * 60 * 1000;
+const DAY_MINUTES = 24 * 60;
+// Point-in-time tasks are drawn as blocks this long.
+const POINT_BLOCK_MINUTES = 30;
 const DEFAULT_TIMEZONE = "America/Mexico_City";
 
-export function resolveCalendarConfig(runtimeConfig: RivetRuntimeConfig | null): EffectiveCalendarConfig {
-  const calendarTimezone = runtimeConfig?.calendar?.timezone?.trim();
-  const fallbackTimezone = runtimeConfig?.time?.timezone?.trim() || runtimeConfig?.timezone?.trim() || DEFAULT_TIMEZONE;
-  const timezoneCandidate = calendarTimezone || fallbackTimezone;
-  const timezone = resolveTimezone(timezoneCandidate);
+export const CALENDAR_MARKER_SHAPES: CalendarMarkerShape[] = ["triangle", "circle", "square", "diamond", "ring"];
+const MARKER_CATEGORIES: CalendarMarkerCategory[] = ["kanban", "external", "unassigned"];
+const DEFAULT_BOARD_MARKER_COLOR = "hsl(212 74% 54%)";
+
+export const DEFAULT_MARKER_STYLES: Record<CalendarMarkerCategory, CalendarMarkerStyle> = {
+  kanban: { shape: "triangle", color: null, label: "Kanban board task" },
+  external: { shape: "circle", color: null, label: "External calendar task" },
+  unassigned: { shape: "square", color: CALENDAR_UNAFFILIATED_COLOR, label: "Unassigned task" }
+};
+
+export const DEFAULT_SCHEDULED_MARKER_STYLE: CalendarMarkerStyle = { shape: "ring", color: null, label: "Scheduled start" };
+
+// Stand-ins for the legend when a category keeps per-task colors.
+const LEGEND_SAMPLE_COLORS: Record<CalendarMarkerCategory, string> = {
+  kanban: "var(--mui-palette-primary-main)",
+  external: "#d64545",
+  unassigned: CALENDAR_UNAFFILIATED_COLOR
+};
+
+// Mirrors the backend's `HH:MM` / `9:00am` default-time syntax; anything else is
+// ignored there too, so it falls back to midnight.
+export function normalizeClockTime(raw: string | undefined): string | null {
+  const match = /^(\d{1,2}):(\d{2})\s*([ap]m)?$/i.exec(raw?.trim() ?? "");
+  if (!match) {
+    return null;
+  }
+  let hour = Number(match[1]);
+  const minute = Number(match[2]);
+  const meridiem = match[3]?.toLowerCase();
+  if (minute > 59) {
+    return null;
+  }
+  if (meridiem) {
+    if (hour === 0 || hour > 12) {
+      return null;
+    }
+    hour = (hour % 12) + (meridiem === "pm" ? 12 : 0);
+  } else if (hour > 23) {
+    return null;
+  }
+  return `${String(hour).padStart(2, "0")}:${String(minute).padStart(2, "0")}`;
+}
 
-  const weekStartRaw = runtimeConfig?.calendar?.policies?.week_start ?? "monday";
-  const weekStart = weekStartRaw.toLowerCase() === "sunday" ? "sunday" : "monday";
+export function resolveCalendarConfig(runtimeConfig: RivetRuntimeConfig | null): EffectiveCalendarConfig {
+  const backendTimezone = validTimezone(runtimeConfig?.effective?.timezone);
+  const candidates: [TimezoneSource, string | undefined][] = [
+    ["calendar.timezone", runtimeConfig?.calendar?.timezone],
+    ["time.timezone", runtimeConfig?.time?.timezone],
+    ["timezone", runtimeConfig?.timezone],
+    ["backend", backendTimezone ?? undefined]
+  ];
+  const [timezoneSource, timezone] = candidates
+    .map(([source, candidate]) => [source, validTimezone(candidate)] as const)
+    .find((entry): entry is readonly [TimezoneSource, string] => entry[1] !== null) ?? ["default", DEFAULT_TIMEZONE];
+
+  const locale = resolveLocale(runtimeConfig?.calendar?.locale);
+  const weekStartRaw = (runtimeConfig?.calendar?.policies?.week_start ?? "monday").toLowerCase();
+  const weekStart = weekStartRaw === "locale" ? localeWeekStart(locale) : weekStartRaw === "sunday" ? "sunday" : "monday";
   const redDotLimit = clampPositiveInt(runtimeConfig?.calendar?.policies?.red_dot_limit, 5000);
+  const dayMarkerLimit = Math.min(clampPositiveInt(runtimeConfig?.calendar?.policies?.day_marker_limit, 6), redDotLimit);
   const taskListLimit = clampPositiveInt(runtimeConfig?.calendar?.policies?.task_list_limit, 200);
   const taskListWindowDays = clampPositiveInt(runtimeConfig?.calendar?.policies?.task_list_window_days, 365);
 
@@ -32,9 +106,14 @@ export function resolveCalendarConfig(runtimeConfig: RivetRuntimeConfig | null):
 
   return {
     timezone,
+    timezone_source: timezoneSource,
+    backend_timezone: backendTimezone,
+    due_default_time: normalizeClockTime(runtimeConfig?.time?.due_default_time) ?? "00:00",
+    locale,
     policies: {
       week_start: weekStart,
       red_dot_limit: redDotLimit,
+      day_marker_limit: dayMarkerLimit,
       task_list_limit: taskListLimit,
       task_list_window_days: taskListWindowDays
     },
@@ -51,17 +130,102 @@ export function resolveCalendarConfig(runtimeConfig: RivetRuntimeConfig | null):
     toggles: {
       de_emphasize_past_periods: runtimeConfig?.calendar?.toggles?.de_emphasize_past_periods ?? true,
       filter_tasks_before_now: runtimeConfig?.calendar?.toggles?.filter_tasks_before_now ?? true,
-      hide_past_markers: runtimeConfig?.calendar?.toggles?.hide_past_markers ?? true
+      hide_past_markers: runtimeConfig?.calendar?.toggles?.hide_past_markers ?? true,
+      show_week_numbers: runtimeConfig?.calendar?.toggles?.show_week_numbers ?? false,
+      show_due_markers: runtimeConfig?.calendar?.toggles?.show_due_markers ?? true,
+      show_scheduled_markers: runtimeConfig?.calendar?.toggles?.show_scheduled_markers ?? true
+    },
+    markers: resolveMarkerScheme(runtimeConfig)
+  };
+}
+
+function markerShape(raw: string | undefined, fallback: CalendarMarkerShape): CalendarMarkerShape {
+  const wanted = raw?.trim().toLowerCase();
+  return CALENDAR_MARKER_SHAPES.find((shape) => shape === wanted) ?? fallback;
+}
+
+function markerColor(raw: string | undefined): string | null {
+  const trimmed = raw?.trim();
+  return trimmed ? normalizeMarkerColor(trimmed) : null;
+}
+
+// `[calendar.markers]` overrides the built-in categories field by field and
+// adds tag rules; unknown shapes fall back to the category default. Tag rules
+// win over categories and are checked in tag-name order. `scheduled` styles
+// the scheduled-start markers the same way.
+export function resolveMarkerScheme(runtimeConfig: RivetRuntimeConfig | null): CalendarMarkerScheme {
+  const configured = runtimeConfig?.calendar?.markers;
+  const categories = { ...DEFAULT_MARKER_STYLES };
+  for (const category of MARKER_CATEGORIES) {
+    const entry: CalendarMarkerConfig | undefined = configured?.[category];
+    const fallback = DEFAULT_MARKER_STYLES[category];
+    categories[category] = {
+      shape: markerShape(entry?.shape, fallback.shape),
+      color: markerColor(entry?.color) ?? fallback.color,
+      label: entry?.label?.trim() || fallback.label
+    };
+  }
+
+  const tags = Object.entries(configured?.tags ?? {})
+    .map(([tag, entry]) => ({
+      tag: tag.trim().toLowerCase(),
+      shape: markerShape(entry.shape, "diamond"),
+      color: markerColor(entry.color) ?? CALENDAR_UNAFFILIATED_COLOR,
+      label: entry.label?.trim() || `Tagged ${tag.trim()}`
+    }))
+    .filter((rule) => rule.tag.length > 0)
+    .sort((a, b) => a.tag.localeCompare(b.tag));
+
+  const scheduled = configured?.scheduled;
+  return {
+    categories,
+    tags,
+    scheduled: {
+      shape: markerShape(scheduled?.shape, DEFAULT_SCHEDULED_MARKER_STYLE.shape),
+      color: markerColor(scheduled?.color) ?? DEFAULT_SCHEDULED_MARKER_STYLE.color,
+      label: scheduled?.label?.trim() || DEFAULT_SCHEDULED_MARKER_STYLE.label
     }
   };
 }
 
-function resolveTimezone(timezoneCandidate: string): string {
+export function calendarLegendEntries(scheme: CalendarMarkerScheme): CalendarLegendEntry[] {
+  const entries: CalendarLegendEntry[] = MARKER_CATEGORIES.map((category) => {
+    const style = scheme.categories[category];
+    return {
+      key: category,
+      label: style.label,
+      marker: { shape: style.shape, color: style.color ?? LEGEND_SAMPLE_COLORS[category] }
+    };
+  });
+  for (const rule of scheme.tags) {
+    entries.push({
+      key: `tag:${rule.tag}`,
+      label: rule.label,
+      marker: { shape: rule.shape, color: rule.color }
+    });
+  }
+  return entries;
+}
+
+// Legend row for scheduled starts, which sit apart from the due markers above.
+export function scheduledLegendEntry(scheme: CalendarMarkerScheme): CalendarLegendEntry {
+  return {
+    key: "scheduled",
+    label: scheme.scheduled.label,
+    marker: { shape: scheme.scheduled.shape, color: scheme.scheduled.color ?? LEGEND_SAMPLE_COLORS.kanban }
+  };
+}
+
+function validTimezone(candidate: string | undefined): string | null {
+  const trimmed = candidate?.trim();
+  if (!trimmed) {
+    return null;
+  }
   try {
-    new Intl.DateTimeFormat("en-US", { timeZone: timezoneCandidate });
-    return timezoneCandidate;
+    new Intl.DateTimeFormat("en-US", { timeZone: trimmed });
+    return trimmed;
   } catch {
-    return DEFAULT_TIMEZONE;
+    return null;
   }
 }
 
@@ -134,6 +298,37 @@ export function shiftYears(date: Date, years: number): Date {
   return toCalendarDate(year, month, day);
 }
 
+// Jumps land within this many years of today; farther dates are clamped.
+export const CALENDAR_JUMP_YEAR_SPAN = 100;
+
+export function clampCalendarJump(date: Date, today: Date): { date: Date; clamped: boolean } {
+  const min = toCalendarDate(today.getUTCFullYear() - CALENDAR_JUMP_YEAR_SPAN, 1, 1);
+  const max = toCalendarDate(today.getUTCFullYear() + CALENDAR_JUMP_YEAR_SPAN, 12, 31);
+  if (date.getTime() < min.getTime()) {
+    return { date: min, clamped: true };
+  }
+  if (date.getTime() > max.getTime()) {
+    return { date: max, clamped: true };
+  }
+  return { date, clamped: false };
+}
+
+// Year choices for the month/year jump: `radius` years around today, widened to
+// include the focused year.
+export function calendarJumpYears(focus: Date, today: Date, radius = 10): number[] {
+  const todayYear = today.getUTCFullYear();
+  const focusYear = focus.getUTCFullYear();
+  const first = Math.min(todayYear - radius, focusYear);
+  const last = Math.max(todayYear + radius, focusYear);
+  return Array.from({ length: last - first + 1 }, (_, index) => first + index);
+}
+
+// Same day of the month in the chosen month, or its last day when shorter.
+export function calendarMonthJump(focus: Date, year: number, month: number): Date {
+  const day = Math.min(focus.getUTCDate(), lastDayOfMonth(year, month).getUTCDate());
+  return toCalendarDate(year, month, day);
+}
+
 export function weekStartDay(weekStart: CalendarWeekStart | string): number {
   return weekStart.toLowerCase() === "sunday" ? 0 : 1;
 }
@@ -145,10 +340,30 @@ export function startOfWeek(date: Date, weekStart: CalendarWeekStart | string):
   return addDays(date, -diff);
 }
 
-export function weekdayLabels(weekStart: CalendarWeekStart | string): string[] {
-  const labels = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
-  const start = weekStartDay(weekStart);
-  return [...labels.slice(start), ...labels.slice(0, start)];
+export function weekdayLabels(weekStart: CalendarWeekStart | string, locale = DEFAULT_LOCALE): string[] {
+  const start = startOfWeek(toCalendarDate(2000, 1, 5), weekStart);
+  return Array.from({ length: 7 }, (_, offset) => weekdayLabel(weekdayKeyOf(addDays(start, offset)), locale));
+}
+
+export function isoWeekOf(date: Date): IsoWeek {
+  // The Thursday of an ISO week always falls in the week's ISO year, which
+  // resolves the Dec 29-31 / Jan 1-3 boundary weeks.
+  const mondayOffset = (date.getUTCDay() + 6) % 7;
+  const thursday = addDays(date, 3 - mondayOffset);
+  const year = thursday.getUTCFullYear();
+  const dayOfYear = Math.round((thursday.getTime() - toCalendarDate(year, 1, 1).getTime()) / DAY_MS);
+  return { year, week: Math.floor(dayOfYear / 7) + 1 };
+}
+
+export function isoWeekForRow(rowStart: Date, weekStart: CalendarWeekStart | string): IsoWeek {
+  // ISO weeks start on Monday. A Sunday-first row shares six days with the ISO
+  // week of its Monday, so that week labels the row.
+  const monday = weekStartDay(weekStart) === 0 ? addDays(rowStart, 1) : rowStart;
+  return isoWeekOf(monday);
+}
+
+export function formatIsoWeek(week: IsoWeek): string {
+  return `W${String(week.week).padStart(2, "0")}`;
 }
 
 export function shiftCalendarFocus(current: Date, view: CalendarViewMode, step: number, weekStart: CalendarWeekStart | string): Date {
@@ -168,6 +383,40 @@ export function shiftCalendarFocus(current: Date, view: CalendarViewMode, step:
   }
 }
 
+export type CalendarArrowKey = "ArrowLeft" | "ArrowRight" | "ArrowUp" | "ArrowDown";
+
+// Moves the keyboard focus one cell: a day in the month, week and day views
+// (a week for up/down), a month in the year and quarter cards (up/down jump a
+// three-card row).
+export function moveCalendarFocus(current: Date, view: CalendarViewMode, key: CalendarArrowKey): Date {
+  const forward = key === "ArrowRight" || key === "ArrowDown" ? 1 : -1;
+  const vertical = key === "ArrowUp" || key === "ArrowDown";
+  switch (view) {
+    case "year":
+    case "quarter":
+      return shiftMonths(current, forward * (vertical ? 3 : 1));
+    case "month":
+    case "week":
+      return addDays(current, forward * (vertical ? 7 : 1));
+    default:
+      return addDays(current, forward);
+  }
+}
+
+// Enter opens the focused cell one level down; the day view has nothing below.
+export function calendarDrillView(view: CalendarViewMode): CalendarViewMode | null {
+  switch (view) {
+    case "year":
+    case "quarter":
+      return "month";
+    case "month":
+    case "week":
+      return "day";
+    default:
+      return null;
+  }
+}
+
 export function calendarWindow(view: CalendarViewMode, focus: Date, weekStart: CalendarWeekStart | string): { start: Date; end: Date } {
   switch (view) {
     case "year":
@@ -198,7 +447,13 @@ export function calendarWindow(view: CalendarViewMode, focus: Date, weekStart: C
   }
 }
 
-export function calendarTitleForView(view: CalendarViewMode, focus: Date, weekStart: CalendarWeekStart | string): string {
+export function calendarTitleForView(
+  view: CalendarViewMode,
+  focus: Date,
+  weekStart: CalendarWeekStart | string,
+  showWeekNumbers = false,
+  locale = DEFAULT_LOCALE
+): string {
   const year = focus.getUTCFullYear();
   if (view === "year") {
     return `Year View ${year}`;
@@ -206,19 +461,24 @@ export function calendarTitleForView(view: CalendarViewMode, focus: Date, weekSt
   if (view === "quarter") {
     const quarter = Math.floor(focus.getUTCMonth() / 3) + 1;
     const quarterStartMonth = Math.floor(focus.getUTCMonth() / 3) * 3 + 1;
-    const start = firstDayOfMonth(year, quarterStartMonth).toLocaleString("en-US", { month: "short", timeZone: "UTC" });
-    const end = firstDayOfMonth(year, quarterStartMonth + 2).toLocaleString("en-US", { month: "short", timeZone: "UTC" });
+    const start = monthLabel(monthKeyOf(firstDayOfMonth(year, quarterStartMonth)), locale, "short");
+    const end = monthLabel(monthKeyOf(firstDayOfMonth(year, quarterStartMonth + 2)), locale, "short");
     return `Quarter View Q${quarter} ${year} (${start}-${end})`;
   }
   if (view === "month") {
-    return `Month View ${focus.toLocaleString("en-US", { month: "long", year: "numeric", timeZone: "UTC" })}`;
+    return `Month View ${formatCalendarLabel(focus, locale, { month: "long", year: "numeric" })}`;
   }
   if (view === "week") {
     const start = startOfWeek(focus, weekStart);
     const end = addDays(start, 6);
-    return `Week View ${calendarDateToIso(start)} - ${calendarDateToIso(end)}`;
+    const range = `${calendarDateToIso(start)} - ${calendarDateToIso(end)}`;
+    if (showWeekNumbers) {
+      const week = isoWeekForRow(start, weekStart);
+      return `Week View ${week.year}-${formatIsoWeek(week)} (${range})`;
+    }
+    return `Week View ${range}`;
   }
-  return `Day View ${focus.toLocaleString("en-US", { weekday: "long", year: "numeric", month: "2-digit", day: "2-digit", timeZone: "UTC" })}`;
+  return `Day View ${formatCalendarLabel(focus, locale, { weekday: "long", year: "numeric", month: "2-digit", day: "2-digit" })}`;
 }
 
 export function monthWeekStarts(focus: Date, weekStart: CalendarWeekStart | string): Date[] {
@@ -364,32 +624,73 @@ function shortWeekdayToIndex(value: string): number {
   return 6;
 }
 
-function markerForTask(task: TaskDto, boardColors: Record<string, string>, calendarColors: Record<string, string>): CalendarTaskMarker {
+// Keyed by the normalized `cal_source:` tag value so lookups match what the
+// import path stamped on each event task.
+export function buildExternalCalendarColorMap(sources: ExternalCalendarSource[]): Record<string, string> {
+  const map: Record<string, string> = {};
+  for (const source of sources) {
+    map[normalizeTagValue(source.id)] = source.color.trim();
+  }
+  return map;
+}
+
+// Calendar color for an imported event task: the source's current color, then
+// the `cal_color:` stamped at import. Tasks whose source was removed fall back
+// to the unaffiliated color. Returns null for tasks without a `cal_source:`.
+export function externalCalendarColorForTask(task: TaskDto, calendarColors: Record<string, string>): string | null {
   const calendarId = firstTagValue(task.tags, CAL_SOURCE_TAG_KEY);
-  if (calendarId) {
-    const color = calendarColors[calendarId]
-      || calendarColors[calendarId.toLowerCase()]
-      || normalizeMarkerColor(firstTagValue(task.tags, CAL_COLOR_TAG_KEY) ?? "#d64545");
+  if (!calendarId) {
+    return null;
+  }
+  if (!Object.hasOwn(calendarColors, calendarId)) {
+    return CALENDAR_UNAFFILIATED_COLOR;
+  }
+  return normalizeMarkerColor(calendarColors[calendarId] || firstTagValue(task.tags, CAL_COLOR_TAG_KEY) || "");
+}
+
+export function markerForTask(
+  task: TaskDto,
+  scheme: CalendarMarkerScheme,
+  boardColors: Record<string, string>,
+  calendarColors: Record<string, string>
+): CalendarTaskMarker {
+  const tags = new Set(task.tags.map((tag) => tag.toLowerCase()));
+  const rule = scheme.tags.find((candidate) => tags.has(candidate.tag));
+  if (rule) {
+    return {
+      shape: rule.shape,
+      color: rule.color
+    };
+  }
+
+  const calendarColor = externalCalendarColorForTask(task, calendarColors);
+  if (calendarColor) {
+    const style = scheme.categories.external;
     return {
-      shape: "circle",
-      color
+      shape: style.shape,
+      color: style.color ?? calendarColor
     };
   }
 
   const boardId = firstTagValue(task.tags, BOARD_TAG_KEY);
   if (boardId) {
+    const style = scheme.categories.kanban;
     return {
-      shape: "triangle",
-      color: boardColors[boardId] || "hsl(212 74% 54%)"
+      shape: style.shape,
+      color: style.color ?? (boardColors[boardId] || DEFAULT_BOARD_MARKER_COLOR)
     };
   }
 
+  const style = scheme.categories.unassigned;
   return {
-    shape: "square",
-    color: "#7f8691"
+    shape: style.shape,
+    color: style.color ?? CALENDAR_UNAFFILIATED_COLOR
   };
 }
 
+// One entry per due date and one per scheduled start, each behind its own
+// toggle. Scheduled entries are points in time with the scheduled marker in
+// the task's category color; event durations only stretch due entries.
 export function collectCalendarDueTasks(
   tasks: TaskDto[],
   config: EffectiveCalendarConfig,
@@ -401,39 +702,176 @@ export function collectCalendarDueTasks(
     if (!statusVisible(task.status, config)) {
       continue;
     }
-    const dueRaw = task.due?.trim();
-    if (!dueRaw) {
-      continue;
+    const marker = markerForTask(task, config.markers, boardColors, calendarColors);
+    const dueUtcMs = config.toggles.show_due_markers ? parseOptionalUtcMs(task.due) : null;
+    if (dueUtcMs !== null) {
+      const durationMinutes = taskDurationMinutes(task);
+      const endUtcMs = durationMinutes > 0 ? dueUtcMs + durationMinutes * 60 * 1000 : null;
+      entries.push({
+        task,
+        kind: "due",
+        dueUtcMs,
+        dueLocal: zonedDateTimeParts(dueUtcMs, config.timezone),
+        endUtcMs,
+        endLocal: endUtcMs === null ? null : zonedDateTimeParts(endUtcMs, config.timezone),
+        allDay: firstTagValue(task.tags, CAL_ALL_DAY_TAG_KEY) === "true",
+        marker
+      });
     }
-    const dueUtcMs = parseTaskDueUtcMs(dueRaw);
-    if (dueUtcMs === null) {
-      continue;
+    const scheduledUtcMs = config.toggles.show_scheduled_markers ? parseOptionalUtcMs(task.scheduled) : null;
+    if (scheduledUtcMs !== null) {
+      const style = config.markers.scheduled;
+      entries.push({
+        task,
+        kind: "scheduled",
+        dueUtcMs: scheduledUtcMs,
+        dueLocal: zonedDateTimeParts(scheduledUtcMs, config.timezone),
+        endUtcMs: null,
+        endLocal: null,
+        allDay: false,
+        marker: { shape: style.shape, color: style.color ?? marker.color }
+      });
     }
-    entries.push({
-      task,
-      dueUtcMs,
-      dueLocal: zonedDateTimeParts(dueUtcMs, config.timezone),
-      marker: markerForTask(task, boardColors, calendarColors)
-    });
   }
   entries.sort((a, b) => a.dueUtcMs - b.dueUtcMs);
   return entries;
 }
 
+function parseOptionalUtcMs(raw: string | null | undefined): number | null {
+  const trimmed = raw?.trim();
+  return trimmed ? parseTaskDueUtcMs(trimmed) : null;
+}
+
+// Imported events carry their length as `cal_duration:<minutes>`.
+function taskDurationMinutes(task: TaskDto): number {
+  const raw = Number(firstTagValue(task.tags, CAL_DURATION_TAG_KEY) ?? "");
+  return Number.isFinite(raw) && raw > 0 ? Math.floor(raw) : 0;
+}
+
 function dateTupleToNumber(year: number, month: number, day: number): number {
   return (year * 10000) + (month * 100) + day;
 }
 
+function dateToNumber(day: Date): number {
+  return dateTupleToNumber(day.getUTCFullYear(), day.getUTCMonth() + 1, day.getUTCDate());
+}
+
+function partsToMinute(parts: ZonedDateTimeParts): number {
+  return parts.hour * 60 + parts.minute;
+}
+
+// First and last local days an entry touches; an end exactly at midnight
+// does not spill into that day.
+function entryDaySpan(entry: CalendarDueTaskEntry): [number, number] {
+  const first = dateTupleToNumber(entry.dueLocal.year, entry.dueLocal.month, entry.dueLocal.day);
+  const end = entry.endLocal;
+  if (!end) {
+    return [first, first];
+  }
+  const endDay = toCalendarDate(end.year, end.month, end.day);
+  const last = dateToNumber(partsToMinute(end) === 0 && end.second === 0 ? addDays(endDay, -1) : endDay);
+  return [first, Math.max(first, last)];
+}
+
+function entryCoversDate(entry: CalendarDueTaskEntry, day: Date): boolean {
+  const [first, last] = entryDaySpan(entry);
+  const value = dateToNumber(day);
+  return value >= first && value <= last;
+}
+
+// Splits one day into an all-day band and timed blocks positioned within
+// `hourStart..hourEnd`. Timed entries that cover the whole day join the
+// band; overlapping blocks share the width in side-by-side columns.
+export function layoutCalendarDay(
+  entries: CalendarDueTaskEntry[],
+  day: Date,
+  hourStart: number,
+  hourEnd: number
+): CalendarDayLayout {
+  const value = dateToNumber(day);
+  const rangeStart = hourStart * 60;
+  const rangeEnd = (hourEnd + 1) * 60;
+  const allDay: CalendarDueTaskEntry[] = [];
+  const timed: CalendarTimedBlock[] = [];
+
+  for (const entry of entriesForDate(entries, day)) {
+    if (entry.allDay) {
+      allDay.push(entry);
+      continue;
+    }
+    const [first] = entryDaySpan(entry);
+    const start = first === value ? partsToMinute(entry.dueLocal) : 0;
+    let end: number;
+    if (!entry.endLocal) {
+      end = Math.min(DAY_MINUTES, start + POINT_BLOCK_MINUTES);
+    } else {
+      const endValue = dateTupleToNumber(entry.endLocal.year, entry.endLocal.month, entry.endLocal.day);
+      end = endValue === value ? partsToMinute(entry.endLocal) : DAY_MINUTES;
+    }
+    if (start === 0 && end === DAY_MINUTES) {
+      allDay.push(entry);
+      continue;
+    }
+    const startMinute = Math.max(start, rangeStart);
+    const endMinute = Math.min(Math.max(end, start + 1), rangeEnd);
+    if (endMinute <= startMinute) {
+      continue;
+    }
+    timed.push({
+      entry,
+      startMinute,
+      endMinute,
+      top: ((startMinute - rangeStart) / (rangeEnd - rangeStart)) * 100,
+      height: ((endMinute - startMinute) / (rangeEnd - rangeStart)) * 100,
+      column: 0,
+      columns: 1
+    });
+  }
+
+  timed.sort((a, b) => a.startMinute - b.startMinute || b.endMinute - a.endMinute);
+  let cluster: CalendarTimedBlock[] = [];
+  let columnEnds: number[] = [];
+  let clusterEnd = -1;
+  const closeCluster = () => {
+    for (const block of cluster) {
+      block.columns = columnEnds.length;
+    }
+    cluster = [];
+    columnEnds = [];
+  };
+  for (const block of timed) {
+    if (block.startMinute >= clusterEnd) {
+      closeCluster();
+    }
+    const visualEnd = Math.max(block.endMinute, block.startMinute + POINT_BLOCK_MINUTES / 2);
+    const free = columnEnds.findIndex((columnEnd) => columnEnd <= block.startMinute);
+    if (free === -1) {
+      block.column = columnEnds.length;
+      columnEnds.push(visualEnd);
+    } else {
+      block.column = free;
+      columnEnds[free] = visualEnd;
+    }
+    cluster.push(block);
+    clusterEnd = Math.max(clusterEnd, visualEnd);
+  }
+  closeCluster();
+
+  allDay.sort((a, b) => a.dueUtcMs - b.dueUtcMs);
+  return { allDay, blocks: timed };
+}
+
 export function periodTasks(entries: CalendarDueTaskEntry[], view: CalendarViewMode, focus: Date, weekStart: CalendarWeekStart | string): CalendarDueTaskEntry[] {
   const window = calendarWindow(view, focus, weekStart);
   const startValue = dateTupleToNumber(window.start.getUTCFullYear(), window.start.getUTCMonth() + 1, window.start.getUTCDate());
   const endValue = dateTupleToNumber(window.end.getUTCFullYear(), window.end.getUTCMonth() + 1, window.end.getUTCDate());
   return entries.filter((entry) => {
-    const dueValue = dateTupleToNumber(entry.dueLocal.year, entry.dueLocal.month, entry.dueLocal.day);
-    return dueValue >= startValue && dueValue <= endValue;
+    const [first, last] = entryDaySpan(entry);
+    return first <= endValue && last >= startValue;
   });
 }
 
+// Counts tasks, so one with both a due and a scheduled entry counts once.
 export function periodStats(entries: CalendarDueTaskEntry[]): CalendarStats {
   const stats: CalendarStats = {
     total: 0,
@@ -442,7 +880,12 @@ export function periodStats(entries: CalendarDueTaskEntry[]): CalendarStats {
     completed: 0,
     deleted: 0
   };
+  const seen = new Set<string>();
   for (const entry of entries) {
+    if (seen.has(entry.task.uuid)) {
+      continue;
+    }
+    seen.add(entry.task.uuid);
     stats.total += 1;
     if (entry.task.status === "Pending") {
       stats.pending += 1;
@@ -457,17 +900,22 @@ export function periodStats(entries: CalendarDueTaskEntry[]): CalendarStats {
   return stats;
 }
 
+// Every entry touching `day`, so multi-day events show on each of them.
 export function entriesForDate(entries: CalendarDueTaskEntry[], day: Date): CalendarDueTaskEntry[] {
-  const year = day.getUTCFullYear();
-  const month = day.getUTCMonth() + 1;
-  const date = day.getUTCDate();
-  return entries.filter((entry) => entry.dueLocal.year === year && entry.dueLocal.month === month && entry.dueLocal.day === date);
+  return entries.filter((entry) => entryCoversDate(entry, day));
 }
 
 export function markersForDate(entries: CalendarDueTaskEntry[], day: Date): CalendarTaskMarker[] {
   return entriesForDate(entries, day).map((entry) => entry.marker);
 }
 
+// Splits markers into those drawn and the "+N" remainder. Only the drawing is
+// capped; task counts always come from the full entry list.
+export function capMarkers(markers: CalendarTaskMarker[], limit: number): { shown: CalendarTaskMarker[]; overflow: number } {
+  const shown = markers.slice(0, Math.max(0, limit));
+  return { shown, overflow: markers.length - shown.length };
+}
+
 export function formatDueDateTime(utcMs: number, timezone: string): string {
   const formatter = new Intl.DateTimeFormat("en-CA", {
     timeZone: timezone,
@@ -481,6 +929,24 @@ export function formatDueDateTime(utcMs: number, timezone: string): string {
   return `${formatter.format(new Date(utcMs))} (${timezone})`;
 }
 
+// Due time, or the start-end range for entries that carry a duration.
+export function formatEntrySpan(entry: CalendarDueTaskEntry, timezone: string): string {
+  if (entry.endUtcMs === null || !entry.endLocal) {
+    return formatDueDateTime(entry.dueUtcMs, timezone);
+  }
+  const [first, last] = entryDaySpan(entry);
+  const isoDay = (value: number) =>
+    `${Math.floor(value / 10000)}-${String(Math.floor(value / 100) % 100).padStart(2, "0")}-${String(value % 100).padStart(2, "0")}`;
+  if (entry.allDay) {
+    return first === last ? `${isoDay(first)}, all day` : `${isoDay(first)} - ${isoDay(last)}, all day`;
+  }
+  const clock = (parts: ZonedDateTimeParts) => `${String(parts.hour).padStart(2, "0")}:${String(parts.minute).padStart(2, "0")}`;
+  const start = `${isoDay(first)} ${clock(entry.dueLocal)}`;
+  const endDay = dateTupleToNumber(entry.endLocal.year, entry.endLocal.month, entry.endLocal.day);
+  const end = endDay === first ? clock(entry.endLocal) : `${isoDay(endDay)} ${clock(entry.endLocal)}`;
+  return `${start} - ${end} (${timezone})`;
+}
+
 export function calendarMonthGridStart(focus: Date, weekStart: CalendarWeekStart | string): Date {
   const monthFirst = firstDayOfMonth(focus.getUTCFullYear(), focus.getUTCMonth() + 1);
   return startOfWeek(monthFirst, weekStart);
@@ -0,0 +1,101 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  applyQuickAddSuggestion,
+  buildCaptureTask,
+  captureTag,
+  editDistance,
+  isInboxTask,
+  quickAddSuggestions,
+  quickAddVocabulary
+} from "./capture";
+import type { TaskDto } from "../types/core";
+
+function task(overrides: Partial<TaskDto> = {}): TaskDto {
+  return {
+    uuid: "a",
+    id: 1,
+    title: "a",
+    description: "",
+    status: "Pending",
+    project: null,
+    tags: [],
+    priority: null,
+    due: null,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null,
+    ...overrides
+  };
+}
+
+describe("capture", () => {
+  it("defaults to the inbox tag and ignores blank input", () => {
+    expect(captureTag(null)).toBe("inbox");
+    expect(captureTag({ capture: { tag: " +triage " } })).toBe("triage");
+    expect(buildCaptureTask("   ", null)).toBeNull();
+    expect(buildCaptureTask(" Call the bank ", null)).toMatchObject({
+      title: "Call the bank",
+      tags: ["inbox"],
+      project: null,
+      priority: null
+    });
+  });
+
+  it("applies configured default attributes", () => {
+    const input = buildCaptureTask("Renew passport", {
+      capture: { tag: "triage", tags: ["errand", "triage"], project: "home", priority: "h" }
+    });
+    expect(input).toMatchObject({
+      tags: ["triage", "errand"],
+      project: "home",
+      priority: "High"
+    });
+  });
+
+  it("lifts quick-add modifiers out of the title", () => {
+    const input = buildCaptureTask("Buy milk project:home +errand pri:h -- +1 later", {
+      capture: { project: "inbox-project" }
+    });
+    expect(input).toMatchObject({
+      title: "Buy milk +1 later",
+      project: "home",
+      tags: ["inbox", "errand"],
+      priority: "High"
+    });
+  });
+
+  it("suggests the closest known key, project and tag for typos", () => {
+    const vocabulary = quickAddVocabulary([
+      task({ project: "home", tags: ["work", "kanban:todo"] }),
+      task({ uuid: "b", project: "garden" })
+    ]);
+    expect(editDistance("projetc", "project")).toBe(1);
+
+    const text = "Fix sink projetc:hmoe +wrok +kanbn:todo";
+    const suggestions = quickAddSuggestions(text, vocabulary);
+    expect(suggestions).toEqual([
+      { original: "projetc:hmoe", suggestion: "project:home" },
+      { original: "+wrok", suggestion: "+work" },
+      { original: "+kanbn:todo", suggestion: "+kanban:todo" }
+    ]);
+    expect(applyQuickAddSuggestion(text, suggestions[0])).toBe("Fix sink project:home +wrok +kanbn:todo");
+
+    // New names far from anything known and words after `--` are left alone.
+    expect(quickAddSuggestions("Plan trip project:travel +errand -- projetc:hmoe", vocabulary)).toEqual([]);
+  });
+
+  it("only treats pending tasks with the capture tag as inbox items", () => {
+    expect(isInboxTask(task({ tags: ["inbox"] }), "inbox")).toBe(true);
+    expect(isInboxTask(task({ tags: ["inbox"], status: "Waiting" }), "inbox")).toBe(false);
+    expect(isInboxTask(task({ tags: ["inbox"], status: "Completed" }), "inbox")).toBe(false);
+    expect(isInboxTask(task({ tags: ["home"] }), "inbox")).toBe(false);
+  });
+});
@@ -0,0 +1,204 @@
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskCreate, TaskDto, TaskPriority } from "../types/core";
+
+export const DEFAULT_CAPTURE_TAG = "inbox";
+
+const CAPTURE_PRIORITIES: Record<string, TaskPriority> = {
+  l: "Low",
+  low: "Low",
+  m: "Medium",
+  medium: "Medium",
+  h: "High",
+  high: "High"
+};
+
+// Same default as the CLI's `inbox.tag`; a leading `+` is tolerated so the
+// value can be copied straight from a taskrc.
+export function captureTag(config: RivetRuntimeConfig | null): string {
+  const raw = (config?.capture?.tag ?? "").trim().replace(/^\+/, "");
+  return raw.length > 0 ? raw : DEFAULT_CAPTURE_TAG;
+}
+
+// Keys the capture line understands; `pro`/`pri` are accepted shorthands.
+const QUICK_ADD_KEYS = ["project", "priority"];
+const QUICK_ADD_KEY_ALIASES: Record<string, string> = { pro: "project", pri: "priority" };
+// Same cut-off as the CLI: at most one edit per four characters.
+const QUICK_ADD_MIN_SIMILARITY = 0.75;
+
+export interface QuickAddVocabulary {
+  projects: string[];
+  tags: string[];
+}
+
+export interface QuickAddSuggestion {
+  original: string;
+  suggestion: string;
+}
+
+interface QuickAddParts {
+  title: string;
+  project: string | null;
+  tags: string[];
+  priority: TaskPriority | null;
+}
+
+// Quick-add modifiers on the capture line, like `task in`: `project:home`,
+// `+tag` and `pri:H` are lifted out, everything after `--` is title text.
+function parseQuickAdd(text: string): QuickAddParts {
+  const words: string[] = [];
+  const parts: QuickAddParts = { title: "", project: null, tags: [], priority: null };
+  let literal = false;
+  for (const word of text.trim().split(/\s+/)) {
+    if (literal || word.length === 0) {
+      words.push(word);
+      continue;
+    }
+    if (word === "--") {
+      literal = true;
+      continue;
+    }
+    if (word.length > 1 && word.startsWith("+")) {
+      parts.tags.push(word.slice(1));
+      continue;
+    }
+    const [key, value] = splitModifier(word);
+    const canonical = key === null ? null : QUICK_ADD_KEY_ALIASES[key] ?? key;
+    if (canonical === "project" && value) {
+      parts.project = value;
+      continue;
+    }
+    const priority = canonical === "priority" ? CAPTURE_PRIORITIES[(value ?? "").toLowerCase()] : undefined;
+    if (priority) {
+      parts.priority = priority;
+      continue;
+    }
+    words.push(word);
+  }
+  parts.title = words.join(" ").trim();
+  return parts;
+}
+
+function splitModifier(word: string): [string | null, string | null] {
+  const index = word.search(/[:=]/);
+  if (index <= 0) {
+    return [null, null];
+  }
+  return [word.slice(0, index).toLowerCase(), word.slice(index + 1)];
+}
+
+// Case-insensitive edit distance where swapping two neighbours is one edit,
+// matching `edit_distance` in rivet-core.
+export function editDistance(left: string, right: string): number {
+  const a = [...left.toLowerCase()];
+  const b = [...right.toLowerCase()];
+  const rows = Array.from({ length: a.length + 1 }, (_, i) => Array.from({ length: b.length + 1 }, (_, j) => (i === 0 ? j : j === 0 ? i : 0)));
+  for (let i = 1; i <= a.length; i += 1) {
+    for (let j = 1; j <= b.length; j += 1) {
+      const cost = a[i - 1] === b[j - 1] ? 0 : 1;
+      let best = Math.min(rows[i - 1][j] + 1, rows[i][j - 1] + 1, rows[i - 1][j - 1] + cost);
+      if (i > 1 && j > 1 && a[i - 1] === b[j - 2] && a[i - 2] === b[j - 1]) {
+        best = Math.min(best, rows[i - 2][j - 2] + 1);
+      }
+      rows[i][j] = best;
+    }
+  }
+  return rows[a.length][b.length];
+}
+
+// Nearest candidate close enough to be a typo of `word`; null when `word`
+// is already one of them or nothing is close.
+export function closestMatch(word: string, candidates: Iterable<string>): string | null {
+  let best: { distance: number; value: string } | null = null;
+  for (const candidate of candidates) {
+    if (candidate === word) {
+      return null;
+    }
+    const distance = editDistance(word, candidate);
+    const longest = Math.max([...word].length, [...candidate].length);
+    if (1 - distance / longest >= QUICK_ADD_MIN_SIMILARITY && (best === null || distance < best.distance)) {
+      best = { distance, value: candidate };
+    }
+  }
+  return best?.value ?? null;
+}
+
+export function quickAddVocabulary(tasks: TaskDto[]): QuickAddVocabulary {
+  const projects = new Set<string>();
+  const tags = new Set<string>();
+  for (const task of tasks) {
+    if (task.project) {
+      projects.add(task.project);
+    }
+    for (const tag of task.tags) {
+      tags.add(tag);
+    }
+  }
+  return { projects: [...projects].sort(), tags: [...tags].sort() };
+}
+
+// "Did you mean" fixes for the capture line, in the order the typos appear.
+export function quickAddSuggestions(text: string, vocabulary: QuickAddVocabulary): QuickAddSuggestion[] {
+  const suggestions: QuickAddSuggestion[] = [];
+  for (const word of text.trim().split(/\s+/)) {
+    if (word === "--") {
+      break;
+    }
+    let suggestion: string | null = null;
+    if (word.length > 1 && word.startsWith("+")) {
+      const tag = closestMatch(word.slice(1), vocabulary.tags);
+      suggestion = tag === null ? null : `+${tag}`;
+    } else {
+      const [rawKey, value] = splitModifier(word);
+      if (rawKey !== null && value !== null) {
+        const known = QUICK_ADD_KEYS.includes(QUICK_ADD_KEY_ALIASES[rawKey] ?? rawKey);
+        const key = known ? rawKey : closestMatch(rawKey, QUICK_ADD_KEYS);
+        const isProject = key !== null && (QUICK_ADD_KEY_ALIASES[key] ?? key) === "project";
+        const fixedValue = isProject && value ? closestMatch(value, vocabulary.projects) ?? value : value;
+        if (key !== null) {
+          suggestion = `${key}${word.charAt(rawKey.length)}${fixedValue}`;
+        }
+      }
+    }
+    if (suggestion !== null && suggestion.toLowerCase() !== word.toLowerCase() && !suggestions.some((entry) => entry.original === word)) {
+      suggestions.push({ original: word, suggestion });
+    }
+  }
+  return suggestions;
+}
+
+// Swaps every whole-word occurrence of the typo for the suggestion.
+export function applyQuickAddSuggestion(text: string, suggestion: QuickAddSuggestion): string {
+  return text
+    .split(/(\s+)/)
+    .map((part) => (part === suggestion.original ? suggestion.suggestion : part))
+    .join("");
+}
+
+export function buildCaptureTask(text: string, config: RivetRuntimeConfig | null): TaskCreate | null {
+  const parsed = parseQuickAdd(text);
+  const title = parsed.title;
+  if (title.length === 0) {
+    return null;
+  }
+  const capture = config?.capture;
+  const tags = [captureTag(config), ...(capture?.tags ?? []), ...parsed.tags]
+    .map((tag) => tag.trim())
+    .filter((tag, index, all) => tag.length > 0 && all.indexOf(tag) === index);
+  const project = parsed.project ?? (capture?.project ?? "").trim();
+  return {
+    title,
+    description: "",
+    project: project.length > 0 ? project : null,
+    tags,
+    priority: parsed.priority ?? CAPTURE_PRIORITIES[(capture?.priority ?? "").trim().toLowerCase()] ?? null,
+    due: null,
+    wait: null,
+    scheduled: null
+  };
+}
+
+// Captures still to be processed. Waiting tasks were already deferred on
+// purpose, so they stay out of the inbox until they wake up.
+export function isInboxTask(task: TaskDto, tag: string): boolean {
+  return task.status === "Pending" && task.tags.includes(tag);
+}
@@ -0,0 +1,53 @@
+import { describe, expect, it } from "vitest";
+
+import { defaultCompletedRange, groupTasksByCompletionDay, sortByCompletion } from "./completed";
+import { toCalendarDate } from "./calendar";
+import type { TaskDto } from "../types/core";
+
+function task(uuid: string, completed: string | null): TaskDto {
+  return {
+    uuid,
+    id: null,
+    title: uuid,
+    description: "",
+    status: "Completed",
+    project: null,
+    tags: [],
+    priority: null,
+    due: null,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null
+  };
+}
+
+describe("completed view helpers", () => {
+  it("defaults to the last seven days", () => {
+    expect(defaultCompletedRange(toCalendarDate(2026, 3, 4))).toEqual({ from: "2026-02-26", to: "2026-03-04" });
+  });
+
+  it("sorts newest first and groups by project-local completion day", () => {
+    const tasks = [
+      task("early", "20260301T090000Z"),
+      task("unknown", null),
+      task("late", "20260302T030000Z"),
+      task("mid", "2026-03-01T20:00:00Z"),
+      task("next", "20260302T060000Z")
+    ];
+    expect(sortByCompletion(tasks).map((entry) => entry.uuid)).toEqual(["next", "late", "mid", "early", "unknown"]);
+
+    const groups = groupTasksByCompletionDay(tasks, "America/New_York");
+    expect(groups.map((group) => [group.day, group.tasks.map((entry) => entry.uuid)])).toEqual([
+      ["2026-03-02", ["next"]],
+      ["2026-03-01", ["late", "mid", "early"]],
+      [null, ["unknown"]]
+    ]);
+  });
+});
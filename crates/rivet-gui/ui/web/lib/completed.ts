@@ -0,0 +1,53 @@
+import type { TaskDto } from "../types/core";
+import { addDays, calendarDateToIso, parseTaskDueUtcMs, toCalendarDate, zonedDateTimeParts } from "./calendar";
+
+// Inclusive `YYYY-MM-DD` bounds; an empty string leaves that side open.
+export interface CompletedRange {
+  from: string;
+  to: string;
+}
+
+export interface CompletionDayGroup {
+  day: string | null;
+  tasks: TaskDto[];
+}
+
+// The last seven days including today: the usual weekly review window.
+export function defaultCompletedRange(today: Date): CompletedRange {
+  return {
+    from: calendarDateToIso(addDays(today, -6)),
+    to: calendarDateToIso(today)
+  };
+}
+
+function completedUtcMs(task: TaskDto): number | null {
+  return task.completed ? parseTaskDueUtcMs(task.completed) : null;
+}
+
+export function completionDay(task: TaskDto, timezone: string): string | null {
+  const utcMs = completedUtcMs(task);
+  if (utcMs === null) {
+    return null;
+  }
+  const parts = zonedDateTimeParts(utcMs, timezone);
+  return calendarDateToIso(toCalendarDate(parts.year, parts.month, parts.day));
+}
+
+// Most recently completed first; tasks without a completion time sink.
+export function sortByCompletion(tasks: TaskDto[]): TaskDto[] {
+  return [...tasks].sort((left, right) => (completedUtcMs(right) ?? -Infinity) - (completedUtcMs(left) ?? -Infinity));
+}
+
+export function groupTasksByCompletionDay(tasks: TaskDto[], timezone: string): CompletionDayGroup[] {
+  const groups: CompletionDayGroup[] = [];
+  for (const task of sortByCompletion(tasks)) {
+    const day = completionDay(task, timezone);
+    const last = groups[groups.length - 1];
+    if (last && last.day === day) {
+      last.tasks.push(task);
+    } else {
+      groups.push({ day, tasks: [task] });
+    }
+  }
+  return groups;
+}
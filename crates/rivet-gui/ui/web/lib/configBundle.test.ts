@@ -0,0 +1,62 @@
+import { describe, expect, it } from "vitest";
+
+import type { ExternalCalendarSource } from "../types/core";
+import type { KanbanBoardDef } from "../types/ui";
+import { buildGuiSettingsBundle, guiSettingsConflicts, mergeGuiSettings, parseGuiSettingsBundle } from "./configBundle";
+
+function board(id: string, name: string, color = "#336699"): KanbanBoardDef {
+  return { id, name, color };
+}
+
+function calendar(id: string, name: string, location = "https://example.com/cal.ics"): ExternalCalendarSource {
+  return {
+    id,
+    name,
+    color: "#884422",
+    location,
+    refresh_minutes: 30,
+    enabled: true,
+    imported_ics_file: false,
+    read_only: true,
+    show_reminders: false,
+    offline_support: false
+  };
+}
+
+describe("parseGuiSettingsBundle", () => {
+  it("round-trips the built section and rejects malformed ones", () => {
+    const built = buildGuiSettingsBundle([board("b1", "Main")], [calendar("c1", "Holidays")]);
+    expect(parseGuiSettingsBundle(JSON.parse(JSON.stringify(built)))).toEqual(built);
+    expect(parseGuiSettingsBundle({ kanban_boards: [board("b2", "Work")] })).toEqual({
+      kanban_boards: [board("b2", "Work")],
+      external_calendars: []
+    });
+    expect(parseGuiSettingsBundle(null)).toBeNull();
+    expect(parseGuiSettingsBundle({ kanban_boards: [{ id: "", name: "x", color: "" }] })).toBeNull();
+  });
+});
+
+describe("guiSettingsConflicts", () => {
+  it("lists entries that share an id but differ", () => {
+    const bundle = buildGuiSettingsBundle([board("b1", "Main", "#000000"), board("b2", "Work")], [calendar("c1", "Holidays")]);
+    expect(guiSettingsConflicts(bundle, [board("b1", "Main"), board("b3", "Home")], [calendar("c1", "Holidays")])).toEqual([
+      'board "Main"'
+    ]);
+  });
+});
+
+describe("mergeGuiSettings", () => {
+  it("adds new ids and replaces existing ones only when overwriting", () => {
+    const bundle = buildGuiSettingsBundle([board("b1", "Renamed"), board("b2", "Work")], [calendar("c1", "Moved", "https://example.com/new.ics")]);
+    const boards = [board("b1", "Main")];
+    const calendars = [calendar("c1", "Holidays")];
+
+    const kept = mergeGuiSettings(bundle, boards, calendars, false);
+    expect(kept.kanban_boards.map((entry) => entry.name)).toEqual(["Main", "Work"]);
+    expect(kept.external_calendars[0]?.name).toBe("Holidays");
+
+    const replaced = mergeGuiSettings(bundle, boards, calendars, true);
+    expect(replaced.kanban_boards.map((entry) => entry.name)).toEqual(["Renamed", "Work"]);
+    expect(replaced.external_calendars[0]?.location).toBe("https://example.com/new.ics");
+  });
+});
@@ -0,0 +1,87 @@
+import { GuiSettingsBundleSchema } from "../api/schemas";
+import type { ExternalCalendarSource } from "../types/core";
+import type { KanbanBoardDef } from "../types/ui";
+
+// GUI-only preferences carried in the `gui` section of a settings bundle.
+// The CLI passes this section through untouched.
+export interface GuiSettingsBundle {
+  kanban_boards: KanbanBoardDef[];
+  external_calendars: ExternalCalendarSource[];
+}
+
+export function buildGuiSettingsBundle(boards: readonly KanbanBoardDef[], calendars: readonly ExternalCalendarSource[]): GuiSettingsBundle {
+  return {
+    kanban_boards: boards.map((board) => ({ ...board })),
+    external_calendars: calendars.map((source) => ({ ...source }))
+  };
+}
+
+// Returns null when the bundle has no usable `gui` section (e.g. one written
+// by `task config export` on a machine that never ran the GUI).
+export function parseGuiSettingsBundle(raw: unknown): GuiSettingsBundle | null {
+  if (raw === null || raw === undefined) {
+    return null;
+  }
+  const parsed = GuiSettingsBundleSchema.safeParse(raw);
+  if (!parsed.success) {
+    return null;
+  }
+  return {
+    kanban_boards: (parsed.data.kanban_boards ?? []) as KanbanBoardDef[],
+    external_calendars: parsed.data.external_calendars ?? []
+  };
+}
+
+function sameJson(left: unknown, right: unknown): boolean {
+  return JSON.stringify(left) === JSON.stringify(right);
+}
+
+// Boards and calendars that exist locally under the same id but differ from
+// the bundled copy, as labels for the import confirmation.
+export function guiSettingsConflicts(
+  bundle: GuiSettingsBundle,
+  boards: readonly KanbanBoardDef[],
+  calendars: readonly ExternalCalendarSource[]
+): string[] {
+  const conflicts: string[] = [];
+  for (const board of bundle.kanban_boards) {
+    const local = boards.find((candidate) => candidate.id === board.id);
+    if (local && !sameJson(local, board)) {
+      conflicts.push(`board "${local.name}"`);
+    }
+  }
+  for (const source of bundle.external_calendars) {
+    const local = calendars.find((candidate) => candidate.id === source.id);
+    if (local && !sameJson(local, source)) {
+      conflicts.push(`calendar "${local.name}"`);
+    }
+  }
+  return conflicts;
+}
+
+function mergeById<T extends { id: string }>(local: readonly T[], incoming: readonly T[], overwrite: boolean): T[] {
+  const merged = local.map((item) => {
+    const replacement = overwrite ? incoming.find((candidate) => candidate.id === item.id) : undefined;
+    return replacement ? { ...replacement } : item;
+  });
+  for (const item of incoming) {
+    if (!local.some((candidate) => candidate.id === item.id)) {
+      merged.push({ ...item });
+    }
+  }
+  return merged;
+}
+
+// Bundled entries with new ids are always added; entries whose id already
+// exists replace the local copy only when `overwrite` is set.
+export function mergeGuiSettings(
+  bundle: GuiSettingsBundle,
+  boards: readonly KanbanBoardDef[],
+  calendars: readonly ExternalCalendarSource[],
+  overwrite: boolean
+): GuiSettingsBundle {
+  return {
+    kanban_boards: mergeById(boards, bundle.kanban_boards, overwrite),
+    external_calendars: mergeById(calendars, bundle.external_calendars, overwrite)
+  };
+}
@@ -0,0 +1,39 @@
+import { describe, expect, it } from "vitest";
+
+import type { ConfigUpdateResult } from "../api/tauri";
+import { describeConfigUpdate, rejectedConfigUpdates } from "./configUpdates";
+
+function result(partial: Partial<ConfigUpdateResult>): ConfigUpdateResult {
+  return {
+    section: "notifications.due",
+    key: "pre_notify_minutes",
+    status: "applied",
+    reason: null,
+    previous: 15,
+    effective: 30,
+    ...partial
+  };
+}
+
+describe("describeConfigUpdate", () => {
+  it("describes each status", () => {
+    expect(describeConfigUpdate(result({}))).toBe("notifications.due.pre_notify_minutes: 15 → 30");
+    expect(describeConfigUpdate(result({ section: "ui.theme", key: "mode", previous: null, effective: "night" }))).toBe(
+      'ui.theme.mode: unset → "night"'
+    );
+    expect(describeConfigUpdate(result({ status: "unchanged", effective: 15 }))).toBe(
+      "notifications.due.pre_notify_minutes: unchanged (15)"
+    );
+    expect(
+      describeConfigUpdate(result({ status: "rejected", reason: "expected a whole number from 1 to 43200", effective: 15 }))
+    ).toBe("notifications.due.pre_notify_minutes: rejected, expected a whole number from 1 to 43200");
+    expect(describeConfigUpdate(result({ status: "skipped" }))).toBe(
+      "notifications.due.pre_notify_minutes: not saved, another update was rejected"
+    );
+  });
+
+  it("picks out rejected updates", () => {
+    const rejected = result({ key: "enabled", status: "rejected", reason: "expected a boolean, got a string" });
+    expect(rejectedConfigUpdates([result({}), rejected, result({ status: "skipped" })])).toEqual([rejected]);
+  });
+});
@@ -0,0 +1,27 @@
+import type { ConfigUpdateResult } from "../api/tauri";
+
+function formatConfigValue(value: unknown): string {
+  if (value === null || value === undefined) {
+    return "unset";
+  }
+  return typeof value === "string" ? `"${value}"` : String(value);
+}
+
+// One line per `config_apply_updates` result for the settings dialog.
+export function describeConfigUpdate(result: ConfigUpdateResult): string {
+  const name = `${result.section}.${result.key}`;
+  switch (result.status) {
+    case "applied":
+      return `${name}: ${formatConfigValue(result.previous)} → ${formatConfigValue(result.effective)}`;
+    case "unchanged":
+      return `${name}: unchanged (${formatConfigValue(result.effective)})`;
+    case "rejected":
+      return `${name}: rejected, ${result.reason ?? "invalid value"}`;
+    case "skipped":
+      return `${name}: not saved, another update was rejected`;
+  }
+}
+
+export function rejectedConfigUpdates(results: readonly ConfigUpdateResult[]): ConfigUpdateResult[] {
+  return results.filter((result) => result.status === "rejected");
+}
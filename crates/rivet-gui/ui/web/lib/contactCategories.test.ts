@@ -0,0 +1,28 @@
+import { describe, expect, it } from "vitest";
+
+import type { ContactDto } from "../types/core";
+import { contactHasCategory, countContactCategories, normalizeCategories, parseVcardCategories } from "./contactCategories";
+
+function contactWith(categories: string[]): ContactDto {
+  return { categories } as ContactDto;
+}
+
+describe("contact categories", () => {
+  it("normalizes like the backend: trimmed, unique ignoring case", () => {
+    expect(normalizeCategories([" Work", "friends", "work ", "", "Friends"])).toEqual(["Work", "friends"]);
+  });
+
+  it("splits vCard CATEGORIES on unescaped commas", () => {
+    expect(parseVcardCategories("Vendors, Work,Parts\\, Tools")).toEqual(["Vendors", "Work", "Parts, Tools"]);
+  });
+
+  it("matches and counts categories case-insensitively", () => {
+    const contacts = [contactWith(["Work", "Friends"]), contactWith(["work"]), contactWith([])];
+    expect(contactHasCategory(contacts[1], "WORK")).toBe(true);
+    expect(contactHasCategory(contacts[2], "work")).toBe(false);
+    expect(countContactCategories(contacts)).toEqual([
+      { category: "Work", count: 2 },
+      { category: "Friends", count: 1 }
+    ]);
+  });
+});
@@ -0,0 +1,62 @@
+import type { ContactCategoryCount, ContactDto } from "../types/core";
+
+// Trims, drops empties and repeats (case-insensitive, first spelling wins),
+// matching what the backend stores.
+export function normalizeCategories(categories: string[]): string[] {
+  const seen = new Set<string>();
+  const out: string[] = [];
+  for (const raw of categories) {
+    const category = raw.trim();
+    const key = category.toLowerCase();
+    if (category.length === 0 || seen.has(key)) {
+      continue;
+    }
+    seen.add(key);
+    out.push(category);
+  }
+  return out;
+}
+
+// vCard CATEGORIES value: commas separate, `\,` is a literal comma.
+export function parseVcardCategories(value: string): string[] {
+  const out: string[] = [];
+  let current = "";
+  for (let index = 0; index < value.length; index += 1) {
+    const ch = value[index];
+    if (ch === "\\" && index + 1 < value.length) {
+      current += value[index + 1];
+      index += 1;
+    } else if (ch === ",") {
+      out.push(current);
+      current = "";
+    } else {
+      current += ch;
+    }
+  }
+  out.push(current);
+  return normalizeCategories(out);
+}
+
+export function contactHasCategory(contact: ContactDto, category: string): boolean {
+  const wanted = category.trim().toLowerCase();
+  return contact.categories.some((value) => value.toLowerCase() === wanted);
+}
+
+// Facet counts, most used first, then by name.
+export function countContactCategories(contacts: ContactDto[]): ContactCategoryCount[] {
+  const counts = new Map<string, ContactCategoryCount>();
+  for (const contact of contacts) {
+    for (const category of contact.categories) {
+      const key = category.toLowerCase();
+      const entry = counts.get(key);
+      if (entry) {
+        entry.count += 1;
+      } else {
+        counts.set(key, { category, count: 1 });
+      }
+    }
+  }
+  return [...counts.values()].sort(
+    (left, right) => right.count - left.count || left.category.toLowerCase().localeCompare(right.category.toLowerCase())
+  );
+}
@@ -0,0 +1,36 @@
+import { describe, expect, it } from "vitest";
+
+import { contactedWithinDays, lastContactedByContact, lastContactedLabel, sortContactsByRecency } from "./contactInteractions";
+
+const NOW = Date.parse("2026-03-10T12:00:00Z");
+
+describe("contact interactions", () => {
+  it("keeps the newest interaction per contact", () => {
+    const newest = lastContactedByContact([
+      { id: "1", contact_id: "a", kind: "email", value: "a@example.com", at: "2026-03-01T10:00:00Z" },
+      { id: "2", contact_id: "a", kind: "phone", value: "+1", at: "2026-03-09T10:00:00Z" },
+      { id: "3", contact_id: "b", kind: "email", value: "b@example.com", at: "2026-02-01T10:00:00Z" }
+    ]);
+    expect(newest.get("a")).toBe("2026-03-09T10:00:00Z");
+    expect(newest.get("b")).toBe("2026-02-01T10:00:00Z");
+  });
+
+  it("sorts by recency with never-contacted last", () => {
+    const sorted = sortContactsByRecency([
+      { id: "never", last_contacted: null },
+      { id: "old", last_contacted: "2026-01-01T00:00:00Z" },
+      { id: "new", last_contacted: "2026-03-09T00:00:00Z" }
+    ]);
+    expect(sorted.map((contact) => contact.id)).toEqual(["new", "old", "never"]);
+  });
+
+  it("filters and labels by days since contact", () => {
+    expect(contactedWithinDays({ last_contacted: "2026-03-05T12:00:00Z" }, 7, NOW)).toBe(true);
+    expect(contactedWithinDays({ last_contacted: "2026-02-05T12:00:00Z" }, 7, NOW)).toBe(false);
+    expect(contactedWithinDays({ last_contacted: null }, 7, NOW)).toBe(false);
+    expect(lastContactedLabel("2026-03-10T08:00:00Z", NOW)).toBe("Contacted today");
+    expect(lastContactedLabel("2026-03-09T08:00:00Z", NOW)).toBe("Contacted yesterday");
+    expect(lastContactedLabel("2026-03-01T12:00:00Z", NOW)).toBe("Contacted 9 days ago");
+    expect(lastContactedLabel(null, NOW)).toBeNull();
+  });
+});
@@ -0,0 +1,58 @@
+import type { ContactDto, ContactInteraction } from "../types/core";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+// Choices for the "Contacted within" filter, in days.
+export const CONTACTED_WITHIN_DAYS_OPTIONS = [7, 30, 90, 365];
+
+function interactionMs(raw: string | null | undefined): number | null {
+  if (!raw) {
+    return null;
+  }
+  const ms = Date.parse(raw);
+  return Number.isNaN(ms) ? null : ms;
+}
+
+// Newest interaction per contact, like the backend's `last_contacted`.
+export function lastContactedByContact(interactions: ContactInteraction[]): Map<string, string> {
+  const newest = new Map<string, string>();
+  for (const interaction of interactions) {
+    const current = newest.get(interaction.contact_id);
+    if ((interactionMs(interaction.at) ?? -Infinity) > (interactionMs(current) ?? -Infinity)) {
+      newest.set(interaction.contact_id, interaction.at);
+    }
+  }
+  return newest;
+}
+
+export function contactedWithinDays(contact: Pick<ContactDto, "last_contacted">, days: number, nowMs: number): boolean {
+  const at = interactionMs(contact.last_contacted);
+  return at !== null && at >= nowMs - days * DAY_MS;
+}
+
+// Most recently contacted first, never-contacted last; ties keep their order.
+export function sortContactsByRecency<T extends Pick<ContactDto, "last_contacted">>(contacts: T[]): T[] {
+  return [...contacts].sort((a, b) => {
+    const left = interactionMs(a.last_contacted);
+    const right = interactionMs(b.last_contacted);
+    if (left === right) {
+      return 0;
+    }
+    if (left === null || right === null) {
+      return left === null ? 1 : -1;
+    }
+    return right - left;
+  });
+}
+
+export function lastContactedLabel(raw: string | null | undefined, nowMs: number): string | null {
+  const at = interactionMs(raw);
+  if (at === null) {
+    return null;
+  }
+  const days = Math.floor((nowMs - at) / DAY_MS);
+  if (days <= 0) {
+    return "Contacted today";
+  }
+  return days === 1 ? "Contacted yesterday" : `Contacted ${days} days ago`;
+}
@@ -0,0 +1,57 @@
+import { describe, expect, it } from "vitest";
+
+import { formatDateWith, formatTaskDate, humanize, parseDateFormat, resolveDateFormat } from "./dateFormat";
+
+const TZ = "America/Mexico_City";
+const NOW = Date.UTC(2026, 1, 17, 18, 5, 0);
+
+describe("dateFormat", () => {
+  it("renders Taskwarrior codes in the project timezone", () => {
+    const format = parseDateFormat("D/M/Y H:N \\a\\t a");
+    expect(formatDateWith(format, NOW, TZ, NOW)).toBe("17/02/2026 12:05 at Tue");
+  });
+
+  it("rejects unknown codes with the valid list", () => {
+    expect(() => parseDateFormat("Y-Q")).toThrow(/'Q'/);
+  });
+
+  it("supports relative output", () => {
+    const relative = parseDateFormat("relative");
+    expect(formatDateWith(relative, NOW + 3 * 86_400_000, TZ, NOW)).toBe("in 3 days");
+    expect(formatDateWith(relative, NOW - 2 * 3_600_000, TZ, NOW)).toBe("2 hours ago");
+  });
+
+  it("humanizes past and future at unit boundaries like the core", () => {
+    const cases: Array<[offsetSeconds: number, long: string, short: string]> = [
+      [0, "just now", "now"],
+      [59, "just now", "now"],
+      [-59, "just now", "now"],
+      [60, "in 1 minute", "in 1m"],
+      [-59 * 60, "59 minutes ago", "59m ago"],
+      [-2 * 3_600, "2 hours ago", "2h ago"],
+      [23 * 3_600, "in 23 hours", "in 23h"],
+      [-24 * 3_600, "yesterday", "1d ago"],
+      [47 * 3_600, "tomorrow", "in 1d"],
+      [3 * 86_400, "in 3 days", "in 3d"],
+      [-13 * 86_400, "13 days ago", "13d ago"],
+      [14 * 86_400, "in 2 weeks", "in 2w"],
+      [-60 * 86_400, "2 months ago", "2mo ago"],
+      [365 * 86_400, "in 1 year", "in 1y"]
+    ];
+    for (const [offset, long, short] of cases) {
+      expect(humanize(NOW + offset * 1000, NOW)).toBe(long);
+      expect(humanize(NOW + offset * 1000, NOW, "short")).toBe(short);
+    }
+  });
+
+  it("takes the relative style from [time].humanize", () => {
+    const format = resolveDateFormat({ time: { dateformat: "relative", humanize: "short" } });
+    expect(formatTaskDate("20260220T180500Z", format, TZ, NOW)).toBe("in 3d");
+  });
+
+  it("formats raw task dates and falls back on invalid config", () => {
+    const format = resolveDateFormat({ time: { dateformat: "Q" } });
+    expect(formatTaskDate("20260217T180500Z", format, TZ, NOW)).toBe("2026-02-17");
+    expect(formatTaskDate("not a date", format, TZ, NOW)).toBe("not a date");
+  });
+});
@@ -0,0 +1,162 @@
+import { isoWeekOf, parseTaskDueUtcMs, toCalendarDate, zonedDateTimeParts } from "./calendar";
+import { logger } from "./logger";
+import { timezoneAbbreviation } from "./timezone";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { ZonedDateTimeParts } from "../types/ui";
+
+// Taskwarrior-style display codes, mirroring `rivet_core::datetime::DateFormat`.
+export const DEFAULT_DATE_FORMAT = "Y-M-D";
+
+const WEEKDAY_NAMES = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+const MONTH_NAMES = [
+  "January",
+  "February",
+  "March",
+  "April",
+  "May",
+  "June",
+  "July",
+  "August",
+  "September",
+  "October",
+  "November",
+  "December"
+];
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+type DateFormatToken = { code: string } | { literal: string };
+
+// Mirrors `rivet_core::datetime::HumanizeStyle`: "in 3 days" vs "in 3d".
+export type HumanizeStyle = "long" | "short";
+
+export type DateFormat = { kind: "codes"; tokens: DateFormatToken[] } | { kind: "relative"; style: HumanizeStyle };
+
+const pad = (value: number, width = 2) => String(value).padStart(width, "0");
+
+function dayOfYear(parts: ZonedDateTimeParts): number {
+  const start = Date.UTC(parts.year, 0, 1);
+  return Math.floor((Date.UTC(parts.year, parts.month - 1, parts.day) - start) / DAY_MS) + 1;
+}
+
+const CODE_RENDERERS: Record<string, (parts: ZonedDateTimeParts) => string> = {
+  Y: (parts) => String(parts.year),
+  y: (parts) => pad(parts.year % 100),
+  M: (parts) => pad(parts.month),
+  m: (parts) => String(parts.month),
+  D: (parts) => pad(parts.day),
+  d: (parts) => String(parts.day),
+  H: (parts) => pad(parts.hour),
+  h: (parts) => String(parts.hour),
+  N: (parts) => pad(parts.minute),
+  n: (parts) => String(parts.minute),
+  S: (parts) => pad(parts.second),
+  s: (parts) => String(parts.second),
+  A: (parts) => WEEKDAY_NAMES[parts.weekday] ?? "",
+  a: (parts) => (WEEKDAY_NAMES[parts.weekday] ?? "").slice(0, 3),
+  B: (parts) => MONTH_NAMES[parts.month - 1] ?? "",
+  b: (parts) => (MONTH_NAMES[parts.month - 1] ?? "").slice(0, 3),
+  V: (parts) => pad(isoWeekOf(toCalendarDate(parts.year, parts.month, parts.day)).week),
+  J: (parts) => pad(dayOfYear(parts), 3),
+  j: (parts) => String(dayOfYear(parts))
+};
+
+export function parseDateFormat(spec: string, style: HumanizeStyle = "long"): DateFormat {
+  if (spec.trim().toLowerCase() === "relative") {
+    return { kind: "relative", style };
+  }
+  const tokens: DateFormatToken[] = [];
+  for (let index = 0; index < spec.length; index += 1) {
+    const ch = spec[index];
+    if (ch === "\\") {
+      const literal = spec[index + 1];
+      if (literal === undefined) {
+        throw new Error(`dangling '\\' at end of date format '${spec}'`);
+      }
+      tokens.push({ literal });
+      index += 1;
+    } else if (ch in CODE_RENDERERS) {
+      tokens.push({ code: ch });
+    } else if (/[A-Za-z]/.test(ch)) {
+      throw new Error(
+        `unknown date format code '${ch}' in '${spec}' (valid codes: ${Object.keys(CODE_RENDERERS).join("")}; use 'relative' for relative dates or '\\${ch}' for a literal)`
+      );
+    } else {
+      tokens.push({ literal: ch });
+    }
+  }
+  return { kind: "codes", tokens };
+}
+
+const HUMANIZE_UNITS: Array<[limitSeconds: number, unitSeconds: number, long: string, short: string]> = [
+  [3_600, 60, "minute", "m"],
+  [86_400, 3_600, "hour", "h"],
+  [14 * 86_400, 86_400, "day", "d"],
+  [60 * 86_400, 7 * 86_400, "week", "w"],
+  [365 * 86_400, 30 * 86_400, "month", "mo"],
+  [Number.POSITIVE_INFINITY, 365 * 86_400, "year", "y"]
+];
+
+// Same rules as `rivet_core::datetime::humanize_with`, so the GUI and CLI phrase
+// relative times identically.
+export function humanize(utcMs: number, nowUtcMs: number, style: HumanizeStyle = "long"): string {
+  const deltaSeconds = Math.trunc((utcMs - nowUtcMs) / 1000);
+  const future = deltaSeconds > 0;
+  const seconds = Math.abs(deltaSeconds);
+  if (seconds < 60) {
+    return style === "long" ? "just now" : "now";
+  }
+  const [, unitSeconds, unit, short] = HUMANIZE_UNITS.find(([limit]) => seconds < limit) ?? HUMANIZE_UNITS[HUMANIZE_UNITS.length - 1];
+  const amount = Math.floor(seconds / unitSeconds);
+  if (style === "long" && unit === "day" && amount === 1) {
+    return future ? "tomorrow" : "yesterday";
+  }
+  const label = style === "long" ? `${amount} ${unit}${amount === 1 ? "" : "s"}` : `${amount}${short}`;
+  return future ? `in ${label}` : `${label} ago`;
+}
+
+export function formatDateWith(format: DateFormat, utcMs: number, timezone: string, nowUtcMs: number): string {
+  if (format.kind === "relative") {
+    return humanize(utcMs, nowUtcMs, format.style);
+  }
+  const parts = zonedDateTimeParts(utcMs, timezone);
+  return format.tokens.map((token) => ("code" in token ? CODE_RENDERERS[token.code](parts) : token.literal)).join("");
+}
+
+export function resolveHumanizeStyle(runtimeConfig: RivetRuntimeConfig | null): HumanizeStyle {
+  const raw = runtimeConfig?.time?.humanize?.trim();
+  if (raw === undefined || raw === "" || raw === "long" || raw === "short") {
+    return raw === "short" ? "short" : "long";
+  }
+  logger.warn("config.humanize", `invalid humanize style '${raw}' (expected long or short)`);
+  return "long";
+}
+
+export function resolveDateFormat(runtimeConfig: RivetRuntimeConfig | null): DateFormat {
+  const spec = runtimeConfig?.time?.dateformat?.trim() || DEFAULT_DATE_FORMAT;
+  try {
+    return parseDateFormat(spec, resolveHumanizeStyle(runtimeConfig));
+  } catch (error) {
+    logger.warn("config.dateformat", String(error));
+    return parseDateFormat(DEFAULT_DATE_FORMAT);
+  }
+}
+
+export function formatTaskDate(rawDate: string, format: DateFormat, timezone: string, nowUtcMs: number): string {
+  const utcMs = parseTaskDueUtcMs(rawDate);
+  return utcMs === null ? rawDate : formatDateWith(format, utcMs, timezone, nowUtcMs);
+}
+
+export function humanizeTaskDate(rawDate: string, nowUtcMs: number, style: HumanizeStyle): string {
+  const utcMs = parseTaskDueUtcMs(rawDate);
+  return utcMs === null ? rawDate : humanize(utcMs, nowUtcMs, style);
+}
+
+// Absolute dates get the zone abbreviation so a reader can tell which clock they are on.
+export function formatTaskDateWithZone(rawDate: string, format: DateFormat, timezone: string, nowUtcMs: number): string {
+  const utcMs = parseTaskDueUtcMs(rawDate);
+  if (utcMs === null) {
+    return rawDate;
+  }
+  const formatted = formatDateWith(format, utcMs, timezone, nowUtcMs);
+  return format.kind === "relative" ? formatted : `${formatted} ${timezoneAbbreviation(utcMs, timezone)}`;
+}
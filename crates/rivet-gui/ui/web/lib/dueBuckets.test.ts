@@ -0,0 +1,70 @@
+import { describe, expect, it } from "vitest";
+
+import type { TaskDto } from "../types/core";
+import { dueBucketForUtcMs, groupTasksByDue } from "./dueBuckets";
+
+const TZ = "America/Mexico_City";
+// Tuesday 2026-02-17 06:00 local.
+const NOW = Date.UTC(2026, 1, 17, 12, 0, 0);
+
+function at(day: number, hour: number): number {
+  return Date.UTC(2026, 1, day, hour, 0, 0);
+}
+
+function makeTask(uuid: string, due: string | null): TaskDto {
+  return {
+    uuid,
+    id: null,
+    title: uuid,
+    description: "",
+    status: "Pending",
+    project: null,
+    tags: [],
+    priority: null,
+    due,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null
+  };
+}
+
+describe("dueBucketForUtcMs", () => {
+  it("buckets by project-local day", () => {
+    expect(dueBucketForUtcMs(null, NOW, TZ, "monday")).toBe("no_due");
+    expect(dueBucketForUtcMs(at(17, 11), NOW, TZ, "monday")).toBe("overdue");
+    expect(dueBucketForUtcMs(at(17, 20), NOW, TZ, "monday")).toBe("today");
+    expect(dueBucketForUtcMs(at(18, 18), NOW, TZ, "monday")).toBe("tomorrow");
+  });
+
+  it("ends this week according to week_start", () => {
+    expect(dueBucketForUtcMs(at(21, 18), NOW, TZ, "sunday")).toBe("this_week");
+    expect(dueBucketForUtcMs(at(22, 18), NOW, TZ, "monday")).toBe("this_week");
+    expect(dueBucketForUtcMs(at(22, 18), NOW, TZ, "sunday")).toBe("later");
+  });
+});
+
+describe("groupTasksByDue", () => {
+  it("returns non-empty groups in urgency order sorted by due", () => {
+    const groups = groupTasksByDue(
+      [
+        makeTask("none", null),
+        makeTask("later", "20260310T180000Z"),
+        makeTask("today-late", "20260217T230000Z"),
+        makeTask("today-early", "20260217T150000Z"),
+        makeTask("late", "20260210T180000Z")
+      ],
+      NOW,
+      TZ,
+      "monday"
+    );
+    expect(groups.map((group) => group.bucket)).toEqual(["overdue", "today", "later", "no_due"]);
+    expect(groups[1].tasks.map((task) => task.uuid)).toEqual(["today-early", "today-late"]);
+  });
+});
@@ -0,0 +1,100 @@
+import type { TaskDto } from "../types/core";
+import type { CalendarWeekStart } from "../types/ui";
+import { addDays, parseTaskDueUtcMs, startOfWeek, toCalendarDate, zonedDateTimeParts } from "./calendar";
+
+export type DueBucket = "overdue" | "today" | "tomorrow" | "this_week" | "later" | "no_due";
+
+export const DUE_BUCKET_ORDER: DueBucket[] = ["overdue", "today", "tomorrow", "this_week", "later", "no_due"];
+
+export const DUE_BUCKET_LABELS: Record<DueBucket, string> = {
+  overdue: "Overdue",
+  today: "Today",
+  tomorrow: "Tomorrow",
+  this_week: "This Week",
+  later: "Later",
+  no_due: "No Due"
+};
+
+export interface DueBucketGroup {
+  bucket: DueBucket;
+  label: string;
+  tasks: TaskDto[];
+}
+
+function zonedCalendarDate(utcMs: number, timezone: string): Date {
+  const parts = zonedDateTimeParts(utcMs, timezone);
+  return toCalendarDate(parts.year, parts.month, parts.day);
+}
+
+// Mirrors `rivet_core::datetime::due_bucket` so the list and the CLI agree on boundaries.
+export function dueBucketForUtcMs(
+  dueUtcMs: number | null,
+  nowUtcMs: number,
+  timezone: string,
+  weekStart: CalendarWeekStart | string
+): DueBucket {
+  if (dueUtcMs === null) {
+    return "no_due";
+  }
+  if (dueUtcMs < nowUtcMs) {
+    return "overdue";
+  }
+
+  const today = zonedCalendarDate(nowUtcMs, timezone);
+  const dueDate = zonedCalendarDate(dueUtcMs, timezone).getTime();
+  const weekEnd = addDays(startOfWeek(today, weekStart), 6).getTime();
+
+  if (dueDate <= today.getTime()) {
+    return "today";
+  }
+  if (dueDate === addDays(today, 1).getTime()) {
+    return "tomorrow";
+  }
+  if (dueDate <= weekEnd) {
+    return "this_week";
+  }
+  return "later";
+}
+
+export function dueBucketFor(
+  task: TaskDto,
+  nowUtcMs: number,
+  timezone: string,
+  weekStart: CalendarWeekStart | string
+): DueBucket {
+  const rawDue = task.due?.trim();
+  const dueUtcMs = rawDue ? parseTaskDueUtcMs(rawDue) : null;
+  return dueBucketForUtcMs(dueUtcMs, nowUtcMs, timezone, weekStart);
+}
+
+export function groupTasksByDue(
+  tasks: TaskDto[],
+  nowUtcMs: number,
+  timezone: string,
+  weekStart: CalendarWeekStart | string
+): DueBucketGroup[] {
+  const byBucket = new Map<DueBucket, Array<{ task: TaskDto; dueUtcMs: number | null }>>();
+  for (const task of tasks) {
+    const rawDue = task.due?.trim();
+    const dueUtcMs = rawDue ? parseTaskDueUtcMs(rawDue) : null;
+    const bucket = dueBucketForUtcMs(dueUtcMs, nowUtcMs, timezone, weekStart);
+    const entries = byBucket.get(bucket) ?? [];
+    entries.push({ task, dueUtcMs });
+    byBucket.set(bucket, entries);
+  }
+
+  const groups: DueBucketGroup[] = [];
+  for (const bucket of DUE_BUCKET_ORDER) {
+    const entries = byBucket.get(bucket);
+    if (!entries || entries.length === 0) {
+      continue;
+    }
+    entries.sort((left, right) => (left.dueUtcMs ?? 0) - (right.dueUtcMs ?? 0));
+    groups.push({
+      bucket,
+      label: DUE_BUCKET_LABELS[bucket],
+      tasks: entries.map((entry) => entry.task)
+    });
+  }
+  return groups;
+}
@@ -0,0 +1,40 @@
+import { describe, expect, it } from "vitest";
+
+import { countDifferences, diffFields, mergeDescriptions, type FieldSpec } from "./fieldDiff";
+
+interface Item {
+  title: string;
+  tags: string[];
+  project: string | null;
+}
+
+const specs: FieldSpec<Item>[] = [
+  { key: "title", label: "Title", value: (item) => item.title },
+  { key: "tags", label: "Tags", value: (item) => item.tags },
+  { key: "project", label: "Project", value: (item) => item.project }
+];
+
+describe("field diff", () => {
+  it("flags only the fields that differ", () => {
+    const rows = diffFields(
+      { title: "Pay rent ", tags: ["home", "bills"], project: null },
+      { title: "Pay rent", tags: ["bills", "home"], project: "money" },
+      specs
+    );
+    expect(rows.map((row) => [row.key, row.differs])).toEqual([
+      ["title", false],
+      ["tags", false],
+      ["project", true]
+    ]);
+    expect(rows[1].left).toBe("bills, home");
+    expect(rows[2].left).toBe("");
+    expect(countDifferences(rows)).toBe(1);
+  });
+
+  it("merges descriptions once", () => {
+    expect(mergeDescriptions("first", "second")).toBe("first\n\nsecond");
+    expect(mergeDescriptions("first\n\nsecond", "second")).toBe("first\n\nsecond");
+    expect(mergeDescriptions("", " second ")).toBe("second");
+    expect(mergeDescriptions("first", "  ")).toBe("first");
+  });
+});
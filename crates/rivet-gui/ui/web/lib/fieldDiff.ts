@@ -0,0 +1,58 @@
+// Field-by-field comparison shared by the task compare dialog and the
+// contact merge dialog. Values are compared after trimming; list values are
+// compared as sets, so tag order never counts as a difference.
+export type FieldValue = string | string[] | null | undefined;
+
+export interface FieldSpec<T> {
+  key: string;
+  label: string;
+  value: (item: T) => FieldValue;
+}
+
+export interface FieldDiffRow {
+  key: string;
+  label: string;
+  left: string;
+  right: string;
+  differs: boolean;
+}
+
+function normalizeFieldValue(value: FieldValue): string {
+  if (Array.isArray(value)) {
+    return value
+      .map((entry) => entry.trim())
+      .filter((entry) => entry.length > 0)
+      .sort((a, b) => a.localeCompare(b))
+      .join(", ");
+  }
+  return value?.trim() ?? "";
+}
+
+export function diffFields<T>(left: T, right: T, specs: FieldSpec<T>[]): FieldDiffRow[] {
+  return specs.map((spec) => {
+    const leftValue = normalizeFieldValue(spec.value(left));
+    const rightValue = normalizeFieldValue(spec.value(right));
+    return {
+      key: spec.key,
+      label: spec.label,
+      left: leftValue,
+      right: rightValue,
+      differs: leftValue !== rightValue
+    };
+  });
+}
+
+export function countDifferences(rows: FieldDiffRow[]): number {
+  return rows.filter((row) => row.differs).length;
+}
+
+// Appends `other` to `kept` as a new paragraph, skipping it when it is empty
+// or already contained, so merging twice is a no-op.
+export function mergeDescriptions(kept: string, other: string): string {
+  const base = kept.trim();
+  const extra = other.trim();
+  if (!extra || base.includes(extra)) {
+    return base;
+  }
+  return base ? `${base}\n\n${extra}` : extra;
+}
@@ -0,0 +1,67 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  collectFocusNotificationEvents,
+  formatCountdown,
+  formatFocusDuration,
+  pomodoroRemainingMs,
+  resolveFocusConfig,
+  runningFocusTask,
+  totalFocusSeconds
+} from "./focus";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+
+const task = (uuid: string, started: string | null, focus_seconds = 0, status: TaskDto["status"] = "Pending") =>
+  ({ uuid, title: uuid, status, started, focus_seconds }) as TaskDto;
+
+describe("resolveFocusConfig", () => {
+  it("defaults and clamps the pomodoro length", () => {
+    expect(resolveFocusConfig(null).pomodoro_minutes).toBe(25);
+    expect(resolveFocusConfig({ focus: { pomodoro_minutes: 0 } } as RivetRuntimeConfig).pomodoro_minutes).toBe(0);
+    expect(resolveFocusConfig({ focus: { pomodoro_minutes: -5 } } as RivetRuntimeConfig).pomodoro_minutes).toBe(0);
+  });
+});
+
+describe("focus timer", () => {
+  const now = Date.parse("2026-03-04T10:10:00Z");
+
+  it("picks the most recently started open task", () => {
+    const tasks = [
+      task("a", "20260304T100000Z"),
+      task("b", "20260304T100500Z"),
+      task("c", "20260304T100800Z", 0, "Completed"),
+      task("d", null)
+    ];
+    expect(runningFocusTask(tasks)?.uuid).toBe("b");
+    expect(runningFocusTask([task("d", null)])).toBeNull();
+  });
+
+  it("adds the running clock to recorded focus time", () => {
+    expect(totalFocusSeconds(task("a", "20260304T100000Z", 90), now)).toBe(690);
+    expect(totalFocusSeconds(task("a", null, 90), now)).toBe(90);
+    expect(formatFocusDuration(45)).toBe("45s");
+    expect(formatFocusDuration(690)).toBe("11m");
+    expect(formatFocusDuration(3900)).toBe("1h 05m");
+  });
+
+  it("counts the pomodoro down from the start", () => {
+    const running = task("a", "20260304T100000Z");
+    expect(pomodoroRemainingMs(running, { pomodoro_minutes: 25 }, now)).toBe(15 * 60_000);
+    expect(pomodoroRemainingMs(running, { pomodoro_minutes: 0 }, now)).toBeNull();
+    expect(formatCountdown(15 * 60_000 - 500)).toBe("15:00");
+    expect(formatCountdown(61_000)).toBe("01:01");
+  });
+
+  it("notifies once per session when the pomodoro runs out", () => {
+    const tasks = [task("a", "20260304T100000Z")];
+    const config = { pomodoro_minutes: 5 };
+    const sent = new Set<string>();
+    const events = collectFocusNotificationEvents(tasks, config, sent, now);
+    expect(events).toHaveLength(1);
+    sent.add(events[0].key);
+    expect(collectFocusNotificationEvents(tasks, config, sent, now)).toHaveLength(0);
+    expect(collectFocusNotificationEvents([task("a", "20260304T100900Z")], config, sent, now)).toHaveLength(0);
+    expect(collectFocusNotificationEvents([task("a", "20260304T100000Z")], config, sent, now + 60_000)).toHaveLength(0);
+  });
+});
@@ -0,0 +1,96 @@
+import { parseTaskDueUtcMs } from "./calendar";
+import type { DueNotificationEvent } from "./notifications";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+
+export const DEFAULT_POMODORO_MINUTES = 25;
+
+export interface FocusConfig {
+  // 0 means the timer only counts up.
+  pomodoro_minutes: number;
+}
+
+export function resolveFocusConfig(config: RivetRuntimeConfig | null): FocusConfig {
+  const minutes = Number(config?.focus?.pomodoro_minutes ?? DEFAULT_POMODORO_MINUTES);
+  return {
+    pomodoro_minutes: Number.isFinite(minutes) ? Math.max(0, Math.min(24 * 60, Math.floor(minutes))) : DEFAULT_POMODORO_MINUTES
+  };
+}
+
+function startedUtcMs(task: TaskDto): number | null {
+  return task.started ? parseTaskDueUtcMs(task.started) : null;
+}
+
+// The most recently started open task; the header shows one timer at a time.
+export function runningFocusTask(tasks: readonly TaskDto[]): TaskDto | null {
+  let running: TaskDto | null = null;
+  let latest = -Infinity;
+  for (const task of tasks) {
+    const startMs = startedUtcMs(task);
+    if (startMs !== null && startMs > latest && (task.status === "Pending" || task.status === "Waiting")) {
+      running = task;
+      latest = startMs;
+    }
+  }
+  return running;
+}
+
+// Recorded focus time plus the clock that is still running.
+export function totalFocusSeconds(task: TaskDto, nowUtcMs: number): number {
+  const startMs = startedUtcMs(task);
+  const running = startMs === null ? 0 : Math.max(0, Math.floor((nowUtcMs - startMs) / 1000));
+  return task.focus_seconds + running;
+}
+
+// Time left in the pomodoro started with the task, or null when the task is
+// not running or the countdown is off.
+export function pomodoroRemainingMs(task: TaskDto, config: FocusConfig, nowUtcMs: number): number | null {
+  const startMs = startedUtcMs(task);
+  if (startMs === null || config.pomodoro_minutes === 0) {
+    return null;
+  }
+  return Math.max(0, startMs + config.pomodoro_minutes * 60_000 - nowUtcMs);
+}
+
+export function formatFocusDuration(seconds: number): string {
+  if (seconds < 60) {
+    return `${Math.max(0, Math.floor(seconds))}s`;
+  }
+  const minutes = Math.floor(seconds / 60);
+  if (minutes < 60) {
+    return `${minutes}m`;
+  }
+  return `${Math.floor(minutes / 60)}h ${String(minutes % 60).padStart(2, "0")}m`;
+}
+
+export function formatCountdown(ms: number): string {
+  const totalSeconds = Math.ceil(ms / 1000);
+  const minutes = Math.floor(totalSeconds / 60);
+  return `${String(minutes).padStart(2, "0")}:${String(totalSeconds % 60).padStart(2, "0")}`;
+}
+
+// One notification per started session once its pomodoro runs out; the key
+// includes the start so restarting the task arms a new one.
+export function collectFocusNotificationEvents(
+  tasks: readonly TaskDto[],
+  config: FocusConfig,
+  sent: Set<string>,
+  nowUtcMs: number
+): DueNotificationEvent[] {
+  const events: DueNotificationEvent[] = [];
+  for (const task of tasks) {
+    const remaining = pomodoroRemainingMs(task, config, nowUtcMs);
+    if (remaining !== 0 || !(task.status === "Pending" || task.status === "Waiting")) {
+      continue;
+    }
+    const key = `${task.uuid}:${startedUtcMs(task)}:focus:${config.pomodoro_minutes}`;
+    if (!sent.has(key)) {
+      events.push({
+        key,
+        title: `Focus session done (${config.pomodoro_minutes}m)`,
+        body: `${task.title || "Untitled Task"}\nTake a break, or stop the task to record the time.`
+      });
+    }
+  }
+  return events;
+}
@@ -0,0 +1,46 @@
+import { describe, expect, it } from "vitest";
+
+import { boardCardSort, boardLaneSort, moveBoardBefore, parseBoardListSort, sortBoards, sortLanes } from "./kanbanSort";
+import type { KanbanBoardDef } from "../types/ui";
+
+const board = (id: string, name: string, patch: Partial<KanbanBoardDef> = {}): KanbanBoardDef => ({
+  id,
+  name,
+  color: "#000000",
+  ...patch
+});
+
+describe("kanban sort preferences", () => {
+  it("falls back for boards saved without preferences", () => {
+    expect(boardLaneSort(board("a", "A"))).toBe("schema");
+    expect(boardCardSort(board("a", "A"))).toBe("manual");
+    expect(boardCardSort(board("a", "A", { card_sort: "bogus" as never }))).toBe("manual");
+    expect(boardCardSort(board("a", "A", { card_sort: "due" }))).toBe("due");
+    expect(boardLaneSort(null)).toBe("schema");
+    expect(parseBoardListSort("recent")).toBe("recent");
+    expect(parseBoardListSort(null)).toBe("custom");
+  });
+
+  it("sorts lanes by label when asked", () => {
+    expect(sortLanes(["todo", "working", "finished"], "schema")).toEqual(["todo", "working", "finished"]);
+    expect(sortLanes(["todo", "working", "finished"], "alpha")).toEqual(["finished", "todo", "working"]);
+  });
+
+  it("sorts the board list", () => {
+    const boards = [
+      board("1", "work", { opened_at: 10 }),
+      board("2", "Home", { opened_at: 30 }),
+      board("3", "errands")
+    ];
+    expect(sortBoards(boards, "custom").map((entry) => entry.id)).toEqual(["1", "2", "3"]);
+    expect(sortBoards(boards, "name").map((entry) => entry.id)).toEqual(["3", "2", "1"]);
+    expect(sortBoards(boards, "recent").map((entry) => entry.id)).toEqual(["2", "1", "3"]);
+  });
+
+  it("moves a board before another in custom order", () => {
+    const boards = [board("1", "a"), board("2", "b"), board("3", "c")];
+    expect(moveBoardBefore(boards, "3", "1").map((entry) => entry.id)).toEqual(["3", "1", "2"]);
+    expect(moveBoardBefore(boards, "1", "3").map((entry) => entry.id)).toEqual(["2", "1", "3"]);
+    expect(moveBoardBefore(boards, "1", "1").map((entry) => entry.id)).toEqual(["1", "2", "3"]);
+  });
+});
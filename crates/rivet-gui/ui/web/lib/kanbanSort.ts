@@ -0,0 +1,62 @@
+import { humanizeLane } from "./tags";
+import type { KanbanBoardDef, KanbanBoardListSort, KanbanCardSort, KanbanLaneSort } from "../types/ui";
+
+export const KANBAN_LANE_SORTS: ReadonlyArray<{ id: KanbanLaneSort; label: string }> = [
+  { id: "schema", label: "Schema order" },
+  { id: "alpha", label: "A-Z" }
+];
+
+export const KANBAN_CARD_SORTS: ReadonlyArray<{ id: KanbanCardSort; label: string }> = [
+  { id: "manual", label: "Manual" },
+  { id: "urgency", label: "Urgency" },
+  { id: "due", label: "Due date" }
+];
+
+export const KANBAN_BOARD_LIST_SORTS: ReadonlyArray<{ id: KanbanBoardListSort; label: string }> = [
+  { id: "custom", label: "Custom" },
+  { id: "name", label: "Name" },
+  { id: "recent", label: "Recently opened" }
+];
+
+export function boardLaneSort(board: KanbanBoardDef | null): KanbanLaneSort {
+  return KANBAN_LANE_SORTS.find((entry) => entry.id === board?.lane_sort)?.id ?? "schema";
+}
+
+export function boardCardSort(board: KanbanBoardDef | null): KanbanCardSort {
+  return KANBAN_CARD_SORTS.find((entry) => entry.id === board?.card_sort)?.id ?? "manual";
+}
+
+export function parseBoardListSort(raw: string | null): KanbanBoardListSort {
+  return KANBAN_BOARD_LIST_SORTS.find((entry) => entry.id === raw)?.id ?? "custom";
+}
+
+// Lane order for display only; moving a card to the "next" lane still
+// follows the schema.
+export function sortLanes(columns: readonly string[], mode: KanbanLaneSort): string[] {
+  if (mode === "schema") {
+    return [...columns];
+  }
+  return [...columns].sort((left, right) => humanizeLane(left).localeCompare(humanizeLane(right)));
+}
+
+// "custom" is the stored array order, which board drags rewrite.
+export function sortBoards(boards: readonly KanbanBoardDef[], mode: KanbanBoardListSort): KanbanBoardDef[] {
+  if (mode === "name") {
+    return [...boards].sort((left, right) => left.name.localeCompare(right.name, undefined, { sensitivity: "base" }));
+  }
+  if (mode === "recent") {
+    return [...boards].sort((left, right) => (right.opened_at ?? 0) - (left.opened_at ?? 0));
+  }
+  return [...boards];
+}
+
+export function moveBoardBefore(boards: readonly KanbanBoardDef[], boardId: string, beforeId: string): KanbanBoardDef[] {
+  const moving = boards.find((board) => board.id === boardId);
+  if (!moving || boardId === beforeId) {
+    return [...boards];
+  }
+  const rest = boards.filter((board) => board.id !== boardId);
+  const index = rest.findIndex((board) => board.id === beforeId);
+  rest.splice(index === -1 ? rest.length : index, 0, moving);
+  return rest;
+}
@@ -0,0 +1,44 @@
+import { describe, expect, it } from "vitest";
+
+import { calendarTitleForView, resolveCalendarConfig, toCalendarDate, weekdayLabels } from "./calendar";
+import { localeWeekStart, monthLabel, normalizeLocale, resolveLocale, weekdayLabel } from "./locale";
+import type { RivetRuntimeConfig } from "../types/config";
+
+describe("locale labels", () => {
+  it("maps canonical keys to display names", () => {
+    expect(monthLabel("mar", "en-US")).toBe("March");
+    expect(monthLabel("mar", "de-DE")).toBe("März");
+    expect(monthLabel("dec", "fr", "short")).toBe("déc.");
+    expect(weekdayLabel("mon", "en-US")).toBe("Mon");
+    expect(weekdayLabel("sun", "es", "long")).toBe("domingo");
+  });
+
+  it("orders weekday headers from the week start", () => {
+    expect(weekdayLabels("monday")).toEqual(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]);
+    expect(weekdayLabels("sunday", "de-DE")).toEqual(["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"]);
+  });
+
+  it("localizes calendar titles", () => {
+    const focus = toCalendarDate(2026, 2, 18);
+    expect(calendarTitleForView("month", focus, "monday")).toBe("Month View February 2026");
+    expect(calendarTitleForView("month", focus, "monday", false, "es")).toBe("Month View febrero de 2026");
+    expect(calendarTitleForView("quarter", focus, "monday", false, "de-DE")).toBe("Quarter View Q1 2026 (Jan-Mär)");
+  });
+
+  it("normalizes POSIX names and falls back for unknown locales", () => {
+    expect(normalizeLocale("de_DE.UTF-8")).toBe("de-DE");
+    expect(normalizeLocale("C")).toBeNull();
+    expect(normalizeLocale("")).toBeNull();
+    expect(resolveLocale("pt_BR")).toBe("pt-BR");
+  });
+
+  it("takes the first weekday from the locale when asked", () => {
+    expect(localeWeekStart("en-US")).toBe("sunday");
+    expect(localeWeekStart("de-DE")).toBe("monday");
+    const config = (week_start: string) =>
+      resolveCalendarConfig({ calendar: { locale: "en-GB", policies: { week_start } } } as RivetRuntimeConfig);
+    expect(config("locale").policies.week_start).toBe("monday");
+    expect(config("sunday").policies.week_start).toBe("sunday");
+    expect(config("locale").locale).toBe("en-GB");
+  });
+});
@@ -0,0 +1,100 @@
+import type { CalendarWeekStart } from "../types/ui";
+import { MONTH_KEYS, WEEKDAY_KEYS, type MonthKey, type WeekdayKey } from "./tags";
+
+// Display names for the canonical month/weekday keys. Keys stay English in
+// tags and config; only what the calendar shows follows the locale.
+export const DEFAULT_LOCALE = "en-US";
+
+export type LabelWidth = "long" | "short" | "narrow";
+
+// Regions whose calendars start the week on Sunday (CLDR firstDay=sun), for
+// runtimes without Intl.Locale week info.
+const SUNDAY_FIRST_REGIONS = new Set([
+  "AG", "AS", "BR", "BS", "BT", "BW", "BZ", "CA", "CO", "DM", "DO", "ET", "GT", "GU", "HK", "HN", "ID", "IL", "IN",
+  "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO", "MT", "MX", "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR",
+  "PT", "PY", "SA", "SG", "SV", "TH", "TT", "TW", "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW"
+]);
+
+const formatters = new Map<string, Intl.DateTimeFormat>();
+
+function formatter(locale: string, options: Intl.DateTimeFormatOptions): Intl.DateTimeFormat {
+  const key = `${locale}|${JSON.stringify(options)}`;
+  let cached = formatters.get(key);
+  if (!cached) {
+    cached = new Intl.DateTimeFormat(locale, { ...options, timeZone: "UTC" });
+    formatters.set(key, cached);
+  }
+  return cached;
+}
+
+// Accepts BCP 47 tags and POSIX names ("de_DE.UTF-8"); null when the runtime
+// does not know the locale.
+export function normalizeLocale(raw: string | null | undefined): string | null {
+  const trimmed = (raw ?? "").trim().replace(/[.@].*$/, "").replaceAll("_", "-");
+  if (!trimmed || trimmed === "C" || trimmed === "POSIX") {
+    return null;
+  }
+  try {
+    return Intl.DateTimeFormat.supportedLocalesOf([trimmed])[0] ?? null;
+  } catch {
+    return null;
+  }
+}
+
+function systemLocale(): string | null {
+  if (typeof navigator !== "undefined") {
+    const fromNavigator = normalizeLocale(navigator.language);
+    if (fromNavigator) {
+      return fromNavigator;
+    }
+  }
+  return normalizeLocale(Intl.DateTimeFormat().resolvedOptions().locale);
+}
+
+// `[calendar] locale`, else the system locale, else en-US.
+export function resolveLocale(configured: string | null | undefined): string {
+  return normalizeLocale(configured) ?? systemLocale() ?? DEFAULT_LOCALE;
+}
+
+interface LocaleWithWeekInfo extends Intl.Locale {
+  getWeekInfo?: () => { firstDay: number };
+  weekInfo?: { firstDay: number };
+}
+
+export function localeWeekStart(locale: string): CalendarWeekStart {
+  try {
+    const intlLocale = new Intl.Locale(locale) as LocaleWithWeekInfo;
+    const info = intlLocale.getWeekInfo?.() ?? intlLocale.weekInfo;
+    if (info) {
+      return info.firstDay === 7 ? "sunday" : "monday";
+    }
+    const region = intlLocale.maximize().region ?? "";
+    return SUNDAY_FIRST_REGIONS.has(region) ? "sunday" : "monday";
+  } catch {
+    return "monday";
+  }
+}
+
+export function monthLabel(month: MonthKey, locale: string, width: LabelWidth = "long"): string {
+  const index = MONTH_KEYS.indexOf(month);
+  return formatter(locale, { month: width }).format(new Date(Date.UTC(2000, index, 1)));
+}
+
+export function weekdayLabel(day: WeekdayKey, locale: string, width: LabelWidth = "short"): string {
+  // 2000-01-03 was a Monday, matching the order of WEEKDAY_KEYS.
+  const index = WEEKDAY_KEYS.indexOf(day);
+  return formatter(locale, { weekday: width }).format(new Date(Date.UTC(2000, 0, 3 + index)));
+}
+
+export function monthKeyOf(date: Date): MonthKey {
+  return MONTH_KEYS[date.getUTCMonth()] ?? "jan";
+}
+
+export function weekdayKeyOf(date: Date): WeekdayKey {
+  return WEEKDAY_KEYS[(date.getUTCDay() + 6) % 7] ?? "mon";
+}
+
+// A calendar date (UTC midnight) formatted in `locale`.
+export function formatCalendarLabel(date: Date, locale: string, options: Intl.DateTimeFormatOptions): string {
+  return formatter(locale, options).format(date);
+}
@@ -0,0 +1,76 @@
+import { describe, expect, it } from "vitest";
+
+import {
+  clearPending,
+  markPending,
+  optimisticDone,
+  optimisticUncomplete,
+  reconcileTasks,
+  rollbackTasks
+} from "./optimistic";
+import type { TaskDto } from "../types/core";
+
+function task(uuid: string, overrides: Partial<TaskDto> = {}): TaskDto {
+  return {
+    uuid,
+    id: null,
+    title: uuid,
+    description: "",
+    status: "Pending",
+    project: null,
+    tags: [],
+    priority: null,
+    due: null,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null,
+    ...overrides
+  };
+}
+
+describe("optimistic task helpers", () => {
+  const nowIso = "2026-03-01T12:00:00.000Z";
+
+  it("flips status locally for done and uncomplete", () => {
+    expect(optimisticDone(task("a"), nowIso)).toMatchObject({ status: "Completed", completed: nowIso });
+    expect(optimisticUncomplete(task("a", { status: "Completed", completed: nowIso }), nowIso).completed).toBeNull();
+    expect(optimisticUncomplete(task("a", { status: "Completed" }), nowIso).status).toBe("Pending");
+    expect(
+      optimisticUncomplete(task("a", { status: "Completed", wait: "2026-03-05T00:00:00Z" }), nowIso).status
+    ).toBe("Waiting");
+  });
+
+  it("tracks pending operations per task", () => {
+    const ops = markPending({}, ["a", "b"], "done");
+    expect(ops).toEqual({ a: "done", b: "done" });
+    expect(clearPending(ops, ["a"])).toEqual({ b: "done" });
+  });
+
+  it("restores removed and modified tasks in place on rollback", () => {
+    const previous = [task("a"), task("b"), task("c")];
+    const current = [task("a", { status: "Completed" }), task("c")];
+    const restored = rollbackTasks(current, previous, ["a", "b"]);
+    expect(restored.map((entry) => [entry.uuid, entry.status])).toEqual([
+      ["a", "Pending"],
+      ["b", "Pending"],
+      ["c", "Pending"]
+    ]);
+  });
+
+  it("reconciles with the server while keeping in-flight tasks", () => {
+    const local = [task("a", { status: "Completed" }), task("b")];
+    const server = [task("a"), task("b", { title: "renamed" }), task("spawned")];
+    const reconciled = reconcileTasks(server, local, { a: "done", b: "delete" });
+    expect(reconciled.map((entry) => [entry.uuid, entry.status])).toEqual([
+      ["a", "Completed"],
+      ["spawned", "Pending"]
+    ]);
+  });
+});
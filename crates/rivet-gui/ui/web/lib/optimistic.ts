@@ -0,0 +1,60 @@
+import type { TaskDto } from "../types/core";
+import type { PendingTaskOp } from "../types/ui";
+
+export type PendingTaskOps = Record<string, PendingTaskOp>;
+
+export function optimisticDone(task: TaskDto, nowIso: string): TaskDto {
+  return { ...task, status: "Completed", modified: nowIso, completed: nowIso };
+}
+
+export function optimisticUncomplete(task: TaskDto, nowIso: string): TaskDto {
+  const waiting = task.wait !== null && Date.parse(task.wait) > Date.parse(nowIso);
+  return { ...task, status: waiting ? "Waiting" : "Pending", modified: nowIso, completed: null };
+}
+
+export function markPending(ops: PendingTaskOps, uuids: Iterable<string>, op: PendingTaskOp): PendingTaskOps {
+  const next = { ...ops };
+  for (const uuid of uuids) {
+    next[uuid] = op;
+  }
+  return next;
+}
+
+export function clearPending(ops: PendingTaskOps, uuids: Iterable<string>): PendingTaskOps {
+  const next = { ...ops };
+  for (const uuid of uuids) {
+    delete next[uuid];
+  }
+  return next;
+}
+
+// Puts the pre-action copies of `uuids` back, re-inserting removed tasks at
+// their previous position.
+export function rollbackTasks(current: TaskDto[], previous: TaskDto[], uuids: Iterable<string>): TaskDto[] {
+  const targets = new Set(uuids);
+  const restored = current.filter((task) => !targets.has(task.uuid));
+  previous.forEach((task, index) => {
+    if (targets.has(task.uuid)) {
+      restored.splice(Math.min(index, restored.length), 0, task);
+    }
+  });
+  return restored;
+}
+
+// The server snapshot wins, except for tasks whose round trip is still in
+// flight: those keep their optimistic copy (or stay hidden while a delete is
+// pending). Tasks the server created on its own, such as a spawned recurrence,
+// come straight from the snapshot.
+export function reconcileTasks(server: TaskDto[], local: TaskDto[], pending: PendingTaskOps): TaskDto[] {
+  const localById = new Map(local.map((task) => [task.uuid, task]));
+  return server.flatMap((task) => {
+    const op = pending[task.uuid];
+    if (!op) {
+      return [task];
+    }
+    if (op === "delete") {
+      return [];
+    }
+    return [localById.get(task.uuid) ?? task];
+  });
+}
@@ -0,0 +1,18 @@
+import { describe, expect, it } from "vitest";
+
+import { isCommandAllowed, isReadOnly } from "./permissions";
+
+describe("command permissions", () => {
+  it("allows categories that are not disabled", () => {
+    expect(isCommandAllowed([], "tasks_write")).toBe(true);
+    expect(isCommandAllowed(["tasks_delete"], "tasks_write")).toBe(true);
+    expect(isCommandAllowed(["tasks_delete"], "tasks_delete")).toBe(false);
+  });
+
+  it("reports read-only only when every category is off", () => {
+    expect(isReadOnly(["tasks_write", "tasks_delete"])).toBe(false);
+    expect(
+      isReadOnly(["tasks_write", "tasks_delete", "contacts_write", "contacts_delete", "calendar_import", "config_write"])
+    ).toBe(true);
+  });
+});
@@ -0,0 +1,19 @@
+import type { CommandCategory } from "../types/core";
+
+export function isCommandAllowed(disabled: readonly CommandCategory[], category: CommandCategory): boolean {
+  return !disabled.includes(category);
+}
+
+// True when `[permissions]` leaves nothing writable, so views can drop their
+// edit affordances wholesale instead of button by button.
+export function isReadOnly(disabled: readonly CommandCategory[]): boolean {
+  const all: CommandCategory[] = [
+    "tasks_write",
+    "tasks_delete",
+    "contacts_write",
+    "contacts_delete",
+    "calendar_import",
+    "config_write"
+  ];
+  return all.every((category) => disabled.includes(category));
+}
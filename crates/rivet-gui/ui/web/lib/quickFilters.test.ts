@@ -0,0 +1,94 @@
+import { describe, expect, it } from "vitest";
+
+import type { TaskDto } from "../types/core";
+import { explainQuickFilters, filterByQuickFilters, matchesQuickFilter, quickFilterCounts, toggleQuickFilter } from "./quickFilters";
+
+const TZ = "America/Mexico_City";
+// Tuesday 2026-02-17 06:00 local.
+const NOW = Date.UTC(2026, 1, 17, 12, 0, 0);
+
+function makeTask(uuid: string, patch: Partial<TaskDto> = {}): TaskDto {
+  return {
+    uuid,
+    id: null,
+    title: uuid,
+    description: "",
+    status: "Pending",
+    project: null,
+    tags: [],
+    priority: null,
+    due: null,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null,
+    ...patch
+  };
+}
+
+describe("matchesQuickFilter", () => {
+  it("matches overdue open tasks only", () => {
+    const late = makeTask("late", { due: "20260217T110000Z" });
+    const done = makeTask("done", { due: "20260217T110000Z", status: "Completed" });
+    expect(matchesQuickFilter(late, "overdue", NOW, TZ)).toBe(true);
+    expect(matchesQuickFilter(done, "overdue", NOW, TZ)).toBe(false);
+    expect(matchesQuickFilter(makeTask("none"), "overdue", NOW, TZ)).toBe(false);
+  });
+
+  it("compares due days in the calendar timezone", () => {
+    // 23:30 local on the 17th, already the 18th in UTC.
+    expect(matchesQuickFilter(makeTask("tonight", { due: "20260218T053000Z" }), "due_today", NOW, TZ)).toBe(true);
+    expect(matchesQuickFilter(makeTask("tomorrow", { due: "20260218T070000Z" }), "due_today", NOW, TZ)).toBe(false);
+  });
+
+  it("checks priority, project and tags", () => {
+    const task = makeTask("a", { priority: "High", project: "home", tags: [] });
+    expect(matchesQuickFilter(task, "high_priority", NOW, TZ)).toBe(true);
+    expect(matchesQuickFilter(task, "has_project", NOW, TZ)).toBe(true);
+    expect(matchesQuickFilter(task, "untagged", NOW, TZ)).toBe(true);
+    expect(matchesQuickFilter(makeTask("b", { project: " ", tags: ["x"] }), "has_project", NOW, TZ)).toBe(false);
+    expect(matchesQuickFilter(makeTask("b", { tags: ["x"] }), "untagged", NOW, TZ)).toBe(false);
+  });
+});
+
+describe("quick filter combinations", () => {
+  const tasks = [
+    makeTask("a", { priority: "High", project: "home" }),
+    makeTask("b", { priority: "High", tags: ["x"] }),
+    makeTask("c", { project: "work" })
+  ];
+
+  it("AND-combines active chips", () => {
+    expect(filterByQuickFilters(tasks, [], NOW, TZ)).toHaveLength(3);
+    expect(filterByQuickFilters(tasks, ["high_priority", "has_project"], NOW, TZ).map((task) => task.uuid)).toEqual([
+      "a"
+    ]);
+  });
+
+  it("counts each chip against the other active ones", () => {
+    const counts = quickFilterCounts(tasks, ["high_priority"], NOW, TZ);
+    expect(counts.high_priority).toBe(2);
+    expect(counts.has_project).toBe(1);
+    expect(counts.untagged).toBe(1);
+    expect(counts.overdue).toBe(0);
+  });
+
+  it("names the active chips a task fails", () => {
+    const task = makeTask("a", { project: "work" });
+    expect(explainQuickFilters(task, ["has_project", "untagged", "overdue"], NOW, TZ)).toEqual([
+      "filtered out by quick filter 'Overdue'"
+    ]);
+    expect(explainQuickFilters(task, [], NOW, TZ)).toEqual([]);
+  });
+
+  it("toggles chips on and off", () => {
+    expect(toggleQuickFilter([], "overdue")).toEqual(["overdue"]);
+    expect(toggleQuickFilter(["overdue", "untagged"], "overdue")).toEqual(["untagged"]);
+  });
+});
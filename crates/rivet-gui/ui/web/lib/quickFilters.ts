@@ -0,0 +1,85 @@
+import type { TaskDto } from "../types/core";
+import { parseTaskDueUtcMs, zonedDateTimeParts } from "./calendar";
+
+export type QuickFilter = "overdue" | "due_today" | "high_priority" | "has_project" | "untagged";
+
+export const QUICK_FILTERS: { id: QuickFilter; label: string }[] = [
+  { id: "overdue", label: "Overdue" },
+  { id: "due_today", label: "Due Today" },
+  { id: "high_priority", label: "High Priority" },
+  { id: "has_project", label: "Has Project" },
+  { id: "untagged", label: "Untagged" }
+];
+
+function isOpen(task: TaskDto): boolean {
+  return task.status !== "Completed" && task.status !== "Deleted" && task.status !== "Archived";
+}
+
+function sameZonedDay(leftUtcMs: number, rightUtcMs: number, timezone: string): boolean {
+  const left = zonedDateTimeParts(leftUtcMs, timezone);
+  const right = zonedDateTimeParts(rightUtcMs, timezone);
+  return left.year === right.year && left.month === right.month && left.day === right.day;
+}
+
+// Overdue, due today and untagged mirror the `+OVERDUE`, `+TODAY` and
+// `-TAGGED` virtual tags in `rivet_core::filter`: overdue counts open tasks
+// only, and "today" compares calendar days in `timezone`.
+export function matchesQuickFilter(task: TaskDto, filter: QuickFilter, nowUtcMs: number, timezone: string): boolean {
+  if (filter === "high_priority") {
+    return task.priority === "High";
+  }
+  if (filter === "has_project") {
+    return Boolean(task.project?.trim());
+  }
+  if (filter === "untagged") {
+    return task.tags.length === 0;
+  }
+  const dueUtcMs = task.due ? parseTaskDueUtcMs(task.due) : null;
+  if (dueUtcMs === null) {
+    return false;
+  }
+  if (filter === "overdue") {
+    return isOpen(task) && dueUtcMs < nowUtcMs;
+  }
+  return sameZonedDay(dueUtcMs, nowUtcMs, timezone);
+}
+
+// Active chips AND-combine.
+export function filterByQuickFilters(
+  tasks: TaskDto[],
+  active: QuickFilter[],
+  nowUtcMs: number,
+  timezone: string
+): TaskDto[] {
+  if (active.length === 0) {
+    return tasks;
+  }
+  return tasks.filter((task) => active.every((filter) => matchesQuickFilter(task, filter, nowUtcMs, timezone)));
+}
+
+// The active chips that drop `task`, for the details panel's visibility hint.
+export function explainQuickFilters(task: TaskDto, active: QuickFilter[], nowUtcMs: number, timezone: string): string[] {
+  return QUICK_FILTERS.filter(({ id }) => active.includes(id) && !matchesQuickFilter(task, id, nowUtcMs, timezone)).map(
+    ({ label }) => `filtered out by quick filter '${label}'`
+  );
+}
+
+// Per chip, how many of `tasks` the list would show with that chip on and
+// the other active chips unchanged.
+export function quickFilterCounts(
+  tasks: TaskDto[],
+  active: QuickFilter[],
+  nowUtcMs: number,
+  timezone: string
+): Record<QuickFilter, number> {
+  const counts = {} as Record<QuickFilter, number>;
+  for (const { id } of QUICK_FILTERS) {
+    const others = active.filter((filter) => filter !== id);
+    counts[id] = filterByQuickFilters(tasks, [...others, id], nowUtcMs, timezone).length;
+  }
+  return counts;
+}
+
+export function toggleQuickFilter(active: QuickFilter[], filter: QuickFilter): QuickFilter[] {
+  return active.includes(filter) ? active.filter((entry) => entry !== filter) : [...active, filter];
+}
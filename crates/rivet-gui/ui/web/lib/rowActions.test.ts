@@ -0,0 +1,34 @@
+import { describe, expect, it } from "vitest";
+
+import { resolveRowActions, rowActionsFor, TASK_ROW_ACTIONS } from "./rowActions";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+
+describe("resolveRowActions", () => {
+  const config = (rowActions?: string[]) => ({ ui: { task_list: { row_actions: rowActions } } }) as RivetRuntimeConfig;
+
+  it("defaults to every action and keeps the configured order", () => {
+    expect(resolveRowActions(null)).toEqual(TASK_ROW_ACTIONS);
+    expect(resolveRowActions(config(["Delete", "done", "bogus", "done"]))).toEqual(["delete", "done"]);
+    expect(resolveRowActions(config([]))).toEqual([]);
+  });
+});
+
+describe("rowActionsFor", () => {
+  const task = (status: TaskDto["status"]) => ({ uuid: "t", status, tags: [] }) as unknown as TaskDto;
+  const context = { canWrite: true, canDelete: true, nowUtcMs: 0 };
+
+  it("offers done and snooze only on open tasks", () => {
+    expect(rowActionsFor(task("Pending"), TASK_ROW_ACTIONS, context)).toEqual(TASK_ROW_ACTIONS);
+    expect(rowActionsFor(task("Completed"), TASK_ROW_ACTIONS, context)).toEqual(["edit", "delete"]);
+  });
+
+  it("follows permissions", () => {
+    expect(rowActionsFor(task("Pending"), TASK_ROW_ACTIONS, { ...context, canWrite: false })).toEqual(["delete"]);
+    expect(rowActionsFor(task("Pending"), TASK_ROW_ACTIONS, { ...context, canDelete: false })).toEqual([
+      "done",
+      "edit",
+      "snooze"
+    ]);
+  });
+});
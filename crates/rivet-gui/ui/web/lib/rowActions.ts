@@ -0,0 +1,60 @@
+import { logger } from "./logger";
+import { canManuallyCompleteTask } from "./calendar";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+
+export type TaskRowAction = "done" | "edit" | "snooze" | "delete";
+
+export const TASK_ROW_ACTIONS: TaskRowAction[] = ["done", "edit", "snooze", "delete"];
+
+export const TASK_ROW_ACTION_LABELS: Record<TaskRowAction, string> = {
+  done: "Mark done",
+  edit: "Edit",
+  snooze: "Snooze",
+  delete: "Delete"
+};
+
+// `[ui.task_list].row_actions` picks and orders the per-row buttons; an empty
+// list turns them off. Unknown names are dropped with a warning.
+export function resolveRowActions(config: RivetRuntimeConfig | null): TaskRowAction[] {
+  const configured = config?.ui?.task_list?.row_actions;
+  if (!configured) {
+    return TASK_ROW_ACTIONS;
+  }
+  const actions: TaskRowAction[] = [];
+  for (const raw of configured) {
+    const name = raw.trim().toLowerCase();
+    if (!(TASK_ROW_ACTIONS as string[]).includes(name)) {
+      logger.warn("config.ui.task_list.row_actions", `unknown row action '${raw}'`);
+      continue;
+    }
+    if (!actions.includes(name as TaskRowAction)) {
+      actions.push(name as TaskRowAction);
+    }
+  }
+  return actions;
+}
+
+export interface RowActionContext {
+  canWrite: boolean;
+  canDelete: boolean;
+  nowUtcMs: number;
+}
+
+// The configured actions that apply to this task, using the same rules as the
+// details panel buttons.
+export function rowActionsFor(task: TaskDto, actions: readonly TaskRowAction[], context: RowActionContext): TaskRowAction[] {
+  const open = task.status === "Pending" || task.status === "Waiting";
+  return actions.filter((action) => {
+    switch (action) {
+      case "done":
+        return context.canWrite && open && canManuallyCompleteTask(task, context.nowUtcMs);
+      case "edit":
+        return context.canWrite;
+      case "snooze":
+        return context.canWrite && open;
+      case "delete":
+        return context.canDelete;
+    }
+  });
+}
@@ -0,0 +1,43 @@
+import { describe, expect, it } from "vitest";
+
+import { snoozePatch, snoozeUntilUtcMs, snoozedUntilUtcMs, zonedLocalToUtcMs } from "./snooze";
+import type { TaskDto } from "../types/core";
+
+describe("snoozeUntilUtcMs", () => {
+  // Tuesday 2026-03-03 22:30 in Mexico City (UTC-6).
+  const now = Date.parse("2026-03-04T04:30:00Z");
+  const zone = "America/Mexico_City";
+
+  it("lands day presets on the local morning", () => {
+    expect(new Date(snoozeUntilUtcMs("tomorrow", now, zone)).toISOString()).toBe("2026-03-04T15:00:00.000Z");
+    expect(new Date(snoozeUntilUtcMs("next_monday", now, zone)).toISOString()).toBe("2026-03-09T15:00:00.000Z");
+    expect(snoozeUntilUtcMs("later_today", now, zone)).toBe(now + 3 * 60 * 60 * 1000);
+  });
+
+  it("keeps the wall clock across a DST change", () => {
+    // New York springs forward on 2026-03-08.
+    const before = zonedLocalToUtcMs(2026, 3, 5, 10, 0, "America/New_York");
+    expect(new Date(before).toISOString()).toBe("2026-03-05T15:00:00.000Z");
+    expect(new Date(snoozeUntilUtcMs("next_week", before, "America/New_York")).toISOString()).toBe(
+      "2026-03-12T14:00:00.000Z"
+    );
+  });
+});
+
+describe("snoozePatch", () => {
+  const task = (due: string | null, wait: string | null = null) => ({ due, wait }) as TaskDto;
+  const until = Date.parse("2026-03-10T15:00:00Z");
+
+  it("moves wait, and due only when it would come first", () => {
+    expect(snoozePatch(task("2026-03-20T00:00:00Z"), until)).toEqual({ wait: "2026-03-10T15:00:00.000Z" });
+    expect(snoozePatch(task("2026-03-05T00:00:00Z"), until)).toEqual({
+      wait: "2026-03-10T15:00:00.000Z",
+      due: "2026-03-10T15:00:00.000Z"
+    });
+  });
+
+  it("reports the snooze only while the wait is ahead", () => {
+    expect(snoozedUntilUtcMs(task(null, "2026-03-10T15:00:00Z"), until - 1)).toBe(until);
+    expect(snoozedUntilUtcMs(task(null, "2026-03-10T15:00:00Z"), until)).toBeNull();
+  });
+});
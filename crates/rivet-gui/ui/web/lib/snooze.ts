@@ -0,0 +1,65 @@
+import type { TaskDto, TaskPatch } from "../types/core";
+import { parseTaskDueUtcMs, zonedDateTimeParts } from "./calendar";
+
+export type SnoozePreset = "later_today" | "tomorrow" | "next_week" | "next_monday";
+
+export const SNOOZE_PRESETS: { id: SnoozePreset; label: string }[] = [
+  { id: "later_today", label: "Later today (+3h)" },
+  { id: "tomorrow", label: "Tomorrow morning" },
+  { id: "next_week", label: "In a week" },
+  { id: "next_monday", label: "Next Monday" }
+];
+
+const HOUR_MS = 60 * 60 * 1000;
+// Presets that land on a day rather than an offset start at this local hour.
+const SNOOZE_MORNING_HOUR = 9;
+
+// Wall-clock time in `timezone` to UTC. Two passes settle the offset even
+// when the guess and the answer straddle a DST change.
+export function zonedLocalToUtcMs(
+  year: number,
+  month: number,
+  day: number,
+  hour: number,
+  minute: number,
+  timezone: string
+): number {
+  const wanted = Date.UTC(year, month - 1, day, hour, minute);
+  let utcMs = wanted;
+  for (let pass = 0; pass < 2; pass += 1) {
+    const seen = zonedDateTimeParts(utcMs, timezone);
+    const seenMs = Date.UTC(seen.year, seen.month - 1, seen.day, seen.hour, seen.minute);
+    utcMs += wanted - seenMs;
+  }
+  return utcMs;
+}
+
+export function snoozeUntilUtcMs(preset: SnoozePreset, nowUtcMs: number, timezone: string): number {
+  if (preset === "later_today") {
+    return nowUtcMs + 3 * HOUR_MS;
+  }
+  const now = zonedDateTimeParts(nowUtcMs, timezone);
+  if (preset === "next_week") {
+    return zonedLocalToUtcMs(now.year, now.month, now.day + 7, now.hour, now.minute, timezone);
+  }
+  // Days until the target, never today: weekday 1 is Monday.
+  const offset = preset === "tomorrow" ? 1 : ((8 - now.weekday) % 7 || 7);
+  return zonedLocalToUtcMs(now.year, now.month, now.day + offset, SNOOZE_MORNING_HOUR, 0, timezone);
+}
+
+// Moves `wait` to the snooze target; a due date that would fall before it
+// moves too, matching `task <id> snooze` on the CLI.
+export function snoozePatch(task: TaskDto, untilUtcMs: number): TaskPatch {
+  const until = new Date(untilUtcMs).toISOString();
+  const dueUtcMs = task.due ? parseTaskDueUtcMs(task.due) : null;
+  if (dueUtcMs !== null && dueUtcMs < untilUtcMs) {
+    return { wait: until, due: until };
+  }
+  return { wait: until };
+}
+
+// The wait date while it is still ahead, i.e. the task is snoozed.
+export function snoozedUntilUtcMs(task: TaskDto, nowUtcMs: number): number | null {
+  const waitUtcMs = task.wait ? parseTaskDueUtcMs(task.wait) : null;
+  return waitUtcMs !== null && waitUtcMs > nowUtcMs ? waitUtcMs : null;
+}
@@ -1,7 +1,9 @@
 import type { ExternalCalendarSource } from "../types/core";
-import type { KanbanBoardDef } from "../types/ui";
+import type { KanbanBoardDef, KanbanBoardListSort, TaskDraft, TaskTemplate } from "../types/ui";
+import { parseBoardListSort } from "./kanbanSort";
 import { logger } from "./logger";
 import { normalizeMarkerColor } from "./tags";
+import { pruneTaskDrafts } from "./taskDraft";
 
 export const THEME_STORAGE_KEY = "rivet.theme";
 export const WORKSPACE_TAB_STORAGE_KEY = "rivet.workspace_tab";
@@ -10,6 +12,10 @@ export const KANBAN_BOARDS_STORAGE_KEY = "rivet.kanban.boards";
 export const KANBAN_ACTIVE_BOARD_STORAGE_KEY = "rivet.kanban.active_board";
 export const EXTERNAL_CALENDARS_STORAGE_KEY = "rivet.external_calendars";
 export const KANBAN_COMPACT_CARDS_STORAGE_KEY = "rivet.kanban.compact_cards";
+export const KANBAN_BOARD_SORT_STORAGE_KEY = "rivet.kanban.board_sort";
+export const TASK_LIST_GROUP_BY_DUE_STORAGE_KEY = "rivet.tasks.group_by_due";
+export const TASK_TEMPLATES_STORAGE_KEY = "rivet.tasks.templates";
+export const TASK_DRAFTS_STORAGE_KEY = "rivet.tasks.drafts";
 export const DUE_NOTIFICATION_SETTINGS_STORAGE_KEY = "rivet.notifications.due.settings";
 export const DUE_NOTIFICATION_SENT_STORAGE_KEY = "rivet.notifications.due.sent";
 
@@ -248,6 +254,64 @@ export function saveKanbanCompactCards(enabled: boolean): void {
   writeStorageItem(KANBAN_COMPACT_CARDS_STORAGE_KEY, enabled ? "1" : "0");
 }
 
+export function loadKanbanBoardSort(): KanbanBoardListSort {
+  return parseBoardListSort(readStorageItem(KANBAN_BOARD_SORT_STORAGE_KEY));
+}
+
+export function saveKanbanBoardSort(mode: KanbanBoardListSort): void {
+  writeStorageItem(KANBAN_BOARD_SORT_STORAGE_KEY, mode);
+}
+
+export function loadTaskListGroupByDue(): boolean {
+  return readStorageItem(TASK_LIST_GROUP_BY_DUE_STORAGE_KEY) === "1";
+}
+
+export function saveTaskListGroupByDue(enabled: boolean): void {
+  writeStorageItem(TASK_LIST_GROUP_BY_DUE_STORAGE_KEY, enabled ? "1" : "0");
+}
+
+export function loadTaskTemplates(): TaskTemplate[] {
+  const parsed = parseJson<TaskTemplate[]>(readStorageItem(TASK_TEMPLATES_STORAGE_KEY));
+  if (!parsed || !Array.isArray(parsed)) {
+    return [];
+  }
+  return parsed.filter((template) => template.id?.trim() && template.name?.trim() && Array.isArray(template.tags));
+}
+
+export function saveTaskTemplates(templates: TaskTemplate[]): void {
+  writeStorageItem(TASK_TEMPLATES_STORAGE_KEY, JSON.stringify(templates));
+}
+
+function loadTaskDrafts(): Record<string, TaskDraft> {
+  const parsed = parseJson<Record<string, TaskDraft>>(readStorageItem(TASK_DRAFTS_STORAGE_KEY));
+  return parsed && typeof parsed === "object" && !Array.isArray(parsed) ? parsed : {};
+}
+
+function saveTaskDrafts(drafts: Record<string, TaskDraft>): void {
+  if (Object.keys(drafts).length === 0) {
+    removeStorageItem(TASK_DRAFTS_STORAGE_KEY);
+  } else {
+    writeStorageItem(TASK_DRAFTS_STORAGE_KEY, JSON.stringify(drafts));
+  }
+}
+
+export function loadTaskDraft(key: string): TaskDraft | null {
+  const draft = loadTaskDrafts()[key];
+  return draft && typeof draft.title === "string" && Array.isArray(draft.selectedTags) && draft.recurrence ? draft : null;
+}
+
+export function saveTaskDraft(key: string, draft: TaskDraft): void {
+  saveTaskDrafts(pruneTaskDrafts({ ...loadTaskDrafts(), [key]: draft }, draft.saved_at));
+}
+
+export function clearTaskDraft(key: string): void {
+  const drafts = loadTaskDrafts();
+  if (key in drafts) {
+    delete drafts[key];
+    saveTaskDrafts(drafts);
+  }
+}
+
 export function loadExternalCalendars(): ExternalCalendarSource[] {
   const parsed = parseJson<ExternalCalendarSource[]>(readStorageItem(EXTERNAL_CALENDARS_STORAGE_KEY));
   if (!parsed || !Array.isArray(parsed)) {
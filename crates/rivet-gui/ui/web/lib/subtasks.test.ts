@@ -0,0 +1,61 @@
+import { describe, expect, it } from "vitest";
+
+import type { TaskDto } from "../types/core";
+import { indexTasksById, openSubtaskIds, subtaskProgress } from "./subtasks";
+
+function task(uuid: string, overrides: Partial<TaskDto> = {}): TaskDto {
+  return {
+    uuid,
+    id: null,
+    title: uuid,
+    description: "",
+    status: "Pending",
+    project: null,
+    tags: [],
+    priority: null,
+    due: null,
+    wait: null,
+    scheduled: null,
+    created: null,
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null,
+    ...overrides
+  };
+}
+
+describe("subtasks", () => {
+  const root = task("root", { children: ["a", "b", "c"] });
+  const tasks = [
+    root,
+    task("a", { parent: "root", status: "Completed", children: ["a1"] }),
+    task("a1", { parent: "a", status: "Waiting" }),
+    task("b", { parent: "root" }),
+    task("c", { parent: "root", status: "Completed" })
+  ];
+  const byId = indexTasksById(tasks);
+
+  it("counts completed children against the total", () => {
+    expect(subtaskProgress(root, byId)).toEqual({ done: 2, total: 3 });
+    expect(subtaskProgress(task("leaf"), byId)).toBeNull();
+  });
+
+  it("counts children missing from the snapshot as open", () => {
+    const partial = indexTasksById([root]);
+    expect(subtaskProgress(root, partial)).toEqual({ done: 0, total: 3 });
+  });
+
+  it("collects open descendants below completed children", () => {
+    expect(openSubtaskIds(root, byId).sort()).toEqual(["a1", "b"]);
+  });
+
+  it("stops on cyclic links", () => {
+    const x = task("x", { children: ["y"] });
+    const loop = indexTasksById([x, task("y", { children: ["x"] })]);
+    expect(openSubtaskIds(x, loop)).toEqual(["y"]);
+  });
+});
@@ -0,0 +1,62 @@
+import type { TaskDto } from "../types/core";
+
+export interface SubtaskProgress {
+  done: number;
+  total: number;
+}
+
+function isOpen(task: TaskDto): boolean {
+  return task.status === "Pending" || task.status === "Waiting";
+}
+
+export function indexTasksById(tasks: TaskDto[]): Map<string, TaskDto> {
+  return new Map(tasks.map((task) => [task.uuid, task]));
+}
+
+// Returns null for tasks without subtasks. Children missing from the loaded
+// snapshot still count toward the total so the ratio never overstates progress.
+export function subtaskProgress(task: TaskDto, tasksById: ReadonlyMap<string, TaskDto>): SubtaskProgress | null {
+  if (task.children.length === 0) {
+    return null;
+  }
+  const done = task.children.filter((uuid) => tasksById.get(uuid)?.status === "Completed").length;
+  return { done, total: task.children.length };
+}
+
+// Asks before completing a parent that still has open subtasks. Resolves to
+// false without prompting when there is nothing else to complete.
+export async function confirmCompleteSubtasks(
+  task: TaskDto | undefined,
+  tasksById: ReadonlyMap<string, TaskDto>,
+  confirm: (message: string) => Promise<boolean>
+): Promise<boolean> {
+  if (!task) {
+    return false;
+  }
+  const open = openSubtaskIds(task, tasksById).length;
+  return open > 0 && confirm(`Also complete ${open} open subtask(s)?`);
+}
+
+// Open tasks anywhere below `task` in the subtask tree, mirroring the core
+// `done` propagation so the GUI completes the same set as the CLI.
+export function openSubtaskIds(task: TaskDto, tasksById: ReadonlyMap<string, TaskDto>): string[] {
+  const found: string[] = [];
+  const seen = new Set<string>([task.uuid]);
+  const frontier = [...task.children];
+  while (frontier.length > 0) {
+    const uuid = frontier.pop();
+    if (uuid === undefined || seen.has(uuid)) {
+      continue;
+    }
+    seen.add(uuid);
+    const child = tasksById.get(uuid);
+    if (!child) {
+      continue;
+    }
+    if (isOpen(child)) {
+      found.push(uuid);
+    }
+    frontier.push(...child.children);
+  }
+  return found;
+}
@@ -1,6 +1,18 @@
 import { describe, expect, it } from "vitest";
 
-import { tagsForKanbanMove } from "./tags";
+import {
+  addTagWithSelection,
+  appendRecurrenceTags,
+  isDemoTask,
+  isKanbanRailTag,
+  recurrenceFromTags,
+  recurrenceProgress,
+  tagSelectionMode,
+  tagsForKanbanMove,
+  tagsForTagDrop,
+  unknownTagValues
+} from "./tags";
+import type { TagSchema } from "../types/config";
 
 describe("tagsForKanbanMove", () => {
   it("updates lane while preserving board when boardId is omitted", () => {
@@ -28,3 +40,102 @@ describe("tagsForKanbanMove", () => {
     expect(next).toContain("kanban:todo");
   });
 });
+
+describe("recurrence end conditions", () => {
+  it("round-trips a count and keeps the remaining instances", () => {
+    const draft = recurrenceFromTags(["recur:weekly", "recur_count:10", "recur_left:7"]);
+    expect(draft.ends).toBe("count");
+    expect(draft.remaining).toBe(7);
+    const tags: string[] = [];
+    appendRecurrenceTags(tags, draft);
+    expect(tags).toEqual(["recur:weekly", "recur_count:10", "recur_left:7"]);
+  });
+
+  it("drops the countdown when the count is edited or the end switches to a date", () => {
+    const draft = recurrenceFromTags(["recur:daily", "recur_count:5", "recur_left:2"]);
+    const recount = ["recur:daily", "recur_count:5", "recur_left:2"];
+    appendRecurrenceTags(recount, { ...draft, count: "8", remaining: null });
+    expect(recount).toEqual(["recur:daily", "recur_count:8"]);
+    const dated = ["recur:daily", "recur_count:5", "recur_left:2"];
+    appendRecurrenceTags(dated, { ...draft, ends: "until", until: "2026-12-31" });
+    expect(dated).toEqual(["recur:daily", "recur_until:2026-12-31"]);
+  });
+
+  it("reports which occurrence a task is", () => {
+    expect(recurrenceProgress(["recur:weekly", "recur_count:10", "recur_left:7"])).toEqual({ occurrence: 4, total: 10 });
+    expect(recurrenceProgress(["recur:weekly", "recur_count:3"])).toEqual({ occurrence: 1, total: 3 });
+    expect(recurrenceProgress(["recur:weekly"])).toBeNull();
+  });
+});
+
+describe("isDemoTask", () => {
+  it("matches only the seeded marker tag", () => {
+    expect(isDemoTask(["kanban:todo", "rivet_demo:true"])).toBe(true);
+    expect(isDemoTask(["demo", "board:rivet-demo"])).toBe(false);
+  });
+});
+
+describe("unknownTagValues", () => {
+  it("counts values the schema no longer lists", () => {
+    const tagLists = [
+      ["kanban:working"],
+      ["kanban:todo"],
+      ["kanban:working", "board:a"],
+      ["kanban:review"],
+      ["home"]
+    ];
+    expect(unknownTagValues(tagLists, "kanban", ["todo", "in_progress", "finished"])).toEqual([
+      { value: "working", count: 2 },
+      { value: "review", count: 1 }
+    ]);
+  });
+});
+
+describe("tagsForTagDrop", () => {
+  const schema: TagSchema = { version: 1, keys: [{ id: "area", selection: "single", values: ["home", "work"] }] };
+
+  it("appends plain tags and swaps single-select values", () => {
+    expect(tagsForTagDrop(["kanban:todo"], "urgent", schema)).toEqual(["kanban:todo", "urgent"]);
+    expect(tagsForTagDrop(["area:home", "urgent"], "area:work", schema)).toEqual(["urgent", "area:work"]);
+    expect(tagsForTagDrop(["urgent"], "urgent", schema)).toBeNull();
+  });
+
+  it("keeps lane and board tags off the side rail", () => {
+    expect(isKanbanRailTag("kanban:todo")).toBe(false);
+    expect(isKanbanRailTag("board:abc")).toBe(false);
+    expect(isKanbanRailTag("area:home")).toBe(true);
+  });
+});
+
+describe("tag selection modes", () => {
+  const schema: TagSchema = {
+    version: 1,
+    keys: [
+      { id: "area", selection: "single", values: ["home", "work"] },
+      { id: "ctx", selection: "multi", values: ["computer", "phone"] },
+      { id: "topic", values: ["rust"] },
+      { id: "stage", selection: "exclusive-group", group: "lifecycle", values: ["active", "paused"] },
+      { id: "status", selection: "exclusive-group", group: "lifecycle", values: ["blocked"] },
+      { id: "risk", selection: "exclusive-group", values: ["low", "high"] }
+    ]
+  };
+
+  it("treats unset and unknown modes as multi", () => {
+    expect(tagSelectionMode(schema, "topic")).toBe("multi");
+    expect(tagSelectionMode(schema, "missing")).toBe("multi");
+    expect(tagSelectionMode({ keys: [{ id: "odd", selection: "several" }] }, "odd")).toBe("multi");
+    expect(tagSelectionMode(schema, "stage")).toBe("exclusive-group");
+    expect(addTagWithSelection(["ctx:computer"], "ctx:phone", schema)).toEqual(["ctx:computer", "ctx:phone"]);
+    expect(addTagWithSelection(["area:home"], "area:work", schema)).toEqual(["area:work"]);
+  });
+
+  it("clears sibling keys in the same exclusive group", () => {
+    expect(addTagWithSelection(["stage:active", "ctx:phone"], "status:blocked", schema)).toEqual([
+      "ctx:phone",
+      "status:blocked"
+    ]);
+    expect(addTagWithSelection(["status:blocked", "stage:active"], "stage:paused", schema)).toEqual(["stage:paused"]);
+    expect(addTagWithSelection(["risk:low", "stage:active"], "risk:high", schema)).toEqual(["stage:active", "risk:high"]);
+    expect(tagsForTagDrop(["stage:active"], "status:blocked", schema)).toEqual(["status:blocked"]);
+  });
+});
@@ -8,9 +8,17 @@ export const RECUR_TIME_TAG_KEY = "recur_time";
 export const RECUR_DAYS_TAG_KEY = "recur_days";
 export const RECUR_MONTHS_TAG_KEY = "recur_months";
 export const RECUR_MONTH_DAY_TAG_KEY = "recur_day";
+export const RECUR_UNTIL_TAG_KEY = "recur_until";
+export const RECUR_COUNT_TAG_KEY = "recur_count";
+export const RECUR_LEFT_TAG_KEY = "recur_left";
 export const CAL_SOURCE_TAG_KEY = "cal_source";
 export const CAL_COLOR_TAG_KEY = "cal_color";
+export const CAL_DURATION_TAG_KEY = "cal_duration";
+export const CAL_ALL_DAY_TAG_KEY = "cal_allday";
 export const CALENDAR_UNAFFILIATED_COLOR = "#7f8691";
+// Marker and board id for `seed_demo` tasks; must match rivet-core `demo.rs`.
+export const DEMO_TAG_KEY = "rivet_demo";
+export const DEMO_BOARD_ID = "rivet-demo";
 
 export const WEEKDAY_KEYS = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"] as const;
 export const MONTH_KEYS = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"] as const;
@@ -70,6 +78,10 @@ export function firstTagValue(tags: string[], key: string): string | null {
   return null;
 }
 
+export function isDemoTask(tags: string[]): boolean {
+  return firstTagValue(tags, DEMO_TAG_KEY) !== null;
+}
+
 export function taskHasTagValue(tags: string[], key: string, value: string): boolean {
   return tags.some((tag) => {
     const [entryKey, entryValue] = splitTag(tag);
@@ -77,6 +89,17 @@ export function taskHasTagValue(tags: string[], key: string, value: string): boo
   });
 }
 
+// Mirrors `normalize_tag_value` in the Tauri backend, which stamps
+// `cal_source:<id>` on imported events.
+export function normalizeTagValue(value: string): string {
+  const collapsed = value
+    .replace(/[^A-Za-z0-9._-]/g, "_")
+    .split("_")
+    .filter((part) => part.length > 0)
+    .join("_");
+  return collapsed || "value";
+}
+
 export function normalizeHexColor(value: string): string | null {
   const raw = value.trim().replace(/^#/, "");
   if (/^[0-9a-fA-F]{3}$/.test(raw)) {
@@ -175,6 +198,9 @@ export function appendRecurrenceTags(tags: string[], recurrence: RecurrenceDraft
   removeTagsForKey(tags, RECUR_DAYS_TAG_KEY);
   removeTagsForKey(tags, RECUR_MONTHS_TAG_KEY);
   removeTagsForKey(tags, RECUR_MONTH_DAY_TAG_KEY);
+  removeTagsForKey(tags, RECUR_UNTIL_TAG_KEY);
+  removeTagsForKey(tags, RECUR_COUNT_TAG_KEY);
+  removeTagsForKey(tags, RECUR_LEFT_TAG_KEY);
 
   const pattern = normalizeRecurrencePattern(recurrence.pattern);
   if (pattern === "none") {
@@ -208,6 +234,42 @@ export function appendRecurrenceTags(tags: string[], recurrence: RecurrenceDraft
       pushTagUnique(tags, `${RECUR_MONTH_DAY_TAG_KEY}:${monthDay}`);
     }
   }
+
+  const until = recurrence.until.trim();
+  const count = parseRecurrenceCount(recurrence.count);
+  if (recurrence.ends === "until" && /^\d{4}-\d{2}-\d{2}$/.test(until)) {
+    pushTagUnique(tags, `${RECUR_UNTIL_TAG_KEY}:${until}`);
+  } else if (recurrence.ends === "count" && count !== null) {
+    pushTagUnique(tags, `${RECUR_COUNT_TAG_KEY}:${count}`);
+    // Without recur_left the backend starts the countdown from the total.
+    if (recurrence.remaining !== null && recurrence.remaining <= count) {
+      pushTagUnique(tags, `${RECUR_LEFT_TAG_KEY}:${recurrence.remaining}`);
+    }
+  }
+}
+
+function parseRecurrenceCount(value: string): number | null {
+  const trimmed = value.trim();
+  if (!/^\d+$/.test(trimmed)) {
+    return null;
+  }
+  const count = Number.parseInt(trimmed, 10);
+  return count > 0 ? count : null;
+}
+
+export interface RecurrenceProgress {
+  occurrence: number;
+  total: number;
+}
+
+// Which instance of a count-limited series this task is, e.g. 2 of 10.
+export function recurrenceProgress(tags: string[]): RecurrenceProgress | null {
+  const total = parseRecurrenceCount(firstTagValue(tags, RECUR_COUNT_TAG_KEY) ?? "");
+  if (total === null) {
+    return null;
+  }
+  const left = parseRecurrenceCount(firstTagValue(tags, RECUR_LEFT_TAG_KEY) ?? "") ?? total;
+  return { occurrence: Math.max(1, total - Math.min(left, total) + 1), total };
 }
 
 export function recurrenceFromTags(tags: string[]): RecurrenceDraft {
@@ -222,18 +284,61 @@ export function recurrenceFromTags(tags: string[]): RecurrenceDraft {
     .map((entry) => entry.trim().toLowerCase())
     .filter((entry): entry is MonthKey => MONTH_KEYS.includes(entry as MonthKey));
   const monthDay = firstTagValue(tags, RECUR_MONTH_DAY_TAG_KEY) ?? "";
+  const until = firstTagValue(tags, RECUR_UNTIL_TAG_KEY) ?? "";
+  const count = firstTagValue(tags, RECUR_COUNT_TAG_KEY) ?? "";
+  const remaining = parseRecurrenceCount(firstTagValue(tags, RECUR_LEFT_TAG_KEY) ?? "");
   return {
     pattern,
     time,
     days,
     months,
-    monthDay
+    monthDay,
+    ends: count ? "count" : until ? "until" : "never",
+    until,
+    count,
+    remaining
   };
 }
 
-export function isSingleSelectKey(schema: TagSchema | null, keyId: string): boolean {
+export type TagSelectionMode = "single" | "multi" | "exclusive-group";
+
+// A key's `selection`. Unset or unknown values mean "multi", which is how keys
+// without "single" always behaved.
+export function tagSelectionMode(schema: TagSchema | null, keyId: string): TagSelectionMode {
   const key = schema?.keys?.find((entry) => entry.id === keyId);
-  return (key?.selection ?? "").toLowerCase() === "single";
+  const raw = (key?.selection ?? "").trim().toLowerCase();
+  return raw === "single" || raw === "exclusive-group" ? raw : "multi";
+}
+
+// Keys whose values a new `keyId` tag replaces: none for "multi", the key
+// itself for "single", and for "exclusive-group" every exclusive-group key that
+// shares its `group` (just itself when it has none).
+export function conflictingTagKeys(schema: TagSchema | null, keyId: string): string[] {
+  const mode = tagSelectionMode(schema, keyId);
+  if (mode === "multi") {
+    return [];
+  }
+  const group = schema?.keys?.find((entry) => entry.id === keyId)?.group?.trim();
+  if (mode === "single" || !group) {
+    return [keyId];
+  }
+  const siblings = (schema?.keys ?? [])
+    .filter((entry) => entry.group?.trim() === group && tagSelectionMode(schema, entry.id) === "exclusive-group")
+    .map((entry) => entry.id);
+  return siblings.includes(keyId) ? siblings : [keyId, ...siblings];
+}
+
+// `tags` plus `tag`, after dropping the values its key's selection mode rules out.
+export function addTagWithSelection(tags: string[], tag: string, schema: TagSchema | null): string[] {
+  const next = [...tags];
+  const [key] = splitTag(tag);
+  if (key) {
+    for (const conflicting of conflictingTagKeys(schema, key)) {
+      removeTagsForKey(next, conflicting);
+    }
+  }
+  pushTagUnique(next, tag);
+  return next;
 }
 
 export interface CollectSubmitTagsInput {
@@ -288,6 +393,23 @@ export function tagsForKanbanMove(
   return next;
 }
 
+// Tags offered as kanban side-rail drop targets; lanes and boards already have
+// their own targets.
+export function isKanbanRailTag(tag: string): boolean {
+  const [key] = splitTag(tag);
+  return key !== KANBAN_TAG_KEY && key !== BOARD_TAG_KEY;
+}
+
+// Tags after dropping a card on `tag`: a single-select key swaps its value, an
+// exclusive-group key also clears its siblings, anything else is appended.
+// `null` when the card already has the tag.
+export function tagsForTagDrop(tags: string[], tag: string, schema: TagSchema | null): string[] | null {
+  if (tags.includes(tag)) {
+    return null;
+  }
+  return addTagWithSelection(tags, tag, schema);
+}
+
 export function boardIdFromTaskTags(tags: string[]): string | null {
   return firstTagValue(tags, BOARD_TAG_KEY);
 }
@@ -303,6 +425,25 @@ export function kanbanLaneFromTask(tags: string[], availableLanes: string[], fal
   return lane;
 }
 
+// Values of `key` that tasks carry but `known` (the schema's list) lacks, most
+// used first; left behind when a schema value is renamed.
+export function unknownTagValues(
+  tagLists: string[][],
+  key: string,
+  known: string[]
+): Array<{ value: string; count: number }> {
+  const counts = new Map<string, number>();
+  for (const tags of tagLists) {
+    const value = firstTagValue(tags, key);
+    if (value && !known.includes(value)) {
+      counts.set(value, (counts.get(value) ?? 0) + 1);
+    }
+  }
+  return [...counts.entries()]
+    .map(([value, count]) => ({ value, count }))
+    .sort((left, right) => right.count - left.count || left.value.localeCompare(right.value));
+}
+
 export function humanizeLane(value: string): string {
   return value
     .split(/[-_]/g)
@@ -0,0 +1,51 @@
+import { describe, expect, it } from "vitest";
+
+import { shellQuote, taskAddCommand } from "./taskCommand";
+import type { TaskDto } from "../types/core";
+
+function task(overrides: Partial<TaskDto>): TaskDto {
+  return {
+    uuid: "00000000-0000-0000-0000-000000000001",
+    id: 1,
+    title: "Task",
+    description: "",
+    status: "Pending",
+    project: null,
+    tags: [],
+    priority: null,
+    due: null,
+    wait: null,
+    scheduled: null,
+    created: "20260216T050000Z",
+    modified: "20260216T050000Z",
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null,
+    ...overrides
+  };
+}
+
+describe("shellQuote", () => {
+  it("leaves plain words and quotes everything else", () => {
+    expect(shellQuote("kanban:todo")).toBe("kanban:todo");
+    expect(shellQuote("")).toBe("''");
+    expect(shellQuote("it's $HOME")).toBe("'it'\\''s $HOME'");
+  });
+});
+
+describe("taskAddCommand", () => {
+  it("emits user fields and skips uuid and entry", () => {
+    const command = taskAddCommand(task({
+      title: "Buy \"milk\" +later",
+      project: "home chores",
+      tags: ["errand"],
+      priority: "High",
+      due: "20260216T050000Z"
+    }));
+    expect(command).toBe("task add 'project:home chores' +errand priority:H due:20260216T050000Z -- 'Buy \"milk\" +later'");
+    expect(command).not.toContain("0000-0000");
+  });
+});
@@ -0,0 +1,38 @@
+import type { TaskDto, TaskPriority } from "../types/core";
+
+const PLAIN_SHELL_WORD = /^[A-Za-z0-9_\-+=:,./@%]+$/;
+
+const PRIORITY_CODES: Record<TaskPriority, string> = {
+  Low: "L",
+  Medium: "M",
+  High: "H"
+};
+
+export function shellQuote(value: string): string {
+  if (PLAIN_SHELL_WORD.test(value)) {
+    return value;
+  }
+  return `'${value.replace(/'/g, "'\\''")}'`;
+}
+
+// Mirrors `Task::to_add_command` in rivet-core so the Details panel and
+// `task <id> export --format=command` emit the same line.
+export function taskAddCommand(task: TaskDto): string {
+  const parts = ["task", "add"];
+  if (task.project) {
+    parts.push(shellQuote(`project:${task.project}`));
+  }
+  for (const tag of task.tags) {
+    parts.push(shellQuote(`+${tag}`));
+  }
+  if (task.priority) {
+    parts.push(`priority:${PRIORITY_CODES[task.priority]}`);
+  }
+  for (const [key, value] of [["due", task.due], ["scheduled", task.scheduled], ["wait", task.wait]] as const) {
+    if (value) {
+      parts.push(shellQuote(`${key}:${value}`));
+    }
+  }
+  parts.push("--", shellQuote(task.title));
+  return parts.join(" ");
+}
@@ -0,0 +1,54 @@
+import { describe, expect, it } from "vitest";
+
+import { pruneTaskDrafts, resolveDraftAutosaveMs, sameDraftFields, taskDraftKey, TASK_DRAFT_MAX_AGE_MS } from "./taskDraft";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDraftFields } from "../types/ui";
+
+const fields: TaskDraftFields = {
+  title: "Write report",
+  description: "",
+  project: "",
+  due: "",
+  customTagInput: "",
+  selectedTags: ["work"],
+  boardId: "",
+  priority: null,
+  recurrence: {
+    pattern: "none",
+    time: "",
+    days: [],
+    months: [],
+    monthDay: "",
+    ends: "never",
+    until: "",
+    count: "",
+    remaining: null
+  }
+};
+
+const config = (seconds?: number) => ({ ui: { task_editor: { draft_autosave_seconds: seconds } } }) as RivetRuntimeConfig;
+
+describe("task drafts", () => {
+  it("keys drafts by dialog mode", () => {
+    expect(taskDraftKey(null)).toBe("add");
+    expect(taskDraftKey("abc")).toBe("edit:abc");
+  });
+
+  it("reads the autosave interval", () => {
+    expect(resolveDraftAutosaveMs(null)).toBe(5000);
+    expect(resolveDraftAutosaveMs(config(2))).toBe(2000);
+    expect(resolveDraftAutosaveMs(config(0))).toBeNull();
+    expect(resolveDraftAutosaveMs(config(-1))).toBe(5000);
+  });
+
+  it("compares only the form fields", () => {
+    expect(sameDraftFields(fields, { ...fields, saved_at: 1 } as TaskDraftFields)).toBe(true);
+    expect(sameDraftFields(fields, { ...fields, description: "x" })).toBe(false);
+  });
+
+  it("drops stale drafts", () => {
+    const now = TASK_DRAFT_MAX_AGE_MS * 2;
+    const pruned = pruneTaskDrafts({ add: { ...fields, saved_at: now - 1000 }, "edit:old": { ...fields, saved_at: 0 } }, now);
+    expect(Object.keys(pruned)).toEqual(["add"]);
+  });
+});
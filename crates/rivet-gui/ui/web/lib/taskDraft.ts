@@ -0,0 +1,49 @@
+import { logger } from "./logger";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDraft, TaskDraftFields } from "../types/ui";
+
+export const DEFAULT_DRAFT_AUTOSAVE_SECONDS = 5;
+// Drafts nobody came back to within a month are dropped on the next save.
+export const TASK_DRAFT_MAX_AGE_MS = 30 * 24 * 60 * 60 * 1000;
+
+// One draft for the add dialog and one per edited task.
+export function taskDraftKey(editUuid: string | null): string {
+  return editUuid ? `edit:${editUuid}` : "add";
+}
+
+// `[ui.task_editor].draft_autosave_seconds`; 0 turns drafts off.
+export function resolveDraftAutosaveMs(config: RivetRuntimeConfig | null): number | null {
+  const raw = config?.ui?.task_editor?.draft_autosave_seconds;
+  if (raw === undefined) {
+    return DEFAULT_DRAFT_AUTOSAVE_SECONDS * 1000;
+  }
+  if (!Number.isFinite(raw) || raw < 0) {
+    logger.warn("config.ui.task_editor.draft_autosave_seconds", `invalid value '${raw}'`);
+    return DEFAULT_DRAFT_AUTOSAVE_SECONDS * 1000;
+  }
+  return raw === 0 ? null : Math.max(1, raw) * 1000;
+}
+
+export function sameDraftFields(left: TaskDraftFields, right: TaskDraftFields): boolean {
+  return JSON.stringify(draftFields(left)) === JSON.stringify(draftFields(right));
+}
+
+export function draftFields(draft: TaskDraftFields): TaskDraftFields {
+  return {
+    title: draft.title,
+    description: draft.description,
+    project: draft.project,
+    due: draft.due,
+    customTagInput: draft.customTagInput,
+    selectedTags: [...draft.selectedTags],
+    boardId: draft.boardId,
+    priority: draft.priority,
+    recurrence: { ...draft.recurrence }
+  };
+}
+
+export function pruneTaskDrafts(drafts: Record<string, TaskDraft>, nowMs: number): Record<string, TaskDraft> {
+  return Object.fromEntries(
+    Object.entries(drafts).filter(([, draft]) => typeof draft?.saved_at === "number" && nowMs - draft.saved_at <= TASK_DRAFT_MAX_AGE_MS)
+  );
+}
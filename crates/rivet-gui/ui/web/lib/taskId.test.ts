@@ -0,0 +1,27 @@
+import { describe, expect, it } from "vitest";
+
+import { resolveIdDisplay, taskDisplayId } from "./taskId";
+import type { RivetRuntimeConfig } from "../types/config";
+
+const config = (idDisplay?: string) => ({ ui: { task_list: { id_display: idDisplay } } }) as RivetRuntimeConfig;
+
+describe("task id display", () => {
+  it("defaults to the short id", () => {
+    expect(resolveIdDisplay(null)).toBe("id");
+    expect(resolveIdDisplay(config())).toBe("id");
+    expect(resolveIdDisplay(config(" UUID "))).toBe("uuid");
+    expect(resolveIdDisplay(config("bogus"))).toBe("id");
+  });
+
+  it("shows the uuid prefix or the working-set id", () => {
+    const task = { id: 12, uuid: "a1b2c3d4-e5f6-4000-8000-000000000000" };
+    expect(taskDisplayId(task, "uuid")).toBe("a1b2c3d4");
+    expect(taskDisplayId(task, "id")).toBe("12");
+    expect(taskDisplayId({ ...task, id: null }, "id")).toBeNull();
+  });
+
+  it("grows the prefix until it has a digit and a letter", () => {
+    expect(taskDisplayId({ id: 1, uuid: "12345678-9abc-4000-8000-000000000003" }, "uuid")).toBe("123456789a");
+    expect(taskDisplayId({ id: 1, uuid: "abcdefab-cdef-4000-8000-000000000004" }, "uuid")).toBe("abcdefabcdef4");
+  });
+});
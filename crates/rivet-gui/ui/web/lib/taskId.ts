@@ -0,0 +1,44 @@
+import { logger } from "./logger";
+import type { RivetRuntimeConfig } from "../types/config";
+import type { TaskDto } from "../types/core";
+
+export type TaskIdDisplay = "id" | "uuid";
+
+// Same length as rivet-core `UUID_PREFIX_LEN`, so the GUI and
+// `task --ids uuid` show the same prefix.
+export const UUID_PREFIX_LEN = 8;
+
+// `[ui.task_list].id_display`, matching the CLI's `id.display`: `uuid` shows
+// a uuid prefix that survives `gc` renumbering instead of the working-set id.
+export function resolveIdDisplay(config: RivetRuntimeConfig | null): TaskIdDisplay {
+  const raw = config?.ui?.task_list?.id_display?.trim().toLowerCase();
+  if (!raw || raw === "id" || raw === "short") {
+    return "id";
+  }
+  if (raw === "uuid") {
+    return "uuid";
+  }
+  logger.warn("config.ui.task_list.id_display", `unknown id display '${raw}'`);
+  return "id";
+}
+
+// Grown like rivet-core `Task::short_uuid` until it holds a digit and a
+// letter, so typing it back selects the task instead of an id or a word.
+function shortUuid(uuid: string): string {
+  const hex = uuid.replace(/-/g, "");
+  for (let len = UUID_PREFIX_LEN; len < hex.length; len += 1) {
+    const prefix = hex.slice(0, len);
+    if (/[0-9]/.test(prefix) && /[a-z]/i.test(prefix)) {
+      return prefix;
+    }
+  }
+  return hex;
+}
+
+// Null when the task has no working-set id (closed tasks) in `id` mode.
+export function taskDisplayId(task: Pick<TaskDto, "id" | "uuid">, mode: TaskIdDisplay): string | null {
+  if (mode === "uuid") {
+    return shortUuid(task.uuid);
+  }
+  return task.id === null ? null : String(task.id);
+}
@@ -0,0 +1,54 @@
+import { describe, expect, it } from "vitest";
+
+import { placeInOrder, sortTasks, taskUrgency } from "./taskSort";
+import type { TaskDto } from "../types/core";
+
+const task = (uuid: string, patch: Partial<TaskDto> = {}) =>
+  ({ uuid, status: "Pending", tags: [], priority: null, due: null, started: null, ...patch }) as TaskDto;
+
+describe("taskUrgency", () => {
+  const now = Date.parse("2026-03-04T12:00:00Z");
+
+  it("follows the core coefficients", () => {
+    expect(taskUrgency(task("a", { priority: "High", tags: ["work"] }), now)).toBeCloseTo(6.8);
+    expect(taskUrgency(task("a", { due: "2026-03-03T11:00:00Z" }), now)).toBeCloseTo(9.7);
+    expect(taskUrgency(task("a", { due: "2026-03-06T00:00:00Z", started: "2026-03-04T10:00:00Z" }), now)).toBeCloseTo(12.4);
+    expect(taskUrgency(task("a", { status: "Waiting", priority: "Low" }), now)).toBeCloseTo(-1.2);
+    expect(taskUrgency(task("a", { status: "Completed", priority: "High" }), now)).toBe(0);
+  });
+});
+
+describe("sortTasks", () => {
+  const now = Date.parse("2026-03-04T12:00:00Z");
+  const tasks = [
+    task("low", { priority: "Low", due: "2026-04-01T00:00:00Z" }),
+    task("none"),
+    task("high", { priority: "High" }),
+    task("soon", { due: "2026-03-05T00:00:00Z" })
+  ];
+  const ids = (sorted: TaskDto[]) => sorted.map((entry) => entry.uuid);
+
+  it("orders by urgency and by due date", () => {
+    expect(ids(sortTasks(tasks, "urgency", { nowUtcMs: now }))).toEqual(["soon", "high", "low", "none"]);
+    expect(ids(sortTasks(tasks, "due", { nowUtcMs: now }))).toEqual(["soon", "low", "none", "high"]);
+  });
+
+  it("keeps unlisted tasks after the manual order", () => {
+    expect(ids(sortTasks(tasks, "manual", { nowUtcMs: now, manualOrder: ["high", "gone", "none"] }))).toEqual([
+      "high",
+      "none",
+      "low",
+      "soon"
+    ]);
+    expect(ids(sortTasks(tasks, "manual", { nowUtcMs: now }))).toEqual(["low", "none", "high", "soon"]);
+  });
+});
+
+describe("placeInOrder", () => {
+  it("moves a task next to the anchor", () => {
+    expect(placeInOrder(["a", "b", "c"], "c", "a", "before")).toEqual(["c", "a", "b"]);
+    expect(placeInOrder(["a", "b", "c"], "a", "b", "after")).toEqual(["b", "a", "c"]);
+    expect(placeInOrder(["a", "b"], "x", null, "after")).toEqual(["a", "b", "x"]);
+    expect(placeInOrder(["a", "b"], "a", "a", "before")).toEqual(["b", "a"]);
+  });
+});
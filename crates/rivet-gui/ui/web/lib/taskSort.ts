@@ -0,0 +1,90 @@
+import { parseTaskDueUtcMs } from "./calendar";
+import type { TaskDto } from "../types/core";
+
+export type TaskSortMode = "manual" | "urgency" | "due";
+
+const PRIORITY_URGENCY: Record<string, number> = {
+  High: 6.0,
+  Medium: 3.9,
+  Low: 1.8
+};
+
+// Mirrors `Task::urgency` in rivet-core without the dependency penalty and
+// the `urgency.user.*` coefficients, which the DTO does not carry.
+export function taskUrgency(task: TaskDto, nowUtcMs: number): number {
+  if (task.status === "Completed" || task.status === "Deleted" || task.status === "Archived") {
+    return 0;
+  }
+  let urgency = task.tags.length * 0.8;
+  urgency += task.priority ? PRIORITY_URGENCY[task.priority] ?? 0 : 0;
+  if (task.status === "Waiting") {
+    urgency -= 3.0;
+  } else if (task.started) {
+    urgency += 4.0;
+  }
+  const dueUtcMs = task.due ? parseTaskDueUtcMs(task.due) : null;
+  if (dueUtcMs !== null) {
+    const days = Math.floor((dueUtcMs - nowUtcMs) / 60_000) / (24 * 60);
+    if (days <= -1) {
+      urgency += 9.7;
+    } else if (days <= 0) {
+      urgency += 9.3;
+    } else if (days <= 1) {
+      urgency += 8.8;
+    } else if (days <= 2) {
+      urgency += 8.4;
+    } else if (days <= 7) {
+      urgency += 6.0;
+    } else {
+      urgency += 3.0;
+    }
+  }
+  return urgency;
+}
+
+function compareKeys(left: number, right: number): number {
+  return left === right ? 0 : left < right ? -1 : 1;
+}
+
+function dueKey(task: TaskDto): number {
+  return (task.due ? parseTaskDueUtcMs(task.due) : null) ?? Infinity;
+}
+
+// Sorts a copy of `tasks`. "manual" follows `manualOrder` and keeps tasks it
+// does not list after the ordered ones, in their incoming order; "due" puts
+// undated tasks last. Ties keep the incoming order.
+export function sortTasks(
+  tasks: readonly TaskDto[],
+  mode: TaskSortMode,
+  options: { nowUtcMs: number; manualOrder?: readonly string[] }
+): TaskDto[] {
+  if (mode === "manual") {
+    const rank = new Map((options.manualOrder ?? []).map((uuid, index) => [uuid, index]));
+    return [...tasks].sort((left, right) =>
+      compareKeys(rank.get(left.uuid) ?? Infinity, rank.get(right.uuid) ?? Infinity)
+    );
+  }
+  if (mode === "due") {
+    return [...tasks].sort((left, right) => compareKeys(dueKey(left), dueKey(right)));
+  }
+  const scores = new Map(tasks.map((task) => [task.uuid, taskUrgency(task, options.nowUtcMs)]));
+  return [...tasks].sort((left, right) => compareKeys(scores.get(right.uuid) ?? 0, scores.get(left.uuid) ?? 0));
+}
+
+// Moves `taskId` next to `anchorId` in a display order; an anchor that is
+// missing (or the task itself) appends the task at the end.
+export function placeInOrder(
+  order: readonly string[],
+  taskId: string,
+  anchorId: string | null,
+  position: "before" | "after"
+): string[] {
+  const next = order.filter((uuid) => uuid !== taskId);
+  const anchorIndex = anchorId === null || anchorId === taskId ? -1 : next.indexOf(anchorId);
+  if (anchorIndex === -1) {
+    next.push(taskId);
+  } else {
+    next.splice(position === "before" ? anchorIndex : anchorIndex + 1, 0, taskId);
+  }
+  return next;
+}
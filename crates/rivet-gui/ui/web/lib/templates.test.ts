@@ -0,0 +1,74 @@
+import { describe, expect, it } from "vitest";
+
+import { templateDueIso, templateFormTags, templateFromTask, upsertTemplate } from "./templates";
+import type { TaskDto } from "../types/core";
+
+const NOW = Date.UTC(2026, 2, 10, 15, 30);
+
+const task = (patch: Partial<TaskDto> = {}): TaskDto => ({
+  uuid: "u1",
+  id: 4,
+  title: "Weekly report",
+  description: "send to team",
+  status: "Completed",
+  project: "work",
+  tags: ["recur:weekly", "recur_count:5", "recur_left:2", "recur_series:abc", "kanban:finished", "board:ops", "report"],
+  priority: "High",
+  due: "2026-03-12T09:00:00Z",
+  wait: "2026-03-11T09:00:00Z",
+  scheduled: null,
+  created: "2026-03-01T00:00:00Z",
+  modified: "2026-03-09T00:00:00Z",
+  completed: "2026-03-09T00:00:00Z",
+  started: null,
+  focus_seconds: 120,
+  parent: null,
+  children: [],
+  contact: null,
+  ...patch
+});
+
+describe("task templates", () => {
+  it("keeps the task shape and drops instance state", () => {
+    const template = templateFromTask(task(), "  Report  ", { includeDue: false, nowUtcMs: NOW, timezone: "UTC" });
+    expect(template.name).toBe("Report");
+    expect(template.title).toBe("Weekly report");
+    expect(template.project).toBe("work");
+    expect(template.priority).toBe("High");
+    expect(template.tags).toEqual(["recur:weekly", "recur_count:5", "board:ops", "report"]);
+    expect(template.due_offset).toBeNull();
+    expect(templateDueIso(template, NOW, "UTC")).toBeNull();
+    expect(templateFormTags(template)).toEqual(["report"]);
+  });
+
+  it("stores the due date as days from today at the same local time", () => {
+    const template = templateFromTask(task(), "", { includeDue: true, nowUtcMs: NOW, timezone: "UTC" });
+    expect(template.name).toBe("Weekly report");
+    expect(template.due_offset).toEqual({ days: 2, hour: 9, minute: 0 });
+    expect(templateDueIso(template, Date.UTC(2026, 5, 1, 8, 0), "UTC")).toBe("2026-06-03T09:00:00.000Z");
+
+    const overdue = templateFromTask(task({ due: "2026-03-01T09:00:00Z" }), "x", {
+      includeDue: true,
+      nowUtcMs: NOW,
+      timezone: "UTC"
+    });
+    expect(overdue.due_offset?.days).toBe(0);
+  });
+
+  it("replaces a template saved under the same name", () => {
+    const first = templateFromTask(task(), "Report", { includeDue: false, nowUtcMs: NOW, timezone: "UTC" });
+    const other = templateFromTask(task(), "Errand", { includeDue: false, nowUtcMs: NOW, timezone: "UTC" });
+    const list = upsertTemplate(upsertTemplate([], first), other);
+    expect(list.map((entry) => entry.name)).toEqual(["Errand", "Report"]);
+
+    const again = templateFromTask(task({ title: "Monthly report" }), "report", {
+      includeDue: false,
+      nowUtcMs: NOW,
+      timezone: "UTC"
+    });
+    const replaced = upsertTemplate(list, again);
+    expect(replaced).toHaveLength(2);
+    expect(replaced[1].id).toBe(first.id);
+    expect(replaced[1].title).toBe("Monthly report");
+  });
+});
@@ -0,0 +1,83 @@
+import type { TaskDto } from "../types/core";
+import type { TaskTemplate, TaskTemplateDueOffset } from "../types/ui";
+import { parseTaskDueUtcMs, zonedDateTimeParts } from "./calendar";
+import { zonedLocalToUtcMs } from "./snooze";
+import { BOARD_TAG_KEY, DEMO_TAG_KEY, KANBAN_TAG_KEY, RECUR_LEFT_TAG_KEY, RECUR_TAG_KEY, splitTag } from "./tags";
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+// Tags that describe where one task is rather than what it is: its place in
+// a recurring series, its kanban lane and the demo marker. Matches the
+// series tag written by rivet-core `recur.rs`.
+const INSTANCE_TAG_KEYS = new Set([RECUR_LEFT_TAG_KEY, "recur_series", KANBAN_TAG_KEY, DEMO_TAG_KEY]);
+
+export interface TemplateCaptureOptions {
+  includeDue: boolean;
+  nowUtcMs: number;
+  timezone: string;
+}
+
+// The reusable part of a task. Status, ids and timestamps never carry over;
+// wait and scheduled are dropped with them, and the due date only when asked
+// for, as whole days from today at the same local time (never in the past).
+export function templateFromTask(task: TaskDto, name: string, options: TemplateCaptureOptions): TaskTemplate {
+  return {
+    id: crypto.randomUUID(),
+    name: name.trim() || task.title.trim() || "Template",
+    title: task.title,
+    description: task.description,
+    project: task.project,
+    tags: task.tags.filter((tag) => {
+      const [key] = splitTag(tag);
+      return key === null || !INSTANCE_TAG_KEYS.has(key);
+    }),
+    priority: task.priority,
+    due_offset: options.includeDue && task.due ? dueOffset(task.due, options.nowUtcMs, options.timezone) : null
+  };
+}
+
+function dueOffset(rawDue: string, nowUtcMs: number, timezone: string): TaskTemplateDueOffset | null {
+  const dueUtcMs = parseTaskDueUtcMs(rawDue);
+  if (dueUtcMs === null) {
+    return null;
+  }
+  const due = zonedDateTimeParts(dueUtcMs, timezone);
+  const now = zonedDateTimeParts(nowUtcMs, timezone);
+  const days = Math.round(
+    (Date.UTC(due.year, due.month - 1, due.day) - Date.UTC(now.year, now.month - 1, now.day)) / DAY_MS
+  );
+  return { days: Math.max(0, days), hour: due.hour, minute: due.minute };
+}
+
+// Due date for a task created from `template` now, or null without one.
+export function templateDueIso(template: TaskTemplate, nowUtcMs: number, timezone: string): string | null {
+  const offset = template.due_offset;
+  if (!offset) {
+    return null;
+  }
+  const now = zonedDateTimeParts(nowUtcMs, timezone);
+  return new Date(
+    zonedLocalToUtcMs(now.year, now.month, now.day + offset.days, offset.hour, offset.minute, timezone)
+  ).toISOString();
+}
+
+// Tags the add dialog lists as plain tags: the board and recurrence have
+// their own fields there.
+export function templateFormTags(template: TaskTemplate): string[] {
+  return template.tags.filter((tag) => {
+    const [key] = splitTag(tag);
+    return key !== BOARD_TAG_KEY && key !== RECUR_TAG_KEY && !(key ?? "").startsWith(`${RECUR_TAG_KEY}_`);
+  });
+}
+
+// Saving under an existing name (any case) replaces that template.
+export function upsertTemplate(templates: TaskTemplate[], template: TaskTemplate): TaskTemplate[] {
+  const key = template.name.trim().toLowerCase();
+  const index = templates.findIndex((entry) => entry.name.trim().toLowerCase() === key);
+  if (index < 0) {
+    return [...templates, template].sort((a, b) => a.name.localeCompare(b.name));
+  }
+  const next = [...templates];
+  next[index] = { ...template, id: templates[index].id };
+  return next;
+}
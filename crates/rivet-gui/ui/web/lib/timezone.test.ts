@@ -0,0 +1,48 @@
+import { describe, expect, it } from "vitest";
+
+import { resolveCalendarConfig } from "./calendar";
+import { dueTimezoneMismatch, timezoneAbbreviation, timezoneNeedsAttention } from "./timezone";
+import type { RivetRuntimeConfig } from "../types/config";
+
+describe("timezoneAbbreviation", () => {
+  it("follows daylight saving time", () => {
+    expect(timezoneAbbreviation(Date.parse("2026-01-15T12:00:00Z"), "America/New_York")).toBe("EST");
+    expect(timezoneAbbreviation(Date.parse("2026-07-15T12:00:00Z"), "America/New_York")).toBe("EDT");
+  });
+});
+
+describe("dueTimezoneMismatch", () => {
+  // Midnight 2026-03-06 in Mexico City (UTC-6).
+  const due = "20260306T060000Z";
+
+  it("flags date-only dues entered under the backend zone", () => {
+    expect(dueTimezoneMismatch(due, "Europe/Madrid", "America/Mexico_City")).toBe("America/Mexico_City");
+  });
+
+  it("stays quiet when the zones agree or the due has a time", () => {
+    expect(dueTimezoneMismatch(due, "America/Mexico_City", "America/Mexico_City")).toBeNull();
+    expect(dueTimezoneMismatch(due, "Europe/Madrid", null)).toBeNull();
+    expect(dueTimezoneMismatch("20260306T153000Z", "Europe/Madrid", "America/Mexico_City")).toBeNull();
+  });
+
+  it("matches the configured due default time instead of midnight", () => {
+    // 23:59 on 2026-03-06 in Mexico City.
+    const lateDue = "20260307T055900Z";
+    expect(dueTimezoneMismatch(lateDue, "Europe/Madrid", "America/Mexico_City", "23:59")).toBe("America/Mexico_City");
+    expect(dueTimezoneMismatch(lateDue, "Europe/Madrid", "America/Mexico_City")).toBeNull();
+  });
+});
+
+describe("timezoneNeedsAttention", () => {
+  it("warns on the default zone or a backend mismatch", () => {
+    expect(timezoneNeedsAttention(resolveCalendarConfig({} as RivetRuntimeConfig))).toBe(true);
+    expect(
+      timezoneNeedsAttention(
+        resolveCalendarConfig({ timezone: "UTC", effective: { timezone: "America/Mexico_City" } } as RivetRuntimeConfig)
+      )
+    ).toBe(true);
+    expect(
+      timezoneNeedsAttention(resolveCalendarConfig({ effective: { timezone: "America/Mexico_City" } } as RivetRuntimeConfig))
+    ).toBe(false);
+  });
+});
@@ -0,0 +1,59 @@
+import type { EffectiveCalendarConfig, TimezoneSource } from "../types/ui";
+import { parseTaskDueUtcMs, zonedDateTimeParts } from "./calendar";
+
+const SOURCE_LABELS: Record<TimezoneSource, string> = {
+  "calendar.timezone": "calendar.timezone in rivet.toml",
+  "time.timezone": "time.timezone in rivet.toml",
+  timezone: "timezone in rivet.toml",
+  backend: "the backend's timezone",
+  default: "the built-in default (no timezone configured)"
+};
+
+export function timezoneAbbreviation(utcMs: number, timezone: string): string {
+  const part = new Intl.DateTimeFormat("en-US", { timeZone: timezone, timeZoneName: "short" })
+    .formatToParts(utcMs)
+    .find((entry) => entry.type === "timeZoneName");
+  return part?.value ?? timezone;
+}
+
+export function describeTimezoneSource(config: EffectiveCalendarConfig): string {
+  return `Timezone ${config.timezone} from ${SOURCE_LABELS[config.timezone_source]}`;
+}
+
+// The header warns when dates may render differently from the CLI: either nothing is
+// configured, or the GUI zone differs from the one the backend resolves dates in.
+export function timezoneNeedsAttention(config: EffectiveCalendarConfig): boolean {
+  return (
+    config.timezone_source === "default"
+    || (config.backend_timezone !== null && config.backend_timezone !== config.timezone)
+  );
+}
+
+function isLocalClockTime(utcMs: number, timezone: string, clockTime: string): boolean {
+  const [hour, minute] = clockTime.split(":").map(Number);
+  const parts = zonedDateTimeParts(utcMs, timezone);
+  return parts.hour === hour && parts.minute === minute && parts.second === 0;
+}
+
+// A date-only due (`due:friday`) is stored at the due default time (midnight unless
+// `due_default_time` is set) in the backend's zone. When that instant is not the same
+// wall-clock time where the GUI renders, the task was most likely entered under a
+// different timezone; returns that zone so the UI can say so.
+export function dueTimezoneMismatch(
+  rawDue: string,
+  displayTimezone: string,
+  backendTimezone: string | null,
+  dueDefaultTime = "00:00"
+): string | null {
+  if (!backendTimezone || backendTimezone === displayTimezone) {
+    return null;
+  }
+  const utcMs = parseTaskDueUtcMs(rawDue);
+  if (utcMs === null) {
+    return null;
+  }
+  return isLocalClockTime(utcMs, backendTimezone, dueDefaultTime)
+    && !isLocalClockTime(utcMs, displayTimezone, dueDefaultTime)
+    ? backendTimezone
+    : null;
+}
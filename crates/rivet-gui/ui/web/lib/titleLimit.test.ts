@@ -0,0 +1,31 @@
+import { describe, expect, it } from "vitest";
+
+import { resolveTitleLimit, titleLengthHint, titleRejected } from "./titleLimit";
+import type { RivetRuntimeConfig } from "../types/config";
+
+const config = (max_length?: number, overflow?: string) => ({ tasks: { title: { max_length, overflow } } }) as RivetRuntimeConfig;
+
+describe("titleLimit", () => {
+  it("resolves the limit from [tasks.title]", () => {
+    expect(resolveTitleLimit(null)).toBeNull();
+    expect(resolveTitleLimit(config(0))).toBeNull();
+    expect(resolveTitleLimit(config(-3))).toBeNull();
+    expect(resolveTitleLimit(config(80))).toEqual({ maxLength: 80, overflow: "truncate" });
+    expect(resolveTitleLimit(config(80, " Reject "))).toEqual({ maxLength: 80, overflow: "reject" });
+  });
+
+  it("warns near the limit and explains the overflow", () => {
+    const limit = { maxLength: 10, overflow: "truncate" as const };
+    expect(titleLengthHint("short", limit)).toBeNull();
+    expect(titleLengthHint("ninechars", limit)).toEqual({ over: false, message: "9/10 characters" });
+    expect(titleLengthHint("eleven char", limit)?.over).toBe(true);
+    expect(titleLengthHint("eleven char", limit)?.message).toContain("annotation");
+    expect(titleLengthHint("x".repeat(50), null)).toBeNull();
+  });
+
+  it("only blocks saving when overflow is reject", () => {
+    expect(titleRejected("eleven char", { maxLength: 10, overflow: "truncate" })).toBe(false);
+    expect(titleRejected("eleven char", { maxLength: 10, overflow: "reject" })).toBe(true);
+    expect(titleRejected("  ten chars   ", { maxLength: 10, overflow: "reject" })).toBe(false);
+  });
+});
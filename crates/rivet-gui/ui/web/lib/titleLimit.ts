@@ -0,0 +1,64 @@
+import { logger } from "./logger";
+import type { RivetRuntimeConfig } from "../types/config";
+
+export type TitleOverflow = "truncate" | "reject";
+
+export interface TitleLimit {
+  maxLength: number;
+  overflow: TitleOverflow;
+}
+
+export interface TitleLengthHint {
+  over: boolean;
+  message: string;
+}
+
+// The counter shows up once a title reaches this share of the limit.
+export const TITLE_LIMIT_WARN_RATIO = 0.9;
+
+// `[tasks.title]` max_length / overflow, enforced by the backend on add and update.
+export function resolveTitleLimit(config: RivetRuntimeConfig | null): TitleLimit | null {
+  const section = config?.tasks?.title;
+  const raw = section?.max_length;
+  if (raw === undefined || raw === 0) {
+    return null;
+  }
+  if (!Number.isInteger(raw) || raw < 0) {
+    logger.warn("config.tasks.title.max_length", `invalid value '${raw}'`);
+    return null;
+  }
+  const overflow = (section?.overflow ?? "truncate").trim().toLowerCase();
+  if (overflow !== "truncate" && overflow !== "reject") {
+    logger.warn("config.tasks.title.overflow", `invalid value '${section?.overflow}'`);
+  }
+  return { maxLength: raw, overflow: overflow === "reject" ? "reject" : "truncate" };
+}
+
+// Counts code points, like the backend's character count.
+export function titleLength(title: string): number {
+  return Array.from(title.trim()).length;
+}
+
+export function titleLengthHint(title: string, limit: TitleLimit | null): TitleLengthHint | null {
+  if (!limit) {
+    return null;
+  }
+  const length = titleLength(title);
+  if (length < Math.ceil(limit.maxLength * TITLE_LIMIT_WARN_RATIO)) {
+    return null;
+  }
+  if (length <= limit.maxLength) {
+    return { over: false, message: `${length}/${limit.maxLength} characters` };
+  }
+  const excess = length - limit.maxLength;
+  return {
+    over: true,
+    message: limit.overflow === "reject"
+      ? `${length}/${limit.maxLength} characters: ${excess} over the limit, shorten it to save`
+      : `${length}/${limit.maxLength} characters: will be cut, the full text is kept as an annotation`
+  };
+}
+
+export function titleRejected(title: string, limit: TitleLimit | null): boolean {
+  return limit?.overflow === "reject" && titleLength(title) > limit.maxLength;
+}
@@ -2,7 +2,15 @@ import { describe, expect, it } from "vitest";
 
 import type { TaskDto } from "../types/core";
 import type { TaskFilters } from "../types/ui";
-import { buildTaskFacets, filterTasks } from "./selectors";
+import {
+  buildTaskFacets,
+  explainTaskFilters,
+  facetBulkPatches,
+  filterTasks,
+  matchesFilters,
+  openTasksForContact,
+  unusedTagNames
+} from "./selectors";
 
 function makeTask(index: number): TaskDto {
   return {
@@ -18,7 +26,13 @@ function makeTask(index: number): TaskDto {
     wait: null,
     scheduled: null,
     created: null,
-    modified: null
+    modified: null,
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null
   };
 }
 
@@ -46,6 +60,18 @@ describe("store selectors", () => {
     expect(filtered.every((task) => task.tags.includes("area:alpha"))).toBe(true);
   });
 
+  it("explains exactly the tasks the filters drop", () => {
+    const filters: TaskFilters = { ...baseFilters, status: "Pending", project: "proj-a", due: "has_due" };
+    for (const task of Array.from({ length: 12 }).map((_, index) => makeTask(index))) {
+      expect(explainTaskFilters(task, filters).length === 0).toBe(matchesFilters(task, filters));
+    }
+    expect(explainTaskFilters(makeTask(5), filters)).toEqual([
+      "hidden: status=completed (showing pending tasks)",
+      "filtered out by has due date (no due date)",
+      "filtered out by project 'proj-a' (project is proj-b)"
+    ]);
+  });
+
   it("builds project and tag facets", () => {
     const tasks = Array.from({ length: 12 }).map((_, index) => makeTask(index));
     const facets = buildTaskFacets(tasks);
@@ -53,6 +79,45 @@ describe("store selectors", () => {
     expect(facets.tagFacets.some((entry) => entry.value.startsWith("area:"))).toBe(true);
   });
 
+  it("offers only plain tags without open tasks for cleanup", () => {
+    const usage = [
+      { tag: "old", count: 3, open: 0, last_used: "20260101T000000Z" },
+      { tag: "home", count: 4, open: 2, last_used: "20260301T000000Z" },
+      { tag: "kanban:finished", count: 5, open: 0, last_used: "20260301T000000Z" },
+      { tag: "archive", count: 1, open: 0, last_used: "20250101T000000Z" }
+    ];
+    expect(unusedTagNames(usage)).toEqual(["archive", "old"]);
+  });
+
+  it("lists open tasks linked to a contact, soonest due first", () => {
+    const later = { ...makeTask(1), contact: "c1", due: "2026-04-01T10:00:00Z" };
+    const sooner = { ...makeTask(2), contact: "c1", due: "2026-03-01T10:00:00Z" };
+    const undated = { ...makeTask(3), contact: "c1" };
+    const done = { ...makeTask(5), contact: "c1" };
+    const other = { ...makeTask(7), contact: "c2" };
+
+    expect(openTasksForContact([later, undated, done, other, sooner], "c1").map((task) => task.uuid))
+      .toEqual([sooner.uuid, later.uuid, undated.uuid]);
+  });
+
+  it("builds facet bulk patches for apply, remove and rename", () => {
+    const tasks = Array.from({ length: 6 }).map((_, index) => makeTask(index));
+
+    const apply = facetBulkPatches(tasks, ["task-1", "task-2"], { facet: "tag", value: "area:alpha", operation: "apply" });
+    expect(apply.map((entry) => entry.uuid)).toEqual(["task-1"]);
+    expect(apply[0]?.patch.tags).toContain("area:alpha");
+
+    const remove = facetBulkPatches(tasks, [], { facet: "tag", value: "area:beta", operation: "remove" });
+    expect(remove.map((entry) => entry.uuid)).toEqual(["task-1", "task-3", "task-5"]);
+    expect(remove.every((entry) => !entry.patch.tags?.includes("area:beta"))).toBe(true);
+
+    const rename = facetBulkPatches(tasks, [], { facet: "project", value: "proj-a", operation: "rename", renameTo: "proj-z" });
+    expect(rename.map((entry) => entry.uuid)).toEqual(["task-0", "task-3"]);
+    expect(rename.every((entry) => entry.patch.project === "proj-z")).toBe(true);
+
+    expect(facetBulkPatches(tasks, [], { facet: "project", value: "proj-a", operation: "rename", renameTo: " " })).toEqual([]);
+  });
+
   it("handles large datasets within practical runtime budget", () => {
     const tasks = Array.from({ length: 12_000 }).map((_, index) => makeTask(index));
     const startedAt = performance.now();
@@ -1,5 +1,5 @@
-import type { TaskDto, TaskStatus } from "../types/core";
-import type { DueFilter, PriorityFilter, StatusFilter, TaskFilters } from "../types/ui";
+import type { TagUsage, TaskDto, TaskStatus, TaskUpdateArgs } from "../types/core";
+import type { DueFilter, FacetBulkAction, PriorityFilter, StatusFilter, TaskFilters } from "../types/ui";
 
 function compareText(haystack: string, needle: string): boolean {
   return haystack.toLowerCase().includes(needle.toLowerCase());
@@ -68,6 +68,40 @@ export function matchesFilters(task: TaskDto, filters: TaskFilters): boolean {
   return true;
 }
 
+// Why `filters` drop `task`, one line per failing filter in the wording of
+// `task explain`; empty exactly when `matchesFilters` keeps it.
+export function explainTaskFilters(task: TaskDto, filters: TaskFilters): string[] {
+  const reasons: string[] = [];
+  if (!statusMatches(task.status, filters.status)) {
+    reasons.push(`hidden: status=${task.status.toLowerCase()} (showing ${filters.status.toLowerCase()} tasks)`);
+  }
+  if (!priorityMatches(task, filters.priority)) {
+    reasons.push(`filtered out by priority:${filters.priority} (priority is ${task.priority?.toLowerCase() ?? "none"})`);
+  }
+  if (!dueMatches(task, filters.due)) {
+    reasons.push(filters.due === "has_due" ? "filtered out by has due date (no due date)" : `filtered out by no due date (due ${task.due})`);
+  }
+
+  const search = filters.search.trim();
+  if (search.length > 0) {
+    const haystack = [task.title, task.description, task.project ?? "", task.tags.join(" ")].join(" ");
+    if (!compareText(haystack, search)) {
+      reasons.push(`filtered out by search '${search}' (not in title, description, project or tags)`);
+    }
+  }
+
+  const project = filters.project.trim();
+  if (project.length > 0 && !compareText(task.project ?? "", project)) {
+    reasons.push(`filtered out by project '${project}' (project is ${task.project ?? "none"})`);
+  }
+
+  const tag = filters.tag.trim();
+  if (tag.length > 0 && !task.tags.some((entry) => compareText(entry, tag))) {
+    reasons.push(`filtered out by tag '${tag}' (no matching tag)`);
+  }
+  return reasons;
+}
+
 export function facetCounts(items: string[]): Array<{ value: string; count: number }> {
   const map = new Map<string, number>();
   for (const item of items) {
@@ -103,6 +137,69 @@ export function buildTaskFacets(tasks: TaskDto[]): TaskFacetBundle {
   };
 }
 
+// Plain tags that no open task carries any more, matching what the backend's
+// `tags_cleanup_unused` removes. `key:value` tags drive lanes, boards and
+// recurrence, so they are never offered for cleanup.
+export function unusedTagNames(usage: TagUsage[]): string[] {
+  return usage
+    .filter((entry) => entry.open === 0 && !entry.tag.includes(":"))
+    .map((entry) => entry.tag)
+    .sort((a, b) => a.localeCompare(b));
+}
+
+// Open tasks waiting on a contact, soonest due first.
+export function openTasksForContact(tasks: TaskDto[], contactId: string): TaskDto[] {
+  return tasks
+    .filter((task) => task.contact === contactId && (task.status === "Pending" || task.status === "Waiting"))
+    .sort((left, right) => {
+      if (left.due === right.due) {
+        return 0;
+      }
+      if (left.due === null || right.due === null) {
+        return left.due === null ? 1 : -1;
+      }
+      return left.due.localeCompare(right.due);
+    });
+}
+
 export function filterTasks(tasks: TaskDto[], filters: TaskFilters): TaskDto[] {
   return tasks.filter((task) => matchesFilters(task, filters));
 }
+
+// Turns a facet context action into per-task patches. "apply" targets the
+// selected tasks, while "remove" and "rename" touch every task in `tasks`
+// that currently carries the facet value.
+export function facetBulkPatches(tasks: TaskDto[], selectedIds: string[], action: FacetBulkAction): TaskUpdateArgs[] {
+  const value = action.value.trim();
+  const renameTo = action.renameTo?.trim() ?? "";
+  if (!value || (action.operation === "rename" && (!renameTo || renameTo === value))) {
+    return [];
+  }
+
+  const selected = new Set(selectedIds);
+  const patches: TaskUpdateArgs[] = [];
+  for (const task of tasks) {
+    if (action.facet === "tag") {
+      const hasTag = task.tags.includes(value);
+      if (action.operation === "apply" && selected.has(task.uuid) && !hasTag) {
+        patches.push({ uuid: task.uuid, patch: { tags: [...task.tags, value] } });
+      } else if (action.operation === "remove" && hasTag) {
+        patches.push({ uuid: task.uuid, patch: { tags: task.tags.filter((tag) => tag !== value) } });
+      } else if (action.operation === "rename" && hasTag) {
+        const tags = task.tags.filter((tag) => tag !== value && tag !== renameTo);
+        patches.push({ uuid: task.uuid, patch: { tags: [...tags, renameTo] } });
+      }
+      continue;
+    }
+
+    const hasProject = task.project === value;
+    if (action.operation === "apply" && selected.has(task.uuid) && !hasProject) {
+      patches.push({ uuid: task.uuid, patch: { project: value } });
+    } else if (action.operation === "remove" && hasProject) {
+      patches.push({ uuid: task.uuid, patch: { project: null } });
+    } else if (action.operation === "rename" && hasProject) {
+      patches.push({ uuid: task.uuid, patch: { project: renameTo } });
+    }
+  }
+  return patches;
+}
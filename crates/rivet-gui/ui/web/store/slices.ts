@@ -1,6 +1,33 @@
 import { useShallow } from "zustand/react/shallow";
 
 import { useAppStore } from "./useAppStore";
+import { quickAddVocabulary, type QuickAddVocabulary } from "../lib/capture";
+import { runningFocusTask } from "../lib/focus";
+import { isCommandAllowed } from "../lib/permissions";
+import { isDemoTask } from "../lib/tags";
+import type { CommandCategory } from "../types/core";
+
+// Whether `[permissions]` leaves `category` enabled; the backend rejects the
+// command either way, this only decides whether to offer it.
+export function useCommandAllowed(category: CommandCategory): boolean {
+  return useAppStore((state) => isCommandAllowed(state.disabledCommands, category));
+}
+
+// Projects and tags already in use, which quick-add typos are matched against.
+export function useQuickAddVocabulary(): QuickAddVocabulary {
+  const projects = useAppStore(useShallow((state) => quickAddVocabulary(state.tasks).projects));
+  const tags = useAppStore(useShallow((state) => quickAddVocabulary(state.tasks).tags));
+  return { projects, tags };
+}
+
+// The task whose clock is running, for the header focus timer.
+export function useFocusTimerSlice() {
+  return useAppStore(useShallow((state) => ({
+    runningTask: runningFocusTask(state.tasks),
+    runtimeConfig: state.runtimeConfig,
+    stopTaskClock: state.stopTaskClock
+  })));
+}
 
 export function useShellSlice() {
   return useAppStore(useShallow((state) => ({
@@ -17,12 +44,24 @@ export function useShellSlice() {
     openAddTaskDialog: state.openAddTaskDialog,
     closeAddTaskDialog: state.closeAddTaskDialog,
     createTask: state.createTask,
+    quickCaptureOpen: state.quickCaptureOpen,
+    openQuickCapture: state.openQuickCapture,
+    closeQuickCapture: state.closeQuickCapture,
+    captureTask: state.captureTask,
+    disabledCommands: state.disabledCommands,
     loading: state.loading,
     runtimeConfig: state.runtimeConfig,
     dictionaryLanguages: state.dictionaryLanguages,
     tagSchema: state.tagSchema,
     tagColorMap: state.tagColorMap,
-    kanbanBoards: state.kanbanBoards
+    kanbanBoards: state.kanbanBoards,
+    taskTemplates: state.taskTemplates,
+    deleteTaskTemplate: state.deleteTaskTemplate,
+    toast: state.toast,
+    dismissToast: state.dismissToast,
+    externalCalendars: state.externalCalendars,
+    scheduleExternalCalendarSync: state.scheduleExternalCalendarSync,
+    subscribeExternalCalendarSync: state.subscribeExternalCalendarSync
   })));
 }
 
@@ -33,13 +72,18 @@ export function useSettingsSlice() {
     closeSettings: state.closeSettings,
     dueConfig: state.dueNotificationConfig,
     duePermission: state.dueNotificationPermission,
+    configUpdateResults: state.configUpdateResults,
     themeFollowSystem: state.themeFollowSystem,
     setThemeFollowSystem: state.setThemeFollowSystem,
     setDueNotificationsEnabled: state.setDueNotificationsEnabled,
     setDuePreNotifyEnabled: state.setDuePreNotifyEnabled,
     setDuePreNotifyMinutes: state.setDuePreNotifyMinutes,
     requestDueNotificationPermission: state.requestDueNotificationPermission,
-    scanDueNotifications: state.scanDueNotifications
+    scanDueNotifications: state.scanDueNotifications,
+    demoTaskCount: state.tasks.filter((task) => isDemoTask(task.tags)).length,
+    clearDemoData: state.clearDemoData,
+    exportSettings: state.exportSettings,
+    importSettings: state.importSettings
   })));
 }
 
@@ -58,6 +102,26 @@ export function useTaskWorkspaceSlice() {
     tagSchema: state.tagSchema,
     tagColorMap: state.tagColorMap,
     kanbanBoards: state.kanbanBoards,
+    runtimeConfig: state.runtimeConfig,
+    groupByDue: state.taskListGroupByDue,
+    toggleGroupByDue: state.toggleTaskListGroupByDue,
+    taskTemplates: state.taskTemplates,
+    saveTaskAsTemplate: state.saveTaskAsTemplate,
+    staleTasks: state.staleTasks,
+    completedTasks: state.completedTasks,
+    completedRange: state.completedRange,
+    loadCompletedTasks: state.loadCompletedTasks,
+    setCompletedRange: state.setCompletedRange,
+    loadStaleTasks: state.loadStaleTasks,
+    duplicateGroups: state.duplicateGroups,
+    loadDuplicateGroups: state.loadDuplicateGroups,
+    resolveDuplicates: state.resolveDuplicates,
+    tagUsage: state.tagUsage,
+    loadTagUsage: state.loadTagUsage,
+    cleanupUnusedTags: state.cleanupUnusedTags,
+    taskHistory: state.taskHistory,
+    pendingTaskOps: state.pendingTaskOps,
+    loadTaskHistory: state.loadTaskHistory,
     filters: state.taskFilters,
     setSearchFilter: state.setTaskSearchFilter,
     setStatusFilter: state.setTaskStatusFilter,
@@ -65,15 +129,20 @@ export function useTaskWorkspaceSlice() {
     setTagFilter: state.setTaskTagFilter,
     setPriorityFilter: state.setTaskPriorityFilter,
     setDueFilter: state.setTaskDueFilter,
+    quickFilters: state.taskQuickFilters,
+    toggleQuickFilter: state.toggleTaskQuickFilter,
     clearFilters: state.clearTaskFilters,
     selectTask: state.selectTask,
     updateTask: state.updateTaskByUuid,
     markTaskDone: state.markTaskDone,
     markTaskUndone: state.markTaskUndone,
+    startTaskClock: state.startTaskClock,
+    stopTaskClock: state.stopTaskClock,
     removeTask: state.removeTask,
     markTasksDoneBulk: state.markTasksDoneBulk,
     markTasksUndoneBulk: state.markTasksUndoneBulk,
-    removeTasksBulk: state.removeTasksBulk
+    removeTasksBulk: state.removeTasksBulk,
+    updateTasksBulk: state.updateTasksBulk
   })));
 }
 
@@ -83,6 +152,7 @@ export function useKanbanWorkspaceSlice() {
     loading: state.loading,
     openAddTaskDialog: state.openAddTaskDialog,
     boards: state.kanbanBoards,
+    boardSort: state.kanbanBoardSort,
     activeBoardId: state.activeKanbanBoardId,
     compactCards: state.kanbanCompactCards,
     draggingTaskId: state.draggingKanbanTaskId,
@@ -92,14 +162,21 @@ export function useKanbanWorkspaceSlice() {
     createBoard: state.createKanbanBoard,
     renameBoard: state.renameActiveKanbanBoard,
     deleteBoard: state.deleteActiveKanbanBoard,
+    setBoardSort: state.setKanbanBoardSort,
+    moveBoard: state.moveKanbanBoard,
+    setBoardView: state.setActiveKanbanBoardView,
+    setCardOrder: state.setActiveKanbanCardOrder,
     toggleCompact: state.toggleKanbanCompactCards,
     setDragging: state.setDraggingKanbanTask,
     setDragOver: state.setDragOverKanbanLane,
     moveTask: state.moveKanbanTask,
     moveTaskToBoard: state.moveKanbanTaskToBoard,
+    dropOnProject: state.dropKanbanTaskOnProject,
+    dropOnTag: state.dropKanbanTaskOnTag,
     markTaskDone: state.markTaskDone,
     markTaskUndone: state.markTaskUndone,
     removeTask: state.removeTask,
+    remapTagValue: state.remapTagValue,
     setStatusFilter: state.setKanbanStatusFilter,
     setProjectFilter: state.setKanbanProjectFilter,
     setTagFilter: state.setKanbanTagFilter,
@@ -119,9 +196,12 @@ export function useCalendarWorkspaceSlice() {
     externalCalendars: state.externalCalendars,
     externalBusy: state.externalCalendarBusy,
     externalLastSync: state.externalCalendarLastSync,
+    externalSyncStatus: state.externalCalendarSyncStatus,
     error: state.error,
     setCalendarView: state.setCalendarView,
     shiftCalendarFocus: state.shiftCalendarFocus,
+    setCalendarFocusDateIso: state.setCalendarFocusDateIso,
+    jumpCalendarToInput: state.jumpCalendarToInput,
     setCalendarTaskFilter: state.setCalendarTaskFilter,
     setCalendarConfigToggle: state.setCalendarConfigToggle,
     navigateCalendar: state.navigateCalendar,
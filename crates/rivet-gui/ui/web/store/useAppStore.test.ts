@@ -15,10 +15,12 @@ const mocks = vi.hoisted(() => ({
   listTasksMock: vi.fn(),
   loadDictionaryEntryMock: vi.fn(),
   loadConfigSnapshotMock: vi.fn(),
+  loadPermissionsMock: vi.fn(),
   loadTagSchemaSnapshotMock: vi.fn(),
   searchDictionaryMock: vi.fn(),
   setCommandFailureSinkMock: vi.fn(),
   syncExternalCalendarMock: vi.fn(),
+  taskHistoryMock: vi.fn(),
   uncompleteTaskMock: vi.fn(),
   updateTaskMock: vi.fn()
 }));
@@ -36,10 +38,12 @@ vi.mock("../api/tauri", () => ({
   listTasks: mocks.listTasksMock,
   loadDictionaryEntry: mocks.loadDictionaryEntryMock,
   loadConfigSnapshot: mocks.loadConfigSnapshotMock,
+  loadPermissions: mocks.loadPermissionsMock,
   loadTagSchemaSnapshot: mocks.loadTagSchemaSnapshotMock,
   searchDictionary: mocks.searchDictionaryMock,
   setCommandFailureSink: mocks.setCommandFailureSinkMock,
   syncExternalCalendar: mocks.syncExternalCalendarMock,
+  taskHistory: mocks.taskHistoryMock,
   uncompleteTask: mocks.uncompleteTaskMock,
   updateTask: mocks.updateTaskMock
 }));
@@ -63,6 +67,12 @@ function sampleTask(title: string, overrides: Partial<TaskDto> = {}): TaskDto {
     scheduled: null,
     created: new Date().toISOString(),
     modified: new Date().toISOString(),
+    completed: null,
+    started: null,
+    focus_seconds: 0,
+    parent: null,
+    children: [],
+    contact: null,
     ...overrides
   };
 }
@@ -92,6 +102,7 @@ describe("useAppStore modal and save regressions", () => {
     mocks.listTasksMock.mockReset();
     mocks.loadDictionaryEntryMock.mockReset();
     mocks.loadConfigSnapshotMock.mockReset();
+    mocks.loadPermissionsMock.mockReset();
     mocks.loadTagSchemaSnapshotMock.mockReset();
     mocks.searchDictionaryMock.mockReset();
     mocks.syncExternalCalendarMock.mockReset();
@@ -190,6 +201,22 @@ describe("useAppStore modal and save regressions", () => {
     expect(current.tasks[0]?.tags).toEqual(["kanban:working", "board:main"]);
   });
 
+  it("completes open subtasks before their parent when asked", async () => {
+    const child = sampleTask("Child step", { parent: "uuid-parent-task" });
+    const parent = sampleTask("Parent task", { children: [child.uuid] });
+    const other = sampleTask("Other task");
+
+    useAppStore.setState({ tasks: [parent, child, other] });
+
+    mocks.doneTaskMock.mockResolvedValueOnce({ ...child, status: "Completed" });
+    mocks.doneTaskMock.mockResolvedValueOnce({ ...parent, status: "Completed" });
+    await useAppStore.getState().markTaskDone(parent.uuid, { completeSubtasks: true });
+
+    const current = useAppStore.getState();
+    expect(mocks.doneTaskMock.mock.calls.map((call) => call[0])).toEqual([child.uuid, parent.uuid]);
+    expect(current.tasks.map((task) => task.status)).toEqual(["Completed", "Completed", "Pending"]);
+  });
+
   it("supports bulk completion and bulk deletion", async () => {
     const one = sampleTask("Bulk one");
     const two = sampleTask("Bulk two");
@@ -218,7 +245,48 @@ describe("useAppStore modal and save regressions", () => {
     expect(current.loading).toBe(false);
     expect(current.tasks.some((task) => task.uuid === one.uuid)).toBe(false);
     expect(current.tasks.some((task) => task.uuid === three.uuid)).toBe(true);
-    expect(current.error).toContain("Failed to delete");
+    expect(current.toast?.message).toContain("Failed to delete");
+    expect(current.pendingTaskOps).toEqual({});
+  });
+
+  it("applies task actions optimistically and rolls back on failure", async () => {
+    const one = sampleTask("Optimistic one");
+    const two = sampleTask("Optimistic two");
+    useAppStore.setState({ tasks: [one, two], selectedTaskId: one.uuid });
+
+    let resolveDone: (task: TaskDto) => void = () => {};
+    mocks.doneTaskMock.mockReturnValueOnce(new Promise<TaskDto>((resolve) => {
+      resolveDone = resolve;
+    }));
+    mocks.listTasksMock.mockResolvedValue([
+      { ...one, status: "Completed" },
+      two,
+      sampleTask("Spawned next")
+    ]);
+    const pendingDone = useAppStore.getState().markTaskDone(one.uuid);
+
+    let current = useAppStore.getState();
+    expect(current.loading).toBe(false);
+    expect(current.tasks[0].status).toBe("Completed");
+    expect(current.pendingTaskOps).toEqual({ [one.uuid]: "done" });
+
+    resolveDone({ ...one, status: "Completed" });
+    await pendingDone;
+    current = useAppStore.getState();
+    expect(current.pendingTaskOps).toEqual({});
+    expect(current.tasks.map((task) => task.title)).toEqual([
+      "Optimistic one",
+      "Optimistic two",
+      "Spawned next"
+    ]);
+
+    mocks.listTasksMock.mockResolvedValue([{ ...one, status: "Completed" }, two]);
+    mocks.deleteTaskMock.mockRejectedValueOnce(new Error("disk full"));
+    await useAppStore.getState().removeTask(two.uuid);
+    current = useAppStore.getState();
+    expect(current.tasks.map((task) => task.uuid)).toEqual([one.uuid, two.uuid]);
+    expect(current.toast?.message).toContain("disk full");
+    expect(current.pendingTaskOps).toEqual({});
   });
 
   it("moves a task between kanban boards and lanes with updated tags", async () => {
@@ -268,6 +336,7 @@ describe("useAppStore modal and save regressions", () => {
       keys: [{ id: "kanban", values: ["todo", "working", "finished"] }]
     });
     mocks.listDictionaryLanguagesMock.mockResolvedValueOnce(["en", "es"]);
+    mocks.loadPermissionsMock.mockResolvedValueOnce({ disabled: ["tasks_delete"] });
 
     await useAppStore.getState().bootstrap();
 
@@ -276,6 +345,7 @@ describe("useAppStore modal and save regressions", () => {
     expect(current.loading).toBe(false);
     expect(current.tasks[0]?.uuid).toBe(startupTask.uuid);
     expect(current.runtimeConfig?.app?.mode).toBe("dev");
+    expect(current.disabledCommands).toEqual(["tasks_delete"]);
 
     current.setActiveTab("tasks");
     expect(useAppStore.getState().activeTab).toBe("tasks");
@@ -317,6 +387,33 @@ describe("useAppStore modal and save regressions", () => {
     expect(useAppStore.getState().commandFailures).toHaveLength(0);
   });
 
+  it("loads completed tasks for the chosen range, newest first", async () => {
+    const older = sampleTask("Older win", { status: "Completed", completed: "2026-03-02T09:00:00Z" });
+    const newer = sampleTask("Newer win", { status: "Completed", completed: "2026-03-04T18:30:00Z" });
+    mocks.listTasksMock.mockResolvedValue([older, newer]);
+
+    useAppStore.getState().setCompletedRange({ from: "2026-03-01", to: "2026-03-07" });
+    await vi.waitFor(() => expect(useAppStore.getState().completedTasks).toHaveLength(2));
+
+    expect(mocks.listTasksMock).toHaveBeenCalledWith(expect.objectContaining({
+      status: "Completed",
+      completed_from: "2026-03-01",
+      completed_to: "2026-03-07"
+    }));
+    expect(useAppStore.getState().completedTasks.map((task) => task.title)).toEqual(["Newer win", "Older win"]);
+  });
+
+  it("loads archived tasks when the completion filter asks for them", async () => {
+    const shelved = sampleTask("Shelved idea", { status: "Archived" });
+    mocks.listTasksMock.mockResolvedValue([shelved]);
+
+    useAppStore.getState().setTaskStatusFilter("Archived");
+    await vi.waitFor(() => expect(useAppStore.getState().archivedTasks).toHaveLength(1));
+
+    expect(mocks.listTasksMock).toHaveBeenCalledWith(expect.objectContaining({ status: "Archived" }));
+    expect(useAppStore.getState().archivedTasks[0]?.title).toBe("Shelved idea");
+  });
+
   it("persists map viewport and map error state across tab switches", () => {
     const current = useAppStore.getState();
     current.setMapViewport([-99.1332, 19.4326], 6.25);
@@ -4,22 +4,42 @@ import { create } from "zustand";
 import {
   applyConfigUpdates,
   addTask,
+  cleanupUnusedTags,
+  clearDemoData,
+  type ConfigBundleConflictPolicy,
   type ConfigEntryUpdate,
+  type ConfigUpdateResult,
   type CommandFailureRecord,
   deleteTask,
   doneTask,
+  exportConfigBundle,
   healthCheck,
+  importConfigBundle,
   importExternalCalendarCached,
   importExternalCalendarIcs,
   listDictionaryLanguages,
   listExternalCalendarCache,
+  listDuplicateTasks,
+  listStaleTasks,
   listTasks,
   loadDictionaryEntry,
+  loadTagStats,
   loadConfigSnapshot,
+  loadPermissions,
   loadTagSchemaSnapshot,
+  parseCalendarDate,
+  onExternalCalendarSyncActivity,
+  onExternalCalendarSynced,
+  remapTags,
+  scheduleExternalCalendars,
+  resolveDuplicateTasks,
   setCommandFailureSink,
   searchDictionary,
+  seedDemoData,
+  startTask,
+  stopTask,
   syncExternalCalendar,
+  taskHistory,
   uncompleteTask,
   updateTask
 } from "../api/tauri";
@@ -27,12 +47,20 @@ import {
   canManuallyCompleteTask,
   calendarDateFromIso,
   calendarDateToIso,
+  clampCalendarJump,
+  buildExternalCalendarColorMap,
   collectCalendarDueTasks,
   isCalendarEventTask,
   resolveCalendarConfig,
   shiftCalendarFocus as shiftFocusDate,
   todayInTimezone
 } from "../lib/calendar";
+import { agendaCounts, agendaMessage, inQuietHours, resolveAgendaOnLaunch, resolveQuietHours, type QuietHours } from "../lib/agenda";
+import { buildCaptureTask } from "../lib/capture";
+import { buildGuiSettingsBundle, guiSettingsConflicts, mergeGuiSettings, parseGuiSettingsBundle } from "../lib/configBundle";
+import { describeConfigUpdate, rejectedConfigUpdates } from "../lib/configUpdates";
+import { defaultCompletedRange, sortByCompletion, type CompletedRange } from "../lib/completed";
+import { collectFocusNotificationEvents, resolveFocusConfig } from "../lib/focus";
 import { logger } from "../lib/logger";
 import {
   browserDueNotificationPermission,
@@ -56,35 +84,55 @@ import {
   loadNotificationSettings,
   loadActiveKanbanBoardId,
   loadExternalCalendars,
+  loadKanbanBoardSort,
   loadKanbanBoards,
   loadKanbanCompactCards,
+  loadTaskListGroupByDue,
+  loadTaskTemplates,
   makeUniqueBoardName,
   newExternalCalendarSource,
   nextBoardColor,
   saveActiveKanbanBoardId,
   saveExternalCalendars,
+  saveKanbanBoardSort,
   saveKanbanBoards,
   saveKanbanCompactCards,
   saveNotificationSentRegistry,
-  saveNotificationSettings
+  saveNotificationSettings,
+  saveTaskListGroupByDue,
+  saveTaskTemplates
 } from "../lib/storage";
+import { indexTasksById, openSubtaskIds } from "../lib/subtasks";
 import {
   BOARD_TAG_KEY,
   boardIdFromTaskTags,
   buildTagColorMap,
   collectTagsForSubmit,
   defaultKanbanLane,
+  isDemoTask,
   kanbanColumnsFromSchema,
   pushTagUnique,
   removeTagsForKey,
   splitTags,
   tagsForKanbanMove,
+  tagsForTagDrop,
   taskHasTagValue
 } from "../lib/tags";
+import {
+  clearPending,
+  markPending,
+  optimisticDone,
+  optimisticUncomplete,
+  reconcileTasks,
+  rollbackTasks
+} from "../lib/optimistic";
+import { moveBoardBefore } from "../lib/kanbanSort";
+import { templateFromTask, upsertTemplate } from "../lib/templates";
+import { filterByQuickFilters, quickFilterCounts, toggleQuickFilter, type QuickFilter } from "../lib/quickFilters";
 import { buildTaskFacets, filterTasks } from "./selectors";
 import type { RivetRuntimeConfig, TagSchema } from "../types/config";
-import type { DictionaryEntry, DictionarySearchHit, ExternalCalendarCacheEntry, ExternalCalendarSource, TaskCreate, TaskDto, TaskPatch } from "../types/core";
-import type { AddTaskDialogContext, DueFilter, DueNotificationConfig, PriorityFilter, RecurrenceDraft, StatusFilter, TaskFilters, ThemeMode, WorkspaceTab } from "../types/ui";
+import type { CommandCategory, DictionaryEntry, DictionarySearchHit, ExternalCalendarCacheEntry, ExternalCalendarSource, ExternalCalendarSyncActivity, ExternalCalendarSyncEvent, ExternalCalendarSyncStatus, TagUsage, TaskCreate, TaskDedupeGroup, TaskDto, TaskHistoryEntry, TaskPatch, TaskUpdateArgs } from "../types/core";
+import type { AddTaskDialogContext, CalendarToggles, DueFilter, DueNotificationConfig, KanbanBoardDef, KanbanBoardListSort, PendingTaskOp, PriorityFilter, RecurrenceDraft, StatusFilter, TaskFilters, TaskTemplate, ThemeMode, ToastMessage, WorkspaceTab } from "../types/ui";
 
 function readStorageString(key: string): string | null {
   if (typeof window === "undefined") {
@@ -267,17 +315,35 @@ interface AppState {
   loading: boolean;
   error: string | null;
   tasks: TaskDto[];
+  staleTasks: TaskDto[];
+  duplicateGroups: TaskDedupeGroup[];
+  tagUsage: TagUsage[];
+  disabledCommands: CommandCategory[];
+  completedTasks: TaskDto[];
+  completedRange: CompletedRange;
+  // Only fetched while a completion filter is set to Archived; `tasks`
+  // never includes them.
+  archivedTasks: TaskDto[];
+  taskHistory: TaskHistoryEntry[];
+  pendingTaskOps: Record<string, PendingTaskOp>;
+  toast: ToastMessage | null;
   selectedTaskId: string | null;
   addTaskDialogOpen: boolean;
   addTaskDialogContext: AddTaskDialogContext;
+  quickCaptureOpen: boolean;
   taskFilters: TaskFilters;
+  // Chips above the task list; AND-combined with `taskFilters`.
+  taskQuickFilters: QuickFilter[];
   kanbanFilters: TaskFilters;
   runtimeConfig: RivetRuntimeConfig | null;
   tagSchema: TagSchema | null;
   tagColorMap: Record<string, string>;
-  kanbanBoards: Array<{ id: string; name: string; color: string }>;
+  kanbanBoards: KanbanBoardDef[];
+  kanbanBoardSort: KanbanBoardListSort;
   activeKanbanBoardId: string | null;
   kanbanCompactCards: boolean;
+  taskListGroupByDue: boolean;
+  taskTemplates: TaskTemplate[];
   draggingKanbanTaskId: string | null;
   dragOverKanbanLane: string | null;
   calendarView: "year" | "quarter" | "month" | "week" | "day";
@@ -286,6 +352,9 @@ interface AppState {
   externalCalendars: ExternalCalendarSource[];
   externalCalendarBusy: boolean;
   externalCalendarLastSync: string | null;
+  // Backend auto-sync state by source id, from `external_calendar_schedule`
+  // and the sync and activity events that follow.
+  externalCalendarSyncStatus: Record<string, ExternalCalendarSyncStatus>;
   dictionaryLanguages: string[];
   dictionaryLanguage: string | null;
   dictionaryQuery: string;
@@ -302,12 +371,30 @@ interface AppState {
   mapLastError: string | null;
   settingsOpen: boolean;
   dueNotificationConfig: DueNotificationConfig;
+  // `[notifications.due] quiet_hours`, resolved once at startup.
+  quietHours: QuietHours | null;
+  // Per-key outcome of the last settings write.
+  configUpdateResults: ConfigUpdateResult[];
   dueNotificationPermission: DueNotificationPermission;
   dueNotificationSent: string[];
   commandFailures: CommandFailureRecord[];
 
   bootstrap: () => Promise<void>;
   loadTasks: () => Promise<void>;
+  loadStaleTasks: () => Promise<void>;
+  loadDuplicateGroups: () => Promise<void>;
+  // Each group lists the kept task first; the rest are merged (or just
+  // deleted) as one write.
+  resolveDuplicates: (groups: string[][], merge: boolean) => Promise<void>;
+  loadTagUsage: () => Promise<void>;
+  cleanupUnusedTags: () => Promise<void>;
+  loadCompletedTasks: () => Promise<void>;
+  loadArchivedTasks: () => Promise<void>;
+  setCompletedRange: (range: CompletedRange) => void;
+  loadTaskHistory: (uuid: string | null) => Promise<void>;
+  resyncTasks: () => Promise<void>;
+  showToast: (message: string, severity?: ToastMessage["severity"]) => void;
+  dismissToast: () => void;
 
   setActiveTab: (tab: WorkspaceTab) => void;
   toggleTheme: () => void;
@@ -321,6 +408,7 @@ interface AppState {
   setTaskTagFilter: (value: string) => void;
   setTaskPriorityFilter: (value: PriorityFilter) => void;
   setTaskDueFilter: (value: DueFilter) => void;
+  toggleTaskQuickFilter: (filter: QuickFilter) => void;
   clearTaskFilters: () => void;
 
   setKanbanStatusFilter: (value: StatusFilter) => void;
@@ -332,37 +420,66 @@ interface AppState {
 
   openAddTaskDialog: (context?: Partial<AddTaskDialogContext>) => void;
   closeAddTaskDialog: () => void;
-  createTask: (input: TaskCreate) => Promise<void>;
+  createTask: (input: TaskCreate) => Promise<boolean>;
+  openQuickCapture: () => void;
+  closeQuickCapture: () => void;
+  captureTask: (text: string) => Promise<void>;
   updateTaskByUuid: (uuid: string, patch: TaskPatch) => Promise<TaskDto | null>;
-  markTaskDone: (uuid: string) => Promise<void>;
+  markTaskDone: (uuid: string, options?: { completeSubtasks?: boolean }) => Promise<void>;
   markTaskUndone: (uuid: string) => Promise<void>;
+  startTaskClock: (uuid: string) => Promise<void>;
+  stopTaskClock: (uuid: string) => Promise<void>;
   removeTask: (uuid: string) => Promise<void>;
   markTasksDoneBulk: (uuids: string[]) => Promise<void>;
   markTasksUndoneBulk: (uuids: string[]) => Promise<void>;
   removeTasksBulk: (uuids: string[]) => Promise<void>;
+  updateTasksBulk: (updates: TaskUpdateArgs[]) => Promise<void>;
 
   setActiveKanbanBoard: (boardId: string | null) => void;
   createKanbanBoard: (requestedName: string) => void;
   renameActiveKanbanBoard: (requestedName: string) => void;
   deleteActiveKanbanBoard: () => Promise<void>;
+  clearDemoData: () => Promise<void>;
+  exportSettings: () => Promise<void>;
+  // Returns what differs from the bundle when `onConflict` is "ask" so the
+  // caller can settle it with "overwrite" or "keep"; empty once applied.
+  importSettings: (bundle: string, onConflict: ConfigBundleConflictPolicy) => Promise<string[]>;
+  remapTagValue: (key: string, from: string, to: string) => Promise<void>;
+  setKanbanBoardSort: (mode: KanbanBoardListSort) => void;
+  moveKanbanBoard: (boardId: string, beforeBoardId: string) => void;
+  setActiveKanbanBoardView: (view: Pick<KanbanBoardDef, "lane_sort" | "card_sort">) => void;
+  setActiveKanbanCardOrder: (order: string[]) => void;
   toggleKanbanCompactCards: () => void;
+  toggleTaskListGroupByDue: () => void;
+  saveTaskAsTemplate: (taskId: string, name: string, includeDue: boolean) => void;
+  deleteTaskTemplate: (templateId: string) => void;
   setDraggingKanbanTask: (taskId: string | null) => void;
   setDragOverKanbanLane: (lane: string | null) => void;
   moveKanbanTask: (taskId: string, lane: string) => Promise<void>;
   moveKanbanTaskToBoard: (taskId: string, boardId: string | null, lane?: string) => Promise<void>;
+  dropKanbanTaskOnProject: (taskId: string, project: string) => Promise<void>;
+  dropKanbanTaskOnTag: (taskId: string, tag: string) => Promise<void>;
 
   setCalendarView: (view: "year" | "quarter" | "month" | "week" | "day") => void;
   setCalendarFocusDateIso: (iso: string) => void;
   shiftCalendarFocus: (step: number) => void;
+  // Resolves typed input through the backend date parser; returns an error
+  // message for invalid input, null once the focus date moved.
+  jumpCalendarToInput: (input: string) => Promise<string | null>;
   navigateCalendar: (iso: string, view?: "year" | "quarter" | "month" | "week" | "day") => void;
   setCalendarTaskFilter: (value: string) => void;
-  setCalendarConfigToggle: (key: "de_emphasize_past_periods" | "filter_tasks_before_now" | "hide_past_markers", enabled: boolean) => void;
+  setCalendarConfigToggle: (key: keyof CalendarToggles, enabled: boolean) => void;
 
   openNewExternalCalendar: () => ExternalCalendarSource;
   saveExternalCalendarSource: (source: ExternalCalendarSource) => void;
   deleteExternalCalendarSource: (calendarId: string) => void;
   syncExternalCalendarSource: (calendarId: string) => Promise<void>;
   syncAllExternalCalendars: () => Promise<void>;
+  scheduleExternalCalendarSync: () => Promise<void>;
+  handleExternalCalendarSynced: (event: ExternalCalendarSyncEvent) => Promise<void>;
+  handleExternalCalendarSyncActivity: (activity: ExternalCalendarSyncActivity) => void;
+  // Resolves to the unsubscribe function.
+  subscribeExternalCalendarSync: () => Promise<() => void>;
   importExternalCalendarFile: (file: File) => Promise<void>;
   listExternalCalendarCachedEntries: () => Promise<ExternalCalendarCacheEntry[]>;
   importExternalCalendarFromCache: (cacheEntry: ExternalCalendarCacheEntry) => Promise<void>;
@@ -408,8 +525,14 @@ export const useAppStore = create<AppState>((set, get) => {
     }
     void (async () => {
       try {
-        const runtimeConfig = await applyConfigUpdates(updates);
-        set({ runtimeConfig });
+        const result = await applyConfigUpdates(updates);
+        set({ runtimeConfig: result.config, configUpdateResults: result.results });
+        const rejected = rejectedConfigUpdates(result.results);
+        if (rejected.length > 0) {
+          const summary = rejected.map(describeConfigUpdate).join("; ");
+          logger.warn("config.persist.rejected", `${context}: ${summary}`);
+          get().showToast(`Settings not saved: ${summary}`, "warning");
+        }
       } catch (error) {
         const message = error instanceof Error ? error.message : String(error);
         logger.error("config.persist.error", `${context}: ${message}`);
@@ -418,6 +541,34 @@ export const useAppStore = create<AppState>((set, get) => {
     })();
   };
 
+  const runTaskClockOp = async (uuid: string, op: "start" | "stop") => {
+    const task = get().tasks.find((entry) => entry.uuid === uuid);
+    if (!task || get().pendingTaskOps[uuid]) {
+      return;
+    }
+
+    set((state) => ({
+      error: null,
+      pendingTaskOps: markPending(state.pendingTaskOps, [uuid], op)
+    }));
+    logger.info(`task.${op}.start`, uuid);
+    try {
+      const updated = await (op === "start" ? startTask(uuid) : stopTask(uuid));
+      set((state) => ({
+        tasks: state.tasks.map((entry) => (entry.uuid === uuid ? updated : entry)),
+        pendingTaskOps: clearPending(state.pendingTaskOps, [uuid])
+      }));
+      logger.info(`task.${op}.done`, uuid);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      set((state) => ({
+        pendingTaskOps: clearPending(state.pendingTaskOps, [uuid])
+      }));
+      get().showToast(`Could not ${op} "${task.title}": ${message}`);
+      logger.error(`task.${op}.error`, `${uuid}: ${message}`);
+    }
+  };
+
   return {
   bootstrapped: false,
   activeTab: loadWorkspaceTab(),
@@ -427,6 +578,16 @@ export const useAppStore = create<AppState>((set, get) => {
   loading: false,
   error: null,
   tasks: [],
+  staleTasks: [],
+  duplicateGroups: [],
+  tagUsage: [],
+  disabledCommands: [],
+  completedTasks: [],
+  archivedTasks: [],
+  completedRange: defaultCompletedRange(todayInTimezone(Intl.DateTimeFormat().resolvedOptions().timeZone)),
+  taskHistory: [],
+  pendingTaskOps: {},
+  toast: null,
   selectedTaskId: null,
   addTaskDialogOpen: false,
   addTaskDialogContext: {
@@ -434,7 +595,9 @@ export const useAppStore = create<AppState>((set, get) => {
     lockBoardSelection: false,
     allowRecurrence: true
   },
+  quickCaptureOpen: false,
   taskFilters: emptyTaskFilters(),
+  taskQuickFilters: [],
   kanbanFilters: {
     ...emptyTaskFilters(),
     status: "Pending"
@@ -443,8 +606,11 @@ export const useAppStore = create<AppState>((set, get) => {
   tagSchema: null,
   tagColorMap: {},
   kanbanBoards: initialBoards,
+  kanbanBoardSort: loadKanbanBoardSort(),
   activeKanbanBoardId: initialActiveBoardId,
   kanbanCompactCards: loadKanbanCompactCards(),
+  taskListGroupByDue: loadTaskListGroupByDue(),
+  taskTemplates: loadTaskTemplates(),
   draggingKanbanTaskId: null,
   dragOverKanbanLane: null,
   calendarView: loadCalendarViewMode(),
@@ -453,6 +619,7 @@ export const useAppStore = create<AppState>((set, get) => {
   externalCalendars: initialExternalCalendars,
   externalCalendarBusy: false,
   externalCalendarLastSync: null,
+  externalCalendarSyncStatus: {},
   dictionaryLanguages: [],
   dictionaryLanguage: null,
   dictionaryQuery: "",
@@ -469,6 +636,8 @@ export const useAppStore = create<AppState>((set, get) => {
   mapLastError: null,
   settingsOpen: false,
   dueNotificationConfig: initialDueNotificationConfig,
+  quietHours: null,
+  configUpdateResults: [],
   dueNotificationPermission: initialDueNotificationPermission,
   dueNotificationSent: initialDueNotificationSent,
   commandFailures: [],
@@ -482,19 +651,37 @@ export const useAppStore = create<AppState>((set, get) => {
 
     try {
       await healthCheck();
-      const [tasks, runtimeConfig, tagSchema, dictionaryLanguages] = await Promise.all([
+      // Must run before listTasks so a fresh store comes back already seeded.
+      const demo = await seedDemoData().catch((error) => {
+        logger.warn("demo.seed.bootstrap", String(error));
+        return null;
+      });
+      if (demo && demo.tasks > 0 && !get().kanbanBoards.some((board) => board.id === demo.board_id)) {
+        const boards = get().kanbanBoards;
+        set({
+          kanbanBoards: [
+            ...boards,
+            { id: demo.board_id, name: makeUniqueBoardName(boards, "Demo"), color: nextBoardColor(boards) }
+          ],
+          activeKanbanBoardId: demo.board_id
+        });
+        logger.info("demo.seed", `tasks=${demo.tasks} board=${demo.board_id}`);
+      }
+      const [tasks, runtimeConfig, tagSchema, dictionaryLanguages, permissions] = await Promise.all([
         listTasks(),
         loadConfigSnapshot(),
         loadTagSchemaSnapshot(),
         listDictionaryLanguages().catch((error) => {
           logger.warn("dictionary.languages.bootstrap", String(error));
           return [] as string[];
-        })
+        }),
+        loadPermissions()
       ]);
       const effective = resolveCalendarConfig(runtimeConfig);
       const today = todayInTimezone(effective.timezone);
       const tagColorMap = buildTagColorMap(tagSchema);
       const resolvedDueConfig = runtimeDueConfig(runtimeConfig, get().dueNotificationConfig);
+      const quietHours = resolveQuietHours(runtimeConfig);
       const resolvedThemeMode = runtimeThemeMode(runtimeConfig) ?? get().themeMode;
       const resolvedFollowSystem = runtimeThemeFollowSystem(runtimeConfig);
       const configuredDictionaryLanguage = normalizedLanguage(runtimeConfig?.dictionary?.default_language ?? null);
@@ -519,12 +706,28 @@ export const useAppStore = create<AppState>((set, get) => {
         themeMode: resolvedThemeMode,
         themeFollowSystem: resolvedFollowSystem,
         dueNotificationConfig: resolvedDueConfig,
+        quietHours,
         calendarFocusDateIso: calendarDateToIso(today),
         dueNotificationPermission: browserDueNotificationPermission(),
         dictionaryLanguages,
-        dictionaryLanguage: resolvedDictionaryLanguage
+        dictionaryLanguage: resolvedDictionaryLanguage,
+        disabledCommands: permissions.disabled
       });
-      logger.info("app.bootstrap.done", `tasks=${tasks.length} timezone=${effective.timezone}`);
+      logger.info(
+        "app.bootstrap.done",
+        `tasks=${tasks.length} timezone=${effective.timezone} disabled=${permissions.disabled.join(",") || "none"}`
+      );
+
+      if (resolveAgendaOnLaunch(runtimeConfig)) {
+        const nowMs = Date.now();
+        const message = agendaMessage(agendaCounts(tasks, nowMs, effective.timezone, effective.policies.week_start));
+        get().showToast(`Today's agenda: ${message}`, "info");
+        // The toast always shows; the desktop notification follows the due
+        // notification switch and stays quiet during quiet hours.
+        const quiet = inQuietHours(quietHours, nowMs, effective.timezone);
+        const notified = resolvedDueConfig.enabled && !quiet && emitDueNotification("Today's agenda", message);
+        logger.info("agenda.launch", `${message} notified=${notified} quiet=${quiet}`);
+      }
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
       logger.error("app.bootstrap.error", message);
@@ -555,6 +758,174 @@ export const useAppStore = create<AppState>((set, get) => {
     }
   },
 
+  async loadStaleTasks() {
+    const stale = get().runtimeConfig?.stale;
+    try {
+      const staleTasks = await listStaleTasks({
+        days: stale?.days ?? null,
+        include_waiting: stale?.include_waiting ?? null,
+        include_blocked: stale?.include_blocked ?? null
+      });
+      set({ staleTasks });
+      logger.debug("tasks.stale.done", `tasks=${staleTasks.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.error("tasks.stale.error", message);
+    }
+  },
+
+  async loadDuplicateGroups() {
+    try {
+      const duplicateGroups = await listDuplicateTasks({
+        threshold: get().runtimeConfig?.dedupe?.threshold ?? null
+      });
+      set({ duplicateGroups });
+      logger.debug("tasks.dedupe.done", `groups=${duplicateGroups.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.error("tasks.dedupe.error", message);
+    }
+  },
+
+  async resolveDuplicates(groups, merge) {
+    if (groups.length === 0) {
+      return;
+    }
+    try {
+      const result = await resolveDuplicateTasks({ groups, merge });
+      get().showToast(
+        `${merge ? "Merged" : "Deleted"} ${result.removed} duplicate task(s) in ${groups.length} group(s).`,
+        "success"
+      );
+      logger.info("tasks.dedupe.apply.done", `groups=${groups.length} removed=${result.removed} merge=${merge}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      get().showToast(`Could not resolve duplicates: ${message}`);
+      logger.error("tasks.dedupe.apply.error", message);
+    }
+    await get().resyncTasks();
+    await get().loadDuplicateGroups();
+  },
+
+  async loadTagUsage() {
+    try {
+      const tagUsage = await loadTagStats();
+      set({ tagUsage });
+      logger.debug("tags.stats.done", `tags=${tagUsage.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.error("tags.stats.error", message);
+    }
+  },
+
+  async cleanupUnusedTags() {
+    try {
+      const result = await cleanupUnusedTags();
+      get().showToast(
+        result.changed > 0
+          ? `Removed ${result.removed.length} unused tag(s) from ${result.changed} task(s).`
+          : "No unused tags to clean up.",
+        "success"
+      );
+      logger.info("tags.cleanup.done", `removed=${result.removed.join(",")} changed=${result.changed}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      get().showToast(`Could not clean up unused tags: ${message}`);
+      logger.error("tags.cleanup.error", message);
+    }
+    await get().resyncTasks();
+    await get().loadTagUsage();
+  },
+
+  async loadCompletedTasks() {
+    const range = get().completedRange;
+    try {
+      const completedTasks = await listTasks({
+        query: null,
+        status: "Completed",
+        project: null,
+        tag: null,
+        completed_from: range.from || null,
+        completed_to: range.to || null
+      });
+      if (get().completedRange !== range) {
+        return;
+      }
+      set({ completedTasks: sortByCompletion(completedTasks) });
+      logger.debug("tasks.completed.done", `from=${range.from} to=${range.to} tasks=${completedTasks.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.error("tasks.completed.error", message);
+    }
+  },
+
+  async loadArchivedTasks() {
+    try {
+      const archivedTasks = await listTasks({
+        query: null,
+        status: "Archived",
+        project: null,
+        tag: null
+      });
+      set({ archivedTasks });
+      logger.debug("tasks.archived.done", `tasks=${archivedTasks.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.error("tasks.archived.error", message);
+    }
+  },
+
+  setCompletedRange(range) {
+    set({ completedRange: range });
+    void get().loadCompletedTasks();
+  },
+
+  async loadTaskHistory(uuid) {
+    if (!uuid) {
+      set({ taskHistory: [] });
+      return;
+    }
+    try {
+      const taskHistoryEntries = await taskHistory(uuid);
+      if (get().selectedTaskId !== uuid) {
+        return;
+      }
+      set({ taskHistory: taskHistoryEntries });
+      logger.debug("tasks.history.done", `uuid=${uuid} entries=${taskHistoryEntries.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      set({ taskHistory: [] });
+      logger.error("tasks.history.error", message);
+    }
+  },
+
+  async resyncTasks() {
+    if (Object.keys(get().pendingTaskOps).length > 0) {
+      return;
+    }
+    try {
+      const serverTasks = await listTasks();
+      set((state) => {
+        const tasks = reconcileTasks(serverTasks, state.tasks, state.pendingTaskOps);
+        const selectedTaskId = state.selectedTaskId && tasks.some((task) => task.uuid === state.selectedTaskId)
+          ? state.selectedTaskId
+          : tasks[0]?.uuid ?? null;
+        return { tasks, selectedTaskId };
+      });
+      logger.debug("tasks.resync.done", `tasks=${serverTasks.length}`);
+    } catch (error) {
+      logger.warn("tasks.resync.error", String(error));
+    }
+  },
+
+  showToast(message, severity = "error") {
+    set({ toast: { id: Date.now(), message, severity } });
+  },
+
+  dismissToast() {
+    set({ toast: null });
+  },
+
   setActiveTab(tab) {
     saveWorkspaceTab(tab);
     set({ activeTab: tab });
@@ -617,6 +988,9 @@ export const useAppStore = create<AppState>((set, get) => {
   },
   setTaskStatusFilter(value) {
     set((state) => ({ taskFilters: { ...state.taskFilters, status: value } }));
+    if (value === "Archived") {
+      void get().loadArchivedTasks();
+    }
   },
   setTaskProjectFilter(value) {
     set((state) => ({ taskFilters: { ...state.taskFilters, project: value } }));
@@ -630,12 +1004,18 @@ export const useAppStore = create<AppState>((set, get) => {
   setTaskDueFilter(value) {
     set((state) => ({ taskFilters: { ...state.taskFilters, due: value } }));
   },
+  toggleTaskQuickFilter(filter) {
+    set((state) => ({ taskQuickFilters: toggleQuickFilter(state.taskQuickFilters, filter) }));
+  },
   clearTaskFilters() {
-    set({ taskFilters: emptyTaskFilters() });
+    set({ taskFilters: emptyTaskFilters(), taskQuickFilters: [] });
   },
 
   setKanbanStatusFilter(value) {
     set((state) => ({ kanbanFilters: { ...state.kanbanFilters, status: value } }));
+    if (value === "Archived") {
+      void get().loadArchivedTasks();
+    }
   },
   setKanbanProjectFilter(value) {
     set((state) => ({ kanbanFilters: { ...state.kanbanFilters, project: value } }));
@@ -712,13 +1092,39 @@ export const useAppStore = create<AppState>((set, get) => {
         }
       }));
       logger.info("task.create.done", created.uuid);
+      return true;
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
       set({ loading: false, error: message });
       logger.error("task.create.error", message);
+      return false;
     }
   },
 
+  openQuickCapture() {
+    set({ quickCaptureOpen: true });
+    logger.info("modal.quick_capture.open", "");
+  },
+
+  closeQuickCapture() {
+    set({ quickCaptureOpen: false });
+  },
+
+  async captureTask(text) {
+    const input = buildCaptureTask(text, get().runtimeConfig);
+    if (!input) {
+      return;
+    }
+    await get().createTask(input);
+    const error = get().error;
+    if (error) {
+      get().showToast(`Could not capture "${input.title}": ${error}`);
+      return;
+    }
+    set({ quickCaptureOpen: false });
+    get().showToast(`Captured "${input.title}" to +${input.tags[0]}`, "success");
+  },
+
   async updateTaskByUuid(uuid, patch) {
     set({ loading: true, error: null });
     logger.debug("task.update.start", uuid);
@@ -738,9 +1144,9 @@ export const useAppStore = create<AppState>((set, get) => {
     }
   },
 
-  async markTaskDone(uuid) {
+  async markTaskDone(uuid, options) {
     const task = get().tasks.find((entry) => entry.uuid === uuid);
-    if (!task) {
+    if (!task || get().pendingTaskOps[uuid]) {
       return;
     }
     if (!(task.status === "Pending" || task.status === "Waiting")) {
@@ -753,63 +1159,116 @@ export const useAppStore = create<AppState>((set, get) => {
       return;
     }
 
-    set({ loading: true, error: null });
-    logger.info("task.done.start", uuid);
+    const subtaskIds = options?.completeSubtasks ? openSubtaskIds(task, indexTasksById(get().tasks)) : [];
+    const targets = [...subtaskIds, uuid];
+    const targetSet = new Set(targets);
+    const previous = get().tasks;
+    const nowIso = new Date().toISOString();
+
+    set((state) => ({
+      error: null,
+      tasks: state.tasks.map((entry) => (targetSet.has(entry.uuid) ? optimisticDone(entry, nowIso) : entry)),
+      pendingTaskOps: markPending(state.pendingTaskOps, targets, "done")
+    }));
+    logger.info("task.done.start", `${uuid} subtasks=${subtaskIds.length}`);
+    const confirmed = new Set<string>();
     try {
-      const updated = await doneTask(uuid);
-      set((state) => ({
-        loading: false,
-        tasks: state.tasks.map((task) => (task.uuid === uuid ? updated : task))
-      }));
+      for (const targetId of targets) {
+        const updated = await doneTask(targetId);
+        confirmed.add(targetId);
+        set((state) => ({
+          tasks: state.tasks.map((entry) => (entry.uuid === targetId ? updated : entry)),
+          pendingTaskOps: clearPending(state.pendingTaskOps, [targetId])
+        }));
+      }
       logger.info("task.done.done", uuid);
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
-      set({ loading: false, error: message });
+      const unconfirmed = targets.filter((targetId) => !confirmed.has(targetId));
+      set((state) => ({
+        tasks: rollbackTasks(state.tasks, previous, unconfirmed),
+        pendingTaskOps: clearPending(state.pendingTaskOps, unconfirmed)
+      }));
+      get().showToast(`Could not complete "${task.title}": ${message}`);
       logger.error("task.done.error", `${uuid}: ${message}`);
     }
+    await get().resyncTasks();
   },
 
   async markTaskUndone(uuid) {
     const task = get().tasks.find((entry) => entry.uuid === uuid);
-    if (!task || task.status !== "Completed") {
+    if (!task || task.status !== "Completed" || get().pendingTaskOps[uuid]) {
       return;
     }
 
-    set({ loading: true, error: null });
+    const previous = get().tasks;
+    const nowIso = new Date().toISOString();
+    set((state) => ({
+      error: null,
+      tasks: state.tasks.map((entry) => (entry.uuid === uuid ? optimisticUncomplete(entry, nowIso) : entry)),
+      pendingTaskOps: markPending(state.pendingTaskOps, [uuid], "uncomplete")
+    }));
     logger.info("task.uncomplete.start", uuid);
     try {
       const updated = await uncompleteTask(uuid);
       set((state) => ({
-        loading: false,
-        tasks: state.tasks.map((entry) => (entry.uuid === uuid ? updated : entry))
+        tasks: state.tasks.map((entry) => (entry.uuid === uuid ? updated : entry)),
+        pendingTaskOps: clearPending(state.pendingTaskOps, [uuid])
       }));
       logger.info("task.uncomplete.done", uuid);
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
-      set({ loading: false, error: message });
+      set((state) => ({
+        tasks: rollbackTasks(state.tasks, previous, [uuid]),
+        pendingTaskOps: clearPending(state.pendingTaskOps, [uuid])
+      }));
+      get().showToast(`Could not reopen "${task.title}": ${message}`);
       logger.error("task.uncomplete.error", `${uuid}: ${message}`);
     }
+    await get().resyncTasks();
+  },
+
+  async startTaskClock(uuid) {
+    await runTaskClockOp(uuid, "start");
+  },
+
+  async stopTaskClock(uuid) {
+    await runTaskClockOp(uuid, "stop");
   },
 
   async removeTask(uuid) {
-    set({ loading: true, error: null });
+    const task = get().tasks.find((entry) => entry.uuid === uuid);
+    if (!task || get().pendingTaskOps[uuid]) {
+      return;
+    }
+
+    const previous = get().tasks;
+    const wasSelected = get().selectedTaskId === uuid;
+    set((state) => {
+      const nextTasks = state.tasks.filter((entry) => entry.uuid !== uuid);
+      return {
+        error: null,
+        tasks: nextTasks,
+        selectedTaskId: wasSelected ? nextTasks[0]?.uuid ?? null : state.selectedTaskId,
+        pendingTaskOps: markPending(state.pendingTaskOps, [uuid], "delete")
+      };
+    });
     logger.info("task.delete.start", uuid);
     try {
       await deleteTask(uuid);
-      set((state) => {
-        const nextTasks = state.tasks.filter((task) => task.uuid !== uuid);
-        return {
-          loading: false,
-          tasks: nextTasks,
-          selectedTaskId: state.selectedTaskId === uuid ? nextTasks[0]?.uuid ?? null : state.selectedTaskId
-        };
-      });
+      set((state) => ({ pendingTaskOps: clearPending(state.pendingTaskOps, [uuid]) }));
       logger.info("task.delete.done", uuid);
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
-      set({ loading: false, error: message });
+      set((state) => ({
+        tasks: rollbackTasks(state.tasks, previous, [uuid]),
+        selectedTaskId: wasSelected ? uuid : state.selectedTaskId,
+        pendingTaskOps: clearPending(state.pendingTaskOps, [uuid])
+      }));
+      get().showToast(`Could not delete "${task.title}": ${message}`);
       logger.error("task.delete.error", `${uuid}: ${message}`);
     }
+    await get().resyncTasks();
   },
 
   async markTasksDoneBulk(uuids) {
@@ -824,7 +1283,7 @@ export const useAppStore = create<AppState>((set, get) => {
     const blockedCalendar: string[] = [];
     for (const uuid of targetIds) {
       const task = taskById.get(uuid);
-      if (!task) {
+      if (!task || get().pendingTaskOps[uuid]) {
         continue;
       }
       if (!(task.status === "Pending" || task.status === "Waiting")) {
@@ -845,7 +1304,14 @@ export const useAppStore = create<AppState>((set, get) => {
       return;
     }
 
-    set({ loading: true, error: null });
+    const eligibleSet = new Set(eligible);
+    const previous = get().tasks;
+    const nowIso = new Date().toISOString();
+    set((state) => ({
+      error: null,
+      tasks: state.tasks.map((task) => (eligibleSet.has(task.uuid) ? optimisticDone(task, nowIso) : task)),
+      pendingTaskOps: markPending(state.pendingTaskOps, eligible, "done")
+    }));
     logger.info("task.done.bulk.start", `count=${eligible.length}`);
 
     const updatedById = new Map<string, TaskDto>();
@@ -860,18 +1326,20 @@ export const useAppStore = create<AppState>((set, get) => {
       }
     }
 
-    const failureMessage = failed.length > 0 ? `Failed to complete ${failed.length} task(s).` : null;
     const blockedMessage = blockedCalendar.length > 0 ? `Blocked ${blockedCalendar.length} calendar task(s) before due time.` : null;
-    const errorMessage = [failureMessage, blockedMessage].filter((entry) => Boolean(entry)).join(" ");
     set((state) => ({
-      loading: false,
-      error: errorMessage || null,
-      tasks: state.tasks.map((task) => updatedById.get(task.uuid) ?? task)
+      error: blockedMessage,
+      tasks: rollbackTasks(state.tasks, previous, failed).map((task) => updatedById.get(task.uuid) ?? task),
+      pendingTaskOps: clearPending(state.pendingTaskOps, eligible)
     }));
+    if (failed.length > 0) {
+      get().showToast(`Failed to complete ${failed.length} task(s); their changes were rolled back.`);
+    }
     logger.info(
       "task.done.bulk.done",
       `completed=${updatedById.size} failed=${failed.length} blocked=${blockedCalendar.length}`
     );
+    await get().resyncTasks();
   },
 
   async markTasksUndoneBulk(uuids) {
@@ -881,12 +1349,21 @@ export const useAppStore = create<AppState>((set, get) => {
     }
 
     const taskById = new Map(get().tasks.map((task) => [task.uuid, task] as const));
-    const eligible = targetIds.filter((uuid) => taskById.get(uuid)?.status === "Completed");
+    const eligible = targetIds.filter(
+      (uuid) => taskById.get(uuid)?.status === "Completed" && !get().pendingTaskOps[uuid]
+    );
     if (eligible.length === 0) {
       return;
     }
 
-    set({ loading: true, error: null });
+    const eligibleSet = new Set(eligible);
+    const previous = get().tasks;
+    const nowIso = new Date().toISOString();
+    set((state) => ({
+      error: null,
+      tasks: state.tasks.map((task) => (eligibleSet.has(task.uuid) ? optimisticUncomplete(task, nowIso) : task)),
+      pendingTaskOps: markPending(state.pendingTaskOps, eligible, "uncomplete")
+    }));
     logger.info("task.uncomplete.bulk.start", `count=${eligible.length}`);
 
     const updatedById = new Map<string, TaskDto>();
@@ -902,23 +1379,42 @@ export const useAppStore = create<AppState>((set, get) => {
     }
 
     set((state) => ({
-      loading: false,
-      error: failed.length > 0 ? `Failed to uncomplete ${failed.length} task(s).` : null,
-      tasks: state.tasks.map((task) => updatedById.get(task.uuid) ?? task)
+      tasks: rollbackTasks(state.tasks, previous, failed).map((task) => updatedById.get(task.uuid) ?? task),
+      pendingTaskOps: clearPending(state.pendingTaskOps, eligible)
     }));
+    if (failed.length > 0) {
+      get().showToast(`Failed to uncomplete ${failed.length} task(s); their changes were rolled back.`);
+    }
     logger.info(
       "task.uncomplete.bulk.done",
       `reopened=${updatedById.size} failed=${failed.length}`
     );
+    await get().resyncTasks();
   },
 
   async removeTasksBulk(uuids) {
-    const targetIds = [...new Set(uuids)];
+    const targetIds = [...new Set(uuids)].filter(
+      (uuid) => !get().pendingTaskOps[uuid] && get().tasks.some((task) => task.uuid === uuid)
+    );
     if (targetIds.length === 0) {
       return;
     }
 
-    set({ loading: true, error: null });
+    const targetSet = new Set(targetIds);
+    const previous = get().tasks;
+    const previousSelection = get().selectedTaskId;
+    set((state) => {
+      const nextTasks = state.tasks.filter((task) => !targetSet.has(task.uuid));
+      const selectedTaskId = state.selectedTaskId && targetSet.has(state.selectedTaskId)
+        ? nextTasks[0]?.uuid ?? null
+        : state.selectedTaskId;
+      return {
+        error: null,
+        tasks: nextTasks,
+        selectedTaskId,
+        pendingTaskOps: markPending(state.pendingTaskOps, targetIds, "delete")
+      };
+    });
     logger.info("task.delete.bulk.start", `count=${targetIds.length}`);
 
     const deleted = new Set<string>();
@@ -933,27 +1429,58 @@ export const useAppStore = create<AppState>((set, get) => {
       }
     }
 
-    set((state) => {
-      const nextTasks = state.tasks.filter((task) => !deleted.has(task.uuid));
-      const selectedTaskId = state.selectedTaskId && deleted.has(state.selectedTaskId)
-        ? nextTasks[0]?.uuid ?? null
-        : state.selectedTaskId;
-      return {
-        loading: false,
-        error: failed.length > 0 ? `Failed to delete ${failed.length} task(s).` : state.error,
-        tasks: nextTasks,
-        selectedTaskId
-      };
-    });
+    set((state) => ({
+      tasks: rollbackTasks(state.tasks, previous, failed),
+      selectedTaskId: previousSelection && failed.includes(previousSelection) ? previousSelection : state.selectedTaskId,
+      pendingTaskOps: clearPending(state.pendingTaskOps, targetIds)
+    }));
+    if (failed.length > 0) {
+      get().showToast(`Failed to delete ${failed.length} task(s); they were restored.`);
+    }
     logger.info(
       "task.delete.bulk.done",
       `deleted=${deleted.size} failed=${failed.length}`
     );
+    await get().resyncTasks();
+  },
+
+  async updateTasksBulk(updates) {
+    if (updates.length === 0) {
+      return;
+    }
+
+    set({ loading: true, error: null });
+    logger.info("task.update.bulk.start", `count=${updates.length}`);
+
+    const updatedById = new Map<string, TaskDto>();
+    const failed: string[] = [];
+    for (const entry of updates) {
+      try {
+        const updated = await updateTask(entry);
+        updatedById.set(entry.uuid, updated);
+      } catch (error) {
+        failed.push(entry.uuid);
+        logger.warn("task.update.bulk.item_error", `${entry.uuid}: ${String(error)}`);
+      }
+    }
+
+    set((state) => ({
+      loading: false,
+      error: failed.length > 0 ? `Failed to update ${failed.length} task(s).` : null,
+      tasks: state.tasks.map((task) => updatedById.get(task.uuid) ?? task)
+    }));
+    logger.info(
+      "task.update.bulk.done",
+      `updated=${updatedById.size} failed=${failed.length}`
+    );
   },
 
   setActiveKanbanBoard(boardId) {
     saveActiveKanbanBoardId(boardId);
-    set({ activeKanbanBoardId: boardId });
+    const openedAt = Date.now();
+    const nextBoards = get().kanbanBoards.map((board) => (board.id === boardId ? { ...board, opened_at: openedAt } : board));
+    saveKanbanBoards(nextBoards);
+    set({ activeKanbanBoardId: boardId, kanbanBoards: nextBoards });
     logger.info("kanban.board.select", boardId ?? "(none)");
   },
 
@@ -1032,12 +1559,177 @@ export const useAppStore = create<AppState>((set, get) => {
     }
   },
 
+  async remapTagValue(key, from, to) {
+    try {
+      const result = await remapTags({ key, from, to });
+      get().showToast(`Moved ${result.changed} task(s) from ${key}:${from} to ${key}:${to}.`);
+      logger.info("tags.remap.done", `${key}:${from}->${to} changed=${result.changed}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      get().showToast(`Could not remap ${key}:${from}: ${message}`);
+      logger.error("tags.remap.error", message);
+    }
+    await get().resyncTasks();
+  },
+
+  async clearDemoData() {
+    try {
+      const result = await clearDemoData();
+      const boards = get().kanbanBoards;
+      const nextBoards = boards.filter((board) => board.id !== result.board_id);
+      if (nextBoards.length !== boards.length) {
+        const activeId = get().activeKanbanBoardId;
+        const nextActive = activeId === result.board_id ? (nextBoards[0]?.id ?? null) : activeId;
+        saveKanbanBoards(nextBoards);
+        saveActiveKanbanBoardId(nextActive);
+        set({ kanbanBoards: nextBoards, activeKanbanBoardId: nextActive });
+      }
+      set((state) => ({ tasks: state.tasks.filter((task) => !isDemoTask(task.tags)) }));
+      get().showToast(`Cleared ${result.tasks} demo task(s).`);
+      logger.info("demo.clear.done", `tasks=${result.tasks}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      get().showToast(`Could not clear demo data: ${message}`);
+      logger.error("demo.clear.error", message);
+    }
+    await get().resyncTasks();
+  },
+
+  async exportSettings() {
+    try {
+      const gui = buildGuiSettingsBundle(get().kanbanBoards, get().externalCalendars);
+      const bundle = await exportConfigBundle(gui);
+      if (typeof window === "undefined") {
+        return;
+      }
+      const blob = new Blob([bundle], { type: "application/json" });
+      const url = URL.createObjectURL(blob);
+      const link = document.createElement("a");
+      link.href = url;
+      link.download = `rivet-settings-${new Date().toISOString().slice(0, 10)}.json`;
+      link.click();
+      URL.revokeObjectURL(url);
+      logger.info("settings.export.done", `boards=${gui.kanban_boards.length} calendars=${gui.external_calendars.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      get().showToast(`Could not export settings: ${message}`);
+      logger.error("settings.export.error", message);
+    }
+  },
+
+  async importSettings(bundle, onConflict) {
+    try {
+      const result = await importConfigBundle(bundle, onConflict);
+      const gui = parseGuiSettingsBundle(result.gui);
+      const guiConflicts = gui ? guiSettingsConflicts(gui, get().kanbanBoards, get().externalCalendars) : [];
+      if (onConflict === "ask" && (!result.applied || guiConflicts.length > 0)) {
+        const conflicts = [...(result.applied ? [] : result.conflicts), ...guiConflicts];
+        logger.info("settings.import.conflicts", conflicts.join(", "));
+        return conflicts;
+      }
+
+      if (gui) {
+        const merged = mergeGuiSettings(gui, get().kanbanBoards, get().externalCalendars, onConflict === "overwrite");
+        saveKanbanBoards(merged.kanban_boards);
+        saveExternalCalendars(merged.external_calendars);
+        set({ kanbanBoards: merged.kanban_boards, externalCalendars: merged.external_calendars });
+      }
+      const [runtimeConfig, tagSchema] = await Promise.all([loadConfigSnapshot(), loadTagSchemaSnapshot()]);
+      set({
+        runtimeConfig,
+        tagSchema,
+        tagColorMap: buildTagColorMap(tagSchema),
+        dueNotificationConfig: runtimeDueConfig(runtimeConfig, get().dueNotificationConfig),
+        quietHours: resolveQuietHours(runtimeConfig),
+        themeFollowSystem: runtimeThemeFollowSystem(runtimeConfig)
+      });
+
+      const backups = result.backups.length > 0 ? ` Backed up ${result.backups.length} file(s) first.` : "";
+      get().showToast(`Imported settings: ${result.written.length} file(s) written, ${result.kept.length} kept.${backups}`, "success");
+      logger.info(
+        "settings.import.done",
+        `written=${result.written.join(",") || "none"} kept=${result.kept.join(",") || "none"} backups=${result.backups.length}`
+      );
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      get().showToast(`Could not import settings: ${message}`);
+      logger.error("settings.import.error", message);
+    }
+    return [];
+  },
+
+  setKanbanBoardSort(mode) {
+    saveKanbanBoardSort(mode);
+    set({ kanbanBoardSort: mode });
+  },
+
+  moveKanbanBoard(boardId, beforeBoardId) {
+    const nextBoards = moveBoardBefore(get().kanbanBoards, boardId, beforeBoardId);
+    saveKanbanBoards(nextBoards);
+    set({ kanbanBoards: nextBoards });
+    logger.info("kanban.board.move", `${boardId}->${beforeBoardId}`);
+  },
+
+  setActiveKanbanBoardView(view) {
+    const activeId = get().activeKanbanBoardId;
+    if (!activeId) {
+      return;
+    }
+    const nextBoards = get().kanbanBoards.map((board) => (board.id === activeId ? { ...board, ...view } : board));
+    saveKanbanBoards(nextBoards);
+    set({ kanbanBoards: nextBoards });
+  },
+
+  setActiveKanbanCardOrder(order) {
+    const activeId = get().activeKanbanBoardId;
+    if (!activeId) {
+      return;
+    }
+    // Drop tasks that no longer exist so the stored order does not grow forever.
+    const known = new Set(get().tasks.map((task) => task.uuid));
+    const cardOrder = order.filter((uuid) => known.has(uuid));
+    const nextBoards = get().kanbanBoards.map((board) => (board.id === activeId ? { ...board, card_order: cardOrder } : board));
+    saveKanbanBoards(nextBoards);
+    set({ kanbanBoards: nextBoards });
+  },
+
   toggleKanbanCompactCards() {
     const next = !get().kanbanCompactCards;
     saveKanbanCompactCards(next);
     set({ kanbanCompactCards: next });
   },
 
+  toggleTaskListGroupByDue() {
+    const next = !get().taskListGroupByDue;
+    saveTaskListGroupByDue(next);
+    set({ taskListGroupByDue: next });
+  },
+
+  saveTaskAsTemplate(taskId, name, includeDue) {
+    const state = get();
+    const task = state.tasks.find((entry) => entry.uuid === taskId)
+      ?? state.completedTasks.find((entry) => entry.uuid === taskId);
+    if (!task) {
+      return;
+    }
+    const template = templateFromTask(task, name, {
+      includeDue,
+      nowUtcMs: Date.now(),
+      timezone: resolveCalendarConfig(state.runtimeConfig).timezone
+    });
+    const next = upsertTemplate(state.taskTemplates, template);
+    saveTaskTemplates(next);
+    set({ taskTemplates: next });
+    logger.info("task_templates.save", `${template.name} from ${taskId}`);
+    get().showToast(`Saved template "${template.name}".`, "success");
+  },
+
+  deleteTaskTemplate(templateId) {
+    const next = get().taskTemplates.filter((entry) => entry.id !== templateId);
+    saveTaskTemplates(next);
+    set({ taskTemplates: next });
+  },
+
   setDraggingKanbanTask(taskId) {
     set({ draggingKanbanTaskId: taskId });
   },
@@ -1082,6 +1774,30 @@ export const useAppStore = create<AppState>((set, get) => {
     await get().updateTaskByUuid(taskId, { tags: nextTags });
   },
 
+  async dropKanbanTaskOnProject(taskId, project) {
+    set({ draggingKanbanTaskId: null, dragOverKanbanLane: null });
+    const task = get().tasks.find((entry) => entry.uuid === taskId);
+    if (!task || task.project === project) {
+      return;
+    }
+    logger.info("kanban.task.drop_project", `${taskId} -> ${project}`);
+    await get().updateTaskByUuid(taskId, { project });
+  },
+
+  async dropKanbanTaskOnTag(taskId, tag) {
+    set({ draggingKanbanTaskId: null, dragOverKanbanLane: null });
+    const task = get().tasks.find((entry) => entry.uuid === taskId);
+    if (!task) {
+      return;
+    }
+    const nextTags = tagsForTagDrop(task.tags, tag, get().tagSchema);
+    if (!nextTags) {
+      return;
+    }
+    logger.info("kanban.task.drop_tag", `${taskId} +${tag}`);
+    await get().updateTaskByUuid(taskId, { tags: nextTags });
+  },
+
   setCalendarView(view) {
     saveCalendarViewMode(view);
     set({ calendarView: view });
@@ -1099,6 +1815,29 @@ export const useAppStore = create<AppState>((set, get) => {
     set({ calendarFocusDateIso: calendarDateToIso(shifted) });
   },
 
+  async jumpCalendarToInput(input) {
+    const trimmed = input.trim();
+    if (!trimmed) {
+      return "Enter a date, e.g. 2027-03-14 or march 2027.";
+    }
+    let iso: string;
+    try {
+      iso = await parseCalendarDate(trimmed);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.warn("calendar.jump.invalid", `${trimmed}: ${message}`);
+      return `Not a date: ${trimmed}`;
+    }
+    const effective = resolveCalendarConfig(get().runtimeConfig);
+    const { date, clamped } = clampCalendarJump(calendarDateFromIso(iso), todayInTimezone(effective.timezone));
+    if (clamped) {
+      get().showToast(`${trimmed} is out of range; showing ${calendarDateToIso(date)}.`, "warning");
+    }
+    logger.info("calendar.jump", `${trimmed} -> ${calendarDateToIso(date)}`);
+    set({ calendarFocusDateIso: calendarDateToIso(date) });
+    return null;
+  },
+
   navigateCalendar(iso, view) {
     const next = view ?? get().calendarView;
     saveCalendarViewMode(next);
@@ -1184,6 +1923,7 @@ export const useAppStore = create<AppState>((set, get) => {
         externalCalendarLastSync: `Synced ${source.name}: +${result.created} / ~${result.updated} / -${result.deleted}`
       });
       logger.info("external_calendar.sync.done", `${source.id} created=${result.created} updated=${result.updated} deleted=${result.deleted}`);
+      await get().scheduleExternalCalendarSync();
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
       set({
@@ -1227,6 +1967,75 @@ export const useAppStore = create<AppState>((set, get) => {
       externalCalendarLastSync: `Sync all complete: +${created} / ~${updated} / -${deleted}${failed > 0 ? ` (failures=${failed})` : ""}`
     });
     logger.info("external_calendar.sync_all.done", `created=${created} updated=${updated} deleted=${deleted} failed=${failed}`);
+    await get().scheduleExternalCalendarSync();
+  },
+
+  async scheduleExternalCalendarSync() {
+    try {
+      const statuses = await scheduleExternalCalendars(get().externalCalendars);
+      set({ externalCalendarSyncStatus: Object.fromEntries(statuses.map((status) => [status.calendar_id, status])) });
+      logger.debug("external_calendar.schedule.done", `sources=${statuses.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.error("external_calendar.schedule.error", message);
+    }
+  },
+
+  async handleExternalCalendarSynced(event) {
+    const previous = get().externalCalendarSyncStatus[event.calendar_id];
+    const source = get().externalCalendars.find((entry) => entry.id === event.calendar_id);
+    // Failed attempts also wait a full interval before the next try.
+    const nextSync = source && source.refresh_minutes > 0
+      ? new Date(Date.parse(event.synced_at) + source.refresh_minutes * 60_000).toISOString()
+      : null;
+    set((state) => ({
+      externalCalendarSyncStatus: {
+        ...state.externalCalendarSyncStatus,
+        [event.calendar_id]: {
+          calendar_id: event.calendar_id,
+          last_sync: event.result ? event.synced_at : previous?.last_sync ?? null,
+          last_error: event.error,
+          next_sync: nextSync,
+          state: previous?.state ?? "idle"
+        }
+      },
+      externalCalendarLastSync: event.result
+        ? `Auto-synced ${event.name}: +${event.result.created} / ~${event.result.updated} / -${event.result.deleted}`
+        : `Auto-sync failed for ${event.name}: ${event.error ?? "unknown error"}`
+    }));
+    if (event.result && event.result.created + event.result.updated + event.result.deleted > 0) {
+      await get().loadTasks();
+    }
+    logger.info("external_calendar.auto_sync", `${event.calendar_id} ok=${event.result !== null}`);
+  },
+
+  handleExternalCalendarSyncActivity(activity) {
+    const running = new Set(activity.running);
+    const queued = new Set(activity.queued);
+    set((state) => ({
+      externalCalendarSyncStatus: Object.fromEntries(
+        Object.entries(state.externalCalendarSyncStatus).map(([id, status]) => [
+          id,
+          { ...status, state: running.has(id) ? "running" : queued.has(id) ? "queued" : "idle" }
+        ])
+      )
+    }));
+  },
+
+  async subscribeExternalCalendarSync() {
+    const stops = await Promise.all([
+      onExternalCalendarSynced((event) => {
+        void get().handleExternalCalendarSynced(event);
+      }),
+      onExternalCalendarSyncActivity((activity) => {
+        get().handleExternalCalendarSyncActivity(activity);
+      })
+    ]);
+    return () => {
+      for (const stop of stops) {
+        stop();
+      }
+    };
   },
 
   async importExternalCalendarFile(file) {
@@ -1621,14 +2430,21 @@ export const useAppStore = create<AppState>((set, get) => {
     }
 
     const effective = resolveCalendarConfig(state.runtimeConfig);
+    // Nothing is marked sent, so held notifications go out once quiet hours end.
+    if (inQuietHours(state.quietHours, nowMs, effective.timezone)) {
+      return;
+    }
     const sent = new Set(state.dueNotificationSent);
-    const events = collectDueNotificationEvents(
-      state.tasks,
-      effective.timezone,
-      state.dueNotificationConfig,
-      sent,
-      nowMs
-    );
+    const events = [
+      ...collectDueNotificationEvents(
+        state.tasks,
+        effective.timezone,
+        state.dueNotificationConfig,
+        sent,
+        nowMs
+      ),
+      ...collectFocusNotificationEvents(state.tasks, resolveFocusConfig(state.runtimeConfig), sent, nowMs)
+    ];
     if (events.length === 0) {
       return;
     }
@@ -1662,32 +2478,43 @@ export const useAppStore = create<AppState>((set, get) => {
   };
 });
 
-export function useTaskViewData(): {
+// `nowUtcMs` and `timezone` resolve the date-based quick filters.
+export function useTaskViewData(nowUtcMs: number, timezone: string): {
   visibleTasks: TaskDto[];
   projectFacets: Array<{ value: string; count: number }>;
   tagFacets: Array<{ value: string; count: number }>;
+  quickFilterCounts: Record<QuickFilter, number>;
 } {
   const tasks = useAppStore((state) => state.tasks);
+  const archivedTasks = useAppStore((state) => state.archivedTasks);
   const filters = useAppStore((state) => state.taskFilters);
+  const quickFilters = useAppStore((state) => state.taskQuickFilters);
 
   return useMemo(() => {
-    const visibleTasks = filterTasks(tasks, filters);
+    const filtered = filterTasks(filters.status === "Archived" ? archivedTasks : tasks, filters);
+    const visibleTasks = filterByQuickFilters(filtered, quickFilters, nowUtcMs, timezone);
     const facets = buildTaskFacets(visibleTasks);
     return {
       visibleTasks,
       projectFacets: facets.projectFacets,
-      tagFacets: facets.tagFacets
+      tagFacets: facets.tagFacets,
+      quickFilterCounts: quickFilterCounts(filtered, quickFilters, nowUtcMs, timezone)
     };
-  }, [tasks, filters]);
+  }, [tasks, archivedTasks, filters, quickFilters, nowUtcMs, timezone]);
 }
 
 export function useSelectedTask(): TaskDto | null {
   const selectedTaskId = useAppStore((state) => state.selectedTaskId);
   const tasks = useAppStore((state) => state.tasks);
+  const archivedTasks = useAppStore((state) => state.archivedTasks);
   if (!selectedTaskId) {
     return null;
   }
-  return tasks.find((task) => task.uuid === selectedTaskId) ?? null;
+  return (
+    tasks.find((task) => task.uuid === selectedTaskId) ??
+    archivedTasks.find((task) => task.uuid === selectedTaskId) ??
+    null
+  );
 }
 
 export function useKanbanColumns(): string[] {
@@ -1702,11 +2529,13 @@ export function useKanbanViewData(): {
   tagFacets: Array<{ value: string; count: number }>;
 } {
   const tasks = useAppStore((state) => state.tasks);
+  const archivedTasks = useAppStore((state) => state.archivedTasks);
   const activeBoardId = useAppStore((state) => state.activeKanbanBoardId);
   const filters = useAppStore((state) => state.kanbanFilters);
 
   return useMemo(() => {
-    const boardTasks = tasks.filter((task) => {
+    const source = filters.status === "Archived" ? archivedTasks : tasks;
+    const boardTasks = source.filter((task) => {
       if (!activeBoardId) {
         return false;
       }
@@ -1721,7 +2550,7 @@ export function useKanbanViewData(): {
       projectFacets: facets.projectFacets,
       tagFacets: facets.tagFacets
     };
-  }, [tasks, activeBoardId, filters]);
+  }, [tasks, archivedTasks, activeBoardId, filters]);
 }
 
 export function useBoardColorMap(): Record<string, string> {
@@ -1737,13 +2566,12 @@ export function useBoardColorMap(): Record<string, string> {
 
 export function useExternalCalendarColorMap(): Record<string, string> {
   const sources = useAppStore((state) => state.externalCalendars);
-  return useMemo(() => {
-    const map: Record<string, string> = {};
-    for (const source of sources) {
-      map[source.id] = source.color;
-    }
-    return map;
-  }, [sources]);
+  return useMemo(() => buildExternalCalendarColorMap(sources), [sources]);
+}
+
+export function useTasksById(): Map<string, TaskDto> {
+  const tasks = useAppStore((state) => state.tasks);
+  return useMemo(() => indexTasksById(tasks), [tasks]);
 }
 
 export function useCalendarDueEntries() {
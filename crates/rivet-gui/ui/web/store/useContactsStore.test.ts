@@ -8,12 +8,16 @@ const mocks = vi.hoisted(() => ({
   decideContactsDedupeMock: vi.fn(),
   deleteContactMock: vi.fn(),
   deleteContactsBulkMock: vi.fn(),
+  getContactMock: vi.fn(),
   listContactsMock: vi.fn(),
   listContactsDedupeCandidatesMock: vi.fn(),
   mergeContactsMock: vi.fn(),
   openContactActionMock: vi.fn(),
   previewContactsImportMock: vi.fn(),
+  previewContactsMergeMock: vi.fn(),
+  undoContactsDeleteMock: vi.fn(),
   undoContactsMergeMock: vi.fn(),
+  unlinkContactMock: vi.fn(),
   updateContactMock: vi.fn()
 }));
 
@@ -23,12 +27,16 @@ vi.mock("../api/tauri", () => ({
   decideContactsDedupe: mocks.decideContactsDedupeMock,
   deleteContact: mocks.deleteContactMock,
   deleteContactsBulk: mocks.deleteContactsBulkMock,
+  getContact: mocks.getContactMock,
   listContacts: mocks.listContactsMock,
   listContactsDedupeCandidates: mocks.listContactsDedupeCandidatesMock,
   mergeContacts: mocks.mergeContactsMock,
   openContactAction: mocks.openContactActionMock,
   previewContactsImport: mocks.previewContactsImportMock,
+  previewContactsMerge: mocks.previewContactsMergeMock,
+  undoContactsDelete: mocks.undoContactsDeleteMock,
   undoContactsMerge: mocks.undoContactsMergeMock,
+  unlinkContact: mocks.unlinkContactMock,
   updateContact: mocks.updateContactMock
 }));
 
@@ -57,6 +65,7 @@ function sampleContact(id: string, name: string): ContactDto {
     source_kind: "local",
     remote_id: null,
     link_group_id: null,
+    categories: [],
     created_at: now,
     updated_at: now
   };
@@ -71,12 +80,16 @@ describe("useContactsStore", () => {
     mocks.decideContactsDedupeMock.mockReset();
     mocks.deleteContactMock.mockReset();
     mocks.deleteContactsBulkMock.mockReset();
+    mocks.getContactMock.mockReset();
     mocks.listContactsMock.mockReset();
     mocks.listContactsDedupeCandidatesMock.mockReset();
     mocks.mergeContactsMock.mockReset();
     mocks.openContactActionMock.mockReset();
     mocks.previewContactsImportMock.mockReset();
+    mocks.previewContactsMergeMock.mockReset();
+    mocks.undoContactsDeleteMock.mockReset();
     mocks.undoContactsMergeMock.mockReset();
+    mocks.unlinkContactMock.mockReset();
     mocks.updateContactMock.mockReset();
 
     useContactsStore.setState(initialState, true);
@@ -87,7 +100,8 @@ describe("useContactsStore", () => {
     mocks.listContactsMock.mockResolvedValueOnce({
       contacts,
       next_cursor: null,
-      total: 1
+      total: 1,
+      categories: []
     });
 
     await useContactsStore.getState().loadContacts();
@@ -101,7 +115,8 @@ describe("useContactsStore", () => {
       limit: 200,
       cursor: null,
       source: null,
-      updated_after: null
+      updated_after: null,
+      category: null
     });
   });
 
@@ -110,7 +125,8 @@ describe("useContactsStore", () => {
     mocks.listContactsMock.mockResolvedValue({
       contacts,
       next_cursor: null,
-      total: 1
+      total: 1,
+      categories: []
     });
 
     await useContactsStore.getState().loadContacts();
@@ -119,6 +135,29 @@ describe("useContactsStore", () => {
     expect(mocks.listContactsMock).toHaveBeenCalledTimes(1);
   });
 
+  it("filters by category and keeps the facet counts from the backend", async () => {
+    const work = { ...sampleContact("c-1", "Ada"), categories: ["Work"] };
+    const facets = [
+      { category: "Work", count: 1 },
+      { category: "Friends", count: 1 }
+    ];
+    mocks.listContactsMock.mockResolvedValue({
+      contacts: [work],
+      next_cursor: null,
+      total: 1,
+      categories: facets
+    });
+
+    await useContactsStore.getState().setCategoryFilter("Work");
+
+    expect(mocks.listContactsMock).toHaveBeenCalledWith(expect.objectContaining({ category: "Work" }));
+    expect(useContactsStore.getState().categoryFacets).toEqual(facets);
+
+    await useContactsStore.getState().setCategoryFilter(null);
+    expect(mocks.listContactsMock).toHaveBeenLastCalledWith(expect.objectContaining({ category: null }));
+    expect(mocks.listContactsMock).toHaveBeenCalledTimes(2);
+  });
+
   it("ignores stale in-flight responses", async () => {
     let resolveFirst!: (value: { contacts: ContactDto[]; next_cursor: string | null; total: number }) => void;
     const firstPromise = new Promise<{ contacts: ContactDto[]; next_cursor: string | null; total: number }>((resolve) => {
@@ -130,7 +169,8 @@ describe("useContactsStore", () => {
       .mockResolvedValueOnce({
         contacts: [sampleContact("c-2", "Zoe")],
         next_cursor: null,
-        total: 1
+        total: 1,
+        categories: []
       });
 
     const first = useContactsStore.getState().setQuery("a");
@@ -185,4 +225,138 @@ describe("useContactsStore", () => {
     });
     expect(mocks.listContactsDedupeCandidatesMock).toHaveBeenCalled();
   });
+
+  it("previews a merge without committing it", async () => {
+    const keep = sampleContact("c1", "Ada");
+    const drop = sampleContact("c2", "Ada L.");
+    useContactsStore.setState({
+      contacts: [keep, drop],
+      selectionIds: [keep.id, drop.id],
+      selectedContactId: keep.id
+    });
+    mocks.previewContactsMergeMock.mockResolvedValueOnce({
+      merged: keep,
+      removed_ids: [drop.id]
+    });
+
+    await useContactsStore.getState().previewMergeSelected();
+
+    expect(mocks.previewContactsMergeMock).toHaveBeenCalledWith({
+      ids: [keep.id, drop.id],
+      target_id: keep.id
+    });
+    expect(mocks.mergeContactsMock).not.toHaveBeenCalled();
+    expect(useContactsStore.getState().mergePreview?.removed_ids).toEqual([drop.id]);
+    expect(useContactsStore.getState().contacts).toHaveLength(2);
+  });
+
+  it("offers undo after a bulk delete and restores the contacts", async () => {
+    const ada = sampleContact("c1", "Ada");
+    const grace = sampleContact("c2", "Grace");
+    useContactsStore.setState({
+      contacts: [ada, grace],
+      selectionIds: [ada.id, grace.id],
+      selectedContactId: ada.id
+    });
+    mocks.deleteContactsBulkMock.mockResolvedValueOnce({ deleted: 2, undo_id: "undo-1", linked_tasks: 1 });
+    mocks.listContactsDedupeCandidatesMock.mockResolvedValue({ groups: [] });
+
+    await useContactsStore.getState().removeBulkSelected();
+
+    expect(useContactsStore.getState().contacts).toHaveLength(0);
+    expect(useContactsStore.getState().lastDeleteUndo).toEqual({ undoId: "undo-1", deleted: 2, linkedTasks: 1 });
+
+    mocks.undoContactsDeleteMock.mockResolvedValueOnce({ restored: 2, undo_id: "undo-1" });
+    mocks.listContactsMock.mockResolvedValueOnce({
+      contacts: [ada, grace],
+      next_cursor: null,
+      total: 2,
+      categories: []
+    });
+
+    await useContactsStore.getState().undoLastDelete();
+
+    expect(mocks.undoContactsDeleteMock).toHaveBeenCalledWith({ undo_id: "undo-1" });
+    expect(useContactsStore.getState().lastDeleteUndo).toBeNull();
+    expect(useContactsStore.getState().contacts).toEqual([ada, grace]);
+  });
+
+  it("remembers linked contacts, including ones that were deleted", async () => {
+    const ada = sampleContact("c1", "Ada");
+    mocks.getContactMock.mockResolvedValueOnce(ada).mockResolvedValueOnce(null);
+
+    await useContactsStore.getState().resolveLinkedContact(ada.id);
+    await useContactsStore.getState().resolveLinkedContact("gone");
+
+    expect(mocks.getContactMock).toHaveBeenCalledWith("gone");
+    expect(useContactsStore.getState().linkedContacts).toEqual({ c1: ada, gone: null });
+  });
+
+  it("searches for a linked contact that is not on the loaded page", async () => {
+    const ada = sampleContact("c1", "Ada");
+    const grace = sampleContact("c2", "Grace");
+    useContactsStore.setState({ contacts: [ada], selectedContactId: ada.id });
+    mocks.listContactsMock.mockResolvedValueOnce({
+      contacts: [grace],
+      next_cursor: null,
+      total: 1,
+      categories: []
+    });
+
+    await useContactsStore.getState().showContact(grace);
+
+    expect(useContactsStore.getState().query).toBe("Grace");
+    expect(useContactsStore.getState().selectedContactId).toBe(grace.id);
+  });
+
+  it("sends per-conflict import decisions and reports linked rows", async () => {
+    useContactsStore.setState({
+      importSource: "generic_vcard",
+      importFileName: "people.vcf",
+      importContent: "BEGIN:VCARD\nEND:VCARD\n"
+    });
+    useContactsStore.getState().setImportDecision(2, "link");
+    useContactsStore.getState().setImportDecision(4, "merge");
+    useContactsStore.getState().setImportDecision(4, null);
+    mocks.commitContactsImportMock.mockResolvedValueOnce({
+      batch_id: "batch-1",
+      created: 0,
+      updated: 0,
+      skipped: 0,
+      linked: 1,
+      failed: 0,
+      conflicts: 1,
+      errors: []
+    });
+    mocks.listContactsMock.mockResolvedValueOnce({ contacts: [], next_cursor: null, total: 0, categories: [] });
+    mocks.listContactsDedupeCandidatesMock.mockResolvedValue({ groups: [] });
+
+    await useContactsStore.getState().commitImport("safe");
+
+    expect(mocks.commitContactsImportMock).toHaveBeenCalledWith(expect.objectContaining({
+      mode: "safe",
+      decisions: [{ row: 2, decision: "link" }]
+    }));
+    expect(useContactsStore.getState().importCommitResult?.linked).toBe(1);
+    expect(useContactsStore.getState().importDecisions).toEqual({});
+  });
+
+  it("unlinks through the backend and skips contacts it already freed", async () => {
+    const ada = { ...sampleContact("c1", "Ada"), link_group_id: "g1" };
+    const grace = { ...sampleContact("c2", "Grace"), link_group_id: "g1" };
+    useContactsStore.setState({
+      contacts: [ada, grace],
+      selectionIds: [ada.id, grace.id]
+    });
+    mocks.unlinkContactMock.mockResolvedValueOnce({
+      contacts: [{ ...ada, link_group_id: null }, { ...grace, link_group_id: null }]
+    });
+    mocks.listContactsMock.mockResolvedValueOnce({ contacts: [], next_cursor: null, total: 0, categories: [] });
+
+    await useContactsStore.getState().unlinkSelectedContacts();
+
+    expect(mocks.unlinkContactMock).toHaveBeenCalledTimes(1);
+    expect(mocks.unlinkContactMock).toHaveBeenCalledWith({ id: "c1" });
+    expect(mocks.updateContactMock).not.toHaveBeenCalled();
+  });
 });
@@ -6,26 +6,38 @@ import {
   decideContactsDedupe,
   deleteContact,
   deleteContactsBulk,
+  getContact,
+  listContactInteractions,
   listContacts,
   listContactsDedupeCandidates,
   mergeContacts,
   openContactAction,
+  previewContactsMerge,
   previewContactsImport,
+  pruneContactInteractions,
+  undoContactsDelete,
   undoContactsMerge,
+  unlinkContact,
   updateContact
 } from "../api/tauri";
+import { normalizeCategories } from "../lib/contactCategories";
 import { logger } from "../lib/logger";
 import type {
   ContactAddress,
+  ContactCategoryCount,
   ContactCreate,
   ContactDto,
   ContactFieldValue,
+  ContactImportDecisionKind,
+  ContactInteractionsPruneResult,
   ContactOpenActionArgs,
   ContactPatch,
   ContactsDedupePreviewResult,
   ContactsImportCommitResult,
   ContactsImportPreviewResult,
-  ContactsMergeUndoResult
+  ContactsMergePreviewResult,
+  ContactsMergeUndoResult,
+  ContactsSortMode
 } from "../types/core";
 
 const CONTACTS_PAGE_SIZE = 200;
@@ -75,7 +87,8 @@ export function emptyContactDraft(): ContactCreate {
     source_id: "local",
     source_kind: "local",
     remote_id: null,
-    link_group_id: null
+    link_group_id: null,
+    categories: []
   };
 }
 
@@ -124,12 +137,21 @@ function normalizeDraft(input: ContactCreate): ContactCreate {
     source_id: input.source_id?.trim() || "local",
     source_kind: input.source_kind?.trim() || "local",
     remote_id: input.remote_id?.trim() || null,
-    link_group_id: input.link_group_id?.trim() || null
+    link_group_id: input.link_group_id?.trim() || null,
+    categories: normalizeCategories(input.categories)
   };
 }
 
-function queryCacheKey(query: string, sourceFilter: string | null): string {
-  return `${query.trim()}::${sourceFilter ?? "all"}`;
+interface ContactsListKey {
+  query: string;
+  sourceFilter: string | null;
+  categoryFilter: string | null;
+  sortMode: ContactsSortMode;
+  contactedWithinDays: number | null;
+}
+
+function queryCacheKey(key: ContactsListKey): string {
+  return `${key.query.trim()}::${key.sourceFilter ?? "all"}::${key.categoryFilter ?? "all"}::${key.sortMode}::${key.contactedWithinDays ?? "any"}`;
 }
 
 function mergeContactPages(previous: ContactDto[], next: ContactDto[]): ContactDto[] {
@@ -149,6 +171,12 @@ interface ContactsStore {
   error: string | null;
   query: string;
   sourceFilter: string | null;
+  categoryFilter: string | null;
+  // Category counts for the current query and source, ignoring categoryFilter.
+  categoryFacets: ContactCategoryCount[];
+  sortMode: ContactsSortMode;
+  // Only contacts with a logged call or email in this many days.
+  contactedWithinDays: number | null;
   contacts: ContactDto[];
   selectedContactId: string | null;
   selectionMode: boolean;
@@ -161,15 +189,26 @@ interface ContactsStore {
   importSource: string | null;
   importFileName: string | null;
   importContent: string | null;
+  // Per-conflict overrides of the import mode, keyed by preview conflict row.
+  importDecisions: Record<number, ContactImportDecisionKind>;
   mergeUndoResult: ContactsMergeUndoResult | null;
   lastMergeUndoId: string | null;
+  // Most recent bulk delete that can still be undone.
+  // linkedTasks counts open tasks still pointing at the deleted contacts.
+  lastDeleteUndo: { undoId: string; deleted: number; linkedTasks: number } | null;
+  mergePreview: ContactsMergePreviewResult | null;
+  // Contacts that tasks link to, by id; null once the contact is gone.
+  linkedContacts: Record<string, ContactDto | null>;
 
   formDraft: ContactCreate;
   dirty: boolean;
   editorMode: "add" | "edit";
 
   loadToken: number;
-  queryCache: Record<string, { contacts: ContactDto[]; nextCursor: string | null; total: number }>;
+  queryCache: Record<
+    string,
+    { contacts: ContactDto[]; nextCursor: string | null; total: number; categoryFacets: ContactCategoryCount[] }
+  >;
   queryCacheOrder: string[];
 
   bootstrap: () => Promise<void>;
@@ -177,6 +216,9 @@ interface ContactsStore {
   loadMoreContacts: () => Promise<void>;
   setQuery: (value: string) => Promise<void>;
   setSourceFilter: (value: string | null) => Promise<void>;
+  setCategoryFilter: (value: string | null) => Promise<void>;
+  setSortMode: (value: ContactsSortMode) => Promise<void>;
+  setContactedWithinDays: (value: number | null) => Promise<void>;
 
   selectContact: (id: string | null) => void;
   toggleSelectionMode: () => void;
@@ -193,18 +235,27 @@ interface ContactsStore {
   updateSelectedFromDraft: () => Promise<void>;
   removeSelectedContact: () => Promise<void>;
   removeBulkSelected: () => Promise<void>;
+  undoLastDelete: () => Promise<void>;
+  dismissDeleteUndo: () => void;
 
   refreshDedupe: () => Promise<void>;
   linkSelectedContacts: () => Promise<void>;
   unlinkSelectedContacts: () => Promise<void>;
+  previewMergeSelected: () => Promise<void>;
+  clearMergePreview: () => void;
   mergeSelected: () => Promise<void>;
   undoLastMerge: () => Promise<void>;
   decideDedupeGroup: (groupId: string, decision: "ignored" | "separate") => Promise<void>;
 
   previewImport: (source: string, fileName: string | null, content: string) => Promise<void>;
+  setImportDecision: (row: number, decision: ContactImportDecisionKind | null) => void;
   commitImport: (mode: "safe" | "upsert" | "review") => Promise<void>;
 
   openAction: (args: ContactOpenActionArgs) => Promise<void>;
+  resolveLinkedContact: (id: string) => Promise<void>;
+  showContact: (contact: ContactDto) => Promise<void>;
+  exportInteractions: () => Promise<void>;
+  pruneInteractions: (olderThanDays: number | null) => Promise<ContactInteractionsPruneResult | null>;
 }
 
 export const useContactsStore = create<ContactsStore>((set, get) => ({
@@ -212,6 +263,10 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
   error: null,
   query: "",
   sourceFilter: null,
+  categoryFilter: null,
+  categoryFacets: [],
+  sortMode: "name",
+  contactedWithinDays: null,
   contacts: [],
   selectedContactId: null,
   selectionMode: false,
@@ -224,8 +279,12 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
   importSource: null,
   importFileName: null,
   importContent: null,
+  importDecisions: {},
   mergeUndoResult: null,
   lastMergeUndoId: null,
+  lastDeleteUndo: null,
+  mergePreview: null,
+  linkedContacts: {},
 
   formDraft: emptyContactDraft(),
   dirty: false,
@@ -248,11 +307,12 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     const state = get();
 
     if (!append && !force) {
-      const cached = state.queryCache[queryCacheKey(state.query, state.sourceFilter)];
+      const cached = state.queryCache[queryCacheKey(state)];
       if (cached) {
         set((current) => ({
           contacts: cached.contacts,
           total: cached.total,
+          categoryFacets: cached.categoryFacets,
           nextCursor: cached.nextCursor,
           selectedContactId: current.selectedContactId && cached.contacts.some((contact) => contact.id === current.selectedContactId)
             ? current.selectedContactId
@@ -280,7 +340,10 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
         limit: CONTACTS_PAGE_SIZE,
         cursor,
         source: get().sourceFilter,
-        updated_after: null
+        updated_after: null,
+        category: get().categoryFilter,
+        sort: get().sortMode,
+        contacted_within_days: get().contactedWithinDays
       });
 
       if (get().loadToken !== token) {
@@ -300,19 +363,21 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
           loading: false,
           contacts,
           total: result.total,
+          categoryFacets: result.categories,
           nextCursor: result.next_cursor,
           selectedContactId,
           selectionIds
         };
 
         if (!append) {
-          const cacheKey = queryCacheKey(current.query, current.sourceFilter);
+          const cacheKey = queryCacheKey(current);
           const queryCache = {
             ...current.queryCache,
             [cacheKey]: {
               contacts,
               total: result.total,
-              nextCursor: result.next_cursor
+              nextCursor: result.next_cursor,
+              categoryFacets: result.categories
             }
           };
           const nextOrder = [
@@ -333,7 +398,7 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
 
       const durationMs = performance.now() - startedAt;
       if (durationMs > 100) {
-        logger.warn("contacts.perf.query", `query_ms=${durationMs.toFixed(2)} query=${get().query.trim()} source=${get().sourceFilter ?? "all"}`);
+        logger.warn("contacts.perf.query", `query_ms=${durationMs.toFixed(2)} query=${get().query.trim()} source=${get().sourceFilter ?? "all"} category=${get().categoryFilter ?? "all"}`);
       }
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
@@ -358,6 +423,21 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     await get().loadContacts();
   },
 
+  async setCategoryFilter(value) {
+    set({ categoryFilter: value });
+    await get().loadContacts();
+  },
+
+  async setSortMode(value) {
+    set({ sortMode: value });
+    await get().loadContacts();
+  },
+
+  async setContactedWithinDays(value) {
+    set({ contactedWithinDays: value });
+    await get().loadContacts();
+  },
+
   selectContact(id) {
     set({
       selectedContactId: id,
@@ -439,7 +519,8 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
         source_id: selected.source_id,
         source_kind: selected.source_kind,
         remote_id: selected.remote_id,
-        link_group_id: selected.link_group_id
+        link_group_id: selected.link_group_id,
+        categories: selected.categories
       },
       dirty: false,
       editorMode: "edit"
@@ -450,7 +531,7 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     set({ loading: true, error: null });
     try {
       const created = await addContact(normalizeDraft(get().formDraft));
-      const cacheKey = queryCacheKey(get().query, get().sourceFilter);
+      const cacheKey = queryCacheKey(get());
       set((state) => ({
         loading: false,
         contacts: [created, ...state.contacts],
@@ -463,7 +544,8 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
           [cacheKey]: {
             contacts: [created, ...state.contacts],
             total: state.total + 1,
-            nextCursor: state.nextCursor
+            nextCursor: state.nextCursor,
+            categoryFacets: state.categoryFacets
           }
         }
       }));
@@ -502,15 +584,21 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
         source_id: draft.source_id,
         source_kind: draft.source_kind,
         remote_id: draft.remote_id,
-        link_group_id: draft.link_group_id
+        link_group_id: draft.link_group_id,
+        categories: draft.categories
       };
 
+      const previous = get().contacts.find((contact) => contact.id === selectedId);
       const updated = await updateContact({ id: selectedId, patch });
       set((state) => ({
         loading: false,
         contacts: state.contacts.map((contact) => (contact.id === updated.id ? updated : contact)),
         dirty: false
       }));
+      // Category counts span every page, so only the backend can redo them.
+      if (previous && previous.categories.join("\n") !== updated.categories.join("\n")) {
+        await get().loadContacts({ force: true });
+      }
       await get().refreshDedupe();
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
@@ -557,7 +645,7 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
 
     set({ loading: true, error: null });
     try {
-      await deleteContactsBulk({ ids });
+      const result = await deleteContactsBulk({ ids });
       set((state) => {
         const contacts = state.contacts.filter((contact) => !ids.includes(contact.id));
         return {
@@ -565,7 +653,10 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
           contacts,
           selectedContactId: contacts[0]?.id ?? null,
           selectionIds: [],
-          editorMode: contacts.length > 0 ? "edit" : "add"
+          editorMode: contacts.length > 0 ? "edit" : "add",
+          lastDeleteUndo: result.undo_id
+            ? { undoId: result.undo_id, deleted: result.deleted, linkedTasks: result.linked_tasks }
+            : null
         };
       });
       if (get().selectedContactId) {
@@ -580,6 +671,28 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     }
   },
 
+  async undoLastDelete() {
+    const pending = get().lastDeleteUndo;
+    if (!pending) {
+      return;
+    }
+
+    set({ loading: true, error: null, lastDeleteUndo: null });
+    try {
+      await undoContactsDelete({ undo_id: pending.undoId });
+      set({ loading: false });
+      await get().loadContacts({ force: true });
+      await get().refreshDedupe();
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      set({ loading: false, error: message });
+    }
+  },
+
+  dismissDeleteUndo() {
+    set({ lastDeleteUndo: null });
+  },
+
   async refreshDedupe() {
     try {
       const dedupe = await listContactsDedupeCandidates({ query: get().query || null });
@@ -623,13 +736,18 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     }
     set({ loading: true, error: null });
     try {
+      // contacts_unlink also clears the last other member of a group, so an
+      // id may already be free by the time its turn comes.
+      const cleared = new Set<string>();
       for (const id of ids) {
-        await updateContact({
-          id,
-          patch: {
-            link_group_id: null
-          }
-        });
+        const linked = get().contacts.find((contact) => contact.id === id)?.link_group_id;
+        if (!linked || cleared.has(id)) {
+          continue;
+        }
+        const result = await unlinkContact({ id });
+        for (const contact of result.contacts) {
+          cleared.add(contact.id);
+        }
       }
       set({ loading: false });
       await get().loadContacts({ force: true });
@@ -639,6 +757,30 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     }
   },
 
+  async previewMergeSelected() {
+    const ids = [...new Set(get().selectionIds)];
+    if (ids.length < 2) {
+      set({ mergePreview: null });
+      return;
+    }
+
+    set({ error: null });
+    try {
+      const preview = await previewContactsMerge({
+        ids,
+        target_id: get().selectedContactId
+      });
+      set({ mergePreview: preview });
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      set({ mergePreview: null, error: message });
+    }
+  },
+
+  clearMergePreview() {
+    set({ mergePreview: null });
+  },
+
   async mergeSelected() {
     const ids = [...new Set(get().selectionIds)];
     if (ids.length < 2) {
@@ -659,6 +801,7 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
         selectedContactId: result.merged.id,
         selectionIds: [result.merged.id],
         lastMergeUndoId: result.undo_id,
+        mergePreview: null,
         editorMode: "edit"
       }));
       get().loadDraftFromSelected();
@@ -714,7 +857,8 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
         importPreview: preview,
         importSource: source,
         importFileName: fileName,
-        importContent: content
+        importContent: content,
+        importDecisions: {}
       });
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
@@ -722,6 +866,18 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
     }
   },
 
+  setImportDecision(row, decision) {
+    set((state) => {
+      const importDecisions = { ...state.importDecisions };
+      if (decision) {
+        importDecisions[row] = decision;
+      } else {
+        delete importDecisions[row];
+      }
+      return { importDecisions };
+    });
+  },
+
   async commitImport(mode) {
     const source = get().importSource;
     const fileName = get().importFileName;
@@ -732,15 +888,21 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
 
     set({ loading: true, error: null });
     try {
+      const decisions = Object.entries(get().importDecisions).map(([row, decision]) => ({
+        row: Number(row),
+        decision
+      }));
       const result = await commitContactsImport({
         source,
         file_name: fileName,
         content,
-        mode
+        mode,
+        decisions
       });
       set({
         loading: false,
-        importCommitResult: result
+        importCommitResult: result,
+        importDecisions: {}
       });
       await get().loadContacts({ force: true });
       await get().refreshDedupe();
@@ -756,9 +918,65 @@ export const useContactsStore = create<ContactsStore>((set, get) => ({
       if (result.url && typeof window !== "undefined") {
         window.open(result.url, "_blank", "noopener,noreferrer");
       }
+      if (result.logged) {
+        // last_contacted changed, so every cached page is stale.
+        set({ queryCache: {}, queryCacheOrder: [] });
+        await get().loadContacts({ force: true });
+      }
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      set({ error: message });
+    }
+  },
+
+  async resolveLinkedContact(id) {
+    try {
+      const contact = await getContact(id);
+      set((state) => ({ linkedContacts: { ...state.linkedContacts, [id]: contact } }));
+    } catch (error) {
+      logger.warn("contacts.linked.resolve", String(error));
+    }
+  },
+
+  async showContact(contact) {
+    // Search for it when the loaded page does not include it.
+    if (!get().contacts.some((entry) => entry.id === contact.id)) {
+      await get().setQuery(contact.display_name);
+    }
+    get().selectContact(contact.id);
+  },
+
+  async exportInteractions() {
+    try {
+      const result = await listContactInteractions({ contact_id: null });
+      if (typeof window === "undefined") {
+        return;
+      }
+      const blob = new Blob([JSON.stringify(result.interactions, null, 2)], { type: "application/json" });
+      const url = URL.createObjectURL(blob);
+      const link = document.createElement("a");
+      link.href = url;
+      link.download = `rivet-contact-interactions-${new Date().toISOString().slice(0, 10)}.json`;
+      link.click();
+      URL.revokeObjectURL(url);
+      logger.info("contacts.interactions.export", `count=${result.interactions.length}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      set({ error: message });
+    }
+  },
+
+  async pruneInteractions(olderThanDays) {
+    try {
+      const result = await pruneContactInteractions({ older_than_days: olderThanDays, contact_id: null });
+      logger.info("contacts.interactions.prune", `removed=${result.removed} remaining=${result.remaining}`);
+      set({ queryCache: {}, queryCacheOrder: [] });
+      await get().loadContacts({ force: true });
+      return result;
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
       set({ error: message });
+      return null;
     }
   }
 }));
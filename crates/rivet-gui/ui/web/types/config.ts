@@ -6,7 +6,9 @@ export interface TagSchema {
 export interface TagKey {
   id: string;
   label?: string;
-  selection?: "single" | "multi" | string;
+  selection?: "single" | "multi" | "exclusive-group" | string;
+  // Exclusive-group keys sharing this name hold one value between them.
+  group?: string;
   color?: string;
   allow_custom_values?: boolean;
   values?: string[];
@@ -18,6 +20,7 @@ export interface RivetRuntimeConfig {
   timezone?: string;
   app?: {
     mode?: "dev" | "prod" | string;
+    seed_demo?: boolean;
   };
   logging?: {
     directory?: string;
@@ -25,6 +28,11 @@ export interface RivetRuntimeConfig {
   };
   time?: {
     timezone?: string;
+    dateformat?: string;
+    humanize?: "long" | "short" | string;
+    due_default_time?: string;
+    wait_default_time?: string;
+    scheduled_default_time?: string;
   };
   notifications?: {
     due?: {
@@ -32,6 +40,33 @@ export interface RivetRuntimeConfig {
       pre_notify_enabled?: boolean;
       pre_notify_minutes?: number;
       scan_interval_seconds?: number;
+      quiet_hours?: string;
+    };
+  };
+  agenda?: {
+    on_launch?: boolean;
+  };
+  capture?: {
+    tag?: string;
+    tags?: string[];
+    project?: string;
+    priority?: "L" | "M" | "H" | string;
+  };
+  stale?: {
+    days?: number;
+    include_waiting?: boolean;
+    include_blocked?: boolean;
+  };
+  dedupe?: {
+    threshold?: number;
+  };
+  focus?: {
+    pomodoro_minutes?: number;
+  };
+  tasks?: {
+    title?: {
+      max_length?: number;
+      overflow?: "truncate" | "reject" | string;
     };
   };
   ui?: {
@@ -45,6 +80,13 @@ export interface RivetRuntimeConfig {
       dictionary?: boolean;
       map?: boolean;
     };
+    task_list?: {
+      row_actions?: string[];
+      id_display?: "id" | "uuid" | string;
+    };
+    task_editor?: {
+      draft_autosave_seconds?: number;
+    };
   };
   map?: {
     enabled?: boolean;
@@ -80,9 +122,11 @@ export interface RivetRuntimeConfig {
   calendar?: {
     version?: number;
     timezone?: string;
+    locale?: string;
     policies?: {
-      week_start?: "monday" | "sunday" | string;
+      week_start?: "monday" | "sunday" | "locale" | string;
       red_dot_limit?: number;
+      day_marker_limit?: number;
       task_list_limit?: number;
       task_list_window_days?: number;
     };
@@ -100,6 +144,26 @@ export interface RivetRuntimeConfig {
       de_emphasize_past_periods?: boolean;
       filter_tasks_before_now?: boolean;
       hide_past_markers?: boolean;
+      show_week_numbers?: boolean;
+      show_due_markers?: boolean;
+      show_scheduled_markers?: boolean;
+    };
+    markers?: {
+      kanban?: CalendarMarkerConfig;
+      external?: CalendarMarkerConfig;
+      unassigned?: CalendarMarkerConfig;
+      scheduled?: CalendarMarkerConfig;
+      tags?: Record<string, CalendarMarkerConfig>;
     };
   };
+  // Added by config_snapshot from what the backend resolved; not read from rivet.toml.
+  effective?: {
+    timezone?: string;
+  };
+}
+
+export interface CalendarMarkerConfig {
+  shape?: string;
+  color?: string;
+  label?: string;
 }
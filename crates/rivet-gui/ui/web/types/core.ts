@@ -1,4 +1,4 @@
-export type TaskStatus = "Pending" | "Completed" | "Deleted" | "Waiting";
+export type TaskStatus = "Pending" | "Completed" | "Deleted" | "Waiting" | "Archived";
 export type TaskPriority = "Low" | "Medium" | "High";
 
 export interface TaskDto {
@@ -15,6 +15,12 @@ export interface TaskDto {
   scheduled: string | null;
   created: string | null;
   modified: string | null;
+  completed: string | null;
+  started: string | null;
+  focus_seconds: number;
+  parent: string | null;
+  children: string[];
+  contact: string | null;
 }
 
 export interface TasksListArgs {
@@ -22,6 +28,70 @@ export interface TasksListArgs {
   status: TaskStatus | null;
   project: string | null;
   tag: string | null;
+  contact?: string | null;
+  limit?: number | null;
+  completed_from?: string | null;
+  completed_to?: string | null;
+}
+
+// `tasks_list` response: matches by urgency, cut to `limit`; `total` counts
+// them before the cut.
+export interface TasksListPage {
+  tasks: TaskDto[];
+  total: number;
+}
+
+export interface TasksStaleArgs {
+  days: number | null;
+  include_waiting: boolean | null;
+  include_blocked: boolean | null;
+}
+
+export interface TasksDedupeArgs {
+  threshold: number | null;
+}
+
+// The first (oldest) task of a group is the one kept.
+export interface TaskDedupeGroup {
+  score: number;
+  reason: string;
+  tasks: TaskDto[];
+}
+
+export interface TasksDedupeApplyArgs {
+  groups: string[][];
+  merge: boolean;
+}
+
+export interface TasksDedupeApplyResult {
+  removed: number;
+}
+
+export interface TagsRemapArgs {
+  key: string;
+  from: string;
+  to: string;
+}
+
+export interface TagsRemapResult {
+  changed: number;
+}
+
+export interface TagUsage {
+  tag: string;
+  count: number;
+  open: number;
+  last_used: string;
+}
+
+export interface TagsCleanupResult {
+  removed: string[];
+  changed: number;
+}
+
+export interface DemoDataResult {
+  tasks: number;
+  board_id: string;
 }
 
 export interface TaskCreate {
@@ -33,6 +103,7 @@ export interface TaskCreate {
   due: string | null;
   wait: string | null;
   scheduled: string | null;
+  contact?: string | null;
 }
 
 export interface TaskPatch {
@@ -44,6 +115,8 @@ export interface TaskPatch {
   due?: string | null;
   wait?: string | null;
   scheduled?: string | null;
+  // null unlinks the contact.
+  contact?: string | null;
 }
 
 export interface TaskUpdateArgs {
@@ -51,6 +124,19 @@ export interface TaskUpdateArgs {
   patch: TaskPatch;
 }
 
+export interface TaskFieldChange {
+  field: string;
+  before: string | null;
+  after: string | null;
+}
+
+export interface TaskHistoryEntry {
+  at: string;
+  command: string;
+  action: string;
+  changes: TaskFieldChange[];
+}
+
 export interface TaskIdArg {
   uuid: string;
 }
@@ -91,8 +177,11 @@ export interface ContactDto {
   source_kind: string;
   remote_id: string | null;
   link_group_id: string | null;
+  categories: string[];
   created_at: string;
   updated_at: string;
+  // Newest logged call or email; derived by contacts_list, never stored.
+  last_contacted?: string | null;
 }
 
 export interface ContactsListArgs {
@@ -101,12 +190,23 @@ export interface ContactsListArgs {
   cursor: string | null;
   source: string | null;
   updated_after: string | null;
+  category: string | null;
+  sort?: ContactsSortMode | null;
+  contacted_within_days?: number | null;
+}
+
+export type ContactsSortMode = "name" | "recent";
+
+export interface ContactCategoryCount {
+  category: string;
+  count: number;
 }
 
 export interface ContactsListResult {
   contacts: ContactDto[];
   next_cursor: string | null;
   total: number;
+  categories: ContactCategoryCount[];
 }
 
 export interface ContactCreate {
@@ -129,6 +229,7 @@ export interface ContactCreate {
   source_kind: string | null;
   remote_id: string | null;
   link_group_id: string | null;
+  categories: string[];
 }
 
 export interface ContactPatch {
@@ -151,6 +252,7 @@ export interface ContactPatch {
   source_kind?: string | null;
   remote_id?: string | null;
   link_group_id?: string | null;
+  categories?: string[];
 }
 
 export interface ContactUpdateArgs {
@@ -166,6 +268,26 @@ export interface ContactsDeleteBulkArgs {
   ids: string[];
 }
 
+export interface ContactsDeleteBulkResult {
+  deleted: number;
+  undo_id: string | null;
+  // Open tasks still linked to the deleted contacts.
+  linked_tasks: number;
+}
+
+export interface ContactsDeleteUndoArgs {
+  undo_id: string | null;
+}
+
+export interface ContactsDeleteUndoResult {
+  restored: number;
+  undo_id: string;
+}
+
+export interface ContactsUnlinkResult {
+  contacts: ContactDto[];
+}
+
 export interface ContactsDedupePreviewArgs {
   query: string | null;
 }
@@ -203,9 +325,39 @@ export interface ContactOpenActionArgs {
 export interface ContactOpenActionResult {
   launched: boolean;
   url: string;
+  // False when `[contacts].log_interactions` is off.
+  logged: boolean;
+}
+
+export interface ContactInteraction {
+  id: string;
+  contact_id: string;
+  kind: "email" | "phone" | string;
+  value: string;
+  at: string;
+}
+
+export interface ContactInteractionsArgs {
+  contact_id: string | null;
+}
+
+export interface ContactInteractionsResult {
+  interactions: ContactInteraction[];
+}
+
+export interface ContactInteractionsPruneArgs {
+  older_than_days: number | null;
+  contact_id: string | null;
+}
+
+export interface ContactInteractionsPruneResult {
+  removed: number;
+  remaining: number;
 }
 
 export interface ContactImportConflict {
+  // Position in the parsed file; keys the per-conflict decision on commit.
+  row: number;
   imported: ContactDto;
   existing: ContactDto;
   score: number;
@@ -235,6 +387,15 @@ export interface ContactsImportCommitArgs {
   file_name: string | null;
   content: string;
   mode: "safe" | "upsert" | "review";
+  // Per-conflict overrides of `mode`.
+  decisions?: ContactImportDecision[];
+}
+
+export type ContactImportDecisionKind = "merge" | "skip" | "link";
+
+export interface ContactImportDecision {
+  row: number;
+  decision: ContactImportDecisionKind;
 }
 
 export interface ContactsImportCommitResult {
@@ -242,6 +403,7 @@ export interface ContactsImportCommitResult {
   created: number;
   updated: number;
   skipped: number;
+  linked: number;
   failed: number;
   conflicts: number;
   errors: string[];
@@ -256,6 +418,12 @@ export interface ContactsMergeResult {
   merged: ContactDto;
   removed_ids: string[];
   undo_id: string;
+  relinked_tasks: number;
+}
+
+export interface ContactsMergePreviewResult {
+  merged: ContactDto;
+  removed_ids: string[];
 }
 
 export interface ContactsMergeUndoArgs {
@@ -289,6 +457,35 @@ export interface ExternalCalendarSyncResult {
   refresh_minutes: number;
 }
 
+export type ExternalCalendarSyncState = "idle" | "queued" | "running";
+
+// Backend auto-sync bookkeeping for one source; `next_sync` is null when the
+// source is not auto-synced (disabled, imported or refresh off).
+export interface ExternalCalendarSyncStatus {
+  calendar_id: string;
+  last_sync: string | null;
+  last_error: string | null;
+  next_sync: string | null;
+  state: ExternalCalendarSyncState;
+}
+
+// Payload of the `external-calendar-sync-activity` event: source ids waiting
+// for a fetch slot and those fetching, manual and background alike.
+export interface ExternalCalendarSyncActivity {
+  queued: string[];
+  running: string[];
+}
+
+// Payload of the `external-calendar-synced` event sent after each background
+// sync.
+export interface ExternalCalendarSyncEvent {
+  calendar_id: string;
+  name: string;
+  synced_at: string;
+  result: ExternalCalendarSyncResult | null;
+  error: string | null;
+}
+
 export interface ExternalCalendarCacheEntry {
   cache_id: string;
   name: string;
@@ -367,6 +564,19 @@ export interface MapHealthArgs {
   timeout_ms: number | null;
 }
 
+// Mirrors `CommandCategory` in the Tauri permissions module.
+export type CommandCategory =
+  | "tasks_write"
+  | "tasks_delete"
+  | "contacts_write"
+  | "contacts_delete"
+  | "calendar_import"
+  | "config_write";
+
+export interface PermissionsSnapshot {
+  disabled: CommandCategory[];
+}
+
 export interface MapHealthResult {
   base_url: string;
   catalog_url: string;
@@ -1,4 +1,4 @@
-import type { ExternalCalendarSource, TaskDto, TaskStatus } from "./core";
+import type { ExternalCalendarSource, TaskDto, TaskPriority, TaskStatus } from "./core";
 
 export type WorkspaceTab = "tasks" | "kanban" | "calendar" | "dictionary" | "map" | "contacts";
 export type ThemeMode = "day" | "night";
@@ -8,7 +8,9 @@ export type DueFilter = "all" | "has_due" | "no_due";
 
 export type CalendarViewMode = "year" | "quarter" | "month" | "week" | "day";
 export type CalendarWeekStart = "monday" | "sunday";
-export type CalendarMarkerShape = "triangle" | "circle" | "square";
+export type CalendarMarkerShape = "triangle" | "circle" | "square" | "diamond" | "ring";
+export type CalendarMarkerCategory = "kanban" | "external" | "unassigned";
+export type CalendarEntryKind = "due" | "scheduled";
 
 export interface TaskFilters {
   search: string;
@@ -19,10 +21,29 @@ export interface TaskFilters {
   due: DueFilter;
 }
 
+export interface FacetBulkAction {
+  facet: "project" | "tag";
+  value: string;
+  operation: "apply" | "remove" | "rename";
+  renameTo?: string;
+}
+
+export type KanbanLaneSort = "schema" | "alpha";
+export type KanbanCardSort = "manual" | "urgency" | "due";
+export type KanbanBoardListSort = "name" | "recent" | "custom";
+
 export interface KanbanBoardDef {
   id: string;
   name: string;
   color: string;
+  // View preferences saved with the board; boards stored before these
+  // existed fall back to schema lanes and manual cards.
+  lane_sort?: KanbanLaneSort;
+  card_sort?: KanbanCardSort;
+  // Task uuids in the order cards were dropped while sorting manually.
+  card_order?: string[];
+  // Epoch ms the board was last opened, for the "recent" board list.
+  opened_at?: number;
 }
 
 export interface AddTaskDialogContext {
@@ -37,11 +58,37 @@ export interface RecurrenceDraft {
   days: string[];
   months: string[];
   monthDay: string;
+  ends: "never" | "until" | "count";
+  // YYYY-MM-DD, inclusive.
+  until: string;
+  count: string;
+  // Instances still to come, read from the task; null once the count is edited.
+  remaining: number | null;
+}
+
+// Unsaved contents of the add or edit task dialog, kept in local storage so
+// closing the app mid-edit loses at most one autosave interval.
+export interface TaskDraftFields {
+  title: string;
+  description: string;
+  project: string;
+  due: string;
+  customTagInput: string;
+  selectedTags: string[];
+  boardId: string;
+  priority: TaskPriority | null;
+  recurrence: RecurrenceDraft;
+}
+
+export interface TaskDraft extends TaskDraftFields {
+  saved_at: number;
 }
 
 export interface CalendarPolicies {
   week_start: CalendarWeekStart | string;
   red_dot_limit: number;
+  // Markers drawn in one month or week day cell before the rest fold into "+N".
+  day_marker_limit: number;
   task_list_limit: number;
   task_list_window_days: number;
 }
@@ -62,14 +109,34 @@ export interface CalendarToggles {
   de_emphasize_past_periods: boolean;
   filter_tasks_before_now: boolean;
   hide_past_markers: boolean;
+  show_week_numbers: boolean;
+  show_due_markers: boolean;
+  show_scheduled_markers: boolean;
+}
+
+export interface IsoWeek {
+  year: number;
+  week: number;
 }
 
+// Where the display timezone came from, in precedence order; "backend" is the
+// timezone config_snapshot reports the core resolved.
+export type TimezoneSource = "calendar.timezone" | "time.timezone" | "timezone" | "backend" | "default";
+
 export interface EffectiveCalendarConfig {
   timezone: string;
+  timezone_source: TimezoneSource;
+  // The timezone the backend parses due dates in, when known.
+  backend_timezone: string | null;
+  // Wall-clock time ("HH:MM") the backend gives date-only dues, from [time].due_default_time.
+  due_default_time: string;
+  // BCP 47 locale for month and weekday names, from [calendar].locale or the system.
+  locale: string;
   policies: CalendarPolicies;
   visibility: CalendarVisibility;
   day_view: CalendarDayView;
   toggles: CalendarToggles;
+  markers: CalendarMarkerScheme;
 }
 
 export interface CalendarTaskMarker {
@@ -77,6 +144,34 @@ export interface CalendarTaskMarker {
   color: string;
 }
 
+// `color: null` keeps the per-task color (board color for kanban, source
+// color for external calendars).
+export interface CalendarMarkerStyle {
+  shape: CalendarMarkerShape;
+  color: string | null;
+  label: string;
+}
+
+export interface CalendarTagMarkerRule {
+  tag: string;
+  shape: CalendarMarkerShape;
+  color: string;
+  label: string;
+}
+
+export interface CalendarMarkerScheme {
+  categories: Record<CalendarMarkerCategory, CalendarMarkerStyle>;
+  tags: CalendarTagMarkerRule[];
+  // Drawn at a task's scheduled start instead of its category marker.
+  scheduled: CalendarMarkerStyle;
+}
+
+export interface CalendarLegendEntry {
+  key: string;
+  label: string;
+  marker: CalendarTaskMarker;
+}
+
 export interface ZonedDateTimeParts {
   year: number;
   month: number;
@@ -87,13 +182,37 @@ export interface ZonedDateTimeParts {
   second: number;
 }
 
+// `endUtcMs` is null for point-in-time tasks; all-day entries end at the
+// (exclusive) midnight after their last day. `dueUtcMs` is where the entry
+// sits: the due time, or the scheduled start for `kind: "scheduled"`.
 export interface CalendarDueTaskEntry {
   task: TaskDto;
+  kind: CalendarEntryKind;
   dueUtcMs: number;
   dueLocal: ZonedDateTimeParts;
+  endUtcMs: number | null;
+  endLocal: ZonedDateTimeParts | null;
+  allDay: boolean;
   marker: CalendarTaskMarker;
 }
 
+// Minutes are local minutes-of-day clipped to the visible hour range;
+// `top`/`height` are percentages of that range.
+export interface CalendarTimedBlock {
+  entry: CalendarDueTaskEntry;
+  startMinute: number;
+  endMinute: number;
+  top: number;
+  height: number;
+  column: number;
+  columns: number;
+}
+
+export interface CalendarDayLayout {
+  allDay: CalendarDueTaskEntry[];
+  blocks: CalendarTimedBlock[];
+}
+
 export interface CalendarStats {
   total: number;
   pending: number;
@@ -113,3 +232,30 @@ export interface DueNotificationConfig {
   pre_notify_enabled: boolean;
   pre_notify_minutes: number;
 }
+
+export type PendingTaskOp = "done" | "uncomplete" | "delete" | "start" | "stop";
+
+// A reusable task shape captured from Details ("Save as template"). The
+// due date, when kept, is stored relative to the day the template is used.
+export interface TaskTemplate {
+  id: string;
+  name: string;
+  title: string;
+  description: string;
+  project: string | null;
+  tags: string[];
+  priority: TaskPriority | null;
+  due_offset: TaskTemplateDueOffset | null;
+}
+
+export interface TaskTemplateDueOffset {
+  days: number;
+  hour: number;
+  minute: number;
+}
+
+export interface ToastMessage {
+  id: number;
+  message: string;
+  severity: "error" | "warning" | "info" | "success";
+}
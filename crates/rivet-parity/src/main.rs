@@ -1,4 +1,7 @@
-use std::collections::BTreeSet;
+use std::collections::{
+  BTreeMap,
+  BTreeSet
+};
 use std::fs;
 use std::path::{
   Path,
@@ -13,7 +16,10 @@ use anyhow::{
   Context,
   anyhow
 };
-use clap::Parser;
+use clap::{
+  Parser,
+  ValueEnum
+};
 use serde::{
   Deserialize,
   Serialize
@@ -52,16 +58,74 @@ struct Args {
   #[arg(long)]
   skip_reference: bool,
 
+  /// Run only scenarios carrying one
+  /// of these tags (repeatable).
+  #[arg(long)]
+  tag: Vec<String>,
+
+  /// Print the loaded scenarios with
+  /// their tags and exit.
+  #[arg(long)]
+  list: bool,
+
+  /// Run only the reference engine and
+  /// write its canonical output to a
+  /// golden fixture file.
+  #[arg(long, conflicts_with_all = ["check_golden", "skip_reference"])]
+  record: Option<PathBuf>,
+
+  /// Compare candidate output against
+  /// a golden fixture instead of a
+  /// live reference binary.
+  #[arg(long)]
+  check_golden: Option<PathBuf>,
+
+  /// Fail when the mean scenario
+  /// parity falls below this score
+  /// (0.0-1.0).
+  #[arg(long, value_parser = parse_score)]
+  min_score: Option<f64>,
+
+  /// Fail when any pending, completed
+  /// or deleted bucket score falls
+  /// below this (0.0-1.0).
+  #[arg(long, value_parser = parse_score)]
+  bucket_min: Option<f64>,
+
+  #[arg(
+    long,
+    value_enum,
+    default_value_t = OutputFormat::Text
+  )]
+  format: OutputFormat,
+
   #[arg(long, default_value = "warn")]
   log_level: String
 }
 
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  ValueEnum,
+)]
+enum OutputFormat {
+  Text,
+  Json
+}
+
 #[derive(Debug, Deserialize)]
 struct Scenario {
   name:  String,
   #[serde(default)]
+  tags:  Vec<String>,
+  #[serde(default)]
   hooks: Vec<HookSpec>,
-  steps: Vec<Step>
+  steps: Vec<Step>,
+  #[serde(skip)]
+  path:  PathBuf
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,13 +141,93 @@ struct HookSpec {
   body: String
 }
 
-#[derive(Debug)]
+#[derive(
+  Debug, Serialize, Deserialize,
+)]
 struct EngineResult {
   pending:   Vec<CanonicalTask>,
   completed: Vec<CanonicalTask>,
   deleted:   Vec<CanonicalTask>
 }
 
+#[derive(
+  Debug, Default, Serialize, Deserialize,
+)]
+struct GoldenFixture {
+  scenarios: Vec<GoldenScenario>
+}
+
+#[derive(
+  Debug, Serialize, Deserialize,
+)]
+struct GoldenScenario {
+  name:   String,
+  #[serde(flatten)]
+  result: EngineResult
+}
+
+#[derive(Debug, Serialize)]
+struct BucketScores {
+  pending:   f64,
+  completed: f64,
+  deleted:   f64,
+  scenario:  f64
+}
+
+impl BucketScores {
+  fn buckets(
+    &self
+  ) -> [(&'static str, f64); 3] {
+    [
+      ("pending", self.pending),
+      ("completed", self.completed),
+      ("deleted", self.deleted)
+    ]
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct BucketCounts {
+  pending:   usize,
+  completed: usize,
+  deleted:   usize
+}
+
+#[derive(Debug, Default, Serialize)]
+struct BucketDiff {
+  only_candidate: Vec<CanonicalTask>,
+  only_reference: Vec<CanonicalTask>
+}
+
+impl BucketDiff {
+  fn is_empty(&self) -> bool {
+    self.only_candidate.is_empty()
+      && self.only_reference.is_empty()
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct ScenarioReport {
+  name:             String,
+  /// None when the reference was
+  /// skipped.
+  scores: Option<BucketScores>,
+  /// Buckets with differences only.
+  diffs:
+    BTreeMap<&'static str, BucketDiff>,
+  candidate_counts: BucketCounts
+}
+
+#[derive(Debug, Serialize)]
+struct ParityReport {
+  scenarios:  Vec<ScenarioReport>,
+  overall:    Option<f64>,
+  min_score:  Option<f64>,
+  bucket_min: Option<f64>,
+  passed:     bool,
+  failures:   Vec<String>
+}
+
 #[derive(Debug)]
 struct StepResult {
   status: std::process::ExitStatus,
@@ -125,20 +269,58 @@ fn main() -> anyhow::Result<()> {
       "no scenarios loaded"
     ));
   }
+  let scenarios = select_scenarios(
+    scenarios, &args.tag
+  );
+  if scenarios.is_empty() {
+    return Err(anyhow!(
+      "no scenarios tagged {}",
+      args.tag.join(", ")
+    ));
+  }
+
+  if args.list {
+    for scenario in &scenarios {
+      println!(
+        "{}\t[{}]\t{}",
+        scenario.name,
+        scenario.tags.join(", "),
+        scenario.path.display()
+      );
+    }
+    return Ok(());
+  }
 
-  let reference_available = !args
-    .skip_reference
+  if let Some(path) = &args.record {
+    return record_golden(
+      &args.reference_bin,
+      &scenarios,
+      path
+    );
+  }
+
+  let mut golden = args
+    .check_golden
+    .as_deref()
+    .map(load_golden)
+    .transpose()?;
+
+  let reference_available = golden
+    .is_none()
+    && !args.skip_reference
     && is_reference_available(
       &args.reference_bin
     );
-  if !reference_available {
+  if golden.is_none()
+    && !reference_available
+  {
     warn!(
         reference = %args.reference_bin.display(),
         "reference binary unavailable or skipped; running candidate only"
     );
   }
 
-  let mut overall_scores = Vec::new();
+  let mut reports = Vec::new();
 
   for scenario in scenarios {
     info!(scenario = %scenario.name, "running scenario");
@@ -155,115 +337,448 @@ fn main() -> anyhow::Result<()> {
       )
     })?;
 
-    if reference_available {
-      let reference = run_engine(
-        &args.reference_bin,
-        &scenario
-      )
-      .with_context(|| {
-        format!(
-          "reference failed on \
-           scenario {}",
-          scenario.name
+    let reference =
+      if let Some(fixture) =
+        golden.as_mut()
+      {
+        Some(take_golden_scenario(
+          fixture,
+          &scenario.name
+        )?)
+      } else if reference_available {
+        Some(
+          run_engine(
+            &args.reference_bin,
+            &scenario
+          )
+          .with_context(
+            || {
+              format!(
+                "reference failed on \
+                 scenario {}",
+                scenario.name
+              )
+            }
+          )?
         )
-      })?;
-
-      let pending_score = score_bucket(
-        &candidate.pending,
-        &reference.pending
-      );
-      let completed_score =
-        score_bucket(
-          &candidate.completed,
-          &reference.completed
-        );
-      let deleted_score = score_bucket(
-        &candidate.deleted,
-        &reference.deleted
-      );
-      let scenario_score =
-        (pending_score
-          + completed_score
-          + deleted_score)
-          / 3.0;
+      } else {
+        None
+      };
+
+    reports.push(scenario_report(
+      &scenario.name,
+      &candidate,
+      reference.as_ref()
+    ));
+  }
 
-      overall_scores
-        .push(scenario_score);
+  let report = build_report(
+    reports,
+    args.min_score,
+    args.bucket_min,
+    golden.is_some()
+  );
 
+  match args.format {
+    | OutputFormat::Text => {
+      print_text_report(&report)
+    }
+    | OutputFormat::Json => {
       println!(
-        "Scenario: {}",
-        scenario.name
-      );
-      println!(
-        "  pending parity  : {:.3}",
-        pending_score
-      );
-      println!(
-        "  completed parity: {:.3}",
-        completed_score
-      );
-      println!(
-        "  deleted parity  : {:.3}",
-        deleted_score
-      );
-      println!(
-        "  scenario parity : {:.3}",
-        scenario_score
+        "{}",
+        serde_json::to_string_pretty(
+          &report
+        )?
       );
+    }
+  }
+
+  if !report.passed {
+    return Err(anyhow!(
+      "parity check failed: {}",
+      report.failures.join("; ")
+    ));
+  }
+
+  Ok(())
+}
+
+fn parse_score(
+  raw: &str
+) -> Result<f64, String> {
+  let value: f64 =
+    raw.parse().map_err(|_| {
+      format!("invalid score '{raw}'")
+    })?;
+  if !(0.0..=1.0).contains(&value) {
+    return Err(format!(
+      "score must be between 0.0 and \
+       1.0, got {raw}"
+    ));
+  }
+  Ok(value)
+}
+
+fn scenario_report(
+  name: &str,
+  candidate: &EngineResult,
+  reference: Option<&EngineResult>
+) -> ScenarioReport {
+  let candidate_counts = BucketCounts {
+    pending:   candidate.pending.len(),
+    completed: candidate
+      .completed
+      .len(),
+    deleted:   candidate.deleted.len()
+  };
+  let Some(reference) = reference
+  else {
+    return ScenarioReport {
+      name: name.to_string(),
+      scores: None,
+      diffs: BTreeMap::new(),
+      candidate_counts
+    };
+  };
+
+  let pending = score_bucket(
+    &candidate.pending,
+    &reference.pending
+  );
+  let completed = score_bucket(
+    &candidate.completed,
+    &reference.completed
+  );
+  let deleted = score_bucket(
+    &candidate.deleted,
+    &reference.deleted
+  );
+  let scores = BucketScores {
+    pending,
+    completed,
+    deleted,
+    scenario: (pending
+      + completed
+      + deleted)
+      / 3.0
+  };
 
-      print_diff(
-        "pending",
+  let diffs = [
+    (
+      "pending",
+      bucket_diff(
         &candidate.pending,
         &reference.pending
-      );
-      print_diff(
-        "completed",
+      )
+    ),
+    (
+      "completed",
+      bucket_diff(
         &candidate.completed,
         &reference.completed
-      );
-      print_diff(
-        "deleted",
+      )
+    ),
+    (
+      "deleted",
+      bucket_diff(
         &candidate.deleted,
         &reference.deleted
-      );
-    } else {
-      println!(
-        "Scenario: {}",
-        scenario.name
-      );
+      )
+    )
+  ]
+  .into_iter()
+  .filter(|(_, diff)| !diff.is_empty())
+  .collect();
+
+  ScenarioReport {
+    name: name.to_string(),
+    scores: Some(scores),
+    diffs,
+    candidate_counts
+  }
+}
+
+fn build_report(
+  scenarios: Vec<ScenarioReport>,
+  min_score: Option<f64>,
+  bucket_min: Option<f64>,
+  golden: bool
+) -> ParityReport {
+  let scored: Vec<f64> = scenarios
+    .iter()
+    .filter_map(|entry| {
+      entry
+        .scores
+        .as_ref()
+        .map(|scores| scores.scenario)
+    })
+    .collect();
+  let overall = (!scored.is_empty())
+    .then(|| {
+      scored.iter().sum::<f64>()
+        / scored.len() as f64
+    });
+
+  let mut failures = Vec::new();
+
+  if golden {
+    let mismatches: Vec<&str> =
+      scenarios
+        .iter()
+        .filter(|entry| {
+          entry
+            .scores
+            .as_ref()
+            .is_some_and(|scores| {
+              scores.scenario < 1.0
+            })
+        })
+        .map(|entry| {
+          entry.name.as_str()
+        })
+        .collect();
+    if !mismatches.is_empty() {
+      failures.push(format!(
+        "candidate diverged from \
+         golden fixture in: {}",
+        mismatches.join(", ")
+      ));
+    }
+  }
+
+  if (min_score.is_some()
+    || bucket_min.is_some())
+    && overall.is_none()
+  {
+    failures.push(
+      "no reference scores to check \
+       thresholds against"
+        .to_string()
+    );
+  }
+
+  if let (Some(min), Some(score)) =
+    (min_score, overall)
+    && score < min
+  {
+    failures.push(format!(
+      "overall parity {score:.3} is \
+       below --min-score {min:.3}"
+    ));
+  }
+
+  if let Some(min) = bucket_min {
+    for entry in &scenarios {
+      let Some(scores) = &entry.scores
+      else {
+        continue;
+      };
+      for (bucket, score) in
+        scores.buckets()
+      {
+        if score < min {
+          failures.push(format!(
+            "{} {bucket} parity \
+             {score:.3} is below \
+             --bucket-min {min:.3}",
+            entry.name
+          ));
+        }
+      }
+    }
+  }
+
+  ParityReport {
+    scenarios,
+    overall,
+    min_score,
+    bucket_min,
+    passed: failures.is_empty(),
+    failures
+  }
+}
+
+fn print_text_report(
+  report: &ParityReport
+) {
+  for entry in &report.scenarios {
+    println!(
+      "Scenario: {}",
+      entry.name
+    );
+    let Some(scores) = &entry.scores
+    else {
+      let counts =
+        &entry.candidate_counts;
       println!(
         "  reference skipped; \
          candidate produced:"
       );
       println!(
         "    pending:   {}",
-        candidate.pending.len()
+        counts.pending
       );
       println!(
         "    completed: {}",
-        candidate.completed.len()
+        counts.completed
       );
       println!(
         "    deleted:   {}",
-        candidate.deleted.len()
+        counts.deleted
       );
+      continue;
+    };
+
+    println!(
+      "  pending parity  : {:.3}",
+      scores.pending
+    );
+    println!(
+      "  completed parity: {:.3}",
+      scores.completed
+    );
+    println!(
+      "  deleted parity  : {:.3}",
+      scores.deleted
+    );
+    println!(
+      "  scenario parity : {:.3}",
+      scores.scenario
+    );
+    for (label, diff) in &entry.diffs {
+      print_diff(label, diff);
     }
   }
 
-  if !overall_scores.is_empty() {
-    let mean = overall_scores
-      .iter()
-      .sum::<f64>()
-      / overall_scores.len() as f64;
+  if let Some(overall) = report.overall
+  {
     println!(
       "\nOverall parity score: {:.3}",
-      mean
+      overall
+    );
+  }
+
+  if report.passed {
+    println!("\nParity check: PASS");
+  } else {
+    println!("\nParity check: FAIL");
+    for failure in &report.failures {
+      println!("  - {failure}");
+    }
+  }
+}
+
+fn record_golden(
+  reference_bin: &Path,
+  scenarios: &[Scenario],
+  path: &Path
+) -> anyhow::Result<()> {
+  if !is_reference_available(
+    reference_bin
+  ) {
+    return Err(anyhow!(
+      "reference binary {} is \
+       unavailable; cannot record \
+       golden fixture",
+      reference_bin.display()
+    ));
+  }
+
+  let mut fixture =
+    GoldenFixture::default();
+  for scenario in scenarios {
+    info!(scenario = %scenario.name, "recording scenario");
+    let result = run_engine(
+      reference_bin,
+      scenario
+    )
+    .with_context(|| {
+      format!(
+        "reference failed on scenario \
+         {}",
+        scenario.name
+      )
+    })?;
+    println!(
+      "Recorded {}: pending {}, \
+       completed {}, deleted {}",
+      scenario.name,
+      result.pending.len(),
+      result.completed.len(),
+      result.deleted.len()
+    );
+    fixture.scenarios.push(
+      GoldenScenario {
+        name: scenario.name.clone(),
+        result
+      }
     );
   }
 
+  let text =
+    serde_json::to_string_pretty(
+      &fixture
+    )?;
+  fs::write(path, format!("{text}\n"))
+    .with_context(|| {
+      format!(
+        "failed to write golden \
+         fixture {}",
+        path.display()
+      )
+    })?;
+  println!(
+    "Wrote golden fixture {}",
+    path.display()
+  );
   Ok(())
 }
 
+fn load_golden(
+  path: &Path
+) -> anyhow::Result<GoldenFixture> {
+  let text = fs::read_to_string(path)
+    .with_context(|| {
+    format!(
+      "failed to read golden fixture \
+       {}",
+      path.display()
+    )
+  })?;
+  serde_json::from_str(&text)
+    .with_context(|| {
+      format!(
+        "failed to parse golden \
+         fixture {}",
+        path.display()
+      )
+    })
+}
+
+fn take_golden_scenario(
+  fixture: &mut GoldenFixture,
+  name: &str
+) -> anyhow::Result<EngineResult> {
+  let idx = fixture
+    .scenarios
+    .iter()
+    .position(|entry| {
+      entry.name == name
+    })
+    .ok_or_else(|| {
+      anyhow!(
+        "golden fixture has no \
+         scenario named {name}"
+      )
+    })?;
+  Ok(
+    fixture
+      .scenarios
+      .remove(idx)
+      .result
+  )
+}
+
 fn init_tracing(
   level: &str
 ) -> anyhow::Result<()> {
@@ -295,7 +810,7 @@ fn load_scenarios(
           path.display()
         )
       })?;
-    let scenario: Scenario =
+    let mut scenario: Scenario =
       serde_json::from_str(&text)
         .with_context(|| {
           format!(
@@ -304,12 +819,51 @@ fn load_scenarios(
             path.display()
           )
         })?;
+    scenario.tags =
+      normalize_tags(&scenario.tags);
+    scenario.path = path.clone();
     out.push(scenario);
   }
 
   Ok(out)
 }
 
+fn normalize_tags(
+  raw: &[String]
+) -> Vec<String> {
+  raw
+    .iter()
+    .map(|tag| {
+      tag.trim().to_ascii_lowercase()
+    })
+    .filter(|tag| !tag.is_empty())
+    .collect::<BTreeSet<_>>()
+    .into_iter()
+    .collect()
+}
+
+/// Keeps scenarios carrying any of
+/// `tags`; all of them when `tags` is
+/// empty.
+fn select_scenarios(
+  scenarios: Vec<Scenario>,
+  tags: &[String]
+) -> Vec<Scenario> {
+  let wanted = normalize_tags(tags);
+  if wanted.is_empty() {
+    return scenarios;
+  }
+  scenarios
+    .into_iter()
+    .filter(|scenario| {
+      scenario
+        .tags
+        .iter()
+        .any(|tag| wanted.contains(tag))
+    })
+    .collect()
+}
+
 fn is_reference_available(
   reference_bin: &Path
 ) -> bool {
@@ -381,11 +935,22 @@ fn run_engine(
   )?);
   pending.sort();
   pending.dedup();
-  let completed = collect_export(
+  // Rivet exports archived tasks as
+  // completed. Taskwarrior has no such
+  // status and may reject the filter;
+  // either way it has none to add.
+  let mut completed = collect_export(
     binary,
     &taskrc,
     &["status:completed"]
   )?;
+  completed.extend(
+    collect_export(binary, &taskrc, &[
+      "status:archived"
+    ])
+    .unwrap_or_default()
+  );
+  completed.sort();
   let deleted = collect_export(
     binary,
     &taskrc,
@@ -747,40 +1312,48 @@ fn score_bucket(
   }
 }
 
-fn print_diff(
-  label: &str,
+fn bucket_diff(
   candidate: &[CanonicalTask],
   reference: &[CanonicalTask]
-) {
+) -> BucketDiff {
   let c: BTreeSet<_> =
     candidate.iter().cloned().collect();
   let r: BTreeSet<_> =
     reference.iter().cloned().collect();
 
-  let only_candidate: Vec<_> =
-    c.difference(&r).cloned().collect();
-  let only_reference: Vec<_> =
-    r.difference(&c).cloned().collect();
+  BucketDiff {
+    only_candidate: c
+      .difference(&r)
+      .cloned()
+      .collect(),
+    only_reference: r
+      .difference(&c)
+      .cloned()
+      .collect()
+  }
+}
 
-  if only_candidate.is_empty()
-    && only_reference.is_empty()
-  {
+fn print_diff(
+  label: &str,
+  diff: &BucketDiff
+) {
+  if diff.is_empty() {
     return;
   }
 
   println!("  {label} diff:");
-  if !only_candidate.is_empty() {
+  if !diff.only_candidate.is_empty() {
     println!("    only candidate:");
-    for task in only_candidate {
+    for task in &diff.only_candidate {
       println!(
         "      {} [{:?}]",
         task.description, task.tags
       );
     }
   }
-  if !only_reference.is_empty() {
+  if !diff.only_reference.is_empty() {
     println!("    only reference:");
-    for task in only_reference {
+    for task in &diff.only_reference {
       println!(
         "      {} [{:?}]",
         task.description, task.tags
@@ -793,14 +1366,51 @@ use std::io::IsTerminal;
 
 #[cfg(test)]
 mod tests {
+  use std::collections::BTreeMap;
+
   use serde_json::json;
 
   use super::{
+    BucketCounts,
+    BucketScores,
     CanonicalTask,
+    GoldenFixture,
+    Scenario,
+    ScenarioReport,
+    build_report,
     canonicalize,
-    score_bucket
+    normalize_tags,
+    parse_score,
+    score_bucket,
+    select_scenarios,
+    take_golden_scenario
   };
 
+  fn scored(
+    name: &str,
+    pending: f64
+  ) -> ScenarioReport {
+    ScenarioReport {
+      name:             name
+        .to_string(),
+      scores:           Some(
+        BucketScores {
+          pending,
+          completed: 1.0,
+          deleted: 1.0,
+          scenario: (pending + 2.0)
+            / 3.0
+        }
+      ),
+      diffs:            BTreeMap::new(),
+      candidate_counts: BucketCounts {
+        pending:   1,
+        completed: 0,
+        deleted:   0
+      }
+    }
+  }
+
   #[test]
   fn canonicalize_extracts_expected_fields()
    {
@@ -872,4 +1482,167 @@ mod tests {
         < f64::EPSILON
     );
   }
+
+  #[test]
+  fn golden_fixture_round_trips_by_scenario_name()
+   {
+    let raw = json!({
+        "scenarios": [{
+            "name": "basic",
+            "pending": [{
+                "description": "Demo",
+                "status": "pending",
+                "project": null,
+                "tags": ["a"],
+                "priority": null,
+                "due": null,
+                "scheduled": null,
+                "wait": null,
+                "started": false,
+                "annotations": []
+            }],
+            "completed": [],
+            "deleted": []
+        }]
+    });
+
+    let mut fixture: GoldenFixture =
+      serde_json::from_value(raw)
+        .expect("fixture parses");
+    assert!(
+      take_golden_scenario(
+        &mut fixture,
+        "missing"
+      )
+      .is_err()
+    );
+
+    let result = take_golden_scenario(
+      &mut fixture,
+      "basic"
+    )
+    .expect("scenario present");
+    assert_eq!(result.pending.len(), 1);
+    assert_eq!(
+      result.pending[0].tags,
+      vec!["a".to_string()]
+    );
+    assert!(
+      result.completed.is_empty()
+    );
+  }
+
+  #[test]
+  fn thresholds_fail_on_overall_and_bucket_scores()
+   {
+    let report = build_report(
+      vec![
+        scored("a", 1.0),
+        scored("b", 0.4),
+      ],
+      Some(0.9),
+      Some(0.5),
+      false
+    );
+    assert!(!report.passed);
+    assert_eq!(
+      report.failures.len(),
+      2
+    );
+    assert!(
+      report.failures[0]
+        .contains("--min-score")
+    );
+    assert!(
+      report.failures[1]
+        .starts_with("b pending")
+    );
+
+    let report = build_report(
+      vec![scored("a", 1.0)],
+      Some(1.0),
+      Some(1.0),
+      false
+    );
+    assert!(report.passed);
+
+    let mut skipped = scored("c", 1.0);
+    skipped.scores = None;
+    let report = build_report(
+      vec![skipped],
+      Some(0.5),
+      None,
+      false
+    );
+    assert!(!report.passed);
+    assert!(report.overall.is_none());
+
+    assert!(
+      parse_score("0.95").is_ok()
+    );
+    assert!(
+      parse_score("1.5").is_err()
+    );
+  }
+
+  #[test]
+  fn tag_filter_keeps_matching_scenarios()
+   {
+    let parse = |raw| {
+      let mut scenario: Scenario =
+        serde_json::from_value(raw)
+          .expect("scenario parses");
+      scenario.tags =
+        normalize_tags(&scenario.tags);
+      scenario
+    };
+    let scenarios = || {
+      vec![
+        parse(json!({
+            "name": "recur",
+            "tags": [" Recurrence ", "recurrence", "dates"],
+            "steps": []
+        })),
+        parse(json!({
+            "name": "plain",
+            "steps": []
+        })),
+      ]
+    };
+
+    assert_eq!(
+      scenarios()[0].tags,
+      vec![
+        "dates".to_string(),
+        "recurrence".to_string()
+      ]
+    );
+    let names =
+      |kept: Vec<Scenario>| {
+        kept
+          .into_iter()
+          .map(|scenario| scenario.name)
+          .collect::<Vec<_>>()
+      };
+    assert_eq!(
+      names(select_scenarios(
+        scenarios(),
+        &[]
+      )),
+      vec!["recur", "plain"]
+    );
+    assert_eq!(
+      names(select_scenarios(
+        scenarios(),
+        &["RECURRENCE".to_string()]
+      )),
+      vec!["recur"]
+    );
+    assert!(
+      select_scenarios(scenarios(), &[
+        "missing".to_string()
+      ])
+      .is_empty()
+    );
+  }
 }